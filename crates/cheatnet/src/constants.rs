@@ -12,7 +12,7 @@ use starknet::core::utils::get_selector_from_name;
 use starknet_api::deprecated_contract_class::EntryPointType;
 
 use conversions::string::TryFromHexStr;
-use runtime::starknet::context::ERC20_CONTRACT_ADDRESS;
+use runtime::starknet::context::{ETH_CONTRACT_ADDRESS, STRK_CONTRACT_ADDRESS};
 use runtime::starknet::state::DictStateReader;
 use starknet_api::{core::ContractAddress, transaction::Calldata};
 
@@ -57,8 +57,24 @@ fn contract_class_no_entrypoints() -> ContractClass {
 // Account does not include validations
 #[must_use]
 pub fn build_testing_state() -> DictStateReader {
+    let mut state = build_forked_testing_state();
+
     let test_erc20_class_hash =
         TryFromHexStr::try_from_hex_str(TEST_ERC20_CONTRACT_CLASS_HASH).unwrap();
+    for fee_token_address in [STRK_CONTRACT_ADDRESS, ETH_CONTRACT_ADDRESS] {
+        let fee_token_address = TryFromHexStr::try_from_hex_str(fee_token_address).unwrap();
+        state
+            .address_to_class_hash
+            .insert(fee_token_address, test_erc20_class_hash);
+    }
+
+    state
+}
+
+// Creates a state for tests run on a fork,
+// fee tokens are not predeployed so the token contracts of the forked network are used
+#[must_use]
+pub fn build_forked_testing_state() -> DictStateReader {
     let test_contract_class_hash =
         TryFromHexStr::try_from_hex_str(TEST_CONTRACT_CLASS_HASH).unwrap();
 
@@ -68,12 +84,8 @@ pub fn build_testing_state() -> DictStateReader {
         (test_contract_class_hash, contract_class_no_entrypoints()),
     ]);
 
-    let test_erc20_address = TryFromHexStr::try_from_hex_str(ERC20_CONTRACT_ADDRESS).unwrap();
     let test_address = TryFromHexStr::try_from_hex_str(TEST_ADDRESS).unwrap();
-    let address_to_class_hash = HashMap::from([
-        (test_erc20_address, test_erc20_class_hash),
-        (test_address, test_contract_class_hash),
-    ]);
+    let address_to_class_hash = HashMap::from([(test_address, test_contract_class_hash)]);
 
     DictStateReader {
         address_to_class_hash,
@@ -0,0 +1,94 @@
+use cairo_vm::hint_processor::hint_processor_definition::{
+    HintProcessor, HintProcessorLogic, HintReference,
+};
+use cairo_vm::serde::deserialize_program::ApTracking;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
+use cairo_vm::vm::runners::cairo_runner::{ResourceTracker, RunResources};
+use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
+use std::any::Any;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Reading the clock on every step would slow down the execution noticeably
+const STEPS_BETWEEN_DEADLINE_CHECKS: usize = 10_000;
+
+/// Hint processor that stops the execution once the deadline passes,
+/// by reporting its resources as consumed to the VM.
+/// Wraps both the test and the calls to contracts, so a test looping inside a contract stops as well
+pub struct HintProcessorWithDeadline<'a> {
+    hint_processor: &'a mut dyn HintProcessor,
+    deadline: Option<Instant>,
+    steps_until_check: usize,
+    deadline_exceeded: bool,
+}
+
+impl<'a> HintProcessorWithDeadline<'a> {
+    #[must_use]
+    pub fn new(hint_processor: &'a mut dyn HintProcessor, deadline: Option<Instant>) -> Self {
+        Self {
+            hint_processor,
+            deadline,
+            steps_until_check: STEPS_BETWEEN_DEADLINE_CHECKS,
+            deadline_exceeded: false,
+        }
+    }
+
+    #[must_use]
+    pub fn deadline_exceeded(&self) -> bool {
+        self.deadline_exceeded
+    }
+}
+
+impl HintProcessorLogic for HintProcessorWithDeadline<'_> {
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &Box<dyn Any>,
+        constants: &HashMap<String, Felt252>,
+    ) -> Result<(), HintError> {
+        self.hint_processor
+            .execute_hint(vm, exec_scopes, hint_data, constants)
+    }
+
+    fn compile_hint(
+        &self,
+        hint_code: &str,
+        ap_tracking_data: &ApTracking,
+        reference_ids: &HashMap<String, usize>,
+        references: &[HintReference],
+    ) -> Result<Box<dyn Any>, VirtualMachineError> {
+        self.hint_processor
+            .compile_hint(hint_code, ap_tracking_data, reference_ids, references)
+    }
+}
+
+impl ResourceTracker for HintProcessorWithDeadline<'_> {
+    fn consumed(&self) -> bool {
+        self.deadline_exceeded || self.hint_processor.consumed()
+    }
+
+    fn consume_step(&mut self) {
+        self.hint_processor.consume_step();
+
+        let Some(deadline) = self.deadline else {
+            return;
+        };
+        self.steps_until_check -= 1;
+        if self.steps_until_check == 0 {
+            self.steps_until_check = STEPS_BETWEEN_DEADLINE_CHECKS;
+            self.deadline_exceeded = Instant::now() >= deadline;
+        }
+    }
+
+    fn get_n_steps(&self) -> Option<usize> {
+        self.hint_processor.get_n_steps()
+    }
+
+    fn run_resources(&self) -> &RunResources {
+        self.hint_processor.run_resources()
+    }
+}
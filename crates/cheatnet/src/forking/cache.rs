@@ -26,6 +26,8 @@ struct ForkCacheContent {
     class_hash_at: HashMap<ContractAddress, ClassHash>,
     compiled_contract_class: HashMap<ClassHash, ContractClass>,
     block_info: Option<SerializableBlockInfo>,
+    #[serde(default)]
+    block_hash: Option<Felt252>,
 }
 
 impl Default for ForkCacheContent {
@@ -37,6 +39,7 @@ impl Default for ForkCacheContent {
             class_hash_at: Default::default(),
             compiled_contract_class: Default::default(),
             block_info: Default::default(),
+            block_hash: Default::default(),
         }
     }
 }
@@ -72,6 +75,9 @@ impl ForkCacheContent {
         if other.block_info.is_some() {
             self.block_info.clone_from(&other.block_info);
         }
+        if other.block_hash.is_some() {
+            self.block_hash = other.block_hash;
+        }
     }
 }
 
@@ -235,6 +241,14 @@ impl ForkCache {
     pub(crate) fn cache_get_block_info(&mut self, block_info: BlockInfo) {
         self.fork_cache_content.block_info = Some(block_info.into());
     }
+
+    pub(crate) fn get_block_hash(&self) -> Option<Felt252> {
+        self.fork_cache_content.block_hash
+    }
+
+    pub(crate) fn cache_get_block_hash(&mut self, block_hash: Felt252) {
+        self.fork_cache_content.block_hash = Some(block_hash);
+    }
 }
 
 fn cache_file_path_from_fork_config(
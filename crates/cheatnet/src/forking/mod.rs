@@ -1,2 +1,4 @@
 pub mod cache;
+pub mod multi_fork;
+pub mod snapshot;
 pub mod state;
@@ -0,0 +1,319 @@
+use crate::constants::build_forked_testing_state;
+use crate::forking::snapshot::ForkDataMode;
+use crate::forking::state::{ForkBlockInfo, ForkStateReader};
+use crate::state::{BlockInfoReader, ExtendedStateReader, ForkScopedCheats};
+use anyhow::{ensure, Context, Result};
+use blockifier::blockifier::block::BlockInfo;
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::cached_state::CachedState;
+use blockifier::state::state_api::{State, StateReader, StateResult};
+use cairo_vm::Felt252;
+use camino::Utf8PathBuf;
+use conversions::serde::deserialize::CairoDeserialize;
+use conversions::serde::serialize::CairoSerialize;
+use starknet::core::types::BlockId;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ChainId, ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use url::Url;
+
+/// Identifies one of the forks created within a single test.
+/// Handle `0` always refers to the state the test was started with.
+#[derive(CairoSerialize, CairoDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ForkHandle {
+    pub id: usize,
+}
+
+struct ForkOverlay {
+    state: CachedState<ExtendedStateReader>,
+    chain_id: Option<ChainId>,
+    fork_block_info: Option<ForkBlockInfo>,
+    // Cheats of the fork while it is not selected, the selected fork keeps them in `CheatnetState`
+    scoped_cheats: ForkScopedCheats,
+}
+
+/// State of a fork as of another block than the one it is pinned to, read by `call_contract_at_block`
+pub struct ForkStateAtBlock {
+    pub state: CachedState<ExtendedStateReader>,
+    pub block_info: BlockInfo,
+}
+
+/// Owns a separate state overlay for every fork created in a test.
+/// Overlays are never flushed, so selecting a fork again restores its previous state.
+pub struct ForkRegistry {
+    overlays: Vec<ForkOverlay>,
+    active: usize,
+    // States read by `call_contract_at_block`, by the index of the overlay and the block number
+    states_at_block: HashMap<(usize, BlockNumber), Rc<RefCell<ForkStateAtBlock>>>,
+    cache_dir: Utf8PathBuf,
+    fork_data: Option<ForkDataMode>,
+}
+
+impl ForkRegistry {
+    pub fn new(
+        mut state_reader: ExtendedStateReader,
+        cache_dir: Utf8PathBuf,
+        fork_data: Option<ForkDataMode>,
+    ) -> Result<(Self, BlockInfo)> {
+        let block_info = state_reader.get_block_info()?;
+        let chain_id = state_reader.get_chain_id()?;
+        let fork_block_info = state_reader.get_fork_block_info()?;
+
+        let registry = Self {
+            overlays: vec![ForkOverlay {
+                state: CachedState::new(state_reader),
+                chain_id,
+                fork_block_info,
+                scoped_cheats: ForkScopedCheats::default(),
+            }],
+            active: 0,
+            states_at_block: HashMap::new(),
+            cache_dir,
+            fork_data,
+        };
+
+        Ok((registry, block_info))
+    }
+
+    pub fn create_fork(&mut self, url: Url, block_number: BlockNumber) -> Result<ForkHandle> {
+        let mut fork_state_reader =
+            ForkStateReader::new(url.clone(), block_number, &self.cache_dir)?;
+        if let Some(mode) = &self.fork_data {
+            fork_state_reader = fork_state_reader.with_fork_data(mode, url.as_str())?;
+        }
+
+        let mut state_reader = ExtendedStateReader {
+            dict_state_reader: build_forked_testing_state(),
+            fork_state_reader: Some(fork_state_reader),
+        };
+        let block_info = state_reader.get_block_info()?;
+        let chain_id = state_reader.get_chain_id()?;
+        let fork_block_info = state_reader.get_fork_block_info()?;
+
+        self.overlays.push(ForkOverlay {
+            state: CachedState::new(state_reader),
+            chain_id,
+            fork_block_info,
+            scoped_cheats: ForkScopedCheats {
+                block_info,
+                ..Default::default()
+            },
+        });
+
+        Ok(ForkHandle {
+            id: self.overlays.len() - 1,
+        })
+    }
+
+    /// Makes `handle` the active fork, storing cheats of the previously active one in its overlay.
+    pub fn select_fork(
+        &mut self,
+        handle: ForkHandle,
+        current_cheats: &mut ForkScopedCheats,
+    ) -> Result<()> {
+        ensure!(
+            handle.id < self.overlays.len(),
+            "Fork with id = {} does not exist",
+            handle.id
+        );
+
+        if handle.id != self.active {
+            std::mem::swap(
+                current_cheats,
+                &mut self.overlays[self.active].scoped_cheats,
+            );
+            self.active = handle.id;
+            std::mem::swap(
+                current_cheats,
+                &mut self.overlays[self.active].scoped_cheats,
+            );
+        }
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn active_fork(&self) -> ForkHandle {
+        ForkHandle { id: self.active }
+    }
+
+    #[must_use]
+    pub fn active_chain_id(&self) -> Option<ChainId> {
+        self.overlays[self.active].chain_id.clone()
+    }
+
+    /// Returns the pinned block of the active fork, `None` if the test does not run on a fork
+    #[must_use]
+    pub fn active_fork_block_info(&self) -> Option<ForkBlockInfo> {
+        self.overlays[self.active].fork_block_info.clone()
+    }
+
+    /// State of the active fork as of `block_id`, or of its pinned block if `None`.
+    /// It is created once per block and changes made to the active fork in the test are not visible in it
+    pub fn active_state_at_block(
+        &mut self,
+        block_id: Option<BlockId>,
+    ) -> Result<Rc<RefCell<ForkStateAtBlock>>> {
+        let fork_state_reader = self.overlays[self.active]
+            .state
+            .state
+            .fork_state_reader
+            .as_ref()
+            .context("Calling contracts at a given block can only be done in forked tests")?;
+        let block_number = match block_id {
+            Some(block_id) => fork_state_reader.block_number_of(block_id)?,
+            None => fork_state_reader.block_number(),
+        };
+
+        let key = (self.active, block_number);
+        if let Some(state_at_block) = self.states_at_block.get(&key) {
+            return Ok(state_at_block.clone());
+        }
+
+        let mut state_reader = ExtendedStateReader {
+            dict_state_reader: build_forked_testing_state(),
+            fork_state_reader: Some(fork_state_reader.at_block_number(block_number)?),
+        };
+        let block_info = state_reader.get_block_info()?;
+        let state_at_block = Rc::new(RefCell::new(ForkStateAtBlock {
+            state: CachedState::new(state_reader),
+            block_info,
+        }));
+        self.states_at_block.insert(key, state_at_block.clone());
+
+        Ok(state_at_block)
+    }
+
+    /// Saves the reads recorded in every fork of the test to the fork data snapshots
+    pub fn save_fork_data(&self) -> Result<()> {
+        self.overlays
+            .iter()
+            .filter_map(|overlay| overlay.state.state.fork_state_reader.as_ref())
+            .try_for_each(ForkStateReader::save_fork_data)
+    }
+
+    pub fn active_state(&self) -> &CachedState<ExtendedStateReader> {
+        &self.overlays[self.active].state
+    }
+
+    pub fn active_state_mut(&mut self) -> &mut CachedState<ExtendedStateReader> {
+        &mut self.overlays[self.active].state
+    }
+}
+
+/// State passed to the test execution, forwarding every read and write to the selected fork.
+pub struct MultiForkState {
+    registry: Rc<RefCell<ForkRegistry>>,
+}
+
+impl MultiForkState {
+    #[must_use]
+    pub fn new(registry: Rc<RefCell<ForkRegistry>>) -> Self {
+        Self { registry }
+    }
+}
+
+impl StateReader for MultiForkState {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt252> {
+        self.registry
+            .borrow()
+            .active_state()
+            .get_storage_at(contract_address, key)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.registry
+            .borrow()
+            .active_state()
+            .get_nonce_at(contract_address)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.registry
+            .borrow()
+            .active_state()
+            .get_class_hash_at(contract_address)
+    }
+
+    fn get_compiled_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClass> {
+        self.registry
+            .borrow()
+            .active_state()
+            .get_compiled_contract_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.registry
+            .borrow()
+            .active_state()
+            .get_compiled_class_hash(class_hash)
+    }
+}
+
+impl State for MultiForkState {
+    fn set_storage_at(
+        &mut self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: Felt252,
+    ) -> StateResult<()> {
+        self.registry
+            .borrow_mut()
+            .active_state_mut()
+            .set_storage_at(contract_address, key, value)
+    }
+
+    fn increment_nonce(&mut self, contract_address: ContractAddress) -> StateResult<()> {
+        self.registry
+            .borrow_mut()
+            .active_state_mut()
+            .increment_nonce(contract_address)
+    }
+
+    fn set_class_hash_at(
+        &mut self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) -> StateResult<()> {
+        self.registry
+            .borrow_mut()
+            .active_state_mut()
+            .set_class_hash_at(contract_address, class_hash)
+    }
+
+    fn set_contract_class(
+        &mut self,
+        class_hash: ClassHash,
+        contract_class: ContractClass,
+    ) -> StateResult<()> {
+        self.registry
+            .borrow_mut()
+            .active_state_mut()
+            .set_contract_class(class_hash, contract_class)
+    }
+
+    fn set_compiled_class_hash(
+        &mut self,
+        class_hash: ClassHash,
+        compiled_class_hash: CompiledClassHash,
+    ) -> StateResult<()> {
+        self.registry
+            .borrow_mut()
+            .active_state_mut()
+            .set_compiled_class_hash(class_hash, compiled_class_hash)
+    }
+
+    fn add_visited_pcs(&mut self, class_hash: ClassHash, pcs: &HashSet<usize>) {
+        self.registry
+            .borrow_mut()
+            .active_state_mut()
+            .add_visited_pcs(class_hash, pcs);
+    }
+}
@@ -0,0 +1,289 @@
+use anyhow::{Context, Result};
+use blockifier::blockifier::block::BlockInfo;
+use blockifier::state::errors::StateError::{self, StateReadError};
+use cairo_vm::Felt252;
+use camino::{Utf8Path, Utf8PathBuf};
+use fs2::FileExt;
+use regex::Regex;
+use runtime::starknet::context::SerializableBlockInfo;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::ContractClass;
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// Whether fork reads are recorded to or replayed from snapshot files stored in a directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForkDataMode {
+    Record(Utf8PathBuf),
+    Replay(Utf8PathBuf),
+}
+
+impl ForkDataMode {
+    #[must_use]
+    pub fn is_replay(&self) -> bool {
+        matches!(self, ForkDataMode::Replay(_))
+    }
+}
+
+// Ordered maps keep the snapshot files deterministic, so they can be committed to a repository
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct ForkSnapshotContent {
+    chain_id: Option<String>,
+    block_info: Option<SerializableBlockInfo>,
+    block_hash: Option<Felt252>,
+    storage_at: BTreeMap<ContractAddress, BTreeMap<StorageKey, Felt252>>,
+    nonce_at: BTreeMap<ContractAddress, Nonce>,
+    class_hash_at: BTreeMap<ContractAddress, ClassHash>,
+    compiled_contract_class: BTreeMap<ClassHash, ContractClass>,
+}
+
+impl ForkSnapshotContent {
+    fn from_str(serialized: &str, snapshot_file: &Utf8Path) -> Result<Self> {
+        serde_json::from_str(serialized)
+            .with_context(|| format!("Could not deserialize fork data snapshot {snapshot_file}"))
+    }
+
+    fn extend(&mut self, other: Self) {
+        for (contract_address, storage) in other.storage_at {
+            self.storage_at
+                .entry(contract_address)
+                .or_default()
+                .extend(storage);
+        }
+        self.nonce_at.extend(other.nonce_at);
+        self.class_hash_at.extend(other.class_hash_at);
+        self.compiled_contract_class
+            .extend(other.compiled_contract_class);
+        if other.chain_id.is_some() {
+            self.chain_id = other.chain_id;
+        }
+        if other.block_info.is_some() {
+            self.block_info = other.block_info;
+        }
+        if other.block_hash.is_some() {
+            self.block_hash = other.block_hash;
+        }
+    }
+}
+
+/// Snapshot of every state response received from a single fork.
+/// Unlike [`crate::forking::cache::ForkCache`], a replayed snapshot is the only source of data,
+/// reading anything that was not recorded is an error.
+/// Recorded reads are written only by [`ForkSnapshot::save`], at the end of a test run.
+#[derive(Debug)]
+pub struct ForkSnapshot {
+    content: ForkSnapshotContent,
+    snapshot_file: Utf8PathBuf,
+    replay: bool,
+}
+
+impl ForkSnapshot {
+    pub(crate) fn load_or_new(
+        mode: &ForkDataMode,
+        fork_name: &str,
+        block_number: BlockNumber,
+    ) -> Result<Self> {
+        let (dir, replay) = match mode {
+            ForkDataMode::Record(dir) => (dir, false),
+            ForkDataMode::Replay(dir) => (dir, true),
+        };
+        let snapshot_file = snapshot_file_path(dir, fork_name, block_number);
+
+        let content = if replay {
+            let serialized = fs::read_to_string(&snapshot_file).with_context(|| {
+                format!("Fork data snapshot {snapshot_file} not found, record it with --record-fork-data first")
+            })?;
+            ForkSnapshotContent::from_str(&serialized, &snapshot_file)?
+        } else {
+            fs::create_dir_all(dir).context("Fork data directory could not be created")?;
+            ForkSnapshotContent::default()
+        };
+
+        Ok(Self {
+            content,
+            snapshot_file,
+            replay,
+        })
+    }
+
+    #[must_use]
+    pub fn is_replay(&self) -> bool {
+        self.replay
+    }
+
+    /// Merges the reads recorded so far into the snapshot file, does nothing when replaying
+    pub(crate) fn save(&mut self) -> Result<()> {
+        if self.replay {
+            return Ok(());
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&self.snapshot_file)
+            .with_context(|| format!("Could not open fork data snapshot {}", self.snapshot_file))?;
+
+        // Tests using the same fork record it concurrently, each of them merges its reads
+        file.lock_exclusive()
+            .context("Could not lock on fork data snapshot")?;
+
+        let mut serialized = String::new();
+        file.read_to_string(&mut serialized)?;
+
+        let mut content = if serialized.is_empty() {
+            ForkSnapshotContent::default()
+        } else {
+            ForkSnapshotContent::from_str(&serialized, &self.snapshot_file)?
+        };
+        content.extend(std::mem::take(&mut self.content));
+
+        let output = serde_json::to_string_pretty(&content)?;
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(output.as_bytes())?;
+        file.write_all(b"\n")
+            .with_context(|| format!("Could not save fork data snapshot {}", self.snapshot_file))?;
+
+        file.unlock()?;
+        Ok(())
+    }
+
+    fn not_recorded(&self, what: &str) -> StateError {
+        StateReadError(format!(
+            "{what} was not recorded in fork data snapshot {}",
+            self.snapshot_file
+        ))
+    }
+
+    pub(crate) fn replay_storage_at(
+        &self,
+        contract_address: &ContractAddress,
+        key: &StorageKey,
+    ) -> Result<Felt252, StateError> {
+        self.content
+            .storage_at
+            .get(contract_address)
+            .and_then(|storage| storage.get(key))
+            .copied()
+            .ok_or_else(|| {
+                self.not_recorded(&format!(
+                    "Storage at address: {contract_address:?} and key: {key:?}"
+                ))
+            })
+    }
+
+    pub(crate) fn record_storage_at(
+        &mut self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        value: Felt252,
+    ) {
+        self.content
+            .storage_at
+            .entry(contract_address)
+            .or_default()
+            .insert(key, value);
+    }
+
+    pub(crate) fn replay_nonce_at(
+        &self,
+        contract_address: &ContractAddress,
+    ) -> Result<Nonce, StateError> {
+        self.content
+            .nonce_at
+            .get(contract_address)
+            .copied()
+            .ok_or_else(|| self.not_recorded(&format!("Nonce at {contract_address:?}")))
+    }
+
+    pub(crate) fn record_nonce_at(&mut self, contract_address: ContractAddress, nonce: Nonce) {
+        self.content.nonce_at.insert(contract_address, nonce);
+    }
+
+    pub(crate) fn replay_class_hash_at(
+        &self,
+        contract_address: &ContractAddress,
+    ) -> Result<ClassHash, StateError> {
+        self.content
+            .class_hash_at
+            .get(contract_address)
+            .copied()
+            .ok_or_else(|| self.not_recorded(&format!("Class hash at {contract_address:?}")))
+    }
+
+    pub(crate) fn record_class_hash_at(
+        &mut self,
+        contract_address: ContractAddress,
+        class_hash: ClassHash,
+    ) {
+        self.content
+            .class_hash_at
+            .insert(contract_address, class_hash);
+    }
+
+    pub(crate) fn replay_contract_class(
+        &self,
+        class_hash: &ClassHash,
+    ) -> Result<ContractClass, StateError> {
+        self.content
+            .compiled_contract_class
+            .get(class_hash)
+            .cloned()
+            .ok_or_else(|| self.not_recorded(&format!("Class {class_hash}")))
+    }
+
+    pub(crate) fn record_contract_class(
+        &mut self,
+        class_hash: ClassHash,
+        contract_class: ContractClass,
+    ) {
+        self.content
+            .compiled_contract_class
+            .insert(class_hash, contract_class);
+    }
+
+    pub(crate) fn replay_block(&self) -> Result<(BlockInfo, Felt252), StateError> {
+        self.content
+            .block_info
+            .clone()
+            .zip(self.content.block_hash)
+            .map(|(block_info, block_hash)| (block_info.into(), block_hash))
+            .ok_or_else(|| self.not_recorded("Block info"))
+    }
+
+    pub(crate) fn record_block(&mut self, block_info: BlockInfo, block_hash: Felt252) {
+        self.content.block_info = Some(block_info.into());
+        self.content.block_hash = Some(block_hash);
+    }
+
+    pub(crate) fn replay_chain_id(&self) -> Result<String, StateError> {
+        self.content
+            .chain_id
+            .clone()
+            .ok_or_else(|| self.not_recorded("Chain id"))
+    }
+
+    pub(crate) fn record_chain_id(&mut self, chain_id: String) {
+        self.content.chain_id = Some(chain_id);
+    }
+}
+
+fn snapshot_file_path(
+    dir: &Utf8Path,
+    fork_name: &str,
+    BlockNumber(block_number): BlockNumber,
+) -> Utf8PathBuf {
+    let re = Regex::new(r"[^a-zA-Z0-9]").unwrap();
+
+    // replace non-alphanumeric characters with underscores
+    let sanitized_name = re.replace_all(fork_name, "_");
+
+    dir.join(format!("{sanitized_name}_{block_number}.json"))
+}
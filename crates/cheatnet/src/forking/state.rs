@@ -1,6 +1,7 @@
 use crate::forking::cache::ForkCache;
+use crate::forking::snapshot::{ForkDataMode, ForkSnapshot};
 use crate::state::BlockInfoReader;
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use blockifier::blockifier::block::BlockInfo;
 use blockifier::execution::contract_class::{
     ContractClass as ContractClassBlockifier, ContractClassV0, ContractClassV1,
@@ -10,7 +11,8 @@ use blockifier::state::state_api::{StateReader, StateResult};
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
 use cairo_lang_utils::bigint::BigUintAsHex;
 use cairo_vm::Felt252;
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
+use conversions::serde::serialize::CairoSerialize;
 use conversions::{FromConv, IntoConv};
 use flate2::read::GzDecoder;
 use num_bigint::BigUint;
@@ -28,19 +30,31 @@ use starknet_api::deprecated_contract_class::{
     ContractClass as DeprecatedContractClass, EntryPoint, EntryPointType,
 };
 use starknet_api::state::StorageKey;
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::io::Read;
 use tokio::runtime::Runtime;
 use universal_sierra_compiler_api::{compile_sierra, SierraType};
 use url::Url;
 
+/// Header data of the block a fork is pinned to, as reported by the fork provider
+#[derive(CairoSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct ForkBlockInfo {
+    pub block_number: u64,
+    pub block_hash: Felt252,
+    pub block_timestamp: u64,
+    pub sequencer_address: ContractAddress,
+}
+
 #[derive(Debug)]
 pub struct ForkStateReader {
     client: JsonRpcClient<HttpTransport>,
+    url: Url,
+    cache_dir: Utf8PathBuf,
     block_number: BlockNumber,
     runtime: Runtime,
     cache: RefCell<ForkCache>,
+    snapshot: Option<RefCell<ForkSnapshot>>,
 }
 
 impl ForkStateReader {
@@ -50,18 +64,104 @@ impl ForkStateReader {
                 ForkCache::load_or_new(&url, block_number, cache_dir)
                     .context("Could not create fork cache")?,
             ),
-            client: JsonRpcClient::new(HttpTransport::new(url)),
+            client: JsonRpcClient::new(HttpTransport::new(url.clone())),
+            url,
+            cache_dir: cache_dir.to_owned(),
             block_number,
             runtime: Runtime::new().expect("Could not instantiate Runtime"),
+            snapshot: None,
         })
     }
 
+    /// Records every read to, or serves every read from, the snapshot of `fork_name` in `mode` directory
+    pub fn with_fork_data(mut self, mode: &ForkDataMode, fork_name: &str) -> Result<Self> {
+        self.snapshot = Some(RefCell::new(ForkSnapshot::load_or_new(
+            mode,
+            fork_name,
+            self.block_number,
+        )?));
+        Ok(self)
+    }
+
+    /// Reader of the same node pinned to `block_id` instead, it does not use the fork data snapshot.
+    /// Fails if the node can't serve the block
+    pub fn at_block(&self, block_id: BlockId) -> Result<Self> {
+        self.at_block_number(self.block_number_of(block_id)?)
+    }
+
+    /// Reader of the same node pinned to `block_number` instead, it does not use the fork data snapshot
+    pub fn at_block_number(&self, block_number: BlockNumber) -> Result<Self> {
+        self.ensure_not_replayed()?;
+
+        ForkStateReader::new(self.url.clone(), block_number, &self.cache_dir)
+    }
+
+    /// Number of the block `block_id` refers to, fails if the node can't serve the block
+    pub fn block_number_of(&self, block_id: BlockId) -> Result<BlockNumber> {
+        self.ensure_not_replayed()?;
+
+        match self
+            .runtime
+            .block_on(self.client.get_block_with_tx_hashes(block_id))
+        {
+            Ok(MaybePendingBlockWithTxHashes::Block(block)) => Ok(BlockNumber(block.block_number)),
+            Ok(MaybePendingBlockWithTxHashes::PendingBlock(_)) => {
+                bail!("Reading the state of the pending block is not supported")
+            }
+            Err(err) => Err(anyhow!(err))
+                .with_context(|| format!("Node at {} can't serve block {block_id:?}", self.url)),
+        }
+    }
+
+    #[must_use]
+    pub fn block_number(&self) -> BlockNumber {
+        self.block_number
+    }
+
+    fn ensure_not_replayed(&self) -> Result<()> {
+        ensure!(
+            self.replayed_snapshot().is_none(),
+            "Reading the state at a given block is not supported when replaying fork data"
+        );
+        Ok(())
+    }
+
     pub fn chain_id(&self) -> Result<ChainId> {
+        if let Some(snapshot) = self.replayed_snapshot() {
+            return Ok(ChainId::from(snapshot.replay_chain_id()?));
+        }
+
         let id = self.runtime.block_on(self.client.chain_id())?;
         let id = parse_cairo_short_string(&id)?;
+
+        if let Some(snapshot) = &self.snapshot {
+            snapshot.borrow_mut().record_chain_id(id.clone());
+        }
+
         Ok(ChainId::from(id))
     }
 
+    /// Saves the reads recorded in the fork data snapshot, if there is one
+    pub fn save_fork_data(&self) -> Result<()> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot.borrow_mut().save(),
+            None => Ok(()),
+        }
+    }
+
+    fn replayed_snapshot(&self) -> Option<Ref<'_, ForkSnapshot>> {
+        self.snapshot
+            .as_ref()
+            .map(RefCell::borrow)
+            .filter(|snapshot| snapshot.is_replay())
+    }
+
+    fn record(&self, record: impl FnOnce(&mut ForkSnapshot)) {
+        if let Some(snapshot) = &self.snapshot {
+            record(&mut snapshot.borrow_mut());
+        }
+    }
+
     fn block_id(&self) -> BlockId {
         BlockId::Number(self.block_number.0)
     }
@@ -80,12 +180,8 @@ fn other_provider_error<T>(boxed: impl ToString) -> Result<T, StateError> {
     ))
 }
 
-impl BlockInfoReader for ForkStateReader {
-    fn get_block_info(&mut self) -> StateResult<BlockInfo> {
-        if let Some(cache_hit) = self.cache.borrow().get_block_info() {
-            return Ok(cache_hit);
-        }
-
+impl ForkStateReader {
+    fn fetch_block(&mut self) -> StateResult<(BlockInfo, Felt252)> {
         match self
             .runtime
             .block_on(self.client.get_block_with_tx_hashes(self.block_id()))
@@ -98,12 +194,13 @@ impl BlockInfoReader for ForkStateReader {
                     gas_prices: SerializableGasPrices::default().into(),
                     use_kzg_da: true,
                 };
+                let block_hash = block.block_hash;
 
-                self.cache
-                    .borrow_mut()
-                    .cache_get_block_info(block_info.clone());
+                let mut cache = self.cache.borrow_mut();
+                cache.cache_get_block_info(block_info.clone());
+                cache.cache_get_block_hash(block_hash);
 
-                Ok(block_info)
+                Ok((block_info, block_hash))
             }
             Ok(MaybePendingBlockWithTxHashes::PendingBlock(_)) => {
                 unreachable!("Pending block is not be allowed at the configuration level")
@@ -114,10 +211,48 @@ impl BlockInfoReader for ForkStateReader {
             ))),
         }
     }
+
+    /// Returns information about the block the fork is pinned to, including its hash
+    pub fn get_fork_block_info(&mut self) -> StateResult<ForkBlockInfo> {
+        let (block_info, block_hash) = self.get_block()?;
+
+        Ok(ForkBlockInfo {
+            block_number: block_info.block_number.0,
+            block_hash,
+            block_timestamp: block_info.block_timestamp.0,
+            sequencer_address: block_info.sequencer_address,
+        })
+    }
 }
 
-impl StateReader for ForkStateReader {
-    fn get_storage_at(
+impl ForkStateReader {
+    fn get_block(&mut self) -> StateResult<(BlockInfo, Felt252)> {
+        if let Some(snapshot) = self.replayed_snapshot() {
+            return snapshot.replay_block();
+        }
+
+        let cached = {
+            let cache = self.cache.borrow();
+            cache.get_block_info().zip(cache.get_block_hash())
+        };
+        let (block_info, block_hash) = match cached {
+            Some(cache_hit) => cache_hit,
+            None => self.fetch_block()?,
+        };
+
+        self.record(|snapshot| snapshot.record_block(block_info.clone(), block_hash));
+        Ok((block_info, block_hash))
+    }
+}
+
+impl BlockInfoReader for ForkStateReader {
+    fn get_block_info(&mut self) -> StateResult<BlockInfo> {
+        self.get_block().map(|(block_info, _)| block_info)
+    }
+}
+
+impl ForkStateReader {
+    fn fetch_storage_at(
         &self,
         contract_address: ContractAddress,
         key: StorageKey,
@@ -145,7 +280,7 @@ impl StateReader for ForkStateReader {
         }
     }
 
-    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+    fn fetch_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
         if let Some(cache_hit) = self.cache.borrow().get_nonce_at(&contract_address) {
             return Ok(cache_hit);
         }
@@ -171,7 +306,7 @@ impl StateReader for ForkStateReader {
         }
     }
 
-    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+    fn fetch_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
         if let Some(cache_hit) = self.cache.borrow().get_class_hash_at(&contract_address) {
             return Ok(cache_hit);
         }
@@ -197,35 +332,85 @@ impl StateReader for ForkStateReader {
         }
     }
 
+    fn fetch_contract_class(&self, class_hash: ClassHash) -> StateResult<ContractClassStarknet> {
+        let mut cache = self.cache.borrow_mut();
+
+        if let Some(cache_hit) = cache.get_compiled_contract_class(&class_hash) {
+            return Ok(cache_hit.clone());
+        }
+
+        match self.runtime.block_on(
+            self.client
+                .get_class(self.block_id(), Felt::from_(class_hash)),
+        ) {
+            Ok(contract_class) => Ok(cache
+                .insert_compiled_contract_class(class_hash, contract_class)
+                .clone()),
+            Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => {
+                Err(UndeclaredClassHash(class_hash))
+            }
+            Err(ProviderError::Other(boxed)) => other_provider_error(boxed),
+            Err(x) => Err(StateReadError(format!(
+                "Unable to get compiled class at {class_hash} from fork ({x})"
+            ))),
+        }
+    }
+}
+
+impl StateReader for ForkStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt252> {
+        if let Some(snapshot) = self.replayed_snapshot() {
+            return snapshot.replay_storage_at(&contract_address, &key);
+        }
+
+        let value = self.fetch_storage_at(contract_address, key)?;
+        self.record(|snapshot| snapshot.record_storage_at(contract_address, key, value));
+        Ok(value)
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        if let Some(snapshot) = self.replayed_snapshot() {
+            return snapshot.replay_nonce_at(&contract_address);
+        }
+
+        let nonce = self.fetch_nonce_at(contract_address)?;
+        self.record(|snapshot| snapshot.record_nonce_at(contract_address, nonce));
+        Ok(nonce)
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        if let Some(snapshot) = self.replayed_snapshot() {
+            return snapshot.replay_class_hash_at(&contract_address);
+        }
+
+        let class_hash = self.fetch_class_hash_at(contract_address)?;
+        self.record(|snapshot| snapshot.record_class_hash_at(contract_address, class_hash));
+        Ok(class_hash)
+    }
+
     fn get_compiled_contract_class(
         &self,
         class_hash: ClassHash,
     ) -> StateResult<ContractClassBlockifier> {
-        let mut cache = self.cache.borrow_mut();
+        let replayed = self
+            .replayed_snapshot()
+            .map(|snapshot| snapshot.replay_contract_class(&class_hash));
 
-        let contract_class = {
-            if let Some(cache_hit) = cache.get_compiled_contract_class(&class_hash) {
-                Ok(cache_hit)
-            } else {
-                match self.runtime.block_on(
-                    self.client
-                        .get_class(self.block_id(), Felt::from_(class_hash)),
-                ) {
-                    Ok(contract_class) => {
-                        Ok(cache.insert_compiled_contract_class(class_hash, contract_class))
-                    }
-                    Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => {
-                        Err(UndeclaredClassHash(class_hash))
-                    }
-                    Err(ProviderError::Other(boxed)) => other_provider_error(boxed),
-                    Err(x) => Err(StateReadError(format!(
-                        "Unable to get compiled class at {class_hash} from fork ({x})"
-                    ))),
-                }
-            }
+        let contract_class = if let Some(replayed) = replayed {
+            replayed?
+        } else {
+            let contract_class = self.fetch_contract_class(class_hash)?;
+            self.record(|snapshot| {
+                snapshot.record_contract_class(class_hash, contract_class.clone());
+            });
+            contract_class
         };
 
-        match contract_class? {
+        match contract_class {
             ContractClassStarknet::Sierra(flattened_class) => {
                 let converted_sierra_program: Vec<BigUintAsHex> = flattened_class
                     .sierra_program
@@ -1,6 +1,11 @@
 use state::CheatnetState;
 
 pub mod constants;
+pub mod deadline;
 pub mod forking;
+pub mod output_capture;
 pub mod runtime_extensions;
 pub mod state;
+pub mod state_changes;
+pub mod state_snapshot;
+pub mod storage_accesses;
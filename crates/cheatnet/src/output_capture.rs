@@ -0,0 +1,99 @@
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, Hint};
+use cairo_lang_runner::casm_run::{extract_relocatable, format_for_debug, vm_get_range};
+use cairo_vm::hint_processor::hint_processor_definition::{
+    HintProcessor, HintProcessorLogic, HintReference,
+};
+use cairo_vm::serde::deserialize_program::ApTracking;
+use cairo_vm::types::exec_scope::ExecutionScopes;
+use cairo_vm::vm::errors::hint_errors::HintError;
+use cairo_vm::vm::errors::vm_errors::VirtualMachineError;
+use cairo_vm::vm::runners::cairo_runner::{ResourceTracker, RunResources};
+use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Output printed by the test and the contracts it calls, shared by their hint processors
+pub type CapturedOutput = Rc<RefCell<String>>;
+
+/// Hint processor that collects what is printed instead of writing it to stdout,
+/// so outputs of tests running in parallel are not mixed together.
+/// Wraps both the test and the calls to contracts, so their prints are collected in order
+pub struct HintProcessorWithCapturedOutput<'a> {
+    hint_processor: &'a mut dyn HintProcessor,
+    /// `None` if the output is printed as it is produced
+    captured_output: Option<CapturedOutput>,
+}
+
+impl<'a> HintProcessorWithCapturedOutput<'a> {
+    #[must_use]
+    pub fn new(
+        hint_processor: &'a mut dyn HintProcessor,
+        captured_output: Option<CapturedOutput>,
+    ) -> Self {
+        Self {
+            hint_processor,
+            captured_output,
+        }
+    }
+}
+
+impl HintProcessorLogic for HintProcessorWithCapturedOutput<'_> {
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &Box<dyn Any>,
+        constants: &HashMap<String, Felt252>,
+    ) -> Result<(), HintError> {
+        if let (
+            Some(captured_output),
+            Some(Hint::Core(CoreHintBase::Core(CoreHint::DebugPrint { start, end }))),
+        ) = (&self.captured_output, hint_data.downcast_ref::<Hint>())
+        {
+            let start = extract_relocatable(vm, start)?;
+            let end = extract_relocatable(vm, end)?;
+            let felts = vm_get_range(vm, start, end)
+                .map_err(|_| HintError::CustomHint(Box::from("Failed to read printed data")))?;
+            captured_output
+                .borrow_mut()
+                .push_str(&format_for_debug(felts.into_iter()));
+
+            return Ok(());
+        }
+
+        self.hint_processor
+            .execute_hint(vm, exec_scopes, hint_data, constants)
+    }
+
+    fn compile_hint(
+        &self,
+        hint_code: &str,
+        ap_tracking_data: &ApTracking,
+        reference_ids: &HashMap<String, usize>,
+        references: &[HintReference],
+    ) -> Result<Box<dyn Any>, VirtualMachineError> {
+        self.hint_processor
+            .compile_hint(hint_code, ap_tracking_data, reference_ids, references)
+    }
+}
+
+impl ResourceTracker for HintProcessorWithCapturedOutput<'_> {
+    fn consumed(&self) -> bool {
+        self.hint_processor.consumed()
+    }
+
+    fn consume_step(&mut self) {
+        self.hint_processor.consume_step();
+    }
+
+    fn get_n_steps(&self) -> Option<usize> {
+        self.hint_processor.get_n_steps()
+    }
+
+    fn run_resources(&self) -> &RunResources {
+        self.hint_processor.run_resources()
+    }
+}
@@ -1,3 +1,5 @@
+use crate::deadline::HintProcessorWithDeadline;
+use crate::output_capture::HintProcessorWithCapturedOutput;
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::CheatnetState;
 use crate::runtime_extensions::cheatable_starknet_runtime_extension::CheatableStarknetRuntimeExtension;
 use crate::runtime_extensions::common::get_relocated_vm_trace;
@@ -54,6 +56,8 @@ pub fn execute_entry_point_call_cairo1(
 
     // region: Modified blockifier code
 
+    let deadline = cheatnet_state.deadline;
+    let captured_output = cheatnet_state.captured_output.clone();
     let mut cheatable_runtime = ExtendedRuntime {
         extension: CheatableStarknetRuntimeExtension { cheatnet_state },
         extended_runtime: StarknetRuntime {
@@ -62,13 +66,20 @@ pub fn execute_entry_point_call_cairo1(
     };
 
     // Execute.
-    cheatable_run_entry_point(
+    let mut output_capture =
+        HintProcessorWithCapturedOutput::new(&mut cheatable_runtime, captured_output);
+    let mut hint_processor = HintProcessorWithDeadline::new(&mut output_capture, deadline);
+    let run_result = cheatable_run_entry_point(
         &mut runner,
-        &mut cheatable_runtime,
+        &mut hint_processor,
         &entry_point,
         &args,
         program_extra_data_length,
-    )?;
+    );
+    if hint_processor.deadline_exceeded() {
+        cheatable_runtime.extension.cheatnet_state.deadline_exceeded = true;
+    }
+    run_result?;
 
     let vm_trace = if cheatable_runtime
         .extension
@@ -1,8 +1,11 @@
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::execution::entry_point::execute_constructor_entry_point;
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::CheatnetState;
+use crate::storage_accesses::{StorageAccess, StorageAccessKind};
 use blockifier::execution::syscalls::hint_processor::SyscallHintProcessor;
 use blockifier::execution::syscalls::{
-    DeployRequest, DeployResponse, LibraryCallRequest, SyscallResponse, SyscallResult,
+    storage_read, storage_write, DeployRequest, DeployResponse, LibraryCallRequest,
+    StorageReadRequest, StorageReadResponse, StorageWriteRequest, StorageWriteResponse,
+    SyscallResponse, SyscallResult,
 };
 use blockifier::execution::{call_info::CallInfo, entry_point::ConstructorContext};
 use blockifier::execution::{
@@ -27,7 +30,9 @@ use blockifier::{
 use cairo_vm::types::relocatable::Relocatable;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use cairo_vm::vm::vm_core::VirtualMachine;
+use cairo_vm::Felt252;
 use starknet_api::core::calculate_contract_address;
+use starknet_api::state::StorageKey;
 use starknet_api::{
     core::{ClassHash, ContractAddress},
     deprecated_contract_class::EntryPointType,
@@ -215,3 +220,64 @@ impl SyscallResponse for SingleSegmentResponse {
         write_segment(vm, ptr, self.segment)
     }
 }
+
+// blockifier/src/execution/syscalls/mod.rs (storage_read)
+pub fn storage_read_syscall(
+    request: StorageReadRequest,
+    vm: &mut VirtualMachine,
+    syscall_handler: &mut SyscallHintProcessor<'_>,
+    cheatnet_state: &mut CheatnetState,
+    remaining_gas: &mut u64,
+) -> SyscallResult<StorageReadResponse> {
+    let storage_key = request.address;
+    let response = storage_read(request, vm, syscall_handler, remaining_gas)?;
+
+    record_storage_access(
+        syscall_handler,
+        cheatnet_state,
+        storage_key,
+        StorageAccessKind::Read,
+        response.value,
+    );
+
+    Ok(response)
+}
+
+// blockifier/src/execution/syscalls/mod.rs (storage_write)
+pub fn storage_write_syscall(
+    request: StorageWriteRequest,
+    vm: &mut VirtualMachine,
+    syscall_handler: &mut SyscallHintProcessor<'_>,
+    cheatnet_state: &mut CheatnetState,
+    remaining_gas: &mut u64,
+) -> SyscallResult<StorageWriteResponse> {
+    let (storage_key, value) = (request.address, request.value);
+    let response = storage_write(request, vm, syscall_handler, remaining_gas)?;
+
+    record_storage_access(
+        syscall_handler,
+        cheatnet_state,
+        storage_key,
+        StorageAccessKind::Write,
+        value,
+    );
+
+    Ok(response)
+}
+
+fn record_storage_access(
+    syscall_handler: &SyscallHintProcessor<'_>,
+    cheatnet_state: &mut CheatnetState,
+    storage_key: StorageKey,
+    kind: StorageAccessKind,
+    value: Felt252,
+) {
+    if let Some(recorder) = cheatnet_state.storage_access_recorder.as_mut() {
+        recorder.record(StorageAccess {
+            contract_address: syscall_handler.storage_address(),
+            storage_key,
+            kind,
+            value,
+        });
+    }
+}
@@ -12,13 +12,12 @@ pub fn try_extract_panic_data(err: &str) -> Option<Vec<Felt252>> {
 
     if let Some(captures) = re_felt_array.captures(err) {
         if let Some(panic_data_match) = captures.get(1) {
-            let panic_data_felts: Vec<Felt252> = panic_data_match
+            // Items which are not valid short strings can not be turned back into felts
+            return panic_data_match
                 .as_str()
                 .split_terminator(", ")
-                .map(|s| Felt252::from_short_string(s).unwrap())
+                .map(|s| Felt252::from_short_string(s).ok())
                 .collect();
-
-            return Some(panic_data_felts);
         }
     }
 
@@ -44,7 +43,7 @@ mod test {
 
     #[test]
     fn extracting_plain_panic_data() {
-        let cases: [(&str, Option<Vec<Felt252>>); 4] = [
+        let cases: [(&str, Option<Vec<Felt252>>); 5] = [
             (
                 "Beginning of trace\nGot an exception while executing a hint: Hint Error: Execution failed. Failure reason: 0x434d3232 ('PANIK, DAYTA').\n
                  End of trace",
@@ -58,6 +57,10 @@ mod test {
                 "Got an exception while executing a hint: Hint Error: Execution failed. Failure reason: 0x0 ('').",
                 Some(vec![])
             ),
+            (
+                "Got an exception while executing a hint: Hint Error: Execution failed. Failure reason: 0x434d3232 ('zażółć').",
+                None
+            ),
             ("Custom Hint Error: Invalid trace: \"PANIC, DATA\"", None)
         ];
 
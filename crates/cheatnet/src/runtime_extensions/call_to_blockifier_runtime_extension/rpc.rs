@@ -32,6 +32,8 @@ pub struct UsedResources {
     pub l2_to_l1_payload_lengths: Vec<usize>,
     pub l1_handler_payload_lengths: Vec<usize>,
     pub events: Vec<EventContent>,
+    /// Sierra gas consumed by the test and the contracts it called, `None` if the test was compiled without gas
+    pub sierra_gas_consumed: Option<u64>,
 }
 
 /// Enum representing possible call execution result, along with the data
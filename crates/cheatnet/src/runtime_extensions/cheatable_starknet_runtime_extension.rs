@@ -77,6 +77,29 @@ impl<'a> ExtensionLogic for CheatableStarknetRuntimeExtension<'a> {
                     SyscallSelector::Deploy,
                 )
                 .map(|()| SyscallHandlingResult::Handled),
+            // Executed here only while recording, to record the accesses of successful syscalls
+            SyscallSelector::StorageRead
+                if self.cheatnet_state.storage_access_recorder.is_some() =>
+            {
+                self.execute_syscall(
+                    syscall_handler,
+                    vm,
+                    cheated_syscalls::storage_read_syscall,
+                    SyscallSelector::StorageRead,
+                )
+                .map(|()| SyscallHandlingResult::Handled)
+            }
+            SyscallSelector::StorageWrite
+                if self.cheatnet_state.storage_access_recorder.is_some() =>
+            {
+                self.execute_syscall(
+                    syscall_handler,
+                    vm,
+                    cheated_syscalls::storage_write_syscall,
+                    SyscallSelector::StorageWrite,
+                )
+                .map(|()| SyscallHandlingResult::Handled)
+            }
             _ => Ok(SyscallHandlingResult::Forwarded),
         }
     }
@@ -121,6 +144,8 @@ fn get_syscall_cost(
         SyscallSelector::CallContract => gas_costs.call_contract_gas_cost,
         SyscallSelector::Deploy => gas_costs.deploy_gas_cost,
         SyscallSelector::GetExecutionInfo => gas_costs.get_execution_info_gas_cost,
+        SyscallSelector::StorageRead => gas_costs.storage_read_gas_cost,
+        SyscallSelector::StorageWrite => gas_costs.storage_write_gas_cost,
         _ => unreachable!("Syscall has no associated cost"),
     }
 }
@@ -33,6 +33,10 @@ impl<'a> ExtensionLogic for ForgeConfigExtension<'a> {
             "set_config_ignore" => config_cheatcode!(ignore),
             "set_config_should_panic" => config_cheatcode!(should_panic),
             "set_config_fuzzer" => config_cheatcode!(fuzzer),
+            "set_config_test_case" => config_cheatcode!(test_case),
+            "set_config_available_steps" => config_cheatcode!(available_steps),
+            "set_config_timeout" => config_cheatcode!(timeout),
+            "set_config_serial" => config_cheatcode!(serial),
             "is_config_mode" => Ok(CheatcodeHandlingResult::from_serializable(true)),
             _ => Ok(CheatcodeHandlingResult::Forwarded),
         }
@@ -17,6 +17,8 @@ pub enum BlockId {
     BlockTag,
     BlockHash(Felt252),
     BlockNumber(u64),
+    /// Last block at or before the unix timestamp, can be set only in `Scarb.toml`
+    BlockTimestamp(u64),
 }
 
 #[derive(Debug, Clone, CairoDeserialize, PartialEq)]
@@ -36,6 +38,7 @@ pub enum RawForkConfig {
     Inline(InlineForkConfig),
     Named(ByteArray),
     Overridden(OverriddenForkConfig),
+    Disabled,
 }
 
 // fuzzer
@@ -44,6 +47,24 @@ pub enum RawForkConfig {
 pub struct RawFuzzerConfig {
     pub runs: Option<NonZeroU32>,
     pub seed: Option<u64>,
+    pub ranges: Vec<RawFuzzerRange>,
+    /// Maximal number of runs spent on minimizing a failing input
+    pub shrink_runs: Option<u32>,
+    pub non_deterministic: bool,
+}
+
+#[derive(Debug, Clone, CairoDeserialize, PartialEq)]
+pub struct RawU256 {
+    pub low: u128,
+    pub high: u128,
+}
+
+/// Inclusive bounds of values generated for the fuzzed parameter at `index`
+#[derive(Debug, Clone, CairoDeserialize, PartialEq)]
+pub struct RawFuzzerRange {
+    pub index: usize,
+    pub low: RawU256,
+    pub high: RawU256,
 }
 
 // should panic
@@ -66,6 +87,40 @@ pub struct RawShouldPanicConfig {
 #[derive(Debug, Clone, CairoDeserialize)]
 pub struct RawIgnoreConfig {
     pub is_ignored: bool,
+    /// Reason given in `#[ignore("...")]`
+    pub reason: Option<ByteArray>,
+}
+
+// test case
+
+/// Case of a test expanded from `#[test_case]`, with its arguments as written in the attribute
+#[derive(Debug, Clone, CairoDeserialize, PartialEq)]
+pub struct RawTestCaseConfig {
+    pub name: ByteArray,
+    pub arguments: ByteArray,
+}
+
+// available steps
+
+/// Maximum number of steps of a single test run, overrides the one from the config
+#[derive(Debug, Clone, CairoDeserialize, PartialEq)]
+pub struct RawAvailableStepsConfig {
+    pub steps: u32,
+}
+
+// timeout
+
+/// Wall-clock time limit of a single test run, overrides the one from the config
+#[derive(Debug, Clone, CairoDeserialize, PartialEq)]
+pub struct RawTimeoutConfig {
+    pub seconds: u64,
+}
+
+// serial
+
+#[derive(Debug, Clone, CairoDeserialize, PartialEq)]
+pub struct RawSerialConfig {
+    pub is_serial: bool,
 }
 
 // config
@@ -77,4 +132,8 @@ pub struct RawForgeConfig {
     pub ignore: Option<RawIgnoreConfig>,
     pub should_panic: Option<RawShouldPanicConfig>,
     pub fuzzer: Option<RawFuzzerConfig>,
+    pub test_case: Option<RawTestCaseConfig>,
+    pub available_steps: Option<RawAvailableStepsConfig>,
+    pub timeout: Option<RawTimeoutConfig>,
+    pub serial: Option<RawSerialConfig>,
 }
@@ -0,0 +1,62 @@
+use crate::CheatnetState;
+use anyhow::{Context, Result};
+use blockifier::blockifier::block::GasPrices;
+use blockifier::execution::entry_point::EntryPointExecutionContext;
+use conversions::serde::deserialize::CairoDeserialize;
+use runtime::starknet::context::build_transaction_context;
+use std::num::NonZeroU128;
+use std::sync::Arc;
+
+/// Gas prices of the block, ETH prices are denominated in wei and STRK prices in fri
+#[derive(CairoDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockGasPrices {
+    pub eth_l1_gas_price: u128,
+    pub strk_l1_gas_price: u128,
+    pub eth_l1_data_gas_price: u128,
+    pub strk_l1_data_gas_price: u128,
+}
+
+impl TryFrom<BlockGasPrices> for GasPrices {
+    type Error = anyhow::Error;
+
+    fn try_from(value: BlockGasPrices) -> Result<Self> {
+        let non_zero = |price: u128, name: &str| {
+            NonZeroU128::new(price).with_context(|| format!("{name} must be greater than 0"))
+        };
+
+        Ok(Self {
+            eth_l1_gas_price: non_zero(value.eth_l1_gas_price, "eth_l1_gas_price")?,
+            strk_l1_gas_price: non_zero(value.strk_l1_gas_price, "strk_l1_gas_price")?,
+            eth_l1_data_gas_price: non_zero(value.eth_l1_data_gas_price, "eth_l1_data_gas_price")?,
+            strk_l1_data_gas_price: non_zero(
+                value.strk_l1_data_gas_price,
+                "strk_l1_data_gas_price",
+            )?,
+        })
+    }
+}
+
+impl CheatnetState {
+    /// Changes gas prices of the block the rest of the test is executed in
+    pub fn set_block_gas_prices(
+        &mut self,
+        context: &mut EntryPointExecutionContext,
+        gas_prices: BlockGasPrices,
+    ) -> Result<()> {
+        self.block_info.gas_prices = gas_prices.try_into()?;
+
+        let chain_id = context
+            .tx_context
+            .block_context
+            .chain_info()
+            .chain_id
+            .clone();
+        context.tx_context = Arc::new(build_transaction_context(
+            &self.block_info,
+            Some(chain_id),
+            self.block_context_version,
+        ));
+
+        Ok(())
+    }
+}
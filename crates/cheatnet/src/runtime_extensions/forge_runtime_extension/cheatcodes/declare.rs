@@ -1,12 +1,10 @@
 use crate::runtime_extensions::forge_runtime_extension::{
     cheatcodes::{CheatcodeError, EnhancedHintError},
     contracts_data::ContractsData,
+    sierra_version::unsupported_sierra_versions_message,
 };
-use anyhow::{Context, Result};
-use blockifier::{
-    execution::contract_class::{ContractClass as BlockifierContractClass, ContractClassV1},
-    state::{errors::StateError, state_api::State},
-};
+use anyhow::{anyhow, Context, Result};
+use blockifier::state::{errors::StateError, state_api::State};
 use conversions::serde::serialize::CairoSerialize;
 use conversions::IntoConv;
 use starknet::core::types::contract::SierraClass;
@@ -24,14 +22,24 @@ pub fn declare(
     contract_name: &str,
     contracts_data: &ContractsData,
 ) -> Result<DeclareResult, CheatcodeError> {
-    let contract_artifact = contracts_data
+    contracts_data
         .get_artifacts(contract_name)
         .with_context(|| format!("Failed to get contract artifact for name = {contract_name}."))
         .map_err(EnhancedHintError::from)?;
 
-    let contract_class = ContractClassV1::try_from_json_string(&contract_artifact.casm)
-        .expect("Failed to read contract class from json");
-    let contract_class = BlockifierContractClass::V1(contract_class);
+    let sierra_version = contracts_data
+        .get_sierra_version(contract_name)
+        .expect("Failed to get Sierra version");
+    if let Some(message) =
+        unsupported_sierra_versions_message([(contract_name, sierra_version)], None)
+    {
+        return Err(EnhancedHintError::from(anyhow!(message)).into());
+    }
+
+    let contract_class = contracts_data
+        .get_contract_class(contract_name)
+        .expect("Failed to get contract class")
+        .map_err(EnhancedHintError::from)?;
 
     let class_hash = *contracts_data
         .get_class_hash(contract_name)
@@ -64,6 +72,25 @@ pub fn declare(
     }
 }
 
+/// Class hash of the contract if it is already declared in the state, the contract is not declared otherwise
+pub fn get_declared_class_hash(
+    state: &mut dyn State,
+    contract_name: &str,
+    contracts_data: &ContractsData,
+) -> Result<Option<ClassHash>> {
+    let class_hash = *contracts_data
+        .get_class_hash(contract_name)
+        .with_context(|| {
+            format!("Failed to get class hash for contract name = {contract_name}.")
+        })?;
+
+    match state.get_compiled_contract_class(class_hash) {
+        Ok(_) => Ok(Some(class_hash)),
+        Err(StateError::UndeclaredClassHash(_)) => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
 pub fn get_class_hash(sierra_class: &SierraClass) -> Result<ClassHash> {
     Ok(sierra_class.class_hash()?.into_())
 }
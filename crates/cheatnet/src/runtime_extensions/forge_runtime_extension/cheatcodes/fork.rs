@@ -0,0 +1,173 @@
+use crate::constants::TEST_ADDRESS;
+use crate::forking::multi_fork::{ForkHandle, ForkRegistry, ForkStateAtBlock};
+use crate::forking::state::ForkBlockInfo;
+use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
+    call_entry_point, AddressOrClassHash, CallResult,
+};
+use crate::runtime_extensions::common::create_execute_calldata;
+use crate::state::ForkScopedCheats;
+use crate::CheatnetState;
+use anyhow::{Context, Result};
+use blockifier::execution::entry_point::{CallEntryPoint, CallType, EntryPointExecutionContext};
+use blockifier::execution::execution_utils::ReadOnlySegments;
+use blockifier::execution::syscalls::hint_processor::SyscallHintProcessor;
+use blockifier::state::cached_state::TransactionalState;
+use cairo_vm::types::relocatable::Relocatable;
+use cairo_vm::Felt252;
+use conversions::serde::deserialize::CairoDeserialize;
+use conversions::string::TryFromHexStr;
+use runtime::starknet::context::build_transaction_context;
+use starknet::core::types::{BlockId as StarknetBlockId, BlockTag as StarknetBlockTag};
+use starknet_api::block::BlockNumber;
+use starknet_api::core::{ContractAddress, EntryPointSelector};
+use starknet_api::deprecated_contract_class::EntryPointType;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use url::Url;
+
+#[derive(CairoDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum BlockTag {
+    Latest,
+    Pending,
+}
+
+/// Block to read the state of the fork at, as passed to `call_contract_at_block`
+#[derive(CairoDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum BlockId {
+    Tag(BlockTag),
+    Hash(Felt252),
+    Number(u64),
+}
+
+impl From<BlockId> for StarknetBlockId {
+    fn from(block_id: BlockId) -> Self {
+        match block_id {
+            BlockId::Tag(BlockTag::Latest) => StarknetBlockId::Tag(StarknetBlockTag::Latest),
+            BlockId::Tag(BlockTag::Pending) => StarknetBlockId::Tag(StarknetBlockTag::Pending),
+            BlockId::Hash(hash) => StarknetBlockId::Hash(hash),
+            BlockId::Number(number) => StarknetBlockId::Number(number),
+        }
+    }
+}
+
+impl CheatnetState {
+    fn fork_registry(&self) -> Result<Rc<RefCell<ForkRegistry>>> {
+        self.fork_registry
+            .clone()
+            .context("Creating and selecting forks is not supported in this context")
+    }
+
+    pub fn create_fork(&mut self, url: Url, block_number: BlockNumber) -> Result<ForkHandle> {
+        self.fork_registry()?
+            .borrow_mut()
+            .create_fork(url, block_number)
+    }
+
+    /// Switches the state backend to the fork identified by `handle`.
+    /// Cheats, mocks and spied events are kept separately for every fork.
+    pub fn select_fork(
+        &mut self,
+        context: &mut EntryPointExecutionContext,
+        handle: ForkHandle,
+    ) -> Result<()> {
+        let registry = self.fork_registry()?;
+        let mut registry = registry.borrow_mut();
+
+        let mut current_cheats = ForkScopedCheats::default();
+        self.swap_fork_scoped_cheats(&mut current_cheats);
+        let result = registry.select_fork(handle, &mut current_cheats);
+        self.swap_fork_scoped_cheats(&mut current_cheats);
+        result?;
+
+        context.tx_context = Arc::new(build_transaction_context(
+            &self.block_info,
+            registry.active_chain_id(),
+            self.block_context_version,
+        ));
+
+        Ok(())
+    }
+
+    /// Calls the contract on the active fork as of `block_id`, or of its pinned block if `None`,
+    /// with the block context of that block. Resources used by the call are recorded in `syscall_handler`.
+    /// The call does not see changes made in the test and changes made by the call are discarded.
+    /// Fails if the node can't serve the block
+    pub fn call_contract_at_block(
+        &mut self,
+        syscall_handler: &mut SyscallHintProcessor,
+        contract_address: ContractAddress,
+        entry_point_selector: EntryPointSelector,
+        calldata: &[Felt252],
+        block_id: Option<BlockId>,
+    ) -> Result<CallResult> {
+        let registry = self.fork_registry()?;
+        let state_at_block = registry
+            .borrow_mut()
+            .active_state_at_block(block_id.map(Into::into))?;
+        let chain_id = registry.borrow().active_chain_id();
+
+        let mut state_at_block = state_at_block.borrow_mut();
+        let ForkStateAtBlock { state, block_info } = &mut *state_at_block;
+        let mut state = TransactionalState::create_transactional(state);
+
+        let entry_point = CallEntryPoint {
+            class_hash: None,
+            code_address: Some(contract_address),
+            entry_point_type: EntryPointType::External,
+            entry_point_selector,
+            calldata: create_execute_calldata(calldata),
+            storage_address: contract_address,
+            caller_address: TryFromHexStr::try_from_hex_str(TEST_ADDRESS).unwrap(),
+            call_type: CallType::Call,
+            initial_gas: u64::MAX,
+        };
+
+        let test_tx_context = std::mem::replace(
+            &mut syscall_handler.context.tx_context,
+            Arc::new(build_transaction_context(
+                block_info,
+                chain_id,
+                self.block_context_version,
+            )),
+        );
+
+        let hints = HashMap::new();
+        let mut block_syscall_handler = SyscallHintProcessor::new(
+            &mut state,
+            syscall_handler.resources,
+            syscall_handler.context,
+            Relocatable {
+                segment_index: 0,
+                offset: 0,
+            },
+            entry_point.clone(),
+            &hints,
+            ReadOnlySegments::default(),
+        );
+        let call_result = call_entry_point(
+            &mut block_syscall_handler,
+            self,
+            entry_point,
+            &AddressOrClassHash::ContractAddress(contract_address),
+        );
+        let inner_calls = std::mem::take(&mut block_syscall_handler.inner_calls);
+
+        syscall_handler.context.tx_context = test_tx_context;
+        syscall_handler.inner_calls.extend(inner_calls);
+
+        Ok(call_result)
+    }
+
+    pub fn active_fork(&self) -> Result<ForkHandle> {
+        Ok(self.fork_registry()?.borrow().active_fork())
+    }
+
+    pub fn get_fork_block_info(&self) -> Result<ForkBlockInfo> {
+        self.fork_registry()?
+            .borrow()
+            .active_fork_block_info()
+            .context("get_fork_block_info can only be used in forked tests")
+    }
+}
@@ -3,6 +3,7 @@ use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::Felt252;
 use runtime::EnhancedHintError;
 
+pub mod block_gas_prices;
 pub mod cheat_block_number;
 pub mod cheat_block_timestamp;
 pub mod cheat_caller_address;
@@ -10,11 +11,14 @@ pub mod cheat_execution_info;
 pub mod cheat_sequencer_address;
 pub mod declare;
 pub mod deploy;
+pub mod fork;
 pub mod get_class_hash;
 pub mod l1_handler_execute;
 pub mod mock_call;
 pub mod precalculate_address;
 pub mod replace_bytecode;
+pub mod set_balance;
+pub mod snapshot_state;
 pub mod spy_events;
 pub mod spy_messages_to_l1;
 pub mod storage;
@@ -0,0 +1,66 @@
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::storage::{
+    calculate_variable_address, store,
+};
+use blockifier::context::FeeTokenAddresses;
+use blockifier::state::state_api::State;
+use cairo_vm::Felt252;
+use conversions::serde::deserialize::CairoDeserialize;
+use conversions::IntoConv;
+use starknet::core::utils::get_selector_from_name;
+use starknet_api::core::ContractAddress;
+
+/// Storage variable of the `OpenZeppelin` ERC20 component used by the fee token contracts
+const BALANCES_VARIABLE_NAME: &str = "ERC20_balances";
+
+#[derive(CairoDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token {
+    Strk,
+    Eth,
+}
+
+impl Token {
+    /// Address of the token blockifier charges the fees in, as set in the block context
+    #[must_use]
+    pub fn contract_address(self, fee_token_addresses: &FeeTokenAddresses) -> ContractAddress {
+        match self {
+            Token::Strk => fee_token_addresses.strk_fee_token_address,
+            Token::Eth => fee_token_addresses.eth_fee_token_address,
+        }
+    }
+}
+
+///
+/// # Arguments
+///
+/// * `state`: Blockifier state
+/// * `target`: The address of the account whose balance is changed
+/// * `new_balance_low`: Lower 128 bits of the new balance
+/// * `new_balance_high`: Upper 128 bits of the new balance
+/// * `token`: Fee token which balance is changed
+/// * `fee_token_addresses`: Fee token addresses of the block context
+///
+/// returns: Result<(), Error> - a result containing the error if the balance could not be written
+///
+pub fn set_balance(
+    state: &mut dyn State,
+    target: ContractAddress,
+    new_balance_low: Felt252,
+    new_balance_high: Felt252,
+    token: Token,
+    fee_token_addresses: &FeeTokenAddresses,
+) -> Result<(), anyhow::Error> {
+    let balances_selector = get_selector_from_name(BALANCES_VARIABLE_NAME)
+        .expect("Failed to calculate the balances selector")
+        .into_();
+    let balance_address = calculate_variable_address(balances_selector, Some(&[target.into_()]));
+    let token_address = token.contract_address(fee_token_addresses);
+
+    // `u256` occupies two consecutive storage slots, `low` followed by `high`
+    store(state, token_address, balance_address, new_balance_low)?;
+    store(
+        state,
+        token_address,
+        balance_address + Felt252::ONE,
+        new_balance_high,
+    )
+}
@@ -0,0 +1,84 @@
+use crate::forking::multi_fork::{ForkHandle, ForkRegistry};
+use crate::state_snapshot::{restore, restore_keeping_cheats, snapshot, StateSnapshot};
+use crate::CheatnetState;
+use anyhow::{anyhow, bail, Context, Result};
+use conversions::serde::deserialize::CairoDeserialize;
+use conversions::serde::serialize::CairoSerialize;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Identifies a snapshot taken with `snapshot_state` within a single test
+#[derive(CairoSerialize, CairoDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotHandle {
+    pub id: usize,
+}
+
+pub struct TakenSnapshot {
+    /// Fork which was active when the snapshot was taken
+    fork: ForkHandle,
+    snapshot: StateSnapshot,
+}
+
+impl CheatnetState {
+    fn snapshots_fork_registry(&self) -> Result<Rc<RefCell<ForkRegistry>>> {
+        self.fork_registry.clone().context(
+            "Taking and reverting to snapshots of the state is not supported in this context",
+        )
+    }
+
+    /// Saves the state of the active fork together with cheats, mocks and spied events
+    pub fn snapshot_state(&mut self) -> Result<SnapshotHandle> {
+        let registry = self.snapshots_fork_registry()?;
+        let mut registry = registry.borrow_mut();
+
+        let snapshot = snapshot(self, registry.active_state_mut())?;
+        self.state_snapshots.push(Some(TakenSnapshot {
+            fork: registry.active_fork(),
+            snapshot,
+        }));
+
+        Ok(SnapshotHandle {
+            id: self.state_snapshots.len() - 1,
+        })
+    }
+
+    /// Restores the state saved in the snapshot, cheats are restored too unless `keep_cheats` is set.
+    /// Snapshots taken after `handle` are released, the snapshot itself can be reverted to again.
+    pub fn revert_to_snapshot(&mut self, handle: SnapshotHandle, keep_cheats: bool) -> Result<()> {
+        let registry = self.snapshots_fork_registry()?;
+        let mut registry = registry.borrow_mut();
+
+        // Taken out so that the rest of the state can be borrowed while restoring, put back below
+        let taken = match self.state_snapshots.get_mut(handle.id) {
+            Some(taken @ Some(_)) => taken.take().unwrap(),
+            Some(None) => bail!(
+                "Snapshot with id = {} was released after reverting to an earlier snapshot",
+                handle.id
+            ),
+            None => bail!("Snapshot with id = {} does not exist", handle.id),
+        };
+        let result = if taken.fork == registry.active_fork() {
+            let cached_state = registry.active_state_mut();
+            if keep_cheats {
+                restore_keeping_cheats(&taken.snapshot, self, cached_state)
+            } else {
+                restore(&taken.snapshot, self, cached_state)
+            }
+            .map_err(Into::into)
+        } else {
+            Err(anyhow!(
+                "Snapshot with id = {} was taken on fork with id = {}, select it before reverting",
+                handle.id,
+                taken.fork.id
+            ))
+        };
+        self.state_snapshots[handle.id] = Some(taken);
+        result?;
+
+        for later in &mut self.state_snapshots[handle.id + 1..] {
+            *later = None;
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,15 @@
+use crate::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
 use crate::CheatnetState;
 use blockifier::execution::call_info::OrderedEvent;
+use blockifier::state::state_api::State;
 use cairo_vm::Felt252;
 use conversions::{serde::serialize::CairoSerialize, FromConv};
+use data_transformer::reverse_transform_sequence;
+use starknet::core::types::contract::{
+    AbiEntry, AbiEvent, AbiEventEnum, AbiEventStruct, EventField, EventFieldKind, SierraClass,
+    TypedAbiEvent,
+};
+use starknet::core::utils::get_selector_from_name;
 use starknet_api::core::ContractAddress;
 
 /// Represents an emitted event. It is used in the `CheatnetState` to keep track of events
@@ -38,8 +46,205 @@ impl Event {
     }
 }
 
+/// Event decoded with the ABI of the contract which emitted it
+#[derive(Debug, PartialEq, Clone)]
+pub struct DecodedEvent {
+    pub from: ContractAddress,
+    /// Name of the event variant, e.g. `Transfer`
+    pub name: String,
+    /// Names of the event members and their values in Cairo syntax, in the order of declaration
+    pub fields: Vec<(String, String)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SpiedEvent {
+    Decoded(DecodedEvent),
+    /// The event could not be matched with the ABI of the emitting contract
+    Raw(Event),
+}
+
+impl Event {
+    /// Decodes the event with `abi`, returns `None` if its selector or members don't match any event in it
+    #[must_use]
+    pub fn decode(&self, abi: &[AbiEntry]) -> Option<DecodedEvent> {
+        let events = EventsAbi::new(abi);
+
+        events.root_enums().find_map(|root| {
+            let (name, fields) = events.decode_enum(root, &self.keys, &self.data)?;
+            Some(DecodedEvent {
+                from: self.from,
+                name,
+                fields,
+            })
+        })
+    }
+}
+
 impl CheatnetState {
     pub fn get_events(&mut self, event_offset: usize) -> Vec<Event> {
         self.detected_events[event_offset..].to_vec()
     }
+
+    /// Same as [`CheatnetState::get_events`], but events of contracts from `contracts_data` are decoded
+    /// with their ABI
+    pub fn get_decoded_events(
+        &mut self,
+        event_offset: usize,
+        state: &mut dyn State,
+        contracts_data: &ContractsData,
+    ) -> Vec<SpiedEvent> {
+        self.get_events(event_offset)
+            .into_iter()
+            .map(|event| {
+                contract_abi(event.from, state, contracts_data)
+                    .and_then(|abi| event.decode(&abi))
+                    .map_or(SpiedEvent::Raw(event), SpiedEvent::Decoded)
+            })
+            .collect()
+    }
+}
+
+fn contract_abi(
+    contract_address: ContractAddress,
+    state: &mut dyn State,
+    contracts_data: &ContractsData,
+) -> Option<Vec<AbiEntry>> {
+    let class_hash = state.get_class_hash_at(contract_address).ok()?;
+    let contract_name = contracts_data.get_contract_name(&class_hash)?;
+    let artifacts = contracts_data.get_artifacts(contract_name)?;
+    let sierra_class: SierraClass = serde_json::from_str(&artifacts.sierra).ok()?;
+
+    Some(sierra_class.abi)
+}
+
+type EventFields = Vec<(String, String)>;
+
+struct EventsAbi<'a> {
+    abi: &'a [AbiEntry],
+    structs: Vec<&'a AbiEventStruct>,
+    enums: Vec<&'a AbiEventEnum>,
+}
+
+impl<'a> EventsAbi<'a> {
+    fn new(abi: &'a [AbiEntry]) -> Self {
+        let mut structs = vec![];
+        let mut enums = vec![];
+        for entry in abi {
+            match entry {
+                AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(event))) => {
+                    structs.push(event)
+                }
+                AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(event))) => enums.push(event),
+                _ => {}
+            }
+        }
+
+        Self {
+            abi,
+            structs,
+            enums,
+        }
+    }
+
+    /// Enums which are not variants of other enums, e.g. the `Event` enum of the contract,
+    /// but not the ones of its components
+    fn root_enums(&self) -> impl Iterator<Item = &'a AbiEventEnum> + '_ {
+        self.enums.iter().copied().filter(|candidate| {
+            !self
+                .enums
+                .iter()
+                .flat_map(|event| &event.variants)
+                .any(|variant| variant.r#type == candidate.name)
+        })
+    }
+
+    /// Nested variants are emitted with the selector of their name as the first key,
+    /// flat variants are emitted as their inner event
+    fn decode_enum(
+        &self,
+        event: &AbiEventEnum,
+        keys: &[Felt252],
+        data: &[Felt252],
+    ) -> Option<(String, EventFields)> {
+        event
+            .variants
+            .iter()
+            .find_map(|variant| match variant.kind {
+                EventFieldKind::Nested => {
+                    let (selector, keys) = keys.split_first()?;
+                    if *selector != get_selector_from_name(&variant.name).ok()? {
+                        return None;
+                    }
+                    self.decode_type(&variant.name, &variant.r#type, keys, data)
+                }
+                EventFieldKind::Flat => {
+                    self.decode_type(&variant.name, &variant.r#type, keys, data)
+                }
+                EventFieldKind::Key | EventFieldKind::Data => None,
+            })
+    }
+
+    fn decode_type(
+        &self,
+        name: &str,
+        type_name: &str,
+        keys: &[Felt252],
+        data: &[Felt252],
+    ) -> Option<(String, EventFields)> {
+        if let Some(event) = self.structs.iter().find(|event| event.name == type_name) {
+            let fields = self.decode_struct(event, keys, data)?;
+            return Some((name.to_string(), fields));
+        }
+
+        let event = self.enums.iter().find(|event| event.name == type_name)?;
+        self.decode_enum(event, keys, data)
+    }
+
+    fn decode_struct(
+        &self,
+        event: &AbiEventStruct,
+        keys: &[Felt252],
+        data: &[Felt252],
+    ) -> Option<EventFields> {
+        // Nested and flat members are not supported
+        if event
+            .members
+            .iter()
+            .any(|member| !matches!(member.kind, EventFieldKind::Key | EventFieldKind::Data))
+        {
+            return None;
+        }
+
+        let is_key = |kind: &EventFieldKind| matches!(kind, EventFieldKind::Key);
+        let (key_members, data_members): (Vec<_>, Vec<_>) = event
+            .members
+            .iter()
+            .partition(|member| is_key(&member.kind));
+        let types = |members: &[&EventField]| {
+            members
+                .iter()
+                .map(|member| member.r#type.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        let mut keys = reverse_transform_sequence(keys, &types(&key_members), self.abi)
+            .ok()?
+            .into_iter();
+        let mut data = reverse_transform_sequence(data, &types(&data_members), self.abi)
+            .ok()?
+            .into_iter();
+
+        event
+            .members
+            .iter()
+            .map(|member| {
+                let value = if is_key(&member.kind) {
+                    keys.next()
+                } else {
+                    data.next()
+                }?;
+                Some((member.name.clone(), value))
+            })
+            .collect()
+    }
 }
@@ -71,7 +71,7 @@ pub fn calculate_variable_address(selector: Felt252, key: Option<&[Felt252]>) ->
     }
 }
 
-fn storage_key(storage_address: Felt252) -> Result<StorageKey, anyhow::Error> {
+pub fn storage_key(storage_address: Felt252) -> Result<StorageKey, anyhow::Error> {
     Ok(StorageKey(PatriciaKey::try_from(StarkHash::from_(
         storage_address,
     ))?))
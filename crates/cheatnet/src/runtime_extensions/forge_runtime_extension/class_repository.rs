@@ -0,0 +1,56 @@
+use anyhow::{Context, Result};
+use blockifier::execution::contract_class::{ContractClass, ContractClassV1};
+use starknet_api::core::ClassHash;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Classes of all contracts of the run, parsed from CASM when a contract is declared for the first time
+/// and shared by all tests. `ContractClass` is reference counted, so declaring a contract only adds
+/// a reference to it to the state of the test, the state itself is never shared
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClassRepository {
+    classes: HashMap<ClassHash, OnceLock<ContractClass>>,
+}
+
+impl ClassRepository {
+    /// `class_hashes` - hashes of the contracts which can be declared, none of them is parsed yet
+    #[must_use]
+    pub fn new(class_hashes: impl IntoIterator<Item = ClassHash>) -> Self {
+        Self {
+            classes: class_hashes
+                .into_iter()
+                .map(|class_hash| (class_hash, OnceLock::new()))
+                .collect(),
+        }
+    }
+
+    /// Class parsed from the CASM returned by `casm` if it is requested for the first time,
+    /// `None` if the contract can't be declared
+    #[must_use]
+    pub fn get_or_parse<'a>(
+        &self,
+        class_hash: &ClassHash,
+        contract_name: &str,
+        casm: impl FnOnce() -> Result<&'a str>,
+    ) -> Option<Result<ContractClass>> {
+        let class = self.classes.get(class_hash)?;
+        if let Some(contract_class) = class.get() {
+            return Some(Ok(contract_class.clone()));
+        }
+
+        let parsed = casm().and_then(|casm| {
+            ContractClassV1::try_from_json_string(casm)
+                .with_context(|| format!("Failed to read CASM of contract {contract_name}"))
+        });
+
+        // Tests running in parallel may parse the same class at once, all of them use the first one
+        Some(parsed.map(|parsed| class.get_or_init(|| ContractClass::V1(parsed)).clone()))
+    }
+
+    #[must_use]
+    pub fn is_parsed(&self, class_hash: &ClassHash) -> bool {
+        self.classes
+            .get(class_hash)
+            .is_some_and(|class| class.get().is_some())
+    }
+}
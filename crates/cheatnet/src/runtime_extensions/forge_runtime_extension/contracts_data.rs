@@ -1,6 +1,9 @@
 use super::cheatcodes::declare::get_class_hash;
-use anyhow::Result;
+use super::class_repository::ClassRepository;
+use super::sierra_version::{unsupported_sierra_versions_message, SierraVersion};
+use anyhow::{Context, Result};
 use bimap::BiMap;
+use blockifier::execution::contract_class::ContractClass;
 use camino::Utf8PathBuf;
 use conversions::IntoConv;
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
@@ -9,6 +12,7 @@ use starknet::core::types::contract::{AbiEntry, SierraClass};
 use starknet::core::utils::get_selector_from_name;
 use starknet_api::core::{ClassHash, EntryPointSelector};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 type ContractName = String;
 type FunctionName = String;
@@ -18,12 +22,14 @@ pub struct ContractsData {
     contracts: HashMap<ContractName, ContractData>,
     class_hashes: BiMap<ContractName, ClassHash>,
     selectors: HashMap<EntryPointSelector, FunctionName>,
+    class_repository: Arc<ClassRepository>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 struct ContractData {
     artifacts: StarknetContractArtifacts,
     class_hash: ClassHash,
+    sierra_version: SierraVersion,
     source_sierra_path: Utf8PathBuf,
 }
 
@@ -44,15 +50,36 @@ impl ContractsData {
             .collect::<Result<_>>()?;
         let class_hashes = BiMap::from_iter(class_hashes);
 
+        let sierra_versions: HashMap<&ContractName, SierraVersion> = parsed_contracts
+            .iter()
+            .map(|(name, sierra_class)| {
+                let sierra_version =
+                    SierraVersion::from_sierra_program(&sierra_class.sierra_program)
+                        .with_context(|| format!("Failed to read Sierra version of {name}"))?;
+                Ok((name, sierra_version))
+            })
+            .collect::<Result<_>>()?;
+
+        // Contracts compiled to unsupported Sierra versions can't be declared, so they are never compiled to CASM.
+        // Classes are parsed from CASM only when the contracts are declared
+        let class_repository = ClassRepository::new(
+            class_hashes
+                .iter()
+                .filter(|(name, _)| sierra_versions[name].is_supported())
+                .map(|(_, class_hash)| *class_hash),
+        );
+
         let contracts = contracts
             .into_iter()
             .map(|(name, (artifacts, source_sierra_path))| {
                 let class_hash = *class_hashes.get_by_left(&name).unwrap();
+                let sierra_version = sierra_versions[&name];
                 (
                     name,
                     ContractData {
                         artifacts,
                         class_hash,
+                        sierra_version,
                         source_sierra_path,
                     },
                 )
@@ -69,6 +96,7 @@ impl ContractsData {
             contracts,
             class_hashes,
             selectors,
+            class_repository: Arc::new(class_repository),
         })
     }
 
@@ -79,6 +107,13 @@ impl ContractsData {
             .map(|contract| &contract.artifacts)
     }
 
+    /// Artifacts of all contracts, in no particular order
+    pub fn artifacts(&self) -> impl Iterator<Item = (&ContractName, &StarknetContractArtifacts)> {
+        self.contracts
+            .iter()
+            .map(|(name, contract)| (name, &contract.artifacts))
+    }
+
     #[must_use]
     pub fn get_class_hash(&self, contract_name: &str) -> Option<&ClassHash> {
         self.contracts
@@ -86,6 +121,46 @@ impl ContractsData {
             .map(|contract| &contract.class_hash)
     }
 
+    /// Class of the contract parsed once for the whole run when it is first requested, cheap to clone.
+    /// `None` if there is no such contract or it can't be declared
+    #[must_use]
+    pub fn get_contract_class(&self, contract_name: &str) -> Option<Result<ContractClass>> {
+        let contract = self.contracts.get(contract_name)?;
+        self.class_repository
+            .get_or_parse(&contract.class_hash, contract_name, || {
+                contract.artifacts.casm()
+            })
+    }
+
+    /// Whether the class of the contract was already parsed by [`Self::get_contract_class`]
+    #[must_use]
+    pub fn is_contract_class_parsed(&self, contract_name: &str) -> bool {
+        self.get_class_hash(contract_name)
+            .is_some_and(|class_hash| self.class_repository.is_parsed(class_hash))
+    }
+
+    #[must_use]
+    pub fn get_sierra_version(&self, contract_name: &str) -> Option<SierraVersion> {
+        self.contracts
+            .get(contract_name)
+            .map(|contract| contract.sierra_version)
+    }
+
+    /// Message listing all contracts compiled to Sierra versions which can't be declared,
+    /// so they are reported once instead of by every test declaring them. `None` if there are none
+    #[must_use]
+    pub fn unsupported_sierra_versions_message(
+        &self,
+        cairo_version: Option<&str>,
+    ) -> Option<String> {
+        let sierra_versions = self
+            .contracts
+            .iter()
+            .map(|(name, contract)| (name.as_str(), contract.sierra_version));
+
+        unsupported_sierra_versions_message(sierra_versions, cairo_version)
+    }
+
     #[must_use]
     pub fn get_source_sierra_path(&self, contract_name: &str) -> Option<&Utf8PathBuf> {
         self.contracts
@@ -8,10 +8,11 @@ use crate::runtime_extensions::{
     cheatable_starknet_runtime_extension::SyscallSelector,
     common::{get_relocated_vm_trace, sum_syscall_counters},
     forge_runtime_extension::cheatcodes::{
-        declare::declare,
+        declare::{declare, get_declared_class_hash},
         deploy::{deploy, deploy_at},
         get_class_hash::get_class_hash,
         l1_handler_execute::l1_handler_execute,
+        set_balance::set_balance,
         storage::{calculate_variable_address, load, store},
         CheatcodeError,
     },
@@ -46,12 +47,16 @@ use runtime::{
 };
 use starknet::core::types::Felt;
 use starknet::signers::SigningKey;
+use starknet_api::block::BlockNumber;
 use starknet_api::{core::ClassHash, deprecated_contract_class::EntryPointType::L1Handler};
 use std::collections::HashMap;
+use url::Url;
 
 pub mod cheatcodes;
+pub mod class_repository;
 pub mod contracts_data;
 mod file_operations;
+pub mod sierra_version;
 
 pub type ForgeRuntime<'a> = ExtendedRuntime<ForgeExtension<'a>>;
 
@@ -158,6 +163,19 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
 
                 handle_declare_deploy_result(declare(*state, &contract_name, self.contracts_data))
             }
+            "get_declared_class_hash" => {
+                let state = &mut extended_runtime
+                    .extended_runtime
+                    .extended_runtime
+                    .hint_handler
+                    .state;
+
+                let contract_name: String = input_reader.read::<ByteArray>()?.into();
+                let class_hash =
+                    get_declared_class_hash(*state, &contract_name, self.contracts_data)?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(class_hash))
+            }
             "deploy" => {
                 let class_hash = input_reader.read()?;
                 let calldata: Vec<_> = input_reader.read()?;
@@ -202,13 +220,143 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
 
                 Ok(CheatcodeHandlingResult::from_serializable(contract_address))
             }
+            "create_fork" => {
+                let url: String = input_reader.read::<ByteArray>()?.into();
+                let block_number = input_reader.read()?;
+
+                let url = Url::parse(&url).with_context(|| format!("Invalid fork url = {url}"))?;
+
+                let handle = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .create_fork(url, BlockNumber(block_number))?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(handle))
+            }
+            "select_fork" => {
+                let handle = input_reader.read()?;
+                let cheatnet_runtime = &mut extended_runtime.extended_runtime;
+
+                cheatnet_runtime.extension.cheatnet_state.select_fork(
+                    cheatnet_runtime.extended_runtime.hint_handler.context,
+                    handle,
+                )?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(()))
+            }
+            "set_block_gas_prices" => {
+                let gas_prices = input_reader.read()?;
+                let cheatnet_runtime = &mut extended_runtime.extended_runtime;
+
+                cheatnet_runtime
+                    .extension
+                    .cheatnet_state
+                    .set_block_gas_prices(
+                        cheatnet_runtime.extended_runtime.hint_handler.context,
+                        gas_prices,
+                    )?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(()))
+            }
+            "active_fork" => {
+                let handle = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .active_fork()?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(handle))
+            }
+            "get_fork_block_info" => {
+                let fork_block_info = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .get_fork_block_info()?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(fork_block_info))
+            }
+            "call_contract_at_block" => {
+                let contract_address = input_reader.read()?;
+                let function_selector = input_reader.read()?;
+                let calldata: Vec<_> = input_reader.read()?;
+                let block_id = input_reader.read()?;
+
+                let cheatnet_runtime = &mut extended_runtime.extended_runtime;
+                let call_result = cheatnet_runtime
+                    .extension
+                    .cheatnet_state
+                    .call_contract_at_block(
+                        &mut cheatnet_runtime.extended_runtime.hint_handler,
+                        contract_address,
+                        function_selector,
+                        &calldata,
+                        block_id,
+                    )?;
+
+                let result = match call_result {
+                    CallResult::Success { ret_data } => Ok(ret_data),
+                    CallResult::Failure(CallFailure::Panic { panic_data }) => Err(panic_data),
+                    CallResult::Failure(CallFailure::Error { msg }) => {
+                        return Err(EnhancedHintError::from(HintError::CustomHint(Box::from(
+                            msg,
+                        ))))
+                    }
+                };
+
+                Ok(CheatcodeHandlingResult::from_serializable(result))
+            }
+            "snapshot_state" => {
+                let handle = extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .snapshot_state()?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(handle))
+            }
+            "revert_to" => {
+                let handle = input_reader.read()?;
+                let keep_cheats = input_reader.read()?;
+
+                extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .revert_to_snapshot(handle, keep_cheats)?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(()))
+            }
+            "enter_fixture" => {
+                let name: String = input_reader.read::<ByteArray>()?.into();
+
+                extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .active_fixture = Some(name);
+
+                Ok(CheatcodeHandlingResult::from_serializable(()))
+            }
+            "exit_fixture" => {
+                extended_runtime
+                    .extended_runtime
+                    .extension
+                    .cheatnet_state
+                    .active_fixture = None;
+
+                Ok(CheatcodeHandlingResult::from_serializable(()))
+            }
             "var" => {
                 let name: String = input_reader.read::<ByteArray>()?.into();
 
                 let env_var = self
                     .environment_variables
                     .get(&name)
-                    .with_context(|| format!("Failed to read from env var = {name}"))?;
+                    .with_context(|| {
+                        format!("Environment variable {name} is not set, set it in the environment, in [tool.snforge.env] or with --env {name}=VALUE")
+                    })?;
 
                 let parsed_env_var = Felt252::infer_format_and_parse(env_var)
                     .map_err(|_| anyhow!("Failed to parse value = {env_var} to felt"))?;
@@ -464,6 +612,34 @@ impl<'a> ExtensionLogic for ForgeExtension<'a> {
 
                 Ok(CheatcodeHandlingResult::from_serializable(loaded))
             }
+            "set_balance" => {
+                let hint_handler = &mut extended_runtime
+                    .extended_runtime
+                    .extended_runtime
+                    .hint_handler;
+                let target = input_reader.read()?;
+                let new_balance_low = input_reader.read()?;
+                let new_balance_high = input_reader.read()?;
+                let token = input_reader.read()?;
+                let fee_token_addresses = hint_handler
+                    .context
+                    .tx_context
+                    .block_context
+                    .chain_info()
+                    .fee_token_addresses
+                    .clone();
+                set_balance(
+                    hint_handler.state,
+                    target,
+                    new_balance_low,
+                    new_balance_high,
+                    token,
+                    &fee_token_addresses,
+                )
+                .context("Failed to set balance")?;
+
+                Ok(CheatcodeHandlingResult::from_serializable(()))
+            }
             "map_entry_address" => {
                 let map_selector = input_reader.read()?;
                 let keys: Vec<_> = input_reader.read()?;
@@ -673,5 +849,7 @@ pub fn get_all_used_resources(
         execution_resources,
         l1_handler_payload_lengths,
         l2_to_l1_payload_lengths,
+        // Known only to the runner, from the gas counter returned by the test
+        sierra_gas_consumed: None,
     }
 }
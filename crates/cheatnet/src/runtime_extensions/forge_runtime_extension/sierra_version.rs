@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use cairo_lang_starknet_classes::compiler_version::current_sierra_version_id;
+use num_traits::ToPrimitive;
+use starknet::core::types::contract::SierraClass;
+use starknet_types_core::felt::Felt;
+use std::fmt::{Display, Formatter};
+
+/// Lowest version of Sierra which contracts can be declared with
+pub const MINIMAL_SIERRA_VERSION: SierraVersion = SierraVersion::new(1, 0, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SierraVersion {
+    major: usize,
+    minor: usize,
+    patch: usize,
+}
+
+impl SierraVersion {
+    #[must_use]
+    pub const fn new(major: usize, minor: usize, patch: usize) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Highest version of Sierra, the one produced by the compiler the runner is built with
+    #[must_use]
+    pub fn max_supported() -> Self {
+        let version = current_sierra_version_id();
+
+        Self::new(version.major, version.minor, version.patch)
+    }
+
+    /// Reads the version encoded in the first three felts of the Sierra program
+    pub fn from_sierra_program(sierra_program: &[Felt]) -> Result<Self> {
+        let version = sierra_program
+            .get(..3)
+            .context("Sierra program is too short to contain its version")?
+            .iter()
+            .map(|felt| felt.to_usize().context("Invalid Sierra version"))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(version[0], version[1], version[2]))
+    }
+
+    /// Reads the version of the contract class given as JSON
+    pub fn from_sierra_class(sierra_json: &str) -> Result<Self> {
+        let sierra_class: SierraClass = serde_json::from_str(sierra_json)?;
+
+        Self::from_sierra_program(&sierra_class.sierra_program)
+    }
+
+    #[must_use]
+    pub fn is_supported(self) -> bool {
+        (MINIMAL_SIERRA_VERSION..=Self::max_supported()).contains(&self)
+    }
+}
+
+/// Contracts compiled to unsupported Sierra versions are not compiled to CASM, as they can't be declared anyway.
+/// Contracts whose version can't be read are compiled, so the compiler reports what is wrong with them
+#[must_use]
+pub fn should_compile_to_casm(sierra_json: &str) -> bool {
+    SierraVersion::from_sierra_class(sierra_json).map_or(true, SierraVersion::is_supported)
+}
+
+impl Display for SierraVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Message listing all contracts compiled to unsupported Sierra versions, `None` if there are none.
+/// `cairo_version` is the version of Cairo the contracts were compiled with, if it is known
+pub fn unsupported_sierra_versions_message<'a>(
+    contracts: impl IntoIterator<Item = (&'a str, SierraVersion)>,
+    cairo_version: Option<&str>,
+) -> Option<String> {
+    let mut unsupported: Vec<_> = contracts
+        .into_iter()
+        .filter(|(_, version)| !version.is_supported())
+        .collect();
+    unsupported.sort_unstable();
+
+    let supported_versions = format!(
+        "supported Sierra versions: {MINIMAL_SIERRA_VERSION} - {}",
+        SierraVersion::max_supported()
+    );
+
+    let message = match unsupported[..] {
+        [] => return None,
+        [(contract_name, version)] => format!(
+            "Contract {contract_name} was compiled to Sierra {version}, which is not supported, {supported_versions}"
+        ),
+        _ => {
+            let contracts = unsupported
+                .iter()
+                .map(|(contract_name, version)| format!("{contract_name} (Sierra {version})"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("Contracts {contracts} were compiled to Sierra versions which are not supported, {supported_versions}")
+        }
+    };
+
+    let cairo_version = cairo_version
+        .map(|version| format!(" (currently Cairo {version})"))
+        .unwrap_or_default();
+
+    Some(format!(
+        "{message}\nMake sure the version of Scarb{cairo_version} is compatible with the version of snforge, \
+        either by using an older Scarb or by updating snforge"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_sierra_program() {
+        let sierra_program = [1, 6, 0, 2, 8, 2].map(Felt::from);
+
+        assert_eq!(
+            SierraVersion::from_sierra_program(&sierra_program).unwrap(),
+            SierraVersion::new(1, 6, 0)
+        );
+    }
+
+    #[test]
+    fn from_too_short_sierra_program() {
+        let sierra_program = [1, 6].map(Felt::from);
+
+        assert!(SierraVersion::from_sierra_program(&sierra_program).is_err());
+    }
+
+    #[test]
+    fn supported_versions() {
+        assert!(MINIMAL_SIERRA_VERSION.is_supported());
+        assert!(SierraVersion::max_supported().is_supported());
+        assert!(!SierraVersion::new(0, 1, 0).is_supported());
+        assert!(!SierraVersion::new(999, 0, 0).is_supported());
+    }
+
+    #[test]
+    fn all_unsupported_contracts_are_reported() {
+        let supported = SierraVersion::max_supported();
+        let unsupported = SierraVersion::new(999, 0, 0);
+
+        assert!(unsupported_sierra_versions_message([("Supported", supported)], None).is_none());
+
+        let message = unsupported_sierra_versions_message(
+            [
+                ("Second", unsupported),
+                ("Supported", supported),
+                ("First", unsupported),
+            ],
+            Some("2.9.1"),
+        )
+        .unwrap();
+
+        assert!(message.starts_with(
+            "Contracts First (Sierra 999.0.0), Second (Sierra 999.0.0) were compiled to Sierra versions which are not supported"
+        ));
+        assert!(!message.contains("Supported (Sierra"));
+        assert!(message
+            .contains("Make sure the version of Scarb (currently Cairo 2.9.1) is compatible"));
+    }
+}
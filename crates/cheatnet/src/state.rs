@@ -1,11 +1,15 @@
 use crate::constants::{build_test_entry_point, TEST_CONTRACT_CLASS_HASH};
-use crate::forking::state::ForkStateReader;
+use crate::forking::multi_fork::ForkRegistry;
+use crate::forking::state::{ForkBlockInfo, ForkStateReader};
+use crate::output_capture::CapturedOutput;
 use crate::runtime_extensions::call_to_blockifier_runtime_extension::rpc::CallResult;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::cheat_execution_info::{
     ExecutionInfoMock, ResourceBounds,
 };
+use crate::runtime_extensions::forge_runtime_extension::cheatcodes::snapshot_state::TakenSnapshot;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::Event;
 use crate::runtime_extensions::forge_runtime_extension::cheatcodes::spy_messages_to_l1::MessageToL1;
+use crate::storage_accesses::StorageAccessRecorder;
 use blockifier::blockifier::block::BlockInfo;
 use blockifier::execution::call_info::OrderedL2ToL1Message;
 use blockifier::execution::entry_point::CallEntryPoint;
@@ -22,7 +26,7 @@ use cairo_vm::Felt252;
 use conversions::serde::deserialize::CairoDeserialize;
 use conversions::serde::serialize::{BufferWriter, CairoSerialize};
 use conversions::string::TryFromHexStr;
-use runtime::starknet::context::SerializableBlockInfo;
+use runtime::starknet::context::{BlockContextVersion, SerializableBlockInfo};
 use runtime::starknet::state::DictStateReader;
 use starknet_api::core::{ChainId, EntryPointSelector};
 use starknet_api::transaction::ContractAddressSalt;
@@ -33,6 +37,7 @@ use starknet_api::{
 use std::cell::{Ref, RefCell};
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::Instant;
 
 // Specifies the duration of the cheat
 #[derive(CairoDeserialize, Copy, Clone, Debug, PartialEq, Eq)]
@@ -129,6 +134,13 @@ impl ExtendedStateReader {
             .map(ForkStateReader::chain_id)
             .transpose()
     }
+
+    pub fn get_fork_block_info(&mut self) -> StateResult<Option<ForkBlockInfo>> {
+        self.fork_state_reader
+            .as_mut()
+            .map(ForkStateReader::get_fork_block_info)
+            .transpose()
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
@@ -320,6 +332,34 @@ pub struct TraceData {
     pub is_vm_trace_needed: bool,
 }
 
+/// Part of the `CheatnetState` which is kept separately for every fork created in a test.
+/// This includes cheats, mocks and spied events and messages.
+#[derive(Clone)]
+pub struct ForkScopedCheats {
+    pub cheated_execution_info_contracts: HashMap<ContractAddress, ExecutionInfoMock>,
+    pub global_cheated_execution_info: ExecutionInfoMock,
+    pub mocked_functions:
+        HashMap<ContractAddress, HashMap<EntryPointSelector, CheatStatus<Vec<Felt252>>>>,
+    pub replaced_bytecode_contracts: HashMap<ContractAddress, ClassHash>,
+    pub detected_events: Vec<Event>,
+    pub detected_messages_to_l1: Vec<MessageToL1>,
+    pub block_info: BlockInfo,
+}
+
+impl Default for ForkScopedCheats {
+    fn default() -> Self {
+        Self {
+            cheated_execution_info_contracts: Default::default(),
+            global_cheated_execution_info: Default::default(),
+            mocked_functions: Default::default(),
+            replaced_bytecode_contracts: Default::default(),
+            detected_events: vec![],
+            detected_messages_to_l1: vec![],
+            block_info: SerializableBlockInfo::default().into(),
+        }
+    }
+}
+
 pub struct CheatnetState {
     pub cheated_execution_info_contracts: HashMap<ContractAddress, ExecutionInfoMock>,
     pub global_cheated_execution_info: ExecutionInfoMock,
@@ -331,7 +371,22 @@ pub struct CheatnetState {
     pub detected_messages_to_l1: Vec<MessageToL1>,
     pub deploy_salt_base: u32,
     pub block_info: BlockInfo,
+    /// Versioned constants the execution contexts are built with
+    pub block_context_version: BlockContextVersion,
     pub trace_data: TraceData,
+    pub fork_registry: Option<Rc<RefCell<ForkRegistry>>>,
+    /// Name of the fixture currently being executed before the test body
+    pub active_fixture: Option<String>,
+    /// Set only while storage accesses are being recorded
+    pub storage_access_recorder: Option<StorageAccessRecorder>,
+    /// Snapshots taken with `snapshot_state`, indexed by their id, `None` once released
+    pub state_snapshots: Vec<Option<TakenSnapshot>>,
+    /// Time after which the test and the contracts it calls are stopped
+    pub deadline: Option<Instant>,
+    /// Set when a call to a contract was stopped because of the deadline
+    pub deadline_exceeded: bool,
+    /// Output printed by the test and the contracts it calls, `None` if it is printed as it is produced
+    pub captured_output: Option<CapturedOutput>,
 }
 
 impl Default for CheatnetState {
@@ -353,10 +408,18 @@ impl Default for CheatnetState {
             detected_messages_to_l1: vec![],
             deploy_salt_base: 0,
             block_info: SerializableBlockInfo::default().into(),
+            block_context_version: BlockContextVersion::default(),
             trace_data: TraceData {
                 current_call_stack: NotEmptyCallStack::from(test_call),
                 is_vm_trace_needed: false,
             },
+            fork_registry: None,
+            active_fixture: None,
+            storage_access_recorder: None,
+            state_snapshots: vec![],
+            deadline: None,
+            deadline_exceeded: false,
+            captured_output: None,
         }
     }
 }
@@ -459,6 +522,35 @@ impl CheatnetState {
     pub fn update_cheats(&mut self, address: &ContractAddress) {
         self.progress_cheated_execution_info(*address);
     }
+
+    /// Exchanges cheats of the currently selected fork with `scoped_cheats`.
+    pub fn swap_fork_scoped_cheats(&mut self, scoped_cheats: &mut ForkScopedCheats) {
+        std::mem::swap(
+            &mut self.cheated_execution_info_contracts,
+            &mut scoped_cheats.cheated_execution_info_contracts,
+        );
+        std::mem::swap(
+            &mut self.global_cheated_execution_info,
+            &mut scoped_cheats.global_cheated_execution_info,
+        );
+        std::mem::swap(
+            &mut self.mocked_functions,
+            &mut scoped_cheats.mocked_functions,
+        );
+        std::mem::swap(
+            &mut self.replaced_bytecode_contracts,
+            &mut scoped_cheats.replaced_bytecode_contracts,
+        );
+        std::mem::swap(
+            &mut self.detected_events,
+            &mut scoped_cheats.detected_events,
+        );
+        std::mem::swap(
+            &mut self.detected_messages_to_l1,
+            &mut scoped_cheats.detected_messages_to_l1,
+        );
+        std::mem::swap(&mut self.block_info, &mut scoped_cheats.block_info);
+    }
 }
 
 impl TraceData {
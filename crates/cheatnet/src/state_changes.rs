@@ -0,0 +1,40 @@
+use blockifier::state::cached_state::{StateChanges, StorageEntry};
+use cairo_vm::Felt252;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use std::collections::HashMap;
+use std::mem::ManuallyDrop;
+
+// copy-pasted blockifier::state::cached_state::StateChanges, its fields are private
+// link: https://github.com/starkware-libs/blockifier/blob/eb4958ad98d92dc8f8b493edc8dce1a79038c94d/crates/blockifier/src/state/cached_state.rs#L319
+/// Public view of [`StateChanges`], the only place where they are cast
+pub struct StateMaps {
+    pub nonces: HashMap<ContractAddress, Nonce>,
+    pub class_hashes: HashMap<ContractAddress, ClassHash>,
+    pub storage: HashMap<StorageEntry, Felt252>,
+    pub compiled_class_hashes: HashMap<ClassHash, CompiledClassHash>,
+    pub declared_contracts: HashMap<ClassHash, bool>,
+}
+
+// use to cast blockifier::state::cached_state::StateChanges into same struct but public for us
+union StateMapsHack {
+    origin: ManuallyDrop<StateChanges>,
+    public: ManuallyDrop<StateMaps>,
+}
+
+impl From<StateChanges> for StateMaps {
+    fn from(state_changes: StateChanges) -> Self {
+        let hack = StateMapsHack {
+            origin: ManuallyDrop::new(state_changes),
+        };
+        unsafe { ManuallyDrop::into_inner(hack.public) }
+    }
+}
+
+impl From<StateMaps> for StateChanges {
+    fn from(state_maps: StateMaps) -> Self {
+        let hack = StateMapsHack {
+            public: ManuallyDrop::new(state_maps),
+        };
+        unsafe { ManuallyDrop::into_inner(hack.origin) }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::state::{CheatnetState, ExtendedStateReader, ForkScopedCheats};
+use crate::state_changes::StateMaps;
+use blockifier::execution::contract_class::ContractClass;
+use blockifier::state::cached_state::CachedState;
+use blockifier::state::state_api::{State, StateReader, StateResult};
+use starknet_api::core::ClassHash;
+use std::collections::HashMap;
+
+/// State of the test at some point, which can be restored any number of times to run
+/// calls starting from the same baseline without deploying the contracts again.
+/// Only changes made to the state since the start of the test are kept, not the whole state.
+pub struct StateSnapshot {
+    changes: StateMaps,
+    /// Classes declared in the test, they are reference counted so copying them is cheap
+    declared_classes: HashMap<ClassHash, ContractClass>,
+    cheats: ForkScopedCheats,
+    deploy_salt_base: u32,
+}
+
+/// Takes a snapshot of cheats, mocks, spied events and messages and of the changes made to the
+/// non-forked state. Data read from the fork is not copied, it stays available after restoring
+/// as the fork is kept.
+pub fn snapshot(
+    cheatnet_state: &CheatnetState,
+    cached_state: &mut CachedState<ExtendedStateReader>,
+) -> StateResult<StateSnapshot> {
+    let changes = StateMaps::from(cached_state.get_actual_state_changes()?);
+
+    let declared_classes = changes
+        .compiled_class_hashes
+        .keys()
+        .chain(
+            changes
+                .declared_contracts
+                .iter()
+                .filter(|(_, declared)| **declared)
+                .map(|(class_hash, _)| class_hash),
+        )
+        .map(|class_hash| {
+            let contract_class = cached_state.get_compiled_contract_class(*class_hash)?;
+            Ok((*class_hash, contract_class))
+        })
+        .collect::<StateResult<_>>()?;
+
+    Ok(StateSnapshot {
+        changes,
+        declared_classes,
+        cheats: ForkScopedCheats {
+            cheated_execution_info_contracts: cheatnet_state
+                .cheated_execution_info_contracts
+                .clone(),
+            global_cheated_execution_info: cheatnet_state.global_cheated_execution_info.clone(),
+            mocked_functions: cheatnet_state.mocked_functions.clone(),
+            replaced_bytecode_contracts: cheatnet_state.replaced_bytecode_contracts.clone(),
+            detected_events: cheatnet_state.detected_events.clone(),
+            detected_messages_to_l1: cheatnet_state.detected_messages_to_l1.clone(),
+            block_info: cheatnet_state.block_info.clone(),
+        },
+        deploy_salt_base: cheatnet_state.deploy_salt_base,
+    })
+}
+
+/// Brings back the state from the snapshot, dropping all changes made after it was taken.
+/// Call trace and forks created in the test are not affected.
+pub fn restore(
+    snapshot: &StateSnapshot,
+    cheatnet_state: &mut CheatnetState,
+    cached_state: &mut CachedState<ExtendedStateReader>,
+) -> StateResult<()> {
+    restore_state(snapshot, cheatnet_state, cached_state)?;
+
+    let mut cheats = snapshot.cheats.clone();
+    cheatnet_state.swap_fork_scoped_cheats(&mut cheats);
+
+    Ok(())
+}
+
+/// Same as [`restore`], but cheats, mocks, spied events and messages are kept as they are
+pub fn restore_keeping_cheats(
+    snapshot: &StateSnapshot,
+    cheatnet_state: &mut CheatnetState,
+    cached_state: &mut CachedState<ExtendedStateReader>,
+) -> StateResult<()> {
+    restore_state(snapshot, cheatnet_state, cached_state)
+}
+
+fn restore_state(
+    snapshot: &StateSnapshot,
+    cheatnet_state: &mut CheatnetState,
+    cached_state: &mut CachedState<ExtendedStateReader>,
+) -> StateResult<()> {
+    cheatnet_state.deploy_salt_base = snapshot.deploy_salt_base;
+
+    // Changes are never written to the underlying reader, so it still holds the state
+    // from the start of the test and the snapshot can be replayed on top of it
+    let state_reader = ExtendedStateReader {
+        dict_state_reader: std::mem::take(&mut cached_state.state.dict_state_reader),
+        fork_state_reader: cached_state.state.fork_state_reader.take(),
+    };
+    *cached_state = CachedState::new(state_reader);
+
+    let changes = &snapshot.changes;
+    for ((contract_address, key), value) in &changes.storage {
+        cached_state.set_storage_at(*contract_address, *key, *value)?;
+    }
+    for (class_hash, contract_class) in &snapshot.declared_classes {
+        cached_state.set_contract_class(*class_hash, contract_class.clone())?;
+    }
+    for (class_hash, compiled_class_hash) in &changes.compiled_class_hashes {
+        cached_state.set_compiled_class_hash(*class_hash, *compiled_class_hash)?;
+    }
+    for (contract_address, class_hash) in &changes.class_hashes {
+        cached_state.set_class_hash_at(*contract_address, *class_hash)?;
+    }
+    // Nonces can only be incremented, they never decrease so the loop always ends
+    for (contract_address, nonce) in &changes.nonces {
+        while cached_state.get_nonce_at(*contract_address)? != *nonce {
+            cached_state.increment_nonce(*contract_address)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::state::CheatnetState;
+use cairo_vm::Felt252;
+use starknet_api::core::ContractAddress;
+use starknet_api::state::StorageKey;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageAccessKind {
+    Read,
+    Write,
+}
+
+/// Represents a single `storage_read` or `storage_write` syscall made by a Cairo 1 contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageAccess {
+    /// Address of the contract whose storage was accessed
+    pub contract_address: ContractAddress,
+    pub storage_key: StorageKey,
+    pub kind: StorageAccessKind,
+    /// Value read from the storage or written to it
+    pub value: Felt252,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct StorageAccessRecorder {
+    accesses: Vec<StorageAccess>,
+}
+
+impl StorageAccessRecorder {
+    pub(crate) fn record(&mut self, access: StorageAccess) {
+        self.accesses.push(access);
+    }
+}
+
+impl CheatnetState {
+    /// Starts recording storage reads and writes of all contract calls, in the order they happen.
+    /// Accesses recorded before are dropped.
+    pub fn start_recording_storage_accesses(&mut self) {
+        self.storage_access_recorder = Some(StorageAccessRecorder::default());
+    }
+
+    /// Stops recording and returns all accesses recorded since
+    /// [`CheatnetState::start_recording_storage_accesses`], empty if recording was not started
+    pub fn stop_recording_storage_accesses(&mut self) -> Vec<StorageAccess> {
+        self.storage_access_recorder
+            .take()
+            .map(|recorder| recorder.accesses)
+            .unwrap_or_default()
+    }
+
+    /// Returns accesses recorded so far and keeps recording
+    pub fn take_storage_accesses(&mut self) -> Vec<StorageAccess> {
+        self.storage_access_recorder
+            .as_mut()
+            .map(|recorder| std::mem::take(&mut recorder.accesses))
+            .unwrap_or_default()
+    }
+}
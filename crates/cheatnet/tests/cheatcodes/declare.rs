@@ -1,10 +1,19 @@
 use crate::common::assertions::ClassHashAssert;
-use crate::common::{get_contracts, state::create_cached_state};
+use crate::common::{
+    deploy_at_wrapper, get_contracts, get_contracts_artifacts, state::create_cached_state,
+};
+use blockifier::state::state_api::StateReader;
 use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::declare::{
-    declare, DeclareResult,
+    declare, get_declared_class_hash, DeclareResult,
 };
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::get_class_hash::get_class_hash;
 use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::CheatcodeError;
+use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
+use cheatnet::state::CheatnetState;
 use runtime::EnhancedHintError;
+use scarb_api::StarknetContractArtifacts;
+use serde_json::Value;
+use starknet_api::core::{ClassHash, ContractAddress};
 
 #[test]
 fn declare_simple() {
@@ -78,3 +87,147 @@ fn declare_non_existent() {
         _ => false,
     });
 }
+
+#[test]
+fn declare_unsupported_sierra_version() {
+    let contract_name = "HelloStarknet";
+
+    let mut contracts = get_contracts_artifacts();
+    let (artifacts, _) = contracts.get_mut(contract_name).unwrap();
+    let mut sierra: Value = serde_json::from_str(&artifacts.sierra).unwrap();
+    // Sierra version is encoded in the first three felts of the program
+    sierra["sierra_program"][0] = Value::from("0x3e7");
+    // CASM can't be compiled from Sierra of an unsupported version, so it must not be needed
+    *artifacts = StarknetContractArtifacts::from_sierra(sierra.to_string());
+
+    let contracts_data = ContractsData::try_from(contracts).unwrap();
+
+    let msg = contracts_data
+        .unsupported_sierra_versions_message(Some("2.9.1"))
+        .unwrap();
+    assert!(msg
+        .contains("Contract HelloStarknet was compiled to Sierra 999.0.0, which is not supported"));
+    assert!(msg.contains("Make sure the version of Scarb (currently Cairo 2.9.1)"));
+    assert!(!contracts_data
+        .get_artifacts(contract_name)
+        .unwrap()
+        .is_casm_compiled());
+
+    let mut cached_state = create_cached_state();
+    let output = declare(&mut cached_state, contract_name, &contracts_data);
+
+    assert!(match output {
+        Err(CheatcodeError::Unrecoverable(EnhancedHintError::Anyhow(msg))) => msg
+            .to_string()
+            .contains("Contract HelloStarknet was compiled to Sierra 999.0.0"),
+        _ => false,
+    });
+}
+
+#[test]
+fn get_declared_class_hash_does_not_declare() {
+    let contract_name = "HelloStarknet";
+
+    let mut cached_state = create_cached_state();
+
+    let contracts_data = get_contracts();
+
+    let class_hash =
+        get_declared_class_hash(&mut cached_state, contract_name, &contracts_data).unwrap();
+    assert_eq!(class_hash, None);
+
+    // Class is still undeclared, so declaring it succeeds
+    let class_hash = declare(&mut cached_state, contract_name, &contracts_data)
+        .unwrap()
+        .unwrap_success();
+
+    assert_eq!(
+        get_declared_class_hash(&mut cached_state, contract_name, &contracts_data).unwrap(),
+        Some(class_hash)
+    );
+}
+
+#[test]
+fn get_declared_class_hash_non_existent() {
+    let mut cached_state = create_cached_state();
+
+    let contracts_data = get_contracts();
+
+    let msg = get_declared_class_hash(&mut cached_state, "GoodbyeStarknet", &contracts_data)
+        .unwrap_err()
+        .to_string();
+
+    assert!(msg.contains("Failed") && msg.contains("GoodbyeStarknet"));
+}
+
+#[test]
+fn declare_shares_class_but_not_state() {
+    let contract_name = "HelloStarknet";
+    let contract_address = ContractAddress::from(1_u8);
+
+    let contracts_data = get_contracts();
+
+    let mut first_state = create_cached_state();
+    let mut second_state = create_cached_state();
+
+    for cached_state in [&mut first_state, &mut second_state] {
+        let class_hash = declare(cached_state, contract_name, &contracts_data)
+            .unwrap()
+            .unwrap_success();
+
+        assert_eq!(
+            cached_state
+                .get_compiled_contract_class(class_hash)
+                .unwrap(),
+            contracts_data
+                .get_contract_class(contract_name)
+                .unwrap()
+                .unwrap()
+        );
+    }
+
+    let class_hash = *contracts_data.get_class_hash(contract_name).unwrap();
+    deploy_at_wrapper(
+        &mut first_state,
+        &mut CheatnetState::default(),
+        &class_hash,
+        &[],
+        contract_address,
+    )
+    .unwrap();
+
+    assert_eq!(
+        get_class_hash(&mut first_state, contract_address).unwrap(),
+        class_hash
+    );
+    assert_eq!(
+        get_class_hash(&mut second_state, contract_address).unwrap(),
+        ClassHash::default()
+    );
+
+    // Address is still free in the second state
+    deploy_at_wrapper(
+        &mut second_state,
+        &mut CheatnetState::default(),
+        &class_hash,
+        &[],
+        contract_address,
+    )
+    .unwrap();
+}
+
+#[test]
+fn contract_class_is_parsed_when_declared() {
+    let contract_name = "HelloStarknet";
+    let contracts_data = get_contracts();
+
+    assert!(!contracts_data.is_contract_class_parsed(contract_name));
+
+    let mut cached_state = create_cached_state();
+    declare(&mut cached_state, contract_name, &contracts_data)
+        .unwrap()
+        .unwrap_success();
+
+    assert!(contracts_data.is_contract_class_parsed(contract_name));
+    assert!(!contracts_data.is_contract_class_parsed("ConstructorSimple"));
+}
@@ -17,7 +17,11 @@ mod load;
 mod mock_call;
 mod precalculate_address;
 mod replace_bytecode;
+mod set_balance;
+mod set_block_gas_prices;
 mod spy_events;
+mod state_snapshot;
+mod storage_accesses;
 mod store;
 
 pub fn map_entry_address(var_name: &str, key: &[Felt252]) -> Felt252 {
@@ -0,0 +1,143 @@
+use crate::common::assertions::assert_success;
+use crate::common::get_contracts;
+use blockifier::context::FeeTokenAddresses;
+use blockifier::state::state_api::StateReader;
+use cairo_vm::Felt252;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::set_balance::{
+    set_balance, Token,
+};
+use conversions::string::TryFromHexStr;
+use conversions::IntoConv;
+use runtime::starknet::context::{
+    build_block_context, ETH_CONTRACT_ADDRESS, STRK_CONTRACT_ADDRESS,
+};
+use starknet_api::core::ContractAddress;
+use test_case::test_case;
+
+use super::test_environment::TestEnvironment;
+
+trait SetBalanceTrait {
+    fn fee_token_addresses(&self) -> FeeTokenAddresses;
+    fn replace_fee_tokens(&mut self);
+    fn set_balance(&mut self, target: ContractAddress, low: u128, high: u128, token: Token);
+    fn fee_token_balance(&self, target: ContractAddress, token: Token) -> (Felt252, Felt252);
+}
+
+impl SetBalanceTrait for TestEnvironment {
+    fn fee_token_addresses(&self) -> FeeTokenAddresses {
+        build_block_context(
+            &self.cheatnet_state.block_info,
+            None,
+            self.cheatnet_state.block_context_version,
+        )
+        .chain_info()
+        .fee_token_addresses
+        .clone()
+    }
+
+    // Predeployed tokens have no code, they are replaced to be able to call `balance_of`
+    fn replace_fee_tokens(&mut self) {
+        let contracts_data = get_contracts();
+        let class_hash = self.declare("FeeToken", &contracts_data);
+        let fee_token_addresses = self.fee_token_addresses();
+
+        for token in [Token::Strk, Token::Eth] {
+            self.cheatnet_state.replace_class_for_contract(
+                token.contract_address(&fee_token_addresses),
+                class_hash,
+            );
+        }
+    }
+
+    fn set_balance(&mut self, target: ContractAddress, low: u128, high: u128, token: Token) {
+        let fee_token_addresses = self.fee_token_addresses();
+        set_balance(
+            &mut self.cached_state,
+            target,
+            Felt252::from(low),
+            Felt252::from(high),
+            token,
+            &fee_token_addresses,
+        )
+        .unwrap();
+    }
+
+    // Balance blockifier checks and charges the fees against
+    fn fee_token_balance(&self, target: ContractAddress, token: Token) -> (Felt252, Felt252) {
+        let fee_token_address = token.contract_address(&self.fee_token_addresses());
+
+        self.cached_state
+            .get_fee_token_balance(target, fee_token_address)
+            .unwrap()
+    }
+}
+
+#[test_case(Token::Strk; "strk")]
+#[test_case(Token::Eth; "eth")]
+fn set_balance_of_fee_token(token: Token) {
+    let mut test_env = TestEnvironment::new();
+    let account = ContractAddress::from(123_u16);
+
+    test_env.set_balance(account, 1000, 7, token);
+
+    assert_eq!(
+        test_env.fee_token_balance(account, token),
+        (Felt252::from(1000), Felt252::from(7))
+    );
+}
+
+#[test]
+fn fee_tokens_have_network_addresses() {
+    let test_env = TestEnvironment::new();
+    let fee_token_addresses = test_env.fee_token_addresses();
+
+    assert_eq!(
+        Token::Strk.contract_address(&fee_token_addresses),
+        ContractAddress::try_from_hex_str(STRK_CONTRACT_ADDRESS).unwrap()
+    );
+    assert_eq!(
+        Token::Eth.contract_address(&fee_token_addresses),
+        ContractAddress::try_from_hex_str(ETH_CONTRACT_ADDRESS).unwrap()
+    );
+}
+
+#[test]
+fn set_balance_changes_only_target_token() {
+    let mut test_env = TestEnvironment::new();
+    test_env.replace_fee_tokens();
+    let fee_token_addresses = test_env.fee_token_addresses();
+    let account = ContractAddress::from(123_u16);
+
+    test_env.set_balance(account, 500, 0, Token::Strk);
+
+    assert_success(
+        test_env.call_contract(
+            &Token::Strk.contract_address(&fee_token_addresses),
+            "balance_of",
+            &[account.into_()],
+        ),
+        &[Felt252::from(500), Felt252::from(0)],
+    );
+    assert_success(
+        test_env.call_contract(
+            &Token::Eth.contract_address(&fee_token_addresses),
+            "balance_of",
+            &[account.into_()],
+        ),
+        &[Felt252::from(0), Felt252::from(0)],
+    );
+}
+
+#[test]
+fn set_balance_changes_only_target_account() {
+    let mut test_env = TestEnvironment::new();
+    let account = ContractAddress::from(123_u16);
+    let other_account = ContractAddress::from(456_u16);
+
+    test_env.set_balance(account, 500, 0, Token::Strk);
+
+    assert_eq!(
+        test_env.fee_token_balance(other_account, Token::Strk),
+        (Felt252::from(0), Felt252::from(0))
+    );
+}
@@ -0,0 +1,73 @@
+use blockifier::blockifier::block::GasPrices;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::block_gas_prices::BlockGasPrices;
+use runtime::starknet::context::build_context;
+
+use super::test_environment::TestEnvironment;
+
+fn prices(gas_prices: &GasPrices) -> [u128; 4] {
+    [
+        gas_prices.eth_l1_gas_price.get(),
+        gas_prices.strk_l1_gas_price.get(),
+        gas_prices.eth_l1_data_gas_price.get(),
+        gas_prices.strk_l1_data_gas_price.get(),
+    ]
+}
+
+#[test]
+fn set_block_gas_prices_changes_block_context() {
+    let mut test_env = TestEnvironment::new();
+    let mut context = build_context(
+        &test_env.cheatnet_state.block_info,
+        None,
+        test_env.cheatnet_state.block_context_version,
+    );
+
+    test_env
+        .cheatnet_state
+        .set_block_gas_prices(
+            &mut context,
+            BlockGasPrices {
+                eth_l1_gas_price: 1,
+                strk_l1_gas_price: 2,
+                eth_l1_data_gas_price: 3,
+                strk_l1_data_gas_price: 4,
+            },
+        )
+        .unwrap();
+
+    assert_eq!(
+        prices(&test_env.cheatnet_state.block_info.gas_prices),
+        [1, 2, 3, 4]
+    );
+    assert_eq!(
+        prices(&context.tx_context.block_context.block_info().gas_prices),
+        [1, 2, 3, 4]
+    );
+}
+
+#[test]
+fn set_block_gas_prices_rejects_zero_price() {
+    let mut test_env = TestEnvironment::new();
+    let mut context = build_context(
+        &test_env.cheatnet_state.block_info,
+        None,
+        test_env.cheatnet_state.block_context_version,
+    );
+    let gas_prices_before = prices(&test_env.cheatnet_state.block_info.gas_prices);
+
+    let result = test_env.cheatnet_state.set_block_gas_prices(
+        &mut context,
+        BlockGasPrices {
+            eth_l1_gas_price: 1,
+            strk_l1_gas_price: 0,
+            eth_l1_data_gas_price: 1,
+            strk_l1_data_gas_price: 1,
+        },
+    );
+
+    assert!(result.is_err());
+    assert_eq!(
+        prices(&test_env.cheatnet_state.block_info.gas_prices),
+        gas_prices_before
+    );
+}
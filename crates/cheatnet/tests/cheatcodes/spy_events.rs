@@ -4,7 +4,9 @@ use crate::common::state::create_fork_cached_state_at;
 use crate::common::{call_contract, deploy_contract, felt_selector_from_name};
 use cairo_lang_starknet_classes::keccak::starknet_keccak;
 use cairo_vm::Felt252;
-use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::Event;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::spy_events::{
+    DecodedEvent, Event, SpiedEvent,
+};
 use cheatnet::state::CheatnetState;
 use conversions::string::TryFromHexStr;
 use conversions::IntoConv;
@@ -318,6 +320,68 @@ fn test_emitted_by_emit_events_syscall() {
     );
 }
 
+#[test]
+fn decode_spied_events() {
+    let mut test_env = TestEnvironment::new();
+
+    let contract_address = test_env.deploy("SpyEventsChecker", &[]);
+
+    test_env.call_contract(
+        &contract_address,
+        "emit_three_events",
+        &[
+            Felt252::from(123),
+            Felt252::from(234),
+            Felt252::from(345),
+            Felt252::from(0),
+        ],
+    );
+    test_env.call_contract(
+        &contract_address,
+        "emit_event_syscall",
+        &[Felt252::from(123), Felt252::from(456)],
+    );
+
+    let events =
+        test_env
+            .cheatnet_state
+            .get_decoded_events(0, &mut test_env.cached_state, &get_contracts());
+
+    let decoded = |name: &str, fields: &[(&str, &str)]| {
+        SpiedEvent::Decoded(DecodedEvent {
+            from: contract_address,
+            name: name.to_string(),
+            fields: fields
+                .iter()
+                .map(|(name, value)| ((*name).to_string(), (*value).to_string()))
+                .collect(),
+        })
+    };
+    assert_eq!(
+        events,
+        vec![
+            decoded("FirstEvent", &[("some_data", "0x7b")]),
+            decoded(
+                "SecondEvent",
+                &[("some_data", "0x7b"), ("some_more_data", "0xea")]
+            ),
+            decoded(
+                "ThirdEvent",
+                &[
+                    ("some_data", "0x7b"),
+                    ("some_more_data", "0xea"),
+                    ("even_more_data", "345_u256")
+                ]
+            ),
+            SpiedEvent::Raw(Event {
+                from: contract_address,
+                keys: vec![Felt252::from(123)],
+                data: vec![Felt252::from(456)]
+            }),
+        ]
+    );
+}
+
 #[test]
 fn capture_cairo0_event() {
     let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,50 @@
+use crate::common::assertions::assert_success;
+use crate::common::get_contracts;
+use cairo_vm::Felt252;
+use cheatnet::state_snapshot::{restore, snapshot};
+
+use super::test_environment::TestEnvironment;
+
+#[test]
+fn restore_drops_changes_made_after_snapshot() {
+    let mut test_env = TestEnvironment::new();
+    let contracts_data = get_contracts();
+
+    let contract_address = test_env.deploy("HelloStarknet", &[]);
+    test_env.call_contract(&contract_address, "increase_balance", &[Felt252::from(100)]);
+
+    let snapshot = snapshot(&test_env.cheatnet_state, &mut test_env.cached_state).unwrap();
+
+    // Declaring and deploying the same contract again fails if the previous restore did not work
+    for _ in 0..2 {
+        test_env.call_contract(&contract_address, "increase_balance", &[Felt252::from(50)]);
+        test_env
+            .cheatnet_state
+            .start_cheat_block_number(contract_address, 123);
+        let class_hash = test_env.declare("CheatBlockNumberChecker", &contracts_data);
+        test_env.deploy_wrapper(&class_hash, &[]);
+
+        assert_success(
+            test_env.call_contract(&contract_address, "get_balance", &[]),
+            &[Felt252::from(150)],
+        );
+
+        restore(
+            &snapshot,
+            &mut test_env.cheatnet_state,
+            &mut test_env.cached_state,
+        )
+        .unwrap();
+
+        assert_success(
+            test_env.call_contract(&contract_address, "get_balance", &[]),
+            &[Felt252::from(100)],
+        );
+        assert_eq!(
+            test_env
+                .cheatnet_state
+                .get_cheated_block_number(contract_address),
+            None
+        );
+    }
+}
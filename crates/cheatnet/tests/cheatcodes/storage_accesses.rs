@@ -0,0 +1,89 @@
+use crate::cheatcodes::variable_address;
+use crate::common::assertions::assert_success;
+use cairo_vm::Felt252;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::CallResult;
+use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::storage::storage_key;
+use cheatnet::storage_accesses::{StorageAccess, StorageAccessKind};
+
+use super::test_environment::TestEnvironment;
+
+#[test]
+fn records_reads_and_writes_in_order() {
+    let mut test_env = TestEnvironment::new();
+
+    let contract_address = test_env.deploy("HelloStarknet", &[]);
+    test_env.cheatnet_state.start_recording_storage_accesses();
+
+    test_env.call_contract(&contract_address, "increase_balance", &[Felt252::from(100)]);
+    assert_success(
+        test_env.call_contract(&contract_address, "get_balance", &[]),
+        &[Felt252::from(100)],
+    );
+
+    let storage_key = storage_key(variable_address("balance")).unwrap();
+    let access = |kind, value: u8| StorageAccess {
+        contract_address,
+        storage_key,
+        kind,
+        value: Felt252::from(value),
+    };
+    assert_eq!(
+        test_env.cheatnet_state.stop_recording_storage_accesses(),
+        vec![
+            access(StorageAccessKind::Read, 0),
+            access(StorageAccessKind::Write, 100),
+            access(StorageAccessKind::Read, 100),
+        ]
+    );
+}
+
+#[test]
+fn does_not_record_when_not_started() {
+    let mut test_env = TestEnvironment::new();
+
+    let contract_address = test_env.deploy("HelloStarknet", &[]);
+    test_env.call_contract(&contract_address, "increase_balance", &[Felt252::from(100)]);
+
+    assert!(test_env.cheatnet_state.storage_access_recorder.is_none());
+    assert_eq!(
+        test_env.cheatnet_state.stop_recording_storage_accesses(),
+        vec![]
+    );
+}
+
+#[test]
+fn take_keeps_recording() {
+    let mut test_env = TestEnvironment::new();
+
+    let contract_address = test_env.deploy("HelloStarknet", &[]);
+    test_env.cheatnet_state.start_recording_storage_accesses();
+
+    test_env.call_contract(&contract_address, "get_balance", &[]);
+    assert_eq!(test_env.cheatnet_state.take_storage_accesses().len(), 1);
+
+    test_env.call_contract(&contract_address, "increase_balance", &[Felt252::from(5)]);
+    let accesses = test_env.cheatnet_state.take_storage_accesses();
+    assert_eq!(
+        accesses
+            .iter()
+            .map(|access| access.kind)
+            .collect::<Vec<_>>(),
+        vec![StorageAccessKind::Read, StorageAccessKind::Write]
+    );
+}
+
+#[test]
+fn does_not_record_failed_accesses() {
+    let mut test_env = TestEnvironment::new();
+
+    let contract_address = test_env.deploy("StorageAccessFailure", &[]);
+    test_env.cheatnet_state.start_recording_storage_accesses();
+
+    let output = test_env.call_contract(&contract_address, "read_unsupported_domain", &[]);
+
+    assert!(matches!(output, CallResult::Failure(_)));
+    assert_eq!(
+        test_env.cheatnet_state.stop_recording_storage_accesses(),
+        vec![]
+    );
+}
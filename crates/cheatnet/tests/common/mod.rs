@@ -7,6 +7,7 @@ use cairo_lang_casm::hints::Hint;
 use cairo_vm::types::relocatable::Relocatable;
 use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
 use cairo_vm::Felt252;
+use camino::Utf8PathBuf;
 use cheatnet::constants::TEST_ADDRESS;
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::{
     call_entry_point, AddressOrClassHash,
@@ -26,7 +27,9 @@ use conversions::string::TryFromHexStr;
 use conversions::IntoConv;
 use runtime::starknet::context::build_context;
 use scarb_api::metadata::MetadataCommandExt;
-use scarb_api::{get_contracts_artifacts_and_source_sierra_paths, ScarbCommand};
+use scarb_api::{
+    get_contracts_artifacts_and_source_sierra_paths, ScarbCommand, StarknetContractArtifacts,
+};
 use starknet::core::utils::get_selector_from_name;
 use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector};
 use starknet_api::deprecated_contract_class::EntryPointType;
@@ -67,6 +70,10 @@ pub fn recover_data(output: CallResult) -> Vec<Felt252> {
 }
 
 pub fn get_contracts() -> ContractsData {
+    ContractsData::try_from(get_contracts_artifacts()).unwrap()
+}
+
+pub fn get_contracts_artifacts() -> HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)> {
     let scarb_metadata = ScarbCommand::metadata()
         .inherit_stderr()
         .manifest_path("tests/contracts/Scarb.toml")
@@ -75,10 +82,8 @@ pub fn get_contracts() -> ContractsData {
 
     let package = scarb_metadata.packages.first().unwrap();
 
-    let contracts =
-        get_contracts_artifacts_and_source_sierra_paths(&scarb_metadata, &package.id, None, false)
-            .unwrap();
-    ContractsData::try_from(contracts).unwrap()
+    get_contracts_artifacts_and_source_sierra_paths(&scarb_metadata, &package.id, None, false)
+        .unwrap()
 }
 
 pub fn deploy_contract(
@@ -94,7 +99,11 @@ pub fn deploy_contract(
         .unwrap_success();
 
     let mut execution_resources = ExecutionResources::default();
-    let mut entry_point_execution_context = build_context(&cheatnet_state.block_info, None);
+    let mut entry_point_execution_context = build_context(
+        &cheatnet_state.block_info,
+        None,
+        cheatnet_state.block_context_version,
+    );
     let hints = HashMap::new();
 
     let mut syscall_hint_processor = build_syscall_hint_processor(
@@ -123,7 +132,11 @@ pub fn deploy_wrapper(
     calldata: &[Felt252],
 ) -> Result<ContractAddress, CheatcodeError> {
     let mut execution_resources = ExecutionResources::default();
-    let mut entry_point_execution_context = build_context(&cheatnet_state.block_info, None);
+    let mut entry_point_execution_context = build_context(
+        &cheatnet_state.block_info,
+        None,
+        cheatnet_state.block_context_version,
+    );
     let hints = HashMap::new();
 
     let mut syscall_hint_processor = build_syscall_hint_processor(
@@ -152,7 +165,11 @@ pub fn deploy_at_wrapper(
     contract_address: ContractAddress,
 ) -> Result<ContractAddress, CheatcodeError> {
     let mut execution_resources = ExecutionResources::default();
-    let mut entry_point_execution_context = build_context(&cheatnet_state.block_info, None);
+    let mut entry_point_execution_context = build_context(
+        &cheatnet_state.block_info,
+        None,
+        cheatnet_state.block_context_version,
+    );
     let hints = HashMap::new();
 
     let mut syscall_hint_processor = build_syscall_hint_processor(
@@ -198,7 +215,11 @@ pub fn call_contract(
     };
 
     let mut execution_resources = ExecutionResources::default();
-    let mut entry_point_execution_context = build_context(&cheatnet_state.block_info, None);
+    let mut entry_point_execution_context = build_context(
+        &cheatnet_state.block_info,
+        None,
+        cheatnet_state.block_context_version,
+    );
     let hints = HashMap::new();
 
     let mut syscall_hint_processor = build_syscall_hint_processor(
@@ -1,5 +1,5 @@
 use blockifier::state::cached_state::CachedState;
-use cheatnet::constants::build_testing_state;
+use cheatnet::constants::{build_forked_testing_state, build_testing_state};
 use cheatnet::forking::state::ForkStateReader;
 use cheatnet::state::ExtendedStateReader;
 use shared::test_utils::node_url::node_rpc_url;
@@ -22,7 +22,7 @@ pub fn create_fork_cached_state_at(
 ) -> CachedState<ExtendedStateReader> {
     let node_url = node_rpc_url();
     CachedState::new(ExtendedStateReader {
-        dict_state_reader: build_testing_state(),
+        dict_state_reader: build_forked_testing_state(),
         fork_state_reader: Some(
             ForkStateReader::new(node_url, BlockNumber(block_number), cache_dir.into()).unwrap(),
         ),
@@ -0,0 +1 @@
+mod fee_token;
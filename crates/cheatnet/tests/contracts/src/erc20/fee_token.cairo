@@ -0,0 +1,24 @@
+use starknet::ContractAddress;
+
+#[starknet::interface]
+trait IFeeToken<TContractState> {
+    fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+}
+
+// Uses the same storage layout for balances as the `OpenZeppelin` ERC20 component
+#[starknet::contract]
+mod FeeToken {
+    use starknet::ContractAddress;
+
+    #[storage]
+    struct Storage {
+        ERC20_balances: LegacyMap<ContractAddress, u256>,
+    }
+
+    #[abi(embed_v0)]
+    impl FeeTokenImpl of super::IFeeToken<ContractState> {
+        fn balance_of(self: @ContractState, account: ContractAddress) -> u256 {
+            self.ERC20_balances.read(account)
+        }
+    }
+}
@@ -1 +1,2 @@
 mod map_simple_value_simple_key;
+mod storage_access_failure;
@@ -0,0 +1,13 @@
+#[starknet::contract]
+mod StorageAccessFailure {
+    use starknet::{storage_read_syscall, storage_address_try_from_felt252, SyscallResultTrait};
+
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn read_unsupported_domain(self: @ContractState) -> felt252 {
+        let address = storage_address_try_from_felt252(1).unwrap();
+        storage_read_syscall(1, address).unwrap_syscall()
+    }
+}
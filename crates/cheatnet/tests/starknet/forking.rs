@@ -6,7 +6,8 @@ use blockifier::state::cached_state::CachedState;
 use cairo_vm::vm::errors::hint_errors::HintError;
 use cairo_vm::Felt252;
 use camino::Utf8Path;
-use cheatnet::constants::build_testing_state;
+use cheatnet::constants::build_forked_testing_state;
+use cheatnet::forking::multi_fork::ForkRegistry;
 use cheatnet::forking::{cache::CACHE_VERSION, state::ForkStateReader};
 use cheatnet::runtime_extensions::forge_runtime_extension::cheatcodes::CheatcodeError;
 use cheatnet::state::{BlockInfoReader, CheatnetState, ExtendedStateReader};
@@ -16,8 +17,11 @@ use conversions::IntoConv;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use runtime::EnhancedHintError;
 use serde_json::Value;
+use shared::test_utils::node_url::node_rpc_url;
+use starknet::core::types::BlockId;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ContractAddress;
+use std::rc::Rc;
 use tempfile::TempDir;
 
 #[test]
@@ -148,6 +152,102 @@ fn test_forking_at_block_number() {
     purge_cache(cache_dir.path().to_str().unwrap());
 }
 
+#[test]
+fn fork_state_reader_at_other_block() {
+    let cache_dir = TempDir::new().unwrap();
+    let fork_state_reader = ForkStateReader::new(
+        node_rpc_url(),
+        BlockNumber(53_681),
+        Utf8Path::from_path(cache_dir.path()).unwrap(),
+    )
+    .unwrap();
+    let mut cheatnet_state = CheatnetState::default();
+
+    let contract_address = ContractAddress::try_from_hex_str(
+        "0x202de98471a4fae6bcbabb96cab00437d381abc58b02509043778074d6781e9",
+    )
+    .unwrap();
+    let selector = felt_selector_from_name("get_balance");
+
+    let mut cached_state_before_deploy = CachedState::new(ExtendedStateReader {
+        dict_state_reader: build_forked_testing_state(),
+        fork_state_reader: Some(fork_state_reader.at_block(BlockId::Number(50_000)).unwrap()),
+    });
+    let output = call_contract(
+        &mut cached_state_before_deploy,
+        &mut cheatnet_state,
+        &contract_address,
+        selector,
+        &[],
+    );
+
+    let msg = "Contract not deployed at address: 0x202de98471a4fae6bcbabb96cab00437d381abc58b02509043778074d6781e9";
+    let panic_data_felts: Vec<Felt252> = ByteArray::from(msg).serialize_with_magic();
+    assert_panic(output, &panic_data_felts);
+
+    let mut cached_state = CachedState::new(ExtendedStateReader {
+        dict_state_reader: build_forked_testing_state(),
+        fork_state_reader: Some(fork_state_reader),
+    });
+    let output = call_contract(
+        &mut cached_state,
+        &mut cheatnet_state,
+        &contract_address,
+        selector,
+        &[],
+    );
+
+    assert_success(output, &[Felt252::from(0)]);
+}
+
+#[test]
+fn fork_state_reader_at_block_not_served_by_node() {
+    let cache_dir = TempDir::new().unwrap();
+    let fork_state_reader = ForkStateReader::new(
+        node_rpc_url(),
+        BlockNumber(54_060),
+        Utf8Path::from_path(cache_dir.path()).unwrap(),
+    )
+    .unwrap();
+
+    let msg = fork_state_reader
+        .at_block(BlockId::Number(999_999_999))
+        .unwrap_err()
+        .to_string();
+
+    assert!(msg.starts_with("Node at "), "{msg}");
+    assert!(msg.contains("can't serve block Number(999999999)"), "{msg}");
+}
+
+#[test]
+fn fork_state_at_block_is_created_once_per_block() {
+    let cache_dir = TempDir::new().unwrap();
+    let cache_dir = Utf8Path::from_path(cache_dir.path()).unwrap();
+    let fork_state_reader =
+        ForkStateReader::new(node_rpc_url(), BlockNumber(53_681), cache_dir).unwrap();
+    let (mut registry, _) = ForkRegistry::new(
+        ExtendedStateReader {
+            dict_state_reader: build_forked_testing_state(),
+            fork_state_reader: Some(fork_state_reader),
+        },
+        cache_dir.to_owned(),
+        None,
+    )
+    .unwrap();
+
+    let pinned = registry.active_state_at_block(None).unwrap();
+    let pinned_by_number = registry
+        .active_state_at_block(Some(BlockId::Number(53_681)))
+        .unwrap();
+    let other = registry
+        .active_state_at_block(Some(BlockId::Number(50_000)))
+        .unwrap();
+
+    assert!(Rc::ptr_eq(&pinned, &pinned_by_number));
+    assert!(!Rc::ptr_eq(&pinned, &other));
+    assert_eq!(other.borrow().block_info.block_number, BlockNumber(50_000));
+}
+
 #[test]
 fn call_forked_contract_from_other_contract() {
     let cache_dir = TempDir::new().unwrap();
@@ -644,7 +744,7 @@ fn test_calling_nonexistent_url() {
     let temp_dir = TempDir::new().unwrap();
     let nonexistent_url = "http://nonexistent-node-address.com".parse().unwrap();
     let mut cached_fork_state = CachedState::new(ExtendedStateReader {
-        dict_state_reader: build_testing_state(),
+        dict_state_reader: build_forked_testing_state(),
         fork_state_reader: Some(
             ForkStateReader::new(
                 nonexistent_url,
@@ -0,0 +1,236 @@
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use std::{env, fs};
+
+use crate::CONFIG_FILENAME;
+
+pub const ENV_FILENAME: &str = ".env";
+
+/// Directory of the nearest `Scarb.toml` or `snfoundry.toml`, searched upwards from `current_dir`.
+/// Members of a workspace use the root of the workspace, the nearest directory above with `Scarb.toml`
+/// defining `[workspace]`
+#[must_use]
+pub fn find_project_root(current_dir: &Utf8Path) -> Option<&Utf8Path> {
+    let nearest_root = current_dir
+        .ancestors()
+        .find(|path| path.join("Scarb.toml").is_file() || path.join(CONFIG_FILENAME).is_file())?;
+
+    Some(
+        nearest_root
+            .ancestors()
+            .find(|path| is_workspace_root(path))
+            .unwrap_or(nearest_root),
+    )
+}
+
+fn is_workspace_root(path: &Utf8Path) -> bool {
+    fs::read_to_string(path.join("Scarb.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .is_some_and(|manifest| manifest.contains_key("workspace"))
+}
+
+/// Sets variables from the `.env` file in the project root, variables already set in the
+/// environment take precedence over the ones from the file.
+/// Returns the path of the loaded file, `None` if there is no such file
+pub fn load_env_file(current_dir: &Utf8Path) -> Result<Option<Utf8PathBuf>> {
+    let Some((path, variables)) = read_env_file(current_dir, |name| env::var_os(name).is_some())?
+    else {
+        return Ok(None);
+    };
+
+    for (name, value) in variables {
+        env::set_var(name, value);
+    }
+
+    Ok(Some(path))
+}
+
+/// Reads the `.env` file in the project root, without the variables for which `is_set` returns true
+fn read_env_file(
+    current_dir: &Utf8Path,
+    is_set: impl Fn(&str) -> bool,
+) -> Result<Option<(Utf8PathBuf, Vec<(String, String)>)>> {
+    let Some(path) = find_project_root(current_dir)
+        .map(|root| root.join(ENV_FILENAME))
+        .filter(|path| path.is_file())
+    else {
+        return Ok(None);
+    };
+
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?;
+    let variables = parse_env_file(&content)
+        .with_context(|| format!("Failed to parse {path}"))?
+        .into_iter()
+        .filter(|(name, _)| !is_set(name))
+        .collect();
+
+    Ok(Some((path, variables)))
+}
+
+/// Parses `NAME=value` lines, optionally prefixed with `export`, skipping empty lines and comments.
+/// Values can be wrapped in single or double quotes, unquoted values end before ` #`
+fn parse_env_file(content: &str) -> Result<Vec<(String, String)>> {
+    let mut variables = vec![];
+    let mut errors = vec![];
+
+    for (index, line) in content.lines().enumerate() {
+        match parse_line(line) {
+            Ok(Some(variable)) => variables.push(variable),
+            Ok(None) => {}
+            Err(error) => errors.push(format!("line {}: {error}", index + 1)),
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("{}", errors.join("\n"));
+    }
+
+    Ok(variables)
+}
+
+fn parse_line(line: &str) -> Result<Option<(String, String)>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let line = line.strip_prefix("export ").map_or(line, str::trim_start);
+
+    let Some((name, value)) = line.split_once('=') else {
+        bail!("expected `NAME=value`, found `{line}`");
+    };
+    let name = name.trim();
+    if !is_valid_name(name) {
+        bail!("invalid variable name `{name}`, only letters, digits and `_` are allowed and it cannot start with a digit");
+    }
+
+    Ok(Some((name.to_string(), parse_value(value.trim())?)))
+}
+
+fn is_valid_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+fn parse_value(value: &str) -> Result<String> {
+    let Some(quote) = value
+        .chars()
+        .next()
+        .filter(|char| ['"', '\''].contains(char))
+    else {
+        let value = value
+            .split_once(" #")
+            .map_or(value, |(value, _comment)| value);
+        return Ok(value.trim_end().to_string());
+    };
+
+    let Some((quoted, rest)) = value[1..].split_once(quote) else {
+        bail!("missing closing {quote} in `{value}`");
+    };
+    let rest = rest.trim_start();
+    if !(rest.is_empty() || rest.starts_with('#')) {
+        bail!("unexpected `{rest}` after the quoted value");
+    }
+
+    Ok(quoted.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn parse_lines() {
+        let variables = parse_env_file(indoc! {r#"
+            # RPC nodes
+            SNFORGE_FORK_MAINNET_URL=http://127.0.0.1:5050/rpc
+
+            export ACCOUNT_PASSWORD = "secret # not a comment"
+            NETWORK='sepolia' # comment
+            EMPTY=
+            NODE_URL=http://localhost # comment
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            variables,
+            [
+                ("SNFORGE_FORK_MAINNET_URL", "http://127.0.0.1:5050/rpc"),
+                ("ACCOUNT_PASSWORD", "secret # not a comment"),
+                ("NETWORK", "sepolia"),
+                ("EMPTY", ""),
+                ("NODE_URL", "http://localhost"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn all_malformed_lines_are_reported() {
+        let error = parse_env_file(indoc! {r#"
+            VALID=1
+            MISSING_EQUALS
+            1NAME=value
+            UNCLOSED="value
+        "#})
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            indoc! {r#"
+                line 2: expected `NAME=value`, found `MISSING_EQUALS`
+                line 3: invalid variable name `1NAME`, only letters, digits and `_` are allowed and it cannot start with a digit
+                line 4: missing closing " in `"value`"#}
+        );
+    }
+
+    #[test]
+    fn environment_takes_precedence() {
+        let temp = tempdir().unwrap();
+        let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        fs::write(root.join(CONFIG_FILENAME), "").unwrap();
+        fs::write(root.join(ENV_FILENAME), "ONLY_IN_FILE=file\nIN_ENV=file\n").unwrap();
+        fs::create_dir(root.join("childdir")).unwrap();
+
+        let (path, variables) = read_env_file(&root.join("childdir"), |name| name == "IN_ENV")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(path, root.join(ENV_FILENAME));
+        assert_eq!(
+            variables,
+            [("ONLY_IN_FILE".to_string(), "file".to_string())]
+        );
+    }
+
+    #[test]
+    fn workspace_member_uses_workspace_root() {
+        let temp = tempdir().unwrap();
+        let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        let member = root.join("crates").join("member");
+        fs::create_dir_all(member.join("src")).unwrap();
+        fs::write(
+            root.join("Scarb.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        )
+        .unwrap();
+        fs::write(member.join("Scarb.toml"), "[package]\nname = \"member\"\n").unwrap();
+
+        assert_eq!(find_project_root(&member.join("src")), Some(root.as_path()));
+    }
+
+    #[test]
+    fn no_env_file() {
+        let temp = tempdir().unwrap();
+        let root = Utf8PathBuf::try_from(temp.path().to_path_buf()).unwrap();
+        fs::write(root.join("Scarb.toml"), "").unwrap();
+
+        assert_eq!(load_env_file(&root).unwrap(), None);
+    }
+}
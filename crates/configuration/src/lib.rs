@@ -6,6 +6,9 @@ use std::{env, fs};
 use camino::Utf8PathBuf;
 use tempfile::{tempdir, TempDir};
 use toml::Value;
+
+pub mod env_file;
+
 pub const CONFIG_FILENAME: &str = "snfoundry.toml";
 
 /// Defined in snfoundry.toml
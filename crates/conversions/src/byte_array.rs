@@ -1,10 +1,10 @@
 use crate as conversions; // trick for CairoDeserialize macro
 use crate::serde::deserialize::{BufferReadError, BufferReadResult, BufferReader};
 use crate::{serde::serialize::SerializeToFeltVec, string::TryFromHexStr};
-use cairo_lang_runner::short_string::as_cairo_short_string_ex;
 use cairo_lang_utils::byte_array::{BYTES_IN_WORD, BYTE_ARRAY_MAGIC};
 use cairo_serde_macros::{CairoDeserialize, CairoSerialize};
 use starknet_types_core::felt::Felt as Felt252;
+use thiserror::Error;
 
 #[derive(CairoDeserialize, CairoSerialize, Clone, Debug, PartialEq)]
 pub struct ByteArray {
@@ -13,6 +13,20 @@ pub struct ByteArray {
     pending_word_len: usize,
 }
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ByteArrayError {
+    #[error("Felts do not form a serialized ByteArray")]
+    Malformed,
+    #[error("Serialized ByteArray is followed by {0} unexpected felts")]
+    TrailingFelts(usize),
+    #[error("Pending word length {0} of ByteArray must be smaller than {BYTES_IN_WORD}")]
+    PendingWordTooLong(usize),
+    #[error("Word {word:#x} of ByteArray does not fit in {len} bytes")]
+    WordOutOfRange { word: Felt252, len: usize },
+    #[error("ByteArray is not a valid UTF-8 string")]
+    InvalidUtf8,
+}
+
 impl From<&str> for ByteArray {
     fn from(value: &str) -> Self {
         let chunks = value.as_bytes().chunks_exact(BYTES_IN_WORD);
@@ -52,19 +66,63 @@ impl ByteArray {
             Err(BufferReadError::ParseFailed)
         }
     }
+
+    fn to_bytes(&self) -> Result<Vec<u8>, ByteArrayError> {
+        if self.pending_word_len >= BYTES_IN_WORD {
+            return Err(ByteArrayError::PendingWordTooLong(self.pending_word_len));
+        }
+
+        let mut bytes =
+            Vec::with_capacity(self.words.len() * BYTES_IN_WORD + self.pending_word_len);
+        for word in &self.words {
+            bytes.extend_from_slice(&word_bytes(word, BYTES_IN_WORD)?);
+        }
+        bytes.extend_from_slice(&word_bytes(&self.pending_word, self.pending_word_len)?);
+
+        Ok(bytes)
+    }
+}
+
+fn word_bytes(word: &Felt252, len: usize) -> Result<Vec<u8>, ByteArrayError> {
+    let bytes = word.to_bytes_be();
+    let (overflow, word_bytes) = bytes.split_at(bytes.len() - len);
+
+    if overflow.iter().any(|byte| *byte != 0) {
+        return Err(ByteArrayError::WordOutOfRange { word: *word, len });
+    }
+
+    Ok(word_bytes.to_vec())
+}
+
+/// Encodes `value` as felts of a serialized Cairo `ByteArray`, as used by string arguments
+/// and, prefixed with [`BYTE_ARRAY_MAGIC`], by panic data
+#[must_use]
+pub fn to_byte_array(value: &str) -> Vec<Felt252> {
+    ByteArray::from(value).serialize_to_vec()
+}
+
+/// Decodes a string from felts of a serialized Cairo `ByteArray`, all of the felts must be used
+pub fn from_byte_array(felts: &[Felt252]) -> Result<String, ByteArrayError> {
+    let byte_array: ByteArray = BufferReader::new(felts)
+        .read()
+        .map_err(|_| ByteArrayError::Malformed)?;
+
+    // Length of the words, the words, pending word and its length
+    let used_felts = byte_array.words.len() + 3;
+    if felts.len() > used_felts {
+        return Err(ByteArrayError::TrailingFelts(felts.len() - used_felts));
+    }
+
+    String::from_utf8(byte_array.to_bytes()?).map_err(|_| ByteArrayError::InvalidUtf8)
 }
 
 impl From<ByteArray> for String {
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`, use [`from_byte_array`] to reject them
     fn from(value: ByteArray) -> Self {
-        let full_words_string = value
-            .words
-            .iter()
-            .map(|word| as_cairo_short_string_ex(word, BYTES_IN_WORD).unwrap())
-            .collect::<String>();
-
-        let pending_word_string =
-            as_cairo_short_string_ex(&value.pending_word, value.pending_word_len).unwrap();
+        let bytes = value
+            .to_bytes()
+            .unwrap_or_else(|err| panic!("{err}, ByteArray words must be bytes31"));
 
-        format!("{full_words_string}{pending_word_string}")
+        String::from_utf8_lossy(&bytes).into_owned()
     }
 }
@@ -1,11 +1,14 @@
+use crate::felt252::TryToStarknetType;
 use crate::{from_thru_felt252, FromConv};
-use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce, PatriciaKey};
-use starknet_api::hash::StarkHash;
+use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce};
 use starknet_types_core::felt::Felt as Felt252;
 
 impl FromConv<Felt252> for ContractAddress {
+    /// Panics if the felt is out of range, use [`TryToStarknetType::try_to_contract_address`] for untrusted values
     fn from_(value: Felt252) -> ContractAddress {
-        ContractAddress(PatriciaKey::try_from(StarkHash::from_(value)).unwrap())
+        value
+            .try_to_contract_address()
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
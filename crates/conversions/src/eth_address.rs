@@ -1,10 +1,14 @@
+use crate::felt252::TryToStarknetType;
 use crate::FromConv;
 use starknet_api::core::EthAddress;
 use starknet_types_core::felt::Felt as Felt252;
 
 impl FromConv<Felt252> for EthAddress {
+    /// Panics if the felt is out of range, use [`TryToStarknetType::try_to_eth_address`] for untrusted values
     fn from_(value: Felt252) -> EthAddress {
-        EthAddress::try_from(value).expect("Conversion of felt252 to EthAddress failed")
+        value
+            .try_to_eth_address()
+            .unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
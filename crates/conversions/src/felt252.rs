@@ -4,9 +4,12 @@ use crate::{
     string::{TryFromDecStr, TryFromHexStr},
     FromConv, IntoConv,
 };
-use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce};
+use starknet_api::core::{
+    ClassHash, ContractAddress, EntryPointSelector, EthAddress, Nonce, PatriciaKey,
+};
 use starknet_types_core::felt::{Felt as Felt252, FromStrError};
 use std::vec;
+use thiserror::Error;
 
 impl FromConv<ClassHash> for Felt252 {
     fn from_(value: ClassHash) -> Felt252 {
@@ -72,6 +75,33 @@ impl FromShortString<Felt252> for Felt252 {
     }
 }
 
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FeltConversionError {
+    #[error("Felt {0:#x} is out of range of a contract address, it must be smaller than 2^251")]
+    ContractAddressOutOfRange(Felt252),
+    #[error("Felt {0:#x} is out of range of an Ethereum address, it must fit in 160 bits")]
+    EthAddressOutOfRange(Felt252),
+}
+
+/// Fallible conversions of felts to types which do not cover the whole field
+pub trait TryToStarknetType {
+    fn try_to_contract_address(self) -> Result<ContractAddress, FeltConversionError>;
+
+    fn try_to_eth_address(self) -> Result<EthAddress, FeltConversionError>;
+}
+
+impl TryToStarknetType for Felt252 {
+    fn try_to_contract_address(self) -> Result<ContractAddress, FeltConversionError> {
+        PatriciaKey::try_from(self)
+            .map(ContractAddress)
+            .map_err(|_| FeltConversionError::ContractAddressOutOfRange(self))
+    }
+
+    fn try_to_eth_address(self) -> Result<EthAddress, FeltConversionError> {
+        EthAddress::try_from(self).map_err(|_| FeltConversionError::EthAddressOutOfRange(self))
+    }
+}
+
 pub trait TryInferFormat: Sized {
     /// Parses value from `hex string`, `dec string`, `quotted cairo shortstring `and `quotted cairo string`
     fn infer_format_and_parse(value: &str) -> Result<Vec<Self>, FromStrError>;
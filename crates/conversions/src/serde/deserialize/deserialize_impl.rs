@@ -1,5 +1,5 @@
 use super::{BufferReadError, BufferReadResult, BufferReader, CairoDeserialize};
-use crate::{byte_array::ByteArray, IntoConv};
+use crate::{byte_array::ByteArray, felt252::TryToStarknetType, IntoConv};
 use num_traits::cast::ToPrimitive;
 use starknet::providers::Url;
 use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce};
@@ -96,10 +96,17 @@ macro_rules! impl_deserialize_for_num_type {
 }
 
 impl_deserialize_for_felt_type!(ClassHash);
-impl_deserialize_for_felt_type!(ContractAddress);
 impl_deserialize_for_felt_type!(Nonce);
 impl_deserialize_for_felt_type!(EntryPointSelector);
 
+impl CairoDeserialize for ContractAddress {
+    fn deserialize(reader: &mut BufferReader<'_>) -> BufferReadResult<Self> {
+        Felt252::deserialize(reader)?
+            .try_to_contract_address()
+            .map_err(|_| BufferReadError::ParseFailed)
+    }
+}
+
 impl_deserialize_for_num_type!(u8);
 impl_deserialize_for_num_type!(u16);
 impl_deserialize_for_num_type!(u32);
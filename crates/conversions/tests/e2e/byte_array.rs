@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests_byte_array {
+    use cairo_lang_utils::byte_array::BYTES_IN_WORD;
+    use conversions::byte_array::{from_byte_array, to_byte_array, ByteArray, ByteArrayError};
+    use conversions::serde::deserialize::BufferReader;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use starknet_types_core::felt::Felt as Felt252;
+    use test_case::test_case;
+
+    // 1, 2, 3 and 4 byte long UTF-8 characters
+    const CHARS: [char; 8] = ['a', 'Z', '7', ' ', 'ż', 'ł', '€', '🦀'];
+
+    fn random_string(rng: &mut StdRng, len: usize) -> String {
+        (0..len)
+            .map(|_| CHARS[rng.gen_range(0..CHARS.len())])
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_random_strings() {
+        let mut rng = StdRng::seed_from_u64(2137);
+
+        for _ in 0..500 {
+            let len = rng.gen_range(0..100);
+            let value = random_string(&mut rng, len);
+
+            let felts = to_byte_array(&value);
+
+            assert_eq!(from_byte_array(&felts).unwrap(), value);
+            assert_eq!(
+                String::from(BufferReader::new(&felts).read::<ByteArray>().unwrap()),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_strings_around_word_boundary() {
+        let mut rng = StdRng::seed_from_u64(420);
+
+        for bytes_len in [
+            BYTES_IN_WORD - 1,
+            BYTES_IN_WORD,
+            BYTES_IN_WORD + 1,
+            2 * BYTES_IN_WORD - 1,
+            2 * BYTES_IN_WORD,
+            2 * BYTES_IN_WORD + 1,
+        ] {
+            for _ in 0..50 {
+                // Multi-byte characters straddling the boundary are split between two words
+                let mut value = String::new();
+                while value.len() < bytes_len {
+                    let char = CHARS[rng.gen_range(0..CHARS.len())];
+                    if value.len() + char.len_utf8() <= bytes_len {
+                        value.push(char);
+                    } else {
+                        value.push('a');
+                    }
+                }
+
+                let felts = to_byte_array(&value);
+
+                assert_eq!(felts[0], Felt252::from(bytes_len / BYTES_IN_WORD));
+                assert_eq!(
+                    felts.last(),
+                    Some(&Felt252::from(bytes_len % BYTES_IN_WORD))
+                );
+                assert_eq!(from_byte_array(&felts).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn encodes_multi_byte_characters_as_bytes() {
+        // "ż" is encoded as 0xc5 0xbc
+        assert_eq!(
+            to_byte_array("aż"),
+            vec![
+                Felt252::from(0),
+                Felt252::from(0x61_c5bc_u32),
+                Felt252::from(3)
+            ]
+        );
+    }
+
+    #[test_case(&[], ByteArrayError::Malformed ; "empty")]
+    #[test_case(&[1, 0, 0], ByteArrayError::Malformed ; "missing_word")]
+    #[test_case(&[0, 0x61, 1, 0], ByteArrayError::TrailingFelts(1) ; "trailing_felts")]
+    #[test_case(&[0, 0, 31], ByteArrayError::PendingWordTooLong(31) ; "pending_word_too_long")]
+    #[test_case(&[0, 0x6161, 1], ByteArrayError::WordOutOfRange { word: Felt252::from(0x6161), len: 1 } ; "pending_word_out_of_range")]
+    #[test_case(&[0, 0xc5, 1], ByteArrayError::InvalidUtf8 ; "invalid_utf8")]
+    fn fails_to_decode_invalid_felts(felts: &[u128], error: ByteArrayError) {
+        let felts: Vec<_> = felts.iter().copied().map(Felt252::from).collect();
+
+        assert_eq!(from_byte_array(&felts).unwrap_err(), error);
+    }
+
+    #[test]
+    fn fails_to_decode_word_longer_than_31_bytes() {
+        let word = Felt252::from_bytes_be_slice(&[0x61; 32]);
+
+        assert_eq!(
+            from_byte_array(&[Felt252::from(1), word, Felt252::from(0), Felt252::from(0)])
+                .unwrap_err(),
+            ByteArrayError::WordOutOfRange {
+                word,
+                len: BYTES_IN_WORD
+            }
+        );
+    }
+
+    #[test]
+    fn replaces_invalid_utf8_when_converting_to_string() {
+        let felts: Vec<_> = [0_u8, 0xc5, 1].into_iter().map(Felt252::from).collect();
+        let byte_array: ByteArray = BufferReader::new(&felts).read().unwrap();
+
+        assert_eq!(String::from(byte_array), "\u{FFFD}");
+    }
+}
@@ -3,11 +3,13 @@ mod tests_felt252 {
     use cairo_lang_runner::short_string::as_cairo_short_string;
     use cairo_vm::utils::PRIME_STR;
     use conversions::byte_array::ByteArray;
-    use conversions::felt252::FromShortString;
+    use conversions::felt252::{FeltConversionError, FromShortString, TryToStarknetType};
     use conversions::serde::serialize::SerializeToFeltVec;
-    use conversions::string::{IntoDecStr, TryFromDecStr, TryFromHexStr};
+    use conversions::string::{IntoDecStr, IntoHexStr, TryFromDecStr, TryFromHexStr};
     use conversions::{FromConv, IntoConv};
     use itertools::chain;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use starknet::core::types::Felt;
     use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector, Nonce};
     use starknet_api::hash::StarkHash;
@@ -113,4 +115,64 @@ mod tests_felt252 {
         let expected: Vec<Felt252> = chain!(vec![Felt252::from(1)], serialised_val).collect();
         assert_eq!(res.serialize_to_vec(), expected);
     }
+
+    fn random_felt(rng: &mut StdRng) -> Felt252 {
+        let mut bytes = [0_u8; 32];
+        rng.fill(&mut bytes);
+        // Keep a part of the values small, so they cover both valid and invalid ranges
+        let leading_zeros = rng.gen_range(0..32);
+        bytes[..leading_zeros].fill(0);
+
+        Felt252::from_bytes_be(&bytes)
+    }
+
+    #[test]
+    fn test_random_felts_round_trip() {
+        let mut rng = StdRng::seed_from_u64(2137);
+
+        for _ in 0..1000 {
+            let felt = random_felt(&mut rng);
+
+            assert_eq!(
+                felt,
+                Felt252::try_from_hex_str(&felt.into_hex_string()).unwrap()
+            );
+            assert_eq!(
+                felt,
+                Felt252::try_from_dec_str(&felt.into_dec_string()).unwrap()
+            );
+
+            match felt.try_to_contract_address() {
+                Ok(contract_address) => {
+                    assert!(felt.bits() <= 251);
+                    assert_eq!(felt, contract_address.into_());
+                }
+                Err(err) => {
+                    assert!(felt.bits() > 251);
+                    assert_eq!(err, FeltConversionError::ContractAddressOutOfRange(felt));
+                }
+            }
+
+            match felt.try_to_eth_address() {
+                Ok(eth_address) => {
+                    assert!(felt.bits() <= 160);
+                    assert_eq!(felt, eth_address.into_());
+                }
+                Err(err) => {
+                    assert!(felt.bits() > 160);
+                    assert_eq!(err, FeltConversionError::EthAddressOutOfRange(felt));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_to_contract_address_out_of_range() {
+        let felt = Felt252::TWO.pow(251_u32);
+
+        assert_eq!(
+            felt.try_to_contract_address().unwrap_err().to_string(),
+            "Felt 0x800000000000000000000000000000000000000000000000000000000000000 is out of range of a contract address, it must be smaller than 2^251"
+        );
+    }
 }
@@ -1,3 +1,4 @@
+mod byte_array;
 mod class_hash;
 mod contract_address;
 mod entrypoint_selector;
@@ -0,0 +1,142 @@
+use crate::transformer::map_selectors_to_functions;
+use anyhow::{bail, ensure, Result};
+use itertools::Itertools;
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::{ContractClass, Felt};
+
+/// Returns the ABI of a Sierra contract class.
+/// `None` is returned for Cairo Zero classes and for ABIs that can't be deserialized.
+#[must_use]
+pub fn get_abi(class_definition: &ContractClass) -> Option<Vec<AbiEntry>> {
+    match class_definition {
+        ContractClass::Sierra(class) => serde_json::from_str(class.abi.as_str()).ok(),
+        ContractClass::Legacy(_) => None,
+    }
+}
+
+/// Checks whether a function, or the constructor, named `function_name` is present in `abi`
+pub fn ensure_function_in_abi(abi: &[AbiEntry], function_name: &str) -> Result<()> {
+    let selector_function_map = map_selectors_to_functions(abi);
+
+    if selector_function_map
+        .values()
+        .any(|function| function.name == function_name)
+    {
+        return Ok(());
+    }
+
+    let available = selector_function_map
+        .values()
+        .map(|function| function.name.as_str())
+        .sorted()
+        .join(", ");
+
+    bail!("Function `{function_name}` not found in the ABI, available functions: {available}")
+}
+
+/// Checks whether serialized `calldata` can satisfy the constructor found in `abi`
+pub fn validate_constructor_calldata(calldata: &[Felt], abi: &[AbiEntry]) -> Result<()> {
+    let Some(constructor) = abi.iter().find_map(|entry| match entry {
+        AbiEntry::Constructor(constructor) => Some(constructor),
+        _ => None,
+    }) else {
+        ensure!(
+            calldata.is_empty(),
+            "Invalid constructor calldata: passed {} felts, but the contract has no constructor",
+            calldata.len()
+        );
+        return Ok(());
+    };
+
+    let size = constructor
+        .inputs
+        .iter()
+        .map(|input| serialized_size(&input.r#type, abi))
+        .fold(SerializedSize::fixed(0), SerializedSize::add);
+
+    let signature = format!(
+        "{}({})",
+        constructor.name,
+        constructor
+            .inputs
+            .iter()
+            .map(|input| format!("{}: {}", input.name, input.r#type))
+            .join(", ")
+    );
+
+    if size.fixed && calldata.len() != size.min {
+        bail!(
+            "Invalid constructor calldata: passed {} felts, expected {} for {signature}",
+            calldata.len(),
+            size.min
+        );
+    }
+    if calldata.len() < size.min {
+        bail!(
+            "Invalid constructor calldata: passed {} felts, expected at least {} for {signature}",
+            calldata.len(),
+            size.min
+        );
+    }
+
+    Ok(())
+}
+
+/// Lower bound of the number of felts a value serializes to,
+/// `fixed` is set if every value of the type serializes to exactly `min` felts
+#[derive(Clone, Copy)]
+struct SerializedSize {
+    min: usize,
+    fixed: bool,
+}
+
+impl SerializedSize {
+    fn fixed(min: usize) -> Self {
+        Self { min, fixed: true }
+    }
+
+    fn at_least(min: usize) -> Self {
+        Self { min, fixed: false }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            min: self.min + other.min,
+            fixed: self.fixed && other.fixed,
+        }
+    }
+}
+
+fn serialized_size(r#type: &str, abi: &[AbiEntry]) -> SerializedSize {
+    if r#type == "core::byte_array::ByteArray" {
+        // Length of the data array, pending word and its length
+        return SerializedSize::at_least(3);
+    }
+    if r#type.starts_with("core::array::Array::<") || r#type.starts_with("core::array::Span::<") {
+        return SerializedSize::at_least(1);
+    }
+
+    for entry in abi {
+        match entry {
+            AbiEntry::Struct(abi_struct) if abi_struct.name == r#type => {
+                return abi_struct
+                    .members
+                    .iter()
+                    .map(|member| serialized_size(&member.r#type, abi))
+                    .fold(SerializedSize::fixed(0), SerializedSize::add);
+            }
+            AbiEntry::Enum(abi_enum) if abi_enum.name == r#type => {
+                return SerializedSize::at_least(1);
+            }
+            _ => {}
+        }
+    }
+
+    if r#type.starts_with("core::") && !r#type.contains('<') {
+        // felt252, integers, addresses and other single felt primitives
+        SerializedSize::fixed(1)
+    } else {
+        // Tuples and anything else not described by the ABI, nothing is assumed about them
+        SerializedSize::at_least(0)
+    }
+}
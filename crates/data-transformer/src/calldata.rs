@@ -1,5 +1,6 @@
-use super::transformer::transform;
+use super::transformer::{transform, transform_with_abi};
 use serde::{Deserialize, Serialize};
+use starknet::core::types::contract::AbiEntry;
 use starknet::core::types::{ContractClass, Felt};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -51,4 +52,18 @@ impl Calldata {
             }
         }
     }
+
+    /// Same as [`Calldata::serialized`], but uses the given `abi` instead of the one embedded in the contract class
+    pub fn serialized_with_abi(
+        self,
+        abi: &[AbiEntry],
+        function_selector: &Felt,
+    ) -> anyhow::Result<Vec<Felt>> {
+        match self {
+            Calldata::Serialized(serialized) => Ok(serialized),
+            Calldata::Expressions(ref expressions) => {
+                transform_with_abi(expressions, abi, function_selector)
+            }
+        }
+    }
 }
@@ -1,7 +1,11 @@
+mod abi;
 pub mod cairo_types;
 mod calldata;
+mod reverse_transformer;
 mod sierra_abi;
 mod transformer;
 
+pub use abi::{ensure_function_in_abi, get_abi, validate_constructor_calldata};
 pub use calldata::Calldata;
-pub use transformer::transform;
+pub use reverse_transformer::{reverse_transform, reverse_transform_sequence};
+pub use transformer::{transform, transform_with_abi};
@@ -0,0 +1,338 @@
+use anyhow::{bail, ensure, Context, Result};
+use conversions::byte_array::from_byte_array;
+use itertools::Itertools;
+use num_bigint::{BigInt, BigUint};
+use starknet::core::types::contract::{AbiEntry, AbiEnum, AbiStruct};
+use starknet::core::types::Felt;
+
+/// Interpret `data` as a serialized value of `type_name` and return it as an expression in Cairo syntax,
+/// which can be passed back as calldata.
+/// Structs and enums are looked up in `abi`, either by the full path or by the name if it's unique.
+pub fn reverse_transform(data: &[Felt], type_name: &str, abi: &[AbiEntry]) -> Result<String> {
+    let mut decoder = Decoder {
+        data,
+        position: 0,
+        abi,
+    };
+
+    let value = decoder.decode(type_name)?;
+
+    let left = data.len() - decoder.position;
+    ensure!(
+        left == 0,
+        "Too many felts: {left} left after decoding a value of type {type_name}"
+    );
+
+    Ok(value)
+}
+
+/// Interpret `data` as serialized values of `type_names` following each other,
+/// and return each of them as an expression in Cairo syntax.
+pub fn reverse_transform_sequence(
+    data: &[Felt],
+    type_names: &[&str],
+    abi: &[AbiEntry],
+) -> Result<Vec<String>> {
+    let mut decoder = Decoder {
+        data,
+        position: 0,
+        abi,
+    };
+
+    let values = type_names
+        .iter()
+        .map(|type_name| decoder.decode(type_name))
+        .collect::<Result<Vec<_>>>()?;
+
+    let left = data.len() - decoder.position;
+    ensure!(
+        left == 0,
+        "Too many felts: {left} left after decoding values of types {}",
+        type_names.join(", ")
+    );
+
+    Ok(values)
+}
+
+struct Decoder<'a> {
+    data: &'a [Felt],
+    position: usize,
+    abi: &'a [AbiEntry],
+}
+
+impl<'a> Decoder<'a> {
+    fn next(&mut self, type_name: &str) -> Result<Felt> {
+        let felt =
+            self.data.get(self.position).copied().with_context(|| {
+                format!("Not enough felts to decode a value of type {type_name}")
+            })?;
+        self.position += 1;
+
+        Ok(felt)
+    }
+
+    fn decode(&mut self, type_name: &str) -> Result<String> {
+        if let Some(types) = type_name
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return self.decode_tuple(types);
+        }
+
+        if let Some(item_type) = array_item_type(type_name) {
+            return self.decode_array(type_name, item_type);
+        }
+
+        if is_core_or_short(type_name) {
+            if let Some(value) = self.decode_primitive(type_name)? {
+                return Ok(value);
+            }
+        }
+
+        match self.find_item(type_name)? {
+            Some(Item::Struct(abi_struct)) => self.decode_struct(abi_struct),
+            Some(Item::Enum(abi_enum)) => self.decode_enum(abi_enum),
+            None => bail!("Type {type_name} is not supported and was not found in the ABI"),
+        }
+    }
+
+    fn decode_primitive(&mut self, type_name: &str) -> Result<Option<String>> {
+        let short_name = type_name.rsplit("::").next().unwrap();
+
+        let value = match short_name {
+            "felt252" | "felt" | "ContractAddress" | "ClassHash" | "StorageAddress"
+            | "EthAddress" | "bytes31" => format!("{:#x}", self.next(type_name)?),
+            "bool" => {
+                let value = self.next(type_name)?;
+                if value == Felt::ZERO {
+                    "false".to_string()
+                } else if value == Felt::ONE {
+                    "true".to_string()
+                } else {
+                    bail!("Invalid value {value:#x} of type bool")
+                }
+            }
+            "u8" | "u16" | "u32" | "u64" | "u128" => self.decode_integer(short_name, false)?,
+            "i8" | "i16" | "i32" | "i64" | "i128" => self.decode_integer(short_name, true)?,
+            "u256" => {
+                let low = self.decode_limb(type_name)?;
+                let high = self.decode_limb(type_name)?;
+                format!("{}_u256", (high << 128) + low)
+            }
+            "ByteArray" => {
+                let start = self.position;
+                let words_len = usize::try_from(self.next(type_name)?.to_biguint())
+                    .context("Invalid length of ByteArray")?;
+                // Length of the words, the words, pending word and its length
+                let end = words_len
+                    .checked_add(start + 3)
+                    .filter(|end| *end <= self.data.len())
+                    .with_context(|| {
+                        format!("Not enough felts to decode a value of type {type_name}")
+                    })?;
+
+                let value = from_byte_array(&self.data[start..end])
+                    .context("Invalid value of type ByteArray")?;
+                self.position = end;
+
+                format!("{value:?}")
+            }
+            _ => return Ok(None),
+        };
+
+        Ok(Some(value))
+    }
+
+    fn decode_integer(&mut self, type_name: &str, signed: bool) -> Result<String> {
+        let bits: u32 = type_name[1..].parse().unwrap();
+        let value = BigInt::from(self.next(type_name)?.to_biguint());
+        // Negative numbers are represented as the field prime minus their absolute value
+        let value = if signed && value > BigInt::from(Felt::MAX.to_biguint()) / 2 {
+            value - BigInt::from(Felt::MAX.to_biguint()) - 1
+        } else {
+            value
+        };
+
+        let (min, max) = if signed {
+            (
+                -(BigInt::from(1) << (bits - 1)),
+                BigInt::from(1) << (bits - 1),
+            )
+        } else {
+            (BigInt::from(0), BigInt::from(1) << bits)
+        };
+        ensure!(
+            min <= value && value < max,
+            "Value {value} is out of range of type {type_name}"
+        );
+
+        Ok(format!("{value}_{type_name}"))
+    }
+
+    fn decode_limb(&mut self, type_name: &str) -> Result<BigUint> {
+        let limb = self.next(type_name)?.to_biguint();
+        ensure!(
+            limb.bits() <= 128,
+            "Value {limb} is out of range of a limb of type {type_name}"
+        );
+
+        Ok(limb)
+    }
+
+    fn decode_tuple(&mut self, types: &str) -> Result<String> {
+        let values = split_types(types)
+            .into_iter()
+            .map(|type_name| self.decode(type_name))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(match values.as_slice() {
+            [value] => format!("({value},)"),
+            _ => format!("({})", values.join(", ")),
+        })
+    }
+
+    fn decode_array(&mut self, type_name: &str, item_type: &str) -> Result<String> {
+        let len = usize::try_from(self.next(type_name)?.to_biguint())
+            .with_context(|| format!("Invalid length of {type_name}"))?;
+
+        let items = (0..len)
+            .map(|_| self.decode(item_type))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(format!("array![{}]", items.join(", ")))
+    }
+
+    fn decode_struct(&mut self, abi_struct: &'a AbiStruct) -> Result<String> {
+        let members = abi_struct
+            .members
+            .iter()
+            .map(|member| {
+                self.decode(&member.r#type)
+                    .map(|value| format!("{}: {value}", member.name))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(format!(
+            "{} {{ {} }}",
+            item_name(&abi_struct.name),
+            members.join(", ")
+        ))
+    }
+
+    fn decode_enum(&mut self, abi_enum: &'a AbiEnum) -> Result<String> {
+        let position = self.next(&abi_enum.name)?;
+        let variant = usize::try_from(position.to_biguint())
+            .ok()
+            .and_then(|position| abi_enum.variants.get(position))
+            .with_context(|| {
+                format!(
+                    "Invalid variant {position:#x} of enum {}, it has {} variants",
+                    abi_enum.name,
+                    abi_enum.variants.len()
+                )
+            })?;
+
+        let name = format!("{}::{}", item_name(&abi_enum.name), variant.name);
+
+        if variant.r#type == "()" {
+            Ok(name)
+        } else {
+            Ok(format!("{name}({})", self.decode(&variant.r#type)?))
+        }
+    }
+
+    fn find_item(&self, type_name: &str) -> Result<Option<Item<'a>>> {
+        let abi: &'a [AbiEntry] = self.abi;
+        let items = abi
+            .iter()
+            .filter_map(|entry| match entry {
+                AbiEntry::Struct(abi_struct) => Some(Item::Struct(abi_struct)),
+                AbiEntry::Enum(abi_enum) => Some(Item::Enum(abi_enum)),
+                _ => None,
+            })
+            .collect_vec();
+
+        if let Some(item) = items.iter().find(|item| item.name() == type_name) {
+            return Ok(Some(*item));
+        }
+        if type_name.contains("::") {
+            return Ok(None);
+        }
+
+        let matching = items
+            .into_iter()
+            .filter(|item| item_name(item.name()) == type_name)
+            .collect_vec();
+        ensure!(
+            matching.len() <= 1,
+            "Found more than one type {type_name} in the ABI, please specify a full path to it: {}",
+            matching.iter().map(Item::name).join(", ")
+        );
+
+        Ok(matching.into_iter().next())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Item<'a> {
+    Struct(&'a AbiStruct),
+    Enum(&'a AbiEnum),
+}
+
+impl Item<'_> {
+    fn name(&self) -> &str {
+        match self {
+            Item::Struct(abi_struct) => &abi_struct.name,
+            Item::Enum(abi_enum) => &abi_enum.name,
+        }
+    }
+}
+
+/// Primitives are only recognized in the corelib, or when given without a path
+fn is_core_or_short(type_name: &str) -> bool {
+    !type_name.contains('<') && (type_name.starts_with("core::") || !type_name.contains("::"))
+}
+
+fn array_item_type(type_name: &str) -> Option<&str> {
+    [
+        "core::array::Array::<",
+        "core::array::Span::<",
+        "Array::<",
+        "Span::<",
+    ]
+    .into_iter()
+    .find_map(|prefix| type_name.strip_prefix(prefix))
+    .and_then(|rest| rest.strip_suffix('>'))
+}
+
+/// Name of a struct or an enum without its module path and generic arguments, as it's written in Cairo expressions
+fn item_name(path: &str) -> &str {
+    let without_generics = path.split_once("::<").map_or(path, |(name, _)| name);
+
+    without_generics.rsplit("::").next().unwrap()
+}
+
+/// Splits comma-separated types, ignoring commas nested in tuples and generic arguments
+fn split_types(types: &str) -> Vec<&str> {
+    let mut result = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (index, char) in types.char_indices() {
+        match char {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(types[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = types[start..].trim();
+    if !last.is_empty() {
+        result.push(last);
+    }
+
+    result
+}
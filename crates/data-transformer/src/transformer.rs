@@ -25,7 +25,16 @@ pub fn transform(
     let abi: Vec<AbiEntry> = serde_json::from_str(sierra_class.abi.as_str())
         .context("Couldn't deserialize ABI received from chain")?;
 
-    let selector_function_map = map_selectors_to_functions(&abi);
+    transform_with_abi(calldata, &abi, function_selector)
+}
+
+/// Same as [`transform`], but uses the given `abi` instead of the one embedded in the contract class
+pub fn transform_with_abi(
+    calldata: &str,
+    abi: &[AbiEntry],
+    function_selector: &Felt,
+) -> Result<Vec<Felt>> {
+    let selector_function_map = map_selectors_to_functions(abi);
 
     let function = selector_function_map
         .get(function_selector)
@@ -39,7 +48,7 @@ pub fn transform(
 
     let calldata = split_expressions(calldata, &db)?;
 
-    process(calldata, function, &abi, &db).context("Error while processing Cairo-like calldata")
+    process(calldata, function, abi, &db).context("Error while processing Cairo-like calldata")
 }
 
 fn split_expressions(input: &str, db: &SimpleParserDatabase) -> Result<Vec<Expr>> {
@@ -80,7 +89,7 @@ fn process(
         .collect::<Result<_>>()
 }
 
-fn map_selectors_to_functions(abi: &[AbiEntry]) -> HashMap<Felt, AbiFunction> {
+pub(crate) fn map_selectors_to_functions(abi: &[AbiEntry]) -> HashMap<Felt, AbiFunction> {
     let mut map = HashMap::new();
 
     for abi_entry in abi {
@@ -1,5 +1,8 @@
 use core::fmt;
-use data_transformer::Calldata;
+use data_transformer::{
+    ensure_function_in_abi, get_abi, reverse_transform, reverse_transform_sequence,
+    validate_constructor_calldata, Calldata,
+};
 use indoc::indoc;
 use itertools::Itertools;
 use primitive_types::U256;
@@ -994,3 +997,175 @@ async fn test_happy_case_contract_constructor() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_validate_constructor_calldata() {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    validate_constructor_calldata(&[Felt::from_hex_unchecked("0x123")], &abi).unwrap();
+}
+
+#[tokio::test]
+async fn test_validate_constructor_calldata_invalid_length() {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let result = validate_constructor_calldata(&[Felt::ONE, Felt::TWO], &abi);
+
+    result.unwrap_err().assert_contains(
+        "Invalid constructor calldata: passed 2 felts, expected 1 for constructor(",
+    );
+}
+
+#[tokio::test]
+async fn test_happy_case_serialized_with_abi() -> anyhow::Result<()> {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let input = vec![String::from("(1010101_u32,)")];
+
+    let result = Calldata::from(input)
+        .serialized_with_abi(&abi, &get_selector_from_name("unsigned_fn").unwrap())?;
+
+    assert_eq!(result, [Felt::from(1_010_101_u32)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ensure_function_in_abi() {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    ensure_function_in_abi(&abi, "unsigned_fn").unwrap();
+    ensure_function_in_abi(&abi, "constructor").unwrap();
+}
+
+#[tokio::test]
+async fn test_ensure_function_in_abi_not_found() {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let result = ensure_function_in_abi(&abi, "nonexistent_fn");
+
+    result
+        .unwrap_err()
+        .assert_contains("Function `nonexistent_fn` not found in the ABI, available functions: ");
+    ensure_function_in_abi(&abi, "nonexistent_fn")
+        .unwrap_err()
+        .assert_contains("unsigned_fn");
+}
+
+#[tokio::test]
+async fn test_reverse_transform_nested_enum() -> anyhow::Result<()> {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let data = [2, 345, 456].into_iter().map(Felt::from).collect_vec();
+
+    let result = reverse_transform(&data, "Enum", &abi)?;
+
+    assert_eq!(
+        result,
+        "Enum::Three(NestedStructWithField { a: SimpleStruct { a: 0x159 }, b: 0x1c8 })"
+    );
+
+    Ok(())
+}
+
+#[test_case(&[1, 2], "core::integer::u256", "680564733841876926926749214863536422913_u256" ; "u256")]
+#[test_case(&[5], "i8", "5_i8" ; "positive_signed")]
+#[test_case(&[1], "bool", "true" ; "bool")]
+#[test_case(&[2, 1, 2], "core::array::Array::<core::integer::u8>", "array![1_u8, 2_u8]" ; "array")]
+#[test_case(&[2137, 1, 0], "(core::felt252, core::integer::u8, data_transformer_contract::Enum)", "(0x859, 1_u8, Enum::One)" ; "tuple")]
+#[test_case(&[0, 0x7a61_c5bc, 4], "core::byte_array::ByteArray", r#""zaż""# ; "multi_byte_byte_array")]
+#[tokio::test]
+async fn test_reverse_transform(data: &[u128], type_name: &str, expected: &str) {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let data = data.iter().copied().map(Felt::from).collect_vec();
+
+    assert_eq!(reverse_transform(&data, type_name, &abi).unwrap(), expected);
+}
+
+#[tokio::test]
+async fn test_reverse_transform_round_trip() -> anyhow::Result<()> {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let value =
+        "(123, 234, Enum::Three(NestedStructWithField { a: SimpleStruct { a: 345 }, b: 456 }))";
+    let data = Calldata::from(vec![format!("({value},)")])
+        .serialized_with_abi(&abi, &get_selector_from_name("tuple_fn").unwrap())?;
+
+    let decoded = reverse_transform(
+        &data,
+        "(core::felt252, core::integer::u8, data_transformer_contract::Enum)",
+        &abi,
+    )?;
+    let encoded_again = Calldata::from(vec![format!("({decoded},)")])
+        .serialized_with_abi(&abi, &get_selector_from_name("tuple_fn").unwrap())?;
+
+    assert_eq!(encoded_again, data);
+
+    Ok(())
+}
+
+#[test_case(&[-1], "i8", "-1_i8" ; "negative_signed")]
+#[tokio::test]
+async fn test_reverse_transform_signed(data: &[i128], type_name: &str, expected: &str) {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let data = data.iter().copied().map(Felt::from).collect_vec();
+
+    assert_eq!(reverse_transform(&data, type_name, &abi).unwrap(), expected);
+}
+
+#[test_case(&[], "u8", "Not enough felts to decode a value of type u8" ; "not_enough_data")]
+#[test_case(&[1, 2], "u8", "Too many felts: 1 left after decoding a value of type u8" ; "too_much_data")]
+#[test_case(&[256], "u8", "Value 256 is out of range of type u8" ; "out_of_range")]
+#[test_case(&[5], "Enum", "Invalid variant 0x5 of enum data_transformer_contract::Enum" ; "invalid_variant")]
+#[test_case(&[0, 0xff, 1], "core::byte_array::ByteArray", "Invalid value of type ByteArray" ; "invalid_utf8_byte_array")]
+#[test_case(&[1], "NonexistentStruct", "Type NonexistentStruct is not supported and was not found in the ABI" ; "unknown_type")]
+#[tokio::test]
+async fn test_reverse_transform_invalid(data: &[u128], type_name: &str, error_message: &str) {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let data = data.iter().copied().map(Felt::from).collect_vec();
+
+    reverse_transform(&data, type_name, &abi)
+        .unwrap_err()
+        .assert_contains(error_message);
+}
+
+#[tokio::test]
+async fn test_reverse_transform_sequence() -> anyhow::Result<()> {
+    let contract_class = CLASS.get_or_init(init_class).await.to_owned();
+    let abi = get_abi(&contract_class).unwrap();
+
+    let data = [1, 2, 3, 2137].into_iter().map(Felt::from).collect_vec();
+
+    let result =
+        reverse_transform_sequence(&data, &["core::integer::u256", "u8", "felt252"], &abi)?;
+
+    assert_eq!(
+        result,
+        vec![
+            "680564733841876926926749214863536422913_u256",
+            "3_u8",
+            "0x859"
+        ]
+    );
+
+    reverse_transform_sequence(&data, &["core::integer::u256", "u8"], &abi)
+        .unwrap_err()
+        .assert_contains(
+            "Too many felts: 1 left after decoding values of types core::integer::u256, u8",
+        );
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::rc::Rc;
 
 use crate::build_trace_data::test_sierra_program_path::VersionedProgramPath;
+use crate::test_case_summary::{AnyTestCaseSummary, TestCaseSummary};
+use crate::test_target_summary::TestTargetSummary;
 use blockifier::execution::deprecated_syscalls::DeprecatedSyscallSelector;
 use blockifier::execution::entry_point::{CallEntryPoint, CallType};
 use blockifier::execution::syscalls::hint_processor::SyscallCounter;
@@ -33,6 +35,8 @@ use starknet_api::deprecated_contract_class::EntryPointType;
 pub mod test_sierra_program_path;
 
 pub const TRACE_DIR: &str = "snfoundry_trace";
+/// File in [`TRACE_DIR`] which maps test names to the files with their traces
+pub const TRACE_INDEX_FILE: &str = "index.json";
 
 pub const TEST_CODE_CONTRACT_NAME: &str = "SNFORGE_TEST_CODE";
 pub const TEST_CODE_FUNCTION_NAME: &str = "SNFORGE_TEST_CODE_FUNCTION";
@@ -307,8 +311,25 @@ fn build_profiler_trace_entry(value: &RelocatedTraceEntry) -> ProfilerTraceEntry
     }
 }
 
+/// Name of a file with data of the test, without the extension. `::` is replaced with `.` and other
+/// characters which are not allowed in file names on some platforms with `_`
+#[must_use]
+pub fn sanitize_test_name(test_name: &str) -> String {
+    test_name
+        .replace("::", ".")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
 pub fn save_trace_data(
-    test_name: &String,
+    test_name: &str,
     trace_data: &VersionedProfilerCallTrace,
 ) -> Result<PathBuf> {
     let serialized_trace =
@@ -316,8 +337,51 @@ pub fn save_trace_data(
     let dir_to_save_trace = PathBuf::from(TRACE_DIR);
     fs::create_dir_all(&dir_to_save_trace).context("Failed to create a .trace_data directory")?;
 
-    let filename = format!("{test_name}.json");
+    let filename = format!("{}.json", sanitize_test_name(test_name));
     fs::write(dir_to_save_trace.join(&filename), serialized_trace)
         .context("Failed to write call trace to a file")?;
     Ok(dir_to_save_trace.join(&filename))
 }
+
+/// Names of the passed tests which traces were saved in the run mapped to the names of their files
+#[must_use]
+pub fn trace_index_entries(summaries: &[TestTargetSummary]) -> BTreeMap<String, String> {
+    summaries
+        .iter()
+        .flat_map(|summary| &summary.test_case_summaries)
+        .filter_map(|result| match result {
+            AnyTestCaseSummary::Single(TestCaseSummary::Passed { name, .. }) => {
+                Some((name.clone(), format!("{}.json", sanitize_test_name(name))))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Saves a file mapping names of the tests which traces were saved in the run to the names of their files,
+/// entries of all packages run are collected with [`trace_index_entries`] before
+pub fn save_trace_index(index: &BTreeMap<String, String>) -> Result<()> {
+    let dir_to_save_trace = PathBuf::from(TRACE_DIR);
+    fs::create_dir_all(&dir_to_save_trace).context("Failed to create a .trace_data directory")?;
+    fs::write(
+        dir_to_save_trace.join(TRACE_INDEX_FILE),
+        serde_json::to_string_pretty(index)?,
+    )
+    .context("Failed to write trace index to a file")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitizing_test_names() {
+        assert_eq!(
+            sanitize_test_name("package_integrationtest::module::test_name"),
+            "package_integrationtest.module.test_name"
+        );
+        assert_eq!(sanitize_test_name("a::b<c>/d e"), "a.b_c__d_e");
+    }
+}
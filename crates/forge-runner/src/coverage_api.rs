@@ -1,15 +1,26 @@
+use crate::build_trace_data::sanitize_test_name;
 use anyhow::{ensure, Context, Result};
-use indoc::{formatdoc, indoc};
+use cairo_annotations::trace_data::{
+    CairoExecutionInfo, CallTraceNode, CallTraceV1, VersionedCallTrace,
+};
+use camino::{Utf8Path, Utf8PathBuf};
+use indoc::formatdoc;
+use lcov::{LcovReport, ProgramCoverageInfo};
 use scarb_api::metadata::Metadata;
 use semver::Version;
-use shared::command::CommandExt;
-use std::process::Stdio;
-use std::{env, fs, path::PathBuf, process::Command};
+use shared::print::{emit_warning, DiagnosticCode};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
 use toml_edit::{DocumentMut, Table};
-use which::which;
 
+mod lcov;
+
+/// Default directory of the coverage report, relative to the workspace root
 pub const COVERAGE_DIR: &str = "coverage";
 pub const OUTPUT_FILE_NAME: &str = "coverage.lcov";
+/// Directory in the target directory where call traces of the tests are saved to generate the coverage from
+pub const COVERAGE_DATA_DIR: &str = "snforge-coverage";
 
 const MINIMAL_SCARB_VERSION: Version = Version::new(2, 8, 0);
 
@@ -19,46 +30,112 @@ const CAIRO_COVERAGE_REQUIRED_ENTRIES: [(&str, &str); 3] = [
     ("inlining-strategy", "\"avoid\""),
 ];
 
-pub fn run_coverage(saved_trace_data_paths: &[PathBuf]) -> Result<()> {
-    let coverage = env::var("CAIRO_COVERAGE")
-        .map(PathBuf::from)
-        .ok()
-        .unwrap_or_else(|| PathBuf::from("cairo-coverage"));
+/// Saves the call trace of a passed test, traces of all tests are processed together after the run
+pub fn save_coverage_data(
+    test_name: &str,
+    trace_data: &VersionedCallTrace,
+    coverage_data_dir: &Utf8Path,
+) -> Result<()> {
+    fs::create_dir_all(coverage_data_dir).context("Failed to create a coverage data directory")?;
+    fs::write(
+        coverage_data_dir.join(format!("{}.json", sanitize_test_name(test_name))),
+        serde_json::to_string(trace_data)?,
+    )
+    .context("Failed to write coverage data to a file")?;
 
-    ensure!(
-        which(coverage.as_os_str()).is_ok(),
-        indoc! {
-            r"The 'cairo-coverage' binary was not found in PATH. It may not have been installed.
-            Please refer to the documentation for installation instructions:
-            https://github.com/software-mansion/cairo-coverage/blob/main/README.md"
+    Ok(())
+}
+
+/// Removes data saved by the previous run, so the report covers only the tests run this time
+pub fn clean_coverage_data(coverage_data_dir: &Utf8Path) -> Result<()> {
+    if coverage_data_dir.exists() {
+        fs::remove_dir_all(coverage_data_dir)
+            .with_context(|| format!("Failed to remove {coverage_data_dir}"))?;
+    }
+
+    Ok(())
+}
+
+/// Writes an lcov report of the code executed by the tests which data was saved with [`save_coverage_data`].
+/// Only source files inside `workspace_root` are included
+pub fn generate_coverage(
+    coverage_data_dir: &Utf8Path,
+    output_path: &Utf8Path,
+    workspace_root: &Utf8Path,
+) -> Result<()> {
+    let trace_paths: Vec<Utf8PathBuf> = match coverage_data_dir.read_dir_utf8() {
+        Ok(entries) => entries
+            .map(|entry| Ok(entry?.into_path()))
+            .collect::<Result<_>>()?,
+        Err(err) if err.kind() == ErrorKind::NotFound => vec![],
+        Err(err) => Err(err)?,
+    };
+    if trace_paths.is_empty() {
+        emit_warning(
+            DiagnosticCode::MissingCoverageData,
+            "No trace data to generate coverage from",
+        );
+        return Ok(());
+    }
+
+    let mut programs: HashMap<Utf8PathBuf, ProgramCoverageInfo> = HashMap::new();
+    let mut statement_hits: HashMap<Utf8PathBuf, HashMap<usize, usize>> = HashMap::new();
+
+    for trace_path in &trace_paths {
+        let trace = fs::read_to_string(trace_path)
+            .with_context(|| format!("Failed to read {trace_path}"))?;
+        let VersionedCallTrace::V1(call_trace) = serde_json::from_str(&trace)
+            .with_context(|| format!("Failed to parse {trace_path}"))?;
+
+        for execution_info in executions(&call_trace) {
+            let source_sierra_path = &execution_info.source_sierra_path;
+            if !programs.contains_key(source_sierra_path) {
+                let program = ProgramCoverageInfo::load(source_sierra_path)?;
+                programs.insert(source_sierra_path.clone(), program);
+            }
+
+            let hits = statement_hits
+                .entry(source_sierra_path.clone())
+                .or_default();
+            for (statement_idx, count) in
+                programs[source_sierra_path].statement_hits(&execution_info.casm_level_info)
+            {
+                *hits.entry(statement_idx).or_default() += count;
+            }
         }
-    );
+    }
+
+    let mut report = LcovReport::default();
+    for (source_sierra_path, program) in &programs {
+        report.add_program(program, &statement_hits[source_sierra_path]);
+    }
+    report.retain_files(|file| Utf8Path::new(file).starts_with(workspace_root));
+
+    if let Some(dir) = output_path.parent() {
+        fs::create_dir_all(dir).context("Failed to create a coverage dir")?;
+    }
+    fs::write(output_path, report.to_string())
+        .with_context(|| format!("Failed to write coverage report to {output_path}"))?;
 
-    let dir_to_save_coverage = PathBuf::from(COVERAGE_DIR);
-    fs::create_dir_all(&dir_to_save_coverage).context("Failed to create a coverage dir")?;
-    let path_to_save_coverage = dir_to_save_coverage.join(OUTPUT_FILE_NAME);
+    Ok(())
+}
 
-    let trace_files: Vec<&str> = saved_trace_data_paths
+/// Executions of all calls in the trace, including the nested ones
+fn executions(call_trace: &CallTraceV1) -> Vec<&CairoExecutionInfo> {
+    let nested = call_trace
+        .nested_calls
         .iter()
-        .map(|trace_data_path| {
-            trace_data_path
-                .to_str()
-                .expect("Failed to convert trace data path to string")
+        .filter_map(|node| match node {
+            CallTraceNode::EntryPointCall(call) => Some(call),
+            CallTraceNode::DeployWithoutConstructor => None,
         })
-        .collect();
-
-    Command::new(coverage)
-        .arg("--output-path")
-        .arg(&path_to_save_coverage)
-        .args(trace_files)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output_checked()
-        .with_context(|| {
-            "cairo-coverage failed to generate coverage - inspect the errors above for more info"
-        })?;
+        .flat_map(executions);
 
-    Ok(())
+    call_trace
+        .cairo_execution_info
+        .iter()
+        .chain(nested)
+        .collect()
 }
 
 pub fn can_coverage_be_generated(scarb_metadata: &Metadata) -> Result<()> {
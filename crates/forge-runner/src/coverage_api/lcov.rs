@@ -0,0 +1,358 @@
+use anyhow::{anyhow, Context, Result};
+use cairo_annotations::trace_data::CasmLevelInfo;
+use cairo_lang_sierra::debug_info::DebugInfo;
+use cairo_lang_sierra::program::{Program, VersionedProgram};
+use cairo_lang_starknet_classes::contract_class::ContractClass;
+use camino::Utf8Path;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use universal_sierra_compiler_api::compile_sierra_to_casm;
+
+const COVERAGE_ANNOTATIONS_KEY: &str = "github.com/software-mansion/cairo-coverage";
+const PROFILER_ANNOTATIONS_KEY: &str = "github.com/software-mansion/cairo-profiler";
+
+#[derive(Deserialize, Default)]
+struct CoverageAnnotations {
+    statements_code_locations: HashMap<usize, Vec<CodeLocation>>,
+}
+
+#[derive(Deserialize, Default)]
+struct ProfilerAnnotations {
+    statements_functions: HashMap<usize, Vec<String>>,
+}
+
+/// Source file, span and whether the code was inlined
+#[allow(dead_code)]
+#[derive(Deserialize)]
+struct CodeLocation(String, CodeSpan, #[serde(default)] Option<bool>);
+
+#[derive(Deserialize)]
+struct CodeSpan {
+    start: CodeLineColumn,
+    end: CodeLineColumn,
+}
+
+/// Lines are counted from 0
+#[derive(Deserialize)]
+struct CodeLineColumn {
+    line: usize,
+}
+
+/// Sierra program of a contract or a test target, with the information needed to map executed CASM
+/// instructions to the source code
+pub(super) struct ProgramCoverageInfo {
+    /// Code offset of the first CASM instruction of every Sierra statement
+    statement_offsets: Vec<usize>,
+    coverage_annotations: CoverageAnnotations,
+    profiler_annotations: ProfilerAnnotations,
+}
+
+impl ProgramCoverageInfo {
+    /// Loads a contract class or a versioned program saved by snforge
+    pub(super) fn load(source_sierra_path: &Utf8Path) -> Result<Self> {
+        let sierra = fs::read_to_string(source_sierra_path)
+            .with_context(|| format!("Failed to read {source_sierra_path}"))?;
+
+        let (program, debug_info) =
+            if let Ok(contract_class) = serde_json::from_str::<ContractClass>(&sierra) {
+                (
+                    contract_class.extract_sierra_program()?,
+                    contract_class.sierra_program_debug_info,
+                )
+            } else {
+                let artifact = serde_json::from_str::<VersionedProgram>(&sierra)
+                    .with_context(|| format!("Failed to parse {source_sierra_path}"))?
+                    .into_v1()
+                    .with_context(|| format!("Failed to load {source_sierra_path}"))?;
+                (artifact.program, artifact.debug_info)
+            };
+
+        Self::new(&program, debug_info.as_ref())
+            .with_context(|| format!("Failed to generate coverage of {source_sierra_path}"))
+    }
+
+    fn new(program: &Program, debug_info: Option<&DebugInfo>) -> Result<Self> {
+        let annotations = debug_info.map(|debug_info| &debug_info.annotations);
+
+        let coverage_annotations = annotations
+            .and_then(|annotations| annotations.get(COVERAGE_ANNOTATIONS_KEY))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Program has no code locations of statements, make sure it was built with \
+                    `unstable-add-statements-code-locations-debug-info = true`"
+                )
+            })?;
+        let coverage_annotations = serde_json::from_value(coverage_annotations.clone())?;
+        let profiler_annotations = annotations
+            .and_then(|annotations| annotations.get(PROFILER_ANNOTATIONS_KEY))
+            .map(|annotations| serde_json::from_value(annotations.clone()))
+            .transpose()?
+            .unwrap_or_default();
+
+        // Test programs are compiled the same way before running, offsets in contracts match
+        // their CASM as compiling a contract does not change the code of its functions
+        let statement_offsets = compile_sierra_to_casm(program)?
+            .debug_info
+            .into_iter()
+            .map(|(code_offset, _)| code_offset)
+            .collect();
+
+        Ok(Self {
+            statement_offsets,
+            coverage_annotations,
+            profiler_annotations,
+        })
+    }
+
+    /// Number of CASM instructions of every Sierra statement executed in the run
+    pub(super) fn statement_hits(&self, casm_level_info: &CasmLevelInfo) -> HashMap<usize, usize> {
+        let vm_trace = &casm_level_info.vm_trace;
+        // The call header ends with `ret`, which is the last executed instruction, and it is
+        // followed by the program. Without the header the program starts at pc = 1
+        let program_start_pc = if casm_level_info.run_with_call_header {
+            vm_trace.last().map_or(1, |entry| entry.pc + 1)
+        } else {
+            1
+        };
+
+        let mut hits = HashMap::new();
+        for entry in vm_trace {
+            let Some(code_offset) = entry.pc.checked_sub(program_start_pc) else {
+                continue;
+            };
+            let statement_idx = self
+                .statement_offsets
+                .partition_point(|offset| *offset <= code_offset);
+            if let Some(statement_idx) = statement_idx.checked_sub(1) {
+                *hits.entry(statement_idx).or_default() += 1;
+            }
+        }
+
+        hits
+    }
+}
+
+/// Coverage of source files in the lcov format
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct LcovReport {
+    files: BTreeMap<String, FileCoverage>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct FileCoverage {
+    /// The highest number of executions of a statement on each line with code, lines are counted from 1
+    lines: BTreeMap<usize, usize>,
+    functions: BTreeMap<String, FunctionCoverage>,
+}
+
+#[derive(Debug, PartialEq)]
+struct FunctionCoverage {
+    first_line: usize,
+    hits: usize,
+}
+
+impl LcovReport {
+    /// Adds all statements of the program, the ones missing from `statement_hits` as not executed
+    pub(super) fn add_program(
+        &mut self,
+        program: &ProgramCoverageInfo,
+        statement_hits: &HashMap<usize, usize>,
+    ) {
+        for (statement_idx, locations) in &program.coverage_annotations.statements_code_locations {
+            // Next locations are the ones the code was inlined into
+            let Some(CodeLocation(file, span, _)) = locations.first() else {
+                continue;
+            };
+            let hits = statement_hits
+                .get(statement_idx)
+                .copied()
+                .unwrap_or_default();
+            let first_line = span.start.line + 1;
+            let file = self.files.entry(file.clone()).or_default();
+
+            for line in first_line..=span.end.line + 1 {
+                let line_hits = file.lines.entry(line).or_default();
+                *line_hits = (*line_hits).max(hits);
+            }
+
+            let function_name = program
+                .profiler_annotations
+                .statements_functions
+                .get(statement_idx)
+                .and_then(|functions| functions.first());
+            if let Some(function_name) = function_name {
+                let function = file
+                    .functions
+                    .entry(function_name.clone())
+                    .or_insert(FunctionCoverage { first_line, hits });
+                function.first_line = function.first_line.min(first_line);
+                function.hits = function.hits.max(hits);
+            }
+        }
+    }
+
+    pub(super) fn retain_files(&mut self, predicate: impl Fn(&str) -> bool) {
+        self.files.retain(|file, _| predicate(file));
+    }
+}
+
+impl Display for LcovReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (file, coverage) in &self.files {
+            writeln!(f, "TN:")?;
+            writeln!(f, "SF:{file}")?;
+
+            for (name, function) in &coverage.functions {
+                writeln!(f, "FN:{},{name}", function.first_line)?;
+            }
+            for (name, function) in &coverage.functions {
+                writeln!(f, "FNDA:{},{name}", function.hits)?;
+            }
+            writeln!(f, "FNF:{}", coverage.functions.len())?;
+            writeln!(
+                f,
+                "FNH:{}",
+                coverage
+                    .functions
+                    .values()
+                    .filter(|function| function.hits > 0)
+                    .count()
+            )?;
+
+            for (line, hits) in &coverage.lines {
+                writeln!(f, "DA:{line},{hits}")?;
+            }
+            writeln!(f, "LF:{}", coverage.lines.len())?;
+            writeln!(
+                f,
+                "LH:{}",
+                coverage.lines.values().filter(|hits| **hits > 0).count()
+            )?;
+
+            writeln!(f, "end_of_record")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cairo_annotations::trace_data::TraceEntry;
+    use indoc::indoc;
+    use serde_json::json;
+
+    fn program_coverage_info() -> ProgramCoverageInfo {
+        let location = |line: usize| json!(["/ws/src/lib.cairo", {"start": {"line": line, "col": 4}, "end": {"line": line, "col": 10}}, false]);
+
+        ProgramCoverageInfo {
+            statement_offsets: vec![0, 2, 3, 5],
+            coverage_annotations: serde_json::from_value(json!({
+                "statements_code_locations": {
+                    "0": [location(1)],
+                    "1": [location(2), location(10)],
+                    "2": [location(5)],
+                    "3": [["/ws/tests/test.cairo", {"start": {"line": 3, "col": 0}, "end": {"line": 4, "col": 1}}]],
+                }
+            }))
+            .unwrap(),
+            profiler_annotations: serde_json::from_value(json!({
+                "statements_functions": {
+                    "0": ["pkg::add"],
+                    "1": ["pkg::add"],
+                    "2": ["pkg::unused"],
+                    "3": ["tests::test", "tests"],
+                }
+            }))
+            .unwrap(),
+        }
+    }
+
+    fn casm_level_info(pcs: &[usize], run_with_call_header: bool) -> CasmLevelInfo {
+        CasmLevelInfo {
+            run_with_call_header,
+            vm_trace: pcs
+                .iter()
+                .map(|pc| TraceEntry {
+                    pc: *pc,
+                    ap: 0,
+                    fp: 0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn hits_of_statements() {
+        let program = program_coverage_info();
+
+        let hits = program.statement_hits(&casm_level_info(&[1, 2, 3, 3, 6, 7], false));
+
+        assert_eq!(hits, HashMap::from([(0, 2), (1, 2), (3, 2)]));
+    }
+
+    #[test]
+    fn hits_of_statements_with_call_header() {
+        let program = program_coverage_info();
+
+        // Header takes pcs 1..=3, the program starts at pc = 4
+        let hits = program.statement_hits(&casm_level_info(&[1, 2, 4, 7, 3], true));
+
+        assert_eq!(hits, HashMap::from([(0, 1), (2, 1)]));
+    }
+
+    #[test]
+    fn lcov_report() {
+        let program = program_coverage_info();
+        let mut report = LcovReport::default();
+
+        report.add_program(&program, &HashMap::from([(0, 2), (1, 1), (3, 1)]));
+
+        assert_eq!(
+            report.to_string(),
+            indoc! {"
+                TN:
+                SF:/ws/src/lib.cairo
+                FN:2,pkg::add
+                FN:6,pkg::unused
+                FNDA:2,pkg::add
+                FNDA:0,pkg::unused
+                FNF:2
+                FNH:1
+                DA:2,2
+                DA:3,1
+                DA:6,0
+                LF:3
+                LH:2
+                end_of_record
+                TN:
+                SF:/ws/tests/test.cairo
+                FN:4,tests::test
+                FNDA:1,tests::test
+                FNF:1
+                FNH:1
+                DA:4,1
+                DA:5,1
+                LF:2
+                LH:2
+                end_of_record
+            "}
+        );
+    }
+
+    #[test]
+    fn retain_files() {
+        let mut report = LcovReport::default();
+        report.add_program(&program_coverage_info(), &HashMap::new());
+
+        report.retain_files(|file| Utf8Path::new(file).starts_with("/ws/src"));
+
+        assert_eq!(
+            report.files.keys().collect::<Vec<_>>(),
+            vec!["/ws/src/lib.cairo"]
+        );
+    }
+}
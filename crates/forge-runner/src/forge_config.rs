@@ -1,8 +1,14 @@
 use camino::Utf8PathBuf;
+use cheatnet::forking::snapshot::ForkDataMode;
 use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
+use clap::ValueEnum;
+use runtime::starknet::context::BlockContextVersion;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq)]
 pub struct ForgeConfig {
@@ -15,18 +21,58 @@ pub struct TestRunnerConfig {
     pub exit_first: bool,
     pub fuzzer_runs: NonZeroU32,
     pub fuzzer_seed: u64,
+    /// Steps limit of a single test run, `#[available_steps]` attribute takes precedence over it
     pub max_n_steps: Option<u32>,
+    /// Time limit of a single test run, `#[timeout]` attribute takes precedence over it
+    pub timeout: Option<Duration>,
+    /// Gas limit of a single test run, `#[available_gas]` attribute takes precedence over it
+    pub max_gas: Option<u128>,
+    /// Resource the gas used by tests is computed from
+    pub tracked_resource: TrackedResource,
+    /// Versioned constants and default block info tests are run with
+    pub block_context_version: BlockContextVersion,
     pub is_vm_trace_needed: bool,
     pub cache_dir: Utf8PathBuf,
+    pub fork_data: Option<ForkDataMode>,
     pub contracts_data: ContractsData,
     pub environment_variables: HashMap<String, String>,
+    /// Directory where failing inputs of fuzz tests are saved to be replayed on subsequent runs
+    pub fuzz_corpus_dir: Utf8PathBuf,
+    /// Remove corpus entries that no longer fail
+    pub prune_fuzz_corpus: bool,
+    /// Collect what tests print and show it only for failed tests, instead of printing it right away
+    pub capture_output: bool,
+}
+
+/// Resource the gas used by a test is computed from, gas computed from different resources is not comparable
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackedResource {
+    /// L1 gas estimated from the VM resources: steps, builtins, syscalls, events and messages
+    #[default]
+    CairoSteps,
+    /// Sierra gas consumed by the test and the called contracts, requires tests compiled with gas
+    SierraGas,
+}
+
+impl Display for TrackedResource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = self
+            .to_possible_value()
+            .expect("No tracked resource is skipped");
+        write!(f, "{}", name.get_name())
+    }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct OutputConfig {
     pub detailed_resources: bool,
+    /// Write test events as JSON lines to stdout
+    pub json: bool,
     pub execution_data_to_save: ExecutionDataToSave,
     pub versioned_programs_dir: Utf8PathBuf,
+    /// Directory where call traces of the passed tests are saved to generate the coverage from
+    pub coverage_data_dir: Utf8PathBuf,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy, Default)]
@@ -55,10 +101,15 @@ impl ExecutionDataToSave {
 /// [`TestRunnerConfig`] to another function.
 pub struct RuntimeConfig<'a> {
     pub max_n_steps: Option<u32>,
+    pub timeout: Option<Duration>,
+    pub tracked_resource: TrackedResource,
+    pub block_context_version: BlockContextVersion,
     pub is_vm_trace_needed: bool,
     pub cache_dir: &'a Utf8PathBuf,
+    pub fork_data: Option<&'a ForkDataMode>,
     pub contracts_data: &'a ContractsData,
     pub environment_variables: &'a HashMap<String, String>,
+    pub capture_output: bool,
 }
 
 impl<'a> RuntimeConfig<'a> {
@@ -66,10 +117,15 @@ impl<'a> RuntimeConfig<'a> {
     pub fn from(value: &'a TestRunnerConfig) -> RuntimeConfig<'a> {
         Self {
             max_n_steps: value.max_n_steps,
+            timeout: value.timeout,
+            tracked_resource: value.tracked_resource,
+            block_context_version: value.block_context_version,
             is_vm_trace_needed: value.is_vm_trace_needed,
             cache_dir: &value.cache_dir,
+            fork_data: value.fork_data.as_ref(),
             contracts_data: &value.contracts_data,
             environment_variables: &value.environment_variables,
+            capture_output: value.capture_output,
         }
     }
 }
@@ -1,17 +1,34 @@
 use crate::fuzzer::arguments::CairoType;
-use anyhow::{Ok, Result};
+use anyhow::{anyhow, ensure, Ok, Result};
+use cheatnet::runtime_extensions::forge_config_extension::config::{RawFuzzerRange, RawU256};
+use num_bigint::BigUint;
+use num_traits::One;
 use rand::rngs::StdRng;
 use rand::Rng;
+use std::ops::{Shl, Sub};
 
 mod arguments;
+mod corpus;
 mod random;
+mod shrink;
 
+pub use corpus::FuzzCorpus;
 pub use random::RandomFuzzer;
+pub use shrink::Shrinker;
 use std::num::NonZeroU32;
+use std::time::Duration;
+
+/// Runs spent on minimizing a failing input if the test does not configure it
+pub const DEFAULT_SHRINK_RUNS: u32 = 256;
+/// Shrinking stops after this time even if the runs budget was not used up
+pub const SHRINK_TIME_LIMIT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, Clone)]
 pub struct FuzzerArg {
     cairo_type: CairoType,
+    /// Inclusive bounds of generated values
+    low: BigUint,
+    high: BigUint,
     run_with_min_value: u32,
     run_with_max_value: u32,
 }
@@ -27,11 +44,19 @@ pub struct RunParams {
 }
 
 impl RunParams {
-    pub fn from(rng: &mut StdRng, total_runs: NonZeroU32, arguments: &[&str]) -> Result<Self> {
-        let arguments = arguments
+    pub fn from(
+        rng: &mut StdRng,
+        total_runs: NonZeroU32,
+        arguments: &[&str],
+        ranges: &[RawFuzzerRange],
+    ) -> Result<Self> {
+        let mut arguments = arguments
             .iter()
             .map(|arg| -> Result<FuzzerArg> {
                 let argument = CairoType::from_name(arg)?;
+                let low = CairoType::low();
+                let high = argument.high().sub(BigUint::one());
+
                 if total_runs.get() >= 3 {
                     let run_with_min_value = rng.gen_range(1..=total_runs.get());
                     let run_with_max_value = rng.gen_range(1..=total_runs.get());
@@ -44,12 +69,16 @@ impl RunParams {
 
                     Ok(FuzzerArg {
                         cairo_type: argument,
+                        low,
+                        high,
                         run_with_max_value,
                         run_with_min_value,
                     })
                 } else {
                     Ok(FuzzerArg {
                         cairo_type: argument,
+                        low,
+                        high,
                         run_with_max_value: u32::MAX,
                         run_with_min_value: u32::MAX,
                     })
@@ -57,6 +86,34 @@ impl RunParams {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        let arguments_count = arguments.len();
+        for range in ranges {
+            let argument = arguments.get_mut(range.index).ok_or_else(|| {
+                anyhow!(
+                    "Fuzzer range was specified for argument {}, but the test has only {} fuzzed arguments",
+                    range.index,
+                    arguments_count
+                )
+            })?;
+            let low = u256_to_biguint(&range.low);
+            let high = u256_to_biguint(&range.high);
+
+            ensure!(
+                low <= high,
+                "Fuzzer range of argument {} is empty, low = {low} is greater than high = {high}",
+                range.index
+            );
+            ensure!(
+                high <= argument.high,
+                "Fuzzer range of argument {} exceeds values of its type, high = {high} is greater than {}",
+                range.index,
+                argument.high
+            );
+
+            argument.low = low;
+            argument.high = high;
+        }
+
         Ok(Self {
             arguments,
             total_runs,
@@ -64,3 +121,7 @@ impl RunParams {
         })
     }
 }
+
+fn u256_to_biguint(value: &RawU256) -> BigUint {
+    BigUint::from(value.high).shl(128) + BigUint::from(value.low)
+}
@@ -4,7 +4,7 @@ use num_bigint::{BigUint, RandBigInt};
 use num_integer::Integer;
 use num_traits::{One, Zero};
 use rand::prelude::StdRng;
-use std::ops::{Add, Shl, Shr, Sub};
+use std::ops::{Add, Shl, Shr};
 
 #[derive(Debug, Copy, Clone)]
 pub enum CairoType {
@@ -15,6 +15,7 @@ pub enum CairoType {
     U128,
     U256,
     Felt252,
+    ContractAddress,
 }
 
 impl CairoType {
@@ -22,6 +23,7 @@ impl CairoType {
         BigUint::zero()
     }
 
+    /// Exclusive upper bound of the values of the type
     pub fn high(self) -> BigUint {
         match self {
             CairoType::U8 => BigUint::from(u8::MAX).add(BigUint::one()),
@@ -31,49 +33,38 @@ impl CairoType {
             CairoType::U128 => BigUint::from(u128::MAX).add(BigUint::one()),
             CairoType::U256 => BigUint::from(1_u32).shl(256),
             CairoType::Felt252 => Felt252::prime(),
+            // Addresses outside of this range are rejected by Starknet
+            CairoType::ContractAddress => BigUint::from(1_u32).shl(251),
         }
     }
 
-    pub fn gen(self, rng: &mut StdRng) -> Vec<Felt252> {
-        match self {
-            CairoType::U8
-            | CairoType::U16
-            | CairoType::U32
-            | CairoType::U64
-            | CairoType::U128
-            | CairoType::Felt252 => {
-                vec![Felt252::from(
-                    rng.gen_biguint_range(&Self::low(), &self.high()),
-                )]
-            }
-            CairoType::U256 => {
-                let val = rng.gen_biguint_range(&Self::low(), &self.high());
-                u256_to_felt252(val)
-            }
-        }
+    /// Generates a value from `low..=high`, serialized the way it is passed to the test
+    pub fn gen(self, rng: &mut StdRng, low: &BigUint, high: &BigUint) -> Vec<Felt252> {
+        self.serialize(rng.gen_biguint_range(low, &high.add(BigUint::one())))
     }
 
-    pub fn min(self) -> Vec<Felt252> {
+    pub fn serialize(self, value: BigUint) -> Vec<Felt252> {
         match self {
             CairoType::U8
             | CairoType::U16
             | CairoType::U32
             | CairoType::U64
             | CairoType::U128
-            | CairoType::Felt252 => vec![Felt252::from(Self::low())],
-            CairoType::U256 => vec![Felt252::from(Self::low()), Felt252::from(Self::low())],
+            | CairoType::Felt252
+            | CairoType::ContractAddress => vec![Felt252::from(value)],
+            CairoType::U256 => u256_to_felt252(value),
         }
     }
 
-    pub fn max(self) -> Vec<Felt252> {
+    /// Reads a value serialized with [`CairoType::serialize`] from the front of `serialized`
+    pub fn deserialize(self, serialized: &mut impl Iterator<Item = Felt252>) -> Option<BigUint> {
         match self {
-            CairoType::U8
-            | CairoType::U16
-            | CairoType::U32
-            | CairoType::U64
-            | CairoType::U128
-            | CairoType::Felt252 => vec![Felt252::from(self.high().sub(BigUint::one()))],
-            CairoType::U256 => u256_to_felt252(self.high().sub(BigUint::one())),
+            CairoType::U256 => {
+                let low = serialized.next()?.to_biguint();
+                let high = serialized.next()?.to_biguint();
+                Some(high.shl(128) + low)
+            }
+            _ => serialized.next().map(|value| value.to_biguint()),
         }
     }
 }
@@ -87,13 +78,17 @@ fn u256_to_felt252(val: BigUint) -> Vec<Felt252> {
 impl CairoType {
     pub fn from_name(name: &str) -> Result<Self> {
         match name {
-            "u8" => Ok(Self::U8),
-            "u16" => Ok(Self::U16),
-            "u32" => Ok(Self::U32),
-            "u64" => Ok(Self::U64),
-            "u128" => Ok(Self::U128),
+            "u8" | "core::integer::u8" => Ok(Self::U8),
+            "u16" | "core::integer::u16" => Ok(Self::U16),
+            "u32" | "core::integer::u32" => Ok(Self::U32),
+            "u64" | "core::integer::u64" => Ok(Self::U64),
+            "u128" | "core::integer::u128" => Ok(Self::U128),
             "u256" | "core::integer::u256" => Ok(Self::U256),
-            "felt252" => Ok(Self::Felt252),
+            "felt252" | "core::felt252" => Ok(Self::Felt252),
+            "ContractAddress"
+            | "starknet::ContractAddress"
+            | "starknet::contract_address::ContractAddress"
+            | "core::starknet::contract_address::ContractAddress" => Ok(Self::ContractAddress),
             _ => Err(anyhow!(
                 "Tried to use incorrect type for fuzzing. Type = {name} is not supported"
             )),
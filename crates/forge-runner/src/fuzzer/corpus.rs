@@ -0,0 +1,303 @@
+use crate::fuzzer::arguments::CairoType;
+use anyhow::{anyhow, Context, Result};
+use cairo_vm::Felt252;
+use camino::{Utf8Path, Utf8PathBuf};
+use num_bigint::BigUint;
+use num_traits::Num;
+use serde::{Deserialize, Serialize};
+use shared::print::{emit_warning, DiagnosticCode};
+use starknet_types_core::hash::{Poseidon, StarkHash};
+use std::fs;
+
+/// Version of the corpus entry format, entries saved with other versions are not replayed
+const CORPUS_VERSION: u32 = 1;
+
+/// Failing inputs of a single fuzz test, saved to be replayed on subsequent runs
+#[derive(Debug)]
+pub struct FuzzCorpus {
+    dir: Utf8PathBuf,
+    arg_types: Vec<String>,
+    cairo_types: Vec<CairoType>,
+}
+
+/// Input loaded from the corpus
+#[derive(Debug)]
+pub struct CorpusInput {
+    pub path: Utf8PathBuf,
+    pub arguments: Vec<Felt252>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CorpusEntry {
+    version: u32,
+    arguments: Vec<CorpusArgument>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct CorpusArgument {
+    #[serde(rename = "type")]
+    ty: String,
+    value: String,
+}
+
+impl FuzzCorpus {
+    pub fn new(corpus_dir: &Utf8Path, test_name: &str, arg_types: &[&str]) -> Result<Self> {
+        let cairo_types = arg_types
+            .iter()
+            .map(|name| CairoType::from_name(name))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            // `::` can't be used in paths on all platforms
+            dir: corpus_dir.join(test_name.replace("::", "__")),
+            arg_types: arg_types.iter().map(ToString::to_string).collect(),
+            cairo_types,
+        })
+    }
+
+    /// Loads inputs saved for the test, sorted by their paths.
+    /// Entries that can't be replayed with the current parameters of the test are skipped,
+    /// and removed if `prune` is set.
+    pub fn load(&self, prune: bool) -> Result<Vec<CorpusInput>> {
+        if !self.dir.exists() {
+            return Ok(vec![]);
+        }
+
+        let mut paths = self
+            .dir
+            .read_dir_utf8()
+            .with_context(|| format!("Failed to read fuzz corpus directory = {}", self.dir))?
+            .map(|entry| entry.map(|entry| entry.into_path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.retain(|path| path.extension() == Some("json"));
+        paths.sort();
+
+        let mut inputs = vec![];
+        for path in paths {
+            match self.read_entry(&path) {
+                Ok(arguments) => inputs.push(CorpusInput { path, arguments }),
+                Err(error) => {
+                    if prune {
+                        remove(&path)?;
+                    } else {
+                        emit_warning(
+                            DiagnosticCode::InvalidFuzzCorpus,
+                            error.context(format!(
+                                "Skipping fuzz corpus entry = {path}, use --prune-fuzz-corpus to remove it"
+                            )),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    /// Saves a failing input, returns the path of the entry
+    pub fn save(&self, arguments: &[Felt252]) -> Result<Utf8PathBuf> {
+        let entry = CorpusEntry::new(&self.arg_types, &self.cairo_types, arguments)
+            .ok_or_else(|| anyhow!("Failing arguments do not match the fuzzed parameters"))?;
+
+        let hash = Poseidon::hash_array(arguments).to_fixed_hex_string();
+        let path = self.dir.join(format!("{}.json", &hash[2..18]));
+
+        fs::create_dir_all(&self.dir).context("Failed to create a fuzz corpus directory")?;
+        fs::write(&path, serde_json::to_string_pretty(&entry)?)
+            .with_context(|| format!("Failed to save fuzz corpus entry = {path}"))?;
+
+        Ok(path)
+    }
+
+    fn read_entry(&self, path: &Utf8Path) -> Result<Vec<Felt252>> {
+        let content = fs::read_to_string(path)?;
+        let entry: CorpusEntry = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse fuzz corpus entry = {path}"))?;
+
+        entry.arguments(&self.arg_types, &self.cairo_types)
+    }
+}
+
+impl CorpusInput {
+    /// Removes the entry, e.g. when it no longer fails
+    pub fn remove(&self) -> Result<()> {
+        remove(&self.path)
+    }
+}
+
+fn remove(path: &Utf8Path) -> Result<()> {
+    fs::remove_file(path).with_context(|| format!("Failed to remove fuzz corpus entry = {path}"))
+}
+
+impl CorpusEntry {
+    fn new(arg_types: &[String], cairo_types: &[CairoType], arguments: &[Felt252]) -> Option<Self> {
+        let mut serialized = arguments.iter().copied();
+        let arguments = arg_types
+            .iter()
+            .zip(cairo_types)
+            .map(|(ty, cairo_type)| {
+                let value = cairo_type.deserialize(&mut serialized)?;
+                Some(CorpusArgument {
+                    ty: ty.clone(),
+                    value: format_value(*cairo_type, &value),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        if serialized.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            version: CORPUS_VERSION,
+            arguments,
+        })
+    }
+
+    fn arguments(&self, arg_types: &[String], cairo_types: &[CairoType]) -> Result<Vec<Felt252>> {
+        if self.version != CORPUS_VERSION {
+            return Err(anyhow!(
+                "Entry was saved with version = {}, but version = {CORPUS_VERSION} is supported",
+                self.version
+            ));
+        }
+
+        let saved_types: Vec<_> = self.arguments.iter().map(|argument| &argument.ty).collect();
+        if saved_types.len() != arg_types.len()
+            || saved_types
+                .iter()
+                .zip(arg_types)
+                .any(|(saved, ty)| *saved != ty)
+        {
+            return Err(anyhow!(
+                "Entry was saved for parameters of types = {saved_types:?}, but the test takes {arg_types:?}"
+            ));
+        }
+
+        let mut serialized = vec![];
+        for (argument, cairo_type) in self.arguments.iter().zip(cairo_types) {
+            let value = parse_value(&argument.value)
+                .filter(|value| *value < cairo_type.high())
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Value = {} is not a valid {} argument",
+                        argument.value,
+                        argument.ty
+                    )
+                })?;
+            serialized.extend(cairo_type.serialize(value));
+        }
+
+        Ok(serialized)
+    }
+}
+
+/// Integers are saved as decimal numbers, felts and addresses as hex
+fn format_value(cairo_type: CairoType, value: &BigUint) -> String {
+    match cairo_type {
+        CairoType::Felt252 | CairoType::ContractAddress => format!("{value:#x}"),
+        _ => value.to_string(),
+    }
+}
+
+fn parse_value(value: &str) -> Option<BigUint> {
+    match value.strip_prefix("0x") {
+        Some(hex) => BigUint::from_str_radix(hex, 16).ok(),
+        None => BigUint::from_str_radix(value, 10).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types(names: &[&str]) -> (Vec<String>, Vec<CairoType>) {
+        (
+            names.iter().map(ToString::to_string).collect(),
+            names
+                .iter()
+                .map(|name| CairoType::from_name(name).unwrap())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn entry_roundtrip() {
+        let (arg_types, cairo_types) = types(&["core::integer::u256", "felt252", "u8"]);
+        let arguments = [
+            Felt252::from(1000_u16),
+            Felt252::from(1_u8),
+            Felt252::from(255_u8),
+            Felt252::from(7_u8),
+        ];
+
+        let entry = CorpusEntry::new(&arg_types, &cairo_types, &arguments).unwrap();
+
+        assert_eq!(
+            entry.arguments,
+            [
+                CorpusArgument {
+                    ty: "core::integer::u256".to_string(),
+                    value: "340282366920938463463374607431768212456".to_string(),
+                },
+                CorpusArgument {
+                    ty: "felt252".to_string(),
+                    value: "0xff".to_string(),
+                },
+                CorpusArgument {
+                    ty: "u8".to_string(),
+                    value: "7".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            entry.arguments(&arg_types, &cairo_types).unwrap(),
+            arguments
+        );
+    }
+
+    #[test]
+    fn changed_parameters_invalidate_entry() {
+        let (arg_types, cairo_types) = types(&["u64", "u64"]);
+        let entry = CorpusEntry::new(
+            &arg_types,
+            &cairo_types,
+            &[Felt252::from(1_u8), Felt252::from(2_u8)],
+        )
+        .unwrap();
+
+        let (arg_types, cairo_types) = types(&["u64", "u32"]);
+        assert!(entry.arguments(&arg_types, &cairo_types).is_err());
+
+        let (arg_types, cairo_types) = types(&["u64"]);
+        assert!(entry.arguments(&arg_types, &cairo_types).is_err());
+    }
+
+    #[test]
+    fn other_version_invalidates_entry() {
+        let (arg_types, cairo_types) = types(&["u8"]);
+        let mut entry = CorpusEntry::new(&arg_types, &cairo_types, &[Felt252::from(1_u8)]).unwrap();
+        entry.version = CORPUS_VERSION + 1;
+
+        assert!(entry.arguments(&arg_types, &cairo_types).is_err());
+    }
+
+    #[test]
+    fn value_out_of_type_range_is_rejected() {
+        let (arg_types, cairo_types) = types(&["u8"]);
+        let entry: CorpusEntry = serde_json::from_str(
+            r#"{ "version": 1, "arguments": [{ "type": "u8", "value": "256" }] }"#,
+        )
+        .unwrap();
+
+        assert!(entry.arguments(&arg_types, &cairo_types).is_err());
+    }
+
+    #[test]
+    fn mismatched_arguments_are_not_saved() {
+        let (arg_types, cairo_types) = types(&["u256"]);
+
+        assert!(CorpusEntry::new(&arg_types, &cairo_types, &[Felt252::from(1_u8)]).is_none());
+        assert!(CorpusEntry::new(&arg_types, &cairo_types, &[Felt252::from(1_u8); 3]).is_none());
+    }
+}
@@ -1,6 +1,7 @@
-use crate::fuzzer::RunParams;
+use crate::fuzzer::{RunParams, Shrinker};
 use anyhow::Result;
 use cairo_vm::Felt252;
+use cheatnet::runtime_extensions::forge_config_extension::config::RawFuzzerRange;
 use rand::prelude::StdRng;
 use rand::SeedableRng;
 use std::num::NonZeroU32;
@@ -12,9 +13,14 @@ pub struct RandomFuzzer {
 }
 
 impl RandomFuzzer {
-    pub fn create(seed: u64, total_runs: NonZeroU32, arguments: &[&str]) -> Result<Self> {
+    pub fn create(
+        seed: u64,
+        total_runs: NonZeroU32,
+        arguments: &[&str],
+        ranges: &[RawFuzzerRange],
+    ) -> Result<Self> {
         let mut rng = StdRng::seed_from_u64(seed);
-        let run_params = RunParams::from(&mut rng, total_runs, arguments)?;
+        let run_params = RunParams::from(&mut rng, total_runs, arguments, ranges)?;
 
         Ok(Self { rng, run_params })
     }
@@ -31,16 +37,24 @@ impl RandomFuzzer {
                 let current_run = self.run_params.executed_runs;
 
                 if argument.run_with_min_value == current_run {
-                    argument.cairo_type.min()
+                    argument.cairo_type.serialize(argument.low.clone())
                 } else if argument.run_with_max_value == current_run {
-                    argument.cairo_type.max()
+                    argument.cairo_type.serialize(argument.high.clone())
                 } else {
-                    argument.cairo_type.gen(&mut self.rng)
+                    argument
+                        .cairo_type
+                        .gen(&mut self.rng, &argument.low, &argument.high)
                 }
             })
             .collect()
     }
 
+    /// Returns `None` if `failing_arguments` were not generated for the fuzzed arguments
+    #[must_use]
+    pub fn shrinker(&self, failing_arguments: &[Felt252]) -> Option<Shrinker> {
+        Shrinker::new(&self.run_params.arguments, failing_arguments)
+    }
+
     fn next_run(&mut self) {
         self.run_params.executed_runs += 1;
     }
@@ -50,8 +64,9 @@ impl RandomFuzzer {
 mod tests {
     use super::*;
     use crate::fuzzer::{arguments::CairoType, FuzzerArg};
+    use cheatnet::runtime_extensions::forge_config_extension::config::RawU256;
     use num_bigint::BigUint;
-    use num_traits::Zero;
+    use num_traits::{One, Zero};
     use rand::{thread_rng, RngCore};
 
     impl FuzzerArg {
@@ -62,6 +77,8 @@ mod tests {
         ) -> Self {
             Self {
                 cairo_type,
+                low: CairoType::low(),
+                high: cairo_type.high() - BigUint::one(),
                 run_with_min_value,
                 run_with_max_value,
             }
@@ -164,6 +181,7 @@ mod tests {
             seed,
             NonZeroU32::new(3).unwrap(),
             &["felt252", "felt252", "felt252"],
+            &[],
         )
         .unwrap();
         let values = fuzzer.next_args();
@@ -172,6 +190,7 @@ mod tests {
             seed,
             NonZeroU32::new(3).unwrap(),
             &["felt252", "felt252", "felt252"],
+            &[],
         )
         .unwrap();
         let values_from_seed = fuzzer.next_args();
@@ -186,20 +205,21 @@ mod tests {
         let arguments = vec!["felt252", "felt252", "felt252"];
         let args_number = arguments.len();
 
-        let mut fuzzer = RandomFuzzer::create(seed, runs_number, &arguments).unwrap();
+        let mut fuzzer = RandomFuzzer::create(seed, runs_number, &arguments, &[]).unwrap();
 
         let mut min_used = vec![false; args_number];
         let mut max_used = vec![false; args_number];
 
+        let min = Felt252::ZERO;
+        let max = Felt252::MAX;
+
         for _ in 1..=runs_number.get() {
             let values = fuzzer.next_args();
             for (i, value) in values.iter().enumerate() {
-                assert!(
-                    *value >= CairoType::Felt252.min()[0] && *value <= CairoType::Felt252.max()[0]
-                );
-                if *value == CairoType::Felt252.min()[0] {
+                assert!(*value >= min && *value <= max);
+                if *value == min {
                     min_used[i] = true;
-                } else if *value == CairoType::Felt252.max()[0] {
+                } else if *value == max {
                     max_used[i] = true;
                 }
             }
@@ -215,6 +235,7 @@ mod tests {
             1234,
             NonZeroU32::new(512).unwrap(),
             &["felt252", "invalid", "args"],
+            &[],
         );
         let err = result.unwrap_err();
 
@@ -226,11 +247,126 @@ mod tests {
     #[test]
     fn fuzzer_less_than_3_runs() {
         for runs in 1..2 {
-            let result = RandomFuzzer::create(1234, NonZeroU32::new(runs).unwrap(), &["felt252"]);
+            let result =
+                RandomFuzzer::create(1234, NonZeroU32::new(runs).unwrap(), &["felt252"], &[]);
             let mut fuzzer = result.unwrap();
 
             // just check if it panics
             fuzzer.next_args();
         }
     }
+
+    fn range(index: usize, low: u128, high: u128) -> RawFuzzerRange {
+        RawFuzzerRange {
+            index,
+            low: RawU256 { low, high: 0 },
+            high: RawU256 { low: high, high: 0 },
+        }
+    }
+
+    #[test]
+    fn values_respect_ranges() {
+        let seed = thread_rng().next_u64();
+        let runs_number = NonZeroU32::new(50).unwrap();
+        let ranges = [range(0, 10, 20), range(1, 1, 10_000)];
+
+        let mut fuzzer = RandomFuzzer::create(seed, runs_number, &["u8", "u256"], &ranges).unwrap();
+
+        let mut min_used = false;
+        let mut max_used = false;
+
+        for _ in 1..=runs_number.get() {
+            let values = fuzzer.next_args();
+            // u256 is serialized as low and high felts
+            assert_eq!(values.len(), 3);
+
+            assert!(values[0] >= Felt252::from(10_u8) && values[0] <= Felt252::from(20_u8));
+            assert!(values[1] >= Felt252::from(1_u8) && values[1] <= Felt252::from(10_000_u16));
+            assert_eq!(values[2], Felt252::ZERO);
+
+            min_used |= values[1] == Felt252::from(1_u8);
+            max_used |= values[1] == Felt252::from(10_000_u16);
+        }
+
+        assert!(min_used && max_used);
+    }
+
+    #[test]
+    fn contract_address_is_in_address_range() {
+        let seed = thread_rng().next_u64();
+        let runs_number = NonZeroU32::new(20).unwrap();
+        let max_address = Felt252::from(BigUint::one() << 251) - Felt252::ONE;
+
+        let mut fuzzer =
+            RandomFuzzer::create(seed, runs_number, &["ContractAddress"], &[]).unwrap();
+
+        for _ in 1..=runs_number.get() {
+            assert!(fuzzer.next_args()[0] <= max_address);
+        }
+    }
+
+    #[test]
+    fn contract_address_is_fuzzed_by_qualified_path() {
+        let seed = thread_rng().next_u64();
+        let runs_number = NonZeroU32::new(3).unwrap();
+
+        for path in [
+            "starknet::ContractAddress",
+            "starknet::contract_address::ContractAddress",
+            "core::starknet::contract_address::ContractAddress",
+        ] {
+            let mut fuzzer = RandomFuzzer::create(seed, runs_number, &[path], &[]).unwrap();
+
+            assert_eq!(fuzzer.next_args().len(), 1);
+        }
+    }
+
+    #[test]
+    fn same_seed_reproduces_all_runs() {
+        let seed = thread_rng().next_u64();
+        let runs_number = NonZeroU32::new(20).unwrap();
+        let arguments = ["u64", "felt252", "u256", "ContractAddress"];
+        let ranges = [range(0, 5, 500)];
+
+        let generate_all = || {
+            let mut fuzzer = RandomFuzzer::create(seed, runs_number, &arguments, &ranges).unwrap();
+            (1..=runs_number.get())
+                .map(|_| fuzzer.next_args())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(generate_all(), generate_all());
+    }
+
+    #[test]
+    fn create_fuzzer_with_empty_range() {
+        let result = RandomFuzzer::create(
+            1234,
+            NonZeroU32::new(10).unwrap(),
+            &["u8"],
+            &[range(0, 20, 10)],
+        );
+        let err = result.unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Fuzzer range of argument 0 is empty, low = 20 is greater than high = 10"
+        );
+    }
+
+    #[test]
+    fn create_fuzzer_with_range_exceeding_type() {
+        let result = RandomFuzzer::create(
+            1234,
+            NonZeroU32::new(10).unwrap(),
+            &["u8"],
+            &[range(0, 0, 256)],
+        );
+        let err = result.unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Fuzzer range of argument 0 exceeds values of its type, high = 256 is greater than 255"
+        );
+    }
 }
@@ -0,0 +1,253 @@
+use crate::fuzzer::arguments::CairoType;
+use crate::fuzzer::FuzzerArg;
+use cairo_vm::Felt252;
+use num_bigint::BigUint;
+use num_traits::One;
+
+/// Minimizes a failing input, one argument at a time.
+///
+/// Every argument is binary searched between its lower bound and the smallest value known to fail,
+/// so each candidate halves the distance to the lower bound. Passes over all arguments are repeated
+/// until none of them can be made smaller, as changing one argument can let another one shrink.
+#[derive(Debug)]
+pub struct Shrinker {
+    types: Vec<CairoType>,
+    lower_bounds: Vec<BigUint>,
+    /// Smallest input known to fail
+    values: Vec<BigUint>,
+    /// Argument that is currently shrunk
+    index: usize,
+    /// Smallest value of the current argument that was not yet found to pass
+    low: BigUint,
+    candidate: Option<BigUint>,
+    shrunk_in_pass: bool,
+}
+
+impl Shrinker {
+    /// Returns `None` if `failing_arguments` do not match the fuzzed arguments
+    pub(crate) fn new(arguments: &[FuzzerArg], failing_arguments: &[Felt252]) -> Option<Self> {
+        let mut serialized = failing_arguments.iter().copied();
+        let values = arguments
+            .iter()
+            .map(|argument| argument.cairo_type.deserialize(&mut serialized))
+            .collect::<Option<Vec<_>>>()?;
+
+        if serialized.next().is_some() {
+            return None;
+        }
+
+        let lower_bounds: Vec<_> = arguments
+            .iter()
+            .map(|argument| argument.low.clone())
+            .collect();
+
+        Some(Self {
+            types: arguments
+                .iter()
+                .map(|argument| argument.cairo_type)
+                .collect(),
+            low: lower_bounds.first().cloned().unwrap_or_default(),
+            lower_bounds,
+            values,
+            index: 0,
+            candidate: None,
+            shrunk_in_pass: false,
+        })
+    }
+
+    /// Next input to be tried, `None` once the input can't be minimized any further
+    pub fn next_candidate(&mut self) -> Option<Vec<Felt252>> {
+        assert!(
+            self.candidate.is_none(),
+            "Result of the previous candidate was not reported"
+        );
+
+        while self.index < self.values.len() {
+            let high = &self.values[self.index];
+
+            if &self.low < high {
+                // Lower bound is tried first, as it is most often the minimal failing value
+                let candidate = if self.low == self.lower_bounds[self.index] {
+                    self.low.clone()
+                } else {
+                    &self.low + (high - &self.low) / BigUint::from(2_u8)
+                };
+                let input = self.serialize_with(&candidate);
+                self.candidate = Some(candidate);
+
+                return Some(input);
+            }
+
+            self.next_argument();
+        }
+
+        None
+    }
+
+    /// Reports whether the test failed with the last candidate
+    pub fn report(&mut self, failed: bool) {
+        let candidate = self
+            .candidate
+            .take()
+            .expect("No candidate was returned by the shrinker");
+
+        if failed {
+            self.values[self.index] = candidate;
+            self.shrunk_in_pass = true;
+        } else {
+            self.low = candidate + BigUint::one();
+        }
+    }
+
+    /// Smallest input known to fail
+    #[must_use]
+    pub fn minimized(&self) -> Vec<Felt252> {
+        self.types
+            .iter()
+            .zip(&self.values)
+            .flat_map(|(cairo_type, value)| cairo_type.serialize(value.clone()))
+            .collect()
+    }
+
+    fn next_argument(&mut self) {
+        self.index += 1;
+
+        // A single argument is already minimal after its search, it doesn't depend on other ones
+        if self.index == self.values.len() && self.shrunk_in_pass && self.values.len() > 1 {
+            self.index = 0;
+            self.shrunk_in_pass = false;
+        }
+
+        if let Some(low) = self.lower_bounds.get(self.index) {
+            self.low = low.clone();
+        }
+    }
+
+    fn serialize_with(&self, candidate: &BigUint) -> Vec<Felt252> {
+        self.types
+            .iter()
+            .zip(&self.values)
+            .enumerate()
+            .flat_map(|(index, (cairo_type, value))| {
+                let value = if index == self.index {
+                    candidate
+                } else {
+                    value
+                };
+                cairo_type.serialize(value.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::ToPrimitive;
+
+    fn shrink(
+        arguments: &[FuzzerArg],
+        failing_arguments: &[Felt252],
+        fails: impl Fn(&[Felt252]) -> bool,
+    ) -> (Vec<Felt252>, usize) {
+        let mut shrinker = Shrinker::new(arguments, failing_arguments).unwrap();
+        let mut runs = 0;
+
+        while let Some(candidate) = shrinker.next_candidate() {
+            shrinker.report(fails(&candidate));
+            runs += 1;
+        }
+
+        (shrinker.minimized(), runs)
+    }
+
+    fn as_u128(value: &Felt252) -> u128 {
+        value.to_biguint().to_u128().unwrap()
+    }
+
+    #[test]
+    fn shrinks_to_boundary() {
+        let arguments = [FuzzerArg::new(CairoType::U128, 0, 0)];
+
+        let (minimized, runs) = shrink(&arguments, &[Felt252::from(u128::MAX)], |input| {
+            as_u128(&input[0]) >= 1000
+        });
+
+        assert_eq!(minimized, [Felt252::from(1000_u16)]);
+        // Binary search needs a logarithmic number of runs
+        assert!(runs <= 130, "{runs}");
+    }
+
+    #[test]
+    fn shrinks_to_lower_bound() {
+        let mut argument = FuzzerArg::new(CairoType::U64, 0, 0);
+        argument.low = BigUint::from(50_u8);
+
+        let (minimized, runs) = shrink(&[argument], &[Felt252::from(12345_u16)], |_| true);
+
+        assert_eq!(minimized, [Felt252::from(50_u8)]);
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn shrinks_every_argument() {
+        let arguments = [
+            FuzzerArg::new(CairoType::U256, 0, 0),
+            FuzzerArg::new(CairoType::U8, 0, 0),
+        ];
+        let failing = [
+            Felt252::from(u128::MAX),
+            Felt252::from(7_u8),
+            Felt252::from(200_u8),
+        ];
+
+        // Fails when a.high > 3 and b > 100
+        let (minimized, _) = shrink(&arguments, &failing, |input| {
+            as_u128(&input[1]) > 3 && as_u128(&input[2]) > 100
+        });
+
+        assert_eq!(
+            minimized,
+            [
+                Felt252::from(0_u8),
+                Felt252::from(4_u8),
+                Felt252::from(101_u8)
+            ]
+        );
+    }
+
+    #[test]
+    fn repeats_pass_when_arguments_depend_on_each_other() {
+        let arguments = [
+            FuzzerArg::new(CairoType::U32, 0, 0),
+            FuzzerArg::new(CairoType::U32, 0, 0),
+        ];
+
+        // `a` can't shrink until `b` does
+        let (minimized, _) = shrink(
+            &arguments,
+            &[Felt252::from(100_u8), Felt252::from(100_u8)],
+            |input| as_u128(&input[0]) >= as_u128(&input[1]) && as_u128(&input[1]) >= 10,
+        );
+
+        assert_eq!(minimized, [Felt252::from(10_u8), Felt252::from(10_u8)]);
+    }
+
+    #[test]
+    fn already_minimal_input_needs_no_runs() {
+        let arguments = [FuzzerArg::new(CairoType::Felt252, 0, 0)];
+
+        let (minimized, runs) = shrink(&arguments, &[Felt252::from(0_u8)], |_| true);
+
+        assert_eq!(minimized, [Felt252::from(0_u8)]);
+        assert_eq!(runs, 0);
+    }
+
+    #[test]
+    fn mismatched_arguments_are_rejected() {
+        let arguments = [FuzzerArg::new(CairoType::U256, 0, 0)];
+
+        assert!(Shrinker::new(&arguments, &[Felt252::from(1_u8)]).is_none());
+        assert!(Shrinker::new(&arguments, &[Felt252::from(1_u8); 3]).is_none());
+    }
+}
@@ -8,16 +8,14 @@ use blockifier::fee::gas_usage::{
     get_consumed_message_to_l2_emissions_cost, get_da_gas_cost,
     get_log_message_to_l1_emissions_cost,
 };
-use blockifier::state::cached_state::{CachedState, StorageEntry};
+use blockifier::state::cached_state::CachedState;
 use blockifier::state::errors::StateError;
 use blockifier::transaction::objects::{GasVector, HasRelatedFeeType};
 use blockifier::utils::u128_from_usize;
-use cairo_vm::Felt252;
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
 use cheatnet::state::ExtendedStateReader;
-use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use cheatnet::state_changes::StateMaps;
 use starknet_api::transaction::EventContent;
-use std::collections::HashMap;
 
 pub fn calculate_used_gas(
     transaction_context: &TransactionContext,
@@ -129,36 +127,12 @@ fn get_messages_costs(
 fn clear_compiled_class_hash_update(
     state_changes: blockifier::state::cached_state::StateChanges,
 ) -> blockifier::state::cached_state::StateChanges {
-    // copy-pasted blockifier::state::cached_state::StateChanges
-    // link: https://github.com/starkware-libs/blockifier/blob/eb4958ad98d92dc8f8b493edc8dce1a79038c94d/crates/blockifier/src/state/cached_state.rs#L319
-    struct StateMapsCopy {
-        _nonces: HashMap<ContractAddress, Nonce>,
-        _class_hashes: HashMap<ContractAddress, ClassHash>,
-        _storage: HashMap<StorageEntry, Felt252>,
-        compiled_class_hashes: HashMap<ClassHash, CompiledClassHash>,
-        _declared_contracts: HashMap<ClassHash, bool>,
-    }
-
-    // use to cast blockifier::state::cached_state::StateChanges into same struct but public for us
-    union StateMapsHack {
-        origin: std::mem::ManuallyDrop<blockifier::state::cached_state::StateChanges>,
-        public: std::mem::ManuallyDrop<StateMapsCopy>,
-    }
+    let mut state_maps = StateMaps::from(state_changes);
+    // compiled_class_hash_updates is used only for keeping track of declares
+    // which we don't want to include in gas cost
+    state_maps.compiled_class_hashes.clear();
 
-    let mut u = StateMapsHack {
-        origin: std::mem::ManuallyDrop::new(state_changes),
-    };
-    unsafe {
-        // compiled_class_hash_updates is used only for keeping track of declares
-        // which we don't want to include in gas cost
-        // clear on public struct
-        (*u.public).compiled_class_hashes.clear();
-
-        // cast back into original blockifier::state::cached_state::StateChanges
-        std::mem::ManuallyDrop::<blockifier::state::cached_state::StateChanges>::into_inner(
-            u.origin,
-        )
-    }
+    state_maps.into()
 }
 
 fn get_l1_data_cost(
@@ -199,8 +173,37 @@ pub fn check_available_gas(
                 )),
                 arguments,
                 test_statistics: (),
+                captured_output: None,
             }
         }
         _ => summary,
     }
 }
+
+/// Fails a passed test which used more gas than `max_gas` from `[tool.snforge]`
+#[must_use]
+pub fn check_max_gas(
+    max_gas: Option<u128>,
+    summary: TestCaseSummary<Single>,
+) -> TestCaseSummary<Single> {
+    match (max_gas, summary) {
+        (
+            Some(max_gas),
+            TestCaseSummary::Passed {
+                name,
+                arguments,
+                gas_info,
+                ..
+            },
+        ) if gas_info > max_gas => TestCaseSummary::Failed {
+            name,
+            msg: Some(format!(
+                "\n\tTest cost exceeded max_gas = {max_gas}. Consumed gas: ~{gas_info}"
+            )),
+            arguments,
+            test_statistics: (),
+            captured_output: None,
+        },
+        (_, summary) => summary,
+    }
+}
@@ -1,10 +1,11 @@
 use crate::build_trace_data::test_sierra_program_path::VersionedProgramPath;
-use crate::coverage_api::run_coverage;
-use crate::forge_config::{ExecutionDataToSave, ForgeConfig, TestRunnerConfig};
-use crate::fuzzer::RandomFuzzer;
+use crate::coverage_api::save_coverage_data;
+use crate::forge_config::{ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig};
+use crate::fuzzer::{FuzzCorpus, RandomFuzzer, Shrinker, DEFAULT_SHRINK_RUNS, SHRINK_TIME_LIMIT};
 use crate::running::{run_fuzz_test, run_test};
+use crate::running_tests::RunningTests;
 use crate::test_case_summary::TestCaseSummary;
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use build_trace_data::save_trace_data;
 use cairo_lang_sierra::program::{ConcreteTypeLongId, Function, TypeDeclaration};
 use camino::Utf8Path;
@@ -15,12 +16,14 @@ use package_tests::with_config_resolved::{
     TestCaseWithResolvedConfig, TestTargetWithResolvedConfig,
 };
 use profiler_api::run_profiler;
-use shared::print::print_as_warning;
+use shared::print::{emit_warning, DiagnosticCode};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::num::NonZeroU32;
 use std::sync::Arc;
-use test_case_summary::{AnyTestCaseSummary, Fuzzing};
+use std::time::Instant;
+use test_case_summary::{AnyTestCaseSummary, Fuzzing, Single};
 use tokio::sync::mpsc::{channel, Sender};
+use tokio::sync::{Mutex, OwnedMutexGuard};
 use tokio::task::JoinHandle;
 use universal_sierra_compiler_api::AssembledProgramWithDebugInfo;
 
@@ -30,6 +33,8 @@ pub mod expected_result;
 pub mod forge_config;
 pub mod package_tests;
 pub mod profiler_api;
+pub mod resource_report;
+pub mod running_tests;
 pub mod test_case_summary;
 pub mod test_target_summary;
 
@@ -39,6 +44,7 @@ pub mod printing;
 pub mod running;
 
 pub const CACHE_DIR: &str = ".snfoundry_cache";
+pub const FUZZ_CORPUS_DIR: &str = ".snfoundry/fuzz_corpus";
 
 const BUILTINS: [&str; 11] = [
     "Pedersen",
@@ -60,32 +66,22 @@ pub trait TestCaseFilter {
 
 pub fn maybe_save_trace_and_profile(
     result: &AnyTestCaseSummary,
-    execution_data_to_save: ExecutionDataToSave,
-) -> Result<Option<PathBuf>> {
+    output_config: &OutputConfig,
+) -> Result<()> {
+    let execution_data_to_save = output_config.execution_data_to_save;
+
     if let AnyTestCaseSummary::Single(TestCaseSummary::Passed {
         name, trace_data, ..
     }) = result
     {
-        if execution_data_to_save.is_vm_trace_needed() {
+        if execution_data_to_save.trace || execution_data_to_save.profile {
             let trace_path = save_trace_data(name, trace_data)?;
             if execution_data_to_save.profile {
                 run_profiler(name, &trace_path)?;
             }
-            return Ok(Some(trace_path));
         }
-    }
-    Ok(None)
-}
-
-pub fn maybe_generate_coverage(
-    execution_data_to_save: ExecutionDataToSave,
-    saved_trace_data_paths: &[PathBuf],
-) -> Result<()> {
-    if execution_data_to_save.coverage {
-        if saved_trace_data_paths.is_empty() {
-            print_as_warning(&anyhow!("No trace data to generate coverage from"));
-        } else {
-            run_coverage(saved_trace_data_paths)?;
+        if execution_data_to_save.coverage {
+            save_coverage_data(name, trace_data, &output_config.coverage_data_dir)?;
         }
     }
     Ok(())
@@ -111,7 +107,10 @@ pub fn maybe_save_versioned_program(
     Ok(maybe_versioned_program_path)
 }
 
+/// Tests marked with `#[serial]` hold `serial_lock` while running, so they never overlap with each other.
+/// The test is added to `running_tests` once it starts, it is up to the caller to remove it when it finishes
 #[must_use]
+#[allow(clippy::too_many_arguments)]
 pub fn run_for_test_case(
     args: Vec<ConcreteTypeLongId>,
     case: Arc<TestCaseWithResolvedConfig>,
@@ -119,21 +118,26 @@ pub fn run_for_test_case(
     forge_config: Arc<ForgeConfig>,
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
+    serial_lock: Arc<Mutex<()>>,
+    running_tests: RunningTests,
 ) -> JoinHandle<Result<AnyTestCaseSummary>> {
     if args.is_empty() {
         tokio::task::spawn(async move {
+            let _guard = lock_if_serial(&case, serial_lock).await;
             let res = run_test(
                 case,
                 casm_program,
                 forge_config.test_runner_config.clone(),
                 maybe_versioned_program_path,
                 send,
+                running_tests,
             )
             .await??;
             Ok(AnyTestCaseSummary::Single(res))
         })
     } else {
         tokio::task::spawn(async move {
+            let _guard = lock_if_serial(&case, serial_lock).await;
             let res = run_with_fuzzing(
                 args,
                 case,
@@ -141,6 +145,7 @@ pub fn run_for_test_case(
                 forge_config.test_runner_config.clone(),
                 maybe_versioned_program_path,
                 send,
+                running_tests,
             )
             .await??;
             Ok(AnyTestCaseSummary::Fuzzing(res))
@@ -148,6 +153,17 @@ pub fn run_for_test_case(
     }
 }
 
+async fn lock_if_serial(
+    case: &TestCaseWithResolvedConfig,
+    serial_lock: Arc<Mutex<()>>,
+) -> Option<OwnedMutexGuard<()>> {
+    if case.config.serial {
+        Some(serial_lock.lock_owned().await)
+    } else {
+        None
+    }
+}
+
 fn argument_type_name(arg: &ConcreteTypeLongId) -> &str {
     let name = arg.generic_id.0.as_str();
 
@@ -165,6 +181,20 @@ fn argument_type_name(arg: &ConcreteTypeLongId) -> &str {
     }
 }
 
+/// Checks that the fuzzed arguments have supported types and that the ranges of the fuzzer config fit them,
+/// the same way as before running the test
+pub fn validate_fuzzer_config(
+    args: &[ConcreteTypeLongId],
+    fuzzer_config: Option<&RawFuzzerConfig>,
+) -> Result<()> {
+    let arg_types = args.iter().map(argument_type_name).collect::<Vec<_>>();
+    let ranges = fuzzer_config.map_or([].as_slice(), |config| config.ranges.as_slice());
+
+    RandomFuzzer::create(0, NonZeroU32::MIN, &arg_types, ranges)?;
+
+    Ok(())
+}
+
 fn run_with_fuzzing(
     args: Vec<ConcreteTypeLongId>,
     case: Arc<TestCaseWithResolvedConfig>,
@@ -172,52 +202,98 @@ fn run_with_fuzzing(
     test_runner_config: Arc<TestRunnerConfig>,
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
+    running_tests: RunningTests,
 ) -> JoinHandle<Result<TestCaseSummary<Fuzzing>>> {
     tokio::task::spawn(async move {
         if send.is_closed() {
-            return Ok(TestCaseSummary::Skipped {});
+            return Ok(TestCaseSummary::Skipped {
+                name: case.name.clone(),
+            });
         }
 
         let (fuzzing_send, mut fuzzing_rec) = channel(1);
         let arg_types = args.iter().map(argument_type_name).collect::<Vec<_>>();
 
-        let (fuzzer_runs, fuzzer_seed) = match case.config.fuzzer_config {
-            Some(RawFuzzerConfig { runs, seed }) => (
+        let (fuzzer_runs, fuzzer_seed, ranges, shrink_runs) = match &case.config.fuzzer_config {
+            Some(RawFuzzerConfig {
+                runs,
+                seed,
+                ranges,
+                shrink_runs,
+                non_deterministic,
+            }) => (
                 runs.unwrap_or(test_runner_config.fuzzer_runs),
                 seed.unwrap_or(test_runner_config.fuzzer_seed),
+                ranges.as_slice(),
+                // Smaller input of a non-deterministic test can fail for a different reason
+                if *non_deterministic {
+                    0
+                } else {
+                    shrink_runs.unwrap_or(DEFAULT_SHRINK_RUNS)
+                },
             ),
             _ => (
                 test_runner_config.fuzzer_runs,
                 test_runner_config.fuzzer_seed,
+                [].as_slice(),
+                DEFAULT_SHRINK_RUNS,
             ),
         };
-        let mut fuzzer = RandomFuzzer::create(fuzzer_seed, fuzzer_runs, &arg_types)?;
+        let mut fuzzer = RandomFuzzer::create(fuzzer_seed, fuzzer_runs, &arg_types, ranges)?;
+        let corpus = FuzzCorpus::new(&test_runner_config.fuzz_corpus_dir, &case.name, &arg_types)?;
 
-        let mut tasks = FuturesUnordered::new();
+        let Some(mut results) = replay_corpus(
+            &corpus,
+            &case,
+            &casm_program,
+            &test_runner_config,
+            &maybe_versioned_program_path,
+            &running_tests,
+        )
+        .await?
+        else {
+            return Ok(TestCaseSummary::Skipped {
+                name: case.name.clone(),
+            });
+        };
+        let replayed_runs = u32::try_from(results.len())?;
 
-        for _ in 1..=fuzzer_runs.get() {
-            let args = fuzzer.next_args();
+        if !matches!(
+            results.last(),
+            Some(
+                TestCaseSummary::Failed { .. }
+                    | TestCaseSummary::FixtureFailed { .. }
+                    | TestCaseSummary::TimedOut { .. }
+            )
+        ) {
+            let mut tasks = FuturesUnordered::new();
 
-            tasks.push(run_fuzz_test(
-                args,
-                case.clone(),
-                casm_program.clone(),
-                test_runner_config.clone(),
-                maybe_versioned_program_path.clone(),
-                send.clone(),
-                fuzzing_send.clone(),
-            ));
-        }
+            for _ in 1..=fuzzer_runs.get() {
+                let args = fuzzer.next_args();
+
+                tasks.push(run_fuzz_test(
+                    args,
+                    case.clone(),
+                    casm_program.clone(),
+                    test_runner_config.clone(),
+                    maybe_versioned_program_path.clone(),
+                    fuzzing_send.clone(),
+                    running_tests.clone(),
+                ));
+            }
 
-        let mut results = vec![];
-        while let Some(task) = tasks.next().await {
-            let result = task??;
+            while let Some(task) = tasks.next().await {
+                let result = task??;
 
-            results.push(result.clone());
+                results.push(result.clone());
 
-            if let TestCaseSummary::Failed { .. } = result {
-                fuzzing_rec.close();
-                break;
+                if let TestCaseSummary::Failed { .. }
+                | TestCaseSummary::FixtureFailed { .. }
+                | TestCaseSummary::TimedOut { .. } = result
+                {
+                    fuzzing_rec.close();
+                    break;
+                }
             }
         }
 
@@ -227,20 +303,61 @@ fn run_with_fuzzing(
                 .filter(|item| {
                     matches!(
                         item,
-                        TestCaseSummary::Passed { .. } | TestCaseSummary::Failed { .. }
+                        TestCaseSummary::Passed { .. }
+                            | TestCaseSummary::Failed { .. }
+                            | TestCaseSummary::FixtureFailed { .. }
+                            | TestCaseSummary::TimedOut { .. }
                     )
                 })
                 .count(),
         )?;
 
-        let fuzzing_run_summary: TestCaseSummary<Fuzzing> = TestCaseSummary::from(results);
+        let shrinker = match results.last() {
+            Some(TestCaseSummary::Failed { arguments, .. }) if shrink_runs > 0 => {
+                fuzzer.shrinker(arguments)
+            }
+            _ => None,
+        };
+        let minimized = match shrinker {
+            Some(shrinker) => {
+                shrink_failing_input(
+                    shrinker,
+                    shrink_runs,
+                    &case,
+                    &casm_program,
+                    &test_runner_config,
+                    &maybe_versioned_program_path,
+                    &running_tests,
+                )
+                .await?
+            }
+            None => None,
+        };
+
+        if let Some(TestCaseSummary::Failed { arguments, .. }) = results.last() {
+            let failing_arguments = match &minimized {
+                Some(TestCaseSummary::Failed { arguments, .. }) => arguments,
+                _ => arguments,
+            };
+            if let Err(error) = corpus.save(failing_arguments) {
+                emit_warning(
+                    DiagnosticCode::InvalidFuzzCorpus,
+                    error.context("Failed to save the failing input to fuzz corpus"),
+                );
+            }
+        }
+
+        let fuzzing_run_summary: TestCaseSummary<Fuzzing> =
+            TestCaseSummary::from(results, fuzzer_seed, minimized);
 
         if let TestCaseSummary::Passed { .. } = fuzzing_run_summary {
             // Because we execute tests parallel, it's possible to
             // get Passed after Skipped. To treat fuzzing a test as Passed
             // we have to ensure that all fuzzing subtests Passed
-            if runs != fuzzer_runs.get() {
-                return Ok(TestCaseSummary::Skipped {});
+            if runs != replayed_runs + fuzzer_runs.get() {
+                return Ok(TestCaseSummary::Skipped {
+                    name: case.name.clone(),
+                });
             };
         };
 
@@ -248,6 +365,105 @@ fn run_with_fuzzing(
     })
 }
 
+/// Runs the test with inputs saved in the corpus, one by one, until one of them fails.
+/// Returns `None` if the test was skipped.
+async fn replay_corpus(
+    corpus: &FuzzCorpus,
+    case: &Arc<TestCaseWithResolvedConfig>,
+    casm_program: &Arc<AssembledProgramWithDebugInfo>,
+    test_runner_config: &Arc<TestRunnerConfig>,
+    maybe_versioned_program_path: &Arc<Option<VersionedProgramPath>>,
+    running_tests: &RunningTests,
+) -> Result<Option<Vec<TestCaseSummary<Single>>>> {
+    let (replay_send, _replay_rec) = channel(1);
+    let mut results = vec![];
+
+    for input in corpus.load(test_runner_config.prune_fuzz_corpus)? {
+        let result = run_fuzz_test(
+            input.arguments.clone(),
+            case.clone(),
+            casm_program.clone(),
+            test_runner_config.clone(),
+            maybe_versioned_program_path.clone(),
+            replay_send.clone(),
+            running_tests.clone(),
+        )
+        .await??;
+
+        match result {
+            TestCaseSummary::Failed { .. }
+            | TestCaseSummary::FixtureFailed { .. }
+            | TestCaseSummary::TimedOut { .. } => {
+                results.push(result);
+                break;
+            }
+            TestCaseSummary::Passed { .. } => {
+                if test_runner_config.prune_fuzz_corpus {
+                    input.remove()?;
+                }
+                results.push(result);
+            }
+            TestCaseSummary::Ignored { .. } | TestCaseSummary::Skipped { .. } => return Ok(None),
+        }
+    }
+
+    Ok(Some(results))
+}
+
+/// Runs the test with smaller inputs within the `shrink_runs` budget and [`SHRINK_TIME_LIMIT`],
+/// returns the result of the smallest input that still fails, if one was found
+async fn shrink_failing_input(
+    mut shrinker: Shrinker,
+    shrink_runs: u32,
+    case: &Arc<TestCaseWithResolvedConfig>,
+    casm_program: &Arc<AssembledProgramWithDebugInfo>,
+    test_runner_config: &Arc<TestRunnerConfig>,
+    maybe_versioned_program_path: &Arc<Option<VersionedProgramPath>>,
+    running_tests: &RunningTests,
+) -> Result<Option<TestCaseSummary<Single>>> {
+    // Fuzzing channel is already closed by the failure, shrinking runs must not be skipped because of it
+    let (shrinking_send, _shrinking_rec) = channel(1);
+    let started = Instant::now();
+    let mut minimized = None;
+
+    for _ in 0..shrink_runs {
+        if started.elapsed() >= SHRINK_TIME_LIMIT {
+            break;
+        }
+        let Some(candidate) = shrinker.next_candidate() else {
+            break;
+        };
+
+        let result = run_fuzz_test(
+            candidate,
+            case.clone(),
+            casm_program.clone(),
+            test_runner_config.clone(),
+            maybe_versioned_program_path.clone(),
+            shrinking_send.clone(),
+            running_tests.clone(),
+        )
+        .await??;
+
+        match result {
+            TestCaseSummary::Failed { .. } => {
+                shrinker.report(true);
+                minimized = Some(result);
+            }
+            // Input that timed out or failed in a fixture does not reproduce the original failure
+            TestCaseSummary::Passed { .. }
+            | TestCaseSummary::FixtureFailed { .. }
+            | TestCaseSummary::TimedOut { .. }
+            | TestCaseSummary::Ignored { .. } => {
+                shrinker.report(false);
+            }
+            TestCaseSummary::Skipped { .. } => break,
+        }
+    }
+
+    Ok(minimized)
+}
+
 #[allow(clippy::implicit_hasher)]
 #[must_use]
 pub fn function_args(
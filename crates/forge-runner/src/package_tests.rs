@@ -1,5 +1,7 @@
 use cairo_lang_sierra::ids::GenericTypeId;
 use cairo_lang_sierra::program::ProgramArtifact;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 use universal_sierra_compiler_api::AssembledProgramWithDebugInfo;
 
@@ -15,6 +17,35 @@ pub enum TestTargetLocation {
     Tests,
 }
 
+/// Kind of the tests of a target, telling apart tests with the same names in `src/` and `tests/`
+#[derive(Debug, PartialEq, Clone, Copy, Hash, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestKind {
+    /// Tests of the main crate
+    Unit,
+    /// Tests of the crate in the `tests/` directory
+    Integration,
+}
+
+impl TestTargetLocation {
+    #[must_use]
+    pub fn test_kind(self) -> TestKind {
+        match self {
+            TestTargetLocation::Lib => TestKind::Unit,
+            TestTargetLocation::Tests => TestKind::Integration,
+        }
+    }
+}
+
+impl Display for TestKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TestKind::Unit => write!(f, "unit"),
+            TestKind::Integration => write!(f, "integration"),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct TestDetails {
     pub sierra_entry_point_statement_idx: usize,
@@ -22,6 +53,16 @@ pub struct TestDetails {
     pub return_types: Vec<(GenericTypeId, i16)>,
 }
 
+impl TestDetails {
+    /// Tests compiled with gas take the gas builtin and return the remaining gas
+    #[must_use]
+    pub fn is_compiled_with_gas(&self) -> bool {
+        self.parameter_types
+            .iter()
+            .any(|(type_id, _)| type_id.0 == "GasBuiltin")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TestTarget<C> {
     pub tests_location: TestTargetLocation,
@@ -2,8 +2,10 @@ use super::{TestCase, TestTarget};
 use crate::expected_result::{ExpectedPanicValue, ExpectedTestResult};
 use cheatnet::runtime_extensions::forge_config_extension::config::{
     Expected, RawForgeConfig, RawForkConfig, RawFuzzerConfig, RawShouldPanicConfig,
+    RawTestCaseConfig,
 };
 use conversions::serde::serialize::SerializeToFeltVec;
+use std::time::Duration;
 
 pub type TestTargetWithConfig = TestTarget<TestCaseConfig>;
 
@@ -15,19 +17,33 @@ pub type TestCaseWithConfig = TestCase<TestCaseConfig>;
 pub struct TestCaseConfig {
     pub available_gas: Option<usize>,
     pub ignored: bool,
+    /// Reason given in `#[ignore("...")]`
+    pub ignore_reason: Option<String>,
     pub expected_result: ExpectedTestResult,
     pub fork_config: Option<RawForkConfig>,
     pub fuzzer_config: Option<RawFuzzerConfig>,
+    pub test_case_config: Option<RawTestCaseConfig>,
+    /// Steps limit set with `#[available_steps]`
+    pub available_steps: Option<u32>,
+    /// Time limit set with `#[timeout]`
+    pub timeout: Option<Duration>,
+    /// Set with `#[serial]`
+    pub serial: bool,
 }
 
 impl From<RawForgeConfig> for TestCaseConfig {
     fn from(value: RawForgeConfig) -> Self {
         Self {
             available_gas: value.available_gas.map(|v| v.gas),
-            ignored: value.ignore.is_some_and(|v| v.is_ignored),
+            ignored: value.ignore.as_ref().is_some_and(|v| v.is_ignored),
+            ignore_reason: value.ignore.and_then(|v| v.reason).map(String::from),
             expected_result: value.should_panic.into(),
             fork_config: value.fork,
             fuzzer_config: value.fuzzer,
+            test_case_config: value.test_case,
+            available_steps: value.available_steps.map(|v| v.steps),
+            timeout: value.timeout.map(|v| Duration::from_secs(v.seconds)),
+            serial: value.serial.is_some_and(|v| v.is_serial),
         }
     }
 }
@@ -1,7 +1,10 @@
 use super::{TestCase, TestTarget};
 use crate::expected_result::ExpectedTestResult;
-use cheatnet::runtime_extensions::forge_config_extension::config::RawFuzzerConfig;
+use cheatnet::runtime_extensions::forge_config_extension::config::{
+    RawFuzzerConfig, RawTestCaseConfig,
+};
 use starknet_api::block::BlockNumber;
+use std::time::Duration;
 use url::Url;
 
 pub type TestTargetWithResolvedConfig = TestTarget<TestCaseResolvedConfig>;
@@ -10,6 +13,8 @@ pub type TestCaseWithResolvedConfig = TestCase<TestCaseResolvedConfig>;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ResolvedForkConfig {
+    /// Name of the fork from the Scarb.toml, `None` for forks configured inline
+    pub name: Option<String>,
     pub url: Url,
     pub block_number: BlockNumber,
 }
@@ -21,7 +26,16 @@ pub struct ResolvedForkConfig {
 pub struct TestCaseResolvedConfig {
     pub available_gas: Option<usize>,
     pub ignored: bool,
+    /// Reason given in `#[ignore("...")]`
+    pub ignore_reason: Option<String>,
     pub expected_result: ExpectedTestResult,
     pub fork_config: Option<ResolvedForkConfig>,
     pub fuzzer_config: Option<RawFuzzerConfig>,
+    pub test_case_config: Option<RawTestCaseConfig>,
+    /// Steps limit set with `#[available_steps]`, takes precedence over the one from the config
+    pub available_steps: Option<u32>,
+    /// Time limit set with `#[timeout]`, takes precedence over the one from the config
+    pub timeout: Option<Duration>,
+    /// Set with `#[serial]`, such tests never run concurrently with each other
+    pub serial: bool,
 }
@@ -1,13 +1,23 @@
+use crate::package_tests::with_config_resolved::ResolvedForkConfig;
 use crate::test_case_summary::{AnyTestCaseSummary, FuzzingStatistics, TestCaseSummary};
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
+use cheatnet::runtime_extensions::forge_config_extension::config::RawTestCaseConfig;
 use console::style;
+use shared::human_println;
+
+pub fn print_test_result(
+    any_test_result: &AnyTestCaseSummary,
+    print_detailed_resources: bool,
+    fork_config: Option<&ResolvedForkConfig>,
+    test_case_config: Option<&RawTestCaseConfig>,
+) {
+    let result_header = result_header(any_test_result);
+    let result_name = any_test_result.name();
 
-pub fn print_test_result(any_test_result: &AnyTestCaseSummary, print_detailed_resources: bool) {
     if any_test_result.is_skipped() {
+        human_println!("{result_header} {result_name} (cancelled)");
         return;
     }
-    let result_header = result_header(any_test_result);
-    let result_name = any_test_result.name().unwrap();
 
     let result_msg = result_message(any_test_result);
 
@@ -15,7 +25,7 @@ pub fn print_test_result(any_test_result: &AnyTestCaseSummary, print_detailed_re
     if let AnyTestCaseSummary::Fuzzing(test_result) = any_test_result {
         fuzzer_report = match test_result {
             TestCaseSummary::Passed {
-                test_statistics: FuzzingStatistics { runs },
+                test_statistics: FuzzingStatistics { runs, .. },
                 gas_info,
                 ..
             } => Some(format!(
@@ -24,14 +34,54 @@ pub fn print_test_result(any_test_result: &AnyTestCaseSummary, print_detailed_re
             )),
             TestCaseSummary::Failed {
                 arguments,
-                test_statistics: FuzzingStatistics { runs },
+                test_statistics:
+                    FuzzingStatistics {
+                        runs,
+                        seed,
+                        minimized_arguments,
+                    },
+                ..
+            }
+            | TestCaseSummary::FixtureFailed {
+                arguments,
+                test_statistics:
+                    FuzzingStatistics {
+                        runs,
+                        seed,
+                        minimized_arguments,
+                    },
+                ..
+            }
+            | TestCaseSummary::TimedOut {
+                arguments,
+                test_statistics:
+                    FuzzingStatistics {
+                        runs,
+                        seed,
+                        minimized_arguments,
+                    },
                 ..
-            } => Some(format!(" (runs: {runs}, arguments: {arguments:?})")),
+            } => {
+                let minimized = minimized_arguments
+                    .as_ref()
+                    .map(|minimized| format!(", minimized arguments: {minimized:?}"))
+                    .unwrap_or_default();
+                Some(format!(
+                    " (runs: {runs}, seed: {seed}, arguments: {arguments:?}{minimized})"
+                ))
+            }
             _ => None,
         };
     }
     let fuzzer_report = fuzzer_report.unwrap_or_else(String::new);
 
+    let test_case_report = match test_case_config {
+        Some(test_case_config) if any_test_result.is_failed() => {
+            format_test_case_info(test_case_config)
+        }
+        _ => String::new(),
+    };
+
     let gas_usage = match any_test_result {
         AnyTestCaseSummary::Single(TestCaseSummary::Passed { gas_info, .. }) => {
             format!(" (gas: ~{gas_info})")
@@ -39,6 +89,24 @@ pub fn print_test_result(any_test_result: &AnyTestCaseSummary, print_detailed_re
         _ => String::new(),
     };
 
+    let timeout_info = match any_test_result {
+        AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { elapsed, .. })
+        | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { elapsed, .. }) => {
+            format!(" (elapsed: {:.2}s)", elapsed.as_secs_f64())
+        }
+        _ => String::new(),
+    };
+
+    let ignore_info = any_test_result
+        .ignore_reason()
+        .map(|reason| format!(" (reason: {reason})"))
+        .unwrap_or_default();
+
+    let fork_info = match fork_config {
+        Some(fork_config) if !any_test_result.is_ignored() => format_fork_info(fork_config),
+        _ => String::new(),
+    };
+
     let used_resources = match (print_detailed_resources, any_test_result) {
         (true, AnyTestCaseSummary::Single(TestCaseSummary::Passed { used_resources, .. })) => {
             format_detailed_resources(used_resources)
@@ -46,7 +114,25 @@ pub fn print_test_result(any_test_result: &AnyTestCaseSummary, print_detailed_re
         _ => String::new(),
     };
 
-    println!("{result_header} {result_name}{fuzzer_report}{gas_usage}{used_resources}{result_msg}");
+    human_println!(
+        "{result_header} {result_name}{fuzzer_report}{test_case_report}{gas_usage}{timeout_info}{ignore_info}{fork_info}{used_resources}{result_msg}"
+    );
+}
+
+fn format_fork_info(fork_config: &ResolvedForkConfig) -> String {
+    let fork = fork_config
+        .name
+        .clone()
+        .unwrap_or_else(|| fork_config.url.to_string());
+
+    format!(" (fork: {fork}, block: {})", fork_config.block_number.0)
+}
+
+fn format_test_case_info(test_case_config: &RawTestCaseConfig) -> String {
+    let name = String::from(test_case_config.name.clone());
+    let arguments = String::from(test_case_config.arguments.clone());
+
+    format!(" (case: {name}, arguments: ({arguments}))")
 }
 
 fn format_detailed_resources(used_resources: &UsedResources) -> String {
@@ -57,13 +143,17 @@ fn format_detailed_resources(used_resources: &UsedResources) -> String {
 
     let builtins = format_items(&sorted_builtins);
     let syscalls = format_items(&sorted_syscalls);
+    let sierra_gas = used_resources
+        .sierra_gas_consumed
+        .map(|gas| format!("\n        sierra gas: {gas}"))
+        .unwrap_or_default();
 
     format!(
         "
         steps: {}
         memory holes: {}
         builtins: ({})
-        syscalls: ({})
+        syscalls: ({}){sierra_gas}
         ",
         vm_resources.n_steps, vm_resources.n_memory_holes, builtins, syscalls,
     )
@@ -92,26 +182,39 @@ where
 }
 
 fn result_message(any_test_result: &AnyTestCaseSummary) -> String {
-    if let Some(msg) = any_test_result.msg() {
-        if any_test_result.is_passed() {
-            return format!("\n\nSuccess data:{msg}");
-        }
-        if any_test_result.is_failed() {
-            return format!("\n\nFailure data:{msg}");
+    let captured_output = any_test_result
+        .captured_output()
+        .map(|output| format!("\nCaptured output:\n{}\n", output.trim_end()))
+        .unwrap_or_default();
+
+    match any_test_result.msg() {
+        Some(msg) if any_test_result.is_passed() => format!("\n\nSuccess data:{msg}"),
+        Some(msg) if any_test_result.is_failed() => {
+            format!("\n\nFailure data:{msg}{captured_output}")
         }
+        _ if !captured_output.is_empty() => format!("\n{captured_output}"),
+        _ => String::new(),
     }
-    String::new()
 }
 
 fn result_header(any_test_result: &AnyTestCaseSummary) -> String {
     if any_test_result.is_passed() {
         return format!("[{}]", style("PASS").green());
     }
+    if any_test_result.is_timed_out() {
+        return format!("[{}]", style("TIMEOUT").red());
+    }
+    if any_test_result.is_fixture_failed() {
+        return format!("[{}]", style("FIXTURE FAIL").red());
+    }
     if any_test_result.is_failed() {
         return format!("[{}]", style("FAIL").red());
     }
     if any_test_result.is_ignored() {
         return format!("[{}]", style("IGNORE").yellow());
     }
+    if any_test_result.is_skipped() {
+        return format!("[{}]", style("SKIP").yellow());
+    }
     unreachable!()
 }
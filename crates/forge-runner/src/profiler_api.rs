@@ -1,3 +1,4 @@
+use crate::build_trace_data::sanitize_test_name;
 use anyhow::{Context, Result};
 use shared::command::CommandExt;
 use std::process::Stdio;
@@ -12,7 +13,8 @@ pub fn run_profiler(test_name: &str, trace_path: &PathBuf) -> Result<()> {
         .unwrap_or_else(|| PathBuf::from("cairo-profiler"));
     let dir_to_save_profile = PathBuf::from(PROFILE_DIR);
     fs::create_dir_all(&dir_to_save_profile).context("Failed to create a profile dir")?;
-    let path_to_save_profile = dir_to_save_profile.join(format!("{test_name}.pb.gz"));
+    let path_to_save_profile =
+        dir_to_save_profile.join(format!("{}.pb.gz", sanitize_test_name(test_name)));
 
     Command::new(profiler)
         .arg(trace_path)
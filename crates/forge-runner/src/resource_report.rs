@@ -0,0 +1,391 @@
+use crate::forge_config::TrackedResource;
+use crate::test_case_summary::{AnyTestCaseSummary, Single, TestCaseSummary};
+use crate::test_target_summary::TestTargetSummary;
+use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::iter::Sum;
+use std::ops::AddAssign;
+
+/// Resources used by a passed test, in a form independent of the VM types
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct ResourceReport {
+    pub steps: usize,
+    pub memory_holes: usize,
+    pub gas: u128,
+    /// Resource the gas was computed from
+    pub tracked_resource: TrackedResource,
+    /// Sierra gas consumed by the test and the called contracts, `None` if the test was compiled without gas
+    pub sierra_gas_consumed: Option<u64>,
+    /// Number of instances of each used builtin, by its name
+    pub builtins: BTreeMap<String, usize>,
+    /// Number of calls of each used syscall, by its name
+    pub syscalls: BTreeMap<String, usize>,
+    /// Number of emitted events, also by the called contracts
+    pub events: usize,
+    /// Number of felts in keys and data of the emitted events
+    pub events_size: usize,
+    /// Number of messages sent to L1
+    pub l2_to_l1_messages: usize,
+    /// Number of felts in payloads of the messages sent to L1
+    pub l2_to_l1_payload_size: usize,
+}
+
+impl ResourceReport {
+    #[must_use]
+    pub fn new(
+        gas: u128,
+        tracked_resource: TrackedResource,
+        used_resources: &UsedResources,
+    ) -> Self {
+        let execution_resources = &used_resources.execution_resources;
+
+        Self {
+            steps: execution_resources.n_steps,
+            memory_holes: execution_resources.n_memory_holes,
+            gas,
+            tracked_resource,
+            sierra_gas_consumed: used_resources.sierra_gas_consumed,
+            builtins: execution_resources
+                .builtin_instance_counter
+                .iter()
+                .map(|(builtin, count)| (builtin.to_str().to_string(), *count))
+                .collect(),
+            syscalls: used_resources
+                .syscall_counter
+                .iter()
+                .map(|(syscall, count)| (format!("{syscall:?}"), *count))
+                .collect(),
+            events: used_resources.events.len(),
+            events_size: used_resources
+                .events
+                .iter()
+                .map(|event| event.keys.len() + event.data.0.len())
+                .sum(),
+            l2_to_l1_messages: used_resources.l2_to_l1_payload_lengths.len(),
+            l2_to_l1_payload_size: used_resources.l2_to_l1_payload_lengths.iter().sum(),
+        }
+    }
+}
+
+impl AddAssign<&ResourceReport> for ResourceReport {
+    fn add_assign(&mut self, other: &ResourceReport) {
+        self.steps += other.steps;
+        self.memory_holes += other.memory_holes;
+        self.gas += other.gas;
+        // All tests of a run track the same resource
+        self.tracked_resource = other.tracked_resource;
+        // Tests compiled without gas are skipped, the sum is `None` only if none of them used gas
+        self.sierra_gas_consumed = match (self.sierra_gas_consumed, other.sierra_gas_consumed) {
+            (Some(gas), Some(other_gas)) => Some(gas + other_gas),
+            (gas, other_gas) => gas.or(other_gas),
+        };
+        for (builtin, count) in &other.builtins {
+            *self.builtins.entry(builtin.clone()).or_default() += count;
+        }
+        for (syscall, count) in &other.syscalls {
+            *self.syscalls.entry(syscall.clone()).or_default() += count;
+        }
+        self.events += other.events;
+        self.events_size += other.events_size;
+        self.l2_to_l1_messages += other.l2_to_l1_messages;
+        self.l2_to_l1_payload_size += other.l2_to_l1_payload_size;
+    }
+}
+
+impl Sum for ResourceReport {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |mut total, report| {
+            total += &report;
+            total
+        })
+    }
+}
+
+/// Sum of resources used by the passed tests, fuzz tests are not included
+#[must_use]
+pub fn total_resource_report(summaries: &[TestTargetSummary]) -> ResourceReport {
+    summaries
+        .iter()
+        .flat_map(|summary| &summary.test_case_summaries)
+        .filter_map(|test_case_summary| match test_case_summary {
+            AnyTestCaseSummary::Single(case) => case.resource_report(),
+            AnyTestCaseSummary::Fuzzing(_) => None,
+        })
+        .sum()
+}
+
+impl TestCaseSummary<Single> {
+    /// Returns `None` if the test did not pass
+    #[must_use]
+    pub fn resource_report(&self) -> Option<ResourceReport> {
+        match self {
+            TestCaseSummary::Passed {
+                gas_info,
+                tracked_resource,
+                used_resources,
+                ..
+            } => Some(ResourceReport::new(
+                *gas_info,
+                *tracked_resource,
+                used_resources,
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Writes labeled reports as CSV, with a column for every builtin and syscall used in any of them.
+/// Builtins and syscalls not used in a report are written as 0, Sierra gas of tests compiled without gas is left empty.
+pub fn write_resource_reports_csv(
+    reports: &[(String, ResourceReport)],
+    mut writer: impl Write,
+) -> io::Result<()> {
+    let builtins: BTreeSet<_> = reports
+        .iter()
+        .flat_map(|(_, report)| report.builtins.keys())
+        .collect();
+    let syscalls: BTreeSet<_> = reports
+        .iter()
+        .flat_map(|(_, report)| report.syscalls.keys())
+        .collect();
+
+    let header = [
+        "label",
+        "steps",
+        "memory_holes",
+        "gas",
+        "sierra_gas_consumed",
+        "events",
+        "events_size",
+        "l2_to_l1_messages",
+        "l2_to_l1_payload_size",
+    ]
+    .into_iter()
+    .chain(builtins.iter().map(|name| name.as_str()))
+    .chain(syscalls.iter().map(|name| name.as_str()))
+    .map(escape_csv_field)
+    .collect::<Vec<_>>();
+    writeln!(writer, "{}", header.join(","))?;
+
+    for (label, report) in reports {
+        let row = [
+            escape_csv_field(label),
+            report.steps.to_string(),
+            report.memory_holes.to_string(),
+            report.gas.to_string(),
+            report
+                .sierra_gas_consumed
+                .map(|gas| gas.to_string())
+                .unwrap_or_default(),
+            report.events.to_string(),
+            report.events_size.to_string(),
+            report.l2_to_l1_messages.to_string(),
+            report.l2_to_l1_payload_size.to_string(),
+        ]
+        .into_iter()
+        .chain(
+            builtins
+                .iter()
+                .map(|name| report.builtins.get(*name).unwrap_or(&0).to_string()),
+        )
+        .chain(
+            syscalls
+                .iter()
+                .map(|name| report.syscalls.get(*name).unwrap_or(&0).to_string()),
+        )
+        .collect::<Vec<_>>();
+        writeln!(writer, "{}", row.join(","))?;
+    }
+
+    Ok(())
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(
+        steps: usize,
+        gas: u128,
+        builtins: &[(&str, usize)],
+        syscalls: &[(&str, usize)],
+    ) -> ResourceReport {
+        ResourceReport {
+            steps,
+            memory_holes: 1,
+            gas,
+            builtins: builtins
+                .iter()
+                .map(|(name, count)| ((*name).to_string(), *count))
+                .collect(),
+            syscalls: syscalls
+                .iter()
+                .map(|(name, count)| ((*name).to_string(), *count))
+                .collect(),
+            ..ResourceReport::default()
+        }
+    }
+
+    fn to_csv(reports: &[(String, ResourceReport)]) -> String {
+        let mut output = vec![];
+        write_resource_reports_csv(reports, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn header_is_union_of_keys() {
+        let reports = vec![
+            (
+                "first".to_string(),
+                report(10, 100, &[("range_check", 2)], &[("StorageRead", 1)]),
+            ),
+            (
+                "second".to_string(),
+                report(20, 200, &[("pedersen", 1)], &[("EmitEvent", 3)]),
+            ),
+        ];
+
+        assert_eq!(
+            to_csv(&reports),
+            "label,steps,memory_holes,gas,sierra_gas_consumed,events,events_size,l2_to_l1_messages,l2_to_l1_payload_size,pedersen,range_check,EmitEvent,StorageRead\n\
+             first,10,1,100,,0,0,0,0,0,2,0,1\n\
+             second,20,1,200,,0,0,0,0,1,0,3,0\n"
+        );
+    }
+
+    #[test]
+    fn header_does_not_depend_on_order() {
+        let first = (
+            "first".to_string(),
+            report(10, 100, &[("range_check", 2)], &[]),
+        );
+        let second = (
+            "second".to_string(),
+            report(20, 200, &[("bitwise", 1)], &[]),
+        );
+
+        let header = |csv: String| csv.lines().next().unwrap().to_string();
+
+        assert_eq!(
+            header(to_csv(&[first.clone(), second.clone()])),
+            header(to_csv(&[second, first]))
+        );
+    }
+
+    #[test]
+    fn sum_of_reports() {
+        let total: ResourceReport = [
+            ResourceReport {
+                sierra_gas_consumed: Some(1000),
+                events: 2,
+                events_size: 5,
+                ..report(10, 100, &[("range_check", 2)], &[("StorageRead", 1)])
+            },
+            ResourceReport {
+                events: 1,
+                events_size: 2,
+                l2_to_l1_messages: 1,
+                l2_to_l1_payload_size: 3,
+                ..report(20, 200, &[("range_check", 1), ("pedersen", 1)], &[])
+            },
+        ]
+        .into_iter()
+        .sum();
+
+        assert_eq!(
+            total,
+            ResourceReport {
+                steps: 30,
+                memory_holes: 2,
+                gas: 300,
+                tracked_resource: TrackedResource::CairoSteps,
+                sierra_gas_consumed: Some(1000),
+                builtins: BTreeMap::from([
+                    ("pedersen".to_string(), 1),
+                    ("range_check".to_string(), 3)
+                ]),
+                syscalls: BTreeMap::from([("StorageRead".to_string(), 1)]),
+                events: 3,
+                events_size: 7,
+                l2_to_l1_messages: 1,
+                l2_to_l1_payload_size: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn sum_of_reports_without_sierra_gas() {
+        let total: ResourceReport = [report(10, 100, &[], &[]), report(20, 200, &[], &[])]
+            .into_iter()
+            .sum();
+
+        assert_eq!(total.sierra_gas_consumed, None);
+    }
+
+    #[test]
+    fn sum_keeps_tracked_resource() {
+        let sierra_gas_report = |gas| ResourceReport {
+            tracked_resource: TrackedResource::SierraGas,
+            sierra_gas_consumed: Some(gas),
+            ..report(10, u128::from(gas), &[], &[])
+        };
+
+        let total: ResourceReport = [sierra_gas_report(1000), sierra_gas_report(500)]
+            .into_iter()
+            .sum();
+
+        assert_eq!(total.tracked_resource, TrackedResource::SierraGas);
+        assert_eq!(total.gas, 1500);
+        assert_eq!(total.sierra_gas_consumed, Some(1500));
+    }
+
+    #[test]
+    fn sierra_gas_in_csv() {
+        let reports = vec![
+            (
+                "with_gas".to_string(),
+                ResourceReport {
+                    sierra_gas_consumed: Some(1500),
+                    ..report(10, 100, &[], &[])
+                },
+            ),
+            ("without_gas".to_string(), report(20, 200, &[], &[])),
+        ];
+
+        assert_eq!(
+            to_csv(&reports),
+            "label,steps,memory_holes,gas,sierra_gas_consumed,events,events_size,l2_to_l1_messages,l2_to_l1_payload_size\n\
+             with_gas,10,1,100,1500,0,0,0,0\n\
+             without_gas,20,1,200,,0,0,0,0\n"
+        );
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert_eq!(
+            to_csv(&[]),
+            "label,steps,memory_holes,gas,sierra_gas_consumed,events,events_size,l2_to_l1_messages,l2_to_l1_payload_size\n"
+        );
+    }
+
+    #[test]
+    fn escapes_labels() {
+        let reports = vec![("test \"a\", b".to_string(), report(1, 2, &[], &[]))];
+
+        assert_eq!(
+            to_csv(&reports),
+            "label,steps,memory_holes,gas,sierra_gas_consumed,events,events_size,l2_to_l1_messages,l2_to_l1_payload_size\n\
+             \"test \"\"a\"\", b\",1,1,2,,0,0,0,0\n"
+        );
+    }
+}
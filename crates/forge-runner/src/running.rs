@@ -1,18 +1,22 @@
 use crate::build_trace_data::test_sierra_program_path::VersionedProgramPath;
-use crate::forge_config::{RuntimeConfig, TestRunnerConfig};
+use crate::forge_config::{RuntimeConfig, TestRunnerConfig, TrackedResource};
 use crate::gas::calculate_used_gas;
 use crate::package_tests::with_config_resolved::{ResolvedForkConfig, TestCaseWithResolvedConfig};
+use crate::running_tests::RunningTests;
 use crate::test_case_summary::{Single, TestCaseSummary};
 use anyhow::{bail, ensure, Result};
 use blockifier::execution::entry_point::EntryPointExecutionContext;
-use blockifier::state::cached_state::CachedState;
 use cairo_lang_runner::{RunResult, RunnerError, SierraCasmRunner};
-use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use cairo_vm::vm::runners::cairo_runner::{ExecutionResources, ResourceTracker};
 use cairo_vm::Felt252;
 use camino::Utf8Path;
 use casm::{get_assembled_program, run_assembled_program};
 use cheatnet::constants as cheatnet_constants;
+use cheatnet::deadline::HintProcessorWithDeadline;
+use cheatnet::forking::multi_fork::{ForkRegistry, MultiForkState};
+use cheatnet::forking::snapshot::ForkDataMode;
 use cheatnet::forking::state::ForkStateReader;
+use cheatnet::output_capture::{CapturedOutput, HintProcessorWithCapturedOutput};
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::CallToBlockifierExtension;
 use cheatnet::runtime_extensions::cheatable_starknet_runtime_extension::CheatableStarknetRuntimeExtension;
@@ -21,9 +25,10 @@ use cheatnet::runtime_extensions::forge_runtime_extension::{
     get_all_used_resources, update_top_call_execution_resources, update_top_call_l1_resources,
     update_top_call_vm_trace, ForgeExtension, ForgeRuntime,
 };
-use cheatnet::state::{BlockInfoReader, CallTrace, CheatnetState, ExtendedStateReader};
-use entry_code::create_entry_code;
+use cheatnet::state::{CallTrace, CheatnetState, ExtendedStateReader};
+use entry_code::{create_entry_code, INITIAL_GAS};
 use hints::{hints_by_representation, hints_to_params};
+use num_traits::ToPrimitive;
 use runtime::starknet::context::{build_context, set_max_steps};
 use runtime::{ExtendedRuntime, StarknetRuntime};
 use std::cell::RefCell;
@@ -31,6 +36,7 @@ use std::default::Default;
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use syscall_handler::build_syscall_handler;
 use tokio::sync::mpsc::Sender;
 use tokio::task::JoinHandle;
@@ -50,13 +56,18 @@ pub fn run_test(
     test_runner_config: Arc<TestRunnerConfig>,
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
     send: Sender<()>,
+    running_tests: RunningTests,
 ) -> JoinHandle<Result<TestCaseSummary<Single>>> {
     tokio::task::spawn_blocking(move || {
+        running_tests.start(&case.name);
+
         // Due to the inability of spawn_blocking to be abruptly cancelled,
         // a channel is used to receive information indicating
         // that the execution of the task is no longer necessary.
         if send.is_closed() {
-            return Ok(TestCaseSummary::Skipped {});
+            return Ok(TestCaseSummary::Skipped {
+                name: case.name.clone(),
+            });
         }
         let run_result = run_test_case(
             vec![],
@@ -65,38 +76,38 @@ pub fn run_test(
             &RuntimeConfig::from(&test_runner_config),
         );
 
-        // TODO: code below is added to fix snforge tests
-        // remove it after improve exit-first tests
-        // issue #1043
-        if send.is_closed() {
-            return Ok(TestCaseSummary::Skipped {});
-        }
-
         extract_test_case_summary(
             run_result,
             &case,
             vec![],
             &test_runner_config.contracts_data,
             &maybe_versioned_program_path,
+            test_runner_config.max_gas,
         )
     })
 }
 
+/// Runs started after a failure with `--exit-first` are still let finish,
+/// only the ones after a failure of this test are skipped
 pub(crate) fn run_fuzz_test(
     args: Vec<Felt252>,
     case: Arc<TestCaseWithResolvedConfig>,
     casm_program: Arc<AssembledProgramWithDebugInfo>,
     test_runner_config: Arc<TestRunnerConfig>,
     maybe_versioned_program_path: Arc<Option<VersionedProgramPath>>,
-    send: Sender<()>,
     fuzzing_send: Sender<()>,
+    running_tests: RunningTests,
 ) -> JoinHandle<Result<TestCaseSummary<Single>>> {
     tokio::task::spawn_blocking(move || {
+        running_tests.start(&case.name);
+
         // Due to the inability of spawn_blocking to be abruptly cancelled,
         // a channel is used to receive information indicating
         // that the execution of the task is no longer necessary.
-        if send.is_closed() | fuzzing_send.is_closed() {
-            return Ok(TestCaseSummary::Skipped {});
+        if fuzzing_send.is_closed() {
+            return Ok(TestCaseSummary::Skipped {
+                name: case.name.clone(),
+            });
         }
 
         let run_result = run_test_case(
@@ -106,19 +117,13 @@ pub(crate) fn run_fuzz_test(
             &Arc::new(RuntimeConfig::from(&test_runner_config)),
         );
 
-        // TODO: code below is added to fix snforge tests
-        // remove it after improve exit-first tests
-        // issue #1043
-        if send.is_closed() {
-            return Ok(TestCaseSummary::Skipped {});
-        }
-
         extract_test_case_summary(
             run_result,
             &case,
             args,
             &test_runner_config.contracts_data,
             &maybe_versioned_program_path,
+            test_runner_config.max_gas,
         )
     })
 }
@@ -127,7 +132,16 @@ pub struct RunResultWithInfo {
     pub(crate) run_result: Result<RunResult, RunnerError>,
     pub(crate) call_trace: Rc<RefCell<CallTrace>>,
     pub(crate) gas_used: u128,
+    pub(crate) tracked_resource: TrackedResource,
     pub(crate) used_resources: UsedResources,
+    /// Fixture that was still running when the execution stopped
+    pub(crate) failed_fixture: Option<String>,
+    /// Time after which the execution was stopped because of the timeout
+    pub(crate) timed_out_after: Option<Duration>,
+    /// Steps available to the test, set when the execution stopped after consuming all of them
+    pub(crate) exhausted_steps: Option<usize>,
+    /// Output printed by the test, `None` if it was not captured
+    pub(crate) captured_output: Option<String>,
 }
 
 #[allow(clippy::too_many_lines)]
@@ -142,6 +156,13 @@ pub fn run_test_case(
         "\n\t`available_gas` attribute was incorrectly configured. Make sure you use scarb >= 2.4.4\n"
     );
 
+    let started = Instant::now();
+    let deadline = case
+        .config
+        .timeout
+        .or(runtime_config.timeout)
+        .map(|timeout| started + timeout);
+
     let (entry_code, builtins) = create_entry_code(args, &case.test_details, casm_program);
 
     let assembled_program = get_assembled_program(casm_program, entry_code);
@@ -149,33 +170,57 @@ pub fn run_test_case(
     let string_to_hint = hints_by_representation(&assembled_program);
     let hints_dict = hints_to_params(&assembled_program);
 
-    let mut state_reader = ExtendedStateReader {
-        dict_state_reader: cheatnet_constants::build_testing_state(),
-        fork_state_reader: get_fork_state_reader(
-            runtime_config.cache_dir,
-            &case.config.fork_config,
-        )?,
+    let fork_state_reader = get_fork_state_reader(
+        runtime_config.cache_dir,
+        runtime_config.fork_data,
+        &case.config.fork_config,
+    )?;
+    let dict_state_reader = if fork_state_reader.is_some() {
+        cheatnet_constants::build_forked_testing_state()
+    } else {
+        cheatnet_constants::build_testing_state()
     };
-    let block_info = state_reader.get_block_info()?;
-    let chain_id = state_reader.get_chain_id()?;
+    let state_reader = ExtendedStateReader {
+        dict_state_reader,
+        fork_state_reader,
+    };
+    let (fork_registry, mut block_info) = ForkRegistry::new(
+        state_reader,
+        runtime_config.cache_dir.clone(),
+        runtime_config.fork_data.cloned(),
+    )?;
+    if fork_registry.active_fork_block_info().is_none() {
+        block_info = runtime_config
+            .block_context_version
+            .default_block_info()
+            .into();
+    }
+    let chain_id = fork_registry.active_chain_id();
+    let fork_registry = Rc::new(RefCell::new(fork_registry));
 
-    let mut context = build_context(&block_info, chain_id);
+    let mut context = build_context(&block_info, chain_id, runtime_config.block_context_version);
 
-    if let Some(max_n_steps) = runtime_config.max_n_steps {
+    if let Some(max_n_steps) = case.config.available_steps.or(runtime_config.max_n_steps) {
         set_max_steps(&mut context, max_n_steps);
     }
-    let mut cached_state = CachedState::new(state_reader);
+    let available_steps = context.vm_run_resources.get_n_steps();
+    let mut multi_fork_state = MultiForkState::new(fork_registry.clone());
     let mut execution_resources = ExecutionResources::default();
     let syscall_handler = build_syscall_handler(
-        &mut cached_state,
+        &mut multi_fork_state,
         &string_to_hint,
         &mut execution_resources,
         &mut context,
         &case.test_details.parameter_types,
     );
 
+    let captured_output = runtime_config.capture_output.then(CapturedOutput::default);
     let mut cheatnet_state = CheatnetState {
         block_info,
+        block_context_version: runtime_config.block_context_version,
+        fork_registry: Some(fork_registry.clone()),
+        deadline,
+        captured_output: captured_output.clone(),
         ..Default::default()
     };
     cheatnet_state.trace_data.is_vm_trace_needed = runtime_config.is_vm_trace_needed;
@@ -205,57 +250,122 @@ pub fn run_test_case(
         extended_runtime: call_to_blockifier_runtime,
     };
 
-    let run_result =
-        match run_assembled_program(&assembled_program, builtins, hints_dict, &mut forge_runtime) {
-            Ok(runner) => {
-                let vm_resources_without_inner_calls = runner
-                    .get_execution_resources()
-                    .unwrap()
-                    .filter_unused_builtins();
-                *forge_runtime
-                    .extended_runtime
-                    .extended_runtime
-                    .extended_runtime
-                    .hint_handler
-                    .resources += &vm_resources_without_inner_calls;
-
-                let ap = runner.relocated_trace.as_ref().unwrap().last().unwrap().ap;
-
-                let (results_data, gas_counter) = SierraCasmRunner::get_results_data(
-                    &case.test_details.return_types,
-                    &runner.relocated_memory,
-                    ap,
-                );
-                assert_eq!(results_data.len(), 1);
-
-                let (_, values) = results_data[0].clone();
-                let value = SierraCasmRunner::handle_main_return_value(
-                    // Here we assume that all test either panic or do not return any value
-                    // This is true for all test right now, but in case it changes
-                    // this logic will need to be updated
-                    Some(0),
-                    values,
-                    &runner.relocated_memory,
-                );
-
-                update_top_call_vm_trace(&mut forge_runtime, &runner);
-
-                Ok((gas_counter, runner.relocated_memory, value))
-            }
-            Err(err) => Err(RunnerError::CairoRunError(err)),
-        };
+    let mut output_capture =
+        HintProcessorWithCapturedOutput::new(&mut forge_runtime, captured_output.clone());
+    let mut hint_processor = HintProcessorWithDeadline::new(&mut output_capture, deadline);
+    let run_result = run_assembled_program(
+        &assembled_program,
+        builtins,
+        hints_dict,
+        &mut hint_processor,
+    );
+    let test_deadline_exceeded = hint_processor.deadline_exceeded();
+    // `None` if the output was not captured or nothing was printed
+    let captured_output = captured_output
+        .map(|output| output.take())
+        .filter(|output| !output.is_empty());
+    // Calls to contracts stopped by the deadline may have been handled by the test as failed calls
+    let timed_out_after = (test_deadline_exceeded
+        || forge_runtime
+            .extended_runtime
+            .extended_runtime
+            .extension
+            .cheatnet_state
+            .deadline_exceeded)
+        .then(|| started.elapsed());
+    let exhausted_steps = available_steps.filter(|_| {
+        run_result.is_err()
+            && timed_out_after.is_none()
+            && get_context(&forge_runtime).vm_run_resources.get_n_steps() == Some(0)
+    });
+
+    let run_result = match run_result {
+        Ok(runner) => {
+            let vm_resources_without_inner_calls = runner
+                .get_execution_resources()
+                .unwrap()
+                .filter_unused_builtins();
+            *forge_runtime
+                .extended_runtime
+                .extended_runtime
+                .extended_runtime
+                .hint_handler
+                .resources += &vm_resources_without_inner_calls;
+
+            let ap = runner.relocated_trace.as_ref().unwrap().last().unwrap().ap;
+
+            let (results_data, gas_counter) = SierraCasmRunner::get_results_data(
+                &case.test_details.return_types,
+                &runner.relocated_memory,
+                ap,
+            );
+            assert_eq!(results_data.len(), 1);
+
+            let (_, values) = results_data[0].clone();
+            let value = SierraCasmRunner::handle_main_return_value(
+                // Here we assume that all test either panic or do not return any value
+                // This is true for all test right now, but in case it changes
+                // this logic will need to be updated
+                Some(0),
+                values,
+                &runner.relocated_memory,
+            );
+
+            update_top_call_vm_trace(&mut forge_runtime, &runner);
+
+            Ok((gas_counter, runner.relocated_memory, value))
+        }
+        Err(err) => Err(RunnerError::CairoRunError(err)),
+    };
 
     let call_trace_ref = get_call_trace_ref(&mut forge_runtime);
+    let failed_fixture = forge_runtime
+        .extended_runtime
+        .extended_runtime
+        .extension
+        .cheatnet_state
+        .active_fixture
+        .take();
 
     update_top_call_execution_resources(&mut forge_runtime);
     update_top_call_l1_resources(&mut forge_runtime);
+    // Contracts are called from the test with their own gas counters, it is not deducted from the test one.
+    // Gas consumed by a call includes the gas of the calls it made
+    let contracts_gas_consumed: u64 = forge_runtime
+        .extended_runtime
+        .extended_runtime
+        .extended_runtime
+        .hint_handler
+        .inner_calls
+        .iter()
+        .map(|call_info| call_info.execution.gas_consumed)
+        .sum();
     let transaction_context = get_context(&forge_runtime).tx_context.clone();
-    let used_resources = get_all_used_resources(forge_runtime, &transaction_context);
-    let gas = calculate_used_gas(
-        &transaction_context,
-        &mut cached_state,
-        used_resources.clone(),
-    )?;
+    let mut used_resources = get_all_used_resources(forge_runtime, &transaction_context);
+    // Tests compiled without gas do not return the gas counter
+    used_resources.sierra_gas_consumed = run_result
+        .as_ref()
+        .ok()
+        .and_then(|(gas_counter, _, _)| *gas_counter)
+        .and_then(|remaining_gas| (Felt252::from(INITIAL_GAS) - remaining_gas).to_u64())
+        .map(|test_gas_consumed| test_gas_consumed + contracts_gas_consumed);
+    let gas = match runtime_config.tracked_resource {
+        TrackedResource::CairoSteps => calculate_used_gas(
+            &transaction_context,
+            fork_registry.borrow_mut().active_state_mut(),
+            used_resources.clone(),
+        )?,
+        TrackedResource::SierraGas => match (&run_result, used_resources.sierra_gas_consumed) {
+            (_, Some(sierra_gas_consumed)) => u128::from(sierra_gas_consumed),
+            // Failed runs do not return the gas counter
+            (Err(_), None) => 0,
+            (Ok(_), None) => bail!(
+                "Test {} did not return the gas counter, `--tracked-resource sierra-gas` requires tests compiled with gas",
+                case.name
+            ),
+        },
+    };
+    fork_registry.borrow().save_fork_data()?;
 
     Ok(RunResultWithInfo {
         run_result: run_result.map(|(gas_counter, memory, value)| RunResult {
@@ -266,19 +376,60 @@ pub fn run_test_case(
             profiling_info: None,
         }),
         gas_used: gas,
+        tracked_resource: runtime_config.tracked_resource,
         used_resources,
         call_trace: call_trace_ref,
+        failed_fixture,
+        timed_out_after,
+        exhausted_steps,
+        captured_output,
     })
 }
 
 fn extract_test_case_summary(
-    run_result: Result<RunResultWithInfo>,
+    mut run_result: Result<RunResultWithInfo>,
     case: &TestCaseWithResolvedConfig,
     args: Vec<Felt252>,
     contracts_data: &ContractsData,
     maybe_versioned_program_path: &Option<VersionedProgramPath>,
+    max_gas: Option<u128>,
 ) -> Result<TestCaseSummary<Single>> {
-    match run_result {
+    let captured_output = run_result
+        .as_mut()
+        .ok()
+        .and_then(|result_with_info| result_with_info.captured_output.take());
+
+    let summary = match run_result {
+        Ok(RunResultWithInfo {
+            timed_out_after: Some(elapsed),
+            ..
+        }) => Ok(TestCaseSummary::TimedOut {
+            name: case.name.clone(),
+            elapsed,
+            arguments: args,
+            test_statistics: (),
+        }),
+        Ok(RunResultWithInfo {
+            run_result,
+            failed_fixture: Some(fixture),
+            ..
+        }) => Ok(TestCaseSummary::from_failed_fixture(
+            &fixture, run_result, case, args,
+        )),
+        Ok(RunResultWithInfo {
+            exhausted_steps: Some(available_steps),
+            ..
+        }) => Ok(TestCaseSummary::Failed {
+            name: case.name.clone(),
+            msg: Some(format!(
+                "\n    Test ran out of steps after consuming all {available_steps} available steps\n    \
+                The limit can be raised with `#[available_steps(<steps>)]` attribute, \
+                `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag\n"
+            )),
+            arguments: args,
+            test_statistics: (),
+            captured_output: None,
+        }),
         Ok(result_with_info) => {
             match result_with_info.run_result {
                 Ok(run_result) => Ok(TestCaseSummary::from_run_result_and_info(
@@ -286,10 +437,12 @@ fn extract_test_case_summary(
                     case,
                     args,
                     result_with_info.gas_used,
+                    result_with_info.tracked_resource,
                     result_with_info.used_resources,
                     &result_with_info.call_trace,
                     contracts_data,
                     maybe_versioned_program_path,
+                    max_gas,
                 )),
                 // CairoRunError comes from VirtualMachineError which may come from HintException that originates in TestExecutionSyscallHandler
                 Err(RunnerError::CairoRunError(error)) => Ok(TestCaseSummary::Failed {
@@ -300,30 +453,48 @@ fn extract_test_case_summary(
                     )),
                     arguments: args,
                     test_statistics: (),
+                    captured_output: None,
                 }),
                 Err(err) => bail!(err),
             }
         }
-        // `ForkStateReader.get_block_info`, `get_fork_state_reader, `calculate_used_gas` may return an error
+        // `ForkStateReader.get_block_info`, `get_fork_state_reader, `calculate_used_gas`, `save_fork_data` may return an error
         // `available_gas` may be specified with Scarb ~2.4
         Err(error) => Ok(TestCaseSummary::Failed {
             name: case.name.clone(),
             msg: Some(error.to_string()),
             arguments: args,
             test_statistics: (),
+            captured_output: None,
         }),
-    }
+    };
+
+    summary.map(|summary| summary.with_captured_output(captured_output))
 }
 
 fn get_fork_state_reader(
     cache_dir: &Utf8Path,
+    fork_data: Option<&ForkDataMode>,
     fork_config: &Option<ResolvedForkConfig>,
 ) -> Result<Option<ForkStateReader>> {
     fork_config
         .as_ref()
-        .map(|ResolvedForkConfig { url, block_number }| {
-            ForkStateReader::new(url.clone(), *block_number, cache_dir)
-        })
+        .map(
+            |ResolvedForkConfig {
+                 name,
+                 url,
+                 block_number,
+             }| {
+                let fork_state_reader =
+                    ForkStateReader::new(url.clone(), *block_number, cache_dir)?;
+
+                match fork_data {
+                    Some(mode) => fork_state_reader
+                        .with_fork_data(mode, name.as_deref().unwrap_or(url.as_str())),
+                    None => Ok(fork_state_reader),
+                }
+            },
+        )
         .transpose()
 }
 
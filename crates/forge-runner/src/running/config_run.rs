@@ -14,7 +14,10 @@ use cairo_vm::Felt252;
 use cheatnet::runtime_extensions::forge_config_extension::{
     config::RawForgeConfig, ForgeConfigExtension,
 };
-use runtime::{starknet::context::build_context, ExtendedRuntime, StarknetRuntime};
+use runtime::{
+    starknet::context::{build_context, BlockContextVersion},
+    ExtendedRuntime, StarknetRuntime,
+};
 use starknet_api::block::{BlockNumber, BlockTimestamp};
 use std::{default::Default, num::NonZeroU128};
 use universal_sierra_compiler_api::AssembledProgramWithDebugInfo;
@@ -82,7 +85,7 @@ pub fn run_config_pass(
     let string_to_hint = hints_by_representation(&assembled_program);
     let hints_dict = hints_to_params(&assembled_program);
 
-    let mut context = build_context(&block_info, None);
+    let mut context = build_context(&block_info, None, BlockContextVersion::default());
 
     let mut execution_resources = ExecutionResources::default();
 
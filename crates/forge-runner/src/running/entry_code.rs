@@ -4,12 +4,14 @@ use cairo_lang_runner::{Arg, SierraCasmRunner};
 use cairo_vm::{types::builtin_name::BuiltinName, Felt252};
 use universal_sierra_compiler_api::AssembledProgramWithDebugInfo;
 
+/// Gas available to the test code, tests compiled with gas return the remaining part of it
+pub(crate) const INITIAL_GAS: usize = usize::MAX;
+
 pub fn create_entry_code(
     args: Vec<Felt252>,
     test_details: &TestDetails,
     casm_program: &AssembledProgramWithDebugInfo,
 ) -> (Vec<Instruction>, Vec<BuiltinName>) {
-    let initial_gas = usize::MAX;
     let runner_args: Vec<Arg> = args.into_iter().map(Arg::Value).collect();
     let sierra_instruction_idx = test_details.sierra_entry_point_statement_idx;
     let casm_entry_point_offset = casm_program.debug_info[sierra_instruction_idx].0;
@@ -17,7 +19,7 @@ pub fn create_entry_code(
     SierraCasmRunner::create_entry_code_from_params(
         &test_details.parameter_types,
         &runner_args,
-        initial_gas,
+        INITIAL_GAS,
         casm_entry_point_offset,
     )
     .unwrap()
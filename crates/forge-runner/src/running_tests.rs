@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Tests which have started running and have not finished yet, shared between the test tasks
+/// and the progress output
+#[derive(Debug, Clone, Default)]
+pub struct RunningTests(Arc<Mutex<HashMap<String, Instant>>>);
+
+impl RunningTests {
+    /// Marks the test as running, runs of a fuzz test after the first one do not change its start
+    pub fn start(&self, name: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(name.to_string())
+            .or_insert_with(Instant::now);
+    }
+
+    pub fn finish(&self, name: &str) {
+        self.0.lock().unwrap().remove(name);
+    }
+
+    /// Returns up to `count` tests which have been running for the longest time, the longest first
+    #[must_use]
+    pub fn longest_running(&self, count: usize) -> Vec<(String, Duration)> {
+        let mut running: Vec<_> = self
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, started)| (name.clone(), started.elapsed()))
+            .collect();
+        running.sort_by(|(_, a), (_, b)| b.cmp(a));
+        running.truncate(count);
+
+        running
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn longest_running_first() {
+        let running_tests = RunningTests::default();
+        running_tests.start("first");
+        std::thread::sleep(Duration::from_millis(10));
+        running_tests.start("second");
+        running_tests.start("third");
+        running_tests.finish("third");
+
+        let names: Vec<_> = running_tests
+            .longest_running(5)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        assert_eq!(names, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn start_keeps_first_start() {
+        let running_tests = RunningTests::default();
+        running_tests.start("fuzz");
+        std::thread::sleep(Duration::from_millis(10));
+        running_tests.start("fuzz");
+
+        let running = running_tests.longest_running(1);
+
+        assert_eq!(running.len(), 1);
+        assert!(running[0].1 >= Duration::from_millis(10));
+    }
+}
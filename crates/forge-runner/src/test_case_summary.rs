@@ -1,11 +1,12 @@
 use crate::build_trace_data::build_profiler_call_trace;
 use crate::build_trace_data::test_sierra_program_path::VersionedProgramPath;
 use crate::expected_result::{ExpectedPanicValue, ExpectedTestResult};
-use crate::gas::check_available_gas;
+use crate::forge_config::TrackedResource;
+use crate::gas::{check_available_gas, check_max_gas};
 use crate::package_tests::with_config_resolved::TestCaseWithResolvedConfig;
 use cairo_annotations::trace_data::VersionedCallTrace as VersionedProfilerCallTrace;
 use cairo_lang_runner::short_string::as_cairo_short_string;
-use cairo_lang_runner::{RunResult, RunResultValue};
+use cairo_lang_runner::{RunResult, RunResultValue, RunnerError};
 use cairo_vm::Felt252;
 use cheatnet::runtime_extensions::call_to_blockifier_runtime_extension::rpc::UsedResources;
 use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
@@ -16,6 +17,7 @@ use shared::utils::build_readable_text;
 use std::cell::RefCell;
 use std::option::Option;
 use std::rc::Rc;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq, Clone, Default)]
 pub struct GasStatistics {
@@ -57,6 +59,10 @@ impl GasStatistics {
 #[derive(Debug, PartialEq, Clone)]
 pub struct FuzzingStatistics {
     pub runs: usize,
+    /// Seed the arguments were generated with, allows reproducing a failure
+    pub seed: u64,
+    /// Smallest arguments the test still failed with, if shrinking found any
+    pub minimized_arguments: Option<Vec<Felt252>>,
 }
 
 pub trait TestType {
@@ -94,6 +100,8 @@ pub enum TestCaseSummary<T: TestType> {
         arguments: Vec<Felt252>,
         /// Information on used gas
         gas_info: <T as TestType>::GasInfo,
+        /// Resource the gas was computed from
+        tracked_resource: TrackedResource,
         /// Resources used during test
         used_resources: UsedResources,
         /// Statistics of the test run
@@ -111,14 +119,47 @@ pub enum TestCaseSummary<T: TestType> {
         arguments: Vec<Felt252>,
         /// Statistics of the test run
         test_statistics: <T as TestType>::TestStatistics,
+        /// Output printed by the test case, `None` if it was not captured or is empty
+        captured_output: Option<String>,
+    },
+    /// Fixture requested by the test case panicked, the test body was not executed
+    FixtureFailed {
+        /// Name of the test case
+        name: String,
+        /// Name of the fixture that failed
+        fixture: String,
+        /// Message describing the fixture failure
+        msg: Option<String>,
+        /// Arguments used in the test case run
+        arguments: Vec<Felt252>,
+        /// Statistics of the test run
+        test_statistics: <T as TestType>::TestStatistics,
+        /// Output printed by the test case, `None` if it was not captured or is empty
+        captured_output: Option<String>,
+    },
+    /// Test case did not finish within its timeout
+    TimedOut {
+        /// Name of the test case
+        name: String,
+        /// Time the test case was running for before it was stopped
+        elapsed: Duration,
+        /// Arguments used in the test case run
+        arguments: Vec<Felt252>,
+        /// Statistics of the test run
+        test_statistics: <T as TestType>::TestStatistics,
     },
     /// Test case ignored due to `#[ignored]` attribute or `--ignored` flag
     Ignored {
         /// Name of the test case
         name: String,
+        /// Reason given in `#[ignore("...")]`
+        reason: Option<String>,
+    },
+    /// Test case cancelled after a failure with `--exit-first`, its result is not known
+    Skipped {
+        /// Name of the test case
+        name: String,
     },
-    /// Test case skipped due to exit first or execution interrupted, test result is ignored.
-    Skipped {},
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -130,12 +171,14 @@ pub enum AnyTestCaseSummary {
 
 impl<T: TestType> TestCaseSummary<T> {
     #[must_use]
-    pub fn name(&self) -> Option<&str> {
+    pub fn name(&self) -> &str {
         match self {
             TestCaseSummary::Failed { name, .. }
+            | TestCaseSummary::FixtureFailed { name, .. }
             | TestCaseSummary::Passed { name, .. }
-            | TestCaseSummary::Ignored { name, .. } => Some(name),
-            TestCaseSummary::Skipped { .. } => None,
+            | TestCaseSummary::TimedOut { name, .. }
+            | TestCaseSummary::Ignored { name, .. }
+            | TestCaseSummary::Skipped { name } => name,
         }
     }
 
@@ -143,15 +186,33 @@ impl<T: TestType> TestCaseSummary<T> {
     pub fn msg(&self) -> Option<&str> {
         match self {
             TestCaseSummary::Failed { msg: Some(msg), .. }
+            | TestCaseSummary::FixtureFailed { msg: Some(msg), .. }
             | TestCaseSummary::Passed { msg: Some(msg), .. } => Some(msg),
             _ => None,
         }
     }
+
+    #[must_use]
+    pub fn captured_output(&self) -> Option<&str> {
+        match self {
+            TestCaseSummary::Failed {
+                captured_output, ..
+            }
+            | TestCaseSummary::FixtureFailed {
+                captured_output, ..
+            } => captured_output.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 impl TestCaseSummary<Fuzzing> {
     #[must_use]
-    pub fn from(results: Vec<TestCaseSummary<Single>>) -> Self {
+    pub fn from(
+        results: Vec<TestCaseSummary<Single>>,
+        seed: u64,
+        minimized: Option<TestCaseSummary<Single>>,
+    ) -> Self {
         let last: TestCaseSummary<Single> = results
             .iter()
             .last()
@@ -164,6 +225,7 @@ impl TestCaseSummary<Fuzzing> {
                 msg,
                 arguments,
                 gas_info: _,
+                tracked_resource,
                 used_resources: _,
                 test_statistics: (),
                 trace_data: _,
@@ -182,8 +244,13 @@ impl TestCaseSummary<Fuzzing> {
                     msg,
                     arguments,
                     gas_info: GasStatistics::new(&gas_usages),
+                    tracked_resource,
                     used_resources: UsedResources::default(),
-                    test_statistics: FuzzingStatistics { runs },
+                    test_statistics: FuzzingStatistics {
+                        runs,
+                        seed,
+                        minimized_arguments: None,
+                    },
                     trace_data: (),
                 }
             }
@@ -192,16 +259,70 @@ impl TestCaseSummary<Fuzzing> {
                 msg,
                 arguments,
                 test_statistics: (),
-            } => TestCaseSummary::Failed {
+                captured_output,
+            } => {
+                // Failure message and output of the minimized input match the reported minimized arguments
+                let (msg, captured_output, minimized_arguments) = match minimized {
+                    Some(TestCaseSummary::Failed {
+                        msg,
+                        arguments,
+                        captured_output,
+                        ..
+                    }) => (msg, captured_output, Some(arguments)),
+                    _ => (msg, captured_output, None),
+                };
+
+                TestCaseSummary::Failed {
+                    name,
+                    msg,
+                    arguments,
+                    test_statistics: FuzzingStatistics {
+                        runs: results.len(),
+                        seed,
+                        minimized_arguments,
+                    },
+                    captured_output,
+                }
+            }
+            TestCaseSummary::FixtureFailed {
                 name,
+                fixture,
+                msg,
+                arguments,
+                test_statistics: (),
+                captured_output,
+            } => TestCaseSummary::FixtureFailed {
+                name,
+                fixture,
                 msg,
                 arguments,
                 test_statistics: FuzzingStatistics {
                     runs: results.len(),
+                    seed,
+                    minimized_arguments: None,
                 },
+                captured_output,
             },
-            TestCaseSummary::Ignored { name } => TestCaseSummary::Ignored { name: name.clone() },
-            TestCaseSummary::Skipped {} => TestCaseSummary::Skipped {},
+            TestCaseSummary::TimedOut {
+                name,
+                elapsed,
+                arguments,
+                test_statistics: (),
+            } => TestCaseSummary::TimedOut {
+                name,
+                elapsed,
+                arguments,
+                test_statistics: FuzzingStatistics {
+                    runs: results.len(),
+                    seed,
+                    minimized_arguments: None,
+                },
+            },
+            TestCaseSummary::Ignored { name, reason } => TestCaseSummary::Ignored {
+                name: name.clone(),
+                reason: reason.clone(),
+            },
+            TestCaseSummary::Skipped { name } => TestCaseSummary::Skipped { name: name.clone() },
         }
     }
 }
@@ -214,10 +335,12 @@ impl TestCaseSummary<Single> {
         test_case: &TestCaseWithResolvedConfig,
         arguments: Vec<Felt252>,
         gas: u128,
+        tracked_resource: TrackedResource,
         used_resources: UsedResources,
         call_trace: &Rc<RefCell<InternalCallTrace>>,
         contracts_data: &ContractsData,
         maybe_versioned_program_path: &Option<VersionedProgramPath>,
+        max_gas: Option<u128>,
     ) -> Self {
         let name = test_case.name.clone();
         let msg = extract_result_data(&run_result, &test_case.config.expected_result);
@@ -230,6 +353,7 @@ impl TestCaseSummary<Single> {
                         arguments,
                         test_statistics: (),
                         gas_info: gas,
+                        tracked_resource,
                         used_resources,
                         trace_data: VersionedProfilerCallTrace::V1(build_profiler_call_trace(
                             call_trace,
@@ -237,13 +361,18 @@ impl TestCaseSummary<Single> {
                             maybe_versioned_program_path,
                         )),
                     };
-                    check_available_gas(&test_case.config.available_gas, summary)
+                    if test_case.config.available_gas.is_some() {
+                        check_available_gas(&test_case.config.available_gas, summary)
+                    } else {
+                        check_max_gas(max_gas, summary)
+                    }
                 }
                 ExpectedTestResult::Panics(_) => TestCaseSummary::Failed {
                     name,
                     msg,
                     arguments,
                     test_statistics: (),
+                    captured_output: None,
                 },
             },
             RunResultValue::Panic(value) => match &test_case.config.expected_result {
@@ -252,6 +381,7 @@ impl TestCaseSummary<Single> {
                     msg,
                     arguments,
                     test_statistics: (),
+                    captured_output: None,
                 },
                 ExpectedTestResult::Panics(panic_expectation) => match panic_expectation {
                     ExpectedPanicValue::Exact(expected) if !is_matching(&value, expected) => {
@@ -260,6 +390,7 @@ impl TestCaseSummary<Single> {
                             msg,
                             arguments,
                             test_statistics: (),
+                            captured_output: None,
                         }
                     }
                     _ => TestCaseSummary::Passed {
@@ -268,6 +399,7 @@ impl TestCaseSummary<Single> {
                         arguments,
                         test_statistics: (),
                         gas_info: gas,
+                        tracked_resource,
                         used_resources,
                         trace_data: VersionedProfilerCallTrace::V1(build_profiler_call_trace(
                             call_trace,
@@ -279,6 +411,71 @@ impl TestCaseSummary<Single> {
             },
         }
     }
+
+    /// Output is kept only for failed test cases, passing ones are reported without it
+    #[must_use]
+    pub(crate) fn with_captured_output(self, output: Option<String>) -> Self {
+        match self {
+            TestCaseSummary::Failed {
+                name,
+                msg,
+                arguments,
+                test_statistics,
+                captured_output: _,
+            } => TestCaseSummary::Failed {
+                name,
+                msg,
+                arguments,
+                test_statistics,
+                captured_output: output,
+            },
+            TestCaseSummary::FixtureFailed {
+                name,
+                fixture,
+                msg,
+                arguments,
+                test_statistics,
+                captured_output: _,
+            } => TestCaseSummary::FixtureFailed {
+                name,
+                fixture,
+                msg,
+                arguments,
+                test_statistics,
+                captured_output: output,
+            },
+            _ => self,
+        }
+    }
+
+    /// Fixture errors are reported regardless of the expected test result, the test body never ran
+    #[must_use]
+    pub(crate) fn from_failed_fixture(
+        fixture: &str,
+        run_result: Result<RunResult, RunnerError>,
+        test_case: &TestCaseWithResolvedConfig,
+        arguments: Vec<Felt252>,
+    ) -> Self {
+        let reason = match run_result {
+            Ok(RunResult {
+                value: RunResultValue::Panic(panic_data),
+                ..
+            }) => build_readable_text(&panic_data).unwrap_or_default(),
+            Ok(_) => String::new(),
+            Err(error) => format!("\n    {error}\n"),
+        };
+
+        TestCaseSummary::FixtureFailed {
+            name: test_case.name.clone(),
+            fixture: fixture.to_string(),
+            msg: Some(format!(
+                "\n    Fixture `{fixture}` failed, test body was not executed\n{reason}"
+            )),
+            arguments,
+            test_statistics: (),
+            captured_output: None,
+        }
+    }
 }
 
 fn join_short_strings(data: &[Felt252]) -> String {
@@ -352,7 +549,7 @@ fn extract_result_data(run_result: &RunResult, expectation: &ExpectedTestResult)
 
 impl AnyTestCaseSummary {
     #[must_use]
-    pub fn name(&self) -> Option<&str> {
+    pub fn name(&self) -> &str {
         match self {
             AnyTestCaseSummary::Fuzzing(case) => case.name(),
             AnyTestCaseSummary::Single(case) => case.name(),
@@ -367,6 +564,14 @@ impl AnyTestCaseSummary {
         }
     }
 
+    #[must_use]
+    pub fn captured_output(&self) -> Option<&str> {
+        match self {
+            AnyTestCaseSummary::Fuzzing(case) => case.captured_output(),
+            AnyTestCaseSummary::Single(case) => case.captured_output(),
+        }
+    }
+
     #[must_use]
     pub fn is_passed(&self) -> bool {
         matches!(
@@ -376,12 +581,38 @@ impl AnyTestCaseSummary {
         )
     }
 
+    /// Timed out tests and tests with a failed fixture are failed as well
     #[must_use]
     pub fn is_failed(&self) -> bool {
         matches!(
             self,
-            AnyTestCaseSummary::Single(TestCaseSummary::Failed { .. })
-                | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed { .. })
+            AnyTestCaseSummary::Single(
+                TestCaseSummary::Failed { .. }
+                    | TestCaseSummary::FixtureFailed { .. }
+                    | TestCaseSummary::TimedOut { .. }
+            ) | AnyTestCaseSummary::Fuzzing(
+                TestCaseSummary::Failed { .. }
+                    | TestCaseSummary::FixtureFailed { .. }
+                    | TestCaseSummary::TimedOut { .. }
+            )
+        )
+    }
+
+    #[must_use]
+    pub fn is_fixture_failed(&self) -> bool {
+        matches!(
+            self,
+            AnyTestCaseSummary::Single(TestCaseSummary::FixtureFailed { .. })
+                | AnyTestCaseSummary::Fuzzing(TestCaseSummary::FixtureFailed { .. })
+        )
+    }
+
+    #[must_use]
+    pub fn is_timed_out(&self) -> bool {
+        matches!(
+            self,
+            AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { .. })
+                | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { .. })
         )
     }
 
@@ -402,4 +633,16 @@ impl AnyTestCaseSummary {
                 | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Ignored { .. })
         )
     }
+
+    /// Returns `None` if the test was not ignored or was ignored without a reason
+    #[must_use]
+    pub fn ignore_reason(&self) -> Option<&str> {
+        match self {
+            AnyTestCaseSummary::Single(TestCaseSummary::Ignored { reason, .. })
+            | AnyTestCaseSummary::Fuzzing(TestCaseSummary::Ignored { reason, .. }) => {
+                reason.as_deref()
+            }
+            _ => None,
+        }
+    }
 }
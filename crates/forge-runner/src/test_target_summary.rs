@@ -1,10 +1,14 @@
 use crate::test_case_summary::AnyTestCaseSummary;
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// Summary of the test run in the file
 #[derive(Debug)]
 pub struct TestTargetSummary {
     /// Summaries of each test case in the file
     pub test_case_summaries: Vec<AnyTestCaseSummary>,
+    /// Time it took to run each test case, by its name
+    pub test_case_durations: HashMap<String, Duration>,
 }
 
 impl TestTargetSummary {
@@ -1,26 +1,62 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Context, Result};
 use cairo_vm::Felt252;
+use camino::{Utf8Path, Utf8PathBuf};
 use conversions::{string::IntoHexStr, IntoConv};
+use serde::{Deserialize, Serialize};
 use starknet::{
-    core::types::{BlockId, MaybePendingBlockWithTxHashes},
+    core::types::{BlockId, BlockTag, MaybePendingBlockWithTxHashes},
     providers::{jsonrpc::HttpTransport, JsonRpcClient, Provider},
 };
 use starknet_api::block::BlockNumber;
 use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io::ErrorKind;
 use tokio::runtime::Handle;
 use url::Url;
 
+pub const BLOCK_TIMESTAMPS_FILE: &str = "block_timestamps.json";
+
 #[derive(Default)]
 pub struct BlockNumberMap {
     url_to_latest_block_number: HashMap<Url, BlockNumber>,
     url_and_hash_to_block_number: HashMap<(Url, Felt252), BlockNumber>,
+    url_and_timestamp_to_block_number: HashMap<(Url, u64), BlockNumber>,
+    timestamps_cache_file: Option<Utf8PathBuf>,
+    offline: bool,
+}
+
+/// Block resolved from a timestamp, saved so the same block is forked in the next runs
+#[derive(Serialize, Deserialize)]
+struct ResolvedTimestamp {
+    url: String,
+    timestamp: u64,
+    block_number: u64,
 }
 
 impl BlockNumberMap {
+    /// Map that refuses to query nodes, used when fork data is replayed from snapshots
+    #[must_use]
+    pub fn offline() -> Self {
+        Self {
+            offline: true,
+            ..Default::default()
+        }
+    }
+
+    fn ensure_online(&self, url: &Url) -> Result<()> {
+        ensure!(
+            !self.offline,
+            "Block number of the fork with url = {url} cannot be resolved while replaying fork data, pin the fork to a block number instead"
+        );
+        Ok(())
+    }
+
     pub async fn get_latest_block_number(&mut self, url: Url) -> Result<BlockNumber> {
         let block_number = if let Some(block_number) = self.url_to_latest_block_number.get(&url) {
             *block_number
         } else {
+            self.ensure_online(&url)?;
             let latest_block_number = fetch_latest_block_number(url.clone()).await?;
 
             self.url_to_latest_block_number
@@ -42,6 +78,7 @@ impl BlockNumberMap {
         {
             *block_number
         } else {
+            self.ensure_online(&url)?;
             let block_number = fetch_block_number_for_hash(url.clone(), hash).await?;
 
             self.url_and_hash_to_block_number
@@ -53,6 +90,82 @@ impl BlockNumberMap {
         Ok(block_number)
     }
 
+    /// Reads block numbers resolved from timestamps in the previous runs from `cache_dir`,
+    /// new ones are saved there as soon as they are resolved
+    pub fn with_timestamps_cache(mut self, cache_dir: &Utf8Path) -> Result<Self> {
+        let path = cache_dir.join(BLOCK_TIMESTAMPS_FILE);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => Err(err)?,
+        };
+
+        if let Some(content) = content {
+            let resolved_timestamps: Vec<ResolvedTimestamp> = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {path}"))?;
+
+            for resolved in resolved_timestamps {
+                let url =
+                    Url::parse(&resolved.url).with_context(|| format!("Failed to parse {path}"))?;
+                self.url_and_timestamp_to_block_number.insert(
+                    (url, resolved.timestamp),
+                    BlockNumber(resolved.block_number),
+                );
+            }
+        }
+        self.timestamps_cache_file = Some(path);
+
+        Ok(self)
+    }
+
+    /// Returns the last block at or before the unix `timestamp`
+    pub async fn get_block_number_for_timestamp(
+        &mut self,
+        url: Url,
+        timestamp: u64,
+    ) -> Result<BlockNumber> {
+        if let Some(block_number) = self
+            .url_and_timestamp_to_block_number
+            .get(&(url.clone(), timestamp))
+        {
+            return Ok(*block_number);
+        }
+
+        self.ensure_online(&url)?;
+        let (block_number, is_latest) =
+            fetch_block_number_for_timestamp(url.clone(), timestamp).await?;
+
+        // Newer blocks may still be produced at or before the timestamp, so the latest one is not pinned
+        if !is_latest {
+            self.url_and_timestamp_to_block_number
+                .insert((url, timestamp), block_number);
+            self.save_timestamps_cache()?;
+        }
+
+        Ok(block_number)
+    }
+
+    fn save_timestamps_cache(&self) -> Result<()> {
+        let Some(path) = &self.timestamps_cache_file else {
+            return Ok(());
+        };
+
+        let mut resolved_timestamps: Vec<_> = self
+            .url_and_timestamp_to_block_number
+            .iter()
+            .map(|((url, timestamp), block_number)| ResolvedTimestamp {
+                url: url.to_string(),
+                timestamp: *timestamp,
+                block_number: block_number.0,
+            })
+            .collect();
+        resolved_timestamps.sort_by(|a, b| (&a.url, a.timestamp).cmp(&(&b.url, b.timestamp)));
+
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_string_pretty(&resolved_timestamps)?)
+            .with_context(|| format!("Failed to write {path}"))
+    }
+
     #[must_use]
     pub fn get_url_to_latest_block_number(&self) -> &HashMap<Url, BlockNumber> {
         &self.url_to_latest_block_number
@@ -84,3 +197,130 @@ async fn fetch_block_number_for_hash(url: Url, block_hash: Felt252) -> Result<Bl
         )),
     }
 }
+
+/// Returns the last block at or before `timestamp` and whether it is the latest block
+async fn fetch_block_number_for_timestamp(url: Url, timestamp: u64) -> Result<(BlockNumber, bool)> {
+    let (latest_block_number, latest_timestamp) =
+        fetch_block_timestamp(url.clone(), BlockId::Tag(BlockTag::Latest)).await?;
+    if latest_timestamp <= timestamp {
+        return Ok((BlockNumber(latest_block_number), true));
+    }
+
+    let (_, first_timestamp) = fetch_block_timestamp(url.clone(), BlockId::Number(0)).await?;
+    ensure!(
+        first_timestamp <= timestamp,
+        "No block at or before block_id.timestamp = {timestamp} found for the fork with url = {url}, the first block has timestamp = {first_timestamp}"
+    );
+
+    let block_number = last_block_at_or_before(0, latest_block_number, timestamp, |number| {
+        let url = url.clone();
+        async move {
+            fetch_block_timestamp(url.clone(), BlockId::Number(number))
+                .await
+                .map(|(_, timestamp)| timestamp)
+                .with_context(|| {
+                    format!("Failed to fetch block {number} from the fork with url = {url}, resolving block_id.timestamp requires a node serving historical blocks")
+                })
+        }
+    })
+    .await?;
+
+    Ok((BlockNumber(block_number), false))
+}
+
+/// Binary search for the last block with timestamp at or before `timestamp`,
+/// block `low` must have an earlier or equal timestamp and block `high` a later one
+async fn last_block_at_or_before<F, Fut>(
+    mut low: u64,
+    mut high: u64,
+    timestamp: u64,
+    mut block_timestamp: F,
+) -> Result<u64>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<u64>>,
+{
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if block_timestamp(middle).await? <= timestamp {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    Ok(low)
+}
+
+async fn fetch_block_timestamp(url: Url, block_id: BlockId) -> Result<(u64, u64)> {
+    let client = JsonRpcClient::new(HttpTransport::new(url));
+
+    match Handle::current()
+        .spawn(async move { client.get_block_with_tx_hashes(block_id).await })
+        .await??
+    {
+        MaybePendingBlockWithTxHashes::Block(block) => Ok((block.block_number, block.timestamp)),
+        MaybePendingBlockWithTxHashes::PendingBlock(_) => {
+            Err(anyhow!("Expected a block, received a pending block"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    async fn search(timestamps: &[u64], timestamp: u64) -> u64 {
+        let high = timestamps.len() as u64 - 1;
+
+        last_block_at_or_before(0, high, timestamp, |number| {
+            let block_timestamp = timestamps[usize::try_from(number).unwrap()];
+            async move { Ok(block_timestamp) }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn finds_last_block_at_or_before_timestamp() {
+        let timestamps = [100, 110, 110, 120, 150, 170, 200];
+
+        assert_eq!(search(&timestamps, 100).await, 0);
+        assert_eq!(search(&timestamps, 110).await, 2);
+        assert_eq!(search(&timestamps, 149).await, 3);
+        assert_eq!(search(&timestamps, 150).await, 4);
+        assert_eq!(search(&timestamps, 199).await, 5);
+    }
+
+    #[tokio::test]
+    async fn resolved_timestamps_are_cached() {
+        let temp = TempDir::new().unwrap();
+        let cache_dir = Utf8Path::from_path(temp.path()).unwrap();
+        let url = Url::parse("http://127.0.0.1:1").unwrap();
+
+        let mut block_number_map = BlockNumberMap::default()
+            .with_timestamps_cache(cache_dir)
+            .unwrap();
+        block_number_map
+            .url_and_timestamp_to_block_number
+            .insert((url.clone(), 1000), BlockNumber(7));
+        block_number_map.save_timestamps_cache().unwrap();
+
+        // The node is not queried for cached timestamps, also while replaying fork data
+        let mut block_number_map = BlockNumberMap::offline()
+            .with_timestamps_cache(cache_dir)
+            .unwrap();
+        assert_eq!(
+            block_number_map
+                .get_block_number_for_timestamp(url.clone(), 1000)
+                .await
+                .unwrap(),
+            BlockNumber(7)
+        );
+        assert!(block_number_map
+            .get_block_number_for_timestamp(url, 1001)
+            .await
+            .is_err());
+    }
+}
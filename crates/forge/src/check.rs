@@ -0,0 +1,423 @@
+use crate::json_output::SCHEMA_VERSION;
+use crate::run_tests::resolve_config::validate_fork_config;
+use crate::scarb::config::{ForgeConfigFromScarb, ForkTarget};
+use crate::scarb::{
+    build_artifacts_with_scarb, load_test_artifacts, should_compile_starknet_contract_target,
+};
+use crate::{pretty_printing, ExitStatus};
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::Parser;
+use configuration::load_package_config;
+use forge_runner::package_tests::with_config::TestTargetWithConfig;
+use forge_runner::package_tests::TestTargetLocation;
+use forge_runner::running::with_config::test_target_with_config;
+use forge_runner::{function_args, validate_fuzzer_config};
+use regex::Regex;
+use scarb_api::metadata::{workspace_metadata, Metadata, PackageMetadata};
+use scarb_api::{get_contracts_artifacts_and_source_sierra_paths, target_dir_for_workspace};
+use scarb_ui::args::{FeaturesSpec, PackagesFilter};
+use serde::Serialize;
+use shared::print::set_human_output_to_stderr;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use walkdir::WalkDir;
+
+#[derive(Parser, Debug)]
+pub struct CheckArgs {
+    #[command(flatten)]
+    packages_filter: PackagesFilter,
+
+    /// Specify features to enable
+    #[command(flatten)]
+    features: FeaturesSpec,
+
+    /// Build contracts separately in the scarb starknet contract target
+    #[arg(long)]
+    no_optimization: bool,
+
+    /// Write problems found in each package as a JSON line to stdout, other output is written to stderr
+    #[arg(long)]
+    json: bool,
+}
+
+/// Problem which would make a test fail or not run as intended, found without running it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Problem {
+    /// Line of the problem, if it was found in the source code
+    pub line: Option<usize>,
+    /// Test with the problem, if it was found in its attributes
+    pub test: Option<String>,
+    pub message: String,
+}
+
+impl Display for Problem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "{line}: ")?;
+        }
+        if let Some(test) = &self.test {
+            write!(f, "{test}: ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Problems found in a package, grouped by the file they are in, relative to the package root
+#[derive(Debug, Default, Serialize)]
+pub struct PackageCheck {
+    pub package: String,
+    /// Number of collected tests
+    pub tests: usize,
+    pub files: BTreeMap<Utf8PathBuf, Vec<Problem>>,
+}
+
+impl PackageCheck {
+    fn add(&mut self, file: Utf8PathBuf, problem: Problem) {
+        self.files.entry(file).or_default().push(problem);
+    }
+
+    #[must_use]
+    pub fn problems_count(&self) -> usize {
+        self.files.values().map(Vec::len).sum()
+    }
+
+    fn emit(&self) -> Result<()> {
+        #[derive(Serialize)]
+        struct CheckLine<'a> {
+            schema_version: u32,
+            #[serde(flatten)]
+            check: &'a PackageCheck,
+        }
+
+        let line = CheckLine {
+            schema_version: SCHEMA_VERSION,
+            check: self,
+        };
+        println!("{}", serde_json::to_string(&line)?);
+
+        Ok(())
+    }
+}
+
+/// Builds the packages and collects their tests without running them, failing if any problems were found
+pub fn check_workspace(args: &CheckArgs) -> Result<ExitStatus> {
+    // Keeps stdout free of anything but the JSON lines
+    set_human_output_to_stderr(args.json);
+
+    let scarb_metadata = workspace_metadata()?;
+    let packages: Vec<PackageMetadata> = args
+        .packages_filter
+        .match_many(&scarb_metadata)
+        .context("Failed to find any packages matching the specified filter")?;
+    let filter = PackagesFilter::generate_for::<Metadata>(packages.iter());
+
+    build_artifacts_with_scarb(
+        filter,
+        args.features.clone(),
+        &scarb_metadata.app_version_info.version,
+        args.no_optimization,
+    )?;
+
+    let snforge_target_dir_path =
+        target_dir_for_workspace(&scarb_metadata).join(&scarb_metadata.current_profile);
+    let mut problems_found = false;
+
+    for package in packages {
+        let check = check_package(
+            &package,
+            &scarb_metadata,
+            &snforge_target_dir_path,
+            args.no_optimization,
+        )?;
+
+        if args.json {
+            check.emit()?;
+        }
+        pretty_printing::print_package_check(&check);
+        problems_found |= check.problems_count() > 0;
+    }
+
+    Ok(if problems_found {
+        ExitStatus::Failure
+    } else {
+        ExitStatus::Success
+    })
+}
+
+fn check_package(
+    package: &PackageMetadata,
+    scarb_metadata: &Metadata,
+    snforge_target_dir_path: &Utf8Path,
+    no_optimization: bool,
+) -> Result<PackageCheck> {
+    let raw_test_targets = load_test_artifacts(snforge_target_dir_path, package)?;
+    let contracts = get_contracts_artifacts_and_source_sierra_paths(
+        scarb_metadata,
+        &package.id,
+        None,
+        !should_compile_starknet_contract_target(
+            &scarb_metadata.app_version_info.version,
+            no_optimization,
+        ),
+    )?;
+    let fork_targets =
+        load_package_config::<ForgeConfigFromScarb>(scarb_metadata, &package.id)?.fork;
+    let sources = SourceFiles::load(&package.root)?;
+
+    let mut check = PackageCheck {
+        package: package.name.clone(),
+        ..PackageCheck::default()
+    };
+
+    for raw_test_target in raw_test_targets {
+        let tests_location = raw_test_target.tests_location;
+
+        match test_target_with_config(raw_test_target) {
+            Ok(test_target) => {
+                check.tests += test_target.test_cases.len();
+
+                for (test, message) in attribute_problems(&test_target, &fork_targets) {
+                    check.add(
+                        sources.file_of_test(tests_location, &test),
+                        Problem {
+                            line: None,
+                            test: Some(test),
+                            message,
+                        },
+                    );
+                }
+            }
+            // Attributes are read by running the config pass of the tests, which fails for the whole target
+            Err(error) => check.add(
+                Utf8PathBuf::from(tests_dir(tests_location)),
+                Problem {
+                    line: None,
+                    test: None,
+                    message: format!("Failed to collect tests: {error:#}"),
+                },
+            ),
+        }
+    }
+
+    for (file, line, contract_name) in sources.declared_contracts() {
+        if !contracts.contains_key(&contract_name) {
+            check.add(
+                file,
+                Problem {
+                    line: Some(line),
+                    test: None,
+                    message: format!(
+                        "Contract = {contract_name} is declared, but it was not found in the artifacts of package {}",
+                        package.name
+                    ),
+                },
+            );
+        }
+    }
+
+    Ok(check)
+}
+
+/// Problems with the fork and fuzzer attributes, as pairs of the test name and the message
+fn attribute_problems(
+    test_target: &TestTargetWithConfig,
+    fork_targets: &[ForkTarget],
+) -> Vec<(String, String)> {
+    let sierra_program = &test_target.sierra_program.program;
+    let type_declarations: HashMap<_, _> = sierra_program
+        .type_declarations
+        .iter()
+        .map(|f| (f.id.id, f))
+        .collect();
+
+    let mut problems = vec![];
+    for case in &test_target.test_cases {
+        if let Some(fork_config) = &case.config.fork_config {
+            if let Err(error) = validate_fork_config(fork_config, fork_targets) {
+                problems.push((case.name.clone(), format!("{error:#}")));
+            }
+        }
+
+        let args = sierra_program
+            .funcs
+            .iter()
+            .find(|f| {
+                f.id.debug_name
+                    .as_ref()
+                    .is_some_and(|name| name.ends_with(&case.name))
+            })
+            .map(|function| function_args(function, &type_declarations))
+            .unwrap_or_default();
+        let fuzzer_config = case.config.fuzzer_config.as_ref();
+
+        if args.is_empty() && fuzzer_config.is_some() {
+            problems.push((
+                case.name.clone(),
+                "Fuzzer config is set, but the test has no arguments to fuzz".to_string(),
+            ));
+        } else if let Err(error) = validate_fuzzer_config(&args, fuzzer_config) {
+            problems.push((case.name.clone(), format!("{error:#}")));
+        }
+    }
+
+    problems
+}
+
+fn tests_dir(tests_location: TestTargetLocation) -> &'static str {
+    match tests_location {
+        TestTargetLocation::Lib => "src",
+        TestTargetLocation::Tests => "tests",
+    }
+}
+
+/// Cairo files of the package, searched for the tests and the declared contracts
+struct SourceFiles {
+    /// Paths relative to the package root with the contents of the files
+    files: Vec<(Utf8PathBuf, String)>,
+}
+
+impl SourceFiles {
+    fn load(package_root: &Utf8Path) -> Result<Self> {
+        let mut files = vec![];
+
+        for dir in ["src", "tests"] {
+            let dir = package_root.join(dir);
+            if !dir.is_dir() {
+                continue;
+            }
+
+            for entry in WalkDir::new(&dir).sort_by_file_name() {
+                let entry = entry.with_context(|| format!("Failed to read directory {dir}"))?;
+                let path = Utf8Path::from_path(entry.path()).with_context(|| {
+                    format!("Path {} is not valid UTF-8", entry.path().display())
+                })?;
+
+                if path.extension() == Some("cairo") {
+                    let content = fs::read_to_string(path)
+                        .with_context(|| format!("Failed to read {path}"))?;
+                    files.push((path.strip_prefix(package_root)?.to_path_buf(), content));
+                }
+            }
+        }
+
+        Ok(Self { files })
+    }
+
+    /// File defining the function of the test, or the directory of its target if it can't be found
+    fn file_of_test(&self, tests_location: TestTargetLocation, test_name: &str) -> Utf8PathBuf {
+        let dir = tests_dir(tests_location);
+        let function_name = test_name.rsplit("::").next().unwrap_or(test_name);
+        let definition = Regex::new(&format!(r"\bfn\s+{}\s*[(<]", regex::escape(function_name)))
+            .expect("Escaped function name is a valid regex");
+
+        self.files
+            .iter()
+            .find(|(path, content)| path.starts_with(dir) && definition.is_match(content))
+            .map_or_else(|| Utf8PathBuf::from(dir), |(path, _)| path.clone())
+    }
+
+    /// Names of the contracts passed as string literals to `declare`, with the files and lines of the calls
+    fn declared_contracts(&self) -> Vec<(Utf8PathBuf, usize, String)> {
+        let declare =
+            Regex::new(r#"\bdeclare\(\s*"([^"]*)"\s*\)"#).expect("Declare regex is valid");
+
+        let mut declared = vec![];
+        for (path, content) in &self.files {
+            for (index, line) in content.lines().enumerate() {
+                if line.trim_start().starts_with("//") {
+                    continue;
+                }
+                for captures in declare.captures_iter(line) {
+                    declared.push((path.clone(), index + 1, captures[1].to_string()));
+                }
+            }
+        }
+
+        declared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(files: &[(&str, &str)]) -> SourceFiles {
+        SourceFiles {
+            files: files
+                .iter()
+                .map(|(path, content)| (Utf8PathBuf::from(path), (*content).to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn declared_contracts_with_lines() {
+        let sources = sources(&[(
+            "tests/test_contract.cairo",
+            "fn deploy() {\n    let contract = declare(\"HelloStarknet\").unwrap();\n    // declare(\"Commented\")\n    declare( \"Other\" );\n}\n",
+        )]);
+
+        assert_eq!(
+            sources.declared_contracts(),
+            [
+                (
+                    Utf8PathBuf::from("tests/test_contract.cairo"),
+                    2,
+                    "HelloStarknet".to_string()
+                ),
+                (
+                    Utf8PathBuf::from("tests/test_contract.cairo"),
+                    4,
+                    "Other".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn file_of_test_in_target_dir() {
+        let sources = sources(&[
+            ("src/lib.cairo", "#[test]\nfn same_name() {}\n"),
+            ("tests/a.cairo", "#[test]\nfn other() {}\n"),
+            ("tests/b.cairo", "#[test]\nfn same_name() {}\n"),
+        ]);
+
+        assert_eq!(
+            sources.file_of_test(
+                TestTargetLocation::Tests,
+                "pkg_integrationtest::b::same_name"
+            ),
+            "tests/b.cairo"
+        );
+        assert_eq!(
+            sources.file_of_test(TestTargetLocation::Lib, "pkg::tests::same_name"),
+            "src/lib.cairo"
+        );
+        assert_eq!(
+            sources.file_of_test(TestTargetLocation::Tests, "pkg_integrationtest::c::missing"),
+            "tests"
+        );
+    }
+
+    #[test]
+    fn problem_display() {
+        let problem = Problem {
+            line: Some(12),
+            test: None,
+            message: "Contract = Missing is declared".to_string(),
+        };
+        let attribute_problem = Problem {
+            line: None,
+            test: Some("pkg::test".to_string()),
+            message: "Fork configuration named = NAME not found in the Scarb.toml".to_string(),
+        };
+
+        assert_eq!(problem.to_string(), "12: Contract = Missing is declared");
+        assert_eq!(
+            attribute_problem.to_string(),
+            "pkg::test: Fork configuration named = NAME not found in the Scarb.toml"
+        );
+    }
+}
@@ -1,13 +1,29 @@
 use crate::scarb::config::ForgeConfigFromScarb;
+use anyhow::{ensure, Context, Result};
 use camino::Utf8PathBuf;
+use cheatnet::forking::snapshot::ForkDataMode;
 use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
 use forge_runner::forge_config::{
-    ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig,
+    ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig, TrackedResource,
 };
 use rand::{thread_rng, RngCore};
+use runtime::starknet::context::BlockContextVersion;
+use std::collections::HashMap;
 use std::env;
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroU64};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Parses `KEY=VALUE` passed with `--env`, the value may contain `=`
+pub(crate) fn parse_env_override(value: &str) -> Result<(String, String)> {
+    let (key, value) = value.split_once('=').context("Expected KEY=VALUE")?;
+    ensure!(
+        !key.is_empty(),
+        "Name of the environment variable can't be empty"
+    );
+
+    Ok((key.to_string(), value.to_string()))
+}
 
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::fn_params_excessive_bools)]
@@ -16,13 +32,23 @@ pub fn combine_configs(
     fuzzer_runs: Option<NonZeroU32>,
     fuzzer_seed: Option<u64>,
     detailed_resources: bool,
+    json: bool,
     save_trace_data: bool,
     build_profile: bool,
     coverage: bool,
     max_n_steps: Option<u32>,
+    timeout: Option<NonZeroU64>,
+    tracked_resource: TrackedResource,
+    block_context_version: Option<BlockContextVersion>,
     contracts_data: ContractsData,
     cache_dir: Utf8PathBuf,
+    fork_data: Option<ForkDataMode>,
+    fuzz_corpus_dir: Utf8PathBuf,
+    prune_fuzz_corpus: bool,
+    nocapture: bool,
     versioned_programs_dir: Utf8PathBuf,
+    coverage_data_dir: Utf8PathBuf,
+    env_overrides: &[(String, String)],
     forge_config_from_scarb: &ForgeConfigFromScarb,
 ) -> ForgeConfig {
     let execution_data_to_save = ExecutionDataToSave::from_flags(
@@ -31,6 +57,10 @@ pub fn combine_configs(
         coverage || forge_config_from_scarb.coverage,
     );
 
+    // Read once, so all tests see the same values
+    let environment_variables =
+        environment_variables(env::vars(), &forge_config_from_scarb.env, env_overrides);
+
     ForgeConfig {
         test_runner_config: Arc::new(TestRunnerConfig {
             exit_first: exit_first || forge_config_from_scarb.exit_first,
@@ -41,19 +71,48 @@ pub fn combine_configs(
                 .or(forge_config_from_scarb.fuzzer_seed)
                 .unwrap_or_else(|| thread_rng().next_u64()),
             max_n_steps: max_n_steps.or(forge_config_from_scarb.max_n_steps),
+            timeout: timeout
+                .or(forge_config_from_scarb.timeout)
+                .map(|timeout| Duration::from_secs(timeout.get())),
+            max_gas: forge_config_from_scarb
+                .max_gas
+                .map(|max_gas| u128::from(max_gas.get())),
+            tracked_resource,
+            block_context_version: block_context_version
+                .or(forge_config_from_scarb.block_context)
+                .unwrap_or_default(),
             is_vm_trace_needed: execution_data_to_save.is_vm_trace_needed(),
             cache_dir,
+            fork_data,
             contracts_data,
-            environment_variables: env::vars().collect(),
+            environment_variables,
+            fuzz_corpus_dir,
+            prune_fuzz_corpus,
+            capture_output: !nocapture,
         }),
         output_config: Arc::new(OutputConfig {
             detailed_resources: detailed_resources || forge_config_from_scarb.detailed_resources,
+            json,
             execution_data_to_save,
             versioned_programs_dir,
+            coverage_data_dir,
         }),
     }
 }
 
+/// Variables of the process environment, overridden by `[tool.snforge.env]` and then by `--env`
+fn environment_variables(
+    process_env: impl IntoIterator<Item = (String, String)>,
+    scarb_env: &HashMap<String, String>,
+    env_overrides: &[(String, String)],
+) -> HashMap<String, String> {
+    let mut environment_variables: HashMap<_, _> = process_env.into_iter().collect();
+    environment_variables.extend(scarb_env.clone());
+    environment_variables.extend(env_overrides.iter().cloned());
+
+    environment_variables
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,10 +127,20 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
+            Default::default(),
             None,
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            &[],
             &Default::default(),
         );
         let config2 = combine_configs(
@@ -82,10 +151,20 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
+            Default::default(),
             None,
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            &[],
             &Default::default(),
         );
 
@@ -107,10 +186,20 @@ mod tests {
             false,
             false,
             false,
+            false,
+            None,
+            None,
+            Default::default(),
             None,
             Default::default(),
             Default::default(),
             Default::default(),
+            Default::default(),
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            &[],
             &Default::default(),
         );
         assert_eq!(
@@ -121,15 +210,25 @@ mod tests {
                     fuzzer_runs: NonZeroU32::new(256).unwrap(),
                     fuzzer_seed: config.test_runner_config.fuzzer_seed,
                     max_n_steps: None,
+                    timeout: None,
+                    max_gas: None,
+                    tracked_resource: TrackedResource::CairoSteps,
+                    block_context_version: BlockContextVersion::default(),
                     is_vm_trace_needed: false,
                     cache_dir: Default::default(),
+                    fork_data: None,
                     contracts_data: Default::default(),
                     environment_variables: config.test_runner_config.environment_variables.clone(),
+                    fuzz_corpus_dir: Default::default(),
+                    prune_fuzz_corpus: false,
+                    capture_output: true,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: false,
+                    json: false,
                     execution_data_to_save: ExecutionDataToSave::default(),
                     versioned_programs_dir: Default::default(),
+                    coverage_data_dir: Default::default(),
                 }),
             }
         );
@@ -147,6 +246,10 @@ mod tests {
             build_profile: true,
             coverage: true,
             max_n_steps: Some(1_000_000),
+            timeout: NonZeroU64::new(120),
+            max_gas: NonZeroU64::new(1_000_000),
+            block_context: Some(BlockContextVersion::V0_13_0),
+            env: HashMap::new(),
         };
 
         let config = combine_configs(
@@ -157,10 +260,20 @@ mod tests {
             false,
             false,
             false,
+            false,
             None,
+            None,
+            Default::default(),
+            None,
+            Default::default(),
             Default::default(),
             Default::default(),
             Default::default(),
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            &[],
             &config_from_scarb,
         );
         assert_eq!(
@@ -171,19 +284,29 @@ mod tests {
                     fuzzer_runs: NonZeroU32::new(1234).unwrap(),
                     fuzzer_seed: 500,
                     max_n_steps: Some(1_000_000),
+                    timeout: Some(Duration::from_secs(120)),
+                    max_gas: Some(1_000_000),
+                    tracked_resource: TrackedResource::CairoSteps,
+                    block_context_version: BlockContextVersion::V0_13_0,
                     is_vm_trace_needed: true,
                     cache_dir: Default::default(),
+                    fork_data: None,
                     contracts_data: Default::default(),
                     environment_variables: config.test_runner_config.environment_variables.clone(),
+                    fuzz_corpus_dir: Default::default(),
+                    prune_fuzz_corpus: false,
+                    capture_output: true,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: true,
+                    json: false,
                     execution_data_to_save: ExecutionDataToSave {
                         trace: true,
                         profile: true,
                         coverage: true,
                     },
                     versioned_programs_dir: Default::default(),
+                    coverage_data_dir: Default::default(),
                 }),
             }
         );
@@ -201,19 +324,33 @@ mod tests {
             build_profile: false,
             coverage: false,
             max_n_steps: Some(1234),
+            timeout: NonZeroU64::new(120),
+            max_gas: NonZeroU64::new(1_000_000),
+            block_context: Some(BlockContextVersion::V0_13_0),
+            env: HashMap::new(),
         };
         let config = combine_configs(
             true,
             Some(NonZeroU32::new(100).unwrap()),
             Some(32),
             true,
+            false,
             true,
             true,
             true,
             Some(1_000_000),
+            NonZeroU64::new(60),
+            TrackedResource::SierraGas,
+            Some(BlockContextVersion::V0_13_2),
+            Default::default(),
             Default::default(),
             Default::default(),
             Default::default(),
+            false,
+            false,
+            Default::default(),
+            Default::default(),
+            &[],
             &config_from_scarb,
         );
 
@@ -225,21 +362,68 @@ mod tests {
                     fuzzer_runs: NonZeroU32::new(100).unwrap(),
                     fuzzer_seed: 32,
                     max_n_steps: Some(1_000_000),
+                    timeout: Some(Duration::from_secs(60)),
+                    max_gas: Some(1_000_000),
+                    tracked_resource: TrackedResource::SierraGas,
+                    block_context_version: BlockContextVersion::V0_13_2,
                     is_vm_trace_needed: true,
                     cache_dir: Default::default(),
+                    fork_data: None,
                     contracts_data: Default::default(),
                     environment_variables: config.test_runner_config.environment_variables.clone(),
+                    fuzz_corpus_dir: Default::default(),
+                    prune_fuzz_corpus: false,
+                    capture_output: true,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: true,
+                    json: false,
                     execution_data_to_save: ExecutionDataToSave {
                         trace: true,
                         profile: true,
                         coverage: true,
                     },
                     versioned_programs_dir: Default::default(),
+                    coverage_data_dir: Default::default(),
                 }),
             }
         );
     }
+
+    #[test]
+    fn env_override_parsing() {
+        assert_eq!(
+            parse_env_override("URL=http://a.b/?c=d").unwrap(),
+            ("URL".to_string(), "http://a.b/?c=d".to_string())
+        );
+        assert_eq!(
+            parse_env_override("EMPTY=").unwrap(),
+            ("EMPTY".to_string(), String::new())
+        );
+        assert!(parse_env_override("NO_VALUE").is_err());
+        assert!(parse_env_override("=1").is_err());
+    }
+
+    #[test]
+    fn environment_variables_precedence() {
+        let process_env = [
+            ("FROM_PROCESS".to_string(), "1".to_string()),
+            ("FROM_SCARB".to_string(), "1".to_string()),
+            ("FROM_CLI".to_string(), "1".to_string()),
+        ];
+        let scarb_env = HashMap::from([
+            ("FROM_SCARB".to_string(), "2".to_string()),
+            ("FROM_CLI".to_string(), "2".to_string()),
+        ]);
+
+        let environment_variables = environment_variables(
+            process_env,
+            &scarb_env,
+            &[("FROM_CLI".to_string(), "3".to_string())],
+        );
+
+        assert_eq!(environment_variables["FROM_PROCESS"], "1");
+        assert_eq!(environment_variables["FROM_SCARB"], "2");
+        assert_eq!(environment_variables["FROM_CLI"], "3");
+    }
 }
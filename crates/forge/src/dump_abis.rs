@@ -0,0 +1,192 @@
+use crate::scarb::{build_artifacts_with_scarb, should_compile_starknet_contract_target};
+use crate::{pretty_printing, ExitStatus};
+use anyhow::{bail, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Parser;
+use scarb_api::contracts_filter::ContractsFilter;
+use scarb_api::get_contracts_sierra_and_source_sierra_paths;
+use scarb_api::metadata::{workspace_metadata, Metadata, PackageMetadata};
+use scarb_ui::args::{FeaturesSpec, PackagesFilter};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+#[derive(Parser, Debug)]
+pub struct DumpAbisArgs {
+    /// Directory to write the ABIs to, created if it does not exist
+    #[arg(long, value_name = "DIR")]
+    out: Utf8PathBuf,
+
+    #[command(flatten)]
+    packages_filter: PackagesFilter,
+
+    /// Specify features to enable
+    #[command(flatten)]
+    features: FeaturesSpec,
+
+    /// Build contracts separately in the scarb starknet contract target
+    #[arg(long)]
+    no_optimization: bool,
+
+    /// Write ABIs only of the contracts which names match the glob pattern, e.g. `ERC20*`
+    #[arg(long, value_name = "GLOB")]
+    contracts_filter: Option<ContractsFilter>,
+}
+
+/// ABI of a contract written to the output directory
+#[derive(Debug, PartialEq, Eq)]
+pub struct WrittenAbi {
+    pub package: String,
+    pub contract_name: String,
+    pub path: Utf8PathBuf,
+}
+
+/// Builds the packages and writes the ABI of every contract available in their tests to `<name>.abi.json` files
+pub fn dump_abis(args: &DumpAbisArgs) -> Result<ExitStatus> {
+    let scarb_metadata = workspace_metadata()?;
+    let packages: Vec<PackageMetadata> = args
+        .packages_filter
+        .match_many(&scarb_metadata)
+        .context("Failed to find any packages matching the specified filter")?;
+    let filter = PackagesFilter::generate_for::<Metadata>(packages.iter());
+
+    build_artifacts_with_scarb(
+        filter,
+        args.features.clone(),
+        &scarb_metadata.app_version_info.version,
+        args.no_optimization,
+    )?;
+
+    // Contracts keyed by the package and then by the contract name, to write them in a stable order
+    let mut contracts = BTreeMap::new();
+    for package in &packages {
+        let package_contracts = get_contracts_sierra_and_source_sierra_paths(
+            &scarb_metadata,
+            &package.id,
+            None,
+            !should_compile_starknet_contract_target(
+                &scarb_metadata.app_version_info.version,
+                args.no_optimization,
+            ),
+        )?;
+        let package_contracts: BTreeMap<_, _> = package_contracts
+            .into_iter()
+            .map(|(name, (artifacts, _))| (name, artifacts))
+            .collect();
+        contracts.insert(package.name.clone(), package_contracts);
+    }
+
+    if let Some(contracts_filter) = &args.contracts_filter {
+        contracts_filter.ensure_matches_any(
+            contracts
+                .values()
+                .flat_map(|package_contracts| package_contracts.keys().map(String::as_str)),
+        )?;
+        for package_contracts in contracts.values_mut() {
+            contracts_filter.retain(package_contracts);
+        }
+    }
+
+    fs::create_dir_all(&args.out)
+        .with_context(|| format!("Failed to create directory {}", args.out))?;
+
+    let file_names = abi_file_names(
+        contracts
+            .iter()
+            .flat_map(|(package, contracts)| contracts.keys().map(move |name| (package, name))),
+    )?;
+
+    let mut written = vec![];
+    for (package, package_contracts) in &contracts {
+        for (contract_name, artifacts) in package_contracts {
+            let abi = artifacts.abi().with_context(|| {
+                format!("Failed to read ABI of contract = {contract_name} from package {package}")
+            })?;
+            let path = args.out.join(&file_names[&(package, contract_name)]);
+            fs::write(&path, serde_json::to_string_pretty(&abi)? + "\n")
+                .with_context(|| format!("Failed to write {path}"))?;
+
+            written.push(WrittenAbi {
+                package: package.clone(),
+                contract_name: contract_name.clone(),
+                path,
+            });
+        }
+    }
+
+    pretty_printing::print_written_abis(&written);
+
+    Ok(ExitStatus::Success)
+}
+
+/// Names of the ABI files by the package and the contract name. Contracts with the same name in
+/// multiple packages, e.g. built as external contracts of other packages, are qualified with the package name.
+/// Fails if a qualified name is the same as the name of another contract, so no ABI is overwritten
+fn abi_file_names<'a>(
+    contracts: impl Iterator<Item = (&'a String, &'a String)> + Clone,
+) -> Result<HashMap<(&'a String, &'a String), String>> {
+    let mut packages_by_name: HashMap<&String, usize> = HashMap::new();
+    for (_, contract_name) in contracts.clone() {
+        *packages_by_name.entry(contract_name).or_default() += 1;
+    }
+
+    let mut file_names = HashMap::new();
+    let mut contracts_by_file_name: HashMap<String, (&String, &String)> = HashMap::new();
+    for (package, contract_name) in contracts {
+        let file_name = if packages_by_name[contract_name] > 1 {
+            format!("{package}_{contract_name}.abi.json")
+        } else {
+            format!("{contract_name}.abi.json")
+        };
+
+        if let Some((other_package, other_contract_name)) =
+            contracts_by_file_name.insert(file_name.clone(), (package, contract_name))
+        {
+            bail!(
+                "ABIs of contract = {other_contract_name} from package {other_package} and contract = {contract_name} from package {package} would both be written to {file_name}, use `--contracts-filter` or `--package` to write only one of them"
+            );
+        }
+        file_names.insert((package, contract_name), file_name);
+    }
+
+    Ok(file_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colliding_names_are_qualified_with_package() {
+        let contracts: Vec<(String, String)> = [
+            ("addition", "AdditionContract"),
+            ("fibonacci", "AdditionContract"),
+            ("fibonacci", "FibonacciContract"),
+        ]
+        .iter()
+        .map(|(package, name)| ((*package).to_string(), (*name).to_string()))
+        .collect();
+
+        let file_names =
+            abi_file_names(contracts.iter().map(|(package, name)| (package, name))).unwrap();
+        let file_name = |index: usize| {
+            let (package, name) = &contracts[index];
+            file_names[&(package, name)].as_str()
+        };
+
+        assert_eq!(file_name(0), "addition_AdditionContract.abi.json");
+        assert_eq!(file_name(1), "fibonacci_AdditionContract.abi.json");
+        assert_eq!(file_name(2), "FibonacciContract.abi.json");
+    }
+
+    #[test]
+    fn qualified_name_colliding_with_other_contract_is_rejected() {
+        let contracts: Vec<(String, String)> = [("a", "C"), ("b", "C"), ("c", "a_C")]
+            .iter()
+            .map(|(package, name)| ((*package).to_string(), (*name).to_string()))
+            .collect();
+
+        let result = abi_file_names(contracts.iter().map(|(package, name)| (package, name)));
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,502 @@
+use anyhow::{bail, ensure, Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::ValueEnum;
+use forge_runner::forge_config::TrackedResource;
+use forge_runner::test_case_summary::AnyTestCaseSummary;
+use forge_runner::test_target_summary::TestTargetSummary;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io::ErrorKind;
+use std::str::FromStr;
+
+pub const GAS_SNAPSHOT_FILE: &str = ".snfoundry/gas_snapshot.json";
+pub const SIERRA_GAS_SNAPSHOT_FILE: &str = ".snfoundry/gas_snapshot_sierra_gas.json";
+
+const BASIS_POINTS_IN_PERCENT: u128 = 100;
+const BASIS_POINTS_IN_WHOLE: u128 = 100 * BASIS_POINTS_IN_PERCENT;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GasSnapshotMode {
+    /// Save gas used by the tests as the new baseline
+    Write,
+    /// Fail if gas used by any test increased above the baseline by more than the tolerance
+    Check,
+}
+
+/// Allowed increase of gas used by a test, in basis points (hundredths of a percent)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Tolerance(u128);
+
+impl FromStr for Tolerance {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let percentage = value.trim().strip_suffix('%').unwrap_or(value.trim());
+        let (integer, fraction) = percentage.split_once('.').unwrap_or((percentage, ""));
+
+        if integer.is_empty()
+            || fraction.len() > 2
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|char| char.is_ascii_digit())
+        {
+            bail!("Tolerance must be a percentage with at most two decimal places, e.g. 2% or 0.5%, got `{value}`");
+        }
+
+        let integer: u128 = integer
+            .parse()
+            .with_context(|| format!("Tolerance `{value}` is too large"))?;
+        let fraction: u128 = format!("{fraction:0<2}").parse()?;
+
+        integer
+            .checked_mul(BASIS_POINTS_IN_PERCENT)
+            .and_then(|basis_points| basis_points.checked_add(fraction))
+            .map(Self)
+            .with_context(|| format!("Tolerance `{value}` is too large"))
+    }
+}
+
+impl Display for Tolerance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_percentage(f, self.0)
+    }
+}
+
+/// Gas used by the tests, keyed by the test name. Fuzz tests are not included, as their gas depends on the arguments
+#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+pub struct GasSnapshot {
+    /// Resource the gas was computed from, missing in snapshots written by older versions
+    #[serde(default)]
+    pub tracked_resource: TrackedResource,
+    pub tests: BTreeMap<String, GasSnapshotEntry>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct GasSnapshotEntry {
+    pub gas: u128,
+    /// `None` for tests compiled without gas, missing in snapshots written by older versions
+    #[serde(default)]
+    pub sierra_gas_consumed: Option<u64>,
+    pub steps: usize,
+    pub builtins: BTreeMap<String, usize>,
+}
+
+impl GasSnapshot {
+    #[must_use]
+    pub fn new(tracked_resource: TrackedResource) -> Self {
+        Self {
+            tracked_resource,
+            tests: BTreeMap::new(),
+        }
+    }
+
+    /// Records gas used by the passed tests
+    pub fn record(&mut self, summaries: &[TestTargetSummary]) {
+        let reports = summaries
+            .iter()
+            .flat_map(|summary| &summary.test_case_summaries)
+            .filter_map(|test_case_summary| match test_case_summary {
+                AnyTestCaseSummary::Single(case) => {
+                    case.resource_report().map(|report| (case.name(), report))
+                }
+                AnyTestCaseSummary::Fuzzing(_) => None,
+            });
+
+        for (name, report) in reports {
+            self.tests.insert(
+                name.to_string(),
+                GasSnapshotEntry {
+                    gas: report.gas,
+                    sierra_gas_consumed: report.sierra_gas_consumed,
+                    steps: report.steps,
+                    builtins: report.builtins,
+                },
+            );
+        }
+    }
+
+    /// Keeps entries of `previous` for tests which were not run, e.g. because the tests were filtered
+    #[must_use]
+    pub fn merged_over(self, previous: GasSnapshot) -> Self {
+        let mut tests = previous.tests;
+        tests.extend(self.tests);
+
+        Self {
+            tracked_resource: self.tracked_resource,
+            tests,
+        }
+    }
+
+    #[must_use]
+    pub fn compare(&self, baseline: &GasSnapshot, tolerance: Tolerance) -> GasSnapshotComparison {
+        let mut comparison = GasSnapshotComparison::default();
+
+        for (name, entry) in &self.tests {
+            let Some(baseline_entry) = baseline.tests.get(name) else {
+                comparison.new_tests.push(name.clone());
+                continue;
+            };
+            let change = GasChange {
+                name: name.clone(),
+                baseline: baseline_entry.gas,
+                current: entry.gas,
+            };
+
+            // Saturating, so gas close to `u128::MAX` can't overflow the comparison
+            if change.current.saturating_mul(BASIS_POINTS_IN_WHOLE)
+                > change
+                    .baseline
+                    .saturating_mul(BASIS_POINTS_IN_WHOLE.saturating_add(tolerance.0))
+            {
+                comparison.regressions.push(change);
+            } else if change.current < change.baseline {
+                comparison.improvements.push(change);
+            }
+        }
+
+        comparison
+    }
+
+    /// Returns `None` if the file does not exist
+    pub fn load(path: &Utf8Path) -> Result<Option<Self>> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => Err(err)?,
+        };
+
+        let snapshot =
+            serde_json::from_str(&content).with_context(|| format!("Failed to parse {path}"))?;
+
+        Ok(Some(snapshot))
+    }
+
+    pub fn save(&self, path: &Utf8Path) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+
+        Ok(())
+    }
+}
+
+/// Loads the baseline, failing if it was not written yet or if its gas was computed from another resource
+pub fn load_baseline(path: &Utf8Path, tracked_resource: TrackedResource) -> Result<GasSnapshot> {
+    let baseline = GasSnapshot::load(path)?.with_context(|| {
+        format!("Gas snapshot {path} not found, create it with `snforge test --gas-snapshot write`")
+    })?;
+    ensure!(
+        baseline.tracked_resource == tracked_resource,
+        "Gas snapshot {path} was written with `--tracked-resource {}`, it cannot be compared with gas computed from {tracked_resource}",
+        baseline.tracked_resource
+    );
+
+    Ok(baseline)
+}
+
+/// Snapshots of gas computed from different resources are kept in separate files
+#[must_use]
+pub fn gas_snapshot_path(
+    workspace_root: &Utf8Path,
+    tracked_resource: TrackedResource,
+) -> Utf8PathBuf {
+    workspace_root.join(match tracked_resource {
+        TrackedResource::CairoSteps => GAS_SNAPSHOT_FILE,
+        TrackedResource::SierraGas => SIERRA_GAS_SNAPSHOT_FILE,
+    })
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct GasSnapshotComparison {
+    /// Tests which gas increased by more than the tolerance
+    pub regressions: Vec<GasChange>,
+    pub improvements: Vec<GasChange>,
+    /// Tests absent from the baseline
+    pub new_tests: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct GasChange {
+    pub name: String,
+    pub baseline: u128,
+    pub current: u128,
+}
+
+impl Display for GasChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} -> {} (", self.name, self.baseline, self.current)?;
+        if self.baseline == 0 {
+            write!(f, "+inf%")?;
+        } else if self.current >= self.baseline {
+            write!(f, "+")?;
+            write_percentage(
+                f,
+                (self.current - self.baseline).saturating_mul(BASIS_POINTS_IN_WHOLE)
+                    / self.baseline,
+            )?;
+        } else {
+            write!(f, "-")?;
+            write_percentage(
+                f,
+                (self.baseline - self.current).saturating_mul(BASIS_POINTS_IN_WHOLE)
+                    / self.baseline,
+            )?;
+        }
+        write!(f, ")")
+    }
+}
+
+fn write_percentage(f: &mut Formatter<'_>, basis_points: u128) -> std::fmt::Result {
+    write!(
+        f,
+        "{}.{:02}%",
+        basis_points / BASIS_POINTS_IN_PERCENT,
+        basis_points % BASIS_POINTS_IN_PERCENT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn snapshot(tests: &[(&str, u128)]) -> GasSnapshot {
+        GasSnapshot {
+            tracked_resource: TrackedResource::CairoSteps,
+            tests: tests
+                .iter()
+                .map(|(name, gas)| {
+                    (
+                        (*name).to_string(),
+                        GasSnapshotEntry {
+                            gas: *gas,
+                            sierra_gas_consumed: Some(1000),
+                            steps: 10,
+                            builtins: BTreeMap::from([("range_check".to_string(), 1)]),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn change(name: &str, baseline: u128, current: u128) -> GasChange {
+        GasChange {
+            name: name.to_string(),
+            baseline,
+            current,
+        }
+    }
+
+    #[test]
+    fn parse_tolerance() {
+        assert_eq!("2%".parse::<Tolerance>().unwrap(), Tolerance(200));
+        assert_eq!("2".parse::<Tolerance>().unwrap(), Tolerance(200));
+        assert_eq!("0.5%".parse::<Tolerance>().unwrap(), Tolerance(50));
+        assert_eq!("1.25%".parse::<Tolerance>().unwrap(), Tolerance(125));
+        assert_eq!(Tolerance(125).to_string(), "1.25%");
+    }
+
+    #[test]
+    fn parse_invalid_tolerance() {
+        for value in ["", "%", "-1%", "1.234%", "a%", ".5%"] {
+            assert!(value.parse::<Tolerance>().is_err(), "{value} was accepted");
+        }
+    }
+
+    #[test]
+    fn parse_too_large_tolerance() {
+        let value = format!("{}%", u128::MAX / 10);
+
+        assert!(value
+            .parse::<Tolerance>()
+            .unwrap_err()
+            .to_string()
+            .contains("is too large"));
+    }
+
+    #[test]
+    fn compare_huge_gas_does_not_overflow() {
+        let comparison = snapshot(&[("pkg::test", u128::MAX)]).compare(
+            &snapshot(&[("pkg::test", u128::MAX / 2)]),
+            Tolerance(u128::MAX),
+        );
+
+        assert!(comparison.regressions.is_empty());
+        assert_eq!(
+            change("pkg::test", 1, u128::MAX).to_string(),
+            format!(
+                "pkg::test: 1 -> {} (+{}.{:02}%)",
+                u128::MAX,
+                u128::MAX / 100,
+                u128::MAX % 100
+            )
+        );
+    }
+
+    #[test]
+    fn merged_over_previous_snapshot() {
+        let previous = snapshot(&[("pkg::not_run", 1), ("pkg::run", 2)]);
+        let current = snapshot(&[("pkg::run", 3), ("pkg::new", 4)]);
+
+        assert_eq!(
+            current.merged_over(previous),
+            snapshot(&[("pkg::not_run", 1), ("pkg::run", 3), ("pkg::new", 4)])
+        );
+    }
+
+    #[test]
+    fn compare_with_baseline() {
+        let baseline = snapshot(&[
+            ("pkg::regressed", 100),
+            ("pkg::within_tolerance", 100),
+            ("pkg::improved", 100),
+            ("pkg::unchanged", 100),
+            ("pkg::removed", 100),
+        ]);
+        let current = snapshot(&[
+            ("pkg::regressed", 103),
+            ("pkg::within_tolerance", 102),
+            ("pkg::improved", 90),
+            ("pkg::unchanged", 100),
+            ("pkg::new", 1),
+        ]);
+
+        let comparison = current.compare(&baseline, "2%".parse().unwrap());
+
+        assert_eq!(
+            comparison,
+            GasSnapshotComparison {
+                regressions: vec![change("pkg::regressed", 100, 103)],
+                improvements: vec![change("pkg::improved", 100, 90)],
+                new_tests: vec!["pkg::new".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn any_increase_is_regression_without_tolerance() {
+        let comparison = snapshot(&[("pkg::test", 101)])
+            .compare(&snapshot(&[("pkg::test", 100)]), Tolerance::default());
+
+        assert_eq!(comparison.regressions, vec![change("pkg::test", 100, 101)]);
+    }
+
+    #[test]
+    fn display_change() {
+        assert_eq!(
+            change("pkg::test", 300, 400).to_string(),
+            "pkg::test: 300 -> 400 (+33.33%)"
+        );
+        assert_eq!(
+            change("pkg::test", 400, 300).to_string(),
+            "pkg::test: 400 -> 300 (-25.00%)"
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let temp = TempDir::new().unwrap();
+        let path = gas_snapshot_path(
+            Utf8Path::from_path(temp.path()).unwrap(),
+            TrackedResource::CairoSteps,
+        );
+        let snapshot = snapshot(&[("pkg::b", 2), ("pkg::a", 1)]);
+
+        snapshot.save(&path).unwrap();
+
+        assert_eq!(GasSnapshot::load(&path).unwrap(), Some(snapshot));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            indoc::indoc! {r#"
+                {
+                  "tracked_resource": "cairo_steps",
+                  "tests": {
+                    "pkg::a": {
+                      "gas": 1,
+                      "sierra_gas_consumed": 1000,
+                      "steps": 10,
+                      "builtins": {
+                        "range_check": 1
+                      }
+                    },
+                    "pkg::b": {
+                      "gas": 2,
+                      "sierra_gas_consumed": 1000,
+                      "steps": 10,
+                      "builtins": {
+                        "range_check": 1
+                      }
+                    }
+                  }
+                }
+            "#}
+        );
+    }
+
+    #[test]
+    fn load_without_sierra_gas() {
+        let temp = TempDir::new().unwrap();
+        let path = gas_snapshot_path(
+            Utf8Path::from_path(temp.path()).unwrap(),
+            TrackedResource::CairoSteps,
+        );
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(
+            &path,
+            r#"{"tests": {"pkg::a": {"gas": 1, "steps": 10, "builtins": {}}}}"#,
+        )
+        .unwrap();
+
+        let snapshot = GasSnapshot::load(&path).unwrap().unwrap();
+
+        assert_eq!(snapshot.tracked_resource, TrackedResource::CairoSteps);
+        assert_eq!(snapshot.tests["pkg::a"].sierra_gas_consumed, None);
+    }
+
+    #[test]
+    fn missing_baseline() {
+        let temp = TempDir::new().unwrap();
+        let path = gas_snapshot_path(
+            Utf8Path::from_path(temp.path()).unwrap(),
+            TrackedResource::CairoSteps,
+        );
+
+        let err = load_baseline(&path, TrackedResource::CairoSteps).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("create it with `snforge test --gas-snapshot write`"));
+    }
+
+    #[test]
+    fn snapshots_of_tracked_resources_are_separate() {
+        let root = Utf8Path::new("/ws");
+
+        assert_eq!(
+            gas_snapshot_path(root, TrackedResource::CairoSteps),
+            "/ws/.snfoundry/gas_snapshot.json"
+        );
+        assert_eq!(
+            gas_snapshot_path(root, TrackedResource::SierraGas),
+            "/ws/.snfoundry/gas_snapshot_sierra_gas.json"
+        );
+    }
+
+    #[test]
+    fn baseline_of_other_tracked_resource() {
+        let temp = TempDir::new().unwrap();
+        let path = Utf8Path::from_path(temp.path())
+            .unwrap()
+            .join("gas_snapshot.json");
+        snapshot(&[("pkg::a", 1)]).save(&path).unwrap();
+
+        let err = load_baseline(&path, TrackedResource::SierraGas).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("was written with `--tracked-resource cairo-steps`"));
+    }
+}
@@ -1,6 +1,11 @@
 use crate::scarb::config::SCARB_MANIFEST_TEMPLATE_CONTENT;
+use crate::shared_cache::LAST_RUN_FILE;
+use crate::target_hashes::TARGET_HASHES_FILE;
 use crate::CAIRO_EDITION;
-use anyhow::{anyhow, bail, Context, Ok, Result};
+use anyhow::{anyhow, bail, ensure, Context, Ok, Result};
+use forge_runner::build_trace_data::test_sierra_program_path::VERSIONED_PROGRAMS_DIR;
+use forge_runner::build_trace_data::TRACE_DIR;
+use forge_runner::{CACHE_DIR, FUZZ_CORPUS_DIR};
 use include_dir::{include_dir, Dir};
 use indoc::formatdoc;
 use scarb_api::ScarbCommand;
@@ -16,6 +21,53 @@ static TEMPLATE: Dir = include_dir!("starknet_forge_template");
 const DEFAULT_ASSERT_MACROS: Version = Version::new(0, 1, 0);
 const MINIMAL_SCARB_FOR_CORRESPONDING_ASSERT_MACROS: Version = Version::new(2, 8, 0);
 
+/// Files created by `snforge init`, which must not exist beforehand
+const GENERATED_FILES: [&str; 4] = [
+    "Scarb.toml",
+    "snfoundry.toml",
+    "src/lib.cairo",
+    "tests/test_contract.cairo",
+];
+
+const CAIRO_KEYWORDS: [&str; 33] = [
+    "as",
+    "break",
+    "const",
+    "continue",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "implicits",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "mut",
+    "nopanic",
+    "of",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "trait",
+    "true",
+    "type",
+    "use",
+    "while",
+];
+
+/// Dependencies of the created project, a package with the same name would shadow them
+const RESERVED_PACKAGE_NAMES: [&str; 4] = ["core", "starknet", "snforge_std", "assert_macros"];
+
 fn create_snfoundry_manifest(path: &PathBuf) -> Result<()> {
     fs::write(
         path,
@@ -139,31 +191,140 @@ fn add_assert_macros(document: &mut DocumentMut, scarb: &Version) -> Result<()>
 
 fn extend_gitignore(path: &Path) -> Result<()> {
     let mut file = OpenOptions::new()
+        .create(true)
         .append(true)
         .open(path.join(".gitignore"))?;
-    writeln!(file, ".snfoundry_cache/")?;
+
+    for ignored in [
+        format!("{CACHE_DIR}/"),
+        format!("{VERSIONED_PROGRAMS_DIR}/"),
+        format!("{TRACE_DIR}/"),
+        format!("{FUZZ_CORPUS_DIR}/"),
+        TARGET_HASHES_FILE.to_string(),
+        LAST_RUN_FILE.to_string(),
+    ] {
+        writeln!(file, "{ignored}")?;
+    }
 
     Ok(())
 }
 
+/// Package names must be valid Cairo identifiers, as they are used as the names of the modules
+fn validate_package_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let starts_correctly = chars
+        .next()
+        .is_some_and(|char| char.is_ascii_lowercase() || char == '_');
+
+    ensure!(
+        starts_correctly
+            && chars.all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_'),
+        "Invalid package name `{name}`, it must start with a lowercase letter or `_` \
+        and contain only lowercase letters, digits and `_`"
+    );
+    ensure!(
+        !CAIRO_KEYWORDS.contains(&name),
+        "Invalid package name `{name}`, it is a Cairo keyword"
+    );
+    ensure!(
+        !RESERVED_PACKAGE_NAMES.contains(&name),
+        "Invalid package name `{name}`, it is the name of a dependency of the project"
+    );
+
+    Ok(())
+}
+
+fn package_name(name: Option<&str>, project_path: &Path) -> Result<String> {
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => project_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| {
+                format!(
+                    "Failed to get the package name from {}, pass it with `--name`",
+                    project_path.display()
+                )
+            })?
+            .to_string(),
+    };
+    validate_package_name(&name)?;
+
+    Ok(name)
+}
+
+/// Creates a project in a new directory at `path`
+pub fn new(path: &Path, name: Option<&str>) -> Result<()> {
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
+    let project_path = current_dir.join(path);
+
+    ensure!(
+        !project_path.exists(),
+        "Destination {} already exists, run `snforge init` inside it to create a project in an existing directory",
+        project_path.display()
+    );
+
+    let package_name = package_name(name, &project_path)?;
+    create_project(&project_path, &package_name)
+}
+
+/// Creates a project in the current directory, which must not contain any of the generated files
+pub fn init(name: Option<&str>) -> Result<()> {
+    let project_path = env::current_dir().context("Failed to get current directory")?;
+    let package_name = package_name(name, &project_path)?;
+
+    ensure_no_generated_files(&project_path)?;
+    create_project(&project_path, &package_name)
+}
+
+/// Creates a project in the directory `<project_name>`, which may already exist but must not contain
+/// any of the generated files, as done by `scarb new` with `snforge` chosen as the test runner
 pub fn run(project_name: &str) -> Result<()> {
-    let current_dir = std::env::current_dir().context("Failed to get current directory")?;
+    let current_dir = env::current_dir().context("Failed to get current directory")?;
     let project_path = current_dir.join(project_name);
+
+    validate_package_name(project_name)?;
+    ensure_no_generated_files(&project_path)?;
+    create_project(&project_path, project_name)
+}
+
+fn ensure_no_generated_files(project_path: &Path) -> Result<()> {
+    let existing_files: Vec<_> = GENERATED_FILES
+        .iter()
+        .filter(|file| project_path.join(file).exists())
+        .copied()
+        .collect();
+    ensure!(
+        existing_files.is_empty(),
+        "Failed to create a project in {}, files would be overwritten: {}",
+        project_path.display(),
+        existing_files.join(", ")
+    );
+
+    Ok(())
+}
+
+fn create_project(project_path: &Path, package_name: &str) -> Result<()> {
     let scarb_manifest_path = project_path.join("Scarb.toml");
     let snfoundry_manifest_path = project_path.join("snfoundry.toml");
 
-    // if there is no Scarb.toml run `scarb new`
+    // if there is no Scarb.toml run `scarb new`, or `scarb init` in an existing directory
     if !scarb_manifest_path.is_file() {
-        ScarbCommand::new_with_stdio()
-            .current_dir(current_dir)
-            .arg("new")
-            .arg(&project_path)
+        let mut scarb_command = ScarbCommand::new_with_stdio();
+        if project_path.exists() {
+            scarb_command.current_dir(project_path).arg("init");
+        } else {
+            scarb_command.arg("new").arg(project_path);
+        }
+        scarb_command
+            .arg("--name")
+            .arg(package_name)
             .env("SCARB_INIT_TEST_RUNNER", "cairo-test")
             .run()
             .context("Failed to initialize a new project")?;
 
         ScarbCommand::new_with_stdio()
-            .current_dir(&project_path)
+            .current_dir(project_path)
             .manifest_path(scarb_manifest_path.clone())
             .offline()
             .arg("remove")
@@ -184,7 +345,7 @@ pub fn run(project_name: &str) -> Result<()> {
 
     if env::var("DEV_DISABLE_SNFORGE_STD_DEPENDENCY").is_err() {
         ScarbCommand::new_with_stdio()
-            .current_dir(&project_path)
+            .current_dir(project_path)
             .manifest_path(scarb_manifest_path.clone())
             .offline()
             .arg("add")
@@ -199,7 +360,7 @@ pub fn run(project_name: &str) -> Result<()> {
     }
 
     ScarbCommand::new_with_stdio()
-        .current_dir(&project_path)
+        .current_dir(project_path)
         .manifest_path(scarb_manifest_path.clone())
         .offline()
         .arg("add")
@@ -207,10 +368,10 @@ pub fn run(project_name: &str) -> Result<()> {
         .run()
         .context("Failed to add starknet")?;
 
-    update_config(&project_path.join("Scarb.toml"), &cairo_version)?;
-    extend_gitignore(&project_path)?;
-    overwrite_files_from_scarb_template("src", &project_path, project_name)?;
-    overwrite_files_from_scarb_template("tests", &project_path, project_name)?;
+    update_config(&scarb_manifest_path, &cairo_version)?;
+    extend_gitignore(project_path)?;
+    overwrite_files_from_scarb_template("src", project_path, package_name)?;
+    overwrite_files_from_scarb_template("tests", project_path, package_name)?;
 
     // Fetch to create lock file.
     ScarbCommand::new_with_stdio()
@@ -221,3 +382,44 @@ pub fn run(project_name: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_package_names() {
+        for name in ["hello_starknet", "_private", "contract2"] {
+            assert!(validate_package_name(name).is_ok(), "{name}");
+        }
+    }
+
+    #[test]
+    fn invalid_package_names() {
+        for name in [
+            "",
+            "2contract",
+            "hello-starknet",
+            "HelloStarknet",
+            "hello starknet",
+            "mod",
+            "starknet",
+            "snforge_std",
+        ] {
+            assert!(validate_package_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn package_name_from_directory() {
+        assert_eq!(
+            package_name(None, Path::new("/projects/hello_starknet")).unwrap(),
+            "hello_starknet"
+        );
+        assert_eq!(
+            package_name(Some("other"), Path::new("/projects/hello_starknet")).unwrap(),
+            "other"
+        );
+        assert!(package_name(None, Path::new("/projects/hello-starknet")).is_err());
+    }
+}
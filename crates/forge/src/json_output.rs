@@ -0,0 +1,430 @@
+use crate::test_order::TestOrder;
+use anyhow::Result;
+use forge_runner::package_tests::TestKind;
+use forge_runner::resource_report::{total_resource_report, ResourceReport};
+use forge_runner::test_case_summary::{
+    AnyTestCaseSummary, FuzzingStatistics, GasStatistics, TestCaseSummary,
+};
+use forge_runner::test_target_summary::TestTargetSummary;
+use serde::Serialize;
+use shared::print::{drain_diagnostics, Diagnostic};
+use std::time::Duration;
+
+/// Version of the `--json` output, bumped on every breaking change of the events
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Event written to stdout as a single JSON line when running with `--json`
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Event<'a> {
+    SuiteStarted {
+        package: &'a str,
+        test_count: usize,
+        /// `None` when filtering with `--exact`
+        filtered_out: Option<usize>,
+        /// Order in which the tests are started, which is also the order of `test_started` events
+        test_order: TestOrder,
+        /// `None` unless the tests are shuffled
+        shuffle_seed: Option<u64>,
+    },
+    TestStarted {
+        name: &'a str,
+        kind: TestKind,
+    },
+    TestFinished(TestFinished<'a>),
+    /// Emitted once a test fails with `--exit-first`, tests which have not started yet are skipped
+    RunCancelled {
+        /// Name of the failed test
+        name: &'a str,
+    },
+    SuiteFinished {
+        package: &'a str,
+        passed: usize,
+        failed: usize,
+        skipped: usize,
+        ignored: usize,
+        /// `None` when filtering with `--exact`
+        filtered_out: Option<usize>,
+        duration_ms: u64,
+        /// Sum of resources used by the passed tests, without fuzz tests
+        resources: ResourceReport,
+    },
+    /// Warning or error reported during the run, identical diagnostics are emitted once
+    Diagnostic(&'a Diagnostic),
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestFinished<'a> {
+    name: &'a str,
+    /// Whether the test is in `src/` or `tests/`
+    kind: TestKind,
+    status: TestStatus,
+    duration_ms: u64,
+    /// Gas used by a passed test, statistics of all runs for fuzz tests
+    gas: Option<Gas>,
+    /// Resources used by a passed test, not reported for fuzz tests
+    resources: Option<ResourceReport>,
+    /// Failure data of a failed test
+    message: Option<&'a str>,
+    fuzzer: Option<Fuzzer>,
+    ignore_reason: Option<&'a str>,
+    /// Output printed by a failed test
+    captured_output: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TestStatus {
+    Passed,
+    Failed,
+    /// A fixture requested by the test panicked
+    FixtureFailed,
+    TimedOut,
+    Ignored,
+    /// Not run because of `--exit-first`
+    Skipped,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Gas {
+    Single(u128),
+    Fuzzing {
+        min: u128,
+        max: u128,
+        mean: f64,
+        std_deviation: f64,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct Fuzzer {
+    runs: usize,
+    seed: u64,
+}
+
+#[derive(Serialize)]
+struct EventLine<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    event: Event<'a>,
+}
+
+impl<'a> Event<'a> {
+    #[must_use]
+    pub fn suite_finished(
+        package: &'a str,
+        summaries: &[TestTargetSummary],
+        filtered_out: Option<usize>,
+        duration: Duration,
+    ) -> Self {
+        let count = |counter: fn(&TestTargetSummary) -> usize| -> usize {
+            summaries.iter().map(counter).sum()
+        };
+
+        Event::SuiteFinished {
+            package,
+            passed: count(TestTargetSummary::count_passed),
+            failed: count(TestTargetSummary::count_failed),
+            skipped: count(TestTargetSummary::count_skipped),
+            ignored: count(TestTargetSummary::count_ignored),
+            filtered_out,
+            duration_ms: duration_ms(duration),
+            resources: total_resource_report(summaries),
+        }
+    }
+
+    /// Writes the event as a single line to stdout
+    pub fn emit(self) -> Result<()> {
+        let line = EventLine {
+            schema_version: SCHEMA_VERSION,
+            event: self,
+        };
+        println!("{}", serde_json::to_string(&line)?);
+
+        Ok(())
+    }
+}
+
+/// Emits the diagnostics reported since the last call as events
+pub fn emit_diagnostics() -> Result<()> {
+    for diagnostic in drain_diagnostics() {
+        Event::Diagnostic(&diagnostic).emit()?;
+    }
+
+    Ok(())
+}
+
+impl<'a> TestFinished<'a> {
+    #[must_use]
+    pub fn new(result: &'a AnyTestCaseSummary, kind: TestKind, duration: Duration) -> Self {
+        let status = if result.is_passed() {
+            TestStatus::Passed
+        } else if result.is_timed_out() {
+            TestStatus::TimedOut
+        } else if result.is_fixture_failed() {
+            TestStatus::FixtureFailed
+        } else if result.is_failed() {
+            TestStatus::Failed
+        } else if result.is_skipped() {
+            TestStatus::Skipped
+        } else {
+            TestStatus::Ignored
+        };
+
+        let (gas, resources) = match result {
+            AnyTestCaseSummary::Single(case @ TestCaseSummary::Passed { gas_info, .. }) => {
+                (Some(Gas::Single(*gas_info)), case.resource_report())
+            }
+            AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+                gas_info:
+                    GasStatistics {
+                        min,
+                        max,
+                        mean,
+                        std_deviation,
+                    },
+                ..
+            }) => (
+                Some(Gas::Fuzzing {
+                    min: *min,
+                    max: *max,
+                    mean: *mean,
+                    std_deviation: *std_deviation,
+                }),
+                None,
+            ),
+            _ => (None, None),
+        };
+
+        let fuzzer = match result {
+            AnyTestCaseSummary::Fuzzing(
+                TestCaseSummary::Passed {
+                    test_statistics: FuzzingStatistics { runs, seed, .. },
+                    ..
+                }
+                | TestCaseSummary::Failed {
+                    test_statistics: FuzzingStatistics { runs, seed, .. },
+                    ..
+                }
+                | TestCaseSummary::FixtureFailed {
+                    test_statistics: FuzzingStatistics { runs, seed, .. },
+                    ..
+                }
+                | TestCaseSummary::TimedOut {
+                    test_statistics: FuzzingStatistics { runs, seed, .. },
+                    ..
+                },
+            ) => Some(Fuzzer {
+                runs: *runs,
+                seed: *seed,
+            }),
+            _ => None,
+        };
+
+        Self {
+            name: result.name(),
+            kind,
+            status,
+            duration_ms: duration_ms(duration),
+            gas,
+            resources,
+            message: result.msg().filter(|_| result.is_failed()),
+            fuzzer,
+            ignore_reason: result.ignore_reason(),
+            captured_output: result.captured_output(),
+        }
+    }
+}
+
+fn duration_ms(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use shared::print::DiagnosticCode;
+
+    fn to_json(event: Event) -> serde_json::Value {
+        serde_json::to_value(EventLine {
+            schema_version: SCHEMA_VERSION,
+            event,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn ignored_test_finished() {
+        let result = AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
+            name: "tests::ignored".to_string(),
+            reason: Some("flaky".to_string()),
+        });
+        let event = TestFinished::new(&result, TestKind::Unit, Duration::from_millis(3));
+
+        assert_eq!(
+            to_json(Event::TestFinished(event)),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "test_finished",
+                "name": "tests::ignored",
+                "kind": "unit",
+                "status": "ignored",
+                "duration_ms": 3,
+                "gas": null,
+                "resources": null,
+                "message": null,
+                "fuzzer": null,
+                "ignore_reason": "flaky",
+                "captured_output": null,
+            })
+        );
+    }
+
+    #[test]
+    fn failed_fuzz_test_finished() {
+        let result = AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+            name: "tests::fuzzed".to_string(),
+            msg: Some("\n    0x0 ('')\n".to_string()),
+            arguments: vec![],
+            test_statistics: FuzzingStatistics {
+                runs: 7,
+                seed: 1234,
+                minimized_arguments: None,
+            },
+            captured_output: None,
+        });
+        let event = TestFinished::new(&result, TestKind::Integration, Duration::from_secs(1));
+
+        assert_eq!(
+            to_json(Event::TestFinished(event)),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "test_finished",
+                "name": "tests::fuzzed",
+                "kind": "integration",
+                "status": "failed",
+                "duration_ms": 1000,
+                "gas": null,
+                "resources": null,
+                "message": "\n    0x0 ('')\n",
+                "fuzzer": { "runs": 7, "seed": 1234 },
+                "ignore_reason": null,
+                "captured_output": null,
+            })
+        );
+    }
+
+    #[test]
+    fn fixture_failed_test_finished() {
+        let result = AnyTestCaseSummary::Single(TestCaseSummary::FixtureFailed {
+            name: "tests::with_fixture".to_string(),
+            fixture: "setup".to_string(),
+            msg: Some("\n    Fixture `setup` failed, test body was not executed\n".to_string()),
+            arguments: vec![],
+            test_statistics: (),
+            captured_output: None,
+        });
+        let event = TestFinished::new(&result, TestKind::Unit, Duration::from_millis(5));
+
+        assert_eq!(
+            to_json(Event::TestFinished(event)),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "test_finished",
+                "name": "tests::with_fixture",
+                "kind": "unit",
+                "status": "fixture_failed",
+                "duration_ms": 5,
+                "gas": null,
+                "resources": null,
+                "message": "\n    Fixture `setup` failed, test body was not executed\n",
+                "fuzzer": null,
+                "ignore_reason": null,
+                "captured_output": null,
+            })
+        );
+    }
+
+    #[test]
+    fn skipped_test_finished() {
+        let result = AnyTestCaseSummary::Single(TestCaseSummary::Skipped {
+            name: "tests::skipped".to_string(),
+        });
+        let event = TestFinished::new(&result, TestKind::Unit, Duration::ZERO);
+
+        assert_eq!(
+            to_json(Event::TestFinished(event)),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "test_finished",
+                "name": "tests::skipped",
+                "kind": "unit",
+                "status": "skipped",
+                "duration_ms": 0,
+                "gas": null,
+                "resources": null,
+                "message": null,
+                "fuzzer": null,
+                "ignore_reason": null,
+                "captured_output": null,
+            })
+        );
+    }
+
+    #[test]
+    fn run_cancelled() {
+        assert_eq!(
+            to_json(Event::RunCancelled {
+                name: "tests::failing"
+            }),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "run_cancelled",
+                "name": "tests::failing",
+            })
+        );
+    }
+
+    #[test]
+    fn suite_started() {
+        assert_eq!(
+            to_json(Event::SuiteStarted {
+                package: "package",
+                test_count: 2,
+                filtered_out: None,
+                test_order: TestOrder::Shuffle,
+                shuffle_seed: Some(42),
+            }),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "suite_started",
+                "package": "package",
+                "test_count": 2,
+                "filtered_out": null,
+                "test_order": "shuffle",
+                "shuffle_seed": 42,
+            })
+        );
+    }
+
+    #[test]
+    fn diagnostic() {
+        let diagnostic = Diagnostic::warning(
+            DiagnosticCode::MissingStarknetArtifacts,
+            "Starknet artifacts file not found",
+        );
+
+        assert_eq!(
+            to_json(Event::Diagnostic(&diagnostic)),
+            json!({
+                "schema_version": SCHEMA_VERSION,
+                "type": "diagnostic",
+                "severity": "warning",
+                "code": "W0001",
+                "message": "Starknet artifacts file not found",
+            })
+        );
+    }
+}
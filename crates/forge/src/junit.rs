@@ -0,0 +1,423 @@
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use forge_runner::test_case_summary::{AnyTestCaseSummary, FuzzingStatistics, TestCaseSummary};
+use forge_runner::test_target_summary::TestTargetSummary;
+use std::fmt::Write;
+use std::fs;
+use std::time::Duration;
+
+/// JUnit XML report of the run written with `--junit-path`, with one test suite per test target
+#[derive(Debug, Default)]
+pub struct JunitReport {
+    suites: Vec<TestSuite>,
+}
+
+#[derive(Debug)]
+struct TestSuite {
+    name: String,
+    package: String,
+    duration: Duration,
+    cases: Vec<TestCase>,
+}
+
+#[derive(Debug)]
+struct TestCase {
+    name: String,
+    classname: String,
+    duration: Duration,
+    outcome: Outcome,
+    fuzzer: Option<(usize, u64)>,
+}
+
+#[derive(Debug)]
+enum Outcome {
+    Passed,
+    Failed {
+        kind: &'static str,
+        message: String,
+        text: Option<String>,
+    },
+    Skipped {
+        message: Option<String>,
+    },
+}
+
+impl JunitReport {
+    /// Adds results of tests from a single test target.
+    /// `not_run` are names of tests from the target which were not run, with the reasons why.
+    pub fn add_test_target(
+        &mut self,
+        package: &str,
+        summary: &TestTargetSummary,
+        not_run: Vec<(String, String)>,
+        duration: Duration,
+    ) {
+        let mut cases: Vec<_> = summary
+            .test_case_summaries
+            .iter()
+            .map(|result| {
+                let duration = summary
+                    .test_case_durations
+                    .get(result.name())
+                    .copied()
+                    .unwrap_or_default();
+
+                TestCase::new(result.name(), duration, outcome(result), fuzzer(result))
+            })
+            .chain(not_run.into_iter().map(|(name, reason)| {
+                TestCase::new(
+                    &name,
+                    Duration::ZERO,
+                    Outcome::Skipped {
+                        message: Some(reason),
+                    },
+                    None,
+                )
+            }))
+            .collect();
+
+        let Some(name) = cases
+            .first()
+            .map(|case| crate_name(&case.classname).to_string())
+        else {
+            return;
+        };
+        // Tests are run concurrently, so the order of the results is not deterministic
+        cases.sort_by(|a, b| (&a.classname, &a.name).cmp(&(&b.classname, &b.name)));
+
+        self.suites.push(TestSuite {
+            name,
+            package: package.to_string(),
+            duration,
+            cases,
+        });
+    }
+
+    /// Writes the report to the file, creating its parent directories if needed
+    pub fn write(&self, path: &Utf8Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {parent}"))?;
+        }
+
+        fs::write(path, self.to_xml())
+            .with_context(|| format!("Failed to write JUnit report to {path}"))
+    }
+
+    fn to_xml(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let duration = self.suites.iter().map(|suite| suite.duration).sum();
+
+        writeln!(
+            xml,
+            "<testsuites {}>",
+            counts_attributes(self.suites.iter().flat_map(|suite| &suite.cases), duration)
+        )
+        .unwrap();
+        for suite in &self.suites {
+            suite.write_xml(&mut xml);
+        }
+        xml.push_str("</testsuites>\n");
+
+        xml
+    }
+}
+
+impl TestSuite {
+    fn write_xml(&self, xml: &mut String) {
+        writeln!(
+            xml,
+            "  <testsuite name=\"{}\" package=\"{}\" {}>",
+            escape(&self.name),
+            escape(&self.package),
+            counts_attributes(&self.cases, self.duration)
+        )
+        .unwrap();
+        for case in &self.cases {
+            case.write_xml(xml);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+}
+
+impl TestCase {
+    fn new(
+        full_name: &str,
+        duration: Duration,
+        outcome: Outcome,
+        fuzzer: Option<(usize, u64)>,
+    ) -> Self {
+        let (classname, name) = full_name.rsplit_once("::").unwrap_or(("", full_name));
+
+        Self {
+            name: name.to_string(),
+            classname: classname.to_string(),
+            duration,
+            outcome,
+            fuzzer,
+        }
+    }
+
+    fn write_xml(&self, xml: &mut String) {
+        write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{}\" time=\"{}\"",
+            escape(&self.name),
+            escape(&self.classname),
+            seconds(self.duration)
+        )
+        .unwrap();
+
+        if self.fuzzer.is_none() && matches!(self.outcome, Outcome::Passed) {
+            xml.push_str("/>\n");
+            return;
+        }
+        xml.push_str(">\n");
+
+        if let Some((runs, seed)) = self.fuzzer {
+            xml.push_str("      <properties>\n");
+            writeln!(
+                xml,
+                "        <property name=\"fuzzer_runs\" value=\"{runs}\"/>"
+            )
+            .unwrap();
+            writeln!(
+                xml,
+                "        <property name=\"fuzzer_seed\" value=\"{seed}\"/>"
+            )
+            .unwrap();
+            xml.push_str("      </properties>\n");
+        }
+
+        match &self.outcome {
+            Outcome::Passed => {}
+            Outcome::Failed {
+                kind,
+                message,
+                text,
+            } => {
+                write!(
+                    xml,
+                    "      <failure message=\"{}\" type=\"{kind}\"",
+                    escape(message)
+                )
+                .unwrap();
+                match text {
+                    Some(text) => writeln!(xml, ">{}</failure>", escape(text)).unwrap(),
+                    None => xml.push_str("/>\n"),
+                }
+            }
+            Outcome::Skipped { message: None } => xml.push_str("      <skipped/>\n"),
+            Outcome::Skipped {
+                message: Some(message),
+            } => writeln!(xml, "      <skipped message=\"{}\"/>", escape(message)).unwrap(),
+        }
+
+        xml.push_str("    </testcase>\n");
+    }
+}
+
+fn outcome(result: &AnyTestCaseSummary) -> Outcome {
+    let elapsed = match result {
+        AnyTestCaseSummary::Single(TestCaseSummary::TimedOut { elapsed, .. })
+        | AnyTestCaseSummary::Fuzzing(TestCaseSummary::TimedOut { elapsed, .. }) => Some(elapsed),
+        _ => None,
+    };
+
+    if let Some(elapsed) = elapsed {
+        Outcome::Failed {
+            kind: "timeout",
+            message: format!("Test timed out after {:.2}s", elapsed.as_secs_f64()),
+            text: None,
+        }
+    } else if result.is_failed() {
+        let text = result.msg().map(str::trim).filter(|msg| !msg.is_empty());
+        // The first line holds the panic data, the rest is a backtrace or details of the failure
+        let message = text
+            .and_then(|text| text.lines().next())
+            .map_or_else(|| "Test failed".to_string(), |line| line.trim().to_string());
+
+        let kind = if result.is_fixture_failed() {
+            "fixture"
+        } else {
+            "panic"
+        };
+
+        Outcome::Failed {
+            kind,
+            message,
+            text: text.map(str::to_string),
+        }
+    } else if result.is_ignored() {
+        Outcome::Skipped {
+            message: result.ignore_reason().map(str::to_string),
+        }
+    } else if result.is_skipped() {
+        Outcome::Skipped {
+            message: Some("Cancelled after a failure with --exit-first".to_string()),
+        }
+    } else {
+        Outcome::Passed
+    }
+}
+
+fn fuzzer(result: &AnyTestCaseSummary) -> Option<(usize, u64)> {
+    match result {
+        AnyTestCaseSummary::Fuzzing(
+            TestCaseSummary::Passed {
+                test_statistics: FuzzingStatistics { runs, seed, .. },
+                ..
+            }
+            | TestCaseSummary::Failed {
+                test_statistics: FuzzingStatistics { runs, seed, .. },
+                ..
+            }
+            | TestCaseSummary::FixtureFailed {
+                test_statistics: FuzzingStatistics { runs, seed, .. },
+                ..
+            }
+            | TestCaseSummary::TimedOut {
+                test_statistics: FuzzingStatistics { runs, seed, .. },
+                ..
+            },
+        ) => Some((*runs, *seed)),
+        _ => None,
+    }
+}
+
+fn counts_attributes<'a>(
+    cases: impl IntoIterator<Item = &'a TestCase>,
+    duration: Duration,
+) -> String {
+    let (mut tests, mut failures, mut skipped) = (0, 0, 0);
+    for case in cases {
+        tests += 1;
+        match case.outcome {
+            Outcome::Passed => {}
+            Outcome::Failed { .. } => failures += 1,
+            Outcome::Skipped { .. } => skipped += 1,
+        }
+    }
+
+    format!(
+        "tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"{skipped}\" time=\"{}\"",
+        seconds(duration)
+    )
+}
+
+fn crate_name(classname: &str) -> &str {
+    classname.split("::").next().unwrap()
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escapes the special characters and replaces the ones which are not allowed in XML 1.0
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(char),
+            char if char.is_control() => escaped.push(char::REPLACEMENT_CHARACTER),
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use std::collections::HashMap;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(
+            escape("a < b && 'c' > \"d\"\u{0}"),
+            "a &lt; b &amp;&amp; &apos;c&apos; &gt; &quot;d&quot;\u{FFFD}"
+        );
+    }
+
+    #[test]
+    fn failed_skipped_and_not_run_tests() {
+        let summary = TestTargetSummary {
+            test_case_summaries: vec![
+                AnyTestCaseSummary::Single(TestCaseSummary::Failed {
+                    name: "pkg::tests::failing".to_string(),
+                    msg: Some("\n    0x0 ('')\n\nerror occurred in contract\n".to_string()),
+                    arguments: vec![],
+                    test_statistics: (),
+                    captured_output: None,
+                }),
+                AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
+                    name: "pkg::tests::ignored".to_string(),
+                    reason: Some("flaky".to_string()),
+                }),
+                AnyTestCaseSummary::Single(TestCaseSummary::Skipped {
+                    name: "pkg::tests::skipped".to_string(),
+                }),
+            ],
+            test_case_durations: HashMap::from([(
+                "pkg::tests::failing".to_string(),
+                Duration::from_millis(1500),
+            )]),
+        };
+
+        let mut report = JunitReport::default();
+        report.add_test_target(
+            "pkg",
+            &summary,
+            vec![(
+                "pkg::other::partitioned".to_string(),
+                "Not in partition 2/2".to_string(),
+            )],
+            Duration::from_secs(2),
+        );
+
+        assert_eq!(
+            report.to_xml(),
+            indoc! {r#"
+                <?xml version="1.0" encoding="UTF-8"?>
+                <testsuites tests="4" failures="1" errors="0" skipped="3" time="2.000">
+                  <testsuite name="pkg" package="pkg" tests="4" failures="1" errors="0" skipped="3" time="2.000">
+                    <testcase name="partitioned" classname="pkg::other" time="0.000">
+                      <skipped message="Not in partition 2/2"/>
+                    </testcase>
+                    <testcase name="failing" classname="pkg::tests" time="1.500">
+                      <failure message="0x0 (&apos;&apos;)" type="panic">0x0 (&apos;&apos;)
+
+                error occurred in contract</failure>
+                    </testcase>
+                    <testcase name="ignored" classname="pkg::tests" time="0.000">
+                      <skipped message="flaky"/>
+                    </testcase>
+                    <testcase name="skipped" classname="pkg::tests" time="0.000">
+                      <skipped message="Cancelled after a failure with --exit-first"/>
+                    </testcase>
+                  </testsuite>
+                </testsuites>
+            "#}
+        );
+    }
+
+    #[test]
+    fn empty_test_target_is_omitted() {
+        let summary = TestTargetSummary {
+            test_case_summaries: vec![],
+            test_case_durations: HashMap::new(),
+        };
+
+        let mut report = JunitReport::default();
+        report.add_test_target("pkg", &summary, vec![], Duration::ZERO);
+
+        assert!(report.suites.is_empty());
+    }
+}
@@ -1,22 +1,46 @@
 use anyhow::Result;
+use camino::Utf8PathBuf;
+use check::CheckArgs;
 use clap::{Parser, Subcommand, ValueEnum};
-use forge_runner::CACHE_DIR;
+use combine_configs::parse_env_override;
+use configuration::env_file::load_env_file;
+use dump_abis::DumpAbisArgs;
+use forge_runner::{forge_config::TrackedResource, CACHE_DIR};
+use gas_snapshot::{GasSnapshotMode, Tolerance};
+use regex::Regex;
 use run_tests::workspace::run_for_workspace;
-use scarb_api::{metadata::MetadataCommandExt, ScarbCommand};
+use runtime::starknet::context::BlockContextVersion;
+use scarb_api::{contracts_filter::ContractsFilter, metadata::workspace_metadata, ScarbCommand};
 use scarb_ui::args::{FeaturesSpec, PackagesFilter};
-use std::{fs, num::NonZeroU32, thread::available_parallelism};
+use std::{
+    env, fs,
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+    thread::available_parallelism,
+};
+use test_filter::{Partition, TestKindFilter};
+use test_order::TestOrder;
 use tokio::runtime::Builder;
 use universal_sierra_compiler_api::UniversalSierraCompilerCommand;
 
 pub mod block_number_map;
+pub mod check;
 mod combine_configs;
+mod dump_abis;
+mod gas_snapshot;
 mod init;
+mod json_output;
+pub mod junit;
 pub mod pretty_printing;
+mod progress;
+pub mod run_report;
 pub mod run_tests;
 pub mod scarb;
 mod shared_cache;
+mod target_hashes;
 pub mod test_filter;
+pub mod test_order;
 mod warn;
+mod watch;
 
 pub const CAIRO_EDITION: &str = "2023_11";
 
@@ -55,6 +79,10 @@ Report bugs: https://github.com/foundry-rs/starknet-foundry/issues/new/choose\
 struct Cli {
     #[command(subcommand)]
     subcommand: ForgeSubcommand,
+
+    /// Do not load environment variables from the `.env` file in the project root
+    #[arg(long, global = true)]
+    no_env_file: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -64,10 +92,32 @@ enum ForgeSubcommand {
         #[command(flatten)]
         args: TestArgs,
     },
+    /// Build the project and collect its tests without running them, reporting invalid attributes and declared contracts which don't exist
+    Check {
+        #[command(flatten)]
+        args: CheckArgs,
+    },
+    /// Build the project and write the ABI of every contract to `<DIR>/<CONTRACT_NAME>.abi.json` files
+    DumpAbis {
+        #[command(flatten)]
+        args: DumpAbisArgs,
+    },
     /// Create a new directory with a Forge project
+    New {
+        /// Path of the directory to create, must not exist
+        path: Utf8PathBuf,
+        /// Name of the package, defaults to the name of the directory
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Create a Forge project in the current directory, or in a new directory if `<NAME>` is passed
     Init {
-        /// Name of a new project
-        name: String,
+        /// Name of a new project, created in a new directory with the same name
+        #[arg(value_name = "NAME", conflicts_with = "name")]
+        project_name: Option<String>,
+        /// Name of the package, defaults to the name of the current directory
+        #[arg(long)]
+        name: Option<String>,
     },
     /// Clean Forge cache directory
     CleanCache {},
@@ -80,7 +130,7 @@ enum ColorOption {
     Never,
 }
 
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct TestArgs {
     /// Name used to filter tests
@@ -88,6 +138,15 @@ pub struct TestArgs {
     /// Use exact matches for `test_filter`
     #[arg(short, long)]
     exact: bool,
+    /// Run only tests which fully qualified names match the regular expression
+    #[arg(long, value_name = "REGEX", value_parser = Regex::new, conflicts_with_all = ["test_filter", "exact"])]
+    filter_regex: Option<Regex>,
+    /// Exclude tests which names contain the substring, can be used multiple times
+    #[arg(long, value_name = "SUBSTRING")]
+    skip: Vec<String>,
+    /// Exit with code 3 if no tests matched the filters
+    #[arg(long)]
+    deny_no_matches: bool,
 
     /// Stop executing tests after the first failed test
     #[arg(short = 'x', long)]
@@ -114,14 +173,67 @@ pub struct TestArgs {
     #[arg(long)]
     detailed_resources: bool,
 
+    /// Resource the gas used by tests is computed from, `sierra-gas` requires tests compiled with gas
+    #[arg(value_enum, long, value_name = "RESOURCE", default_value_t = TrackedResource::CairoSteps)]
+    tracked_resource: TrackedResource,
+
+    /// Starknet version whose versioned constants and default block info tests are run with, e.g. `0.13.2`.
+    /// Overrides `block_context` from `[tool.snforge]`
+    #[arg(long, value_name = "VERSION")]
+    block_context_version: Option<BlockContextVersion>,
+
+    /// Number of the slowest tests listed with their durations after the run, 0 disables the list
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    durations: usize,
+
+    /// Write test events as JSON lines to stdout, other output is written to stderr
+    #[arg(long)]
+    json: bool,
+
+    /// Write a JUnit XML report of the run to the file
+    #[arg(long, value_name = "FILE")]
+    junit_path: Option<Utf8PathBuf>,
+
+    /// Write resources used by the passed tests and sizes of the contracts as a JSON report to the file
+    #[arg(long, value_name = "FILE")]
+    report_json: Option<Utf8PathBuf>,
+
     /// Control when colored output is used
     #[arg(value_enum, long, default_value_t = ColorOption::Auto, value_name="WHEN")]
     color: ColorOption,
 
-    /// Run tests that failed during the last run
+    /// Run tests that failed during the last run, can be combined with a test name filter
     #[arg(long)]
     rerun_failed: bool,
 
+    /// Skip test targets whose compiled tests and contracts did not change since all their tests last passed
+    #[arg(long)]
+    only_changed: bool,
+
+    /// Run the tests again each time Cairo sources or `Scarb.toml` files of the workspace change
+    #[arg(long)]
+    watch: bool,
+
+    /// Run only tests which failed in the previous iteration of `--watch`, all tests if none failed
+    #[arg(long, requires = "watch")]
+    watch_failed: bool,
+
+    /// Run only one of TOTAL deterministic shards of the tests selected by other filters, e.g. `2/4`
+    #[arg(long, value_name = "INDEX/TOTAL")]
+    partition: Option<Partition>,
+
+    /// Order in which tests are started, they are still run in parallel
+    #[arg(value_enum, long, value_name = "ORDER", default_value_t = TestOrder::Definition)]
+    test_order: TestOrder,
+
+    /// Kind of tests to run, a `unit::` or `integration::` prefix of the test name filter also selects the kind
+    #[arg(value_enum, long, value_name = "KIND", default_value_t = TestKindFilter::All)]
+    test_kind: TestKindFilter,
+
+    /// Seed of `--test-order shuffle`, random by default
+    #[arg(long, value_name = "SEED")]
+    shuffle_seed: Option<u64>,
+
     /// Save execution traces of all test which have passed and are not fuzz tests
     #[arg(long)]
     save_trace_data: bool,
@@ -130,14 +242,31 @@ pub struct TestArgs {
     #[arg(long)]
     build_profile: bool,
 
-    /// Generate a coverage report for the executed tests which have passed and are not fuzz tests using the cairo-coverage
+    /// Generate an lcov coverage report for the executed tests which have passed and are not fuzz tests
     #[arg(long)]
     coverage: bool,
 
+    /// File where the coverage report is written, `coverage/coverage.lcov` in the workspace root by default
+    #[arg(long, value_name = "FILE")]
+    coverage_path: Option<Utf8PathBuf>,
+
     /// Number of maximum steps during a single test. For fuzz tests this value is applied to each subtest separately.
     #[arg(long)]
     max_n_steps: Option<u32>,
 
+    /// Maximum number of tests run at the same time, the number of available cores by default
+    #[arg(short = 'j', long, env = "SNFORGE_JOBS", value_name = "N")]
+    jobs: Option<NonZeroUsize>,
+
+    /// Time limit of a single test run in seconds, tests exceeding it fail with `TIMEOUT` status. For fuzz tests this value is applied to each subtest separately.
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<NonZeroU64>,
+
+    /// Set an environment variable read with the `var` cheatcode, overrides `[tool.snforge.env]` and the process environment.
+    /// Can be used multiple times
+    #[arg(long, value_name = "KEY=VALUE", value_parser = parse_env_override)]
+    env: Vec<(String, String)>,
+
     /// Specify features to enable
     #[command(flatten)]
     pub features: FeaturesSpec,
@@ -145,26 +274,83 @@ pub struct TestArgs {
     /// Build contracts separately in the scarb starknet contract target
     #[arg(long)]
     no_optimization: bool,
+
+    /// Load and compile to CASM only the contracts which names match the glob pattern, e.g. `ERC20*`
+    #[arg(long, value_name = "GLOB")]
+    contracts_filter: Option<ContractsFilter>,
+
+    /// Record all data read from forks into snapshot files stored in the given directory
+    #[arg(long, value_name = "DIR", conflicts_with = "replay_fork_data")]
+    record_fork_data: Option<Utf8PathBuf>,
+
+    /// Serve all fork reads from snapshots recorded with `--record-fork-data`, without accessing the network
+    #[arg(long, value_name = "DIR")]
+    replay_fork_data: Option<Utf8PathBuf>,
+
+    /// Directory where failing inputs of fuzz tests are saved and replayed from, `.snfoundry/fuzz_corpus` in the workspace root by default
+    #[arg(long, value_name = "DIR")]
+    fuzz_corpus_dir: Option<Utf8PathBuf>,
+
+    /// Remove fuzz corpus entries that no longer fail or can't be replayed
+    #[arg(long)]
+    prune_fuzz_corpus: bool,
+
+    /// Print the output of tests as it is produced, instead of showing it only for failed tests
+    #[arg(long)]
+    nocapture: bool,
+
+    /// Write gas used by the tests to `.snfoundry/gas_snapshot.json` or check it against the saved snapshot,
+    /// `.snfoundry/gas_snapshot_sierra_gas.json` is used with `--tracked-resource sierra-gas`
+    #[arg(value_enum, long, value_name = "MODE")]
+    gas_snapshot: Option<GasSnapshotMode>,
+
+    /// Allowed increase of gas used by a test in `--gas-snapshot check`, e.g. `2%`
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        requires = "gas_snapshot",
+        default_value = "0%"
+    )]
+    tolerance: Tolerance,
+
+    /// Only warn when the version of `snforge_std` is not compatible with `snforge`, instead of failing
+    #[arg(long)]
+    no_version_check: bool,
 }
 
 pub enum ExitStatus {
     Success,
     Failure,
+    NoTestsMatched,
 }
 
 pub fn main_execution() -> Result<ExitStatus> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    if !cli.no_env_file && load_env_file(&Utf8PathBuf::try_from(env::current_dir()?)?)?.is_some() {
+        // Parsed again, so arguments read from the environment, e.g. `--jobs`, can be set in the `.env` file
+        cli = Cli::parse();
+    }
 
     ScarbCommand::new().ensure_available()?;
     UniversalSierraCompilerCommand::ensure_available()?;
 
     match cli.subcommand {
-        ForgeSubcommand::Init { name } => {
-            init::run(name.as_str())?;
+        ForgeSubcommand::New { path, name } => {
+            init::new(path.as_std_path(), name.as_deref())?;
             Ok(ExitStatus::Success)
         }
+        ForgeSubcommand::Init { project_name, name } => {
+            match project_name {
+                Some(project_name) => init::run(&project_name)?,
+                None => init::init(name.as_deref())?,
+            }
+            Ok(ExitStatus::Success)
+        }
+        ForgeSubcommand::Check { args } => check::check_workspace(&args),
+        ForgeSubcommand::DumpAbis { args } => dump_abis::dump_abis(&args),
         ForgeSubcommand::CleanCache {} => {
-            let scarb_metadata = ScarbCommand::metadata().inherit_stderr().run()?;
+            let scarb_metadata = workspace_metadata()?;
             let cache_dir = scarb_metadata.workspace.root.join(CACHE_DIR);
 
             if cache_dir.exists() {
@@ -174,19 +360,30 @@ pub fn main_execution() -> Result<ExitStatus> {
             Ok(ExitStatus::Success)
         }
         ForgeSubcommand::Test { args } => {
-            let cores = if let Ok(available_cores) = available_parallelism() {
+            let jobs = if let Some(jobs) = args.jobs {
+                jobs.get()
+            } else if let Ok(available_cores) = available_parallelism() {
                 available_cores.get()
             } else {
                 eprintln!("Failed to get the number of available cores, defaulting to 1");
                 1
             };
 
+            // Tests are run on the blocking threads, so their number limits how many run at once
             let rt = Builder::new_multi_thread()
-                .max_blocking_threads(cores)
+                .max_blocking_threads(jobs)
                 .enable_all()
                 .build()?;
 
-            rt.block_on(run_for_workspace(args))
+            let result = if args.watch {
+                rt.block_on(watch::watch_workspace(args, jobs))
+            } else {
+                rt.block_on(run_for_workspace(args, jobs))
+            };
+            // Tests abandoned after their timeout may still be running, they must not block the exit
+            rt.shutdown_background();
+
+            result
         }
     }
 }
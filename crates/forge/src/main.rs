@@ -4,6 +4,7 @@ fn main() {
     match main_execution() {
         Ok(ExitStatus::Success) => std::process::exit(0),
         Ok(ExitStatus::Failure) => std::process::exit(1),
+        Ok(ExitStatus::NoTestsMatched) => std::process::exit(3),
         Err(error) => {
             pretty_printing::print_error_message(&error);
             std::process::exit(2);
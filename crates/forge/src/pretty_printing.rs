@@ -1,84 +1,339 @@
+use crate::check::PackageCheck;
+use crate::dump_abis::WrittenAbi;
+use crate::gas_snapshot::{GasSnapshotComparison, Tolerance};
+use crate::test_filter::Partition;
+use crate::test_order::TestOrdering;
 use anyhow::Error;
 use console::style;
+use forge_runner::forge_config::TrackedResource;
 use forge_runner::package_tests::TestTargetLocation;
-use forge_runner::{test_case_summary::AnyTestCaseSummary, test_target_summary::TestTargetSummary};
+use forge_runner::resource_report::ResourceReport;
+use forge_runner::{
+    test_case_summary::{AnyTestCaseSummary, TestCaseSummary},
+    test_target_summary::TestTargetSummary,
+};
+use itertools::Itertools;
+use shared::human_println;
 use starknet_api::block::BlockNumber;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::ops::AddAssign;
+use std::time::Duration;
 use url::Url;
 
 pub fn print_error_message(error: &Error) {
     let error_tag = style("ERROR").red();
-    println!("[{error_tag}] {error:#}");
+    human_println!("[{error_tag}] {error:#}");
 }
 
 pub(crate) fn print_collected_tests_count(tests_num: usize, package_name: &str) {
     let plain_text = format!("\n\nCollected {tests_num} test(s) from {package_name} package");
-    println!("{}", style(plain_text).bold());
+    human_println!("{}", style(plain_text).bold());
+}
+
+pub(crate) fn print_package_check(check: &PackageCheck) {
+    let plain_text = format!(
+        "\n\nChecked {} test(s) from {} package",
+        check.tests, check.package
+    );
+    human_println!("{}", style(plain_text).bold());
+
+    if check.files.is_empty() {
+        human_println!("No problems found");
+        return;
+    }
+
+    let error_tag = style("ERROR").red();
+    human_println!("[{error_tag}] Found {} problem(s):", check.problems_count());
+    for (file, problems) in &check.files {
+        human_println!("{file}:");
+        for problem in problems {
+            human_println!("    {problem}");
+        }
+    }
+}
+
+pub(crate) fn print_written_abis(written: &[WrittenAbi]) {
+    if written.is_empty() {
+        human_println!("No contracts found, no ABIs written");
+        return;
+    }
+
+    for abi in written {
+        human_println!(
+            "Written ABI of {}::{} to {}",
+            abi.package,
+            abi.contract_name,
+            abi.path
+        );
+    }
+}
+
+pub(crate) fn print_filter_match_counts(match_counts: &[(String, usize)]) {
+    for (filter, count) in match_counts {
+        human_println!("{filter} matched {count} test(s)");
+    }
+}
+
+pub(crate) fn print_partition(partition: Partition, tests_num: usize) {
+    human_println!("Partition {partition} selected {tests_num} test(s)");
+}
+
+pub(crate) fn print_unchanged_targets(test_target_locations: &[TestTargetLocation]) {
+    let dir_names = test_target_locations
+        .iter()
+        .map(|location| format!("{}/", dir_name(*location)))
+        .join(", ");
+
+    human_println!("Skipped unchanged test targets: {dir_names}");
+}
+
+pub(crate) fn print_test_order(test_ordering: TestOrdering) {
+    human_println!("Test order: {test_ordering}");
+}
+
+pub(crate) fn print_tracked_resource(tracked_resource: TrackedResource) {
+    human_println!("Tracked resource: {tracked_resource}");
 }
 
 pub(crate) fn print_running_tests(test_target_location: TestTargetLocation, tests_num: usize) {
-    let dir_name = match test_target_location {
+    let dir_name = dir_name(test_target_location);
+    let test_kind = test_target_location.test_kind();
+    let plain_text = format!("Running {tests_num} {test_kind} test(s) from {dir_name}/");
+
+    human_println!("{}", style(plain_text).bold());
+}
+
+fn dir_name(test_target_location: TestTargetLocation) -> &'static str {
+    match test_target_location {
         TestTargetLocation::Lib => "src",
         TestTargetLocation::Tests => "tests",
-    };
-    let plain_text = format!("Running {tests_num} test(s) from {dir_name}/");
+    }
+}
+
+/// Numbers of test results of a package, or of all packages of a run
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TestCounts {
+    passed: usize,
+    failed: usize,
+    skipped: usize,
+    ignored: usize,
+}
 
-    println!("{}", style(plain_text).bold());
+impl TestCounts {
+    pub(crate) fn new(summaries: &[TestTargetSummary]) -> Self {
+        Self {
+            passed: summaries.iter().map(TestTargetSummary::count_passed).sum(),
+            failed: summaries.iter().map(TestTargetSummary::count_failed).sum(),
+            skipped: summaries.iter().map(TestTargetSummary::count_skipped).sum(),
+            ignored: summaries.iter().map(TestTargetSummary::count_ignored).sum(),
+        }
+    }
+}
+
+impl AddAssign for TestCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.skipped += other.skipped;
+        self.ignored += other.ignored;
+    }
+}
+
+impl Display for TestCounts {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} skipped, {} ignored",
+            self.passed, self.failed, self.skipped, self.ignored
+        )
+    }
 }
 
 // TODO(#2574): Bring back "filtered out" number in tests summary when running with `--exact` flag
 pub(crate) fn print_test_summary(summaries: &[TestTargetSummary], filtered: Option<usize>) {
-    let passed: usize = summaries.iter().map(TestTargetSummary::count_passed).sum();
-    let failed: usize = summaries.iter().map(TestTargetSummary::count_failed).sum();
-    let skipped: usize = summaries.iter().map(TestTargetSummary::count_skipped).sum();
-    let ignored: usize = summaries.iter().map(TestTargetSummary::count_ignored).sum();
-
-    if let Some(filtered) = filtered {
-        println!(
-            "{}: {} passed, {} failed, {} skipped, {} ignored, {} filtered out",
-            style("Tests").bold(),
-            passed,
-            failed,
-            skipped,
-            ignored,
-            filtered
-        );
-    } else {
-        println!(
-            "{}: {} passed, {} failed, {} skipped, {} ignored, other filtered out",
-            style("Tests").bold(),
-            passed,
-            failed,
-            skipped,
-            ignored
-        );
+    let counts = TestCounts::new(summaries);
+    let filtered = filtered.map_or_else(|| "other".to_string(), |filtered| filtered.to_string());
+
+    human_println!(
+        "{}: {counts}, {filtered} filtered out",
+        style("Tests").bold()
+    );
+}
+
+/// Printed after tests of multiple packages were run, with the results of each package and all of them together
+pub(crate) fn print_packages_summary(package_counts: &[(String, TestCounts)]) {
+    if package_counts.len() < 2 {
+        return;
     }
+    let name_width = package_counts
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or_default();
+    let mut total = TestCounts::default();
+
+    human_println!("\n{}:", style("Tests by package").bold());
+    for (name, counts) in package_counts {
+        human_println!("    {name:<name_width$}  {counts}");
+        total += *counts;
+    }
+    human_println!("{}: {total}", style("Tests in all packages").bold());
+}
+
+/// Printed with `--detailed-resources`, after the summary of the tests
+pub(crate) fn print_total_resources(report: &ResourceReport) {
+    let format_counts = |counts: &BTreeMap<String, usize>| {
+        counts
+            .iter()
+            .sorted_by(|(a_name, a_count), (b_name, b_count)| {
+                b_count.cmp(a_count).then_with(|| a_name.cmp(b_name))
+            })
+            .map(|(name, count)| format!("{name}: {count}"))
+            .join(", ")
+    };
+
+    let sierra_gas = report
+        .sierra_gas_consumed
+        .map(|gas| format!("\n        sierra gas: {gas}"))
+        .unwrap_or_default();
+
+    human_println!(
+        "{}:
+        gas: ~{}{sierra_gas}
+        steps: {}
+        memory holes: {}
+        builtins: ({})
+        syscalls: ({})
+        events: {} (size: {})
+        messages to L1: {} (payload size: {})",
+        style("Total resources of passed tests").bold(),
+        report.gas,
+        report.steps,
+        report.memory_holes,
+        format_counts(&report.builtins),
+        format_counts(&report.syscalls),
+        report.events,
+        report.events_size,
+        report.l2_to_l1_messages,
+        report.l2_to_l1_payload_size,
+    );
 }
 
 pub(crate) fn print_test_seed(seed: u64) {
-    println!("{}: {seed}", style("Fuzzer seed").bold());
+    human_println!("{}: {seed}", style("Fuzzer seed").bold());
 }
 
 pub fn print_failures(all_failed_tests: &[AnyTestCaseSummary]) {
     if all_failed_tests.is_empty() {
         return;
     }
-    let failed_tests_names = all_failed_tests
-        .iter()
-        .map(|any_test_case_summary| any_test_case_summary.name().unwrap());
+    let failed_tests_names = all_failed_tests.iter().map(AnyTestCaseSummary::name);
 
-    println!("\nFailures:");
+    human_println!("\nFailures:");
     for name in failed_tests_names {
-        println!("    {name}");
+        human_println!("    {name}");
     }
 }
 
+pub(crate) fn print_gas_snapshot_comparison(
+    comparison: &GasSnapshotComparison,
+    tolerance: Tolerance,
+) {
+    let GasSnapshotComparison {
+        regressions,
+        improvements,
+        new_tests,
+    } = comparison;
+
+    human_println!("\nGas snapshot check (tolerance {tolerance}):");
+    if regressions.is_empty() {
+        human_println!("    No gas regressions");
+    } else {
+        human_println!("    {}", style("Gas increased above tolerance:").red());
+        for change in regressions {
+            human_println!("        {change}");
+        }
+    }
+    if !improvements.is_empty() {
+        human_println!("    {}", style("Gas decreased:").green());
+        for change in improvements {
+            human_println!("        {change}");
+        }
+    }
+    if !new_tests.is_empty() {
+        human_println!("    Tests missing from the snapshot:");
+        for name in new_tests {
+            human_println!("        {name}");
+        }
+    }
+}
+
+/// Wall time of a test which was run, with the gas it used if it passed
+pub(crate) struct TestDuration {
+    name: String,
+    duration: Duration,
+    gas: Option<String>,
+}
+
+pub(crate) fn test_durations(summaries: &[TestTargetSummary]) -> Vec<TestDuration> {
+    summaries
+        .iter()
+        .flat_map(|summary| {
+            summary
+                .test_case_summaries
+                .iter()
+                .filter(|result| result.is_passed() || result.is_failed())
+                .map(|result| TestDuration {
+                    name: result.name().to_string(),
+                    duration: summary
+                        .test_case_durations
+                        .get(result.name())
+                        .copied()
+                        .unwrap_or_default(),
+                    gas: match result {
+                        AnyTestCaseSummary::Single(TestCaseSummary::Passed {
+                            gas_info, ..
+                        }) => Some(format!("~{gas_info}")),
+                        AnyTestCaseSummary::Fuzzing(TestCaseSummary::Passed {
+                            gas_info, ..
+                        }) => Some(format!("mean ~{:.2}", gas_info.mean)),
+                        _ => None,
+                    },
+                })
+        })
+        .collect()
+}
+
+pub(crate) fn print_slowest_tests(mut test_durations: Vec<TestDuration>, count: usize) {
+    if count == 0 || test_durations.is_empty() {
+        return;
+    }
+    test_durations.sort_by(|a, b| b.duration.cmp(&a.duration));
+    test_durations.truncate(count);
+
+    human_println!("\n{}:", style("Slowest tests").bold());
+    for TestDuration {
+        name,
+        duration,
+        gas,
+    } in test_durations
+    {
+        let gas = gas.map(|gas| format!(" (gas: {gas})")).unwrap_or_default();
+        human_println!("    {:>8.2}s {name}{gas}", duration.as_secs_f64());
+    }
+}
+
+pub fn print_jobs(jobs: usize) {
+    human_println!("{}: {jobs}", style("Jobs").bold());
+}
+
 #[allow(clippy::implicit_hasher)]
 pub fn print_latest_blocks_numbers(url_to_latest_block_number_map: &HashMap<Url, BlockNumber>) {
     if !url_to_latest_block_number_map.is_empty() {
-        println!();
+        human_println!();
     }
     for (url, latest_block_number) in url_to_latest_block_number_map {
-        println!("Latest block number = {latest_block_number} for url = {url}");
+        human_println!("Latest block number = {latest_block_number} for url = {url}");
     }
 }
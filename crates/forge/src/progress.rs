@@ -0,0 +1,161 @@
+use console::{truncate_str, Term};
+use forge_runner::running_tests::RunningTests;
+use itertools::Itertools;
+use scarb_api::CompilationProgress;
+use shared::human_println;
+use shared::print::is_human_output_to_stderr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// How often the progress line is redrawn on terminals
+const REDRAW_INTERVAL: Duration = Duration::from_millis(200);
+/// How often a progress line is printed when the output is not a terminal
+const PLAIN_TEXT_INTERVAL: Duration = Duration::from_secs(10);
+/// Number of the longest running tests shown in the progress line
+const SHOWN_RUNNING_TESTS: usize = 3;
+
+/// Progress of running the tests of a test target, a line redrawn in place on terminals
+/// and printed periodically otherwise
+pub(crate) struct Progress {
+    state: Arc<Mutex<State>>,
+    running_tests: RunningTests,
+    ticker: JoinHandle<()>,
+}
+
+struct State {
+    term: Term,
+    is_term: bool,
+    finished: usize,
+    total: usize,
+    running_tests: RunningTests,
+    /// Whether the progress line is currently displayed on the terminal
+    is_drawn: bool,
+}
+
+impl Progress {
+    pub(crate) fn start(total: usize, running_tests: RunningTests) -> Self {
+        let term = if is_human_output_to_stderr() {
+            Term::stderr()
+        } else {
+            Term::stdout()
+        };
+        let is_term = term.is_term();
+        let state = Arc::new(Mutex::new(State {
+            term,
+            is_term,
+            finished: 0,
+            total,
+            running_tests: running_tests.clone(),
+            is_drawn: false,
+        }));
+
+        let ticker = tokio::spawn({
+            let state = state.clone();
+            let period = if is_term {
+                REDRAW_INTERVAL
+            } else {
+                PLAIN_TEXT_INTERVAL
+            };
+
+            async move {
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                loop {
+                    interval.tick().await;
+                    state.lock().unwrap().report();
+                }
+            }
+        });
+
+        Self {
+            state,
+            running_tests,
+            ticker,
+        }
+    }
+
+    /// Prints the output of a finished test, without it being mixed with the progress line
+    pub(crate) fn test_finished(&self, name: &str, print: impl FnOnce()) {
+        self.running_tests.finish(name);
+
+        let mut state = self.state.lock().unwrap();
+        state.clear();
+        print();
+        state.finished += 1;
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.ticker.abort();
+        self.state.lock().unwrap().clear();
+    }
+}
+
+impl State {
+    fn report(&mut self) {
+        let running = self
+            .running_tests
+            .longest_running(SHOWN_RUNNING_TESTS)
+            .into_iter()
+            .map(|(name, duration)| format!("{name} ({:.1}s)", duration.as_secs_f64()))
+            .join(", ");
+        let line = if running.is_empty() {
+            format!("[{}/{}]", self.finished, self.total)
+        } else {
+            format!("[{}/{}] running: {running}", self.finished, self.total)
+        };
+
+        if self.is_term {
+            let width = usize::from(self.term.size().1);
+            let _ = self.term.clear_line();
+            let _ = self.term.write_str(&truncate_str(&line, width, "..."));
+            self.is_drawn = true;
+        } else {
+            human_println!("{line}");
+        }
+    }
+
+    fn clear(&mut self) {
+        if self.is_drawn {
+            let _ = self.term.clear_line();
+            self.is_drawn = false;
+        }
+    }
+}
+
+/// Progress of compiling contracts to CASM, a line redrawn in place on terminals,
+/// nothing is printed otherwise as the compilation is usually short
+pub(crate) struct ContractsCompilationProgress {
+    term: Term,
+}
+
+impl ContractsCompilationProgress {
+    pub(crate) fn new() -> Self {
+        let term = if is_human_output_to_stderr() {
+            Term::stderr()
+        } else {
+            Term::stdout()
+        };
+
+        Self { term }
+    }
+}
+
+impl CompilationProgress for ContractsCompilationProgress {
+    fn contract_compiled(&self, compiled: usize, total: usize) {
+        if self.term.is_term() {
+            let _ = self.term.clear_line();
+            let _ = self
+                .term
+                .write_str(&format!("Compiled {compiled}/{total} contracts"));
+        }
+    }
+
+    fn finished(&self) {
+        if self.term.is_term() {
+            let _ = self.term.clear_line();
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use forge_runner::resource_report::ResourceReport;
+use forge_runner::test_case_summary::AnyTestCaseSummary;
+use forge_runner::test_target_summary::TestTargetSummary;
+use scarb_api::contract_size::ContractSize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+
+/// Version of the schema of the report, bumped on every change which is not backward compatible
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// JSON report of the run written with `--report-json`, with resources used by the tests
+/// and sizes of the contracts of each package
+#[derive(Debug, Serialize)]
+pub struct RunReport {
+    schema_version: u32,
+    packages: Vec<PackageReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct PackageReport {
+    name: String,
+    /// Resources used by the passed tests, by the test name. Fuzz tests are not included
+    tests: BTreeMap<String, ResourceReport>,
+    /// Sorted by the contract name
+    contracts: Vec<ContractSize>,
+}
+
+impl Default for RunReport {
+    fn default() -> Self {
+        Self {
+            schema_version: REPORT_SCHEMA_VERSION,
+            packages: vec![],
+        }
+    }
+}
+
+impl RunReport {
+    pub fn add_package(
+        &mut self,
+        package: &str,
+        summaries: &[TestTargetSummary],
+        contracts: Vec<ContractSize>,
+    ) {
+        let tests = summaries
+            .iter()
+            .flat_map(|summary| &summary.test_case_summaries)
+            .filter_map(|test_case_summary| match test_case_summary {
+                AnyTestCaseSummary::Single(case) => case
+                    .resource_report()
+                    .map(|report| (case.name().to_string(), report)),
+                AnyTestCaseSummary::Fuzzing(_) => None,
+            })
+            .collect();
+
+        self.packages.push(PackageReport {
+            name: package.to_string(),
+            tests,
+            contracts,
+        });
+    }
+
+    pub fn write(&self, path: &Utf8Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {parent}"))?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write JSON report to {path}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forge_runner::forge_config::TrackedResource;
+    use forge_runner::test_case_summary::TestCaseSummary;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[test]
+    fn only_passed_tests_are_reported() {
+        let summary = TestTargetSummary {
+            test_case_summaries: vec![
+                AnyTestCaseSummary::Single(TestCaseSummary::Failed {
+                    name: "pkg::tests::failing".to_string(),
+                    msg: None,
+                    arguments: vec![],
+                    test_statistics: (),
+                    captured_output: None,
+                }),
+                AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
+                    name: "pkg::tests::ignored".to_string(),
+                    reason: None,
+                }),
+            ],
+            test_case_durations: HashMap::new(),
+        };
+        let mut report = RunReport::default();
+
+        report.add_package("pkg", &[summary], vec![]);
+
+        assert_eq!(report.packages.len(), 1);
+        assert_eq!(report.packages[0].name, "pkg");
+        assert!(report.packages[0].tests.is_empty());
+    }
+
+    #[test]
+    fn schema() {
+        let report = RunReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            packages: vec![PackageReport {
+                name: "pkg".to_string(),
+                tests: BTreeMap::from([(
+                    "pkg::tests::passing".to_string(),
+                    ResourceReport {
+                        steps: 100,
+                        gas: 2,
+                        tracked_resource: TrackedResource::CairoSteps,
+                        sierra_gas_consumed: Some(5000),
+                        builtins: BTreeMap::from([("range_check_builtin".to_string(), 3)]),
+                        ..Default::default()
+                    },
+                )]),
+                contracts: vec![ContractSize {
+                    contract_name: "HelloStarknet".to_string(),
+                    sierra_length: 1000,
+                    casm_length: 2000,
+                    bytecode_size: 300,
+                }],
+            }],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "schema_version": 1,
+                "packages": [{
+                    "name": "pkg",
+                    "tests": {
+                        "pkg::tests::passing": {
+                            "steps": 100,
+                            "memory_holes": 0,
+                            "gas": 2,
+                            "tracked_resource": "cairo_steps",
+                            "sierra_gas_consumed": 5000,
+                            "builtins": { "range_check_builtin": 3 },
+                            "syscalls": {},
+                            "events": 0,
+                            "events_size": 0,
+                            "l2_to_l1_messages": 0,
+                            "l2_to_l1_payload_size": 0
+                        }
+                    },
+                    "contracts": [{
+                        "contract_name": "HelloStarknet",
+                        "sierra_length": 1000,
+                        "casm_length": 2000,
+                        "bytecode_size": 300
+                    }]
+                }]
+            })
+        );
+    }
+}
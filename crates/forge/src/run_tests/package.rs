@@ -1,47 +1,66 @@
 use super::{
     resolve_config::resolve_config,
-    test_target::{run_for_test_target, TestTargetRunResult},
+    test_target::{cancel_test_target, run_for_test_target, TestTargetRunResult},
 };
 use crate::{
     block_number_map::BlockNumberMap,
     combine_configs::combine_configs,
+    json_output::{emit_diagnostics, Event},
+    junit::JunitReport,
     pretty_printing,
+    progress::ContractsCompilationProgress,
     scarb::{
         config::{ForgeConfigFromScarb, ForkTarget},
         load_test_artifacts, should_compile_starknet_contract_target,
     },
-    shared_cache::FailedTestsCache,
+    shared_cache::LastRun,
+    target_hashes::{run_settings, target_hash, TargetHashes},
     test_filter::{NameFilter, TestsFilter},
+    test_order::{TestOrder, TestOrdering},
     warn::{
         warn_if_available_gas_used_with_incompatible_scarb_version,
-        warn_if_incompatible_rpc_version,
+        warn_if_contracts_have_unsupported_sierra_versions, warn_if_incompatible_rpc_version,
+        warn_if_max_n_steps_exceeds_protocol_limit,
     },
     TestArgs,
 };
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
+use cairo_lang_starknet_classes::contract_class::ContractClass;
 use camino::{Utf8Path, Utf8PathBuf};
+use cheatnet::forking::snapshot::ForkDataMode;
 use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
+use cheatnet::runtime_extensions::forge_runtime_extension::sierra_version::should_compile_to_casm;
 use configuration::load_package_config;
 use forge_runner::{
-    forge_config::ForgeConfig,
-    package_tests::{raw::TestTargetRaw, with_config_resolved::TestTargetWithResolvedConfig},
+    forge_config::{ForgeConfig, TrackedResource},
+    package_tests::{
+        raw::TestTargetRaw, with_config_resolved::TestTargetWithResolvedConfig, TestTargetLocation,
+    },
+    resource_report::total_resource_report,
     running::with_config::test_target_with_config,
     test_case_summary::AnyTestCaseSummary,
     test_target_summary::TestTargetSummary,
 };
-use scarb_api::get_contracts_artifacts_and_source_sierra_paths;
+use scarb_api::get_contracts_artifacts_and_source_sierra_paths_with_progress;
 use scarb_metadata::{Metadata, PackageMetadata};
 use std::sync::Arc;
+use std::time::Instant;
 
 pub struct RunForPackageArgs {
     pub test_targets: Vec<TestTargetRaw>,
+    /// Hashes of `test_targets`, computed only with `--only-changed`
+    pub target_hashes: Vec<(TestTargetLocation, String)>,
+    /// Targets skipped with `--only-changed`
+    pub unchanged_targets: Vec<TestTargetLocation>,
     pub tests_filter: TestsFilter,
     pub forge_config: Arc<ForgeConfig>,
     pub fork_targets: Vec<ForkTarget>,
     pub package_name: String,
+    pub test_ordering: TestOrdering,
 }
 
 impl RunForPackageArgs {
+    #[allow(clippy::too_many_arguments)]
     pub fn build(
         package: PackageMetadata,
         scarb_metadata: &Metadata,
@@ -49,10 +68,21 @@ impl RunForPackageArgs {
         cache_dir: &Utf8PathBuf,
         snforge_target_dir_path: &Utf8Path,
         versioned_programs_dir: Utf8PathBuf,
+        coverage_data_dir: Utf8PathBuf,
+        fork_data: Option<ForkDataMode>,
+        fuzz_corpus_dir: Utf8PathBuf,
+        last_run: Option<&LastRun>,
+        saved_target_hashes: Option<&TargetHashes>,
+        test_ordering: TestOrdering,
     ) -> Result<RunForPackageArgs> {
-        let raw_test_targets = load_test_artifacts(snforge_target_dir_path, &package)?;
+        let raw_test_targets = load_test_artifacts(snforge_target_dir_path, &package)?
+            .into_iter()
+            .filter(|test_target| {
+                args.test_kind
+                    .includes(test_target.tests_location.test_kind())
+            });
 
-        let contracts = get_contracts_artifacts_and_source_sierra_paths(
+        let contracts = get_contracts_artifacts_and_source_sierra_paths_with_progress(
             scarb_metadata,
             &package.id,
             None,
@@ -60,41 +90,88 @@ impl RunForPackageArgs {
                 &scarb_metadata.app_version_info.version,
                 args.no_optimization,
             ),
+            &ContractsCompilationProgress::new(),
+            args.contracts_filter.as_ref(),
+            &should_compile_to_casm,
         )?;
-        let contracts_data = ContractsData::try_from(contracts)?;
 
         let forge_config_from_scarb =
             load_package_config::<ForgeConfigFromScarb>(scarb_metadata, &package.id)?;
+        let run_settings = run_settings(args, &forge_config_from_scarb);
+
+        let mut test_targets = vec![];
+        let mut target_hashes = vec![];
+        let mut unchanged_targets = vec![];
+        for test_target in raw_test_targets {
+            let Some(saved_target_hashes) = saved_target_hashes else {
+                test_targets.push(test_target);
+                continue;
+            };
+
+            let location = test_target.tests_location;
+            let hash = target_hash(&test_target, &contracts, &run_settings)?;
+            if saved_target_hashes.is_unchanged(&package.name, location, &hash) {
+                unchanged_targets.push(location);
+            } else {
+                test_targets.push(test_target);
+                target_hashes.push((location, hash));
+            }
+        }
+
+        let contracts_data = ContractsData::try_from(contracts)?;
+        warn_if_contracts_have_unsupported_sierra_versions(
+            &contracts_data,
+            &scarb_metadata.app_version_info.cairo.version,
+        );
+
         let forge_config = Arc::new(combine_configs(
             args.exit_first,
             args.fuzzer_runs,
             args.fuzzer_seed,
             args.detailed_resources,
+            args.json,
             args.save_trace_data,
             args.build_profile,
             args.coverage,
             args.max_n_steps,
+            args.timeout,
+            args.tracked_resource,
+            args.block_context_version,
             contracts_data,
             cache_dir.clone(),
+            fork_data,
+            fuzz_corpus_dir,
+            args.prune_fuzz_corpus,
+            args.nocapture,
             versioned_programs_dir,
+            coverage_data_dir,
+            &args.env,
             &forge_config_from_scarb,
         ));
 
         let test_filter = TestsFilter::from_flags(
             args.test_filter.clone(),
             args.exact,
+            args.filter_regex.clone(),
+            args.skip.clone(),
             args.only_ignored,
             args.include_ignored,
-            args.rerun_failed,
-            FailedTestsCache::new(cache_dir),
+            last_run.is_some(),
+            last_run
+                .map(|last_run| last_run.tests_to_rerun(&package.name))
+                .unwrap_or_default(),
+            args.partition,
         );
 
         Ok(RunForPackageArgs {
-            test_targets: raw_test_targets,
+            test_targets,
+            target_hashes,
+            unchanged_targets,
             forge_config,
             tests_filter: test_filter,
             fork_targets: forge_config_from_scarb.fork,
             package_name: package.name,
+            test_ordering,
         })
     }
 }
@@ -121,63 +198,211 @@ fn sum_test_cases(test_targets: &[TestTargetWithResolvedConfig]) -> usize {
     test_targets.iter().map(|tc| tc.test_cases.len()).sum()
 }
 
+/// Sierra gas is read from the gas counters of the test and the called contracts,
+/// which only programs compiled with gas have
+fn ensure_compiled_with_gas(
+    test_targets: &[TestTargetWithResolvedConfig],
+    contracts_data: &ContractsData,
+    package_name: &str,
+) -> Result<()> {
+    let compiled_without_gas: Vec<_> = test_targets
+        .iter()
+        .filter(|test_target| {
+            test_target
+                .test_cases
+                .iter()
+                .any(|case| !case.test_details.is_compiled_with_gas())
+        })
+        .map(|test_target| match test_target.tests_location {
+            TestTargetLocation::Lib => "src/",
+            TestTargetLocation::Tests => "tests/",
+        })
+        .collect();
+
+    ensure!(
+        compiled_without_gas.is_empty(),
+        "`--tracked-resource sierra-gas` requires tests compiled with gas, but tests of package {package_name} in {} were compiled without it. \
+        Remove `enable-gas = false` from the `[cairo]` section of Scarb.toml",
+        compiled_without_gas.join(", ")
+    );
+
+    let mut contracts_compiled_without_gas = vec![];
+    for (contract_name, artifacts) in contracts_data.artifacts() {
+        let program = serde_json::from_str::<ContractClass>(&artifacts.sierra)
+            .with_context(|| format!("Failed to parse Sierra of contract {contract_name}"))?
+            .extract_sierra_program()
+            .with_context(|| {
+                format!("Failed to extract Sierra program of contract {contract_name}")
+            })?;
+        let uses_gas = program
+            .type_declarations
+            .iter()
+            .any(|declaration| declaration.long_id.generic_id.0 == "GasBuiltin");
+
+        if !uses_gas {
+            contracts_compiled_without_gas.push(contract_name.as_str());
+        }
+    }
+    contracts_compiled_without_gas.sort_unstable();
+
+    ensure!(
+        contracts_compiled_without_gas.is_empty(),
+        "`--tracked-resource sierra-gas` requires contracts compiled with gas, but contracts {} of package {package_name} were compiled without it",
+        contracts_compiled_without_gas.join(", ")
+    );
+
+    Ok(())
+}
+
 pub async fn run_for_package(
     RunForPackageArgs {
         test_targets,
+        target_hashes: _,
+        unchanged_targets,
         forge_config,
         tests_filter,
         fork_targets,
         package_name,
+        test_ordering,
     }: RunForPackageArgs,
     block_number_map: &mut BlockNumberMap,
+    mut junit_report: Option<&mut JunitReport>,
 ) -> Result<Vec<TestTargetSummary>> {
     let mut test_targets =
         test_package_with_config_resolved(test_targets, &fork_targets, block_number_map).await?;
     let all_tests = sum_test_cases(&test_targets);
+    for test_target in &mut test_targets {
+        if !tests_filter.includes_kind(test_target.tests_location.test_kind()) {
+            test_target.test_cases.clear();
+        }
+    }
+    let filter_match_counts = tests_filter.match_counts(&test_targets);
 
+    tests_filter.warn_if_last_failed_tests_missing(&test_targets);
+    let partitioned_out: Vec<_> = test_targets
+        .iter_mut()
+        .map(|test_target| tests_filter.filter_tests(&mut test_target.test_cases))
+        .collect();
     for test_target in &mut test_targets {
-        tests_filter.filter_tests(&mut test_target.test_cases)?;
+        test_ordering.apply(&mut test_target.test_cases);
+    }
+
+    let tracked_resource = forge_config.test_runner_config.tracked_resource;
+    if tracked_resource == TrackedResource::SierraGas {
+        ensure_compiled_with_gas(
+            &test_targets,
+            &forge_config.test_runner_config.contracts_data,
+            &package_name,
+        )?;
     }
 
     warn_if_available_gas_used_with_incompatible_scarb_version(&test_targets)?;
-    warn_if_incompatible_rpc_version(&test_targets).await?;
+    warn_if_max_n_steps_exceeds_protocol_limit(
+        forge_config.test_runner_config.max_n_steps,
+        forge_config.test_runner_config.block_context_version,
+    );
+    let replays_fork_data = forge_config
+        .test_runner_config
+        .fork_data
+        .as_ref()
+        .is_some_and(ForkDataMode::is_replay);
+    if !replays_fork_data {
+        warn_if_incompatible_rpc_version(&test_targets).await?;
+    }
 
     let not_filtered = sum_test_cases(&test_targets);
+    // TODO(#2574): Bring back "filtered out" number in tests summary when running with `--exact` flag
+    let filtered = match tests_filter.name_filter {
+        NameFilter::ExactMatch(_) => None,
+        _ => Some(all_tests - not_filtered),
+    };
+
     pretty_printing::print_collected_tests_count(not_filtered, &package_name);
+    pretty_printing::print_filter_match_counts(&filter_match_counts);
+    if !unchanged_targets.is_empty() {
+        pretty_printing::print_unchanged_targets(&unchanged_targets);
+    }
+    if let Some(partition) = tests_filter.partition() {
+        pretty_printing::print_partition(partition, not_filtered);
+    }
+    if test_ordering.order != TestOrder::Definition {
+        pretty_printing::print_test_order(test_ordering);
+    }
+    if tracked_resource != TrackedResource::CairoSteps {
+        pretty_printing::print_tracked_resource(tracked_resource);
+    }
+
+    let json = forge_config.output_config.json;
+    if json {
+        emit_diagnostics()?;
+        Event::SuiteStarted {
+            package: &package_name,
+            test_count: not_filtered,
+            filtered_out: filtered,
+            test_order: test_ordering.order,
+            shuffle_seed: test_ordering.shuffle_seed,
+        }
+        .emit()?;
+    }
+    let started = Instant::now();
 
     let mut summaries = vec![];
+    let mut interrupted = false;
 
-    for test_target in test_targets {
+    for (test_target, partitioned_out) in test_targets.into_iter().zip(partitioned_out) {
         pretty_printing::print_running_tests(
             test_target.tests_location,
             test_target.test_cases.len(),
         );
 
-        let forge_config = forge_config.clone();
+        let target_started = Instant::now();
 
-        let summary =
-            run_for_test_target(test_target, forge_config, &tests_filter, &package_name).await?;
-
-        match summary {
-            TestTargetRunResult::Ok(summary) => {
-                summaries.push(summary);
-            }
-            TestTargetRunResult::Interrupted(summary) => {
-                summaries.push(summary);
-                // Handle scenario for --exit-first flag.
-                // Because snforge runs test crates one by one synchronously.
-                // In case of test FAIL with --exit-first flag stops processing the next crates
-                break;
+        let summary = if interrupted {
+            // Handle scenario for --exit-first flag.
+            // Because snforge runs test crates one by one synchronously,
+            // tests of the next crates are reported as cancelled without running them
+            cancel_test_target(&test_target, &forge_config)?
+        } else {
+            match run_for_test_target(
+                test_target,
+                forge_config.clone(),
+                &tests_filter,
+                &package_name,
+            )
+            .await?
+            {
+                TestTargetRunResult::Ok(summary) => summary,
+                TestTargetRunResult::Interrupted(summary) => {
+                    interrupted = true;
+                    summary
+                }
             }
+        };
+
+        if let Some(junit_report) = junit_report.as_deref_mut() {
+            let partition = tests_filter.partition();
+            let not_run = partitioned_out
+                .into_iter()
+                .map(|name| {
+                    let reason = format!("Not in partition {}", partition.unwrap());
+                    (name, reason)
+                })
+                .collect();
+            junit_report.add_test_target(
+                &package_name,
+                &summary,
+                not_run,
+                target_started.elapsed(),
+            );
         }
+
+        summaries.push(summary);
     }
 
-    // TODO(#2574): Bring back "filtered out" number in tests summary when running with `--exact` flag
-    if let NameFilter::ExactMatch(_) = tests_filter.name_filter {
-        pretty_printing::print_test_summary(&summaries, None);
-    } else {
-        let filtered = all_tests - not_filtered;
-        pretty_printing::print_test_summary(&summaries, Some(filtered));
+    pretty_printing::print_test_summary(&summaries, filtered);
+
+    if forge_config.output_config.detailed_resources {
+        pretty_printing::print_total_resources(&total_resource_report(&summaries));
     }
 
     let any_fuzz_test_was_run = summaries.iter().any(|test_target_summary| {
@@ -192,5 +417,10 @@ pub async fn run_for_package(
         pretty_printing::print_test_seed(forge_config.test_runner_config.fuzzer_seed);
     }
 
+    if json {
+        emit_diagnostics()?;
+        Event::suite_finished(&package_name, &summaries, filtered, started.elapsed()).emit()?;
+    }
+
     Ok(summaries)
 }
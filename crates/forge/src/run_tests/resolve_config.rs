@@ -27,6 +27,7 @@ pub async fn resolve_config(
             config: TestCaseResolvedConfig {
                 available_gas: case.config.available_gas,
                 ignored: case.config.ignored,
+                ignore_reason: case.config.ignore_reason,
                 expected_result: case.config.expected_result,
                 fork_config: resolve_fork_config(
                     case.config.fork_config,
@@ -35,6 +36,10 @@ pub async fn resolve_config(
                 )
                 .await?,
                 fuzzer_config: case.config.fuzzer_config,
+                test_case_config: case.config.test_case_config,
+                available_steps: case.config.available_steps,
+                timeout: case.config.timeout,
+                serial: case.config.serial,
             },
         });
     }
@@ -52,10 +57,23 @@ async fn resolve_fork_config(
     block_number_map: &mut BlockNumberMap,
     fork_targets: &[ForkTarget],
 ) -> Result<Option<ResolvedForkConfig>> {
-    let Some(fc) = fork_config else {
-        return Ok(None);
+    // Attribute of the test (or its module) takes precedence over the default fork from Scarb.toml
+    let fc = match fork_config {
+        Some(RawForkConfig::Disabled) => return Ok(None),
+        Some(fc) => fc,
+        None => match fork_targets.iter().find(|fork| fork.is_default) {
+            Some(default_fork) => RawForkConfig::Named(default_fork.name.as_str().into()),
+            None => return Ok(None),
+        },
     };
 
+    let name = match &fc {
+        RawForkConfig::Inline(_) | RawForkConfig::Disabled => None,
+        RawForkConfig::Named(name)
+        | RawForkConfig::Overridden(OverriddenForkConfig { name, .. }) => {
+            Some(String::from(name.clone()))
+        }
+    };
     let raw_fork_params = replace_id_with_params(fc, fork_targets)?;
 
     let url = raw_fork_params.url;
@@ -72,9 +90,32 @@ async fn resolve_fork_config(
                 .get_latest_block_number(url.clone())
                 .await?
         }
+        BlockId::BlockTimestamp(timestamp) => {
+            block_number_map
+                .get_block_number_for_timestamp(url.clone(), timestamp)
+                .await?
+        }
     };
 
-    Ok(Some(ResolvedForkConfig { url, block_number }))
+    Ok(Some(ResolvedForkConfig {
+        name,
+        url,
+        block_number,
+    }))
+}
+
+/// Checks that the fork named in the config is defined in Scarb.toml, without fetching its block
+pub(crate) fn validate_fork_config(
+    fork_config: &RawForkConfig,
+    fork_targets: &[ForkTarget],
+) -> Result<()> {
+    match fork_config {
+        RawForkConfig::Inline(_) | RawForkConfig::Disabled => Ok(()),
+        RawForkConfig::Named(name)
+        | RawForkConfig::Overridden(OverriddenForkConfig { name, .. }) => {
+            get_fork_target_from_runner_config(fork_targets, name).map(|_| ())
+        }
+    }
 }
 
 fn get_fork_target_from_runner_config<'a>(
@@ -115,6 +156,7 @@ fn replace_id_with_params(
 
             Ok(InlineForkConfig { url, block })
         }
+        RawForkConfig::Disabled => unreachable!("Disabled fork config is never resolved"),
     }
 }
 
@@ -151,9 +193,14 @@ mod tests {
                 config: TestCaseConfig {
                     available_gas: None,
                     ignored: false,
+                    ignore_reason: None,
                     expected_result: ExpectedTestResult::Success,
                     fork_config: Some(RawForkConfig::Named("non_existent".into())),
                     fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
                 },
                 test_details: TestDetails {
                     sierra_entry_point_statement_idx: 100,
@@ -185,4 +232,44 @@ mod tests {
         .await
         .is_err());
     }
+
+    fn default_fork_target() -> ForkTarget {
+        ForkTarget {
+            is_default: true,
+            ..ForkTarget::new("DEFAULT", "https://default.com", "number", "120").unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_default_fork_without_attribute() {
+        let fork_config = resolve_fork_config(
+            None,
+            &mut BlockNumberMap::default(),
+            &[default_fork_target()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            fork_config,
+            Some(ResolvedForkConfig {
+                name: Some("DEFAULT".to_string()),
+                url: "https://default.com".parse().unwrap(),
+                block_number: BlockNumber(120),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn disabled_fork_overrides_default_fork() {
+        let fork_config = resolve_fork_config(
+            Some(RawForkConfig::Disabled),
+            &mut BlockNumberMap::default(),
+            &[default_fork_target()],
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(fork_config, None);
+    }
 }
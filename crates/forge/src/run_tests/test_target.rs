@@ -1,19 +1,28 @@
+use crate::json_output::{Event, TestFinished};
+use crate::progress::Progress;
 use anyhow::Result;
 use cairo_lang_runner::RunnerError;
 use forge_runner::{
     forge_config::ForgeConfig,
-    function_args, maybe_generate_coverage, maybe_save_trace_and_profile,
-    maybe_save_versioned_program,
+    function_args, maybe_save_trace_and_profile, maybe_save_versioned_program,
     package_tests::with_config_resolved::TestTargetWithResolvedConfig,
     printing::print_test_result,
     run_for_test_case,
+    running_tests::RunningTests,
     test_case_summary::{AnyTestCaseSummary, TestCaseSummary},
     test_target_summary::TestTargetSummary,
     TestCaseFilter,
 };
 use futures::{stream::FuturesUnordered, StreamExt};
-use std::{collections::HashMap, sync::Arc};
-use tokio::sync::mpsc::channel;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{
+    sync::{mpsc::channel, Mutex},
+    task::JoinHandle,
+};
 
 #[non_exhaustive]
 pub enum TestTargetRunResult {
@@ -37,6 +46,8 @@ pub async fn run_for_test_target(
     // As `spawn_blocking` can't be prematurely cancelled (refer: https://dtantsur.github.io/rust-openstack/tokio/task/fn.spawn_blocking.html),
     // a channel is used to signal the task that test processing is no longer necessary.
     let (send, mut rec) = channel(1);
+    // Test targets are run one after another, so `#[serial]` tests of different targets never overlap
+    let serial_lock = Arc::new(Mutex::new(()));
 
     let maybe_versioned_program_path = Arc::new(maybe_save_versioned_program(
         forge_config.output_config.execution_data_to_save,
@@ -51,16 +62,42 @@ pub async fn run_for_test_target(
         .map(|f| (f.id.id, f))
         .collect();
 
+    let fork_configs: HashMap<_, _> = tests
+        .test_cases
+        .iter()
+        .map(|case| (case.name.clone(), case.config.fork_config.clone()))
+        .collect();
+    let test_case_configs: HashMap<_, _> = tests
+        .test_cases
+        .iter()
+        .map(|case| (case.name.clone(), case.config.test_case_config.clone()))
+        .collect();
+
+    let json = forge_config.output_config.json;
+    let kind = tests.tests_location.test_kind();
+    let running_tests = RunningTests::default();
+    let progress = Progress::start(tests.test_cases.len(), running_tests.clone());
+
     for case in tests.test_cases {
         let case_name = case.name.clone();
 
+        if json {
+            Event::TestStarted {
+                name: &case_name,
+                kind,
+            }
+            .emit()?;
+        }
+
         if !tests_filter.should_be_run(&case) {
-            tasks.push(tokio::task::spawn(async {
+            let reason = case.config.ignore_reason.clone();
+            tasks.push(with_duration(tokio::task::spawn(async {
                 // TODO TestCaseType should also be encoded in the test case definition
                 Ok(AnyTestCaseSummary::Single(TestCaseSummary::Ignored {
                     name: case_name,
+                    reason,
                 }))
-            }));
+            })));
             continue;
         };
 
@@ -74,48 +111,66 @@ pub async fn run_for_test_target(
 
         let case = Arc::new(case);
 
-        tasks.push(run_for_test_case(
+        tasks.push(with_duration(run_for_test_case(
             args,
             case,
             casm_program.clone(),
             forge_config.clone(),
             maybe_versioned_program_path.clone(),
             send.clone(),
-        ));
+            serial_lock.clone(),
+            running_tests.clone(),
+        )));
     }
 
     let mut results = vec![];
-    let mut saved_trace_data_paths = vec![];
+    let mut durations = HashMap::new();
     let mut interrupted = false;
 
     while let Some(task) = tasks.next().await {
-        let result = task??;
-
-        print_test_result(&result, forge_config.output_config.detailed_resources);
-
-        let trace_path = maybe_save_trace_and_profile(
-            &result,
-            forge_config.output_config.execution_data_to_save,
-        )?;
-        if let Some(path) = trace_path {
-            saved_trace_data_paths.push(path);
+        let (result, duration) = task??;
+
+        durations.insert(result.name().to_string(), duration);
+
+        let fork_config = fork_configs.get(result.name()).and_then(Option::as_ref);
+        let test_case_config = test_case_configs
+            .get(result.name())
+            .and_then(Option::as_ref);
+
+        progress.test_finished(result.name(), || {
+            print_test_result(
+                &result,
+                forge_config.output_config.detailed_resources,
+                fork_config,
+                test_case_config,
+            );
+        });
+        if json {
+            Event::TestFinished(TestFinished::new(&result, kind, duration)).emit()?;
         }
 
-        if result.is_failed() && forge_config.test_runner_config.exit_first {
+        maybe_save_trace_and_profile(&result, &forge_config.output_config)?;
+
+        if result.is_failed() && forge_config.test_runner_config.exit_first && !interrupted {
             interrupted = true;
+            // Tests which have not started yet are skipped, the running ones are let finish
             rec.close();
+
+            if json {
+                Event::RunCancelled {
+                    name: result.name(),
+                }
+                .emit()?;
+            }
         }
 
         results.push(result);
     }
-
-    maybe_generate_coverage(
-        forge_config.output_config.execution_data_to_save,
-        &saved_trace_data_paths,
-    )?;
+    drop(progress);
 
     let summary = TestTargetSummary {
         test_case_summaries: results,
+        test_case_durations: durations,
     };
 
     if interrupted {
@@ -124,3 +179,55 @@ pub async fn run_for_test_target(
         Ok(TestTargetRunResult::Ok(summary))
     }
 }
+
+/// Reports all tests of a target which was not run because an earlier target failed with `--exit-first`
+pub fn cancel_test_target(
+    tests: &TestTargetWithResolvedConfig,
+    forge_config: &ForgeConfig,
+) -> Result<TestTargetSummary> {
+    let mut results = vec![];
+    let mut durations = HashMap::new();
+
+    for case in &tests.test_cases {
+        let result = AnyTestCaseSummary::Single(TestCaseSummary::Skipped {
+            name: case.name.clone(),
+        });
+
+        print_test_result(
+            &result,
+            forge_config.output_config.detailed_resources,
+            None,
+            None,
+        );
+        if forge_config.output_config.json {
+            let kind = tests.tests_location.test_kind();
+            Event::TestStarted {
+                name: &case.name,
+                kind,
+            }
+            .emit()?;
+            Event::TestFinished(TestFinished::new(&result, kind, Duration::ZERO)).emit()?;
+        }
+
+        durations.insert(case.name.clone(), Duration::ZERO);
+        results.push(result);
+    }
+
+    Ok(TestTargetSummary {
+        test_case_summaries: results,
+        test_case_durations: durations,
+    })
+}
+
+/// Measures the time from spawning the test case task until it finishes
+fn with_duration(
+    task: JoinHandle<Result<AnyTestCaseSummary>>,
+) -> JoinHandle<Result<(AnyTestCaseSummary, Duration)>> {
+    let started = Instant::now();
+
+    tokio::task::spawn(async move {
+        let result = task.await??;
+
+        Ok((result, started.elapsed()))
+    })
+}
@@ -1,39 +1,107 @@
 use super::package::RunForPackageArgs;
 use crate::{
-    block_number_map::BlockNumberMap, pretty_printing, run_tests::package::run_for_package,
-    scarb::build_artifacts_with_scarb, shared_cache::FailedTestsCache,
-    warn::warn_if_snforge_std_not_compatible, ColorOption, ExitStatus, TestArgs,
+    block_number_map::BlockNumberMap,
+    gas_snapshot::{gas_snapshot_path, load_baseline, GasSnapshot, GasSnapshotMode},
+    json_output::emit_diagnostics,
+    junit::JunitReport,
+    pretty_printing::{self, TestCounts},
+    run_report::RunReport,
+    run_tests::package::run_for_package,
+    scarb::{build_artifacts_with_scarb, should_compile_starknet_contract_target},
+    shared_cache::{LastRun, LastRunCache},
+    target_hashes::{all_tests_passed, TargetHashes},
+    test_order::TestOrdering,
+    ColorOption, ExitStatus, TestArgs,
 };
 use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use cheatnet::forking::snapshot::ForkDataMode;
 use forge_runner::{
-    build_trace_data::test_sierra_program_path::VERSIONED_PROGRAMS_DIR,
-    coverage_api::can_coverage_be_generated,
-    test_case_summary::{AnyTestCaseSummary, TestCaseSummary},
+    build_trace_data::{
+        save_trace_index, test_sierra_program_path::VERSIONED_PROGRAMS_DIR, trace_index_entries,
+    },
+    coverage_api::{
+        can_coverage_be_generated, clean_coverage_data, generate_coverage, COVERAGE_DATA_DIR,
+        COVERAGE_DIR, OUTPUT_FILE_NAME,
+    },
+    test_case_summary::AnyTestCaseSummary,
 };
-use forge_runner::{test_target_summary::TestTargetSummary, CACHE_DIR};
+use forge_runner::{test_target_summary::TestTargetSummary, CACHE_DIR, FUZZ_CORPUS_DIR};
+use itertools::Itertools;
 use scarb_api::{
-    metadata::{Metadata, MetadataCommandExt, PackageMetadata},
-    target_dir_for_workspace, ScarbCommand,
+    contract_size::contract_sizes,
+    get_contract_names,
+    metadata::{workspace_metadata, Metadata, PackageMetadata},
+    std_version::ensure_std_version_compatible,
+    target_dir_for_workspace,
 };
 use scarb_ui::args::PackagesFilter;
-use shared::consts::SNFORGE_TEST_FILTER;
+use semver::Version;
+use shared::{
+    consts::SNFORGE_TEST_FILTER,
+    print::{emit_warning, set_collect_diagnostics, set_human_output_to_stderr, DiagnosticCode},
+};
+use std::collections::BTreeMap;
 use std::env;
 
+/// Contracts are filtered separately for every package, the filter has to match a contract of any of them
+fn ensure_contracts_filter_matches(
+    scarb_metadata: &Metadata,
+    packages: &[PackageMetadata],
+    args: &TestArgs,
+) -> Result<()> {
+    let Some(contracts_filter) = &args.contracts_filter else {
+        return Ok(());
+    };
+
+    let use_test_target_contracts = !should_compile_starknet_contract_target(
+        &scarb_metadata.app_version_info.version,
+        args.no_optimization,
+    );
+    let contract_names: Vec<String> = packages
+        .iter()
+        .map(|package| {
+            get_contract_names(scarb_metadata, &package.id, None, use_test_target_contracts)
+        })
+        .flatten_ok()
+        .collect::<Result<_>>()?;
+
+    contracts_filter.ensure_matches_any(contract_names.iter().map(String::as_str))
+}
+
 #[allow(clippy::too_many_lines)]
-pub async fn run_for_workspace(args: TestArgs) -> Result<ExitStatus> {
+pub async fn run_for_workspace(args: TestArgs, jobs: usize) -> Result<ExitStatus> {
     match args.color {
         ColorOption::Always => env::set_var("CLICOLOR_FORCE", "1"),
         ColorOption::Never => env::set_var("CLICOLOR", "0"),
         ColorOption::Auto => (),
     }
 
-    let scarb_metadata = ScarbCommand::metadata().inherit_stderr().run()?;
+    // Keeps stdout free of anything but the JSON events, diagnostics are emitted as events too
+    set_human_output_to_stderr(args.json);
+    set_collect_diagnostics(args.json);
+
+    let fork_data = fork_data_mode(&args)?;
+    // A single seed is used for all packages, so it is enough to pass it to reproduce the whole run
+    let test_ordering = TestOrdering::new(args.test_order, args.shuffle_seed)?;
+    let fuzz_corpus_dir = args.fuzz_corpus_dir.clone().map(resolve_path).transpose()?;
+    let junit_path = args.junit_path.clone().map(resolve_path).transpose()?;
+    let coverage_path = args.coverage_path.clone().map(resolve_path).transpose()?;
+    let report_json_path = args.report_json.clone().map(resolve_path).transpose()?;
+
+    let scarb_metadata = workspace_metadata()?;
 
     if args.coverage {
         can_coverage_be_generated(&scarb_metadata)?;
     }
 
-    warn_if_snforge_std_not_compatible(&scarb_metadata)?;
+    ensure_std_version_compatible(
+        &scarb_metadata,
+        "snforge_std",
+        "snforge",
+        &Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+        args.no_version_check,
+    )?;
 
     let snforge_target_dir_path =
         target_dir_for_workspace(&scarb_metadata).join(&scarb_metadata.current_profile);
@@ -41,7 +109,12 @@ pub async fn run_for_workspace(args: TestArgs) -> Result<ExitStatus> {
     let packages: Vec<PackageMetadata> = args
         .packages_filter
         .match_many(&scarb_metadata)
-        .context("Failed to find any packages matching the specified filter")?;
+        .with_context(|| {
+            format!(
+                "Failed to find any packages matching the specified filter, available packages: {}",
+                workspace_member_names(&scarb_metadata)
+            )
+        })?;
 
     let filter = PackagesFilter::generate_for::<Metadata>(packages.iter());
 
@@ -60,16 +133,71 @@ pub async fn run_for_workspace(args: TestArgs) -> Result<ExitStatus> {
         &scarb_metadata.app_version_info.version,
         args.no_optimization,
     )?;
+    ensure_contracts_filter_matches(&scarb_metadata, &packages, &args)?;
 
-    let mut block_number_map = BlockNumberMap::default();
     let mut all_failed_tests = vec![];
+    let mut all_tests_count = 0;
 
     let workspace_root = &scarb_metadata.workspace.root;
     let cache_dir = workspace_root.join(CACHE_DIR);
+    let mut block_number_map = if fork_data.as_ref().is_some_and(ForkDataMode::is_replay) {
+        BlockNumberMap::offline()
+    } else {
+        BlockNumberMap::default()
+    }
+    .with_timestamps_cache(&cache_dir)?;
     let versioned_programs_dir = workspace_root.join(VERSIONED_PROGRAMS_DIR);
+    let fuzz_corpus_dir = fuzz_corpus_dir.unwrap_or_else(|| workspace_root.join(FUZZ_CORPUS_DIR));
+    let coverage_data_dir = target_dir_for_workspace(&scarb_metadata).join(COVERAGE_DATA_DIR);
+    let coverage_path =
+        coverage_path.unwrap_or_else(|| workspace_root.join(COVERAGE_DIR).join(OUTPUT_FILE_NAME));
+    clean_coverage_data(&coverage_data_dir)?;
+    let mut coverage = false;
+    // Traces of all packages are saved to the same directory, so a single index covers all of them
+    let mut trace_index: Option<BTreeMap<String, String>> = None;
+
+    let last_run_cache = LastRunCache::new(workspace_root);
+    let previous_run = if args.rerun_failed {
+        let previous_run = last_run_cache.load()?;
+        if previous_run.is_none() {
+            emit_warning(
+                DiagnosticCode::MissingPreviousRun,
+                "No results of the previous run found, running all tests",
+            );
+        }
+        previous_run
+    } else {
+        None
+    };
+    let mut target_hashes = if args.only_changed {
+        let saved_target_hashes = TargetHashes::load(workspace_root)?;
+        if saved_target_hashes.is_none() {
+            emit_warning(
+                DiagnosticCode::MissingPreviousRun,
+                "No hashes of the previous run found, running all test targets",
+            );
+        }
+        Some(saved_target_hashes.unwrap_or_default())
+    } else {
+        None
+    };
+    let gas_snapshot_path = gas_snapshot_path(workspace_root, args.tracked_resource);
+    let gas_snapshot_baseline = if args.gas_snapshot == Some(GasSnapshotMode::Check) {
+        Some(load_baseline(&gas_snapshot_path, args.tracked_resource)?)
+    } else {
+        None
+    };
+    let mut gas_snapshot = GasSnapshot::new(args.tracked_resource);
+    let mut all_tests_run = packages.len() == scarb_metadata.workspace.members.len();
+    let mut last_run = LastRun::default();
+    let mut test_durations = vec![];
+    let mut package_test_counts = vec![];
+    let mut junit_report = junit_path.as_ref().map(|_| JunitReport::default());
+    let mut run_report = report_json_path.as_ref().map(|_| RunReport::default());
 
     for package in packages {
         env::set_current_dir(&package.root)?;
+        let package_name = package.name.clone();
 
         let args = RunForPackageArgs::build(
             package,
@@ -78,42 +206,137 @@ pub async fn run_for_workspace(args: TestArgs) -> Result<ExitStatus> {
             &cache_dir,
             &snforge_target_dir_path,
             versioned_programs_dir.clone(),
+            coverage_data_dir.clone(),
+            fork_data.clone(),
+            fuzz_corpus_dir.clone(),
+            previous_run.as_ref(),
+            target_hashes.as_ref(),
+            test_ordering,
         )?;
 
-        let tests_file_summaries = run_for_package(args, &mut block_number_map).await?;
+        let package_target_hashes = args.target_hashes.clone();
+        let runs_all_tests = args.tests_filter.runs_all_tests();
+        all_tests_run &= runs_all_tests && args.unchanged_targets.is_empty();
+        let execution_data_to_save = args.forge_config.output_config.execution_data_to_save;
+        coverage |= execution_data_to_save.coverage;
+        let package_contract_sizes = if run_report.is_some() {
+            contract_sizes(
+                args.forge_config
+                    .test_runner_config
+                    .contracts_data
+                    .artifacts(),
+            )?
+        } else {
+            vec![]
+        };
+
+        let tests_file_summaries =
+            run_for_package(args, &mut block_number_map, junit_report.as_mut()).await?;
+
+        if execution_data_to_save.trace || execution_data_to_save.profile {
+            trace_index
+                .get_or_insert_with(BTreeMap::new)
+                .extend(trace_index_entries(&tests_file_summaries));
+        }
+        last_run.record_package(&package_name, &tests_file_summaries);
+        if let Some(run_report) = &mut run_report {
+            run_report.add_package(&package_name, &tests_file_summaries, package_contract_sizes);
+        }
+        if let Some(target_hashes) = &mut target_hashes {
+            for ((location, hash), summary) in
+                package_target_hashes.into_iter().zip(&tests_file_summaries)
+            {
+                let all_passed = runs_all_tests && all_tests_passed(summary);
+                target_hashes.record(&package_name, location, hash, all_passed);
+            }
+        }
+        gas_snapshot.record(&tests_file_summaries);
+        test_durations.extend(pretty_printing::test_durations(&tests_file_summaries));
+        package_test_counts.push((package_name, TestCounts::new(&tests_file_summaries)));
+        all_tests_count += tests_file_summaries
+            .iter()
+            .map(|summary| summary.test_case_summaries.len())
+            .sum::<usize>();
 
         all_failed_tests.extend(extract_failed_tests(tests_file_summaries));
     }
 
-    FailedTestsCache::new(&cache_dir).save_failed_tests(&all_failed_tests)?;
+    last_run_cache.save(&last_run)?;
+    if let Some(trace_index) = &trace_index {
+        save_trace_index(trace_index)?;
+    }
+    if let Some(target_hashes) = &target_hashes {
+        target_hashes.save(workspace_root)?;
+    }
+    if coverage {
+        // Data of all packages is saved before, so a single report covers the whole workspace
+        generate_coverage(&coverage_data_dir, &coverage_path, workspace_root)?;
+    }
+    if let (Some(path), Some(junit_report)) = (&junit_path, &junit_report) {
+        junit_report.write(path)?;
+    }
+    if let (Some(path), Some(run_report)) = (&report_json_path, &run_report) {
+        run_report.write(path)?;
+    }
 
+    pretty_printing::print_packages_summary(&package_test_counts);
+    pretty_printing::print_slowest_tests(test_durations, args.durations);
+    pretty_printing::print_jobs(jobs);
     pretty_printing::print_latest_blocks_numbers(block_number_map.get_url_to_latest_block_number());
     pretty_printing::print_failures(&all_failed_tests);
 
+    let mut gas_regressed = false;
+    match (args.gas_snapshot, &gas_snapshot_baseline) {
+        (Some(GasSnapshotMode::Write), _) => {
+            // Tests which were not run keep their entries
+            let gas_snapshot = match GasSnapshot::load(&gas_snapshot_path)? {
+                Some(previous) if !all_tests_run => gas_snapshot.merged_over(previous),
+                _ => gas_snapshot,
+            };
+            gas_snapshot.save(&gas_snapshot_path)?;
+        }
+        (Some(GasSnapshotMode::Check), Some(baseline)) => {
+            let comparison = gas_snapshot.compare(baseline, args.tolerance);
+            pretty_printing::print_gas_snapshot_comparison(&comparison, args.tolerance);
+            gas_regressed = !comparison.regressions.is_empty();
+        }
+        _ => {}
+    }
+
     if args.exact {
         unset_forge_test_filter();
     }
+    if args.json {
+        // Diagnostics reported after the last suite finished
+        emit_diagnostics()?;
+    }
 
-    Ok(if all_failed_tests.is_empty() {
-        ExitStatus::Success
-    } else {
+    Ok(if !all_failed_tests.is_empty() || gas_regressed {
         ExitStatus::Failure
+    } else if args.deny_no_matches && all_tests_count == 0 {
+        ExitStatus::NoTestsMatched
+    } else {
+        ExitStatus::Success
     })
 }
 
+fn workspace_member_names(scarb_metadata: &Metadata) -> String {
+    scarb_metadata
+        .packages
+        .iter()
+        .filter(|package| scarb_metadata.workspace.members.contains(&package.id))
+        .map(|package| package.name.as_str())
+        .sorted()
+        .join(", ")
+}
+
 fn extract_failed_tests(
     tests_summaries: Vec<TestTargetSummary>,
 ) -> impl Iterator<Item = AnyTestCaseSummary> {
     tests_summaries
         .into_iter()
         .flat_map(|test_file_summary| test_file_summary.test_case_summaries)
-        .filter(|test_case_summary| {
-            matches!(
-                test_case_summary,
-                AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed { .. })
-                    | AnyTestCaseSummary::Single(TestCaseSummary::Failed { .. })
-            )
-        })
+        .filter(AnyTestCaseSummary::is_failed)
 }
 
 fn set_forge_test_filter(test_filter: String) {
@@ -123,3 +346,18 @@ fn set_forge_test_filter(test_filter: String) {
 fn unset_forge_test_filter() {
     env::remove_var(SNFORGE_TEST_FILTER);
 }
+
+fn fork_data_mode(args: &TestArgs) -> Result<Option<ForkDataMode>> {
+    Ok(match (&args.record_fork_data, &args.replay_fork_data) {
+        (Some(dir), _) => Some(ForkDataMode::Record(resolve_path(dir.clone())?)),
+        (None, Some(dir)) => Some(ForkDataMode::Replay(resolve_path(dir.clone())?)),
+        (None, None) => None,
+    })
+}
+
+fn resolve_path(path: Utf8PathBuf) -> Result<Utf8PathBuf> {
+    // Tests of each package are run from its root, so relative paths are resolved beforehand
+    let current_dir = Utf8PathBuf::try_from(env::current_dir()?)?;
+
+    Ok(current_dir.join(path))
+}
@@ -9,6 +9,7 @@ use scarb_api::ScarbCommand;
 use scarb_metadata::{PackageMetadata, TargetMetadata};
 use scarb_ui::args::{FeaturesSpec, PackagesFilter};
 use semver::Version;
+use shared::print::is_human_output_to_stderr;
 use std::collections::HashMap;
 use std::fs::read_to_string;
 use std::io::ErrorKind;
@@ -55,8 +56,17 @@ pub fn build_artifacts_with_scarb(
     Ok(())
 }
 
+/// Scarb output is meant for humans, so it follows the rest of it to stderr when requested
+fn scarb_command_with_stdio() -> ScarbCommand {
+    let mut command = ScarbCommand::new_with_stdio();
+    if is_human_output_to_stderr() {
+        command.stdout_to_stderr();
+    }
+    command
+}
+
 fn build_contracts_with_scarb(filter: PackagesFilter, features: FeaturesSpec) -> Result<()> {
-    ScarbCommand::new_with_stdio()
+    scarb_command_with_stdio()
         .arg("build")
         .packages_filter(filter)
         .features(features)
@@ -66,7 +76,7 @@ fn build_contracts_with_scarb(filter: PackagesFilter, features: FeaturesSpec) ->
 }
 
 fn build_test_artifacts_with_scarb(filter: PackagesFilter, features: FeaturesSpec) -> Result<()> {
-    ScarbCommand::new_with_stdio()
+    scarb_command_with_stdio()
         .arg("build")
         .arg("--test")
         .packages_filter(filter)
@@ -148,6 +158,7 @@ mod tests {
     use cheatnet::runtime_extensions::forge_config_extension::config::BlockId;
     use configuration::load_package_config;
     use indoc::{formatdoc, indoc};
+    use runtime::starknet::context::BlockContextVersion;
     use scarb_api::metadata::MetadataCommandExt;
     use scarb_metadata::PackageId;
     use std::env;
@@ -194,7 +205,7 @@ mod tests {
                 [[tool.snforge.fork]]
                 name = "THIRD_FORK_NAME"
                 url = "http://some.rpc.url"
-                block_id.hash = "10"
+                block_id.hash = "0xA"
 
                 [[tool.snforge.fork]]
                 name = "FOURTH_FORK_NAME"
@@ -231,7 +242,7 @@ mod tests {
                 fork: vec![
                     ForkTarget::new("FIRST_FORK_NAME", "http://some.rpc.url", "number", "1",)
                         .unwrap(),
-                    ForkTarget::new("SECOND_FORK_NAME", "http://some.rpc.url", "hash", "10",)
+                    ForkTarget::new("SECOND_FORK_NAME", "http://some.rpc.url", "hash", "0xA",)
                         .unwrap(),
                     ForkTarget::new("THIRD_FORK_NAME", "http://some.rpc.url", "hash", "0xa",)
                         .unwrap(),
@@ -241,6 +252,10 @@ mod tests {
                 fuzzer_runs: None,
                 fuzzer_seed: None,
                 max_n_steps: None,
+                timeout: None,
+                max_gas: None,
+                block_context: None,
+                env: HashMap::new(),
                 detailed_resources: false,
                 save_trace_data: false,
                 build_profile: false,
@@ -363,6 +378,89 @@ mod tests {
         assert!(format!("{err:?}").contains("block_id should be set once per fork"));
     }
 
+    #[test]
+    fn get_forge_config_for_package_with_default_fork() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.fork]]
+            name = "FIRST_FORK_NAME"
+            url = "http://some.rpc.url"
+            block_id.number = "1"
+
+            [[tool.snforge.fork]]
+            name = "SECOND_FORK_NAME"
+            url = "http://some.rpc.url"
+            block_id.number = "2"
+            default = true
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+        let config = load_package_config::<ForgeConfigFromScarb>(
+            &scarb_metadata,
+            &scarb_metadata.workspace.members[0],
+        )
+        .unwrap();
+
+        let default_forks: Vec<_> = config
+            .fork
+            .iter()
+            .filter(|fork| fork.is_default)
+            .map(|fork| fork.name.as_str())
+            .collect();
+        assert_eq!(default_forks, vec!["SECOND_FORK_NAME"]);
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_multiple_default_forks() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [[tool.snforge.fork]]
+            name = "FIRST_FORK_NAME"
+            url = "http://some.rpc.url"
+            block_id.number = "1"
+            default = true
+
+            [[tool.snforge.fork]]
+            name = "SECOND_FORK_NAME"
+            url = "http://some.rpc.url"
+            block_id.number = "2"
+            default = true
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+        let err = load_package_config::<ForgeConfigFromScarb>(
+            &scarb_metadata,
+            &scarb_metadata.workspace.members[0],
+        )
+        .unwrap_err();
+
+        assert!(format!("{err:?}").contains(
+            "Only one fork can be marked as default, found: FIRST_FORK_NAME, SECOND_FORK_NAME"
+        ));
+    }
+
     #[test]
     fn get_forge_config_for_package_fails_on_wrong_block_id() {
         let temp = setup_package("simple_package");
@@ -392,7 +490,7 @@ mod tests {
         )
         .unwrap_err();
         assert!(
-            format!("{err:?}").contains("block_id = wrong_variant is not valid. Possible values are = \"number\", \"hash\" and \"tag\"")
+            format!("{err:?}").contains("block_id = wrong_variant is not valid. Possible values are = \"number\", \"hash\", \"tag\" and \"timestamp\"")
         );
     }
 
@@ -458,6 +556,69 @@ mod tests {
         assert_eq!(forge_config.fork[0].block_id, BlockId::BlockTag);
     }
 
+    #[test]
+    fn get_forge_config_for_package_with_block_context() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [tool.snforge]
+            block_context = "0.13.0"
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let forge_config = load_package_config::<ForgeConfigFromScarb>(
+            &scarb_metadata,
+            &scarb_metadata.workspace.members[0],
+        )
+        .unwrap();
+        assert_eq!(
+            forge_config.block_context,
+            Some(BlockContextVersion::V0_13_0)
+        );
+    }
+
+    #[test]
+    fn get_forge_config_for_package_fails_on_unknown_block_context() {
+        let temp = setup_package("simple_package");
+        let content = indoc!(
+            r#"
+            [package]
+            name = "simple_package"
+            version = "0.1.0"
+
+            [tool.snforge]
+            block_context = "0.12.3"
+            "#
+        );
+        temp.child("Scarb.toml").write_str(content).unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+
+        let err = load_package_config::<ForgeConfigFromScarb>(
+            &scarb_metadata,
+            &scarb_metadata.workspace.members[0],
+        )
+        .unwrap_err();
+        assert!(format!("{err:?}").contains(
+            "Unknown block context version = 0.12.3, supported versions are: 0.13.0, 0.13.1, 0.13.2"
+        ));
+    }
+
     #[test]
     fn get_forge_config_resolves_env_variables() {
         let temp = setup_package("simple_package");
@@ -502,6 +663,10 @@ mod tests {
                 fuzzer_runs: None,
                 fuzzer_seed: None,
                 max_n_steps: None,
+                timeout: None,
+                max_gas: None,
+                block_context: None,
+                env: HashMap::new(),
                 detailed_resources: false,
                 save_trace_data: false,
                 build_profile: false,
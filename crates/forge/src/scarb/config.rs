@@ -1,10 +1,12 @@
 use anyhow::{anyhow, bail, Result};
 use cheatnet::runtime_extensions::forge_config_extension::config::BlockId;
 use itertools::Itertools;
+use runtime::starknet::context::BlockContextVersion;
 use serde::Deserialize;
+use shared::block_id::BlockIdArg;
 use std::{
     collections::{HashMap, HashSet},
-    num::NonZeroU32,
+    num::{NonZeroU32, NonZeroU64},
 };
 use url::Url;
 
@@ -15,11 +17,18 @@ pub const SCARB_MANIFEST_TEMPLATE_CONTENT: &str = r#"
 # exit_first = true                                          # Stop tests execution immediately upon the first failure
 # fuzzer_runs = 1234                                         # Number of runs of the random fuzzer
 # fuzzer_seed = 1111                                         # Seed for the random fuzzer
+# timeout = 120                                              # Time limit of a single test run in seconds
+# max_gas = 1000000                                          # Tests using more gas fail, unless they have the `#[available_gas]` attribute
+# block_context = "0.13.2"                                   # Starknet version whose versioned constants tests are run with
+
+# [tool.snforge.env]                                         # Environment variables read with the `var` cheatcode
+# SOME_VARIABLE = "0x123"                                    # Overrides the process environment, is overridden by `--env`
 
 # [[tool.snforge.fork]]                                      # Used for fork testing
 # name = "SOME_NAME"                                         # Fork name
 # url = "http://your.rpc.url"                                # Url of the RPC provider
 # block_id.tag = "latest"                                    # Block to fork from (block tag)
+# default = true                                             # Run tests without the `#[fork]` attribute on this fork
 
 # [[tool.snforge.fork]]
 # name = "SOME_SECOND_NAME"
@@ -62,6 +71,14 @@ pub struct ForgeConfigFromScarb {
     pub fork: Vec<ForkTarget>,
     /// Limit of steps
     pub max_n_steps: Option<u32>,
+    /// Time limit of a single test run in seconds
+    pub timeout: Option<NonZeroU64>,
+    /// Gas limit of tests without the `#[available_gas]` attribute
+    pub max_gas: Option<NonZeroU64>,
+    /// Starknet version whose versioned constants and default block info tests are run with
+    pub block_context: Option<BlockContextVersion>,
+    /// Values of environment variables read with the `var` cheatcode, overriding the process environment
+    pub env: HashMap<String, String>,
 }
 
 #[non_exhaustive]
@@ -70,33 +87,38 @@ pub struct ForkTarget {
     pub name: String,
     pub url: Url,
     pub block_id: BlockId,
+    /// Tests without the `#[fork]` attribute run on the default fork
+    pub is_default: bool,
 }
 
 impl ForkTarget {
     pub fn new(name: &str, url: &str, block_id_type: &str, block_id_value: &str) -> Result<Self> {
         let parsed_url = Url::parse(url).map_err(|_| anyhow!("Failed to parse fork url"))?;
         let block_id = match block_id_type {
-            "number" => BlockId::BlockNumber(
-                block_id_value
-                    .parse()
-                    .map_err(|_| anyhow!("Failed to parse block number"))?,
-            ),
-            "hash" => BlockId::BlockHash(
-                block_id_value
-                    .parse()
-                    .map_err(|_| anyhow!("Failed to parse block hash"))?,
-            ),
-            "tag" => match block_id_value {
-                "latest" => BlockId::BlockTag,
+            "number" => match block_id_value.parse()? {
+                BlockIdArg::Number(number) => BlockId::BlockNumber(number),
+                _ => bail!("block_id.number = {block_id_value} is not a block number, it must be a decimal u64"),
+            },
+            "hash" => match block_id_value.parse()? {
+                BlockIdArg::Hash(hash) => BlockId::BlockHash(hash),
+                _ => bail!("block_id.hash = {block_id_value} is not a block hash, it must be a 0x prefixed hex"),
+            },
+            "tag" => match block_id_value.parse() {
+                Ok(BlockIdArg::Latest) => BlockId::BlockTag,
                 _ => bail!("block_id.tag can only be equal to latest"),
             },
-            block_id_key => bail!("block_id = {block_id_key} is not valid. Possible values are = \"number\", \"hash\" and \"tag\""),
+            "timestamp" => match block_id_value.parse() {
+                Ok(timestamp) => BlockId::BlockTimestamp(timestamp),
+                Err(_) => bail!("block_id.timestamp = {block_id_value} is not a unix timestamp, it must be a decimal u64"),
+            },
+            block_id_key => bail!("block_id = {block_id_key} is not valid. Possible values are = \"number\", \"hash\", \"tag\" and \"timestamp\""),
         };
 
         Ok(Self {
             name: name.to_string(),
             url: parsed_url,
             block_id,
+            is_default: false,
         })
     }
 }
@@ -129,6 +151,15 @@ pub(crate) struct RawForgeConfig {
     pub fork: Vec<RawForkTarget>,
     /// Limit of steps
     pub max_n_steps: Option<u32>,
+    /// Time limit of a single test run in seconds
+    pub timeout: Option<NonZeroU64>,
+    /// Gas limit of tests without the `#[available_gas]` attribute
+    pub max_gas: Option<NonZeroU64>,
+    /// Starknet version whose versioned constants and default block info tests are run with
+    pub block_context: Option<String>,
+    #[serde(default)]
+    /// Values of environment variables read with the `var` cheatcode
+    pub env: HashMap<String, String>,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Default, Clone)]
@@ -136,6 +167,8 @@ pub(crate) struct RawForkTarget {
     pub name: String,
     pub url: String,
     pub block_id: HashMap<String, String>,
+    #[serde(default)]
+    pub default: bool,
 }
 
 fn validate_raw_fork_config(raw_config: RawForgeConfig) -> Result<RawForgeConfig> {
@@ -155,6 +188,19 @@ fn validate_raw_fork_config(raw_config: RawForgeConfig) -> Result<RawForgeConfig
             _ => bail!("block_id should be set once per fork"),
         })?;
 
+    let default_forks: Vec<_> = forks
+        .iter()
+        .filter(|fork| fork.default)
+        .map(|fork| fork.name.as_str())
+        .collect();
+
+    if default_forks.len() > 1 {
+        bail!(
+            "Only one fork can be marked as default, found: {}",
+            default_forks.join(", ")
+        );
+    }
+
     Ok(raw_config)
 }
 
@@ -169,12 +215,15 @@ impl TryFrom<RawForgeConfig> for ForgeConfigFromScarb {
             let (block_id_type, block_id_value) =
                 raw_fork_target.block_id.iter().exactly_one().unwrap();
 
-            fork_targets.push(ForkTarget::new(
-                raw_fork_target.name.as_str(),
-                raw_fork_target.url.as_str(),
-                block_id_type,
-                block_id_value,
-            )?);
+            fork_targets.push(ForkTarget {
+                is_default: raw_fork_target.default,
+                ..ForkTarget::new(
+                    raw_fork_target.name.as_str(),
+                    raw_fork_target.url.as_str(),
+                    block_id_type,
+                    block_id_value,
+                )?
+            });
         }
 
         Ok(ForgeConfigFromScarb {
@@ -187,6 +236,10 @@ impl TryFrom<RawForgeConfig> for ForgeConfigFromScarb {
             coverage: value.coverage,
             fork: fork_targets,
             max_n_steps: value.max_n_steps,
+            timeout: value.timeout,
+            max_gas: value.max_gas,
+            block_context: value.block_context.as_deref().map(str::parse).transpose()?,
+            env: value.env,
         })
     }
 }
@@ -274,7 +327,8 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Failed to parse block number"
+            "Invalid block id = invalid_number, the value is not recognized. \
+            Possible values are latest, pending, block number (decimal u64) and block hash (0x prefixed hex)"
         );
     }
 
@@ -289,7 +343,67 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err().to_string(),
-            "Failed to parse block hash"
+            "Invalid block id = invalid_hash, the value is not recognized. \
+            Possible values are latest, pending, block number (decimal u64) and block hash (0x prefixed hex)"
+        );
+    }
+
+    #[test]
+    fn test_fork_target_new_hash_without_prefix() {
+        let result = ForkTarget::new("TestFork", "http://example.com", "hash", "abc");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid block id = abc, block hash must be prefixed with 0x. \
+            Possible values are latest, pending, block number (decimal u64) and block hash (0x prefixed hex)"
+        );
+    }
+
+    #[test]
+    fn test_fork_target_new_number_as_hash() {
+        let result = ForkTarget::new("TestFork", "http://example.com", "hash", "10");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "block_id.hash = 10 is not a block hash, it must be a 0x prefixed hex"
+        );
+    }
+
+    #[test]
+    fn test_fork_target_new_hash_as_number() {
+        let result = ForkTarget::new("TestFork", "http://example.com", "number", "0x10");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "block_id.number = 0x10 is not a block number, it must be a decimal u64"
+        );
+    }
+
+    #[test]
+    fn test_fork_target_new_pending_tag() {
+        let result = ForkTarget::new("TestFork", "http://example.com", "tag", "pending");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "block_id.tag can only be equal to latest"
+        );
+    }
+
+    #[test]
+    fn test_fork_target_new_valid_timestamp() {
+        let fork_target =
+            ForkTarget::new("TestFork", "http://example.com", "timestamp", "1700000000").unwrap();
+
+        assert_eq!(fork_target.block_id, BlockId::BlockTimestamp(1_700_000_000));
+    }
+
+    #[test]
+    fn test_fork_target_new_invalid_timestamp() {
+        let result = ForkTarget::new("TestFork", "http://example.com", "timestamp", "2024-01-01");
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "block_id.timestamp = 2024-01-01 is not a unix timestamp, it must be a decimal u64"
         );
     }
 }
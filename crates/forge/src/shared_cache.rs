@@ -1,48 +1,216 @@
-use anyhow::Result;
-use camino::Utf8PathBuf;
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
 use forge_runner::test_case_summary::AnyTestCaseSummary;
-use std::fs::File;
-use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use forge_runner::test_target_summary::TestTargetSummary;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::ErrorKind;
 
-#[derive(Debug, PartialEq, Default, Clone)]
-pub struct FailedTestsCache {
+pub const LAST_RUN_FILE: &str = ".snfoundry/last_run.json";
+
+/// Results of the last `snforge test` run, persisted in the workspace
+#[derive(Debug, Clone)]
+pub struct LastRunCache {
     cache_file: Utf8PathBuf,
 }
 
-const FILE_WITH_PREV_TESTS_FAILED: &str = ".prev_tests_failed";
+#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+pub struct LastRun {
+    pub tests: Vec<TestRecord>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct TestRecord {
+    pub package: String,
+    pub name: String,
+    pub status: TestStatus,
+    pub duration_ms: u64,
+    /// Reason given in `#[ignore("...")]` for ignored tests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_reason: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+    /// Cancelled after a failure with `--exit-first`
+    Cancelled,
+}
 
-impl FailedTestsCache {
-    pub fn new(cache_dir: &Utf8PathBuf) -> Self {
+impl LastRunCache {
+    #[must_use]
+    pub fn new(workspace_root: &Utf8Path) -> Self {
         Self {
-            cache_file: cache_dir.join(FILE_WITH_PREV_TESTS_FAILED),
+            cache_file: workspace_root.join(LAST_RUN_FILE),
         }
     }
 
-    pub fn load(&self) -> Result<Vec<String>> {
-        let file = match File::open(&self.cache_file) {
-            Ok(file) => file,
-            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+    /// Returns `None` if no run was recorded yet
+    pub fn load(&self) -> Result<Option<LastRun>> {
+        let content = match fs::read_to_string(&self.cache_file) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
             Err(err) => Err(err)?,
         };
-        let buf: BufReader<File> = BufReader::new(file);
 
-        let tests = buf.lines().collect::<Result<Vec<_>, _>>()?;
+        let last_run = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", self.cache_file))?;
+
+        Ok(Some(last_run))
+    }
 
-        Ok(tests)
+    pub fn save(&self, last_run: &LastRun) -> Result<()> {
+        fs::create_dir_all(self.cache_file.parent().unwrap())?;
+        fs::write(&self.cache_file, serde_json::to_string_pretty(last_run)?)?;
+
+        Ok(())
     }
+}
 
-    pub fn save_failed_tests(&self, all_failed_tests: &[AnyTestCaseSummary]) -> Result<()> {
-        std::fs::create_dir_all(self.cache_file.parent().unwrap())?;
+impl LastRun {
+    /// Records results of the tests run in the package
+    pub fn record_package(&mut self, package: &str, summaries: &[TestTargetSummary]) {
+        for summary in summaries {
+            for test_case_summary in &summary.test_case_summaries {
+                let name = test_case_summary.name();
+                let status = TestStatus::from_summary(test_case_summary);
+                let duration_ms = summary.test_case_durations.get(name).map_or(0, |duration| {
+                    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+                });
 
-        let file = File::create(&self.cache_file)?;
+                self.tests.push(TestRecord {
+                    package: package.to_string(),
+                    name: name.to_string(),
+                    status,
+                    duration_ms,
+                    ignore_reason: test_case_summary.ignore_reason().map(String::from),
+                });
+            }
+        }
+    }
+
+    /// Returns names of tests which failed or were cancelled before their result was known
+    #[must_use]
+    pub fn tests_to_rerun(&self, package: &str) -> Vec<String> {
+        self.tests
+            .iter()
+            .filter(|test| {
+                test.package == package
+                    && matches!(test.status, TestStatus::Failed | TestStatus::Cancelled)
+            })
+            .map(|test| test.name.clone())
+            .collect()
+    }
+}
 
-        let mut file = BufWriter::new(file);
+impl TestStatus {
+    fn from_summary(summary: &AnyTestCaseSummary) -> Self {
+        if summary.is_passed() {
+            Self::Passed
+        } else if summary.is_failed() {
+            Self::Failed
+        } else if summary.is_ignored() {
+            Self::Ignored
+        } else {
+            Self::Cancelled
+        }
+    }
+}
 
-        for line in all_failed_tests {
-            let name = line.name().unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
 
-            writeln!(file, "{name}")?;
+    fn record(package: &str, name: &str, status: TestStatus) -> TestRecord {
+        TestRecord {
+            package: package.to_string(),
+            name: name.to_string(),
+            status,
+            duration_ms: 10,
+            ignore_reason: None,
         }
-        Ok(())
+    }
+
+    #[test]
+    fn load_without_file() {
+        let temp = TempDir::new().unwrap();
+        let cache = LastRunCache::new(Utf8Path::from_path(temp.path()).unwrap());
+
+        assert_eq!(cache.load().unwrap(), None);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let temp = TempDir::new().unwrap();
+        let cache = LastRunCache::new(Utf8Path::from_path(temp.path()).unwrap());
+        let last_run = LastRun {
+            tests: vec![
+                record("pkg", "pkg::test_a", TestStatus::Passed),
+                record("pkg", "pkg::test_b", TestStatus::Failed),
+            ],
+        };
+
+        cache.save(&last_run).unwrap();
+
+        assert!(temp.path().join(LAST_RUN_FILE).is_file());
+        assert_eq!(cache.load().unwrap(), Some(last_run));
+    }
+
+    #[test]
+    fn serialized_format() {
+        let last_run = LastRun {
+            tests: vec![record("pkg", "pkg::test_a", TestStatus::Failed)],
+        };
+
+        assert_eq!(
+            serde_json::to_value(&last_run).unwrap(),
+            serde_json::json!({
+                "tests": [{
+                    "package": "pkg",
+                    "name": "pkg::test_a",
+                    "status": "failed",
+                    "duration_ms": 10
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn load_invalid_file() {
+        let temp = TempDir::new().unwrap();
+        let cache = LastRunCache::new(Utf8Path::from_path(temp.path()).unwrap());
+        fs::create_dir_all(temp.path().join(".snfoundry")).unwrap();
+        fs::write(temp.path().join(LAST_RUN_FILE), "not json").unwrap();
+
+        let err = cache.load().unwrap_err();
+
+        assert!(err.to_string().starts_with("Failed to parse"));
+    }
+
+    #[test]
+    fn tests_to_rerun_of_package() {
+        let last_run = LastRun {
+            tests: vec![
+                record("pkg", "pkg::test_a", TestStatus::Passed),
+                record("pkg", "pkg::test_b", TestStatus::Failed),
+                record("pkg", "pkg::test_c", TestStatus::Ignored),
+                record("pkg", "pkg::test_d", TestStatus::Cancelled),
+                record("other", "other::test_b", TestStatus::Failed),
+            ],
+        };
+
+        assert_eq!(
+            last_run.tests_to_rerun("pkg"),
+            vec!["pkg::test_b".to_string(), "pkg::test_d".to_string()]
+        );
+        assert_eq!(
+            last_run.tests_to_rerun("other"),
+            vec!["other::test_b".to_string()]
+        );
+        assert!(last_run.tests_to_rerun("missing").is_empty());
     }
 }
@@ -0,0 +1,233 @@
+use crate::scarb::config::ForgeConfigFromScarb;
+use crate::TestArgs;
+use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use forge_runner::package_tests::raw::TestTargetRaw;
+use forge_runner::package_tests::TestTargetLocation;
+use forge_runner::test_target_summary::TestTargetSummary;
+use scarb_api::StarknetContractArtifacts;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+
+pub const TARGET_HASHES_FILE: &str = ".snfoundry/target_hashes.json";
+
+/// Hashes of the test targets whose tests all passed in the previous runs, used by `--only-changed`
+#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+pub struct TargetHashes {
+    /// Hash of the compiled tests and the contracts, by `<package>::<src|tests>`
+    pub targets: BTreeMap<String, String>,
+}
+
+impl TargetHashes {
+    /// Returns `None` if no hashes were saved yet
+    pub fn load(workspace_root: &Utf8Path) -> Result<Option<Self>> {
+        let path = hashes_file(workspace_root);
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => Err(err)?,
+        };
+
+        let target_hashes =
+            serde_json::from_str(&content).with_context(|| format!("Failed to parse {path}"))?;
+
+        Ok(Some(target_hashes))
+    }
+
+    pub fn save(&self, workspace_root: &Utf8Path) -> Result<()> {
+        let path = hashes_file(workspace_root);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn is_unchanged(&self, package: &str, location: TestTargetLocation, hash: &str) -> bool {
+        self.targets
+            .get(&target_key(package, location))
+            .is_some_and(|saved_hash| saved_hash == hash)
+    }
+
+    /// Saves the hash of a target whose tests all passed, forgets it otherwise,
+    /// so failing targets are never skipped
+    pub fn record(
+        &mut self,
+        package: &str,
+        location: TestTargetLocation,
+        hash: String,
+        all_passed: bool,
+    ) {
+        let key = target_key(package, location);
+
+        if all_passed {
+            self.targets.insert(key, hash);
+        } else {
+            self.targets.remove(&key);
+        }
+    }
+}
+
+/// Ignored tests are not run without `--include-ignored` anyway, so they don't make the target changed
+#[must_use]
+pub fn all_tests_passed(summary: &TestTargetSummary) -> bool {
+    summary
+        .test_case_summaries
+        .iter()
+        .all(|summary| summary.is_passed() || summary.is_ignored())
+}
+
+/// Settings from the flags and `Scarb.toml` which can change the results of the tests,
+/// a target which passed with different settings is run again.
+/// The fuzzer seed is included only if it is set, otherwise every run uses a random one
+#[must_use]
+pub fn run_settings(args: &TestArgs, forge_config_from_scarb: &ForgeConfigFromScarb) -> String {
+    let mut env: BTreeMap<_, _> = forge_config_from_scarb.env.iter().collect();
+    env.extend(args.env.iter().map(|(name, value)| (name, value)));
+
+    format!(
+        "fuzzer_runs={:?};fuzzer_seed={:?};max_n_steps={:?};timeout={:?};max_gas={:?};\
+         block_context={:?};tracked_resource={};include_ignored={};contracts_filter={:?};\
+         env={env:?};fork={:?}",
+        args.fuzzer_runs.or(forge_config_from_scarb.fuzzer_runs),
+        args.fuzzer_seed.or(forge_config_from_scarb.fuzzer_seed),
+        args.max_n_steps.or(forge_config_from_scarb.max_n_steps),
+        args.timeout.or(forge_config_from_scarb.timeout),
+        forge_config_from_scarb.max_gas,
+        args.block_context_version
+            .or(forge_config_from_scarb.block_context),
+        args.tracked_resource,
+        args.include_ignored,
+        args.contracts_filter,
+        forge_config_from_scarb.fork,
+    )
+}
+
+/// Hashes the compiled test target together with Sierra and CASM of all contracts it can use
+/// and the [`run_settings`]
+pub fn target_hash(
+    test_target: &TestTargetRaw,
+    contracts: &HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>,
+    run_settings: &str,
+) -> Result<String> {
+    let mut hasher = Sha3_256::new()
+        .chain_update(serde_json::to_vec(&test_target.sierra_program)?)
+        .chain_update(run_settings);
+
+    // Contracts are hashed in a fixed order, regardless of the order of the map
+    for (name, (artifacts, _)) in contracts.iter().collect::<BTreeMap<_, _>>() {
+        hasher.update(name);
+        hasher.update(&artifacts.sierra);
+        hasher.update(artifacts.casm()?);
+    }
+
+    Ok(base16ct::lower::encode_string(&hasher.finalize()))
+}
+
+fn target_key(package: &str, location: TestTargetLocation) -> String {
+    let dir_name = match location {
+        TestTargetLocation::Lib => "src",
+        TestTargetLocation::Tests => "tests",
+    };
+
+    format!("{package}::{dir_name}")
+}
+
+fn hashes_file(workspace_root: &Utf8Path) -> Utf8PathBuf {
+    workspace_root.join(TARGET_HASHES_FILE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scarb::config::ForkTarget;
+    use clap::Parser;
+    use tempfile::TempDir;
+
+    fn settings_with_flags(flags: &[&str]) -> String {
+        let args = TestArgs::parse_from([&["snforge"], flags].concat());
+        run_settings(&args, &ForgeConfigFromScarb::default())
+    }
+
+    #[test]
+    fn load_without_file() {
+        let temp = TempDir::new().unwrap();
+
+        assert_eq!(
+            TargetHashes::load(Utf8Path::from_path(temp.path()).unwrap()).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn save_and_load() {
+        let temp = TempDir::new().unwrap();
+        let workspace_root = Utf8Path::from_path(temp.path()).unwrap();
+        let mut target_hashes = TargetHashes::default();
+        target_hashes.record("pkg", TestTargetLocation::Tests, "abc".to_string(), true);
+
+        target_hashes.save(workspace_root).unwrap();
+
+        assert!(temp.path().join(TARGET_HASHES_FILE).is_file());
+        assert_eq!(
+            TargetHashes::load(workspace_root).unwrap(),
+            Some(target_hashes)
+        );
+    }
+
+    #[test]
+    fn unchanged_only_with_same_hash() {
+        let mut target_hashes = TargetHashes::default();
+        target_hashes.record("pkg", TestTargetLocation::Lib, "abc".to_string(), true);
+
+        assert!(target_hashes.is_unchanged("pkg", TestTargetLocation::Lib, "abc"));
+        assert!(!target_hashes.is_unchanged("pkg", TestTargetLocation::Lib, "def"));
+        assert!(!target_hashes.is_unchanged("pkg", TestTargetLocation::Tests, "abc"));
+        assert!(!target_hashes.is_unchanged("other", TestTargetLocation::Lib, "abc"));
+    }
+
+    #[test]
+    fn failing_target_is_forgotten() {
+        let mut target_hashes = TargetHashes::default();
+        target_hashes.record("pkg", TestTargetLocation::Lib, "abc".to_string(), true);
+        target_hashes.record("pkg", TestTargetLocation::Lib, "abc".to_string(), false);
+
+        assert!(!target_hashes.is_unchanged("pkg", TestTargetLocation::Lib, "abc"));
+        assert!(target_hashes.targets.is_empty());
+    }
+
+    #[test]
+    fn run_settings_change_with_flags() {
+        let default_settings = settings_with_flags(&[]);
+
+        assert_eq!(default_settings, settings_with_flags(&[]));
+        for flags in [
+            &["--fuzzer-seed", "1"][..],
+            &["--fuzzer-runs", "10"],
+            &["--max-n-steps", "100"],
+            &["--include-ignored"],
+            &["--contracts-filter", "ERC20*"],
+            &["--env", "KEY=VALUE"],
+        ] {
+            assert_ne!(default_settings, settings_with_flags(flags), "{flags:?}");
+        }
+    }
+
+    #[test]
+    fn run_settings_change_with_scarb_config() {
+        let args = TestArgs::parse_from(["snforge"]);
+        let default_settings = run_settings(&args, &ForgeConfigFromScarb::default());
+        let forge_config_from_scarb = ForgeConfigFromScarb {
+            fork: vec![ForkTarget::new("SEPOLIA", "http://127.0.0.1:5050", "number", "1").unwrap()],
+            ..Default::default()
+        };
+
+        assert_ne!(
+            default_settings,
+            run_settings(&args, &forge_config_from_scarb)
+        );
+    }
+}
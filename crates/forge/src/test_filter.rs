@@ -1,26 +1,62 @@
-use crate::shared_cache::FailedTestsCache;
-use anyhow::Result;
-use forge_runner::package_tests::with_config_resolved::TestCaseWithResolvedConfig;
+use anyhow::{bail, Context};
+use clap::ValueEnum;
+use forge_runner::package_tests::with_config_resolved::{
+    TestCaseWithResolvedConfig, TestTargetWithResolvedConfig,
+};
+use forge_runner::package_tests::TestKind;
 use forge_runner::TestCaseFilter;
+use regex::Regex;
+use shared::print::{emit_warning, DiagnosticCode};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 // Specifies what tests should be included
 pub struct TestsFilter {
     // based on name
     pub(crate) name_filter: NameFilter,
+    // based on skip flags, excludes tests which names contain any of them
+    skip_filters: Vec<String>,
     // based on `#[ignore]` attribute
     ignored_filter: IgnoredFilter,
     // based on rerun_failed flag
     last_failed_filter: bool,
+    // tests of the package that failed during the last run
+    last_failed_tests: Vec<String>,
+    // based on partition flag, applied after all other filters
+    partition: Option<Partition>,
+    // based on `unit::` or `integration::` prefix of the name filter
+    kind: Option<TestKind>,
+}
 
-    failed_tests_cache: FailedTestsCache,
+/// Kinds of tests selected with `--test-kind`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TestKindFilter {
+    /// Tests in `src/`
+    Unit,
+    /// Tests in `tests/`
+    Integration,
+    #[default]
+    All,
 }
 
-#[derive(Debug, PartialEq)]
+impl TestKindFilter {
+    #[must_use]
+    pub fn includes(self, kind: TestKind) -> bool {
+        match self {
+            TestKindFilter::Unit => kind == TestKind::Unit,
+            TestKindFilter::Integration => kind == TestKind::Integration,
+            TestKindFilter::All => true,
+        }
+    }
+}
+
+#[derive(Debug)]
 pub(crate) enum NameFilter {
     All,
     Match(String),
     ExactMatch(String),
+    Regex(Regex),
 }
 
 #[derive(Debug, PartialEq)]
@@ -30,21 +66,85 @@ pub(crate) enum IgnoredFilter {
     All,
 }
 
+/// One of `total` shards of the tests, selected with `--partition <INDEX>/<TOTAL>`.
+/// Tests are assigned to shards by a hash of their fully qualified names,
+/// so adding a test does not move other tests between shards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    /// 1-based
+    index: u64,
+    total: u64,
+}
+
+impl Partition {
+    #[must_use]
+    pub fn includes(&self, test_name: &str) -> bool {
+        stable_hash(test_name) % self.total == self.index - 1
+    }
+}
+
+impl FromStr for Partition {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        let (index, total) = value
+            .split_once('/')
+            .context("Partition must be in the form <INDEX>/<TOTAL>, e.g. 2/4")?;
+        let index: u64 = index
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid partition index `{index}`"))?;
+        let total: u64 = total
+            .trim()
+            .parse()
+            .with_context(|| format!("Invalid partition total `{total}`"))?;
+
+        if total == 0 {
+            bail!("Partition total must be greater than 0");
+        }
+        if index == 0 || index > total {
+            bail!("Partition index must be between 1 and {total}, got {index}");
+        }
+
+        Ok(Self { index, total })
+    }
+}
+
+impl Display for Partition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.index, self.total)
+    }
+}
+
+/// FNV-1a, unlike the hashers from std it is guaranteed to be the same across platforms and releases
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
 impl TestsFilter {
     #[must_use]
-    #[allow(clippy::fn_params_excessive_bools)]
+    #[allow(clippy::fn_params_excessive_bools, clippy::too_many_arguments)]
     pub fn from_flags(
         test_name_filter: Option<String>,
         exact_match: bool,
+        filter_regex: Option<Regex>,
+        skip_filters: Vec<String>,
         only_ignored: bool,
         include_ignored: bool,
         rerun_failed: bool,
-        failed_tests_cache: FailedTestsCache,
+        last_failed_tests: Vec<String>,
+        partition: Option<Partition>,
     ) -> Self {
         assert!(
             !(only_ignored && include_ignored),
             "Arguments only_ignored and include_ignored cannot be both true"
         );
+        assert!(
+            !(filter_regex.is_some() && (exact_match || test_name_filter.is_some())),
+            "Argument filter_regex cannot be used with test_name_filter"
+        );
 
         let ignored_filter = if include_ignored {
             IgnoredFilter::All
@@ -54,6 +154,14 @@ impl TestsFilter {
             IgnoredFilter::NotIgnored
         };
 
+        let (kind, test_name_filter) = match test_name_filter {
+            Some(name) => {
+                let (kind, name) = split_kind_prefix(name);
+                (kind, Some(name))
+            }
+            None => (None, None),
+        };
+
         let name_filter = if exact_match {
             NameFilter::ExactMatch(
                 test_name_filter
@@ -61,22 +169,46 @@ impl TestsFilter {
             )
         } else if let Some(name) = test_name_filter {
             NameFilter::Match(name)
+        } else if let Some(regex) = filter_regex {
+            NameFilter::Regex(regex)
         } else {
             NameFilter::All
         };
 
         Self {
             name_filter,
+            skip_filters,
             ignored_filter,
             last_failed_filter: rerun_failed,
-            failed_tests_cache,
+            last_failed_tests,
+            partition,
+            kind,
         }
     }
 
+    /// Whether all not ignored tests of the targets are run, targets run with any filter may pass
+    /// without running all their tests, so their hashes are not saved with `--only-changed`
+    #[must_use]
+    pub(crate) fn runs_all_tests(&self) -> bool {
+        matches!(self.name_filter, NameFilter::All)
+            && self.skip_filters.is_empty()
+            && self.ignored_filter != IgnoredFilter::Ignored
+            && !self.last_failed_filter
+            && self.partition.is_none()
+            && self.kind.is_none()
+    }
+
+    /// Whether tests of the kind can match the name filter, tests of other kinds are filtered out
+    #[must_use]
+    pub(crate) fn includes_kind(&self, kind: TestKind) -> bool {
+        self.kind.is_none() || self.kind == Some(kind)
+    }
+
+    /// Returns names of tests which were excluded only by the partition
     pub(crate) fn filter_tests(
         &self,
         test_cases: &mut Vec<TestCaseWithResolvedConfig>,
-    ) -> Result<()> {
+    ) -> Vec<String> {
         match &self.name_filter {
             NameFilter::All => {}
             NameFilter::Match(filter) => {
@@ -86,15 +218,15 @@ impl TestsFilter {
             NameFilter::ExactMatch(name) => {
                 test_cases.retain(|tc| tc.name == *name);
             }
+            NameFilter::Regex(regex) => {
+                test_cases.retain(|tc| regex.is_match(&tc.name));
+            }
         };
 
+        test_cases.retain(|tc| !self.skip_filters.iter().any(|skip| tc.name.contains(skip)));
+
         if self.last_failed_filter {
-            match self.failed_tests_cache.load()?.as_slice() {
-                [] => {}
-                result => {
-                    test_cases.retain(|tc| result.iter().any(|name| name == &tc.name));
-                }
-            }
+            test_cases.retain(|tc| self.last_failed_tests.contains(&tc.name));
         }
 
         match self.ignored_filter {
@@ -105,7 +237,99 @@ impl TestsFilter {
             }
         };
 
-        Ok(())
+        let Some(partition) = &self.partition else {
+            return vec![];
+        };
+        let (included, excluded) = std::mem::take(test_cases)
+            .into_iter()
+            .partition(|tc| partition.includes(&tc.name));
+        *test_cases = included;
+
+        excluded.into_iter().map(|tc| tc.name).collect()
+    }
+
+    #[must_use]
+    pub fn partition(&self) -> Option<Partition> {
+        self.partition
+    }
+
+    /// Returns descriptions of the name and skip filters with numbers of tests matched by them
+    pub(crate) fn match_counts(
+        &self,
+        test_targets: &[TestTargetWithResolvedConfig],
+    ) -> Vec<(String, usize)> {
+        let count = |predicate: &dyn Fn(&str) -> bool| {
+            test_targets
+                .iter()
+                .filter(|test_target| self.includes_kind(test_target.tests_location.test_kind()))
+                .flat_map(|test_target| &test_target.test_cases)
+                .filter(|tc| predicate(&tc.name))
+                .count()
+        };
+
+        let name_filter_count = match &self.name_filter {
+            NameFilter::All => None,
+            NameFilter::Match(filter) => Some((
+                format!("Filter `{filter}`"),
+                count(&|name| name.contains(filter.as_str())),
+            )),
+            NameFilter::ExactMatch(filter) => Some((
+                format!("Exact filter `{filter}`"),
+                count(&|name| name == filter),
+            )),
+            NameFilter::Regex(regex) => Some((
+                format!("Regex filter `{regex}`"),
+                count(&|name| regex.is_match(name)),
+            )),
+        };
+        let skip_filter_counts = self.skip_filters.iter().map(|skip| {
+            (
+                format!("Skip filter `{skip}`"),
+                count(&|name| name.contains(skip.as_str())),
+            )
+        });
+
+        name_filter_count
+            .into_iter()
+            .chain(skip_filter_counts)
+            .collect()
+    }
+
+    /// Warns about tests that failed during the last run but are not collected anymore
+    pub(crate) fn warn_if_last_failed_tests_missing(
+        &self,
+        test_targets: &[TestTargetWithResolvedConfig],
+    ) {
+        if !self.last_failed_filter {
+            return;
+        }
+
+        for name in &self.last_failed_tests {
+            let exists = test_targets
+                .iter()
+                .flat_map(|test_target| &test_target.test_cases)
+                .any(|tc| &tc.name == name);
+
+            if !exists {
+                emit_warning(
+                    DiagnosticCode::MissingTest,
+                    format!(
+                        "Test {name} failed during the last run but no longer exists, skipping it"
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Tests with the same names in `src/` and `tests/` are told apart with `unit::` or `integration::` prefix
+fn split_kind_prefix(name: String) -> (Option<TestKind>, String) {
+    if let Some(name) = name.strip_prefix("unit::") {
+        (Some(TestKind::Unit), name.to_string())
+    } else if let Some(name) = name.strip_prefix("integration::") {
+        (Some(TestKind::Integration), name.to_string())
+    } else {
+        (None, name)
     }
 }
 
@@ -123,14 +347,15 @@ impl TestCaseFilter for TestsFilter {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_filter::TestsFilter;
+    use crate::test_filter::{Partition, TestKindFilter, TestsFilter};
     use cairo_lang_sierra::program::Program;
     use cairo_lang_sierra::program::ProgramArtifact;
     use forge_runner::expected_result::ExpectedTestResult;
     use forge_runner::package_tests::with_config_resolved::{
         TestCaseResolvedConfig, TestCaseWithResolvedConfig, TestTargetWithResolvedConfig,
     };
-    use forge_runner::package_tests::{TestDetails, TestTargetLocation};
+    use forge_runner::package_tests::{TestDetails, TestKind, TestTargetLocation};
+    use regex::Regex;
     use std::sync::Arc;
     use universal_sierra_compiler_api::compile_sierra_to_casm;
 
@@ -146,16 +371,65 @@ mod tests {
         }
     }
 
+    fn test_case(name: &str) -> TestCaseWithResolvedConfig {
+        TestCaseWithResolvedConfig {
+            name: name.to_string(),
+            test_details: TestDetails::default(),
+
+            config: TestCaseResolvedConfig {
+                available_gas: None,
+                ignored: false,
+                ignore_reason: None,
+                expected_result: ExpectedTestResult::Success,
+                fork_config: None,
+                fuzzer_config: None,
+                test_case_config: None,
+                available_steps: None,
+                timeout: None,
+                serial: false,
+            },
+        }
+    }
+
+    fn mocked_target(names: &[&str]) -> TestTargetWithResolvedConfig {
+        TestTargetWithResolvedConfig {
+            sierra_program: program_for_testing(),
+            casm_program: Arc::new(compile_sierra_to_casm(&program_for_testing().program).unwrap()),
+            test_cases: names.iter().map(|name| test_case(name)).collect(),
+            tests_location: TestTargetLocation::Tests,
+        }
+    }
+
     #[test]
     #[should_panic(expected = "Arguments only_ignored and include_ignored cannot be both true")]
     fn from_flags_only_ignored_and_include_ignored_both_true() {
-        let _ = TestsFilter::from_flags(None, false, true, true, false, Default::default());
+        let _ = TestsFilter::from_flags(
+            None,
+            false,
+            None,
+            vec![],
+            true,
+            true,
+            false,
+            Default::default(),
+            None,
+        );
     }
 
     #[test]
     #[should_panic(expected = "Argument test_name_filter cannot be None with exact_match")]
     fn from_flags_exact_match_true_without_test_filter_name() {
-        let _ = TestsFilter::from_flags(None, true, false, false, false, Default::default());
+        let _ = TestsFilter::from_flags(
+            None,
+            true,
+            None,
+            vec![],
+            false,
+            false,
+            false,
+            Default::default(),
+            None,
+        );
     }
 
     #[test]
@@ -172,9 +446,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -184,9 +463,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -196,9 +480,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -208,9 +497,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ],
@@ -220,15 +514,18 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some("do".to_string()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
 
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -239,9 +536,14 @@ mod tests {
                 config: TestCaseResolvedConfig {
                     available_gas: None,
                     ignored: false,
+                    ignore_reason: None,
                     expected_result: ExpectedTestResult::Success,
                     fork_config: None,
                     fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
                 },
             },]
         );
@@ -249,14 +551,17 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some("te2::run".to_string()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -267,9 +572,14 @@ mod tests {
                 config: TestCaseResolvedConfig {
                     available_gas: None,
                     ignored: true,
+                    ignore_reason: None,
                     expected_result: ExpectedTestResult::Success,
                     fork_config: None,
                     fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
                 },
             },]
         );
@@ -277,14 +587,17 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some("thing".to_string()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -296,9 +609,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -308,9 +626,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -320,9 +643,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -332,9 +660,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ]
@@ -343,28 +676,34 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some("nonexistent".to_string()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
             Some(String::new()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -376,9 +715,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -388,9 +732,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -400,9 +749,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -412,9 +766,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ]
@@ -433,28 +792,34 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some(String::new()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
             Some("thing".to_string()),
             false,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(filtered.test_cases, vec![]);
     }
@@ -473,9 +838,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -485,9 +855,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -497,9 +872,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -509,9 +889,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ],
@@ -521,42 +906,51 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some(String::new()),
             true,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
             Some("thing".to_string()),
             true,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
             Some("do_thing".to_string()),
             true,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -567,9 +961,14 @@ mod tests {
                 config: TestCaseResolvedConfig {
                     available_gas: None,
                     ignored: false,
+                    ignore_reason: None,
                     expected_result: ExpectedTestResult::Success,
                     fork_config: None,
                     fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
                 },
             },]
         );
@@ -577,14 +976,17 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some("crate1::do_thing".to_string()),
             true,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -595,9 +997,14 @@ mod tests {
                 config: TestCaseResolvedConfig {
                     available_gas: None,
                     ignored: false,
+                    ignore_reason: None,
                     expected_result: ExpectedTestResult::Success,
                     fork_config: None,
                     fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
                 },
             },]
         );
@@ -605,28 +1012,34 @@ mod tests {
         let tests_filter = TestsFilter::from_flags(
             Some("crate3::run_other_thing".to_string()),
             true,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(filtered.test_cases, vec![]);
 
         let tests_filter = TestsFilter::from_flags(
             Some("outer::crate3::run_other_thing".to_string()),
             true,
+            None,
+            vec![],
             false,
             false,
             false,
             Default::default(),
+            None,
         );
 
         let mut filtered = mocked_tests.clone();
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -637,9 +1050,14 @@ mod tests {
                 config: TestCaseResolvedConfig {
                     available_gas: None,
                     ignored: true,
+                    ignore_reason: None,
                     expected_result: ExpectedTestResult::Success,
                     fork_config: None,
                     fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
                 },
             },]
         );
@@ -658,9 +1076,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -670,9 +1093,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -682,9 +1110,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -694,19 +1127,33 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ],
             tests_location: TestTargetLocation::Tests,
         };
 
-        let tests_filter =
-            TestsFilter::from_flags(None, false, true, false, false, Default::default());
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            None,
+            vec![],
+            true,
+            false,
+            false,
+            Default::default(),
+            None,
+        );
         let mut filtered = mocked_tests;
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -718,9 +1165,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -730,9 +1182,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ]
@@ -753,9 +1210,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -765,9 +1227,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -777,9 +1244,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -789,19 +1261,33 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ],
             tests_location: TestTargetLocation::Tests,
         };
 
-        let tests_filter =
-            TestsFilter::from_flags(None, false, false, true, false, Default::default());
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            None,
+            vec![],
+            false,
+            true,
+            false,
+            Default::default(),
+            None,
+        );
         let mut filtered = mocked_tests;
-        tests_filter.filter_tests(&mut filtered.test_cases).unwrap();
+        tests_filter.filter_tests(&mut filtered.test_cases);
 
         assert_eq!(
             filtered.test_cases,
@@ -813,9 +1299,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -825,9 +1316,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -837,9 +1333,14 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: true,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
                 TestCaseWithResolvedConfig {
@@ -849,12 +1350,401 @@ mod tests {
                     config: TestCaseResolvedConfig {
                         available_gas: None,
                         ignored: false,
+                        ignore_reason: None,
                         expected_result: ExpectedTestResult::Success,
                         fork_config: None,
                         fuzzer_config: None,
+                        test_case_config: None,
+                        available_steps: None,
+                        timeout: None,
+                        serial: false,
                     },
                 },
             ]
         );
     }
+
+    #[test]
+    fn filtering_with_rerun_failed() {
+        let mocked_tests = mocked_target(&[
+            "crate1::do_thing",
+            "crate2::run_other_thing",
+            "crate3::run_thing",
+        ]);
+        let last_failed_tests = vec![
+            "crate1::do_thing".to_string(),
+            "crate3::run_thing".to_string(),
+        ];
+
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            None,
+            vec![],
+            false,
+            false,
+            true,
+            last_failed_tests.clone(),
+            None,
+        );
+        let mut filtered = mocked_tests.clone();
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(
+            filtered.test_cases,
+            vec![
+                test_case("crate1::do_thing"),
+                test_case("crate3::run_thing")
+            ]
+        );
+
+        let tests_filter = TestsFilter::from_flags(
+            Some("run".to_string()),
+            false,
+            None,
+            vec![],
+            false,
+            false,
+            true,
+            last_failed_tests,
+            None,
+        );
+        let mut filtered = mocked_tests.clone();
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(filtered.test_cases, vec![test_case("crate3::run_thing")]);
+
+        let tests_filter =
+            TestsFilter::from_flags(None, false, None, vec![], false, false, true, vec![], None);
+        let mut filtered = mocked_tests;
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(filtered.test_cases, vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Argument filter_regex cannot be used with test_name_filter")]
+    fn from_flags_filter_regex_with_test_filter_name() {
+        let _ = TestsFilter::from_flags(
+            Some("thing".to_string()),
+            false,
+            Some(Regex::new("thing").unwrap()),
+            vec![],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+    }
+
+    #[test]
+    fn filtering_with_regex() {
+        let mocked_tests = mocked_target(&[
+            "crate1::test_transfer",
+            "crate1::test_transfer_from_fails",
+            "crate2::test_transfer",
+        ]);
+
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            Some(Regex::new("^crate1::.*_fails$").unwrap()),
+            vec![],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+        let mut filtered = mocked_tests.clone();
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(
+            filtered.test_cases,
+            vec![test_case("crate1::test_transfer_from_fails")]
+        );
+
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            Some(Regex::new("test_transfer$").unwrap()),
+            vec![],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+        let mut filtered = mocked_tests;
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(
+            filtered.test_cases,
+            vec![
+                test_case("crate1::test_transfer"),
+                test_case("crate2::test_transfer")
+            ]
+        );
+    }
+
+    #[test]
+    fn filtering_with_skip() {
+        let mocked_tests = mocked_target(&[
+            "crate1::test_transfer",
+            "crate1::test_transfer_from_fails",
+            "crate2::test_approve",
+        ]);
+
+        let tests_filter = TestsFilter::from_flags(
+            Some("crate1".to_string()),
+            false,
+            None,
+            vec!["fails".to_string()],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+        let mut filtered = mocked_tests.clone();
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(
+            filtered.test_cases,
+            vec![test_case("crate1::test_transfer")]
+        );
+
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            None,
+            vec!["fails".to_string(), "approve".to_string()],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+        let mut filtered = mocked_tests;
+        tests_filter.filter_tests(&mut filtered.test_cases);
+
+        assert_eq!(
+            filtered.test_cases,
+            vec![test_case("crate1::test_transfer")]
+        );
+    }
+
+    #[test]
+    fn match_counts() {
+        let mocked_tests = vec![mocked_target(&[
+            "crate1::test_transfer",
+            "crate1::test_transfer_from_fails",
+            "crate2::test_approve",
+        ])];
+
+        let tests_filter = TestsFilter::from_flags(
+            Some("test_transfer".to_string()),
+            false,
+            None,
+            vec!["fails".to_string(), "tset".to_string()],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+
+        assert_eq!(
+            tests_filter.match_counts(&mocked_tests),
+            vec![
+                ("Filter `test_transfer`".to_string(), 2),
+                ("Skip filter `fails`".to_string(), 1),
+                ("Skip filter `tset`".to_string(), 0),
+            ]
+        );
+
+        let tests_filter = TestsFilter::from_flags(
+            Some("crate2::test_approve".to_string()),
+            true,
+            None,
+            vec![],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+
+        assert_eq!(
+            tests_filter.match_counts(&mocked_tests),
+            vec![("Exact filter `crate2::test_approve`".to_string(), 1)]
+        );
+
+        let tests_filter = TestsFilter::from_flags(
+            None,
+            false,
+            Some(Regex::new("transfer").unwrap()),
+            vec![],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+
+        assert_eq!(
+            tests_filter.match_counts(&mocked_tests),
+            vec![("Regex filter `transfer`".to_string(), 2)]
+        );
+
+        let tests_filter =
+            TestsFilter::from_flags(None, false, None, vec![], false, false, false, vec![], None);
+
+        assert!(tests_filter.match_counts(&mocked_tests).is_empty());
+    }
+
+    #[test]
+    fn parsing_partition() {
+        assert_eq!(
+            "2/4".parse::<Partition>().unwrap(),
+            Partition { index: 2, total: 4 }
+        );
+        assert_eq!("1/1".parse::<Partition>().unwrap().to_string(), "1/1");
+
+        for (value, error) in [
+            (
+                "2",
+                "Partition must be in the form <INDEX>/<TOTAL>, e.g. 2/4",
+            ),
+            ("a/4", "Invalid partition index `a`"),
+            ("1/0", "Partition total must be greater than 0"),
+            ("0/4", "Partition index must be between 1 and 4, got 0"),
+            ("5/4", "Partition index must be between 1 and 4, got 5"),
+        ] {
+            assert_eq!(value.parse::<Partition>().unwrap_err().to_string(), error);
+        }
+    }
+
+    #[test]
+    fn partitions_cover_all_tests_once() {
+        let names: Vec<_> = (0..100)
+            .map(|i| format!("crate::tests::test_{i}"))
+            .collect();
+
+        let mut selected: Vec<_> = (1..=4)
+            .flat_map(|index| {
+                let tests_filter = TestsFilter::from_flags(
+                    None,
+                    false,
+                    None,
+                    vec![],
+                    false,
+                    false,
+                    false,
+                    vec![],
+                    Some(format!("{index}/4").parse().unwrap()),
+                );
+                let mut test_cases: Vec<_> = names.iter().map(|name| test_case(name)).collect();
+                tests_filter.filter_tests(&mut test_cases);
+
+                assert!(!test_cases.is_empty());
+                test_cases.into_iter().map(|tc| tc.name)
+            })
+            .collect();
+        selected.sort();
+
+        let mut expected = names;
+        expected.sort();
+        assert_eq!(selected, expected);
+    }
+
+    #[test]
+    fn partition_applied_after_name_filter() {
+        let names = ["crate::a::test_1", "crate::a::test_2", "crate::b::test_3"];
+
+        let selected: Vec<_> = (1..=2)
+            .flat_map(|index| {
+                let tests_filter = TestsFilter::from_flags(
+                    Some("crate::a".to_string()),
+                    false,
+                    None,
+                    vec![],
+                    false,
+                    false,
+                    false,
+                    vec![],
+                    Some(format!("{index}/2").parse().unwrap()),
+                );
+                let mut test_cases: Vec<_> = names.iter().map(|name| test_case(name)).collect();
+                tests_filter.filter_tests(&mut test_cases);
+
+                test_cases.into_iter().map(|tc| tc.name)
+            })
+            .collect();
+
+        assert_eq!(selected.len(), 2);
+        assert!(selected.iter().all(|name| name.starts_with("crate::a")));
+    }
+
+    #[test]
+    fn kind_prefix_of_name_filter() {
+        let tests_filter = TestsFilter::from_flags(
+            Some("unit::tests::adds".to_string()),
+            false,
+            None,
+            vec![],
+            false,
+            false,
+            false,
+            vec![],
+            None,
+        );
+        let mut test_cases = vec![test_case("crate::tests::adds"), test_case("crate::other")];
+        tests_filter.filter_tests(&mut test_cases);
+
+        assert!(tests_filter.includes_kind(TestKind::Unit));
+        assert!(!tests_filter.includes_kind(TestKind::Integration));
+        assert_eq!(test_cases.len(), 1);
+        assert_eq!(test_cases[0].name, "crate::tests::adds");
+
+        let tests_filter =
+            TestsFilter::from_flags(None, false, None, vec![], false, false, false, vec![], None);
+
+        assert!(tests_filter.includes_kind(TestKind::Unit));
+        assert!(tests_filter.includes_kind(TestKind::Integration));
+    }
+
+    #[test]
+    fn runs_all_tests_only_without_filters() {
+        let runs_all_tests = |test_name_filter: Option<&str>, skip: Vec<String>, only_ignored| {
+            TestsFilter::from_flags(
+                test_name_filter.map(ToString::to_string),
+                false,
+                None,
+                skip,
+                only_ignored,
+                false,
+                false,
+                vec![],
+                None,
+            )
+            .runs_all_tests()
+        };
+
+        assert!(runs_all_tests(None, vec![], false));
+        assert!(!runs_all_tests(Some("unit::"), vec![], false));
+        assert!(!runs_all_tests(Some("integration::adds"), vec![], false));
+        assert!(!runs_all_tests(None, vec!["adds".to_string()], false));
+        assert!(!runs_all_tests(None, vec![], true));
+    }
+
+    #[test]
+    fn test_kind_filter() {
+        assert!(TestKindFilter::All.includes(TestKind::Unit));
+        assert!(TestKindFilter::All.includes(TestKind::Integration));
+        assert!(TestKindFilter::Unit.includes(TestKind::Unit));
+        assert!(!TestKindFilter::Unit.includes(TestKind::Integration));
+        assert!(!TestKindFilter::Integration.includes(TestKind::Unit));
+    }
 }
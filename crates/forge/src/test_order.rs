@@ -0,0 +1,183 @@
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use forge_runner::package_tests::with_config_resolved::TestCaseWithResolvedConfig;
+use rand::prelude::StdRng;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, RngCore, SeedableRng};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// Order in which tests of a test target are started, they still run in parallel
+#[derive(ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TestOrder {
+    /// Order in which the tests are defined
+    #[default]
+    Definition,
+    /// Order of the fully qualified names of the tests
+    Alphabetical,
+    /// Random order, reproducible with `--shuffle-seed`
+    Shuffle,
+}
+
+/// Test order with the seed resolved, `shuffle_seed` is set only for [`TestOrder::Shuffle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TestOrdering {
+    pub order: TestOrder,
+    pub shuffle_seed: Option<u64>,
+}
+
+impl TestOrdering {
+    /// Picks a random seed when shuffling without `--shuffle-seed`
+    pub fn new(order: TestOrder, shuffle_seed: Option<u64>) -> Result<Self> {
+        match (order, shuffle_seed) {
+            (TestOrder::Shuffle, seed) => Ok(Self {
+                order,
+                shuffle_seed: Some(seed.unwrap_or_else(|| thread_rng().next_u64())),
+            }),
+            (_, Some(_)) => bail!("`--shuffle-seed` can only be used with `--test-order shuffle`"),
+            (_, None) => Ok(Self {
+                order,
+                shuffle_seed: None,
+            }),
+        }
+    }
+
+    /// Sorts the tests in place, the same seed always gives the same order of the same tests
+    pub fn apply(&self, test_cases: &mut [TestCaseWithResolvedConfig]) {
+        match (self.order, self.shuffle_seed) {
+            (TestOrder::Definition, _) => {}
+            (TestOrder::Alphabetical, _) => {
+                test_cases.sort_by(|a, b| a.name.cmp(&b.name));
+            }
+            (TestOrder::Shuffle, seed) => {
+                // Definition order may differ between compilations, so shuffling starts from a fixed one
+                test_cases.sort_by(|a, b| a.name.cmp(&b.name));
+                let mut rng = StdRng::seed_from_u64(seed.expect("Seed is set when shuffling"));
+                test_cases.shuffle(&mut rng);
+            }
+        }
+    }
+}
+
+impl Display for TestOrdering {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let order = self
+            .order
+            .to_possible_value()
+            .expect("No test order is skipped");
+        write!(f, "{}", order.get_name())?;
+        if let Some(seed) = self.shuffle_seed {
+            write!(f, " (seed: {seed})")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use forge_runner::expected_result::ExpectedTestResult;
+    use forge_runner::package_tests::with_config_resolved::TestCaseResolvedConfig;
+    use forge_runner::package_tests::TestDetails;
+
+    fn test_cases(names: &[&str]) -> Vec<TestCaseWithResolvedConfig> {
+        names
+            .iter()
+            .map(|name| TestCaseWithResolvedConfig {
+                name: (*name).to_string(),
+                test_details: TestDetails::default(),
+                config: TestCaseResolvedConfig {
+                    available_gas: None,
+                    ignored: false,
+                    ignore_reason: None,
+                    expected_result: ExpectedTestResult::Success,
+                    fork_config: None,
+                    fuzzer_config: None,
+                    test_case_config: None,
+                    available_steps: None,
+                    timeout: None,
+                    serial: false,
+                },
+            })
+            .collect()
+    }
+
+    fn ordered_names(ordering: TestOrdering, names: &[&str]) -> Vec<String> {
+        let mut test_cases = test_cases(names);
+        ordering.apply(&mut test_cases);
+        test_cases.into_iter().map(|case| case.name).collect()
+    }
+
+    const NAMES: [&str; 6] = [
+        "pkg::tests::c",
+        "pkg::tests::a",
+        "pkg::b",
+        "pkg::tests::e",
+        "pkg::d",
+        "pkg::tests::f",
+    ];
+
+    #[test]
+    fn definition_order_is_kept() {
+        let ordering = TestOrdering::new(TestOrder::Definition, None).unwrap();
+
+        assert_eq!(ordered_names(ordering, &NAMES), NAMES);
+    }
+
+    #[test]
+    fn alphabetical_order() {
+        let ordering = TestOrdering::new(TestOrder::Alphabetical, None).unwrap();
+
+        assert_eq!(
+            ordered_names(ordering, &NAMES),
+            [
+                "pkg::b",
+                "pkg::d",
+                "pkg::tests::a",
+                "pkg::tests::c",
+                "pkg::tests::e",
+                "pkg::tests::f"
+            ]
+        );
+    }
+
+    #[test]
+    fn shuffle_depends_only_on_seed() {
+        let ordering = TestOrdering::new(TestOrder::Shuffle, Some(42)).unwrap();
+        let mut reversed = NAMES;
+        reversed.reverse();
+
+        let shuffled = ordered_names(ordering, &NAMES);
+
+        assert_eq!(ordered_names(ordering, &reversed), shuffled);
+
+        let mut sorted = shuffled;
+        sorted.sort();
+        let alphabetical = TestOrdering::new(TestOrder::Alphabetical, None).unwrap();
+        assert_eq!(sorted, ordered_names(alphabetical, &NAMES));
+    }
+
+    #[test]
+    fn seed_is_picked_when_shuffling() {
+        let ordering = TestOrdering::new(TestOrder::Shuffle, None).unwrap();
+
+        assert!(ordering.shuffle_seed.is_some());
+    }
+
+    #[test]
+    fn seed_requires_shuffle() {
+        assert!(TestOrdering::new(TestOrder::Alphabetical, Some(1)).is_err());
+        assert!(TestOrdering::new(TestOrder::Definition, Some(1)).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let shuffle = TestOrdering::new(TestOrder::Shuffle, Some(42)).unwrap();
+        let alphabetical = TestOrdering::new(TestOrder::Alphabetical, None).unwrap();
+
+        assert_eq!(shuffle.to_string(), "shuffle (seed: 42)");
+        assert_eq!(alphabetical.to_string(), "alphabetical");
+    }
+}
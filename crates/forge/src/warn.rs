@@ -1,9 +1,10 @@
-use anyhow::{anyhow, Result};
+use anyhow::Result;
+use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
 use forge_runner::package_tests::with_config_resolved::TestTargetWithResolvedConfig;
-use scarb_api::{package_matches_version_requirement, ScarbCommand};
-use scarb_metadata::Metadata;
-use semver::{Comparator, Op, Version, VersionReq};
-use shared::print::print_as_warning;
+use runtime::starknet::context::BlockContextVersion;
+use scarb_api::ScarbCommand;
+use semver::Version;
+use shared::print::{emit_warning, DiagnosticCode};
 use shared::rpc::create_rpc_client;
 use shared::verify_and_warn_if_incompatible_rpc_version;
 use std::collections::HashSet;
@@ -17,10 +18,11 @@ pub(crate) fn warn_if_available_gas_used_with_incompatible_scarb_version(
             if case.config.available_gas == Some(0)
                 && ScarbCommand::version().run()?.scarb <= Version::new(2, 4, 3)
             {
-                print_as_warning(&anyhow!(
+                emit_warning(
+                    DiagnosticCode::IncompatibleScarbVersion,
                     "`available_gas` attribute was probably specified when using Scarb ~2.4.3 \
-                    Make sure to use Scarb >=2.4.4"
-                ));
+                    Make sure to use Scarb >=2.4.4",
+                );
             }
         }
     }
@@ -28,6 +30,38 @@ pub(crate) fn warn_if_available_gas_used_with_incompatible_scarb_version(
     Ok(())
 }
 
+/// `#[available_steps]` attribute is checked by the plugin during compilation
+pub(crate) fn warn_if_max_n_steps_exceeds_protocol_limit(
+    max_n_steps: Option<u32>,
+    block_context_version: BlockContextVersion,
+) {
+    let protocol_max_n_steps = block_context_version
+        .versioned_constants()
+        .invoke_tx_max_n_steps;
+
+    if let Some(max_n_steps) = max_n_steps.filter(|steps| *steps > protocol_max_n_steps) {
+        emit_warning(
+            DiagnosticCode::ExceededProtocolLimit,
+            format!(
+                "`max_n_steps` = {max_n_steps} exceeds the maximum of {protocol_max_n_steps} steps allowed in a Starknet transaction, \
+                the tested code would not be executable on the network"
+            ),
+        );
+    }
+}
+
+/// Declaring the contracts fails, they are reported once before any test is run
+pub(crate) fn warn_if_contracts_have_unsupported_sierra_versions(
+    contracts_data: &ContractsData,
+    cairo_version: &Version,
+) {
+    if let Some(message) =
+        contracts_data.unsupported_sierra_versions_message(Some(&cairo_version.to_string()))
+    {
+        emit_warning(DiagnosticCode::UnsupportedSierraVersion, message);
+    }
+}
+
 pub(crate) async fn warn_if_incompatible_rpc_version(
     test_targets: &[TestTargetWithResolvedConfig],
 ) -> Result<()> {
@@ -60,29 +94,3 @@ pub(crate) async fn warn_if_incompatible_rpc_version(
 
     Ok(())
 }
-
-fn snforge_std_version_requirement() -> VersionReq {
-    let version = Version::parse(env!("CARGO_PKG_VERSION")).unwrap();
-    let comparator = Comparator {
-        op: Op::Exact,
-        major: version.major,
-        minor: Some(version.minor),
-        patch: Some(version.patch),
-        pre: version.pre,
-    };
-    VersionReq {
-        comparators: vec![comparator],
-    }
-}
-
-pub fn warn_if_snforge_std_not_compatible(scarb_metadata: &Metadata) -> Result<()> {
-    let snforge_std_version_requirement = snforge_std_version_requirement();
-    if !package_matches_version_requirement(
-        scarb_metadata,
-        "snforge_std",
-        &snforge_std_version_requirement,
-    )? {
-        print_as_warning(&anyhow!("Package snforge_std version does not meet the recommended version requirement {snforge_std_version_requirement}, it might result in unexpected behaviour"));
-    }
-    Ok(())
-}
@@ -0,0 +1,194 @@
+use crate::run_tests::workspace::run_for_workspace;
+use crate::{pretty_printing, ExitStatus, TestArgs};
+use anyhow::Result;
+use camino::{Utf8Path, Utf8PathBuf};
+use console::Term;
+use scarb_api::metadata::{workspace_metadata, Metadata};
+use shared::human_println;
+use std::collections::BTreeMap;
+use std::env;
+use std::time::{Duration, Instant, SystemTime};
+use walkdir::{DirEntry, WalkDir};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// Changes are picked up only once files stop changing for this long,
+/// so that saving many files at once triggers a single run
+const DEBOUNCE: Duration = Duration::from_millis(600);
+const MANIFEST_FILE_NAME: &str = "Scarb.toml";
+
+/// Modification time and length of every watched file
+type SourcesState = BTreeMap<Utf8PathBuf, (SystemTime, u64)>;
+
+enum Change {
+    Sources,
+    /// A `Scarb.toml` changed, so members of the workspace may have changed too
+    Manifest,
+}
+
+/// Runs tests, then runs them again each time Cairo sources or Scarb manifests of the workspace change,
+/// until interrupted with Ctrl-C
+pub async fn watch_workspace(mut args: TestArgs, jobs: usize) -> Result<ExitStatus> {
+    // Tests of each package are run from its root, every run must start from the original directory
+    let current_dir = env::current_dir()?;
+    let mut watched_dirs = list_watched_dirs()?;
+    let mut first_run = true;
+
+    loop {
+        env::set_current_dir(&current_dir)?;
+        let state = sources_state(&watched_dirs);
+
+        if !first_run && Term::stdout().is_term() {
+            Term::stdout().clear_screen()?;
+        }
+        first_run = false;
+
+        tokio::select! {
+            result = run_for_workspace(args.clone(), jobs) => {
+                match result {
+                    Ok(exit_status) if args.watch_failed => {
+                        args.rerun_failed = matches!(exit_status, ExitStatus::Failure);
+                    }
+                    Ok(_) => {}
+                    // Usually a compilation error, which the next change is expected to fix
+                    Err(error) => pretty_printing::print_error_message(&error),
+                }
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(ExitStatus::Success),
+        }
+
+        human_println!("\nWatching for changes, press Ctrl-C to exit");
+
+        tokio::select! {
+            change = wait_for_change(&watched_dirs, state) => {
+                if let Change::Manifest = change {
+                    env::set_current_dir(&current_dir)?;
+                    watched_dirs = list_watched_dirs()?;
+                }
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(ExitStatus::Success),
+        }
+    }
+}
+
+/// Root of the workspace and of all its members, members don't have to be inside the workspace root
+fn list_watched_dirs() -> Result<Vec<Utf8PathBuf>> {
+    let scarb_metadata: Metadata = workspace_metadata()?;
+    let mut dirs = vec![scarb_metadata.workspace.root.clone()];
+    dirs.extend(
+        scarb_metadata
+            .packages
+            .iter()
+            .filter(|package| scarb_metadata.workspace.members.contains(&package.id))
+            .map(|package| package.root.clone()),
+    );
+    dirs.sort();
+    dirs.dedup();
+
+    Ok(dirs)
+}
+
+async fn wait_for_change(watched_dirs: &[Utf8PathBuf], mut state: SourcesState) -> Change {
+    let mut changed_paths: Vec<Utf8PathBuf> = vec![];
+    let mut last_change = None;
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let new_state = sources_state(watched_dirs);
+        if new_state == state {
+            if last_change.is_some_and(|time: Instant| time.elapsed() >= DEBOUNCE) {
+                break;
+            }
+            continue;
+        }
+
+        changed_paths.extend(
+            state
+                .keys()
+                .chain(new_state.keys())
+                .filter(|path| state.get(*path) != new_state.get(*path))
+                .cloned(),
+        );
+        state = new_state;
+        last_change = Some(Instant::now());
+    }
+
+    if changed_paths
+        .iter()
+        .any(|path| path.file_name() == Some(MANIFEST_FILE_NAME))
+    {
+        Change::Manifest
+    } else {
+        Change::Sources
+    }
+}
+
+fn sources_state(watched_dirs: &[Utf8PathBuf]) -> SourcesState {
+    watched_dirs
+        .iter()
+        .flat_map(|dir| {
+            WalkDir::new(dir)
+                .into_iter()
+                .filter_entry(|entry| entry.depth() == 0 || !is_ignored_dir(entry))
+        })
+        // Files may be removed while they are being listed
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let path = Utf8Path::from_path(entry.path())?;
+            if !is_watched_file(path) {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+
+            Some((
+                path.to_path_buf(),
+                (metadata.modified().ok()?, metadata.len()),
+            ))
+        })
+        .collect()
+}
+
+fn is_ignored_dir(entry: &DirEntry) -> bool {
+    let name = entry.file_name().to_string_lossy();
+    entry.file_type().is_dir() && (name == "target" || name.starts_with('.'))
+}
+
+fn is_watched_file(path: &Utf8Path) -> bool {
+    path.extension() == Some("cairo") || path.file_name() == Some(MANIFEST_FILE_NAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn sources_state_contains_only_cairo_files_and_manifests() {
+        let temp = TempDir::new().unwrap();
+        let root = Utf8Path::from_path(temp.path()).unwrap();
+        for path in [
+            "Scarb.toml",
+            "src/lib.cairo",
+            "tests/test.cairo",
+            "README.md",
+            "target/dev/lib.cairo",
+            ".snfoundry/cache.cairo",
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let state = sources_state(&[root.to_path_buf()]);
+
+        assert_eq!(
+            state.keys().cloned().collect::<Vec<_>>(),
+            vec![
+                root.join("Scarb.toml"),
+                root.join("src/lib.cairo"),
+                root.join("tests/test.cairo"),
+            ]
+        );
+    }
+}
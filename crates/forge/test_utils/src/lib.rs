@@ -1,3 +1,4 @@
+pub mod resources;
 pub mod runner;
 pub mod running_tests;
 
@@ -0,0 +1,158 @@
+use crate::runner::TestCase;
+use forge_runner::resource_report::ResourceReport;
+use forge_runner::test_case_summary::AnyTestCaseSummary;
+use forge_runner::test_target_summary::TestTargetSummary;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// Allowed difference between the actual and expected numbers of used resources
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tolerance {
+    /// Numbers have to be equal
+    Strict,
+    /// Numbers can differ by at most the given percent of the expected number
+    Percent(f64),
+}
+
+impl Tolerance {
+    #[allow(clippy::cast_precision_loss)]
+    fn allows(self, actual: u128, expected: u128) -> bool {
+        match self {
+            Tolerance::Strict => actual == expected,
+            Tolerance::Percent(percent) => {
+                actual.abs_diff(expected) as f64 <= expected as f64 * percent / 100.0
+            }
+        }
+    }
+}
+
+/// Asserts that used resources match the expected ones within the tolerance.
+/// Sets of used builtins and syscalls always have to be equal, regardless of the tolerance,
+/// gas has to be computed from the same resource and Sierra gas has to be reported either in both or in none of the reports.
+pub fn assert_resources_within(
+    actual: &ResourceReport,
+    expected: &ResourceReport,
+    tolerance: Tolerance,
+) {
+    assert_same_keys("builtins", &actual.builtins, &expected.builtins);
+    assert_same_keys("syscalls", &actual.syscalls, &expected.syscalls);
+    assert_eq!(
+        actual.tracked_resource, expected.tracked_resource,
+        "Gas of the reports is computed from different resources"
+    );
+    assert_eq!(
+        actual.sierra_gas_consumed.is_some(),
+        expected.sierra_gas_consumed.is_some(),
+        "Sierra gas is reported in only one of the reports"
+    );
+
+    let numbers = [
+        ("steps".to_string(), actual.steps, expected.steps),
+        (
+            "memory holes".to_string(),
+            actual.memory_holes,
+            expected.memory_holes,
+        ),
+        ("events".to_string(), actual.events, expected.events),
+        (
+            "events size".to_string(),
+            actual.events_size,
+            expected.events_size,
+        ),
+        (
+            "messages to L1".to_string(),
+            actual.l2_to_l1_messages,
+            expected.l2_to_l1_messages,
+        ),
+        (
+            "payload size of messages to L1".to_string(),
+            actual.l2_to_l1_payload_size,
+            expected.l2_to_l1_payload_size,
+        ),
+    ]
+    .into_iter()
+    .map(|(name, actual, expected)| (name, actual as u128, expected as u128))
+    .chain([("gas".to_string(), actual.gas, expected.gas)])
+    .chain(
+        actual
+            .sierra_gas_consumed
+            .zip(expected.sierra_gas_consumed)
+            .map(|(actual, expected)| {
+                (
+                    "sierra gas".to_string(),
+                    u128::from(actual),
+                    u128::from(expected),
+                )
+            }),
+    )
+    .chain(expected.builtins.iter().map(|(name, expected)| {
+        (
+            format!("builtin {name}"),
+            actual.builtins[name] as u128,
+            *expected as u128,
+        )
+    }))
+    .chain(expected.syscalls.iter().map(|(name, expected)| {
+        (
+            format!("syscall {name}"),
+            actual.syscalls[name] as u128,
+            *expected as u128,
+        )
+    }));
+
+    let mismatches: Vec<_> = numbers
+        .filter(|(_, actual, expected)| !tolerance.allows(*actual, *expected))
+        .map(|(name, actual, expected)| format!("{name}: actual {actual}, expected {expected}"))
+        .collect();
+
+    assert!(
+        mismatches.is_empty(),
+        "Used resources are not within {tolerance:?} of the expected ones:\n{}",
+        mismatches.join("\n")
+    );
+}
+
+/// Asserts that resources used by the passed test match the expected ones within the tolerance,
+/// see [`assert_resources_within`]
+pub fn assert_test_resources_within(
+    result: &[TestTargetSummary],
+    test_case_name: &str,
+    expected: &ResourceReport,
+    tolerance: Tolerance,
+) {
+    let test_name_suffix = format!("::{test_case_name}");
+
+    let result = TestCase::find_test_result(result);
+
+    let actual = result
+        .test_case_summaries
+        .iter()
+        .find_map(|any_case| match any_case {
+            AnyTestCaseSummary::Fuzzing(_) => {
+                panic!("Cannot use assert_test_resources_within for fuzzing tests")
+            }
+            AnyTestCaseSummary::Single(case)
+                if any_case.name().ends_with(test_name_suffix.as_str()) =>
+            {
+                case.resource_report()
+            }
+            AnyTestCaseSummary::Single(_) => None,
+        })
+        .unwrap_or_else(|| panic!("No passed test {test_case_name} found"));
+
+    assert_resources_within(&actual, expected, tolerance);
+}
+
+fn assert_same_keys<V>(
+    kind: impl Display,
+    actual: &BTreeMap<String, V>,
+    expected: &BTreeMap<String, V>,
+) {
+    let actual_keys: Vec<_> = actual.keys().collect();
+    let expected_keys: Vec<_> = expected.keys().collect();
+
+    assert_eq!(
+        actual_keys, expected_keys,
+        "Used {kind} differ from the expected ones"
+    );
+}
@@ -108,7 +108,9 @@ impl Contract {
         .ok_or(anyhow!("there is no contract with name {}", self.name))?
         .0;
 
-        Ok((contract.sierra, contract.casm))
+        let casm = contract.casm()?.to_string();
+
+        Ok((contract.sierra, casm))
     }
 }
 
@@ -205,7 +207,7 @@ impl<'a> TestCase {
                 Ok((
                     name,
                     (
-                        StarknetContractArtifacts { sierra, casm },
+                        StarknetContractArtifacts::new(sierra, casm),
                         Default::default(),
                     ),
                 ))
@@ -256,6 +258,32 @@ pub fn assert_failed(result: &[TestTargetSummary]) {
     );
 }
 
+pub fn assert_timed_out(result: &[TestTargetSummary], test_case_name: &str) {
+    let test_name_suffix = format!("::{test_case_name}");
+
+    let result = TestCase::find_test_result(result);
+
+    assert!(
+        result.test_case_summaries.iter().any(|any_case| {
+            any_case.is_timed_out() && any_case.name().ends_with(test_name_suffix.as_str())
+        }),
+        "Test {test_case_name} didn't time out"
+    );
+}
+
+pub fn assert_fixture_failed(result: &[TestTargetSummary], test_case_name: &str) {
+    let test_name_suffix = format!("::{test_case_name}");
+
+    let result = TestCase::find_test_result(result);
+
+    assert!(
+        result.test_case_summaries.iter().any(|any_case| {
+            any_case.is_fixture_failed() && any_case.name().ends_with(test_name_suffix.as_str())
+        }),
+        "Fixture of test {test_case_name} didn't fail"
+    );
+}
+
 pub fn assert_case_output_contains(
     result: &[TestTargetSummary],
     test_case_name: &str,
@@ -268,10 +296,7 @@ pub fn assert_case_output_contains(
     assert!(result.test_case_summaries.iter().any(|any_case| {
         if any_case.is_passed() || any_case.is_failed() {
             return any_case.msg().unwrap().contains(asserted_msg)
-                && any_case
-                    .name()
-                    .unwrap()
-                    .ends_with(test_name_suffix.as_str());
+                && any_case.name().ends_with(test_name_suffix.as_str());
         }
         false
     }));
@@ -289,11 +314,7 @@ pub fn assert_gas(result: &[TestTargetSummary], test_case_name: &str, asserted_g
             }
             AnyTestCaseSummary::Single(case) => match case {
                 TestCaseSummary::Passed { gas_info: gas, .. } => {
-                    *gas == asserted_gas
-                        && any_case
-                            .name()
-                            .unwrap()
-                            .ends_with(test_name_suffix.as_str())
+                    *gas == asserted_gas && any_case.name().ends_with(test_name_suffix.as_str())
                 }
                 _ => false,
             },
@@ -319,10 +340,7 @@ pub fn assert_syscall(
             AnyTestCaseSummary::Single(case) => match case {
                 TestCaseSummary::Passed { used_resources, .. } => {
                     used_resources.syscall_counter.get(&syscall).unwrap_or(&0) == &expected_count
-                        && any_case
-                            .name()
-                            .unwrap()
-                            .ends_with(test_name_suffix.as_str())
+                        && any_case.name().ends_with(test_name_suffix.as_str())
                 }
                 _ => false,
             },
@@ -352,10 +370,7 @@ pub fn assert_builtin(
                         .get(&builtin)
                         .unwrap_or(&0)
                         == &expected_count
-                        && any_case
-                            .name()
-                            .unwrap()
-                            .ends_with(test_name_suffix.as_str())
+                        && any_case.name().ends_with(test_name_suffix.as_str())
                 }
                 _ => false,
             },
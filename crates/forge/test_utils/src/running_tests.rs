@@ -6,13 +6,15 @@ use forge::{
     run_tests::package::{run_for_package, RunForPackageArgs},
     scarb::load_test_artifacts,
     test_filter::TestsFilter,
+    test_order::TestOrdering,
 };
 use forge_runner::build_trace_data::test_sierra_program_path::VERSIONED_PROGRAMS_DIR;
 use forge_runner::forge_config::{
-    ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig,
+    ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig, TrackedResource,
 };
 use forge_runner::test_target_summary::TestTargetSummary;
-use forge_runner::CACHE_DIR;
+use forge_runner::{CACHE_DIR, FUZZ_CORPUS_DIR};
+use runtime::starknet::context::BlockContextVersion;
 use scarb_api::{metadata::MetadataCommandExt, ScarbCommand};
 use std::num::NonZeroU32;
 use std::sync::Arc;
@@ -21,6 +23,30 @@ use tokio::runtime::Runtime;
 
 #[must_use]
 pub fn run_test_case(test: &TestCase) -> Vec<TestTargetSummary> {
+    run_test_case_tracking(test, TrackedResource::CairoSteps)
+}
+
+#[must_use]
+pub fn run_test_case_tracking(
+    test: &TestCase,
+    tracked_resource: TrackedResource,
+) -> Vec<TestTargetSummary> {
+    run_test_case_with(test, tracked_resource, BlockContextVersion::default())
+}
+
+#[must_use]
+pub fn run_test_case_with_block_context(
+    test: &TestCase,
+    block_context_version: BlockContextVersion,
+) -> Vec<TestTargetSummary> {
+    run_test_case_with(test, TrackedResource::CairoSteps, block_context_version)
+}
+
+fn run_test_case_with(
+    test: &TestCase,
+    tracked_resource: TrackedResource,
+    block_context_version: BlockContextVersion,
+) -> Vec<TestTargetSummary> {
     ScarbCommand::new_with_stdio()
         .current_dir(test.path().unwrap())
         .arg("build")
@@ -46,14 +72,20 @@ pub fn run_test_case(test: &TestCase) -> Vec<TestTargetSummary> {
     rt.block_on(run_for_package(
         RunForPackageArgs {
             test_targets: raw_test_targets,
+            target_hashes: vec![],
+            unchanged_targets: vec![],
             package_name: "test_package".to_string(),
+            test_ordering: TestOrdering::default(),
             tests_filter: TestsFilter::from_flags(
                 None,
                 false,
+                None,
+                vec![],
                 false,
                 false,
                 false,
                 Default::default(),
+                None,
             ),
             forge_config: Arc::new(ForgeConfig {
                 test_runner_config: Arc::new(TestRunnerConfig {
@@ -61,26 +93,37 @@ pub fn run_test_case(test: &TestCase) -> Vec<TestTargetSummary> {
                     fuzzer_runs: NonZeroU32::new(256).unwrap(),
                     fuzzer_seed: 12345,
                     max_n_steps: None,
+                    timeout: None,
+                    max_gas: None,
+                    tracked_resource,
+                    block_context_version,
                     is_vm_trace_needed: false,
                     cache_dir: Utf8PathBuf::from_path_buf(tempdir().unwrap().into_path())
                         .unwrap()
                         .join(CACHE_DIR),
+                    fork_data: None,
                     contracts_data: ContractsData::try_from(test.contracts().unwrap()).unwrap(),
                     environment_variables: test.env().clone(),
+                    fuzz_corpus_dir: test.path().unwrap().join(FUZZ_CORPUS_DIR),
+                    prune_fuzz_corpus: false,
+                    capture_output: true,
                 }),
                 output_config: Arc::new(OutputConfig {
                     detailed_resources: false,
+                    json: false,
                     execution_data_to_save: ExecutionDataToSave::default(),
                     versioned_programs_dir: Utf8PathBuf::from_path_buf(
                         tempdir().unwrap().into_path(),
                     )
                     .unwrap()
                     .join(VERSIONED_PROGRAMS_DIR),
+                    coverage_data_dir: Default::default(),
                 }),
             }),
             fork_targets: vec![],
         },
         &mut BlockNumberMap::default(),
+        None,
     ))
     .expect("Runner fail")
 }
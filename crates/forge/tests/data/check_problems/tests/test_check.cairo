@@ -0,0 +1,17 @@
+use snforge_std::{declare, DeclareResultTrait};
+
+#[test]
+fn declares_existing_contract() {
+    declare("HelloStarknet").unwrap().contract_class();
+}
+
+#[test]
+fn declares_missing_contract() {
+    declare("Nonexistent").unwrap().contract_class();
+}
+
+#[test]
+#[fork("SEPOLIAA")]
+fn misspelled_fork() {
+    assert(1 == 1, 'always true');
+}
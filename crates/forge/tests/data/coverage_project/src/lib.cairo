@@ -7,3 +7,28 @@ pub fn increase_by_one(arg: u8) -> u8 {
     assert(1 == 1, '');
     arg + 1
 }
+
+#[starknet::interface]
+pub trait ICounter<TContractState> {
+    fn increase(ref self: TContractState, amount: u64);
+    fn get(self: @TContractState) -> u64;
+}
+
+#[starknet::contract]
+pub mod Counter {
+    #[storage]
+    struct Storage {
+        value: u64,
+    }
+
+    #[abi(embed_v0)]
+    impl CounterImpl of super::ICounter<ContractState> {
+        fn increase(ref self: ContractState, amount: u64) {
+            self.value.write(self.value.read() + amount);
+        }
+
+        fn get(self: @ContractState) -> u64 {
+            self.value.read()
+        }
+    }
+}
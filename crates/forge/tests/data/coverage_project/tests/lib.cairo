@@ -1,4 +1,6 @@
 use coverage_project::{increase_by_one, increase_by_two};
+use coverage_project::{ICounterDispatcher, ICounterDispatcherTrait};
+use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
 
 
 #[test]
@@ -6,3 +8,14 @@ fn my_test() {
     assert(increase_by_two(1) == 3, ''); // inlines
     assert(increase_by_one(1) == 2, ''); // inlines
 }
+
+#[test]
+fn counter_test() {
+    let contract = declare("Counter").unwrap().contract_class();
+    let (contract_address, _) = contract.deploy(@array![]).unwrap();
+    let dispatcher = ICounterDispatcher { contract_address };
+
+    dispatcher.increase(5);
+
+    assert(dispatcher.get() == 5, 'wrong value');
+}
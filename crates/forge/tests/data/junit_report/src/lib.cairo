@@ -0,0 +1,13 @@
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::add;
+
+    #[test]
+    fn adds_zero() {
+        assert(add(7, 0) == 7, 'wrong sum');
+    }
+}
@@ -0,0 +1,25 @@
+use junit_report::add;
+
+#[test]
+fn passing() {
+    assert(add(2, 3) == 5, 'wrong sum');
+}
+
+#[test]
+fn failing() {
+    assert(add(2, 2) == 5, 'wrong sum');
+}
+
+#[test]
+#[ignore("flaky")]
+fn ignored() {
+    assert(add(1, 1) == 2, 'wrong sum');
+}
+
+#[test]
+#[fuzzer(runs: 10, seed: 100)]
+fn fuzzed(a: u32, b: u32) {
+    let a: u64 = a.into();
+    let b: u64 = b.into();
+    assert(add(a, b) >= a, 'sum too small');
+}
@@ -0,0 +1,18 @@
+pub fn add(a: u64, b: u64) -> u64 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::add;
+
+    #[test]
+    fn adds() {
+        assert(add(2, 2) == 4, 'wrong sum');
+    }
+
+    #[test]
+    fn adds_zero() {
+        assert(add(2, 0) == 2, 'wrong sum');
+    }
+}
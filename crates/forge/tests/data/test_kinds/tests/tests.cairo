@@ -0,0 +1,6 @@
+use test_kinds::add;
+
+#[test]
+fn adds() {
+    assert(add(2, 3) == 5, 'wrong sum');
+}
@@ -1,4 +1,4 @@
-use super::common::runner::{setup_package, test_runner};
+use super::common::runner::{setup_hello_workspace, setup_package, test_runner};
 use crate::e2e::common::get_trace_from_trace_node;
 use cairo_annotations::trace_data::{
     CallTraceNode as ProfilerCallTraceNode, CallTraceV1 as ProfilerCallTrace,
@@ -6,7 +6,10 @@ use cairo_annotations::trace_data::{
 };
 use cairo_lang_sierra::program::VersionedProgram;
 use cairo_lang_starknet_classes::contract_class::ContractClass;
-use forge_runner::build_trace_data::{TEST_CODE_CONTRACT_NAME, TEST_CODE_FUNCTION_NAME, TRACE_DIR};
+use forge_runner::build_trace_data::{
+    TEST_CODE_CONTRACT_NAME, TEST_CODE_FUNCTION_NAME, TRACE_DIR, TRACE_INDEX_FILE,
+};
+use std::collections::HashMap;
 use std::fs;
 
 #[test]
@@ -16,24 +19,24 @@ fn simple_package_save_trace() {
 
     assert!(temp
         .join(TRACE_DIR)
-        .join("simple_package::tests::test_fib.json")
+        .join("simple_package.tests.test_fib.json")
         .exists());
     assert!(!temp
         .join(TRACE_DIR)
-        .join("simple_package_integrationtest::test_simple::test_failing.json")
+        .join("simple_package_integrationtest.test_simple.test_failing.json")
         .exists());
     assert!(!temp
         .join(TRACE_DIR)
-        .join("simple_package::tests::ignored_test.json")
+        .join("simple_package.tests.ignored_test.json")
         .exists());
     assert!(temp
         .join(TRACE_DIR)
-        .join("simple_package_integrationtest::ext_function_test::test_simple.json")
+        .join("simple_package_integrationtest.ext_function_test.test_simple.json")
         .exists());
 
     let trace_data = fs::read_to_string(
         temp.join(TRACE_DIR)
-            .join("simple_package_integrationtest::ext_function_test::test_simple.json"),
+            .join("simple_package_integrationtest.ext_function_test.test_simple.json"),
     )
     .unwrap();
 
@@ -42,6 +45,14 @@ fn simple_package_save_trace() {
 
     assert!(call_trace.nested_calls.is_empty());
 
+    let index = fs::read_to_string(temp.join(TRACE_DIR).join(TRACE_INDEX_FILE)).unwrap();
+    let index: HashMap<String, String> = serde_json::from_str(&index).unwrap();
+    assert_eq!(
+        index["simple_package_integrationtest::ext_function_test::test_simple"],
+        "simple_package_integrationtest.ext_function_test.test_simple.json"
+    );
+    assert!(!index.contains_key("simple_package_integrationtest::test_simple::test_failing"));
+
     // Check if it doesn't crash in case some data already exists
     test_runner(&temp).arg("--save-trace-data").assert().code(1);
 }
@@ -56,7 +67,7 @@ fn trace_has_contract_and_function_names() {
 
     let trace_data = fs::read_to_string(
         temp.join(TRACE_DIR)
-            .join("trace_info_integrationtest::test_trace::test_trace.json"),
+            .join("trace_info_integrationtest.test_trace.test_trace.json"),
     )
     .unwrap();
 
@@ -90,6 +101,60 @@ fn assert_contract_and_function_names(trace: &ProfilerCallTrace) {
     }
 }
 
+#[test]
+fn trace_has_steps_of_nested_calls() {
+    let temp = setup_package("trace");
+    test_runner(&temp)
+        .arg("--save-trace-data")
+        .assert()
+        .success();
+
+    let trace_data = fs::read_to_string(
+        temp.join(TRACE_DIR)
+            .join("trace_info_integrationtest.test_trace.test_trace.json"),
+    )
+    .unwrap();
+
+    let call_trace: ProfilerCallTrace =
+        serde_json::from_str(&trace_data).expect("Failed to parse call_trace");
+
+    // The call of contract A, its call of contract B and the calls of contract C
+    let execute_calls_trace = get_trace_from_trace_node(&call_trace.nested_calls[3]);
+    assert_eq!(count_frames_with_plausible_steps(execute_calls_trace), 5);
+    assert!(
+        call_trace.cumulative_resources.vm_resources.n_steps
+            >= execute_calls_trace
+                .cumulative_resources
+                .vm_resources
+                .n_steps
+    );
+}
+
+/// Steps are cumulative, so every call takes at least as many steps as all of its nested calls together
+fn count_frames_with_plausible_steps(trace: &ProfilerCallTrace) -> usize {
+    let steps = trace.cumulative_resources.vm_resources.n_steps;
+    assert!(steps > 0);
+
+    let nested_traces: Vec<_> = trace
+        .nested_calls
+        .iter()
+        .filter_map(|node| match node {
+            ProfilerCallTraceNode::EntryPointCall(sub_trace) => Some(sub_trace),
+            ProfilerCallTraceNode::DeployWithoutConstructor => None,
+        })
+        .collect();
+    let nested_steps: usize = nested_traces
+        .iter()
+        .map(|sub_trace| sub_trace.cumulative_resources.vm_resources.n_steps)
+        .sum();
+    assert!(steps >= nested_steps);
+
+    1 + nested_traces
+        .into_iter()
+        .map(count_frames_with_plausible_steps)
+        .sum::<usize>()
+}
+
 #[test]
 fn trace_has_cairo_execution_info() {
     let temp = setup_package("trace");
@@ -103,7 +168,7 @@ fn trace_has_cairo_execution_info() {
 
     let trace_data = fs::read_to_string(
         temp.join(TRACE_DIR)
-            .join("trace_info_integrationtest::test_trace::test_trace.json"),
+            .join("trace_info_integrationtest.test_trace.test_trace.json"),
     )
     .unwrap();
 
@@ -145,7 +210,7 @@ fn trace_has_deploy_with_no_constructor_phantom_nodes() {
 
     let trace_data = fs::read_to_string(
         temp.join(TRACE_DIR)
-            .join("trace_info_integrationtest::test_trace::test_trace.json"),
+            .join("trace_info_integrationtest.test_trace.test_trace.json"),
     )
     .unwrap();
 
@@ -166,3 +231,27 @@ fn trace_has_deploy_with_no_constructor_phantom_nodes() {
         cairo_annotations::trace_data::CallTraceNode::DeployWithoutConstructor
     );
 }
+
+#[test]
+fn trace_index_covers_all_packages_of_workspace() {
+    let temp = setup_hello_workspace();
+    test_runner(&temp)
+        .args(["--workspace", "--save-trace-data"])
+        .assert()
+        .code(1);
+
+    let index = fs::read_to_string(temp.join(TRACE_DIR).join(TRACE_INDEX_FILE)).unwrap();
+    let index: HashMap<String, String> = serde_json::from_str(&index).unwrap();
+    assert_eq!(
+        index["addition_integrationtest::nested::simple_case"],
+        "addition_integrationtest.nested.simple_case.json"
+    );
+    assert_eq!(
+        index["fibonacci_tests::abc::abc_test"],
+        "fibonacci_tests.abc.abc_test.json"
+    );
+    assert_eq!(
+        index["hello_workspaces::tests::test_simple"],
+        "hello_workspaces.tests.test_simple.json"
+    );
+}
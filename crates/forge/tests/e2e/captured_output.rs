@@ -0,0 +1,130 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stdout_contains, AsOutput};
+
+fn setup_printing_package() -> TempDir {
+    let temp = setup_package("empty");
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r#"
+            #[test]
+            fn passing() {
+                println!("printed by passing test");
+            }
+
+            #[test]
+            fn failing() {
+                println!("printed by failing test");
+                assert(1 == 2, 'failing check');
+            }
+        "#})
+        .unwrap();
+
+    temp
+}
+
+#[test]
+fn output_is_shown_only_for_failed_tests() {
+    let temp = setup_printing_package();
+
+    let output = test_runner(&temp).assert().code(1);
+    let stdout = output.as_stdout().to_string();
+
+    assert!(!stdout.contains("printed by passing test"));
+    assert_stdout_contains(
+        stdout,
+        indoc! {r"
+            [PASS] empty_integrationtest::test::passing [..]
+            [FAIL] empty_integrationtest::test::failing
+
+            Failure data:
+                0x[..] ('failing check')
+
+            Captured output:
+            printed by failing test
+            Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn output_is_printed_with_nocapture() {
+    let temp = setup_printing_package();
+
+    let output = test_runner(&temp).arg("--nocapture").assert().code(1);
+    let stdout = output.as_stdout().to_string();
+
+    assert!(!stdout.contains("Captured output:"));
+    assert_stdout_contains(
+        stdout,
+        indoc! {r"
+            printed by passing test
+            printed by failing test
+            [PASS] empty_integrationtest::test::passing [..]
+            [FAIL] empty_integrationtest::test::failing
+            Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn output_of_called_contracts_is_captured() {
+    let temp = setup_package("empty");
+    temp.child("src/lib.cairo")
+        .write_str(indoc! {r#"
+            #[starknet::interface]
+            pub trait IPrinter<TContractState> {
+                fn print(self: @TContractState);
+            }
+
+            #[starknet::contract]
+            pub mod Printer {
+                #[storage]
+                struct Storage {}
+
+                #[abi(embed_v0)]
+                impl PrinterImpl of super::IPrinter<ContractState> {
+                    fn print(self: @ContractState) {
+                        println!("printed by contract");
+                    }
+                }
+            }
+        "#})
+        .unwrap();
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+            use empty::{IPrinterDispatcher, IPrinterDispatcherTrait};
+
+            #[test]
+            fn failing_with_call() {
+                let contract = declare("Printer").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+
+                println!("printed before call");
+                IPrinterDispatcher { contract_address }.print();
+                println!("printed after call");
+                assert(1 == 2, 'failing check');
+            }
+        "#})
+        .unwrap();
+
+    let output = test_runner(&temp).assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            [FAIL] empty_integrationtest::test::failing_with_call
+
+            Failure data:
+                0x[..] ('failing check')
+
+            Captured output:
+            printed before call
+            printed by contract
+            printed after call
+            Tests: 0 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
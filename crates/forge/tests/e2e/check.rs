@@ -0,0 +1,70 @@
+use super::common::runner::{runner, setup_package};
+use indoc::indoc;
+use serde_json::{json, Value};
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+
+#[test]
+fn problems_are_reported() {
+    let temp = setup_package("check_problems");
+
+    let output = runner(&temp).arg("check").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Checked 3 test(s) from check_problems package
+            [ERROR] Found 2 problem(s):
+            tests/test_check.cairo:
+                check_problems_integrationtest::test_check::misspelled_fork: Fork configuration named = SEPOLIAA not found in the Scarb.toml
+                10: Contract = Nonexistent is declared, but it was not found in the artifacts of package check_problems
+        "},
+    );
+}
+
+#[test]
+fn problems_are_reported_as_json() {
+    let temp = setup_package("check_problems");
+
+    let output = runner(&temp).args(["check", "--json"]).assert().code(1);
+
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    let check: Value = serde_json::from_str(stdout.trim()).unwrap();
+    assert_eq!(
+        check,
+        json!({
+            "schema_version": 1,
+            "package": "check_problems",
+            "tests": 3,
+            "files": {
+                "tests/test_check.cairo": [
+                    {
+                        "line": null,
+                        "test": "check_problems_integrationtest::test_check::misspelled_fork",
+                        "message": "Fork configuration named = SEPOLIAA not found in the Scarb.toml"
+                    },
+                    {
+                        "line": 10,
+                        "test": null,
+                        "message": "Contract = Nonexistent is declared, but it was not found in the artifacts of package check_problems"
+                    }
+                ]
+            }
+        })
+    );
+    assert_stderr_contains(output, "[ERROR] Found 2 problem(s):");
+}
+
+#[test]
+fn no_problems() {
+    let temp = setup_package("simple_package");
+
+    let output = runner(&temp).arg("check").assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Checked [..] test(s) from simple_package package
+            No problems found
+        "},
+    );
+}
@@ -16,7 +16,7 @@ fn collection_with_lib() {
 
 
         Collected 17 test(s) from collection_with_lib package
-        Running 12 test(s) from src/
+        Running 12 unit test(s) from src/
         [PASS] collection_with_lib::fab::tests::test_simple [..]
         [PASS] collection_with_lib::fob::tests::test_simple [..]
         [PASS] collection_with_lib::tests::test_fib_in_lib [..]
@@ -29,7 +29,7 @@ fn collection_with_lib() {
         [PASS] collection_with_lib::fab::fab_impl::tests::test_super [..]
         [PASS] collection_with_lib::fob::fob_impl::tests::test_fob [..]
         [PASS] collection_with_lib::fab::fab_impl::tests::test_how_does_this_work [..]
-        Running 5 test(s) from tests/
+        Running 5 integration test(s) from tests/
         [PASS] collection_with_lib_tests::fab::fab_mod::test_fab [..]
         [PASS] collection_with_lib_tests::fibfabfob::test_fob [..]
         [PASS] collection_with_lib_tests::fab::test_fab [..]
@@ -54,7 +54,7 @@ fn collection_without_lib() {
 
 
         Collected 17 test(s) from collection_without_lib package
-        Running 12 test(s) from src/
+        Running 12 unit test(s) from src/
         [PASS] collection_without_lib::fab::tests::test_simple [..]
         [PASS] collection_without_lib::fab::fab_impl::tests::test_super [..]
         [PASS] collection_without_lib::tests::test_simple [..]
@@ -67,7 +67,7 @@ fn collection_without_lib() {
         [PASS] collection_without_lib::fab::fab_impl::tests::test_how_does_this_work [..]
         [PASS] collection_without_lib::fob::fob_impl::tests::test_fob [..]
         [PASS] collection_without_lib::fib::tests::test_fib [..]
-        Running 5 test(s) from tests/
+        Running 5 integration test(s) from tests/
         [PASS] collection_without_lib_integrationtest::fibfabfob::test_fab [..]
         [PASS] collection_without_lib_integrationtest::fab::fab_mod::test_fab [..]
         [PASS] collection_without_lib_integrationtest::fab::test_fab [..]
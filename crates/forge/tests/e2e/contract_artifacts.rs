@@ -44,10 +44,10 @@ fn simple_package_no_starknet_contract_target() {
 
 
     Collected 13 test(s) from simple_package package
-    Running 2 test(s) from src/
+    Running 2 unit test(s) from src/
     [PASS] simple_package::tests::test_fib [..]
     [IGNORE] simple_package::tests::ignored_test
-    Running 11 test(s) from tests/
+    Running 11 integration test(s) from tests/
     [PASS] simple_package_integrationtest::contract::call_and_invoke [..]
     [PASS] simple_package_integrationtest::ext_function_test::test_my_test [..]
     [IGNORE] simple_package_integrationtest::ext_function_test::ignored_test
@@ -96,8 +96,8 @@ fn no_optimization_flag() {
 
 
         Collected 1 test(s) from erc20_package package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [PASS] erc20_package_integrationtest::test_complex::complex[..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
         "},
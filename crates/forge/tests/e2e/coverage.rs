@@ -13,12 +13,50 @@ fn test_coverage_project() {
 
     test_runner(&temp).arg("--coverage").assert().success();
 
-    assert!(temp.join(COVERAGE_DIR).join(OUTPUT_FILE_NAME).is_file());
+    let report = fs::read_to_string(temp.join(COVERAGE_DIR).join(OUTPUT_FILE_NAME)).unwrap();
+    assert!(report.contains("src/lib.cairo\n"));
+    assert!(report.contains("coverage_project::increase_by_two\n"));
+    assert!(report.contains("coverage_project::Counter::CounterImpl::increase\n"));
+    assert!(temp
+        .join("target/snforge-coverage/coverage_project_integrationtest.counter_test.json")
+        .is_file());
 
     // Check if it doesn't crash in case some data already exists
     test_runner(&temp).arg("--coverage").assert().success();
 }
 
+#[test]
+#[cfg_attr(not(feature = "scarb_2_8_3"), ignore)]
+fn test_coverage_of_filtered_tests() {
+    let temp = setup_package("coverage_project");
+
+    test_runner(&temp)
+        .args(["--coverage", "my_test"])
+        .assert()
+        .success();
+
+    let report = fs::read_to_string(temp.join(COVERAGE_DIR).join(OUTPUT_FILE_NAME)).unwrap();
+    assert!(report.contains("FNDA:0,coverage_project::Counter::CounterImpl::increase\n"));
+    assert!(!report.contains("FNDA:0,coverage_project::increase_by_two\n"));
+    assert!(!temp
+        .join("target/snforge-coverage/coverage_project_integrationtest.counter_test.json")
+        .exists());
+}
+
+#[test]
+#[cfg_attr(not(feature = "scarb_2_8_3"), ignore)]
+fn test_coverage_path() {
+    let temp = setup_package("coverage_project");
+
+    test_runner(&temp)
+        .args(["--coverage", "--coverage-path", "reports/lcov.info"])
+        .assert()
+        .success();
+
+    assert!(temp.join("reports/lcov.info").is_file());
+    assert!(!temp.join(COVERAGE_DIR).exists());
+}
+
 #[test]
 #[cfg_attr(feature = "scarb_2_8_3", ignore)]
 fn test_fail_on_scarb_version_lt_2_8_0() {
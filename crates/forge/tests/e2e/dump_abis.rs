@@ -0,0 +1,90 @@
+use super::common::runner::{runner, setup_hello_workspace, setup_package};
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::assert_stdout_contains;
+use std::fs;
+
+#[test]
+fn abis_are_written() {
+    let temp = setup_package("simple_package");
+
+    let output = runner(&temp)
+        .args(["dump-abis", "--out", "abis"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Written ABI of simple_package::HelloStarknet to abis[..]HelloStarknet.abi.json
+        "},
+    );
+
+    let abi: Value = serde_json::from_str(
+        &fs::read_to_string(temp.join("abis/HelloStarknet.abi.json")).unwrap(),
+    )
+    .unwrap();
+    assert!(abi
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|entry| entry["type"] == "interface"));
+}
+
+#[test]
+fn colliding_contract_names_are_qualified_with_package() {
+    let temp = setup_hello_workspace();
+
+    runner(&temp)
+        .args(["dump-abis", "--workspace", "--out", "abis"])
+        .assert()
+        .success();
+
+    let abis = temp.join("abis");
+    assert!(abis.join("addition_AdditionContract.abi.json").is_file());
+    assert!(abis.join("fibonacci_AdditionContract.abi.json").is_file());
+    assert!(!abis.join("AdditionContract.abi.json").exists());
+}
+
+#[test]
+fn contracts_filter_selects_abis() {
+    let temp = setup_hello_workspace();
+
+    runner(&temp)
+        .args([
+            "dump-abis",
+            "--workspace",
+            "--out",
+            "abis",
+            "--contracts-filter",
+            "Fib*",
+        ])
+        .assert()
+        .success();
+
+    let abis = temp.join("abis");
+    assert!(abis.join("FibonacciContract.abi.json").is_file());
+    assert!(!abis.join("addition_AdditionContract.abi.json").exists());
+    assert!(!abis.join("fibonacci_AdditionContract.abi.json").exists());
+}
+
+#[test]
+fn contracts_filter_matching_nothing() {
+    let temp = setup_package("simple_package");
+
+    let output = runner(&temp)
+        .args([
+            "dump-abis",
+            "--out",
+            "abis",
+            "--contracts-filter",
+            "ERC721*",
+        ])
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        "[ERROR] No contracts match --contracts-filter = ERC721*, available contracts: [..]",
+    );
+}
@@ -0,0 +1,43 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stdout_contains, AsOutput};
+
+#[test]
+fn slowest_tests_are_listed() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp)
+        .args(["--durations", "2"])
+        .assert()
+        .code(1);
+    let stdout = output.as_stdout();
+
+    assert_stdout_contains(
+        stdout.to_string(),
+        indoc! {r"
+            Tests: 9 passed, 2 failed, 0 skipped, 2 ignored, 0 filtered out
+
+            Slowest tests:
+                [..]s simple_package[..]
+                [..]s simple_package[..]
+        "},
+    );
+
+    let listed = stdout
+        .lines()
+        .skip_while(|line| *line != "Slowest tests:")
+        .skip(1)
+        .take_while(|line| line.trim_start().starts_with(char::is_numeric))
+        .count();
+    assert_eq!(listed, 2);
+}
+
+#[test]
+fn slowest_tests_disabled() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp)
+        .args(["--durations", "0"])
+        .assert()
+        .code(1);
+
+    assert!(!output.as_stdout().contains("Slowest tests"));
+}
@@ -1,6 +1,9 @@
 use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
 use indoc::indoc;
 use shared::test_utils::output_assert::assert_stdout_contains;
+use std::fs;
+use toml_edit::{value, DocumentMut};
 
 #[test]
 fn env_var_reading() {
@@ -24,7 +27,130 @@ fn env_var_reading() {
 
 
         Collected 1 test(s) from env package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
+        [PASS] env::tests::reading_env_vars [..]
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn env_var_precedence() {
+    let temp = setup_package("env");
+    let manifest_path = temp.child("Scarb.toml");
+    let mut scarb_toml = fs::read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<DocumentMut>()
+        .unwrap();
+    scarb_toml["tool"]["snforge"]["env"]["FELT_ENV_VAR"] = value("987654321");
+    scarb_toml["tool"]["snforge"]["env"]["STRING_ENV_VAR"] = value("'fromscarb'");
+    manifest_path.write_str(&scarb_toml.to_string()).unwrap();
+
+    let output = test_runner(&temp)
+        .env("FELT_ENV_VAR", "1")
+        .env("STRING_ENV_VAR", "'fromprocess'")
+        .env(
+            "BYTE_ARRAY_ENV_VAR",
+            r#""that is a very long environment variable that would normally not fit""#,
+        )
+        .args(["--env", "STRING_ENV_VAR='abcde'"])
+        .assert()
+        .code(0);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from env package
+        Running 1 unit test(s) from src/
+        [PASS] env::tests::reading_env_vars [..]
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn env_file_reading() {
+    let temp = setup_package("env");
+    temp.child(".env")
+        .write_str(indoc! {r#"
+            FELT_ENV_VAR=1
+            STRING_ENV_VAR="'abcde'"
+            BYTE_ARRAY_ENV_VAR='"that is a very long environment variable that would normally not fit"'
+        "#})
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .env("FELT_ENV_VAR", "987654321")
+        .assert()
+        .code(0);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from env package
+        Running 1 unit test(s) from src/
+        [PASS] env::tests::reading_env_vars [..]
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn malformed_env_file() {
+    let temp = setup_package("env");
+    temp.child(".env")
+        .write_str(indoc! {r"
+            FELT_ENV_VAR=987654321
+            STRING_ENV_VAR
+            # comment
+            BYTE-ARRAY-ENV-VAR=abc
+        "})
+        .unwrap();
+
+    let output = test_runner(&temp).assert().code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] Failed to parse [..].env: line 2: expected `NAME=value`, found `STRING_ENV_VAR`
+        line 4: invalid variable name `BYTE-ARRAY-ENV-VAR`, only letters, digits and `_` are allowed and it cannot start with a digit
+        "},
+    );
+}
+
+#[test]
+fn env_file_not_loaded_with_no_env_file() {
+    let temp = setup_package("env");
+    temp.child(".env").write_str("STRING_ENV_VAR\n").unwrap();
+
+    let output = test_runner(&temp)
+        .arg("--no-env-file")
+        .env("FELT_ENV_VAR", "987654321")
+        .env("STRING_ENV_VAR", "'abcde'")
+        .env(
+            "BYTE_ARRAY_ENV_VAR",
+            r#""that is a very long environment variable that would normally not fit""#,
+        )
+        .assert()
+        .code(0);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from env package
+        Running 1 unit test(s) from src/
         [PASS] env::tests::reading_env_vars [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
         "},
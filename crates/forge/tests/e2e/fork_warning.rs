@@ -5,7 +5,7 @@ use indoc::formatdoc;
 use lazy_static::lazy_static;
 use shared::consts::EXPECTED_RPC_VERSION;
 use shared::test_utils::node_url::node_url;
-use shared::test_utils::output_assert::assert_stdout_contains;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains, AsOutput};
 use std::{thread::sleep, time::Duration};
 use tokio::{
     net::TcpListener,
@@ -61,18 +61,25 @@ fn should_print_warning() {
 
     let output = test_runner(&temp).assert();
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        formatdoc!(
+            r"
+                [WARNING] W0002: RPC node with the url {node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
+            "
+        ),
+    );
     assert_stdout_contains(
         output,
         formatdoc!(
             r"
                 [..]Compiling[..]
                 [..]Finished[..]
-                [WARNING] RPC node with the url {node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
 
 
                 Collected 1 test(s) from empty package
-                Running 0 test(s) from src/
-                Running 1 test(s) from tests/
+                Running 0 unit test(s) from src/
+                Running 1 integration test(s) from tests/
                 [FAIL] empty_integrationtest::test::t1
 
                 Failure[..]
@@ -115,18 +122,25 @@ fn should_dedup_urls() {
 
     let output = test_runner(&temp).assert();
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        formatdoc!(
+            r"
+                [WARNING] W0002: RPC node with the url {node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
+            "
+        ),
+    );
     assert_stdout_contains(
         output,
         formatdoc!(
             r"
                 [..]Compiling[..]
                 [..]Finished[..]
-                [WARNING] RPC node with the url {node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
 
 
                 Collected 2 test(s) from empty package
-                Running 0 test(s) from src/
-                Running 2 test(s) from tests/
+                Running 0 unit test(s) from src/
+                Running 2 integration test(s) from tests/
                 [FAIL] empty_integrationtest::test::t1
 
                 Failure[..]
@@ -175,19 +189,26 @@ fn should_print_foreach() {
 
     let output = test_runner(&temp).assert();
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        formatdoc!(
+            r"
+                [WARNING] W0002: RPC node with the url http://127.0.0.1:3030/?url={node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
+                [WARNING] W0002: RPC node with the url {node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
+            "
+        ),
+    );
     assert_stdout_contains(
         output,
         formatdoc!(
             r"
                 [..]Compiling[..]
                 [..]Finished[..]
-                [WARNING] RPC node with the url http://127.0.0.1:3030/?url={node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
-                [WARNING] RPC node with the url {node_url} uses incompatible version 0.5.1. Expected version: {EXPECTED_RPC_VERSION}
 
 
                 Collected 2 test(s) from empty package
-                Running 0 test(s) from src/
-                Running 2 test(s) from tests/
+                Running 0 unit test(s) from src/
+                Running 2 integration test(s) from tests/
                 [FAIL] empty_integrationtest::test::t1
 
                 Failure[..]
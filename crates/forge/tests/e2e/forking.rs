@@ -23,7 +23,7 @@ fn without_cache() {
 
 
         Collected 4 test(s) from forking package
-        Running 4 test(s) from src/
+        Running 4 unit test(s) from src/
         [PASS] forking::tests::test_fork_simple [..]
         [PASS] forking::tests::test_fork_simple_number_hex [..]
         [PASS] forking::tests::test_fork_simple_hash_hex [..]
@@ -58,8 +58,8 @@ fn with_cache() {
 
 
         Collected 1 test(s) from forking package
-        Running 1 test(s) from src/
-        [FAIL] forking::tests::test_fork_simple
+        Running 1 unit test(s) from src/
+        [FAIL] forking::tests::test_fork_simple (fork: [..], block: 54060)
 
         Failure data:
             0x42616c616e63652073686f756c642062652030 ('Balance should be 0')
@@ -94,7 +94,7 @@ fn with_clean_cache() {
 
 
         Collected 1 test(s) from forking package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] forking::tests::test_fork_simple [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, other filtered out
         "},
@@ -122,7 +122,7 @@ fn printing_latest_block_number() {
 
 
         Collected 1 test(s) from forking package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] forking::tests::print_block_number_when_latest [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, other filtered out
 
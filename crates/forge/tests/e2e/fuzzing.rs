@@ -16,12 +16,12 @@ fn fuzzing() {
 
 
         Collected 13 test(s) from fuzzing package
-        Running 13 test(s) from src/
+        Running 13 unit test(s) from src/
         [PASS] fuzzing::tests::adding [..]
         [PASS] fuzzing::tests::fuzzed_argument (runs: 256, [..]
         [PASS] fuzzing::tests::fuzzed_both_arguments (runs: 256, [..]
         [PASS] fuzzing::tests::passing [..]
-        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]])
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, seed: [..], arguments: [[..], [..]], minimized arguments: [[..], [..]])
 
         Failure data:
             0x726573756c74203d3d2061202b2062 ('result == a + b')
@@ -34,7 +34,7 @@ fn fuzzing() {
         [PASS] fuzzing::tests::uint64_arg (runs: 256, [..]
         [PASS] fuzzing::tests::uint128_arg (runs: 256, [..]
         [PASS] fuzzing::tests::uint256_arg (runs: 256, [..]
-        Running 0 test(s) from tests/
+        Running 0 integration test(s) from tests/
         Tests: 12 passed, 1 failed, 0 skipped, 0 ignored, 6 filtered out
         Fuzzer seed: [..]
 
@@ -61,12 +61,12 @@ fn fuzzing_set_runs() {
         
         
         Collected 13 test(s) from fuzzing package
-        Running 13 test(s) from src/
+        Running 13 unit test(s) from src/
         [PASS] fuzzing::tests::adding [..]
         [PASS] fuzzing::tests::fuzzed_argument (runs: 10, [..]
         [PASS] fuzzing::tests::fuzzed_both_arguments (runs: 10, [..]
         [PASS] fuzzing::tests::passing [..]
-        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]])
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, seed: [..], arguments: [[..], [..]], minimized arguments: [[..], [..]])
 
         Failure data:
             0x726573756c74203d3d2061202b2062 ('result == a + b')
@@ -79,7 +79,7 @@ fn fuzzing_set_runs() {
         [PASS] fuzzing::tests::uint64_arg (runs: 10, [..]
         [PASS] fuzzing::tests::uint128_arg (runs: 10, [..]
         [PASS] fuzzing::tests::uint256_arg (runs: 10, [..]
-        Running 0 test(s) from tests/
+        Running 0 integration test(s) from tests/
         Tests: 12 passed, 1 failed, 0 skipped, 0 ignored, 6 filtered out
         Fuzzer seed: [..]
 
@@ -106,12 +106,12 @@ fn fuzzing_set_seed() {
         
         
         Collected 13 test(s) from fuzzing package
-        Running 13 test(s) from src/
+        Running 13 unit test(s) from src/
         [PASS] fuzzing::tests::adding [..]
         [PASS] fuzzing::tests::fuzzed_argument (runs: 256, [..]
         [PASS] fuzzing::tests::fuzzed_both_arguments (runs: 256, [..]
         [PASS] fuzzing::tests::passing [..]
-        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, arguments: [[..], [..]])
+        [FAIL] fuzzing::tests::failing_fuzz (runs: 1, seed: 1234, arguments: [[..], [..]], minimized arguments: [[..], [..]])
 
         Failure data:
             0x726573756c74203d3d2061202b2062 ('result == a + b')
@@ -124,7 +124,7 @@ fn fuzzing_set_seed() {
         [PASS] fuzzing::tests::uint64_arg (runs: 256, [..]
         [PASS] fuzzing::tests::uint128_arg (runs: 256, [..]
         [PASS] fuzzing::tests::uint256_arg (runs: 256, [..]
-        Running 0 test(s) from tests/
+        Running 0 integration test(s) from tests/
         Tests: 12 passed, 1 failed, 0 skipped, 0 ignored, 6 filtered out
         Fuzzer seed: 1234
 
@@ -167,7 +167,7 @@ fn fuzzing_incorrect_function_args() {
         
         
         Collected 2 test(s) from fuzzing package
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [ERROR] Tried to use incorrect type for fuzzing. Type = fuzzing_integrationtest::incorrect_args::MyStruct is not supported
         "},
     );
@@ -190,13 +190,14 @@ fn fuzzing_exit_first() {
 
 
         Collected 2 test(s) from fuzzing package
-        Running 2 test(s) from tests/
-        [FAIL] fuzzing_integrationtest::exit_first_fuzz::exit_first_fails_test (runs: 1, arguments: [..])
+        Running 2 integration test(s) from tests/
+        [FAIL] fuzzing_integrationtest::exit_first_fuzz::exit_first_fails_test (runs: 1, seed: [..], arguments: [..])
 
         Failure data:
             0x32202b2062203d3d2032202b2062 ('2 + b == 2 + b')
 
-        Tests: 0 passed, 1 failed, 1 skipped, 0 ignored, 17 filtered out
+        [PASS] fuzzing_integrationtest::exit_first_fuzz::exit_first_hard_test (runs: 256, [..])
+        Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 17 filtered out
 
         Fuzzer seed: [..]
         Failures:
@@ -222,16 +223,17 @@ fn fuzzing_exit_first_single_fail() {
 
 
         Collected 2 test(s) from fuzzing package
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] fuzzing_integrationtest::exit_first_single_fail::exit_first_fails_test
 
         Failure data:
             0x32202b2062203d3d2032202b2062 ('2 + b == 2 + b')
 
+        [PASS] fuzzing_integrationtest::exit_first_single_fail::exit_first_hard_test (runs: 256, [..])
         Failures:
             fuzzing_integrationtest::exit_first_single_fail::exit_first_fails_test
 
-        Tests: 0 passed, 1 failed, 1 skipped, 0 ignored, 17 filtered out
+        Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 17 filtered out
         "},
     );
 }
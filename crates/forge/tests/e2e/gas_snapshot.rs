@@ -0,0 +1,254 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stdout_contains, AsOutput};
+use std::fs;
+use toml_edit::{value, DocumentMut};
+
+const TEST_NAME: &str = "simple_package_integrationtest::contract::call_and_invoke";
+
+fn write_snapshot(temp: &TempDir) {
+    test_runner(temp)
+        .args([TEST_NAME, "--exact", "--gas-snapshot", "write"])
+        .assert()
+        .success();
+}
+
+fn increase_gas_of_contract(temp: &TempDir) {
+    let contract = temp.child("src/hello_starknet.cairo");
+    let content = fs::read_to_string(contract.path()).unwrap();
+    let content = content.replace(
+        "self.balance.write(self.balance.read() + amount);",
+        "self.balance.write(0);\n            self.balance.write(self.balance.read() + amount);",
+    );
+    contract.write_str(&content).unwrap();
+}
+
+#[test]
+fn snapshot_is_written() {
+    let temp = setup_package("simple_package");
+
+    write_snapshot(&temp);
+
+    let snapshot = fs::read_to_string(temp.child(".snfoundry/gas_snapshot.json")).unwrap();
+    let snapshot: serde_json::Value = serde_json::from_str(&snapshot).unwrap();
+    let entry = &snapshot["tests"][TEST_NAME];
+    assert!(entry["gas"].as_u64().unwrap() > 0);
+    assert!(entry["sierra_gas_consumed"].as_u64().unwrap() > 0);
+    assert!(entry["steps"].as_u64().unwrap() > 0);
+    assert!(entry["builtins"].is_object());
+}
+
+#[test]
+fn check_passes_without_changes() {
+    let temp = setup_package("simple_package");
+    write_snapshot(&temp);
+
+    let output = test_runner(&temp)
+        .args([TEST_NAME, "--exact", "--gas-snapshot", "check"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Gas snapshot check (tolerance 0.00%):
+                No gas regressions
+        "},
+    );
+}
+
+#[test]
+fn check_fails_when_gas_increased() {
+    let temp = setup_package("simple_package");
+    write_snapshot(&temp);
+    increase_gas_of_contract(&temp);
+
+    let output = test_runner(&temp)
+        .args([TEST_NAME, "--exact", "--gas-snapshot", "check"])
+        .assert()
+        .code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            [PASS] simple_package_integrationtest::contract::call_and_invoke [..]
+            Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, other filtered out
+
+            Gas snapshot check (tolerance 0.00%):
+                Gas increased above tolerance:
+                    simple_package_integrationtest::contract::call_and_invoke: [..] -> [..] (+[..]%)
+        "},
+    );
+}
+
+#[test]
+fn check_passes_when_gas_increased_within_tolerance() {
+    let temp = setup_package("simple_package");
+    write_snapshot(&temp);
+    increase_gas_of_contract(&temp);
+
+    let output = test_runner(&temp)
+        .args([TEST_NAME, "--exact", "--gas-snapshot", "check"])
+        .args(["--tolerance", "1000%"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Gas snapshot check (tolerance 1000.00%):
+                No gas regressions
+        "},
+    );
+}
+
+#[test]
+fn improvements_are_reported() {
+    let temp = setup_package("simple_package");
+    let contract = temp.child("src/hello_starknet.cairo");
+    let original_content = fs::read_to_string(contract.path()).unwrap();
+    increase_gas_of_contract(&temp);
+    write_snapshot(&temp);
+    contract.write_str(&original_content).unwrap();
+
+    let output = test_runner(&temp)
+        .args([TEST_NAME, "--exact", "--gas-snapshot", "check"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Gas snapshot check (tolerance 0.00%):
+                No gas regressions
+                Gas decreased:
+                    simple_package_integrationtest::contract::call_and_invoke: [..] -> [..] (-[..]%)
+        "},
+    );
+}
+
+#[test]
+fn new_tests_are_reported_without_failing() {
+    let temp = setup_package("empty");
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r"
+            #[test]
+            fn existing() {
+                assert(1 + 1 == 2, 'wrong sum');
+            }
+        "})
+        .unwrap();
+    test_runner(&temp)
+        .args(["--gas-snapshot", "write"])
+        .assert()
+        .success();
+
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r"
+            #[test]
+            fn existing() {
+                assert(1 + 1 == 2, 'wrong sum');
+            }
+
+            #[test]
+            fn added() {
+                assert(2 + 2 == 4, 'wrong sum');
+            }
+        "})
+        .unwrap();
+    let output = test_runner(&temp)
+        .args(["--gas-snapshot", "check"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Gas snapshot check (tolerance 0.00%):
+                No gas regressions
+                Tests missing from the snapshot:
+                    empty_integrationtest::test::added
+        "},
+    );
+}
+
+#[test]
+fn check_without_snapshot() {
+    let temp = setup_package("empty");
+
+    let output = test_runner(&temp)
+        .args(["--gas-snapshot", "check"])
+        .assert()
+        .code(2);
+
+    assert!(output
+        .as_stdout()
+        .contains("create it with `snforge test --gas-snapshot write`"));
+}
+
+#[test]
+fn tolerance_requires_gas_snapshot() {
+    let temp = setup_package("empty");
+
+    test_runner(&temp)
+        .args(["--tolerance", "2%"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn sierra_gas_snapshot_is_separate() {
+    let temp = setup_package("simple_package");
+    write_snapshot(&temp);
+
+    test_runner(&temp)
+        .args([TEST_NAME, "--exact", "--gas-snapshot", "write"])
+        .args(["--tracked-resource", "sierra-gas"])
+        .assert()
+        .success();
+
+    let snapshot =
+        fs::read_to_string(temp.child(".snfoundry/gas_snapshot_sierra_gas.json")).unwrap();
+    let snapshot: serde_json::Value = serde_json::from_str(&snapshot).unwrap();
+    assert_eq!(snapshot["tracked_resource"], "sierra_gas");
+    let entry = &snapshot["tests"][TEST_NAME];
+    assert_eq!(entry["gas"], entry["sierra_gas_consumed"]);
+
+    let snapshot = fs::read_to_string(temp.child(".snfoundry/gas_snapshot.json")).unwrap();
+    let snapshot: serde_json::Value = serde_json::from_str(&snapshot).unwrap();
+    assert_eq!(snapshot["tracked_resource"], "cairo_steps");
+}
+
+#[test]
+fn sierra_gas_requires_tests_compiled_with_gas() {
+    let temp = setup_package("empty");
+    let manifest_path = temp.child("Scarb.toml");
+    let mut scarb_toml = fs::read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<DocumentMut>()
+        .unwrap();
+    scarb_toml["cairo"]["enable-gas"] = value(false);
+    manifest_path.write_str(&scarb_toml.to_string()).unwrap();
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r"
+            #[test]
+            fn passing() {
+                assert(1 + 1 == 2, 'wrong sum');
+            }
+        "})
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .args(["--tracked-resource", "sierra-gas"])
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            [ERROR] `--tracked-resource sierra-gas` requires tests compiled with gas, but tests of package empty in tests/ were compiled without it. Remove `enable-gas = false` from the `[cairo]` section of Scarb.toml
+        "},
+    );
+}
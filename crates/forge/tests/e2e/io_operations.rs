@@ -16,8 +16,8 @@ fn file_reading() {
         [..]Finished[..]
         
         Collected 11 test(s) from file_reading package
-        Running 0 test(s) from src/
-        Running 11 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 11 integration test(s) from tests/
         [FAIL] file_reading_integrationtest::test::json_non_existent
         
         Failure data:
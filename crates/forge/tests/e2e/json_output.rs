@@ -0,0 +1,72 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::assert_stderr_contains;
+use snapbox::assert_matches;
+
+#[test]
+fn json_event_stream() {
+    let temp = setup_package("json_output");
+    let output = test_runner(&temp).arg("--json").assert().code(1);
+
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    assert_matches(
+        indoc! {r#"
+            {"schema_version":1,"type":"suite_started","package":"json_output","test_count":4,"filtered_out":0,"test_order":"definition","shuffle_seed":null}
+            {"schema_version":1,"type":"test_started","name":"json_output_integrationtest::test_json::expected_panic","kind":"integration"}
+            {"schema_version":1,"type":"test_started","name":"json_output_integrationtest::test_json::failing","kind":"integration"}
+            {"schema_version":1,"type":"test_started","name":"json_output_integrationtest::test_json::fuzzed","kind":"integration"}
+            {"schema_version":1,"type":"test_started","name":"json_output_integrationtest::test_json::passing","kind":"integration"}
+            {"schema_version":1,"type":"test_finished","name":"json_output_integrationtest::test_json::expected_panic","kind":"integration","status":"passed","duration_ms":[..],"gas":[..],"resources":{"steps":[..],"memory_holes":[..],"gas":[..],"tracked_resource":"cairo_steps","sierra_gas_consumed":[..],"builtins":{[..]},"syscalls":{[..]},"events":[..],"events_size":[..],"l2_to_l1_messages":[..],"l2_to_l1_payload_size":[..]},"message":null,"fuzzer":null,"ignore_reason":null,"captured_output":null}
+            {"schema_version":1,"type":"test_finished","name":"json_output_integrationtest::test_json::failing","kind":"integration","status":"failed","duration_ms":[..],"gas":null,"resources":null,"message":"[..]0x77726f6e672073756d ('wrong sum')[..]","fuzzer":null,"ignore_reason":null,"captured_output":null}
+            {"schema_version":1,"type":"test_finished","name":"json_output_integrationtest::test_json::fuzzed","kind":"integration","status":"passed","duration_ms":[..],"gas":{"min":[..],"max":[..],"mean":[..],"std_deviation":[..]},"resources":null,"message":null,"fuzzer":{"runs":10,"seed":100},"ignore_reason":null,"captured_output":null}
+            {"schema_version":1,"type":"test_finished","name":"json_output_integrationtest::test_json::passing","kind":"integration","status":"passed","duration_ms":[..],"gas":[..],"resources":{"steps":[..],"memory_holes":[..],"gas":[..],"tracked_resource":"cairo_steps","sierra_gas_consumed":[..],"builtins":{[..]},"syscalls":{[..]},"events":[..],"events_size":[..],"l2_to_l1_messages":[..],"l2_to_l1_payload_size":[..]},"message":null,"fuzzer":null,"ignore_reason":null,"captured_output":null}
+            {"schema_version":1,"type":"suite_finished","package":"json_output","passed":3,"failed":1,"skipped":0,"ignored":0,"filtered_out":0,"duration_ms":[..],"resources":{"steps":[..],"memory_holes":[..],"gas":[..],"tracked_resource":"cairo_steps","sierra_gas_consumed":[..],"builtins":{[..]},"syscalls":{[..]},"events":[..],"events_size":[..],"l2_to_l1_messages":[..],"l2_to_l1_payload_size":[..]}}
+        "#},
+        normalize_events(&stdout),
+    );
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+            Collected 4 test(s) from json_output package
+            Running 4 integration test(s) from tests/
+            [FAIL] json_output_integrationtest::test_json::failing
+            Tests: 3 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+/// Tests run concurrently, so events of different tests are sorted by the test names.
+/// Checks that the stream has the order of a suite, i.e. all tests start before any of them finishes.
+fn normalize_events(stdout: &str) -> String {
+    let mut events: Vec<(usize, String, &str)> = stdout
+        .lines()
+        .map(|line| {
+            let event: Value = serde_json::from_str(line)
+                .unwrap_or_else(|_| panic!("Line is not a JSON event: {line}"));
+            let rank = match event["type"].as_str().unwrap() {
+                "suite_started" => 0,
+                "test_started" => 1,
+                "test_finished" => 2,
+                "suite_finished" => 3,
+                other => panic!("Unexpected event type {other}"),
+            };
+            let name = event["name"].as_str().unwrap_or_default().to_string();
+
+            (rank, name, line)
+        })
+        .collect();
+
+    assert!(
+        events.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+        "Events are out of order:\n{stdout}"
+    );
+
+    events.sort();
+    events
+        .into_iter()
+        .map(|(_, _, line)| format!("{line}\n"))
+        .collect()
+}
@@ -0,0 +1,98 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::PathChild;
+use camino::Utf8PathBuf;
+use indoc::indoc;
+use snapbox::assert_matches;
+use snapbox::cmd::Command as SnapboxCommand;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn report_with_mixed_outcomes() {
+    let temp = setup_package("junit_report");
+    test_runner(&temp)
+        .args(["--junit-path", "reports/junit.xml"])
+        .assert()
+        .code(1);
+
+    let report_path = temp.child("reports/junit.xml");
+    assert_valid_junit(report_path.path());
+    assert_matches(
+        indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites tests="5" failures="1" errors="0" skipped="1" time="[..]">
+              <testsuite name="junit_report" package="junit_report" tests="1" failures="0" errors="0" skipped="0" time="[..]">
+                <testcase name="adds_zero" classname="junit_report::tests" time="[..]"/>
+              </testsuite>
+              <testsuite name="junit_report_integrationtest" package="junit_report" tests="4" failures="1" errors="0" skipped="1" time="[..]">
+                <testcase name="failing" classname="junit_report_integrationtest::test_junit" time="[..]">
+                  <failure message="0x77726f6e672073756d (&apos;wrong sum&apos;)" type="panic">0x77726f6e672073756d (&apos;wrong sum&apos;)[..]
+            ...
+                </testcase>
+                <testcase name="fuzzed" classname="junit_report_integrationtest::test_junit" time="[..]">
+                  <properties>
+                    <property name="fuzzer_runs" value="10"/>
+                    <property name="fuzzer_seed" value="100"/>
+                  </properties>
+                </testcase>
+                <testcase name="ignored" classname="junit_report_integrationtest::test_junit" time="[..]">
+                  <skipped message="flaky"/>
+                </testcase>
+                <testcase name="passing" classname="junit_report_integrationtest::test_junit" time="[..]"/>
+              </testsuite>
+            </testsuites>
+        "#},
+        fs::read_to_string(report_path).unwrap(),
+    );
+}
+
+#[test]
+fn report_with_partition() {
+    let temp = setup_package("junit_report");
+    test_runner(&temp)
+        .args(["--partition", "2/2", "--junit-path", "junit.xml"])
+        .assert()
+        .success();
+
+    let report_path = temp.child("junit.xml");
+    assert_valid_junit(report_path.path());
+    assert_matches(
+        indoc! {r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <testsuites tests="5" failures="0" errors="0" skipped="4" time="[..]">
+              <testsuite name="junit_report" package="junit_report" tests="1" failures="0" errors="0" skipped="1" time="[..]">
+                <testcase name="adds_zero" classname="junit_report::tests" time="0.000">
+                  <skipped message="Not in partition 2/2"/>
+                </testcase>
+              </testsuite>
+              <testsuite name="junit_report_integrationtest" package="junit_report" tests="4" failures="0" errors="0" skipped="3" time="[..]">
+                <testcase name="failing" classname="junit_report_integrationtest::test_junit" time="0.000">
+                  <skipped message="Not in partition 2/2"/>
+                </testcase>
+                <testcase name="fuzzed" classname="junit_report_integrationtest::test_junit" time="0.000">
+                  <skipped message="Not in partition 2/2"/>
+                </testcase>
+                <testcase name="ignored" classname="junit_report_integrationtest::test_junit" time="0.000">
+                  <skipped message="Not in partition 2/2"/>
+                </testcase>
+                <testcase name="passing" classname="junit_report_integrationtest::test_junit" time="[..]"/>
+              </testsuite>
+            </testsuites>
+        "#},
+        fs::read_to_string(report_path).unwrap(),
+    );
+}
+
+fn assert_valid_junit(report_path: &Path) {
+    let schema_path = Utf8PathBuf::from("tests/data/junit.xsd")
+        .canonicalize_utf8()
+        .unwrap();
+
+    SnapboxCommand::new("xmllint")
+        .arg("--noout")
+        .arg("--schema")
+        .arg(schema_path.as_str())
+        .arg(report_path)
+        .assert()
+        .success();
+}
@@ -2,19 +2,31 @@ pub(crate) mod common;
 
 mod build_profile;
 mod build_trace_data;
+mod captured_output;
+mod check;
 mod collection;
 mod color;
 mod components;
 mod contract_artifacts;
 mod coverage;
+mod dump_abis;
+mod durations;
 mod env;
 mod features;
 mod fork_warning;
 mod forking;
 mod fuzzing;
+mod gas_snapshot;
 mod io_operations;
+mod json_output;
+mod junit_report;
+mod report_json;
 mod running;
+mod scheduling;
 mod steps;
+mod test_kinds;
+mod test_order;
 mod trace_print;
 mod trace_resources;
+mod watch;
 mod workspaces;
@@ -0,0 +1,56 @@
+use super::common::runner::{setup_hello_workspace, setup_package, test_runner};
+use assert_fs::fixture::PathChild;
+use std::fs;
+
+#[test]
+fn report_with_tests_and_contracts() {
+    let temp = setup_package("simple_package");
+    test_runner(&temp)
+        .args(["--report-json", "reports/report.json"])
+        .assert()
+        .code(1);
+
+    let report = fs::read_to_string(temp.child("reports/report.json")).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    assert_eq!(report["schema_version"], 1);
+
+    let packages = report["packages"].as_array().unwrap();
+    assert_eq!(packages.len(), 1);
+    assert_eq!(packages[0]["name"], "simple_package");
+
+    let tests = &packages[0]["tests"];
+    let passing = &tests["simple_package_integrationtest::test_simple::test_simple"];
+    assert!(passing["steps"].as_u64().unwrap() > 0);
+    assert_eq!(passing["tracked_resource"], "cairo_steps");
+    assert!(passing["builtins"].is_object());
+    assert!(tests["simple_package_integrationtest::test_simple::test_failing"].is_null());
+
+    let contracts = packages[0]["contracts"].as_array().unwrap();
+    assert_eq!(contracts.len(), 1);
+    assert_eq!(contracts[0]["contract_name"], "HelloStarknet");
+    assert!(contracts[0]["sierra_length"].as_u64().unwrap() > 0);
+    assert!(contracts[0]["bytecode_size"].as_u64().unwrap() > 0);
+}
+
+#[test]
+fn report_of_workspace_inside_package() {
+    let temp = setup_hello_workspace();
+    test_runner(&temp)
+        .current_dir(temp.join("crates/fibonacci"))
+        .args(["--workspace", "--report-json", "report.json"])
+        .assert()
+        .code(1);
+
+    // Relative path is resolved from the directory snforge was run in, not from the root of each package
+    let report = fs::read_to_string(temp.child("crates/fibonacci/report.json")).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+
+    let package_names: Vec<_> = report["packages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|package| package["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(package_names, ["addition", "fibonacci", "hello_workspaces"]);
+    assert!(report["packages"][0]["tests"]["addition::tests::it_works"].is_object());
+}
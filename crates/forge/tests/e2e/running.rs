@@ -7,7 +7,7 @@ use camino::Utf8PathBuf;
 use forge::scarb::config::SCARB_MANIFEST_TEMPLATE_CONTENT;
 use forge::CAIRO_EDITION;
 use indoc::{formatdoc, indoc};
-use shared::test_utils::output_assert::assert_stdout_contains;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains, AsOutput};
 use snapbox::assert_matches;
 use snapbox::cmd::Command as SnapboxCommand;
 use std::ffi::OsString;
@@ -28,10 +28,10 @@ fn simple_package() {
 
 
     Collected 13 test(s) from simple_package package
-    Running 2 test(s) from src/
+    Running 2 unit test(s) from src/
     [PASS] simple_package::tests::test_fib [..]
     [IGNORE] simple_package::tests::ignored_test
-    Running 11 test(s) from tests/
+    Running 11 integration test(s) from tests/
     [PASS] simple_package_integrationtest::contract::call_and_invoke [..]
     [PASS] simple_package_integrationtest::ext_function_test::test_my_test [..]
     [IGNORE] simple_package_integrationtest::ext_function_test::ignored_test
@@ -99,10 +99,10 @@ fn simple_package_with_git_dependency() {
 
 
         Collected 13 test(s) from simple_package package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] simple_package::tests::test_fib [..]
         [IGNORE] simple_package::tests::ignored_test
-        Running 11 test(s) from tests/
+        Running 11 integration test(s) from tests/
         [PASS] simple_package_integrationtest::contract::call_and_invoke [..]
         [PASS] simple_package_integrationtest::ext_function_test::test_my_test [..]
         [IGNORE] simple_package_integrationtest::ext_function_test::ignored_test
@@ -171,7 +171,8 @@ fn with_filter() {
 
 
         Collected 2 test(s) from simple_package package
-        Running 2 test(s) from tests/
+        Filter `two` matched 2 test(s)
+        Running 2 integration test(s) from tests/
         [PASS] simple_package_integrationtest::test_simple::test_two [..]
         [PASS] simple_package_integrationtest::test_simple::test_two_and_two [..]
         Tests: 2 passed, 0 failed, 0 skipped, 0 ignored, 11 filtered out
@@ -196,7 +197,7 @@ fn with_filter_matching_module() {
 
 
         Collected 3 test(s) from simple_package package
-        Running 3 test(s) from tests/
+        Running 3 integration test(s) from tests/
         [PASS] simple_package_integrationtest::ext_function_test::test_my_test [..]
         [IGNORE] simple_package_integrationtest::ext_function_test::ignored_test
         [PASS] simple_package_integrationtest::ext_function_test::test_simple [..]
@@ -223,8 +224,8 @@ fn with_exact_filter() {
 
 
         Collected 1 test(s) from simple_package package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [PASS] simple_package_integrationtest::test_simple::test_two [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, other filtered out
         "},
@@ -249,8 +250,8 @@ fn with_exact_filter_and_duplicated_test_names() {
 
 
         Collected 1 test(s) from duplicated_test_names package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [PASS] duplicated_test_names_integrationtest::tests_a::test_simple [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, other filtered out
         "},
@@ -271,8 +272,109 @@ fn with_non_matching_filter() {
 
 
         Collected 0 test(s) from simple_package package
-        Running 0 test(s) from src/
-        Running 0 test(s) from tests/
+        Filter `qwerty` matched 0 test(s)
+        Running 0 unit test(s) from src/
+        Running 0 integration test(s) from tests/
+        Tests: 0 passed, 0 failed, 0 skipped, 0 ignored, 13 filtered out
+        "},
+    );
+}
+
+#[test]
+fn with_filter_regex() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .arg("--filter-regex")
+        .arg("test_simple::test_two$")
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from simple_package package
+        Regex filter `test_simple::test_two$` matched 1 test(s)
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
+        [PASS] simple_package_integrationtest::test_simple::test_two [..]
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 12 filtered out
+        "},
+    );
+}
+
+#[test]
+fn with_filter_regex_and_exact() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .arg("--filter-regex")
+        .arg("two")
+        .arg("--exact")
+        .assert()
+        .code(2);
+
+    assert_stderr_contains(
+        output,
+        "error: the argument '--filter-regex <REGEX>' cannot be used with '--exact'",
+    );
+}
+
+#[test]
+fn with_skip_flag() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .arg("two")
+        .arg("--skip")
+        .arg("and_two")
+        .arg("--skip")
+        .arg("qwerty")
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from simple_package package
+        Filter `two` matched 2 test(s)
+        Skip filter `and_two` matched 1 test(s)
+        Skip filter `qwerty` matched 0 test(s)
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
+        [PASS] simple_package_integrationtest::test_simple::test_two [..]
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 12 filtered out
+        "},
+    );
+}
+
+#[test]
+fn with_deny_no_matches_flag() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .arg("qwerty")
+        .arg("--deny-no-matches")
+        .assert()
+        .code(3);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 0 test(s) from simple_package package
+        Filter `qwerty` matched 0 test(s)
         Tests: 0 passed, 0 failed, 0 skipped, 0 ignored, 13 filtered out
         "},
     );
@@ -292,9 +394,9 @@ fn with_ignored_flag() {
 
 
         Collected 2 test(s) from simple_package package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] simple_package::tests::ignored_test [..]
-        Running 1 test(s) from tests/
+        Running 1 integration test(s) from tests/
         [FAIL] simple_package_integrationtest::ext_function_test::ignored_test
 
         Failure data:
@@ -322,10 +424,10 @@ fn with_include_ignored_flag() {
 
 
         Collected 13 test(s) from simple_package package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] simple_package::tests::test_fib [..]
         [PASS] simple_package::tests::ignored_test [..]
-        Running 11 test(s) from tests/
+        Running 11 integration test(s) from tests/
         [PASS] simple_package_integrationtest::contract::call_and_invoke [..]
         [PASS] simple_package_integrationtest::ext_function_test::test_my_test [..]
         [FAIL] simple_package_integrationtest::ext_function_test::ignored_test
@@ -377,8 +479,8 @@ fn with_ignored_flag_and_filter() {
 
 
         Collected 1 test(s) from simple_package package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [FAIL] simple_package_integrationtest::ext_function_test::ignored_test
 
         Failure data:
@@ -410,9 +512,9 @@ fn with_include_ignored_flag_and_filter() {
 
 
         Collected 2 test(s) from simple_package package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] simple_package::tests::ignored_test [..]
-        Running 1 test(s) from tests/
+        Running 1 integration test(s) from tests/
         [FAIL] simple_package_integrationtest::ext_function_test::ignored_test
 
         Failure data:
@@ -426,12 +528,137 @@ fn with_include_ignored_flag_and_filter() {
     );
 }
 
+#[test]
+fn with_ignore_reason() {
+    let temp = setup_package("simple_package");
+    temp.child("tests/ignored_with_reason.cairo")
+        .write_str(indoc!(
+            r#"
+                #[test]
+                #[ignore("flaky until #123")]
+                fn flaky_test() {
+                    assert(1 == 2, 'not passing');
+                }
+            "#
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp)
+        .arg("ignored_with_reason")
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from simple_package package
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
+        [IGNORE] simple_package_integrationtest::ignored_with_reason::flaky_test (reason: flaky until #123)
+        Tests: 0 passed, 0 failed, 0 skipped, 1 ignored, 13 filtered out
+        "},
+    );
+
+    let last_run = fs::read_to_string(temp.child(".snfoundry/last_run.json")).unwrap();
+    let last_run: serde_json::Value = serde_json::from_str(&last_run).unwrap();
+    let flaky_test = last_run["tests"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|test| {
+            test["name"] == "simple_package_integrationtest::ignored_with_reason::flaky_test"
+        })
+        .unwrap();
+
+    assert_eq!(flaky_test["status"], "ignored");
+    assert_eq!(flaky_test["ignore_reason"], "flaky until #123");
+}
+
+#[test]
+fn with_partitions() {
+    let temp = setup_package("simple_package");
+
+    let mut names = vec![];
+    for index in 1..=3 {
+        let output = test_runner(&temp)
+            .arg("--include-ignored")
+            .arg("--partition")
+            .arg(format!("{index}/3"))
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8(output.stdout).unwrap();
+
+        assert!(stdout.contains(&format!("Partition {index}/3 selected")));
+        names.extend(run_test_names(&stdout));
+    }
+    names.sort();
+
+    let output = test_runner(&temp)
+        .arg("--include-ignored")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut all_names = run_test_names(&stdout);
+    all_names.sort();
+
+    assert_eq!(all_names.len(), 13);
+    assert_eq!(names, all_names);
+}
+
+fn run_test_names(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("[PASS] ")
+                .or_else(|| line.strip_prefix("[FAIL] "))
+                .map(|rest| rest.split_whitespace().next().unwrap().to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn with_invalid_partition() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .arg("--partition")
+        .arg("4/3")
+        .assert()
+        .code(2);
+
+    assert_stderr_contains(
+        output,
+        "error: invalid value '4/3' for '--partition <INDEX/TOTAL>': Partition index must be between 1 and 3, got 4",
+    );
+
+    let output = test_runner(&temp)
+        .arg("--partition")
+        .arg("1/0")
+        .assert()
+        .code(2);
+
+    assert_stderr_contains(
+        output,
+        "error: invalid value '1/0' for '--partition <INDEX/TOTAL>': Partition total must be greater than 0",
+    );
+}
+
 #[test]
 fn with_rerun_failed_flag_without_cache() {
     let temp = setup_package("simple_package");
 
     let output = test_runner(&temp).arg("--rerun-failed").assert().code(1);
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        indoc! {r"
+        [WARNING] W0009: No results of the previous run found, running all tests
+        "},
+    );
     assert_stdout_contains(
         output,
         indoc! {r"
@@ -440,9 +667,9 @@ fn with_rerun_failed_flag_without_cache() {
 
 
         Collected 13 test(s) from simple_package package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] simple_package::tests::test_fib [..]
-        Running 11 test(s) from tests/
+        Running 11 integration test(s) from tests/
         [PASS] simple_package_integrationtest::contract::call_and_invoke [..]
         [PASS] simple_package_integrationtest::ext_function_test::test_my_test [..]
 
@@ -472,6 +699,79 @@ fn with_rerun_failed_flag_without_cache() {
     );
 }
 
+#[test]
+fn saves_last_run_results() {
+    let temp = setup_package("simple_package");
+
+    test_runner(&temp).assert().code(1);
+
+    let last_run = fs::read_to_string(temp.child(".snfoundry/last_run.json")).unwrap();
+    let last_run: serde_json::Value = serde_json::from_str(&last_run).unwrap();
+    let tests = last_run["tests"].as_array().unwrap();
+    let status_of = |name: &str| {
+        tests
+            .iter()
+            .find(|test| test["name"] == name)
+            .map(|test| test["status"].as_str().unwrap().to_string())
+    };
+
+    assert_eq!(tests.len(), 13);
+    assert!(tests
+        .iter()
+        .all(|test| test["package"] == "simple_package" && test["duration_ms"].is_u64()));
+    assert_eq!(
+        status_of("simple_package_integrationtest::test_simple::test_failing").as_deref(),
+        Some("failed")
+    );
+    assert_eq!(
+        status_of("simple_package::tests::test_fib").as_deref(),
+        Some("passed")
+    );
+    assert_eq!(
+        status_of("simple_package::tests::ignored_test").as_deref(),
+        Some("ignored")
+    );
+}
+
+#[test]
+fn with_rerun_failed_flag_and_removed_test() {
+    let temp = setup_package("simple_package");
+
+    test_runner(&temp).assert().code(1);
+
+    let last_run = temp.child(".snfoundry/last_run.json");
+    let content = fs::read_to_string(&last_run)
+        .unwrap()
+        .replace("test_simple::test_failing", "test_simple::test_removed");
+    last_run.write_str(&content).unwrap();
+
+    let output = test_runner(&temp).arg("--rerun-failed").assert().code(1);
+
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        indoc! {r"
+        [WARNING] W0010: Test simple_package_integrationtest::test_simple::test_removed failed during the last run but no longer exists, skipping it
+        "},
+    );
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+        Collected 1 test(s) from simple_package package
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
+        [FAIL] simple_package_integrationtest::test_simple::test_another_failing
+
+        Failure data:
+            0x6661696c696e6720636865636b ('failing check')
+
+        Tests: 0 passed, 1 failed, 0 skipped, 0 ignored, 12 filtered out
+        "},
+    );
+}
+
 #[test]
 fn with_rerun_failed_flag_and_name_filter() {
     let temp = setup_package("simple_package");
@@ -491,7 +791,7 @@ fn with_rerun_failed_flag_and_name_filter() {
         [..]Finished[..]
 
         Collected 1 test(s) from simple_package package
-        Running 1 test(s) from tests/
+        Running 1 integration test(s) from tests/
         [FAIL] simple_package_integrationtest::test_simple::test_another_failing
 
         Failure data:
@@ -521,8 +821,8 @@ fn with_rerun_failed_flag() {
         [..]Finished[..]
 
         Collected 2 test(s) from simple_package package
-        Running 0 test(s) from src/
-        Running 2 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 2 integration test(s) from tests/
         [FAIL] simple_package_integrationtest::test_simple::test_another_failing
 
         Failure data:
@@ -543,6 +843,72 @@ fn with_rerun_failed_flag() {
     );
 }
 
+#[test]
+fn with_only_changed_flag() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).arg("--only-changed").assert().code(1);
+
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        indoc! {r"
+        [WARNING] W0009: No hashes of the previous run found, running all test targets
+        "},
+    );
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Collected 13 test(s) from simple_package package
+        Running 2 unit test(s) from src/
+        Running 11 integration test(s) from tests/
+        "},
+    );
+
+    // Tests in `src/` passed, so only the failing `tests/` are run again
+    let output = test_runner(&temp).arg("--only-changed").assert().code(1);
+
+    assert!(!output.as_stdout().contains("from src/"));
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Collected 11 test(s) from simple_package package
+        Skipped unchanged test targets: src/
+        Running 11 integration test(s) from tests/
+        Tests: 8 passed, 2 failed, 0 skipped, 1 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn with_only_changed_flag_and_changed_target() {
+    let temp = setup_package("simple_package");
+
+    test_runner(&temp).arg("--only-changed").assert().code(1);
+
+    let hashes = temp.child(".snfoundry/target_hashes.json");
+    let content = fs::read_to_string(&hashes).unwrap();
+    assert!(content.contains("simple_package::src"));
+    assert!(!content.contains("simple_package::tests"));
+
+    // Any change of the compiled tests or contracts makes the target run again
+    let content = content.replace(
+        "simple_package::src\": \"",
+        "simple_package::src\": \"changed",
+    );
+    hashes.write_str(&content).unwrap();
+
+    let output = test_runner(&temp).arg("--only-changed").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Collected 13 test(s) from simple_package package
+        Running 2 unit test(s) from src/
+        Running 11 integration test(s) from tests/
+        "},
+    );
+}
+
 #[test]
 fn with_panic_data_decoding() {
     let temp = setup_package("panic_decoding");
@@ -557,7 +923,7 @@ fn with_panic_data_decoding() {
 
 
         Collected 8 test(s) from panic_decoding package
-        Running 8 test(s) from tests/
+        Running 8 integration test(s) from tests/
         [FAIL] panic_decoding_integrationtest::test_panic_decoding::test_panic_decoding2
 
         Failure data:
@@ -644,13 +1010,14 @@ fn with_exit_first() {
 
 
         Collected 2 test(s) from exit_first package
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] exit_first_integrationtest::ext_function_test::simple_test
 
         Failure data:
             0x73696d706c6520636865636b ('simple check')
 
-        Tests: 0 passed, 1 failed, 1 skipped, 0 ignored, 0 filtered out
+        [PASS] exit_first_integrationtest::ext_function_test::hard_test [..]
+        Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
 
         Failures:
             exit_first_integrationtest::ext_function_test::simple_test
@@ -672,13 +1039,14 @@ fn with_exit_first_flag() {
 
 
         Collected 2 test(s) from exit_first package
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] exit_first_integrationtest::ext_function_test::simple_test
 
         Failure data:
             0x73696d706c6520636865636b ('simple check')
 
-        Tests: 0 passed, 1 failed, 1 skipped, 0 ignored, 0 filtered out
+        [PASS] exit_first_integrationtest::ext_function_test::hard_test [..]
+        Tests: 1 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
 
         Failures:
             exit_first_integrationtest::ext_function_test::simple_test
@@ -686,6 +1054,55 @@ fn with_exit_first_flag() {
     );
 }
 
+#[test]
+fn exit_first_cancels_only_pending_tests() {
+    let temp = setup_package("exit_first");
+    temp.child("src/lib.cairo")
+        .write_str(indoc! {r"
+            fn fib(a: felt252, b: felt252, n: felt252) -> felt252 {
+                match n {
+                    0 => a,
+                    _ => fib(b, a + b, n - 1),
+                }
+            }
+
+            #[cfg(test)]
+            mod tests {
+                #[test]
+                fn failing_unit_test() {
+                    assert(1 == 2, 'unit check');
+                }
+            }
+        "})
+        .unwrap();
+
+    let output = test_runner(&temp).arg("--exit-first").assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 3 test(s) from exit_first package
+        Running 1 unit test(s) from src/
+        [FAIL] exit_first::tests::failing_unit_test
+
+        Failure data:
+            0x756e697420636865636b ('unit check')
+
+        Running 2 integration test(s) from tests/
+        [SKIP] exit_first_integrationtest::ext_function_test::hard_test (cancelled)
+        [SKIP] exit_first_integrationtest::ext_function_test::simple_test (cancelled)
+        Tests: 0 passed, 1 failed, 2 skipped, 0 ignored, 0 filtered out
+
+        Failures:
+            exit_first::tests::failing_unit_test
+        "},
+    );
+}
+
 #[test]
 fn init_new_project() {
     let temp = tempdir_with_tool_versions().unwrap();
@@ -696,7 +1113,157 @@ fn init_new_project() {
         .assert()
         .success();
 
-    validate_init(&temp, false);
+    validate_init(&temp.join("test_name"), false);
+}
+
+#[test]
+fn new_project() {
+    let temp = tempdir_with_tool_versions().unwrap();
+
+    runner(&temp)
+        .args(["new", "projects/my-project", "--name", "test_name"])
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .success();
+
+    validate_init(&temp.join("projects/my-project"), false);
+}
+
+#[test]
+fn new_project_existing_directory() {
+    let temp = tempdir_with_tool_versions().unwrap();
+    temp.child("test_name/README.md")
+        .write_str("readme")
+        .unwrap();
+
+    let output = runner(&temp)
+        .args(["new", "test_name"])
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] Destination [..]test_name already exists, run `snforge init` inside it to create a project in an existing directory
+        "},
+    );
+    assert_eq!(
+        fs::read_to_string(temp.join("test_name/README.md")).unwrap(),
+        "readme"
+    );
+}
+
+#[test]
+fn new_project_invalid_name() {
+    let temp = tempdir_with_tool_versions().unwrap();
+
+    let output = runner(&temp)
+        .args(["new", "test-name"])
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] Invalid package name `test-name`, it must start with a lowercase letter or `_` and contain only lowercase letters, digits and `_`
+        "},
+    );
+    assert!(!temp.join("test-name").exists());
+}
+
+#[test]
+fn init_existing_directory() {
+    let temp = tempdir_with_tool_versions().unwrap();
+    temp.child("test_name/README.md")
+        .write_str("readme")
+        .unwrap();
+
+    runner(&temp)
+        .current_dir(temp.join("test_name"))
+        .arg("init")
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read_to_string(temp.join("test_name/README.md")).unwrap(),
+        "readme"
+    );
+    validate_init(&temp.join("test_name"), false);
+}
+
+#[test]
+fn init_existing_directory_refuses_to_overwrite_files() {
+    let temp = tempdir_with_tool_versions().unwrap();
+    temp.child("test_name/src/lib.cairo")
+        .write_str("fn main() {}")
+        .unwrap();
+
+    let output = runner(&temp)
+        .current_dir(temp.join("test_name"))
+        .arg("init")
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] Failed to create a project in [..]test_name, files would be overwritten: src/lib.cairo
+        "},
+    );
+    assert_eq!(
+        fs::read_to_string(temp.join("test_name/src/lib.cairo")).unwrap(),
+        "fn main() {}"
+    );
+    assert!(!temp.join("test_name/Scarb.toml").exists());
+}
+
+#[test]
+fn init_new_project_invalid_name() {
+    let temp = tempdir_with_tool_versions().unwrap();
+
+    let output = runner(&temp)
+        .args(["init", "test-name"])
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] Invalid package name `test-name`, it must start with a lowercase letter or `_` and contain only lowercase letters, digits and `_`
+        "},
+    );
+    assert!(!temp.join("test-name").exists());
+}
+
+#[test]
+fn init_new_project_refuses_to_overwrite_files() {
+    let temp = tempdir_with_tool_versions().unwrap();
+    temp.child("test_name/src/lib.cairo")
+        .write_str("fn main() {}")
+        .unwrap();
+
+    let output = runner(&temp)
+        .args(["init", "test_name"])
+        .env("DEV_DISABLE_SNFORGE_STD_DEPENDENCY", "true")
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] Failed to create a project in [..]test_name, files would be overwritten: src/lib.cairo
+        "},
+    );
+    assert_eq!(
+        fs::read_to_string(temp.join("test_name/src/lib.cairo")).unwrap(),
+        "fn main() {}"
+    );
+    assert!(!temp.join("test_name/Scarb.toml").exists());
 }
 
 #[test]
@@ -714,7 +1281,7 @@ fn init_new_project_from_scarb() {
         .assert()
         .success();
 
-    validate_init(&temp, true);
+    validate_init(&temp.join("test_name"), true);
 }
 
 pub fn append_to_path_var(path: &Path) -> OsString {
@@ -724,8 +1291,8 @@ pub fn append_to_path_var(path: &Path) -> OsString {
     env::join_paths(script_path.chain(other_paths)).unwrap()
 }
 
-fn validate_init(temp: &TempDir, validate_snforge_std: bool) {
-    let manifest_path = temp.join("test_name/Scarb.toml");
+fn validate_init(project_path: &Path, validate_snforge_std: bool) {
+    let manifest_path = project_path.join("Scarb.toml");
     let scarb_toml = fs::read_to_string(manifest_path.clone()).unwrap();
 
     let snforge_std_assert = if validate_snforge_std {
@@ -784,8 +1351,12 @@ fn validate_init(temp: &TempDir, validate_snforge_std: bool) {
 
     std::fs::write(manifest_path, scarb_toml.to_string()).unwrap();
 
-    let output = test_runner(temp)
-        .current_dir(temp.child(Path::new("test_name")))
+    let gitignore = fs::read_to_string(project_path.join(".gitignore")).unwrap();
+    assert!(gitignore.lines().any(|line| line == ".snfoundry_cache/"));
+
+    let output = SnapboxCommand::new(snforge_test_bin_path())
+        .current_dir(project_path)
+        .arg("test")
         .assert()
         .success();
 
@@ -794,12 +1365,13 @@ fn validate_init(temp: &TempDir, validate_snforge_std: bool) {
         [..]Compiling test_name v0.1.0[..]
         [..]Finished[..]
 
-        Collected 2 test(s) from test_name package
-        Running 0 test(s) from src/
-        Running 2 test(s) from tests/
+        Collected 3 test(s) from test_name package
+        Running 0 unit test(s) from src/
+        Running 3 integration test(s) from tests/
         [PASS] test_name_integrationtest::test_contract::test_increase_balance [..]
         [PASS] test_name_integrationtest::test_contract::test_cannot_increase_balance_with_zero_value [..]
-        Tests: 2 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        [PASS] test_name_integrationtest::test_contract::test_increase_balance_by_any_amount (runs: 256, [..]
+        Tests: 3 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
         "
     );
 
@@ -851,12 +1423,13 @@ fn test_init_project_with_custom_snforge_dependency_git() {
         [..]Compiling test_name v0.1.0[..]
         [..]Finished[..]
 
-        Collected 2 test(s) from test_name package
-        Running 0 test(s) from src/
-        Running 2 test(s) from tests/
+        Collected 3 test(s) from test_name package
+        Running 0 unit test(s) from src/
+        Running 3 integration test(s) from tests/
         [PASS] test_name_integrationtest::test_contract::test_increase_balance [..]
         [PASS] test_name_integrationtest::test_contract::test_cannot_increase_balance_with_zero_value [..]
-        Tests: 2 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        [PASS] test_name_integrationtest::test_contract::test_increase_balance_by_any_amount (runs: 256, [..]
+        Tests: 3 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
         ",
         remote_url.trim_end_matches(".git")
     );
@@ -874,8 +1447,8 @@ fn should_panic() {
         output,
         indoc! { r"
         Collected 14 test(s) from should_panic_test package
-        Running 0 test(s) from src/
-        Running 14 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 14 integration test(s) from tests/
         [FAIL] should_panic_test_integrationtest::should_panic_test::didnt_expect_panic
 
         Failure data:
@@ -979,8 +1552,8 @@ fn printing_in_contracts() {
 
 
         Collected 2 test(s) from contract_printing package
-        Running 0 test(s) from src/
-        Running 2 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 2 integration test(s) from tests/
         Hello world!
         [PASS] contract_printing_integrationtest::test_contract::test_increase_balance [..]
         [PASS] contract_printing_integrationtest::test_contract::test_cannot_increase_balance_with_zero_value [..]
@@ -989,6 +1562,47 @@ fn printing_in_contracts() {
     );
 }
 
+#[test]
+fn incompatible_snforge_std_version_error() {
+    let temp = setup_package("steps");
+    let manifest_path = temp.child("Scarb.toml");
+
+    let mut scarb_toml = fs::read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<DocumentMut>()
+        .unwrap();
+    scarb_toml["dev-dependencies"]["snforge_std"]["path"] = Item::None;
+    scarb_toml["dev-dependencies"]["snforge_std"]["git"] =
+        value("https://github.com/foundry-rs/starknet-foundry.git");
+    scarb_toml["dev-dependencies"]["snforge_std"]["tag"] = value("v0.28.0");
+    manifest_path.write_str(&scarb_toml.to_string()).unwrap();
+
+    let output = test_runner(&temp).assert().code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r#"
+        [ERROR] Package snforge_std 0.28.0 is not compatible with snforge 0.[..], change the snforge_std dependency in [..]Scarb.toml to:
+        snforge_std = { git = "https://github.com/foundry-rs/starknet-foundry.git", tag = "v0.[..]" }
+        Pass `--no-version-check` to run anyway
+        "#},
+    );
+}
+
+#[test]
+fn path_snforge_std_version_not_checked() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp).assert().success();
+
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        indoc! {r"
+        [WARNING] W0013: Package snforge_std is a path dependency, so its version [..] is not checked against snforge [..]
+        "},
+    );
+}
+
 #[test]
 fn incompatible_snforge_std_version_warning() {
     let temp = setup_package("steps");
@@ -1004,29 +1618,40 @@ fn incompatible_snforge_std_version_warning() {
     scarb_toml["dev-dependencies"]["snforge_std"]["tag"] = value("v0.28.0");
     manifest_path.write_str(&scarb_toml.to_string()).unwrap();
 
-    let output = test_runner(&temp).assert().failure();
+    let output = test_runner(&temp)
+        .arg("--no-version-check")
+        .assert()
+        .failure();
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        indoc! {r#"
+        [WARNING] W0003: Package snforge_std 0.28.0 is not compatible with snforge 0.[..], change the snforge_std dependency in [..]Scarb.toml to:
+        snforge_std = { git = "https://github.com/foundry-rs/starknet-foundry.git", tag = "v0.[..]" }
+        "#},
+    );
     assert_stdout_contains(
         output,
         indoc! {r"
         [..]Updating git repository https://github.com/foundry-rs/starknet-foundry
-        [WARNING] Package snforge_std version does not meet the recommended version requirement =0.[..], [..]
         [..]Compiling[..]
         [..]Finished[..]
 
 
         Collected 4 test(s) from steps package
-        Running 4 test(s) from src/
+        Running 4 unit test(s) from src/
         [PASS] steps::tests::steps_570030 [..]
         [FAIL] steps::tests::steps_10000005
 
         Failure data:
-            Could not reach the end of the program. RunResources has no remaining steps.
+            Test ran out of steps after consuming all [..] available steps
+            The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
         [FAIL] steps::tests::steps_11250075
 
         Failure data:
-            Could not reach the end of the program. RunResources has no remaining steps.
+            Test ran out of steps after consuming all [..] available steps
+            The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
         [PASS] steps::tests::steps_9999990 [..]
         Tests: 2 passed, 2 failed, 0 skipped, 0 ignored, 0 filtered out
@@ -1054,19 +1679,100 @@ fn detailed_resources_flag() {
 
 
         Collected 1 test(s) from erc20_package package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [PASS] erc20_package_integrationtest::test_complex::complex[..]
                 steps: [..]
                 memory holes: [..]
                 builtins: ([..])
                 syscalls: ([..])
+                sierra gas: [..]
+
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        Total resources of passed tests:
+                gas: ~[..]
+                sierra gas: [..]
+                steps: [..]
+                memory holes: [..]
+                builtins: ([..])
+                syscalls: ([..])
+                events: [..] (size: [..])
+                messages to L1: 0 (payload size: 0)
+        "},
+    );
+}
+
+fn setup_package_with_max_gas(max_gas: i64) -> TempDir {
+    let temp = setup_package("empty");
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r"
+            #[test]
+            fn keccak_cost() {
+                keccak::keccak_u256s_le_inputs(array![1].span());
+            }
+        "})
+        .unwrap();
+
+    let manifest_path = temp.child("Scarb.toml");
+    let mut scarb_toml = fs::read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<DocumentMut>()
+        .unwrap();
+    scarb_toml["tool"]["snforge"]["max_gas"] = value(max_gas);
+    manifest_path.write_str(&scarb_toml.to_string()).unwrap();
 
+    temp
+}
+
+#[test]
+fn max_gas_not_exceeded() {
+    let temp = setup_package_with_max_gas(11);
+
+    let output = test_runner(&temp).assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [PASS] empty_integrationtest::test::keccak_cost (gas: ~[..])
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
         "},
     );
 }
 
+#[test]
+fn max_gas_exceeded() {
+    let temp = setup_package_with_max_gas(5);
+
+    let output = test_runner(&temp).assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [FAIL] empty_integrationtest::test::keccak_cost
+
+        Failure data:
+        [..]Test cost exceeded max_gas = 5. Consumed gas: ~[..]
+        Tests: 0 passed, 1 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn available_gas_overrides_max_gas() {
+    let temp = setup_package_with_max_gas(5);
+    temp.child("tests/test.cairo")
+        .write_str(indoc! {r"
+            #[test]
+            #[available_gas(11)]
+            fn keccak_cost() {
+                keccak::keccak_u256s_le_inputs(array![1].span());
+            }
+        "})
+        .unwrap();
+
+    test_runner(&temp).assert().success();
+}
+
 #[test]
 fn catch_runtime_errors() {
     let temp = setup_package("simple_package");
@@ -1101,3 +1807,20 @@ fn catch_runtime_errors() {
         ),
     );
 }
+
+#[test]
+fn contracts_filter_matching_nothing() {
+    let temp = setup_package("simple_package");
+
+    let output = test_runner(&temp)
+        .args(["--contracts-filter", "ERC721*"])
+        .assert()
+        .code(2);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [ERROR] No contracts match --contracts-filter = ERC721*, available contracts: [..]
+        "},
+    );
+}
@@ -0,0 +1,151 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use axum::{extract::Path, response::Redirect, routing::any, Router};
+use indoc::{formatdoc, indoc};
+use lazy_static::lazy_static;
+use shared::test_utils::node_url::node_rpc_url;
+use shared::test_utils::output_assert::assert_stdout_contains;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::{
+    net::TcpListener,
+    runtime::{Builder, Runtime},
+};
+
+type StorageReads = Arc<Mutex<HashMap<String, Vec<(Instant, Instant)>>>>;
+
+/// Redirects requests from `/<test name>` to the node, recording when storage reads of each test were made
+fn setup_recording_server(storage_reads: StorageReads) -> SocketAddr {
+    lazy_static! {
+        static ref RT: Runtime = Builder::new_multi_thread().enable_all().build().unwrap();
+    };
+
+    let listener = RT
+        .block_on(TcpListener::bind("127.0.0.1:0"))
+        .expect("Failed to bind the recording server");
+    let address = listener.local_addr().unwrap();
+
+    let app = Router::new().route(
+        "/:test",
+        any(move |Path(test): Path<String>, body: String| {
+            let storage_reads = storage_reads.clone();
+
+            async move {
+                if body.contains("starknet_getStorageAt") {
+                    let started = Instant::now();
+                    // Widens the window in which overlapping tests would be caught
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+
+                    storage_reads
+                        .lock()
+                        .unwrap()
+                        .entry(test)
+                        .or_default()
+                        .push((started, Instant::now()));
+                }
+
+                Redirect::permanent(node_rpc_url().as_str())
+            }
+        }),
+    );
+    RT.spawn(async { axum::serve(listener, app).await.unwrap() });
+
+    address
+}
+
+#[test]
+fn serial_tests_do_not_overlap() {
+    let storage_reads = StorageReads::default();
+    let address = setup_recording_server(storage_reads.clone());
+
+    let temp = setup_package("empty");
+    temp.child("tests/test.cairo")
+        .write_str(&formatdoc!(
+            r#"
+                use snforge_std::load;
+                use starknet::contract_address_const;
+
+                // Reads of different keys are not cached, so each of them is a request to the server
+                fn read_storage(key: felt252) {{
+                    load(
+                        contract_address_const::<
+                            0x202de98471a4fae6bcbabb96cab00437d381abc58b02509043778074d6781e9
+                        >(),
+                        key,
+                        1
+                    );
+                }}
+
+                #[test]
+                #[serial]
+                #[fork(url: "http://{address}/first", block_number: 54060)]
+                fn first() {{
+                    read_storage(1);
+                    read_storage(2);
+                }}
+
+                #[test]
+                #[serial]
+                #[fork(url: "http://{address}/second", block_number: 54060)]
+                fn second() {{
+                    read_storage(1);
+                    read_storage(2);
+                }}
+            "#
+        ))
+        .unwrap();
+
+    let output = test_runner(&temp).assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            [PASS] empty_integrationtest::test::first [..]
+            [PASS] empty_integrationtest::test::second [..]
+            Tests: 2 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+
+    let storage_reads = storage_reads.lock().unwrap();
+    let span = |test: &str| {
+        let reads = &storage_reads[test];
+        assert_eq!(reads.len(), 2, "Expected 2 storage reads of test {test}");
+
+        let started = reads.iter().map(|(started, _)| *started).min().unwrap();
+        let finished = reads.iter().map(|(_, finished)| *finished).max().unwrap();
+        (started, finished)
+    };
+    let (first_started, first_finished) = span("first");
+    let (second_started, second_finished) = span("second");
+
+    assert!(
+        first_finished <= second_started || second_finished <= first_started,
+        "Serial tests were run at the same time"
+    );
+}
+
+#[test]
+fn jobs_are_printed() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp).args(["--jobs", "2"]).assert().code(1);
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Tests: 9 passed, 2 failed, 0 skipped, 2 ignored, 0 filtered out
+            Jobs: 2
+        "},
+    );
+}
+
+#[test]
+fn jobs_from_env() {
+    let temp = setup_package("simple_package");
+    let output = test_runner(&temp).env("SNFORGE_JOBS", "1").assert().code(1);
+
+    assert_stdout_contains(output, "Jobs: 1\n");
+}
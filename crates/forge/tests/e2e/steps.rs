@@ -1,6 +1,6 @@
 use super::common::runner::{setup_package, test_runner};
 use indoc::indoc;
-use shared::test_utils::output_assert::assert_stdout_contains;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains, AsOutput};
 
 #[test]
 fn should_allow_less_than_default() {
@@ -19,26 +19,30 @@ fn should_allow_less_than_default() {
                 [..]Finished[..]
 
                 Collected 4 test(s) from steps package
-                Running 4 test(s) from src/
+                Running 4 unit test(s) from src/
                 [FAIL] steps::tests::steps_570030
 
                 Failure data:
-                    Could not reach the end of the program. RunResources has no remaining steps.
+                    Test ran out of steps after consuming all 100000 available steps
+                    The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
                 [FAIL] steps::tests::steps_11250075
 
                 Failure data:
-                    Could not reach the end of the program. RunResources has no remaining steps.
+                    Test ran out of steps after consuming all 100000 available steps
+                    The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
                 [FAIL] steps::tests::steps_10000005
 
                 Failure data:
-                    Could not reach the end of the program. RunResources has no remaining steps.
+                    Test ran out of steps after consuming all 100000 available steps
+                    The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
                 [FAIL] steps::tests::steps_9999990
 
                 Failure data:
-                    Could not reach the end of the program. RunResources has no remaining steps.
+                    Test ran out of steps after consuming all 100000 available steps
+                    The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
                 Tests: 0 passed, 4 failed, 0 skipped, 0 ignored, 0 filtered out
 
@@ -61,6 +65,12 @@ fn should_allow_more_than_10m() {
         .assert()
         .code(0);
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        indoc! {r"
+                [WARNING] W0005: `max_n_steps` = 15000100 exceeds the maximum of 10000000 steps allowed in a Starknet transaction, the tested code would not be executable on the network
+        "},
+    );
     assert_stdout_contains(
         output,
         indoc!(
@@ -69,7 +79,7 @@ fn should_allow_more_than_10m() {
                 [..]Finished[..]
 
                 Collected 4 test(s) from steps package
-                Running 4 test(s) from src/
+                Running 4 unit test(s) from src/
                 [PASS] steps::tests::steps_570030 (gas: ~1521)
                 [PASS] steps::tests::steps_10000005 (gas: ~26667)
                 [PASS] steps::tests::steps_9999990 (gas: ~26667)
@@ -93,17 +103,19 @@ fn should_default_to_10m() {
             [..]Finished[..]
 
             Collected 4 test(s) from steps package
-            Running 4 test(s) from src/
+            Running 4 unit test(s) from src/
             [PASS] steps::tests::steps_570030 (gas: ~1521)
             [FAIL] steps::tests::steps_10000005
 
             Failure data:
-                Could not reach the end of the program. RunResources has no remaining steps.
+                Test ran out of steps after consuming all [..] available steps
+                The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
             [FAIL] steps::tests::steps_11250075
 
             Failure data:
-                Could not reach the end of the program. RunResources has no remaining steps.
+                Test ran out of steps after consuming all [..] available steps
+                The limit can be raised with `#[available_steps(<steps>)]` attribute, `max_n_steps` in `[tool.snforge]` or `--max-n-steps` flag
 
             [PASS] steps::tests::steps_9999990 (gas: ~26667)
             Tests: 2 passed, 2 failed, 0 skipped, 0 ignored, 0 filtered out
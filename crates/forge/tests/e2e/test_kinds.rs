@@ -0,0 +1,105 @@
+use super::common::runner::{setup_package, test_runner};
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stdout_contains;
+
+#[test]
+fn same_name_in_both_kinds() {
+    let temp = setup_package("test_kinds");
+    let output = test_runner(&temp).arg("tests::adds").assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Collected 3 test(s) from test_kinds package
+            Running 2 unit test(s) from src/
+            [PASS] test_kinds::tests::adds [..]
+            [PASS] test_kinds::tests::adds_zero [..]
+            Running 1 integration test(s) from tests/
+            [PASS] test_kinds_integrationtest::tests::adds [..]
+            Tests: 3 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn kind_prefix_of_filter() {
+    let temp = setup_package("test_kinds");
+    let output = test_runner(&temp)
+        .arg("integration::tests::adds")
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Collected 1 test(s) from test_kinds package
+            Running 0 unit test(s) from src/
+            Running 1 integration test(s) from tests/
+            [PASS] test_kinds_integrationtest::tests::adds [..]
+            Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 2 filtered out
+        "},
+    );
+}
+
+#[test]
+fn kind_prefix_of_exact_filter() {
+    let temp = setup_package("test_kinds");
+    let output = test_runner(&temp)
+        .args(["unit::test_kinds::tests::adds", "--exact"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Collected 1 test(s) from test_kinds package
+            Running 1 unit test(s) from src/
+            [PASS] test_kinds::tests::adds [..]
+            Running 0 integration test(s) from tests/
+            Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, other filtered out
+        "},
+    );
+}
+
+#[test]
+fn only_integration_tests() {
+    let temp = setup_package("test_kinds");
+    let output = test_runner(&temp)
+        .args(["--test-kind", "integration"])
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout).to_string();
+    assert!(!stdout.contains("unit test(s)"));
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Collected 1 test(s) from test_kinds package
+            Running 1 integration test(s) from tests/
+            [PASS] test_kinds_integrationtest::tests::adds [..]
+            Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 0 filtered out
+        "},
+    );
+}
+
+#[test]
+fn kind_in_json_events() {
+    let temp = setup_package("test_kinds");
+    let output = test_runner(&temp)
+        .args(["--json", "--test-kind", "unit"])
+        .assert()
+        .success();
+
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+    let events: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let test_events: Vec<_> = events
+        .iter()
+        .filter(|event| event["type"] == "test_started" || event["type"] == "test_finished")
+        .collect();
+
+    assert_eq!(test_events.len(), 4);
+    assert!(test_events.iter().all(|event| event["kind"] == "unit"));
+}
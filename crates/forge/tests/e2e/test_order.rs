@@ -0,0 +1,141 @@
+use super::common::runner::{setup_package, test_runner};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::{assert_stdout_contains, AsOutput};
+
+const TEST_NAMES: [&str; 8] = [
+    "empty_integrationtest::test::h",
+    "empty_integrationtest::test::c",
+    "empty_integrationtest::test::f",
+    "empty_integrationtest::test::a",
+    "empty_integrationtest::test::g",
+    "empty_integrationtest::test::b",
+    "empty_integrationtest::test::e",
+    "empty_integrationtest::test::d",
+];
+
+fn setup_package_with_tests() -> TempDir {
+    let temp = setup_package("empty");
+    let tests = TEST_NAMES
+        .iter()
+        .map(|name| {
+            let name = name.rsplit("::").next().unwrap();
+            format!("#[test]\nfn {name}() {{\n    assert(1 + 1 == 2, 'wrong sum');\n}}\n")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    temp.child("tests/test.cairo").write_str(&tests).unwrap();
+
+    temp
+}
+
+/// Events of a run with `--json`
+fn run_with_json(temp: &TempDir, args: &[&str]) -> Vec<Value> {
+    let output = test_runner(temp)
+        .arg("--json")
+        .args(args)
+        .assert()
+        .success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+/// Names of the tests in the order they were started
+fn started_tests(events: &[Value]) -> Vec<&str> {
+    events
+        .iter()
+        .filter(|event| event["type"] == "test_started")
+        .map(|event| event["name"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn definition_order_by_default() {
+    let temp = setup_package_with_tests();
+
+    let events = run_with_json(&temp, &[]);
+
+    assert_eq!(started_tests(&events), TEST_NAMES);
+    assert_eq!(events[0]["test_order"], "definition");
+    assert_eq!(events[0]["shuffle_seed"], Value::Null);
+}
+
+#[test]
+fn alphabetical_order() {
+    let temp = setup_package_with_tests();
+
+    let events = run_with_json(&temp, &["--test-order", "alphabetical"]);
+
+    let mut sorted = TEST_NAMES;
+    sorted.sort_unstable();
+    assert_eq!(started_tests(&events), sorted);
+    assert_eq!(events[0]["test_order"], "alphabetical");
+}
+
+#[test]
+fn same_shuffle_seed_gives_same_order() {
+    let temp = setup_package_with_tests();
+    let args = ["--test-order", "shuffle", "--shuffle-seed", "1234"];
+
+    let first_run = run_with_json(&temp, &args);
+    let second_run = run_with_json(&temp, &args);
+
+    assert_eq!(started_tests(&first_run), started_tests(&second_run));
+    assert_eq!(first_run[0]["test_order"], "shuffle");
+    assert_eq!(first_run[0]["shuffle_seed"], 1234);
+
+    let mut started = started_tests(&first_run);
+    started.sort_unstable();
+    let mut sorted = TEST_NAMES;
+    sorted.sort_unstable();
+    assert_eq!(started, sorted);
+}
+
+#[test]
+fn random_shuffle_is_reproduced_with_reported_seed() {
+    let temp = setup_package_with_tests();
+
+    let first_run = run_with_json(&temp, &["--test-order", "shuffle"]);
+    let seed = first_run[0]["shuffle_seed"].as_u64().unwrap().to_string();
+    let second_run = run_with_json(&temp, &["--test-order", "shuffle", "--shuffle-seed", &seed]);
+
+    assert_eq!(started_tests(&first_run), started_tests(&second_run));
+}
+
+#[test]
+fn shuffle_seed_is_printed() {
+    let temp = setup_package_with_tests();
+
+    let output = test_runner(&temp)
+        .args(["--test-order", "shuffle", "--shuffle-seed", "1234"])
+        .assert()
+        .success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            Collected 8 test(s) from empty package
+            Test order: shuffle (seed: 1234)
+        "},
+    );
+}
+
+#[test]
+fn shuffle_seed_requires_shuffle() {
+    let temp = setup_package_with_tests();
+
+    let output = test_runner(&temp)
+        .args(["--shuffle-seed", "1234"])
+        .assert()
+        .code(2);
+
+    assert!(output
+        .as_stdout()
+        .contains("`--shuffle-seed` can only be used with `--test-order shuffle`"));
+}
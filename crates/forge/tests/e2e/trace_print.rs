@@ -6,7 +6,7 @@ use shared::test_utils::output_assert::assert_stdout_contains;
 fn trace_info_print() {
     let temp = setup_package("trace");
 
-    let output = test_runner(&temp).assert().success();
+    let output = test_runner(&temp).arg("--nocapture").assert().success();
 
     assert_stdout_contains(
         output,
@@ -15,8 +15,8 @@ fn trace_info_print() {
         [..]Finished[..]
 
         Collected 1 test(s) from trace_info package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         Entry point type: External
         Selector: [..]
         Calldata: []
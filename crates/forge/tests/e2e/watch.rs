@@ -0,0 +1,134 @@
+use super::common::runner::{runner, setup_hello_workspace, setup_package, snforge_test_bin_path};
+use assert_fs::fixture::{FileWriteStr, PathChild};
+use assert_fs::TempDir;
+use indoc::indoc;
+use shared::test_utils::output_assert::assert_stderr_contains;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+const SUMMARY_TIMEOUT: Duration = Duration::from_secs(300);
+
+fn spawn_watch(temp: &TempDir, args: &[&str]) -> (Child, Receiver<String>) {
+    let mut child = Command::new(snforge_test_bin_path())
+        .current_dir(temp)
+        .arg("test")
+        .arg("--watch")
+        .args(args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let stdout = child.stdout.take().unwrap();
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            if sender.send(line.unwrap()).is_err() {
+                break;
+            }
+        }
+    });
+
+    (child, receiver)
+}
+
+fn next_line_containing(receiver: &Receiver<String>, pattern: &str) -> String {
+    loop {
+        let line = receiver
+            .recv_timeout(SUMMARY_TIMEOUT)
+            .expect("Tests were not run");
+        if line.contains(pattern) {
+            return line;
+        }
+    }
+}
+
+fn next_summary(receiver: &Receiver<String>) -> String {
+    next_line_containing(receiver, "Tests: ")
+}
+
+fn touch_test_file(temp: &TempDir) {
+    let test_file = temp.child("tests/test_simple.cairo");
+    let content = std::fs::read_to_string(&test_file).unwrap();
+    test_file.write_str(&format!("{content}\n")).unwrap();
+}
+
+#[test]
+fn runs_tests_again_after_file_change() {
+    let temp = setup_package("simple_package");
+    let (mut child, receiver) = spawn_watch(&temp, &[]);
+
+    let first_summary = next_summary(&receiver);
+    touch_test_file(&temp);
+    let second_summary = next_summary(&receiver);
+
+    child.kill().unwrap();
+    assert_eq!(
+        first_summary,
+        "Tests: 9 passed, 2 failed, 0 skipped, 2 ignored, 0 filtered out"
+    );
+    assert_eq!(second_summary, first_summary);
+}
+
+#[test]
+fn watch_failed_runs_only_failed_tests_again() {
+    let temp = setup_package("simple_package");
+    let (mut child, receiver) = spawn_watch(&temp, &["--watch-failed"]);
+
+    let first_summary = next_summary(&receiver);
+    touch_test_file(&temp);
+    let second_summary = next_summary(&receiver);
+
+    child.kill().unwrap();
+    assert_eq!(
+        first_summary,
+        "Tests: 9 passed, 2 failed, 0 skipped, 2 ignored, 0 filtered out"
+    );
+    assert!(
+        second_summary.starts_with("Tests: 0 passed, 2 failed"),
+        "{second_summary}"
+    );
+}
+
+#[test]
+fn uses_member_manifest_changed_between_runs() {
+    let temp = setup_hello_workspace();
+    temp.child("crates/addition/tests/fuzzed.cairo")
+        .write_str(indoc! {r"
+            #[test]
+            fn fuzzed(x: felt252) {
+                assert(x == x, 'x == x');
+            }
+        "})
+        .unwrap();
+    let (mut child, receiver) = spawn_watch(&temp, &["--package", "addition"]);
+
+    next_line_containing(&receiver, "Fuzzer seed: ");
+    let member_manifest = temp.child("crates/addition/Scarb.toml");
+    let content = std::fs::read_to_string(&member_manifest).unwrap();
+    member_manifest
+        .write_str(&format!("{content}\n[tool.snforge]\nfuzzer_seed = 1234\n"))
+        .unwrap();
+    let seed = next_line_containing(&receiver, "Fuzzer seed: ");
+
+    child.kill().unwrap();
+    assert!(seed.ends_with("Fuzzer seed: 1234"), "{seed}");
+}
+
+#[test]
+fn watch_failed_requires_watch() {
+    let temp = setup_package("simple_package");
+
+    let output = runner(&temp)
+        .arg("test")
+        .arg("--watch-failed")
+        .assert()
+        .code(2);
+
+    assert_stderr_contains(
+        output,
+        "error: the following required arguments were not provided:\n  --watch",
+    );
+}
@@ -16,9 +16,9 @@ fn root_workspace_without_arguments() {
 
 
         Collected 3 test(s) from hello_workspaces package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] hello_workspaces::tests::test_simple [..]
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] hello_workspaces_integrationtest::test_failing::test_failing
         
         Failure data:
@@ -56,9 +56,9 @@ fn root_workspace_specific_package() {
 
 
         Collected 5 test(s) from addition package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] addition::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] addition_integrationtest::nested::simple_case [..]
         [PASS] addition_integrationtest::nested::contract_test [..]
         [PASS] addition_integrationtest::nested::test_nested::test_two [..]
@@ -86,10 +86,10 @@ fn root_workspace_specific_package2() {
 
 
         Collected 6 test(s) from fibonacci package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci::tests::it_works [..]
         [PASS] fibonacci::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci_tests::lib_test [..]
         [PASS] fibonacci_tests::abc::abc_test [..]
         [PASS] fibonacci_tests::abc::efg::efg_test [..]
@@ -124,8 +124,8 @@ fn root_workspace_specific_package_and_name() {
 
 
         Collected 1 test(s) from addition package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [PASS] addition_integrationtest::nested::simple_case [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 4 filtered out
         "},
@@ -149,9 +149,9 @@ fn root_workspace_specify_root_package() {
 
 
         Collected 3 test(s) from hello_workspaces package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] hello_workspaces::tests::test_simple [..]
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] hello_workspaces_integrationtest::test_failing::test_failing
         
         Failure data:
@@ -189,9 +189,9 @@ fn root_workspace_inside_nested_package() {
 
 
         Collected 5 test(s) from addition package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] addition::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] addition_integrationtest::nested::simple_case [..]
         [PASS] addition_integrationtest::nested::contract_test [..]
         [PASS] addition_integrationtest::nested::test_nested::test_two [..]
@@ -219,9 +219,9 @@ fn root_workspace_for_entire_workspace() {
         
         
         Collected 5 test(s) from addition package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] addition::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] addition_integrationtest::nested::simple_case [..]
         [PASS] addition_integrationtest::nested::contract_test [..]
         [PASS] addition_integrationtest::nested::test_nested::test_two [..]
@@ -230,10 +230,10 @@ fn root_workspace_for_entire_workspace() {
         
         
         Collected 6 test(s) from fibonacci package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci::tests::it_works [..]
         [PASS] fibonacci::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci_tests::lib_test [..]
         [PASS] fibonacci_tests::abc::abc_test [..]
         [PASS] fibonacci_tests::abc::efg::efg_test [..]
@@ -246,9 +246,9 @@ fn root_workspace_for_entire_workspace() {
         
         
         Collected 3 test(s) from hello_workspaces package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] hello_workspaces::tests::test_simple [..]
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] hello_workspaces_integrationtest::test_failing::test_failing
         
         Failure data:
@@ -291,9 +291,9 @@ fn root_workspace_for_entire_workspace_inside_package() {
         
         
         Collected 5 test(s) from addition package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] addition::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] addition_integrationtest::nested::simple_case [..]
         [PASS] addition_integrationtest::nested::contract_test [..]
         [PASS] addition_integrationtest::nested::test_nested::test_two [..]
@@ -302,10 +302,10 @@ fn root_workspace_for_entire_workspace_inside_package() {
         
         
         Collected 6 test(s) from fibonacci package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci::tests::it_works [..]
         [PASS] fibonacci::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci_tests::lib_test [..]
         [PASS] fibonacci_tests::abc::abc_test [..]
         [PASS] fibonacci_tests::abc::efg::efg_test [..]
@@ -318,9 +318,9 @@ fn root_workspace_for_entire_workspace_inside_package() {
         
         
         Collected 3 test(s) from hello_workspaces package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] hello_workspaces::tests::test_simple [..]
-        Running 2 test(s) from tests/
+        Running 2 integration test(s) from tests/
         [FAIL] hello_workspaces_integrationtest::test_failing::test_failing
         
         Failure data:
@@ -366,7 +366,7 @@ fn root_workspace_missing_package() {
 
     let stdout = String::from_utf8_lossy(&result.get_output().stdout);
 
-    assert!(stdout.contains("Failed to find any packages matching the specified filter"));
+    assert!(stdout.contains("Failed to find any packages matching the specified filter, available packages: addition, fibonacci, hello_workspaces"));
 }
 
 #[test]
@@ -385,10 +385,10 @@ fn virtual_workspace_without_arguments() {
         
         
         Collected 6 test(s) from fibonacci2 package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci2::tests::it_works [..]
         [PASS] fibonacci2::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci2_tests::lib_test [..]
         [PASS] fibonacci2_tests::abc::abc_test [..]
         [PASS] fibonacci2_tests::abc::efg::efg_test [..]
@@ -401,9 +401,9 @@ fn virtual_workspace_without_arguments() {
 
 
         Collected 5 test(s) from subtraction package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] subtraction::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] subtraction_integrationtest::nested::simple_case [..]
         [PASS] subtraction_integrationtest::nested::contract_test [..]
         [PASS] subtraction_integrationtest::nested::test_nested::test_two [..]
@@ -431,9 +431,9 @@ fn virtual_workspace_specify_package() {
 
 
         Collected 5 test(s) from subtraction package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] subtraction::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] subtraction_integrationtest::nested::simple_case [..]
         [PASS] subtraction_integrationtest::nested::contract_test [..]
         [PASS] subtraction_integrationtest::nested::test_nested::test_two [..]
@@ -457,10 +457,10 @@ fn virtual_workspace_specific_package2() {
         
         
         Collected 6 test(s) from fibonacci2 package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci2::tests::it_works [..]
         [PASS] fibonacci2::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci2_tests::lib_test [..]
         [PASS] fibonacci2_tests::abc::abc_test [..]
         [PASS] fibonacci2_tests::abc::efg::efg_test [..]
@@ -495,8 +495,8 @@ fn virtual_workspace_specific_package_and_name() {
 
 
         Collected 1 test(s) from subtraction package
-        Running 0 test(s) from src/
-        Running 1 test(s) from tests/
+        Running 0 unit test(s) from src/
+        Running 1 integration test(s) from tests/
         [PASS] subtraction_integrationtest::nested::simple_case [..]
         Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 4 filtered out
         "},
@@ -520,9 +520,9 @@ fn virtual_workspace_inside_nested_package() {
 
 
         Collected 5 test(s) from subtraction package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] subtraction::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] subtraction_integrationtest::nested::simple_case [..]
         [PASS] subtraction_integrationtest::nested::contract_test [..]
         [PASS] subtraction_integrationtest::nested::test_nested::test_two [..]
@@ -548,10 +548,10 @@ fn virtual_workspace_for_entire_workspace() {
         
         
         Collected 6 test(s) from fibonacci2 package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci2::tests::it_works [..]
         [PASS] fibonacci2::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci2_tests::lib_test [..]
         [PASS] fibonacci2_tests::abc::abc_test [..]
         [PASS] fibonacci2_tests::abc::efg::efg_test [..]
@@ -564,9 +564,9 @@ fn virtual_workspace_for_entire_workspace() {
 
 
         Collected 5 test(s) from subtraction package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] subtraction::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] subtraction_integrationtest::nested::simple_case [..]
         [PASS] subtraction_integrationtest::nested::contract_test [..]
         [PASS] subtraction_integrationtest::nested::test_nested::test_two [..]
@@ -596,10 +596,10 @@ fn virtual_workspace_for_entire_workspace_inside_package() {
         
         
         Collected 6 test(s) from fibonacci2 package
-        Running 2 test(s) from src/
+        Running 2 unit test(s) from src/
         [PASS] fibonacci2::tests::it_works [..]
         [PASS] fibonacci2::tests::contract_test [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] fibonacci2_tests::lib_test [..]
         [PASS] fibonacci2_tests::abc::abc_test [..]
         [PASS] fibonacci2_tests::abc::efg::efg_test [..]
@@ -612,9 +612,9 @@ fn virtual_workspace_for_entire_workspace_inside_package() {
 
 
         Collected 5 test(s) from subtraction package
-        Running 1 test(s) from src/
+        Running 1 unit test(s) from src/
         [PASS] subtraction::tests::it_works [..]
-        Running 4 test(s) from tests/
+        Running 4 integration test(s) from tests/
         [PASS] subtraction_integrationtest::nested::simple_case [..]
         [PASS] subtraction_integrationtest::nested::contract_test [..]
         [PASS] subtraction_integrationtest::nested::test_nested::test_two [..]
@@ -651,5 +651,76 @@ fn virtual_workspace_missing_package() {
 
     let stdout = String::from_utf8_lossy(&result.get_output().stdout);
 
-    assert!(stdout.contains("Failed to find any packages matching the specified filter"));
+    assert!(stdout.contains("Failed to find any packages matching the specified filter, available packages: fibonacci2, subtraction"));
+}
+
+#[test]
+fn virtual_workspace_summary_by_package() {
+    let temp = setup_virtual_workspace();
+    let snapbox = test_runner(&temp).arg("--workspace");
+
+    let output = snapbox.current_dir(&temp).assert().code(1);
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        Tests by package:
+            fibonacci2   5 passed, 1 failed, 0 skipped, 0 ignored
+            subtraction  5 passed, 0 failed, 0 skipped, 0 ignored
+        Tests in all packages: 10 passed, 1 failed, 0 skipped, 0 ignored
+        "},
+    );
+}
+
+#[test]
+fn virtual_workspace_repeated_package_and_name() {
+    let temp = setup_virtual_workspace();
+    let snapbox = test_runner(&temp).args([
+        "--package",
+        "fibonacci2",
+        "--package",
+        "subtraction",
+        "it_works",
+    ]);
+
+    let output = snapbox.current_dir(&temp).assert().success();
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]Compiling[..]
+        [..]Compiling[..]
+        [..]Compiling[..]
+        [..]Finished[..]
+
+
+        Collected 1 test(s) from fibonacci2 package
+        Running 1 unit test(s) from src/
+        [PASS] fibonacci2::tests::it_works [..]
+        Running 0 integration test(s) from tests/
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 5 filtered out
+
+
+        Collected 1 test(s) from subtraction package
+        Running 1 unit test(s) from src/
+        [PASS] subtraction::tests::it_works [..]
+        Running 0 integration test(s) from tests/
+        Tests: 1 passed, 0 failed, 0 skipped, 0 ignored, 4 filtered out
+
+        Tests by package:
+            fibonacci2   1 passed, 0 failed, 0 skipped, 0 ignored
+            subtraction  1 passed, 0 failed, 0 skipped, 0 ignored
+        Tests in all packages: 2 passed, 0 failed, 0 skipped, 0 ignored
+        "},
+    );
+}
+
+#[test]
+fn virtual_workspace_single_package_has_no_summary_by_package() {
+    let temp = setup_virtual_workspace();
+    let snapbox = test_runner(&temp).args(["--package", "subtraction"]);
+
+    let output = snapbox.current_dir(&temp).assert().success();
+    let stdout = String::from_utf8_lossy(&output.get_output().stdout);
+
+    assert!(!stdout.contains("Tests by package"));
+    assert!(!stdout.contains("Tests in all packages"));
 }
@@ -0,0 +1,79 @@
+use indoc::indoc;
+use test_utils::runner::{assert_case_output_contains, assert_failed, assert_passed};
+use test_utils::running_tests::run_test_case;
+use test_utils::test_case;
+
+#[test]
+fn available_steps_raises_the_limit() {
+    let test = test_case!(indoc!(
+        r"
+            #[test]
+            #[available_steps(12_000_000)]
+            fn steps_11250075() {
+                let mut i = 0;
+
+                while i != 750_000 {
+                    i = i + 1;
+                    assert(1 + 1 == 2, 'who knows?');
+                }
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn test_exceeding_default_limit_fails() {
+    let test = test_case!(indoc!(
+        r"
+            #[test]
+            fn steps_11250075() {
+                let mut i = 0;
+
+                while i != 750_000 {
+                    i = i + 1;
+                    assert(1 + 1 == 2, 'who knows?');
+                }
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "steps_11250075",
+        "The limit can be raised with `#[available_steps(<steps>)]` attribute",
+    );
+}
+
+#[test]
+fn available_steps_lowers_the_limit() {
+    let test = test_case!(indoc!(
+        r"
+            #[test]
+            #[available_steps(100_000)]
+            fn steps_570030() {
+                let mut i = 0;
+
+                while i != 37_997 {
+                    i = i + 1;
+                    assert(1 + 1 == 2, 'who knows?');
+                }
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "steps_570030",
+        "Test ran out of steps after consuming all 100000 available steps",
+    );
+}
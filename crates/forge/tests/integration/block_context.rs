@@ -0,0 +1,130 @@
+use forge_runner::test_case_summary::{AnyTestCaseSummary, TestCaseSummary};
+use forge_runner::test_target_summary::TestTargetSummary;
+use indoc::indoc;
+use runtime::starknet::context::BlockContextVersion;
+use std::path::Path;
+use test_utils::runner::{
+    assert_case_output_contains, assert_failed, assert_passed, Contract, TestCase,
+};
+use test_utils::running_tests::{run_test_case, run_test_case_with_block_context};
+use test_utils::test_case;
+
+fn gas_used(result: &[TestTargetSummary]) -> u128 {
+    let result = TestCase::find_test_result(result);
+
+    match &result.test_case_summaries[..] {
+        [AnyTestCaseSummary::Single(TestCaseSummary::Passed { gas_info, .. })] => *gas_info,
+        summaries => panic!("Expected a single passed test, got {summaries:?}"),
+    }
+}
+
+#[test]
+fn storage_write_cost_depends_on_block_context_version() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait };
+
+            #[starknet::interface]
+            trait IGasChecker<TContractState> {
+                fn change_balance(ref self: TContractState, new_balance: u64);
+            }
+
+            #[test]
+            fn storage_write_cost() {
+                let contract = declare("GasChecker").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@ArrayTrait::new()).unwrap();
+                let dispatcher = IGasCheckerDispatcher { contract_address };
+
+                dispatcher.change_balance(1);
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "GasChecker".to_string(),
+            Path::new("tests/data/contracts/gas_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result_0_13_0 = run_test_case_with_block_context(&test, BlockContextVersion::V0_13_0);
+    let result_0_13_1 = run_test_case_with_block_context(&test, BlockContextVersion::V0_13_1);
+
+    assert_passed(&result_0_13_0);
+    assert_passed(&result_0_13_1);
+    // Before 0.13.1 state diffs are published in the calldata instead of blobs,
+    // which makes each of the 5 published felts cost hundreds of gas instead of 32
+    assert!(gas_used(&result_0_13_0) > gas_used(&result_0_13_1) + 5 * 32);
+}
+
+#[test]
+fn set_block_gas_prices() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{ deploy_contract, set_block_gas_prices, BlockGasPrices };
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }
+
+            #[test]
+            fn set_block_gas_prices_and_call() {
+                set_block_gas_prices(
+                    BlockGasPrices {
+                        eth_l1_gas_price: 1,
+                        strk_l1_gas_price: 2,
+                        eth_l1_data_gas_price: 3,
+                        strk_l1_data_gas_price: 4,
+                    }
+                );
+
+                let dispatcher: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
+                dispatcher.increase_balance(5);
+                assert(dispatcher.get_balance() == 5, 'wrong balance');
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn set_block_gas_prices_fails_on_zero_price() {
+    let test = test_case!(indoc!(
+        r"
+        use snforge_std::{ set_block_gas_prices, BlockGasPrices };
+
+        #[test]
+        fn zero_gas_price() {
+            set_block_gas_prices(
+                BlockGasPrices {
+                    eth_l1_gas_price: 1,
+                    strk_l1_gas_price: 0,
+                    eth_l1_data_gas_price: 1,
+                    strk_l1_data_gas_price: 1,
+                }
+            );
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "zero_gas_price",
+        "strk_l1_gas_price must be greater than 0",
+    );
+}
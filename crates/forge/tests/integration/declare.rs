@@ -138,3 +138,161 @@ fn redeclare() {
 
     assert_passed(&result);
 }
+
+#[test]
+fn declared_class_hash_lookup() {
+    let contract = Contract::from_code_path(
+        "HelloStarknet".to_string(),
+        Path::new("tests/data/contracts/hello_starknet.cairo"),
+    )
+    .unwrap();
+
+    let test = test_case!(
+        indoc!(
+            r#"
+        use result::ResultTrait;
+        use option::OptionTrait;
+        use snforge_std::{declare, get_declared_class_hash, DeclareResultTrait};
+
+        #[test]
+        fn declared_class_hash_lookup() {
+            assert(get_declared_class_hash("HelloStarknet").is_none(), 'declared before declare');
+
+            let class_hash = declare("HelloStarknet").unwrap().class_hash();
+            assert(get_declared_class_hash("HelloStarknet") == Option::Some(class_hash), 'wrong class hash');
+
+            let redeclared_class_hash = declare("HelloStarknet").unwrap().class_hash();
+            assert(redeclared_class_hash == class_hash, 'redeclare changed class hash');
+        }
+        "#
+        ),
+        contract
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn declared_class_hash_passed_to_factory() {
+    let contract = Contract::from_code_path(
+        "HelloStarknet".to_string(),
+        Path::new("tests/data/contracts/hello_starknet.cairo"),
+    )
+    .unwrap();
+    let factory = Contract::new(
+        "Factory",
+        indoc!(
+            r"
+            use starknet::{ClassHash, ContractAddress};
+
+            #[starknet::interface]
+            trait IFactory<TContractState> {
+                fn create(ref self: TContractState) -> ContractAddress;
+            }
+
+            #[starknet::contract]
+            mod Factory {
+                use starknet::{ClassHash, ContractAddress, SyscallResultTrait};
+                use starknet::syscalls::deploy_syscall;
+
+                #[storage]
+                struct Storage {
+                    class_hash: ClassHash,
+                }
+
+                #[constructor]
+                fn constructor(ref self: ContractState, class_hash: ClassHash) {
+                    self.class_hash.write(class_hash);
+                }
+
+                #[abi(embed_v0)]
+                impl FactoryImpl of super::IFactory<ContractState> {
+                    fn create(ref self: ContractState) -> ContractAddress {
+                        let (contract_address, _) = deploy_syscall(
+                            self.class_hash.read(), 0, array![].span(), false
+                        )
+                            .unwrap_syscall();
+                        contract_address
+                    }
+                }
+            }
+            "
+        ),
+    );
+
+    let test = test_case!(
+        indoc!(
+            r#"
+        use result::ResultTrait;
+        use traits::Into;
+        use starknet::{ContractAddress, ClassHashIntoFelt252};
+        use snforge_std::{declare, get_class_hash, ContractClassTrait, DeclareResultTrait};
+
+        #[starknet::interface]
+        trait IFactory<TContractState> {
+            fn create(ref self: TContractState) -> ContractAddress;
+        }
+
+        #[test]
+        fn declared_class_hash_passed_to_factory() {
+            let class_hash = declare("HelloStarknet").unwrap().class_hash();
+
+            let factory = *declare("Factory").unwrap().contract_class();
+            let (factory_address, _) = factory.deploy(@array![class_hash.into()]).unwrap();
+            let created = IFactoryDispatcher { contract_address: factory_address }.create();
+
+            assert(get_class_hash(created) == class_hash, 'wrong class deployed');
+        }
+        "#
+        ),
+        contract,
+        factory
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn declared_contracts_deployed_in_isolation() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use traits::Into;
+        use starknet::{ContractAddress, ClassHashIntoFelt252};
+        use snforge_std::{declare, get_class_hash, ContractClassTrait, DeclareResultTrait};
+
+        fn deploy_at_fixed_address() {
+            let contract_address: ContractAddress = 123.try_into().unwrap();
+            let class_hash: felt252 = get_class_hash(contract_address).into();
+            assert(class_hash == 0, 'address already deployed');
+
+            let contract = declare("HelloStarknet").unwrap().contract_class();
+            contract.deploy_at(@array![], contract_address).unwrap();
+        }
+
+        #[test]
+        fn first_deployment() {
+            deploy_at_fixed_address();
+        }
+
+        #[test]
+        fn second_deployment() {
+            deploy_at_fixed_address();
+        }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
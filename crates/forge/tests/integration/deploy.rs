@@ -1,6 +1,6 @@
 use indoc::indoc;
 use std::path::Path;
-use test_utils::runner::{assert_passed, Contract};
+use test_utils::runner::{assert_case_output_contains, assert_failed, assert_passed, Contract};
 use test_utils::running_tests::run_test_case;
 use test_utils::test_case;
 
@@ -228,3 +228,150 @@ fn constructor_retdata_struct() {
 
     assert_passed(&result);
 }
+
+#[test]
+fn deploy_contract_returns_dispatcher() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use snforge_std::{ deploy_contract, deploy_contract_at };
+        use starknet::ContractAddress;
+
+        #[starknet::interface]
+        trait IHelloStarknet<TContractState> {
+            fn increase_balance(ref self: TContractState, amount: felt252);
+            fn get_balance(self: @TContractState) -> felt252;
+        }
+
+        #[test]
+        fn deploy_contract_returns_dispatcher() {
+            let dispatcher: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
+            dispatcher.increase_balance(5);
+            assert(dispatcher.get_balance() == 5, 'balance mismatch');
+
+            let contract_address: ContractAddress = 123.try_into().unwrap();
+            let dispatcher: IHelloStarknetDispatcher = deploy_contract_at(
+                "HelloStarknet", @array![], contract_address
+            );
+            assert(dispatcher.contract_address == contract_address, 'address mismatch');
+            assert(dispatcher.get_balance() == 0, 'balance mismatch');
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn deploy_contract_constructor_panic() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use snforge_std::{ deploy_contract, deploy_contract_at };
+
+        #[starknet::interface]
+        trait IPanickingConstructor<TContractState> {
+            fn get_balance(self: @TContractState) -> felt252;
+        }
+
+        #[test]
+        fn deploy_contract_constructor_panic() {
+            let _dispatcher: IPanickingConstructorDispatcher = deploy_contract(
+                "PanickingConstructor", @array![]
+            );
+        }
+
+        #[test]
+        fn deploy_contract_at_constructor_panic() {
+            let _dispatcher: IPanickingConstructorDispatcher = deploy_contract_at(
+                "PanickingConstructor", @array![], 123.try_into().unwrap()
+            );
+        }
+
+        #[test]
+        fn deploy_contract_constructor_panic_with_byte_array() {
+            let _dispatcher: IPanickingConstructorDispatcher = deploy_contract(
+                "ByteArrayPanickingConstructor", @array![]
+            );
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "PanickingConstructor".to_string(),
+            Path::new("tests/data/contracts/panicking_constructor.cairo"),
+        )
+        .unwrap(),
+        Contract::new(
+            "ByteArrayPanickingConstructor",
+            indoc!(
+                r#"
+                #[starknet::contract]
+                mod ByteArrayPanickingConstructor {
+                    #[storage]
+                    struct Storage {}
+
+                    #[constructor]
+                    fn constructor(ref self: ContractState) {
+                        panic!("owner must not be zero");
+                    }
+                }
+                "#
+            )
+        )
+    );
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "deploy_contract_constructor_panic",
+        "Failed to deploy contract PanickingConstructor",
+    );
+    assert_case_output_contains(
+        &result,
+        "deploy_contract_at_constructor_panic",
+        "Failed to deploy contract PanickingConstructor",
+    );
+    assert_case_output_contains(
+        &result,
+        "deploy_contract_constructor_panic_with_byte_array",
+        "Failed to deploy contract ByteArrayPanickingConstructor: owner must not be zero",
+    );
+}
+
+#[test]
+fn deploy_contract_unknown_contract() {
+    let test = test_case!(indoc!(
+        r#"
+        use snforge_std::deploy_contract;
+
+        #[starknet::interface]
+        trait IHelloStarknet<TContractState> {
+            fn get_balance(self: @TContractState) -> felt252;
+        }
+
+        #[test]
+        fn deploy_contract_unknown_contract() {
+            let _dispatcher: IHelloStarknetDispatcher = deploy_contract("NonExistentContract", @array![]);
+        }
+    "#
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "deploy_contract_unknown_contract",
+        "Failed to get contract artifact for name = NonExistentContract.",
+    );
+}
@@ -15,7 +15,7 @@ fn simple_call_and_invoke() {
         use traits::TryInto;
         use starknet::ContractAddress;
         use starknet::Felt252TryIntoContractAddress;
-        use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait };
+        use snforge_std::deploy_contract;
 
         #[starknet::interface]
         trait IHelloStarknet<TContractState> {
@@ -27,9 +27,7 @@ fn simple_call_and_invoke() {
 
         #[test]
         fn simple_call_and_invoke() {
-            let contract = declare("HelloStarknet").unwrap().contract_class();
-            let (contract_address, _) = contract.deploy(@ArrayTrait::new()).unwrap();
-            let dispatcher = IHelloStarknetDispatcher { contract_address };
+            let dispatcher: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
 
             let balance = dispatcher.get_balance();
             assert(balance == 0, 'balance == 0');
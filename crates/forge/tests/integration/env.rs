@@ -138,6 +138,6 @@ fn read_non_existent() {
     assert_case_output_contains(
         &result,
         "read_invalid_short_string",
-        "Failed to read from env var = MY_ENV_VAR",
+        "Environment variable MY_ENV_VAR is not set",
     );
 }
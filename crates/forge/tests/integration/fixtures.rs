@@ -0,0 +1,145 @@
+use indoc::indoc;
+use std::path::Path;
+use test_utils::runner::{
+    assert_case_output_contains, assert_failed, assert_fixture_failed, assert_passed, Contract,
+};
+use test_utils::running_tests::run_test_case;
+use test_utils::test_case;
+
+#[test]
+fn fixture_deploys_contract_for_test() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait, start_cheat_block_number_global};
+            use starknet::get_block_info;
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }
+
+            #[fixture]
+            fn hello() -> IHelloStarknetDispatcher {
+                let contract = declare("HelloStarknet").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                let dispatcher = IHelloStarknetDispatcher { contract_address };
+                dispatcher.increase_balance(10);
+
+                start_cheat_block_number_global(123);
+
+                dispatcher
+            }
+
+            #[test(fixtures: (hello))]
+            fn test_with_fixture(hello: IHelloStarknetDispatcher) {
+                assert(hello.get_balance() == 10, 'Fixture state not kept');
+                assert(get_block_info().unbox().block_number == 123, 'Fixture cheat not kept');
+            }
+
+            #[test(fixtures: (hello))]
+            fn test_fixture_runs_per_test(hello: IHelloStarknetDispatcher) {
+                hello.increase_balance(5);
+                assert(hello.get_balance() == 15, 'State leaked between tests');
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn fixture_with_fuzzing() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }
+
+            #[fixture]
+            fn hello() -> IHelloStarknetDispatcher {
+                let contract = declare("HelloStarknet").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                IHelloStarknetDispatcher { contract_address }
+            }
+
+            #[test(fixtures: (hello))]
+            #[fuzzer(runs: 10, seed: 100)]
+            fn test_fuzzed_with_fixture(hello: IHelloStarknetDispatcher, amount: u64) {
+                hello.increase_balance(amount.into());
+                assert(hello.get_balance() == amount.into(), 'Fixture not fresh per run');
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn fixture_panic_is_reported_as_fixture_error() {
+    let test = test_case!(indoc!(
+        r"
+        #[derive(Drop)]
+        struct Setup {
+            value: felt252,
+        }
+
+        #[fixture]
+        fn setup() -> Setup {
+            let value = 1;
+            assert(value == 2, 'setup failed');
+            Setup { value }
+        }
+
+        #[test(fixtures: (setup))]
+        fn test_failing_fixture(setup: Setup) {
+            assert(setup.value == 1, 'Wrong value');
+        }
+
+        #[test(fixtures: (setup))]
+        #[should_panic]
+        fn test_failing_fixture_should_panic(setup: Setup) {
+            assert(setup.value == 2, 'Wrong value');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_fixture_failed(&result, "test_failing_fixture");
+    assert_fixture_failed(&result, "test_failing_fixture_should_panic");
+    assert_case_output_contains(
+        &result,
+        "test_failing_fixture",
+        "Fixture `setup` failed, test body was not executed",
+    );
+    assert_case_output_contains(&result, "test_failing_fixture", "setup failed");
+    assert_case_output_contains(
+        &result,
+        "test_failing_fixture_should_panic",
+        "Fixture `setup` failed, test body was not executed",
+    );
+}
@@ -1,5 +1,10 @@
-use forge_runner::test_case_summary::{AnyTestCaseSummary, TestCaseSummary};
+use cairo_vm::Felt252;
+use forge_runner::test_case_summary::{AnyTestCaseSummary, FuzzingStatistics, TestCaseSummary};
+use forge_runner::FUZZ_CORPUS_DIR;
 use indoc::indoc;
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
 use test_utils::runner::{assert_passed, TestCase};
 use test_utils::running_tests::run_test_case;
 use test_utils::test_case;
@@ -109,3 +114,231 @@ fn builtin_test() {
 
     assert_passed(&result);
 }
+
+#[test]
+fn fuzzed_argument_in_range() {
+    let test = test_case!(indoc!(
+        r"
+        use starknet::ContractAddress;
+
+        #[test]
+        #[fuzzer(runs: 100, a: range(low: 1, high: 10_000), b: range(low: 340282366920938463463374607431768211456))]
+        fn fuzzed_argument_in_range(a: u64, b: u256, c: felt252, d: ContractAddress) {
+            assert(a >= 1 && a <= 10_000, 'a out of range');
+            assert(b.high >= 1, 'b out of range');
+
+            let max_address: u256 = 0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff;
+            let d: felt252 = d.into();
+            let d: u256 = d.into();
+            assert(d <= max_address, 'd out of range');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn same_seed_reproduces_failure() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 50, seed: 12345, a: range(low: 0, high: 1_000_000))]
+        fn failing_fuzz(a: u64, b: felt252) {
+            // Fails only in the run using the upper bound, so the reported arguments are deterministic
+            assert(a != 1_000_000, 'a at upper bound');
+        }
+    "
+    ));
+
+    let failed_arguments = || {
+        let result = run_test_case(&test);
+        let test_target_summary = TestCase::find_test_result(&result);
+        let AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+            arguments,
+            test_statistics,
+            ..
+        }) = &test_target_summary.test_case_summaries[0]
+        else {
+            panic!("Test should fail")
+        };
+
+        assert_eq!(test_statistics.seed, 12345);
+        arguments.clone()
+    };
+
+    assert_eq!(failed_arguments(), failed_arguments());
+}
+
+fn failed_fuzzing_statistics(test: &TestCase) -> FuzzingStatistics {
+    let result = run_test_case(test);
+    let test_target_summary = TestCase::find_test_result(&result);
+    let AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+        test_statistics, ..
+    }) = &test_target_summary.test_case_summaries[0]
+    else {
+        panic!("Test should fail")
+    };
+
+    test_statistics.clone()
+}
+
+#[test]
+fn failing_input_is_minimized() {
+    let test_code = indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 50, seed: 100)]
+        fn failing_fuzz(a: u128, b: u64) {
+            assert(a < 1000, 'a too big');
+        }
+    "
+    );
+
+    let statistics = failed_fuzzing_statistics(&test_case!(test_code));
+
+    assert_eq!(
+        statistics.minimized_arguments,
+        Some(vec![Felt252::from(1000_u16), Felt252::from(0_u8)])
+    );
+    // Shrinking is deterministic, so the seed reproduces the minimized input as well.
+    // New test case is used, as the failure would be replayed from the corpus of the previous one.
+    assert_eq!(
+        failed_fuzzing_statistics(&test_case!(test_code)).minimized_arguments,
+        statistics.minimized_arguments
+    );
+}
+
+#[test]
+fn failing_input_is_minimized_to_range_lower_bound() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 2, a: range(low: 500, high: 100_000))]
+        fn failing_fuzz(a: u64) {
+            assert(a == 0, 'always fails');
+        }
+    "
+    ));
+
+    let statistics = failed_fuzzing_statistics(&test);
+
+    assert_eq!(
+        statistics.minimized_arguments,
+        Some(vec![Felt252::from(500_u16)])
+    );
+}
+
+#[test]
+fn non_deterministic_test_is_not_minimized() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 10, non_deterministic: true)]
+        fn non_deterministic(a: u128) {
+            assert(a < 1000, 'a too big');
+        }
+
+        #[test]
+        #[fuzzer(runs: 10, shrink_runs: 0)]
+        fn shrinking_disabled(a: u128) {
+            assert(a < 1000, 'a too big');
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    for summary in &TestCase::find_test_result(&result).test_case_summaries {
+        let AnyTestCaseSummary::Fuzzing(TestCaseSummary::Failed {
+            test_statistics, ..
+        }) = summary
+        else {
+            panic!("Test should fail")
+        };
+        assert_eq!(test_statistics.minimized_arguments, None);
+    }
+}
+
+fn corpus_entries(test: &TestCase) -> Vec<PathBuf> {
+    let corpus_dir = test.path().unwrap().join(FUZZ_CORPUS_DIR);
+
+    fs::read_dir(corpus_dir)
+        .unwrap()
+        .flat_map(|test_dir| fs::read_dir(test_dir.unwrap().path()).unwrap())
+        .map(|entry| entry.unwrap().path())
+        .collect()
+}
+
+#[test]
+fn failing_input_is_replayed_from_corpus() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 50)]
+        fn failing_fuzz(a: u128, b: u64) {
+            assert(a < 1000, 'a too big');
+        }
+    "
+    ));
+
+    failed_fuzzing_statistics(&test);
+
+    let entries = corpus_entries(&test);
+    assert_eq!(entries.len(), 1);
+    let entry: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&entries[0]).unwrap()).unwrap();
+    assert_eq!(
+        entry,
+        json!({
+            "version": 1,
+            "arguments": [
+                { "type": "u128", "value": "1000" },
+                { "type": "u64", "value": "0" },
+            ]
+        })
+    );
+
+    // Saved input fails again before any random input is generated
+    let statistics = failed_fuzzing_statistics(&test);
+    assert_eq!(statistics.runs, 1);
+    assert_eq!(corpus_entries(&test), entries);
+}
+
+#[test]
+fn corpus_entry_of_changed_test_is_skipped() {
+    let test = test_case!(indoc!(
+        r"
+        #[test]
+        #[fuzzer(runs: 50)]
+        fn fuzz(a: u128, b: u128) {
+            assert(a < 1000, 'a too big');
+        }
+    "
+    ));
+
+    failed_fuzzing_statistics(&test);
+    let entries = corpus_entries(&test);
+
+    // Saved input would fail the test if it was replayed despite the changed parameters
+    fs::write(
+        test.path().unwrap().join(TestCase::TEST_PATH),
+        indoc!(
+            r"
+            #[test]
+            #[fuzzer(runs: 50)]
+            fn fuzz(a: u128) {
+                assert(a != 1000, 'input was replayed');
+            }
+        "
+        ),
+    )
+    .unwrap();
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+    assert_eq!(corpus_entries(&test), entries);
+}
@@ -1,4 +1,6 @@
 mod available_gas;
+mod available_steps;
+mod block_context;
 mod cheat_block_number;
 mod cheat_block_timestamp;
 mod cheat_caller_address;
@@ -11,6 +13,7 @@ mod deploy_at;
 mod dict;
 mod dispatchers;
 mod env;
+mod fixtures;
 mod fuzzing;
 mod gas;
 mod get_class_hash;
@@ -22,12 +25,16 @@ mod pure_cairo;
 mod replace_bytecode;
 mod resources;
 mod runtime;
+mod set_balance;
 mod setup_fork;
 mod should_panic;
 mod signing;
 mod spy_events;
+mod state_snapshot;
 mod store_load;
 mod syscalls;
+mod test_case;
 mod test_state;
+mod timeout;
 mod too_many_events;
 mod trace;
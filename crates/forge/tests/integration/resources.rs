@@ -3,10 +3,14 @@ use blockifier::execution::deprecated_syscalls::DeprecatedSyscallSelector::{
     StorageWrite,
 };
 use cairo_vm::types::builtin_name::BuiltinName;
+use forge_runner::forge_config::TrackedResource;
+use forge_runner::resource_report::ResourceReport;
+use forge_runner::test_case_summary::AnyTestCaseSummary;
 use indoc::indoc;
 use std::path::Path;
-use test_utils::runner::{assert_builtin, assert_passed, assert_syscall, Contract};
-use test_utils::running_tests::run_test_case;
+use test_utils::resources::{assert_resources_within, assert_test_resources_within, Tolerance};
+use test_utils::runner::{assert_builtin, assert_passed, assert_syscall, Contract, TestCase};
+use test_utils::running_tests::{run_test_case, run_test_case_tracking};
 use test_utils::test_case;
 
 #[test]
@@ -150,6 +154,288 @@ fn syscalls_count() {
     assert_syscall(&result, "emit_event", EmitEvent, 1);
 }
 
+#[test]
+fn events_and_messages_to_l1() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use starknet::{ContractAddress, EthAddress};
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+
+            #[starknet::interface]
+            trait ISpyEventsChecker<TContractState> {
+                fn emit_two_events(
+                    ref self: TContractState, some_data: felt252, some_more_data: ContractAddress
+                );
+            }
+
+            #[starknet::interface]
+            trait IMessageToL1Checker<TContractState> {
+                fn send_message(ref self: TContractState, some_data: Array<felt252>, to_address: EthAddress);
+            }
+
+            #[test]
+            fn events() {
+                let contract = declare("SpyEventsChecker").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                let dispatcher = ISpyEventsCheckerDispatcher { contract_address };
+
+                dispatcher.emit_two_events(123, 456.try_into().unwrap());
+            }
+
+            #[test]
+            fn messages_to_l1() {
+                let contract = declare("MessageToL1Checker").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                let dispatcher = IMessageToL1CheckerDispatcher { contract_address };
+
+                dispatcher.send_message(array![1, 2, 3], 123.try_into().unwrap());
+                dispatcher.send_message(array![], 123.try_into().unwrap());
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "SpyEventsChecker".to_string(),
+            Path::new("tests/data/contracts/spy_events_checker.cairo"),
+        )
+        .unwrap(),
+        Contract::from_code_path(
+            "MessageToL1Checker".to_string(),
+            Path::new("tests/data/contracts/message_to_l1_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+
+    let report = |test_case_name: &str| {
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .iter()
+            .find_map(|case| match case {
+                AnyTestCaseSummary::Single(case)
+                    if case.name().ends_with(&format!("::{test_case_name}")) =>
+                {
+                    case.resource_report()
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    // Selector of the event is its first key, `SecondEvent` has one more key
+    let events = report("events");
+    assert_eq!(events.events, 2);
+    assert_eq!(events.events_size, 5);
+    assert_eq!(events.l2_to_l1_messages, 0);
+
+    let messages_to_l1 = report("messages_to_l1");
+    assert_eq!(messages_to_l1.events, 0);
+    assert_eq!(messages_to_l1.l2_to_l1_messages, 2);
+    assert_eq!(messages_to_l1.l2_to_l1_payload_size, 3);
+}
+
+#[test]
+fn sierra_gas_of_contract_calls() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+            }
+
+            fn deploy() -> IHelloStarknetDispatcher {
+                let contract = declare("HelloStarknet").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                IHelloStarknetDispatcher { contract_address }
+            }
+
+            #[test]
+            fn one_call() {
+                let dispatcher = deploy();
+                dispatcher.increase_balance(1);
+            }
+
+            #[test]
+            fn two_calls() {
+                let dispatcher = deploy();
+                dispatcher.increase_balance(1);
+                dispatcher.increase_balance(1);
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+
+    let sierra_gas = |test_case_name: &str| {
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .iter()
+            .find_map(|case| match case {
+                AnyTestCaseSummary::Single(case)
+                    if case.name().ends_with(&format!("::{test_case_name}")) =>
+                {
+                    case.resource_report()
+                }
+                _ => None,
+            })
+            .unwrap()
+            .sierra_gas_consumed
+            .expect("Test compiled with gas should report Sierra gas")
+    };
+
+    let one_call = sierra_gas("one_call");
+    let two_calls = sierra_gas("two_calls");
+    assert!(one_call > 0);
+    assert!(two_calls > one_call);
+}
+
+#[test]
+fn sierra_gas_includes_gas_of_called_contracts() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+
+            #[starknet::interface]
+            trait ISpinner<TContractState> {
+                fn spin(self: @TContractState, iterations: u64);
+            }
+
+            fn deploy() -> ISpinnerDispatcher {
+                let contract = declare("Spinner").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                ISpinnerDispatcher { contract_address }
+            }
+
+            #[test]
+            fn short_spin() {
+                deploy().spin(1);
+            }
+
+            #[test]
+            fn long_spin() {
+                deploy().spin(1001);
+            }
+        "#
+        ),
+        Contract::new(
+            "Spinner",
+            indoc!(
+                r"
+                #[starknet::interface]
+                trait ISpinner<TContractState> {
+                    fn spin(self: @TContractState, iterations: u64);
+                }
+
+                #[starknet::contract]
+                mod Spinner {
+                    #[storage]
+                    struct Storage {}
+
+                    #[abi(embed_v0)]
+                    impl SpinnerImpl of super::ISpinner<ContractState> {
+                        fn spin(self: @ContractState, iterations: u64) {
+                            let mut i = 0;
+                            while i != iterations {
+                                i += 1;
+                            };
+                        }
+                    }
+                }
+                "
+            )
+        )
+    );
+
+    let result = run_test_case_tracking(&test, TrackedResource::SierraGas);
+    assert_passed(&result);
+
+    let gas = |test_case_name: &str| {
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .iter()
+            .find_map(|case| match case {
+                AnyTestCaseSummary::Single(case)
+                    if case.name().ends_with(&format!("::{test_case_name}")) =>
+                {
+                    case.resource_report()
+                }
+                _ => None,
+            })
+            .unwrap()
+            .gas
+    };
+
+    // The tests differ only in the 1000 loop iterations run by the contract,
+    // each of them takes at least one step, which costs 100 Sierra gas
+    assert!(gas("long_spin") - gas("short_spin") >= 1000 * 100);
+}
+
+#[test]
+fn gas_from_tracked_resource() {
+    let test = test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+            }
+
+            #[test]
+            fn call() {
+                let contract = declare("HelloStarknet").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                IHelloStarknetDispatcher { contract_address }.increase_balance(1);
+            }
+        "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let resource_report = |tracked_resource| {
+        let result = run_test_case_tracking(&test, tracked_resource);
+        assert_passed(&result);
+
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .iter()
+            .find_map(|case| match case {
+                AnyTestCaseSummary::Single(case) => case.resource_report(),
+                AnyTestCaseSummary::Fuzzing(_) => None,
+            })
+            .unwrap()
+    };
+
+    let cairo_steps = resource_report(TrackedResource::CairoSteps);
+    let sierra_gas = resource_report(TrackedResource::SierraGas);
+
+    assert_eq!(cairo_steps.tracked_resource, TrackedResource::CairoSteps);
+    assert_eq!(sierra_gas.tracked_resource, TrackedResource::SierraGas);
+    assert_eq!(
+        sierra_gas.gas,
+        u128::from(sierra_gas.sierra_gas_consumed.unwrap())
+    );
+    assert_ne!(cairo_steps.gas, sierra_gas.gas);
+}
+
 #[test]
 fn accumulate_syscalls() {
     let test = test_case!(
@@ -239,3 +525,111 @@ fn estimation_includes_os_resources() {
         6,
     );
 }
+
+fn resource_report(
+    steps: usize,
+    gas: u128,
+    builtins: &[(&str, usize)],
+    syscalls: &[(&str, usize)],
+) -> ResourceReport {
+    ResourceReport {
+        steps,
+        memory_holes: 0,
+        gas,
+        builtins: builtins
+            .iter()
+            .map(|(name, count)| ((*name).to_string(), *count))
+            .collect(),
+        syscalls: syscalls
+            .iter()
+            .map(|(name, count)| ((*name).to_string(), *count))
+            .collect(),
+        ..ResourceReport::default()
+    }
+}
+
+#[test]
+fn resources_within_tolerance() {
+    let expected = resource_report(1000, 100, &[("range_check", 10)], &[("StorageWrite", 2)]);
+    let actual = resource_report(1050, 95, &[("range_check", 10)], &[("StorageWrite", 2)]);
+
+    assert_resources_within(&actual, &expected, Tolerance::Percent(5.0));
+    assert_resources_within(&expected, &expected, Tolerance::Strict);
+}
+
+#[test]
+#[should_panic(expected = "steps: actual 1051, expected 1000")]
+fn resources_outside_tolerance() {
+    let expected = resource_report(1000, 100, &[], &[]);
+    let actual = resource_report(1051, 100, &[], &[]);
+
+    assert_resources_within(&actual, &expected, Tolerance::Percent(5.0));
+}
+
+#[test]
+#[should_panic(expected = "gas: actual 101, expected 100")]
+fn resources_strict() {
+    let expected = resource_report(1000, 100, &[], &[]);
+    let actual = resource_report(1000, 101, &[], &[]);
+
+    assert_resources_within(&actual, &expected, Tolerance::Strict);
+}
+
+#[test]
+#[should_panic(expected = "Used syscalls differ from the expected ones")]
+fn resources_with_different_syscalls() {
+    let expected = resource_report(1000, 100, &[], &[("StorageWrite", 1)]);
+    let actual = resource_report(1000, 100, &[], &[("StorageRead", 1)]);
+
+    assert_resources_within(&actual, &expected, Tolerance::Percent(50.0));
+}
+
+#[test]
+#[should_panic(expected = "Sierra gas is reported in only one of the reports")]
+fn resources_with_sierra_gas_in_one_report() {
+    let expected = resource_report(1000, 100, &[], &[]);
+    let actual = ResourceReport {
+        sierra_gas_consumed: Some(5000),
+        ..expected.clone()
+    };
+
+    assert_resources_within(&actual, &expected, Tolerance::Percent(50.0));
+}
+
+#[test]
+fn same_tests_use_same_resources() {
+    let test = test_case!(indoc!(
+        r"
+            use starknet::{SyscallResultTrait, StorageAddress};
+
+            #[test]
+            fn first() {
+                let storage_address: StorageAddress = 10.try_into().unwrap();
+                starknet::storage_write_syscall(0, storage_address, 10).unwrap_syscall();
+            }
+
+            #[test]
+            fn second() {
+                let storage_address: StorageAddress = 10.try_into().unwrap();
+                starknet::storage_write_syscall(0, storage_address, 10).unwrap_syscall();
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+    assert_passed(&result);
+
+    let first = TestCase::find_test_result(&result)
+        .test_case_summaries
+        .iter()
+        .find_map(|case| match case {
+            AnyTestCaseSummary::Single(case) if case.name().ends_with("::first") => {
+                case.resource_report()
+            }
+            _ => None,
+        })
+        .unwrap();
+
+    assert_eq!(first.syscalls.get("StorageWrite"), Some(&1));
+    assert_test_resources_within(&result, "second", &first, Tolerance::Strict);
+}
@@ -0,0 +1,99 @@
+use indoc::indoc;
+use test_utils::runner::{assert_passed, Contract};
+use test_utils::running_tests::run_test_case;
+
+#[test]
+fn set_balance_of_fee_tokens() {
+    let test = test_utils::test_case!(
+        indoc!(
+            r#"
+            use starknet::{ContractAddress, contract_address_const};
+            use snforge_std::{
+                declare, replace_bytecode, DeclareResultTrait, set_balance, Token, TokenTrait
+            };
+
+            #[starknet::interface]
+            trait IFeeToken<TContractState> {
+                fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+            }
+
+            // Predeployed tokens have no code, it is replaced to be able to call `balance_of`
+            fn fee_token(token: Token) -> IFeeTokenDispatcher {
+                let class_hash = *declare("FeeToken").unwrap().contract_class().class_hash;
+                replace_bytecode(token.contract_address(), class_hash).unwrap();
+                IFeeTokenDispatcher { contract_address: token.contract_address() }
+            }
+
+            #[test]
+            fn set_strk_balance() {
+                let strk = fee_token(Token::STRK);
+                let account = contract_address_const::<123>();
+
+                set_balance(account, 1_000_000, Token::STRK);
+
+                assert(strk.balance_of(account) == 1_000_000, 'wrong STRK balance');
+            }
+
+            #[test]
+            fn set_eth_balance() {
+                let eth = fee_token(Token::ETH);
+                let strk = fee_token(Token::STRK);
+                let account = contract_address_const::<123>();
+                let balance: u256 = 0x100000000000000000000000000000001;
+
+                set_balance(account, balance, Token::ETH);
+
+                assert(eth.balance_of(account) == balance, 'wrong ETH balance');
+                assert(strk.balance_of(account) == 0, 'STRK balance changed');
+            }
+
+            #[test]
+            fn fee_tokens_have_network_addresses() {
+                let strk = contract_address_const::<
+                    0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d
+                >();
+                let eth = contract_address_const::<
+                    0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7
+                >();
+
+                assert(Token::STRK.contract_address() == strk, 'wrong STRK address');
+                assert(Token::ETH.contract_address() == eth, 'wrong ETH address');
+            }
+        "#
+        ),
+        Contract::new(
+            "FeeToken",
+            indoc!(
+                r"
+                use starknet::ContractAddress;
+
+                #[starknet::interface]
+                trait IFeeToken<TContractState> {
+                    fn balance_of(self: @TContractState, account: ContractAddress) -> u256;
+                }
+
+                #[starknet::contract]
+                mod FeeToken {
+                    use starknet::ContractAddress;
+
+                    #[storage]
+                    struct Storage {
+                        ERC20_balances: LegacyMap<ContractAddress, u256>,
+                    }
+
+                    #[abi(embed_v0)]
+                    impl FeeTokenImpl of super::IFeeToken<ContractState> {
+                        fn balance_of(self: @ContractState, account: ContractAddress) -> u256 {
+                            self.ERC20_balances.read(account)
+                        }
+                    }
+                }
+                "
+            )
+        )
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
@@ -8,21 +8,27 @@ use forge::block_number_map::BlockNumberMap;
 use forge::run_tests::package::run_for_package;
 use forge::scarb::config::ForkTarget;
 use forge::test_filter::TestsFilter;
+use forge::test_order::TestOrdering;
 use tempfile::tempdir;
 use tokio::runtime::Runtime;
 
+use cheatnet::forking::snapshot::ForkDataMode;
 use cheatnet::runtime_extensions::forge_runtime_extension::contracts_data::ContractsData;
 use forge::run_tests::package::RunForPackageArgs;
 use forge::scarb::load_test_artifacts;
 use forge_runner::build_trace_data::test_sierra_program_path::VERSIONED_PROGRAMS_DIR;
 use forge_runner::forge_config::{
-    ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig,
+    ExecutionDataToSave, ForgeConfig, OutputConfig, TestRunnerConfig, TrackedResource,
 };
-use forge_runner::CACHE_DIR;
+use forge_runner::test_target_summary::TestTargetSummary;
+use forge_runner::{CACHE_DIR, FUZZ_CORPUS_DIR};
+use runtime::starknet::context::BlockContextVersion;
 use scarb_api::metadata::MetadataCommandExt;
 use scarb_api::ScarbCommand;
 use shared::test_utils::node_url::node_rpc_url;
-use test_utils::runner::{assert_case_output_contains, assert_failed, assert_passed, Contract};
+use test_utils::runner::{
+    assert_case_output_contains, assert_failed, assert_passed, Contract, TestCase,
+};
 use test_utils::running_tests::run_test_case;
 use test_utils::test_case;
 
@@ -132,14 +138,20 @@ fn fork_aliased_decorator() {
         .block_on(run_for_package(
             RunForPackageArgs {
                 test_targets: raw_test_targets,
+                target_hashes: vec![],
+                unchanged_targets: vec![],
                 package_name: "test_package".to_string(),
+                test_ordering: TestOrdering::default(),
                 tests_filter: TestsFilter::from_flags(
                     None,
                     false,
+                    None,
+                    vec![],
                     false,
                     false,
                     false,
                     Default::default(),
+                    None,
                 ),
                 forge_config: Arc::new(ForgeConfig {
                     test_runner_config: Arc::new(TestRunnerConfig {
@@ -147,21 +159,31 @@ fn fork_aliased_decorator() {
                         fuzzer_runs: NonZeroU32::new(256).unwrap(),
                         fuzzer_seed: 12345,
                         max_n_steps: None,
+                        timeout: None,
+                        max_gas: None,
+                        tracked_resource: TrackedResource::CairoSteps,
+                        block_context_version: BlockContextVersion::default(),
                         is_vm_trace_needed: false,
                         cache_dir: Utf8PathBuf::from_path_buf(tempdir().unwrap().into_path())
                             .unwrap()
                             .join(CACHE_DIR),
+                        fork_data: None,
                         contracts_data: ContractsData::try_from(test.contracts().unwrap()).unwrap(),
                         environment_variables: test.env().clone(),
+                        fuzz_corpus_dir: test.path().unwrap().join(FUZZ_CORPUS_DIR),
+                        prune_fuzz_corpus: false,
+                        capture_output: true,
                     }),
                     output_config: Arc::new(OutputConfig {
                         detailed_resources: false,
+                        json: false,
                         execution_data_to_save: ExecutionDataToSave::default(),
                         versioned_programs_dir: Utf8PathBuf::from_path_buf(
                             tempdir().unwrap().into_path(),
                         )
                         .unwrap()
                         .join(VERSIONED_PROGRAMS_DIR),
+                        coverage_data_dir: Default::default(),
                     }),
                 }),
                 fork_targets: vec![ForkTarget::new(
@@ -173,6 +195,7 @@ fn fork_aliased_decorator() {
                 .unwrap()],
             },
             &mut BlockNumberMap::default(),
+            None,
         ))
         .expect("Runner fail");
 
@@ -224,14 +247,20 @@ fn fork_aliased_decorator_overrding() {
         .block_on(run_for_package(
             RunForPackageArgs {
                 test_targets: raw_test_targets,
+                target_hashes: vec![],
+                unchanged_targets: vec![],
                 package_name: "test_package".to_string(),
+                test_ordering: TestOrdering::default(),
                 tests_filter: TestsFilter::from_flags(
                     None,
                     false,
+                    None,
+                    vec![],
                     false,
                     false,
                     false,
                     Default::default(),
+                    None,
                 ),
                 forge_config: Arc::new(ForgeConfig {
                     test_runner_config: Arc::new(TestRunnerConfig {
@@ -239,21 +268,31 @@ fn fork_aliased_decorator_overrding() {
                         fuzzer_runs: NonZeroU32::new(256).unwrap(),
                         fuzzer_seed: 12345,
                         max_n_steps: None,
+                        timeout: None,
+                        max_gas: None,
+                        tracked_resource: TrackedResource::CairoSteps,
+                        block_context_version: BlockContextVersion::default(),
                         is_vm_trace_needed: false,
                         cache_dir: Utf8PathBuf::from_path_buf(tempdir().unwrap().into_path())
                             .unwrap()
                             .join(CACHE_DIR),
+                        fork_data: None,
                         contracts_data: ContractsData::try_from(test.contracts().unwrap()).unwrap(),
                         environment_variables: test.env().clone(),
+                        fuzz_corpus_dir: test.path().unwrap().join(FUZZ_CORPUS_DIR),
+                        prune_fuzz_corpus: false,
+                        capture_output: true,
                     }),
                     output_config: Arc::new(OutputConfig {
                         detailed_resources: false,
+                        json: false,
                         execution_data_to_save: ExecutionDataToSave::default(),
                         versioned_programs_dir: Utf8PathBuf::from_path_buf(
                             tempdir().unwrap().into_path(),
                         )
                         .unwrap()
                         .join(VERSIONED_PROGRAMS_DIR),
+                        coverage_data_dir: Default::default(),
                     }),
                 }),
                 fork_targets: vec![ForkTarget::new(
@@ -265,6 +304,7 @@ fn fork_aliased_decorator_overrding() {
                 .unwrap()],
             },
             &mut BlockNumberMap::default(),
+            None,
         ))
         .expect("Runner fail");
 
@@ -458,3 +498,401 @@ fn incompatible_abi() {
 
     assert_passed(&result);
 }
+
+#[test]
+fn multiple_forks_in_single_test() {
+    let test = test_case!(formatdoc!(
+        r#"
+            use starknet::contract_address_const;
+            use snforge_std::{{ create_fork, select_fork, active_fork, get_class_hash, load, ForkHandle }};
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {{
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }}
+
+            #[test]
+            #[fork(url: "{url}", block_number: 54060)]
+            fn multiple_forks_in_single_test() {{
+                let contract_address = contract_address_const::<0x202de98471a4fae6bcbabb96cab00437d381abc58b02509043778074d6781e9>();
+                let dispatcher = IHelloStarknetDispatcher {{ contract_address }};
+                let initial_fork = active_fork();
+                assert(initial_fork == ForkHandle {{ id: 0 }}, 'Wrong initial fork');
+
+                dispatcher.increase_balance(100);
+                assert(dispatcher.get_balance() == 100, 'Balance should be 100');
+
+                let same_block_fork = create_fork("{url}", 54060);
+                let early_block_fork = create_fork("{url}", 1);
+
+                select_fork(same_block_fork);
+                assert(dispatcher.get_balance() == 0, 'Fork should not see changes');
+                dispatcher.increase_balance(5);
+                assert(dispatcher.get_balance() == 5, 'Balance should be 5');
+
+                select_fork(early_block_fork);
+                let class_hash: felt252 = get_class_hash(contract_address).into();
+                assert(class_hash == 0, 'Contract should not exist');
+
+                select_fork(initial_fork);
+                assert(dispatcher.get_balance() == 100, 'Overlay should be preserved');
+                let class_hash: felt252 = get_class_hash(contract_address).into();
+                assert(class_hash != 0, 'Contract should exist');
+
+                select_fork(same_block_fork);
+                assert(dispatcher.get_balance() == 5, 'Overlay should be preserved');
+
+                // The same storage slot read from forks of different blocks
+                let eth = contract_address_const::<0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7>();
+                let total_supply_slot = selector!("ERC20_total_supply");
+                let later_block_fork = create_fork("{url}", 100000);
+
+                select_fork(initial_fork);
+                let initial_total_supply = load(eth, total_supply_slot, 2);
+                assert(initial_total_supply == array![88730316280408105750094, 0], 'Wrong total supply');
+
+                select_fork(later_block_fork);
+                let later_total_supply = load(eth, total_supply_slot, 2);
+                assert(later_total_supply != initial_total_supply, 'Storage should differ');
+            }}
+        "#,
+        url = node_rpc_url()
+    )
+    .as_str());
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn select_not_existing_fork() {
+    let test = test_case!(indoc!(
+        r"
+            use snforge_std::{ select_fork, ForkHandle };
+
+            #[test]
+            fn select_not_existing_fork() {
+                select_fork(ForkHandle { id: 3 });
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "select_not_existing_fork",
+        "Fork with id = 3 does not exist",
+    );
+}
+
+#[test]
+fn fork_get_fork_block_info() {
+    let test = test_case!(formatdoc!(
+        r#"
+            use starknet::contract_address_const;
+            use snforge_std::{{ declare, ContractClassTrait, DeclareResultTrait, get_fork_block_info }};
+
+            #[starknet::interface]
+            trait IBlockInfoChecker<TContractState> {{
+                fn read_block_number(self: @TContractState) -> u64;
+                fn read_block_timestamp(self: @TContractState) -> u64;
+                fn read_sequencer_address(self: @TContractState) -> starknet::ContractAddress;
+            }}
+
+            #[test]
+            #[fork(url: "{node_rpc_url}", block_number: 54060)]
+            fn test_fork_block_info() {{
+                let fork_block_info = get_fork_block_info();
+                assert(fork_block_info.block_number == 54060, 'Wrong block number');
+                assert(
+                    fork_block_info.block_hash == 0x06ae121e46f5375f93b00475fb130348ae38148e121f84b0865e17542e9485de,
+                    'Wrong block hash'
+                );
+                assert(fork_block_info.block_timestamp == 1711645884, 'Wrong block timestamp');
+                let expected_sequencer_addr = contract_address_const::<0x1176a1bd84444c89232ec27754698e5d2e7e1a7f1539f12027f28b23ec9f3d8>();
+                assert(fork_block_info.sequencer_address == expected_sequencer_addr, 'Wrong sequencer address');
+
+                let contract = declare("BlockInfoChecker").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@ArrayTrait::new()).unwrap();
+                let dispatcher = IBlockInfoCheckerDispatcher {{ contract_address }};
+
+                assert(dispatcher.read_block_timestamp() == fork_block_info.block_timestamp, 'Timestamps differ');
+                assert(dispatcher.read_block_number() == fork_block_info.block_number, 'Block numbers differ');
+            }}
+        "#,
+        node_rpc_url = node_rpc_url()
+    ).as_str(),
+    Contract::from_code_path(
+        "BlockInfoChecker".to_string(),
+        Path::new("tests/data/contracts/block_info_checker.cairo"),
+    ).unwrap());
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn fork_get_fork_block_info_not_forked() {
+    let test = test_case!(indoc!(
+        r"
+            use snforge_std::get_fork_block_info;
+
+            #[test]
+            fn test_fork_block_info_not_forked() {
+                get_fork_block_info();
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "test_fork_block_info_not_forked",
+        "get_fork_block_info can only be used in forked tests",
+    );
+}
+
+fn run_with_fork_data(
+    test: &TestCase,
+    fork_url: &str,
+    fork_data: ForkDataMode,
+) -> Vec<TestTargetSummary> {
+    let rt = Runtime::new().expect("Could not instantiate Runtime");
+
+    ScarbCommand::new_with_stdio()
+        .current_dir(test.path().unwrap())
+        .arg("build")
+        .arg("--test")
+        .run()
+        .unwrap();
+
+    let metadata = ScarbCommand::metadata()
+        .current_dir(test.path().unwrap())
+        .run()
+        .unwrap();
+
+    let package = metadata
+        .packages
+        .iter()
+        .find(|p| p.name == "test_package")
+        .unwrap();
+
+    let raw_test_targets =
+        load_test_artifacts(&test.path().unwrap().join("target/dev"), package).unwrap();
+
+    let mut block_number_map = if fork_data.is_replay() {
+        BlockNumberMap::offline()
+    } else {
+        BlockNumberMap::default()
+    };
+
+    rt.block_on(run_for_package(
+        RunForPackageArgs {
+            test_targets: raw_test_targets,
+            target_hashes: vec![],
+            unchanged_targets: vec![],
+            package_name: "test_package".to_string(),
+            test_ordering: TestOrdering::default(),
+            tests_filter: TestsFilter::from_flags(
+                None,
+                false,
+                None,
+                vec![],
+                false,
+                false,
+                false,
+                Default::default(),
+                None,
+            ),
+            forge_config: Arc::new(ForgeConfig {
+                test_runner_config: Arc::new(TestRunnerConfig {
+                    exit_first: false,
+                    fuzzer_runs: NonZeroU32::new(256).unwrap(),
+                    fuzzer_seed: 12345,
+                    max_n_steps: None,
+                    timeout: None,
+                    max_gas: None,
+                    tracked_resource: TrackedResource::CairoSteps,
+                    block_context_version: BlockContextVersion::default(),
+                    is_vm_trace_needed: false,
+                    cache_dir: Utf8PathBuf::from_path_buf(tempdir().unwrap().into_path())
+                        .unwrap()
+                        .join(CACHE_DIR),
+                    fork_data: Some(fork_data),
+                    contracts_data: ContractsData::try_from(test.contracts().unwrap()).unwrap(),
+                    environment_variables: test.env().clone(),
+                    fuzz_corpus_dir: test.path().unwrap().join(FUZZ_CORPUS_DIR),
+                    prune_fuzz_corpus: false,
+                    capture_output: true,
+                }),
+                output_config: Arc::new(OutputConfig {
+                    detailed_resources: false,
+                    json: false,
+                    execution_data_to_save: ExecutionDataToSave::default(),
+                    versioned_programs_dir: Utf8PathBuf::from_path_buf(
+                        tempdir().unwrap().into_path(),
+                    )
+                    .unwrap()
+                    .join(VERSIONED_PROGRAMS_DIR),
+                    coverage_data_dir: Default::default(),
+                }),
+            }),
+            fork_targets: vec![
+                ForkTarget::new("SNAPSHOT_FORK", fork_url, "number", "54060").unwrap(),
+            ],
+        },
+        &mut block_number_map,
+        None,
+    ))
+    .expect("Runner fail")
+}
+
+#[test]
+fn fork_record_and_replay_data() {
+    let test = test_case!(indoc!(
+        r#"
+            use starknet::contract_address_const;
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }
+
+            #[test]
+            #[fork("SNAPSHOT_FORK")]
+            fn fork_snapshot() {
+                let dispatcher = IHelloStarknetDispatcher {
+                    contract_address: contract_address_const::<0x202de98471a4fae6bcbabb96cab00437d381abc58b02509043778074d6781e9>()
+                };
+
+                dispatcher.increase_balance(100);
+
+                let balance = dispatcher.get_balance();
+                assert(balance == 100, 'Balance should be 100');
+            }
+        "#
+    ));
+    let fork_data_dir = Utf8PathBuf::from_path_buf(tempdir().unwrap().into_path()).unwrap();
+
+    let result = run_with_fork_data(
+        &test,
+        node_rpc_url().as_str(),
+        ForkDataMode::Record(fork_data_dir.clone()),
+    );
+    assert_passed(&result);
+    assert!(fork_data_dir.join("SNAPSHOT_FORK_54060.json").exists());
+
+    // Node is not reachable, every read has to be served from the snapshot
+    let result = run_with_fork_data(
+        &test,
+        "http://127.0.0.1:1/rpc",
+        ForkDataMode::Replay(fork_data_dir),
+    );
+    assert_passed(&result);
+}
+
+#[test]
+fn fork_replay_data_not_recorded() {
+    let test = test_case!(indoc!(
+        r#"
+            use starknet::syscalls::get_execution_info_syscall;
+
+            #[test]
+            #[fork("SNAPSHOT_FORK")]
+            fn fork_snapshot_missing() {
+                let block_number = get_execution_info_syscall().unwrap().deref().block_info.deref().block_number;
+                assert(block_number == 54060, 'Invalid block');
+            }
+        "#
+    ));
+    let fork_data_dir = Utf8PathBuf::from_path_buf(tempdir().unwrap().into_path()).unwrap();
+
+    let result = run_with_fork_data(
+        &test,
+        "http://127.0.0.1:1/rpc",
+        ForkDataMode::Replay(fork_data_dir),
+    );
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "fork_snapshot_missing",
+        "record it with --record-fork-data first",
+    );
+}
+
+#[test]
+fn call_contract_at_block() {
+    let test = test_case!(formatdoc!(
+        r#"
+            use core::array::ArrayTrait;
+            use starknet::contract_address_const;
+            use snforge_std::{{ call_contract_at_block, BlockId }};
+
+            #[starknet::interface]
+            trait IHelloStarknet<TContractState> {{
+                fn increase_balance(ref self: TContractState, amount: felt252);
+                fn get_balance(self: @TContractState) -> felt252;
+            }}
+
+            #[test]
+            #[fork(url: "{node_rpc_url}", block_number: 54060)]
+            fn test_call_contract_at_block() {{
+                let contract_address = contract_address_const::<0x202de98471a4fae6bcbabb96cab00437d381abc58b02509043778074d6781e9>();
+                let dispatcher = IHelloStarknetDispatcher {{ contract_address }};
+                dispatcher.increase_balance(100);
+                assert(dispatcher.get_balance() == 100, 'Wrong balance');
+
+                // Changes made in the test are not visible at the pinned block
+                let result = call_contract_at_block(
+                    contract_address, selector!("get_balance"), array![].span(), Option::None
+                ).unwrap();
+                assert(result == array![0].span(), 'Wrong balance at pinned block');
+
+                let result = call_contract_at_block(
+                    contract_address, selector!("get_balance"), array![].span(), Option::Some(BlockId::Number(50000))
+                );
+                assert(result.is_err(), 'Contract deployed at 50000');
+            }}
+        "#,
+        node_rpc_url = node_rpc_url()
+    ).as_str());
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn call_contract_at_block_not_forked() {
+    let test = test_case!(indoc!(
+        r#"
+            use starknet::contract_address_const;
+            use snforge_std::{ call_contract_at_block, BlockId };
+
+            #[test]
+            fn test_call_contract_at_block_not_forked() {
+                call_contract_at_block(
+                    contract_address_const::<0x1>(), selector!("get_balance"), array![].span(), Option::Some(BlockId::Number(50000))
+                );
+            }
+        "#
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "test_call_contract_at_block_not_forked",
+        "Calling contracts at a given block can only be done in forked tests",
+    );
+}
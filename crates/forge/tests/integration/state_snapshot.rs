@@ -0,0 +1,173 @@
+use indoc::indoc;
+use std::path::Path;
+use test_utils::runner::{assert_case_output_contains, assert_failed, assert_passed, Contract};
+use test_utils::running_tests::run_test_case;
+use test_utils::test_case;
+
+#[test]
+fn divergent_scenarios_from_the_same_snapshot() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use snforge_std::{ deploy_contract, snapshot_state, revert_to };
+
+        #[starknet::interface]
+        trait IHelloStarknet<TContractState> {
+            fn increase_balance(ref self: TContractState, amount: felt252);
+            fn get_balance(self: @TContractState) -> felt252;
+        }
+
+        #[test]
+        fn divergent_scenarios_from_the_same_snapshot() {
+            let dispatcher: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
+            dispatcher.increase_balance(100);
+
+            let snapshot = snapshot_state();
+
+            // First scenario
+            dispatcher.increase_balance(50);
+            let other: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
+            other.increase_balance(7);
+            assert(dispatcher.get_balance() == 150, 'first scenario balance');
+
+            revert_to(snapshot);
+
+            // Second scenario starts from the same state and deploys at the same address
+            assert(dispatcher.get_balance() == 100, 'balance not reverted');
+            dispatcher.increase_balance(1);
+            let other_again: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
+            assert(other_again.contract_address == other.contract_address, 'address mismatch');
+            assert(other_again.get_balance() == 0, 'deployment not reverted');
+            assert(dispatcher.get_balance() == 101, 'second scenario balance');
+
+            revert_to(snapshot);
+            assert(dispatcher.get_balance() == 100, 'balance not reverted again');
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn revert_to_restores_cheats_unless_kept() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use snforge_std::{
+            deploy_contract, snapshot_state, revert_to, revert_to_keeping_cheats,
+            start_cheat_block_number
+        };
+
+        #[starknet::interface]
+        trait ICheatBlockNumberChecker<TContractState> {
+            fn get_block_number(ref self: TContractState) -> u64;
+        }
+
+        #[test]
+        fn revert_to_restores_cheats_unless_kept() {
+            let dispatcher: ICheatBlockNumberCheckerDispatcher = deploy_contract(
+                "CheatBlockNumberChecker", @array![]
+            );
+            let block_number = dispatcher.get_block_number();
+
+            let snapshot = snapshot_state();
+
+            start_cheat_block_number(dispatcher.contract_address, 123);
+            revert_to(snapshot);
+            assert(dispatcher.get_block_number() == block_number, 'cheat not reverted');
+
+            start_cheat_block_number(dispatcher.contract_address, 123);
+            revert_to_keeping_cheats(snapshot);
+            assert(dispatcher.get_block_number() == 123, 'cheat not kept');
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "CheatBlockNumberChecker".to_string(),
+            Path::new("tests/data/contracts/cheat_block_number_checker.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn nested_snapshots() {
+    let test = test_case!(
+        indoc!(
+            r#"
+        use snforge_std::{ deploy_contract, snapshot_state, revert_to };
+
+        #[starknet::interface]
+        trait IHelloStarknet<TContractState> {
+            fn increase_balance(ref self: TContractState, amount: felt252);
+            fn get_balance(self: @TContractState) -> felt252;
+        }
+
+        #[test]
+        fn nested_snapshots() {
+            let dispatcher: IHelloStarknetDispatcher = deploy_contract("HelloStarknet", @array![]);
+
+            let outer = snapshot_state();
+            dispatcher.increase_balance(1);
+            let inner = snapshot_state();
+            dispatcher.increase_balance(2);
+
+            revert_to(inner);
+            assert(dispatcher.get_balance() == 1, 'inner not restored');
+
+            revert_to(outer);
+            assert(dispatcher.get_balance() == 0, 'outer not restored');
+        }
+    "#
+        ),
+        Contract::from_code_path(
+            "HelloStarknet".to_string(),
+            Path::new("tests/data/contracts/hello_starknet.cairo"),
+        )
+        .unwrap()
+    );
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn revert_to_released_snapshot() {
+    let test = test_case!(indoc!(
+        r"
+        use snforge_std::{ snapshot_state, revert_to };
+
+        #[test]
+        fn revert_to_released_snapshot() {
+            let outer = snapshot_state();
+            let inner = snapshot_state();
+
+            revert_to(outer);
+            revert_to(inner);
+        }
+    "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_failed(&result);
+    assert_case_output_contains(
+        &result,
+        "revert_to_released_snapshot",
+        "Snapshot with id = 1 was released after reverting to an earlier snapshot",
+    );
+}
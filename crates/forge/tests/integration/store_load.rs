@@ -10,7 +10,7 @@ fn store_load_simple() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load };
+            use snforge_std::{ store, load };
 
             #[starknet::interface]
             trait IHelloStarknet<TContractState> {
@@ -19,9 +19,7 @@ fn store_load_simple() {
             }
 
             fn deploy_contract() -> IHelloStarknetDispatcher {
-                let contract = declare("HelloStarknet").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IHelloStarknetDispatcher { contract_address }
+                snforge_std::deploy_contract("HelloStarknet", @array![])
             }
 
             #[test]
@@ -61,7 +59,7 @@ fn store_load_wrong_selector() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load };
+            use snforge_std::{ store, load };
 
             #[starknet::interface]
             trait IHelloStarknet<TContractState> {
@@ -70,9 +68,7 @@ fn store_load_wrong_selector() {
             }
 
             fn deploy_contract() -> IHelloStarknetDispatcher {
-                let contract = declare("HelloStarknet").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IHelloStarknetDispatcher { contract_address }
+                snforge_std::deploy_contract("HelloStarknet", @array![])
             }
 
             #[test]
@@ -111,7 +107,7 @@ fn store_load_wrong_data_length() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load };
+            use snforge_std::{ store, load };
 
             #[starknet::interface]
             trait IHelloStarknet<TContractState> {
@@ -120,9 +116,7 @@ fn store_load_wrong_data_length() {
             }
 
             fn deploy_contract() -> IHelloStarknetDispatcher {
-                let contract = declare("HelloStarknet").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IHelloStarknetDispatcher { contract_address }
+                snforge_std::deploy_contract("HelloStarknet", @array![])
             }
 
             #[test]
@@ -158,7 +152,7 @@ fn store_load_max_boundaries_input() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load };
+            use snforge_std::{ store, load };
 
             #[starknet::interface]
             trait IHelloStarknet<TContractState> {
@@ -167,9 +161,7 @@ fn store_load_max_boundaries_input() {
             }
 
             fn deploy_contract() -> IHelloStarknetDispatcher {
-                let contract = declare("HelloStarknet").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IHelloStarknetDispatcher { contract_address }
+                snforge_std::deploy_contract("HelloStarknet", @array![])
             }
 
             const MAX_STORAGE: felt252 = 3618502788666131106986593281521497120414687020801267626233049500247285301248;
@@ -253,7 +245,7 @@ fn store_load_structure() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load };
+            use snforge_std::{ store, load };
 
             #[derive(Serde, Copy, Drop, starknet::Store)]
             struct NestedStructure {
@@ -287,9 +279,7 @@ fn store_load_structure() {
             }
 
             fn deploy_contract() -> IStorageTesterDispatcher {
-                let contract = declare("StorageTester").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IStorageTesterDispatcher { contract_address }
+                snforge_std::deploy_contract("StorageTester", @array![])
             }
 
             #[test]
@@ -334,7 +324,7 @@ fn store_load_felt_to_structure() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load, map_entry_address };
+            use snforge_std::{ store, load, map_entry_address };
 
             #[derive(Serde, Copy, Drop, starknet::Store)]
             struct NestedStructure {
@@ -368,9 +358,7 @@ fn store_load_felt_to_structure() {
             }
 
             fn deploy_contract() -> IStorageTesterDispatcher {
-                let contract = declare("StorageTester").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IStorageTesterDispatcher { contract_address }
+                snforge_std::deploy_contract("StorageTester", @array![])
             }
 
             #[test]
@@ -423,7 +411,7 @@ fn store_load_structure_to_felt() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, store, load, map_entry_address, DeclareResultTrait };
+            use snforge_std::{ store, load, map_entry_address };
             
             #[derive(Serde, Copy, Drop, starknet::Store, Hash)]
             struct NestedKey {
@@ -450,9 +438,7 @@ fn store_load_structure_to_felt() {
             }
 
             fn deploy_contract() -> IStorageTesterDispatcher {
-                let contract = declare("StorageTester").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IStorageTesterDispatcher { contract_address }
+                snforge_std::deploy_contract("StorageTester", @array![])
             }
 
             #[test]
@@ -503,7 +489,7 @@ fn store_load_felt_to_felt() {
         indoc!(
             r#"
             use starknet::ContractAddress;
-            use snforge_std::{ declare, ContractClassTrait, DeclareResultTrait, store, load, map_entry_address };
+            use snforge_std::{ store, load, map_entry_address };
 
             #[starknet::interface]
             trait IStorageTester<TContractState> {
@@ -512,9 +498,7 @@ fn store_load_felt_to_felt() {
             }
 
             fn deploy_contract() -> IStorageTesterDispatcher {
-                let contract = declare("StorageTester").unwrap().contract_class();
-                let (contract_address, _) = contract.deploy(@array![]).unwrap();
-                IStorageTesterDispatcher { contract_address }
+                snforge_std::deploy_contract("StorageTester", @array![])
             }
 
             #[test]
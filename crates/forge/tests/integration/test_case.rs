@@ -0,0 +1,113 @@
+use indoc::indoc;
+use test_utils::runner::{assert_case_output_contains, assert_passed, TestCase};
+use test_utils::running_tests::run_test_case;
+
+#[test]
+fn every_case_is_a_separate_test() {
+    let test = test_utils::test_case!(indoc!(
+        r#"
+            #[test]
+            #[test_case(2, 3, 5)]
+            #[test_case(0, 0, 0, name: "zeros")]
+            #[test_case(0xffffffff, 1, 0x100000000, name: "large")]
+            fn test_add(a: u64, b: u64, expected: u64) {
+                assert(a + b == expected, 'invalid sum');
+            }
+        "#
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+
+    let mut names: Vec<_> = TestCase::find_test_result(&result)
+        .test_case_summaries
+        .iter()
+        .map(|summary| summary.name().rsplit("tests::").next().unwrap())
+        .collect();
+    names.sort_unstable();
+    assert_eq!(
+        names,
+        ["test_add::case_1", "test_add::large", "test_add::zeros"]
+    );
+}
+
+#[test]
+fn cases_run_in_separate_state() {
+    let test = test_utils::test_case!(indoc!(
+        r#"
+            use starknet::storage_access::{
+                storage_address_from_base, storage_base_address_from_felt252
+            };
+            use starknet::syscalls::{storage_read_syscall, storage_write_syscall};
+
+            #[test]
+            #[test_case(1, name: "first")]
+            #[test_case(2, name: "second")]
+            fn writes_once(value: felt252) {
+                let address = storage_base_address_from_felt252(1);
+                let slot = storage_address_from_base(address);
+
+                assert(storage_read_syscall(0, slot).unwrap() == 0, 'state is shared');
+                storage_write_syscall(0, slot, value).unwrap();
+            }
+        "#
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn should_panic_applies_to_every_case() {
+    let test = test_utils::test_case!(indoc!(
+        r#"
+            #[test]
+            #[should_panic(expected: 'u64_add Overflow')]
+            #[test_case(0xffffffffffffffff, 1)]
+            #[test_case(1, 0xffffffffffffffff, name: "swapped")]
+            fn overflows(a: u64, b: u64) {
+                let _ = a + b;
+            }
+        "#
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+    assert_eq!(
+        TestCase::find_test_result(&result)
+            .test_case_summaries
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn failing_case_is_reported_separately() {
+    let test = test_utils::test_case!(indoc!(
+        r#"
+            #[test]
+            #[test_case(2, 3, 5, name: "correct")]
+            #[test_case(2, 2, 5, name: "wrong")]
+            fn test_add(a: u64, b: u64, expected: u64) {
+                assert(a + b == expected, 'invalid sum');
+            }
+        "#
+    ));
+
+    let result = run_test_case(&test);
+
+    let summaries = &TestCase::find_test_result(&result).test_case_summaries;
+    let failed: Vec<_> = summaries
+        .iter()
+        .filter(|summary| summary.is_failed())
+        .map(|summary| summary.name())
+        .collect();
+
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(failed.len(), 1);
+    assert!(failed[0].ends_with("test_add::wrong"), "{}", failed[0]);
+    assert_case_output_contains(&result, "wrong", "invalid sum");
+}
@@ -0,0 +1,146 @@
+use indoc::indoc;
+use test_utils::runner::{assert_passed, assert_timed_out, Contract, TestCase};
+use test_utils::running_tests::run_test_case;
+
+#[test]
+fn spinning_test_times_out() {
+    let test = test_utils::test_case!(indoc!(
+        r"
+            #[test]
+            #[timeout(1)]
+            fn spinning() {
+                let mut i: u64 = 0;
+                loop {
+                    if i == 0xffffffffffffffff {
+                        break;
+                    }
+                    i += 1;
+                };
+            }
+
+            #[test]
+            fn passing() {
+                assert(1 + 1 == 2, 'invalid sum');
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_timed_out(&result, "spinning");
+
+    let summaries = &TestCase::find_test_result(&result).test_case_summaries;
+    assert!(summaries
+        .iter()
+        .any(|summary| summary.is_passed() && summary.name().ends_with("::passing")));
+    assert!(summaries
+        .iter()
+        .filter(|summary| summary.is_timed_out())
+        .all(|summary| summary.is_failed()));
+}
+
+#[test]
+fn test_within_timeout_passes() {
+    let test = test_utils::test_case!(indoc!(
+        r"
+            #[test]
+            #[timeout(300)]
+            fn short() {
+                let mut i: u64 = 0;
+                while i != 1000 {
+                    i += 1;
+                };
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_passed(&result);
+}
+
+#[test]
+fn spinning_fuzz_test_times_out() {
+    let test = test_utils::test_case!(indoc!(
+        r"
+            #[test]
+            #[timeout(1)]
+            #[fuzzer(runs: 10)]
+            fn spinning(a: u8) {
+                let mut i: u64 = a.into();
+                loop {
+                    if i == 0xffffffffffffffff {
+                        break;
+                    }
+                    i += 1;
+                };
+            }
+        "
+    ));
+
+    let result = run_test_case(&test);
+
+    assert_timed_out(&result, "spinning");
+}
+
+#[test]
+fn test_spinning_in_contract_times_out() {
+    let test = test_utils::test_case!(
+        indoc!(
+            r#"
+            use snforge_std::{declare, ContractClassTrait, DeclareResultTrait};
+
+            #[starknet::interface]
+            trait ISpinner<TContractState> {
+                fn spin(ref self: TContractState);
+            }
+
+            #[test]
+            #[timeout(1)]
+            fn spinning_in_contract() {
+                let contract = declare("Spinner").unwrap().contract_class();
+                let (contract_address, _) = contract.deploy(@array![]).unwrap();
+                let dispatcher = ISpinnerDispatcher { contract_address };
+
+                loop {
+                    dispatcher.spin();
+                };
+            }
+        "#
+        ),
+        Contract::new(
+            "Spinner",
+            indoc!(
+                r"
+                #[starknet::interface]
+                trait ISpinner<TContractState> {
+                    fn spin(ref self: TContractState);
+                }
+
+                #[starknet::contract]
+                mod Spinner {
+                    #[storage]
+                    struct Storage {}
+
+                    #[abi(embed_v0)]
+                    impl SpinnerImpl of super::ISpinner<ContractState> {
+                        fn spin(ref self: ContractState) {
+                            let mut i: u64 = 0;
+                            loop {
+                                if i == 0xffffffffffffffff {
+                                    break;
+                                }
+                                i += 1;
+                            };
+                        }
+                    }
+                }
+                "
+            )
+        )
+    );
+
+    let result = run_test_case(&test);
+
+    assert_timed_out(&result, "spinning_in_contract");
+}
@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use blockifier::blockifier::block::{BlockInfo, GasPrices};
 use blockifier::bouncer::BouncerConfig;
 use blockifier::context::{BlockContext, ChainInfo, FeeTokenAddresses, TransactionContext};
@@ -6,7 +7,7 @@ use blockifier::execution::entry_point::EntryPointExecutionContext;
 use blockifier::transaction::objects::{
     CommonAccountFields, CurrentTransactionInfo, TransactionInfo,
 };
-use blockifier::versioned_constants::VersionedConstants;
+use blockifier::versioned_constants::{StarknetVersion, VersionedConstants};
 use cairo_vm::vm::runners::cairo_runner::RunResources;
 use conversions::string::TryFromHexStr;
 use serde::{Deserialize, Serialize};
@@ -21,30 +22,101 @@ use starknet_api::{
 };
 use starknet_types_core::felt::Felt;
 use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
 use std::num::NonZeroU128;
+use std::str::FromStr;
 use std::sync::Arc;
 
 pub const DEFAULT_CHAIN_ID: &str = "SN_SEPOLIA";
 pub const DEFAULT_BLOCK_NUMBER: u64 = 2000;
 pub const SEQUENCER_ADDRESS: &str = "0x1000";
-pub const ERC20_CONTRACT_ADDRESS: &str = "0x1001";
+/// Address of the STRK fee token contract on Starknet Mainnet and Sepolia
+pub const STRK_CONTRACT_ADDRESS: &str =
+    "0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d";
+/// Address of the ETH fee token contract on Starknet Mainnet and Sepolia
+pub const ETH_CONTRACT_ADDRESS: &str =
+    "0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
 
 fn default_chain_id() -> ChainId {
     ChainId::from(String::from(DEFAULT_CHAIN_ID))
 }
 
+/// Starknet version whose versioned constants (step limits, resource costs) tests are run with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockContextVersion {
+    V0_13_0,
+    #[default]
+    V0_13_1,
+    V0_13_2,
+}
+
+impl BlockContextVersion {
+    pub const ALL: [Self; 3] = [Self::V0_13_0, Self::V0_13_1, Self::V0_13_2];
+
+    #[must_use]
+    pub fn versioned_constants(self) -> &'static VersionedConstants {
+        VersionedConstants::get(match self {
+            Self::V0_13_0 => StarknetVersion::V0_13_0,
+            Self::V0_13_1 => StarknetVersion::V0_13_1,
+            Self::V0_13_2 => StarknetVersion::V0_13_2,
+        })
+    }
+
+    /// Block info of tests which are not run on a fork
+    #[must_use]
+    pub fn default_block_info(self) -> SerializableBlockInfo {
+        SerializableBlockInfo {
+            // Blobs are used for publishing state diffs since 0.13.1
+            use_kzg_da: self != Self::V0_13_0,
+            ..Default::default()
+        }
+    }
+}
+
+impl Display for BlockContextVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let version = match self {
+            Self::V0_13_0 => "0.13.0",
+            Self::V0_13_1 => "0.13.1",
+            Self::V0_13_2 => "0.13.2",
+        };
+        write!(f, "{version}")
+    }
+}
+
+impl FromStr for BlockContextVersion {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|version| version.to_string() == value)
+            .ok_or_else(|| {
+                let supported: Vec<_> = Self::ALL.iter().map(ToString::to_string).collect();
+                anyhow!(
+                    "Unknown block context version = {value}, supported versions are: {}",
+                    supported.join(", ")
+                )
+            })
+    }
+}
+
 #[must_use]
-pub fn build_block_context(block_info: &BlockInfo, chain_id: Option<ChainId>) -> BlockContext {
+pub fn build_block_context(
+    block_info: &BlockInfo,
+    chain_id: Option<ChainId>,
+    version: BlockContextVersion,
+) -> BlockContext {
     BlockContext::new(
         block_info.clone(),
         ChainInfo {
             chain_id: chain_id.unwrap_or_else(default_chain_id),
             fee_token_addresses: FeeTokenAddresses {
-                strk_fee_token_address: contract_address!(ERC20_CONTRACT_ADDRESS),
-                eth_fee_token_address: contract_address!(ERC20_CONTRACT_ADDRESS),
+                strk_fee_token_address: contract_address!(STRK_CONTRACT_ADDRESS),
+                eth_fee_token_address: contract_address!(ETH_CONTRACT_ADDRESS),
             },
         },
-        VersionedConstants::latest_constants().clone(), // 0.13.1
+        version.versioned_constants().clone(),
         BouncerConfig::default(),
     )
 }
@@ -87,9 +159,10 @@ fn build_tx_info() -> TransactionInfo {
 pub fn build_transaction_context(
     block_info: &BlockInfo,
     chain_id: Option<ChainId>,
+    version: BlockContextVersion,
 ) -> TransactionContext {
     TransactionContext {
-        block_context: build_block_context(block_info, chain_id),
+        block_context: build_block_context(block_info, chain_id, version),
         tx_info: build_tx_info(),
     }
 }
@@ -98,8 +171,9 @@ pub fn build_transaction_context(
 pub fn build_context(
     block_info: &BlockInfo,
     chain_id: Option<ChainId>,
+    version: BlockContextVersion,
 ) -> EntryPointExecutionContext {
-    let transaction_context = Arc::new(build_transaction_context(block_info, chain_id));
+    let transaction_context = Arc::new(build_transaction_context(block_info, chain_id, version));
 
     EntryPointExecutionContext::new(transaction_context, ExecutionMode::Execute, false).unwrap()
 }
@@ -9,7 +9,7 @@ use starknet_types_core::felt::Felt;
 use std::collections::HashMap;
 
 /// A simple implementation of `StateReader` using `HashMap`s as storage.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct DictStateReader {
     pub storage_view: HashMap<StorageEntry, Felt>,
     pub address_to_nonce: HashMap<ContractAddress, Nonce>,
@@ -0,0 +1,108 @@
+use crate::StarknetArtifacts;
+use anyhow::{bail, Context, Result};
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use std::fs::File;
+use std::io::Read;
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+const STARKNET_ARTIFACTS_SUFFIX: &str = "starknet_artifacts.json";
+
+/// `.zip` archive with `starknet_artifacts.json` and the files it references, read without extracting it
+pub(crate) struct ArtifactsArchive {
+    path: Utf8PathBuf,
+    archive: ZipArchive<File>,
+    /// Entry of the `starknet_artifacts.json` file, paths in it are relative to its directory
+    artifacts_entry: String,
+}
+
+impl ArtifactsArchive {
+    pub(crate) fn open(path: &Utf8Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Failed to open archive {path}"))?;
+        let archive =
+            ZipArchive::new(file).with_context(|| format!("Failed to read archive {path}"))?;
+
+        let artifacts_entries: Vec<_> = archive
+            .file_names()
+            .filter(|name| name.ends_with(STARKNET_ARTIFACTS_SUFFIX))
+            .collect();
+        let artifacts_entry = match artifacts_entries.as_slice() {
+            [entry] => (*entry).to_string(),
+            [] => bail!("Archive {path} does not contain a {STARKNET_ARTIFACTS_SUFFIX} file"),
+            entries => bail!(
+                "Archive {path} contains multiple {STARKNET_ARTIFACTS_SUFFIX} files: {}",
+                entries.join(", ")
+            ),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            archive,
+            artifacts_entry,
+        })
+    }
+
+    /// Parses `starknet_artifacts.json`, failing if any of the files it references is missing from the archive
+    pub(crate) fn artifacts(&mut self) -> Result<StarknetArtifacts> {
+        let entry = self.artifacts_entry.clone();
+        let starknet_artifacts = self.read_entry(&entry)?;
+        let starknet_artifacts: StarknetArtifacts = serde_json::from_str(&starknet_artifacts)
+            .with_context(|| {
+                format!(
+                    "Failed to parse {entry} from archive {}. Make sure you have enabled sierra code generation in Scarb.toml",
+                    self.path
+                )
+            })?;
+
+        for contract in &starknet_artifacts.contracts {
+            let sierra_entry = self.entry_name(&contract.artifacts.sierra);
+            if !self.archive.file_names().any(|name| name == sierra_entry) {
+                bail!(
+                    "Archive {} does not contain entry {sierra_entry} referenced by contract = {}",
+                    self.path,
+                    contract.contract_name
+                );
+            }
+        }
+
+        Ok(starknet_artifacts)
+    }
+
+    /// Reads the file at the path relative to the `starknet_artifacts.json` file
+    pub(crate) fn read_relative(&mut self, relative_path: &Utf8Path) -> Result<String> {
+        let entry = self.entry_name(relative_path);
+
+        self.read_entry(&entry)
+    }
+
+    /// Entries of zip archives are always separated with `/`
+    fn entry_name(&self, relative_path: &Utf8Path) -> String {
+        let path: Utf8PathBuf = Utf8Path::new(&self.artifacts_entry).parent().map_or_else(
+            || relative_path.to_path_buf(),
+            |dir| dir.join(relative_path),
+        );
+
+        path.components()
+            .map(Utf8Component::as_str)
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn read_entry(&mut self, entry: &str) -> Result<String> {
+        let mut file = match self.archive.by_name(entry) {
+            Ok(file) => file,
+            Err(ZipError::FileNotFound) => {
+                bail!("Archive {} does not contain entry {entry}", self.path)
+            }
+            Err(err) => Err(err).with_context(|| {
+                format!("Failed to read entry {entry} of archive {}", self.path)
+            })?,
+        };
+
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .with_context(|| format!("Failed to read entry {entry} of archive {}", self.path))?;
+
+        Ok(content)
+    }
+}
@@ -30,8 +30,10 @@ pub struct ScarbCommand {
     env: HashMap<OsString, Option<OsString>>,
     inherit_stderr: bool,
     inherit_stdout: bool,
+    stdout_to_stderr: bool,
     json: bool,
     offline: bool,
+    quiet: bool,
     manifest_path: Option<PathBuf>,
     scarb_path: Option<PathBuf>,
 }
@@ -165,6 +167,12 @@ impl ScarbCommand {
         self
     }
 
+    /// Redirect standard output of the command to standard error of this process.
+    pub fn stdout_to_stderr(&mut self) -> &mut Self {
+        self.stdout_to_stderr = true;
+        self
+    }
+
     /// Set output format to JSON.
     pub fn json(&mut self) -> &mut Self {
         self.json = true;
@@ -177,6 +185,12 @@ impl ScarbCommand {
         self
     }
 
+    /// Suppresses Scarb output other than errors.
+    pub fn quiet(&mut self) -> &mut Self {
+        self.quiet = true;
+        self
+    }
+
     /// Build executable `scarb` command.
     #[must_use]
     pub fn command(&self) -> Command {
@@ -192,6 +206,10 @@ impl ScarbCommand {
             cmd.arg("--offline");
         }
 
+        if self.quiet {
+            cmd.arg("--quiet");
+        }
+
         if let Some(manifest_path) = &self.manifest_path {
             cmd.arg("--manifest-path").arg(manifest_path);
         }
@@ -214,7 +232,9 @@ impl ScarbCommand {
             cmd.stderr(Stdio::inherit());
         }
 
-        if self.inherit_stdout {
+        if self.stdout_to_stderr {
+            cmd.stdout(io::stderr());
+        } else if self.inherit_stdout {
             cmd.stdout(Stdio::inherit());
         }
 
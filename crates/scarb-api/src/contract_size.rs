@@ -0,0 +1,208 @@
+use crate::StarknetContractArtifacts;
+use anyhow::{Context, Result};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Maximal size of a Sierra contract class accepted by Starknet, in bytes
+pub const MAX_CONTRACT_CLASS_SIZE: usize = 4_089_446;
+/// Maximal length of the bytecode of a compiled (CASM) contract class accepted by Starknet, in felts
+pub const MAX_BYTECODE_SIZE: usize = 81_920;
+/// Contracts using more than this percentage of any limit are highlighted in the report
+const NEAR_LIMIT_PERCENTAGE: usize = 80;
+
+/// Sizes of a compiled contract, compared against the Starknet size limits
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct ContractSize {
+    pub contract_name: String,
+    /// Size of the Sierra contract class, in bytes
+    pub sierra_length: usize,
+    /// Size of the compiled (CASM) contract class, in bytes
+    pub casm_length: usize,
+    /// Length of the CASM bytecode, in felts
+    pub bytecode_size: usize,
+}
+
+#[derive(Deserialize)]
+struct CasmBytecode {
+    bytecode: Vec<IgnoredAny>,
+}
+
+impl ContractSize {
+    fn from_artifacts(contract_name: &str, artifacts: &StarknetContractArtifacts) -> Result<Self> {
+        let casm_json = artifacts
+            .casm()
+            .with_context(|| format!("Failed to compile casm of contract = {contract_name}"))?;
+        let casm: CasmBytecode = serde_json::from_str(casm_json)
+            .with_context(|| format!("Failed to parse casm of contract = {contract_name}"))?;
+
+        Ok(Self {
+            contract_name: contract_name.to_string(),
+            sierra_length: artifacts.sierra.len(),
+            casm_length: casm_json.len(),
+            bytecode_size: casm.bytecode.len(),
+        })
+    }
+
+    /// Whether the contract uses more than [`NEAR_LIMIT_PERCENTAGE`] of any of the size limits
+    #[must_use]
+    pub fn is_near_limit(&self) -> bool {
+        let is_near = |size: usize, limit: usize| size * 100 > limit * NEAR_LIMIT_PERCENTAGE;
+
+        is_near(self.sierra_length, MAX_CONTRACT_CLASS_SIZE)
+            || is_near(self.bytecode_size, MAX_BYTECODE_SIZE)
+    }
+}
+
+/// Returns sizes of all contracts, sorted by the contract name
+pub fn contract_sizes<'a>(
+    artifacts: impl IntoIterator<Item = (&'a String, &'a StarknetContractArtifacts)>,
+) -> Result<Vec<ContractSize>> {
+    let mut sizes = artifacts
+        .into_iter()
+        .map(|(name, artifacts)| ContractSize::from_artifacts(name, artifacts))
+        .collect::<Result<Vec<_>>>()?;
+    sizes.sort_by(|a, b| a.contract_name.cmp(&b.contract_name));
+
+    Ok(sizes)
+}
+
+/// Table of contract sizes, the largest bytecode first. Contracts near a size limit are marked with `!`
+pub struct ContractSizeReport<'a>(pub &'a [ContractSize]);
+
+impl Display for ContractSizeReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut sizes: Vec<_> = self.0.iter().collect();
+        sizes.sort_by(|a, b| {
+            b.bytecode_size
+                .cmp(&a.bytecode_size)
+                .then_with(|| b.sierra_length.cmp(&a.sierra_length))
+                .then_with(|| a.contract_name.cmp(&b.contract_name))
+        });
+
+        let name_width = sizes
+            .iter()
+            .map(|size| size.contract_name.len())
+            .chain(["Contract".len()])
+            .max()
+            .unwrap_or_default();
+
+        write!(
+            f,
+            "  {:<name_width$}  {:>14}  {:>14}  {:>16}",
+            "Contract", "Sierra [bytes]", "CASM [bytes]", "Bytecode [felts]"
+        )?;
+        for size in sizes {
+            let marker = if size.is_near_limit() { '!' } else { ' ' };
+            write!(
+                f,
+                "\n{marker} {:<name_width$}  {:>14}  {:>14}  {:>16}",
+                size.contract_name, size.sierra_length, size.casm_length, size.bytecode_size
+            )?;
+        }
+        write!(
+            f,
+            "\nLimits: {MAX_CONTRACT_CLASS_SIZE} bytes of Sierra, {MAX_BYTECODE_SIZE} felts of bytecode"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+    use std::collections::HashMap;
+
+    fn artifacts(sierra_length: usize, bytecode_size: usize) -> StarknetContractArtifacts {
+        let bytecode = vec!["\"0x1\""; bytecode_size].join(",");
+
+        StarknetContractArtifacts::new(
+            "x".repeat(sierra_length),
+            format!(r#"{{"bytecode":[{bytecode}]}}"#),
+        )
+    }
+
+    #[test]
+    fn sizes_of_contracts() {
+        let map = HashMap::from([
+            ("B".to_string(), artifacts(10, 2)),
+            ("A".to_string(), artifacts(20, 1)),
+        ]);
+
+        let sizes = contract_sizes(&map).unwrap();
+
+        assert_eq!(
+            sizes,
+            vec![
+                ContractSize {
+                    contract_name: "A".to_string(),
+                    sierra_length: 20,
+                    casm_length: 20,
+                    bytecode_size: 1,
+                },
+                ContractSize {
+                    contract_name: "B".to_string(),
+                    sierra_length: 10,
+                    casm_length: 26,
+                    bytecode_size: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_casm() {
+        let map = HashMap::from([(
+            "Broken".to_string(),
+            StarknetContractArtifacts::new(String::new(), "{}".to_string()),
+        )]);
+
+        let err = contract_sizes(&map).unwrap_err();
+
+        assert_eq!(err.to_string(), "Failed to parse casm of contract = Broken");
+    }
+
+    #[test]
+    fn near_limit() {
+        let size = |sierra_length, bytecode_size| ContractSize {
+            contract_name: "C".to_string(),
+            sierra_length,
+            casm_length: 0,
+            bytecode_size,
+        };
+
+        assert!(
+            !size(MAX_CONTRACT_CLASS_SIZE * 8 / 10, MAX_BYTECODE_SIZE * 8 / 10).is_near_limit()
+        );
+        assert!(size(MAX_CONTRACT_CLASS_SIZE * 9 / 10, 0).is_near_limit());
+        assert!(size(0, MAX_BYTECODE_SIZE * 9 / 10).is_near_limit());
+    }
+
+    #[test]
+    fn report_is_sorted_by_bytecode_size() {
+        let sizes = vec![
+            ContractSize {
+                contract_name: "Small".to_string(),
+                sierra_length: 100,
+                casm_length: 200,
+                bytecode_size: 10,
+            },
+            ContractSize {
+                contract_name: "LargeContract".to_string(),
+                sierra_length: 1000,
+                casm_length: 2000,
+                bytecode_size: 80_000,
+            },
+        ];
+
+        assert_eq!(
+            ContractSizeReport(&sizes).to_string(),
+            indoc! {"
+                  Contract       Sierra [bytes]    CASM [bytes]  Bytecode [felts]
+                ! LargeContract            1000            2000             80000
+                  Small                     100             200                10
+                Limits: 4089446 bytes of Sierra, 81920 felts of bytecode"
+            }
+        );
+    }
+}
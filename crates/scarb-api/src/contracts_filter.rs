@@ -0,0 +1,105 @@
+use anyhow::{bail, Result};
+use glob::{Pattern, PatternError};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Glob pattern passed with `--contracts-filter`, e.g. `ERC20*`, selecting contracts by their names
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractsFilter(Pattern);
+
+impl FromStr for ContractsFilter {
+    type Err = PatternError;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        Pattern::new(pattern).map(Self)
+    }
+}
+
+impl fmt::Display for ContractsFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ContractsFilter {
+    #[must_use]
+    pub fn matches(&self, contract_name: &str) -> bool {
+        self.0.matches(contract_name)
+    }
+
+    /// Removes the contracts not matching the filter
+    pub fn retain<T>(&self, contracts: &mut BTreeMap<String, T>) {
+        contracts.retain(|contract_name, _| self.matches(contract_name));
+    }
+
+    /// Fails if none of the contract names matches the filter, so a typo in it does not go unnoticed
+    pub fn ensure_matches_any<'a>(
+        &self,
+        contract_names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<()> {
+        let mut contract_names: Vec<_> = contract_names.into_iter().collect();
+        if contract_names.iter().any(|name| self.matches(name)) {
+            return Ok(());
+        }
+
+        contract_names.sort_unstable();
+        contract_names.dedup();
+        if contract_names.is_empty() {
+            bail!("No contracts match --contracts-filter = {self}, no contracts were found");
+        }
+        bail!(
+            "No contracts match --contracts-filter = {self}, available contracts: {}",
+            contract_names.join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_glob() {
+        let filter = ContractsFilter::from_str("ERC20*").unwrap();
+
+        assert!(filter.matches("ERC20"));
+        assert!(filter.matches("ERC20Mintable"));
+        assert!(!filter.matches("MyERC20"));
+    }
+
+    #[test]
+    fn retains_matching_contracts() {
+        let filter = ContractsFilter::from_str("*Token").unwrap();
+        let mut contracts = BTreeMap::from([
+            ("GovernanceToken".to_string(), 1),
+            ("Governor".to_string(), 2),
+        ]);
+
+        filter.retain(&mut contracts);
+
+        assert_eq!(
+            contracts,
+            BTreeMap::from([("GovernanceToken".to_string(), 1)])
+        );
+    }
+
+    #[test]
+    fn no_match_is_an_error() {
+        let filter = ContractsFilter::from_str("ERC721*").unwrap();
+
+        let error = filter
+            .ensure_matches_any(["ERC20", "Account", "ERC20"])
+            .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "No contracts match --contracts-filter = ERC721*, available contracts: Account, ERC20"
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_is_rejected() {
+        assert!(ContractsFilter::from_str("ERC[20").is_err());
+    }
+}
@@ -1,18 +1,37 @@
-use anyhow::{anyhow, Context, Result};
+use anyhow::{anyhow, ensure, Context, Result};
+use archive::ArtifactsArchive;
 use camino::{Utf8Path, Utf8PathBuf};
-use scarb_metadata::{CompilationUnitMetadata, Metadata, PackageId};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::ThreadPoolBuilder;
+use scarb_metadata::{CompilationUnitMetadata, Metadata, PackageId, PackageMetadata};
 use semver::VersionReq;
 use serde::Deserialize;
+use shared::consts::SNFOUNDRY_COMPILE_JOBS;
+use shared::print::{emit_warning, DiagnosticCode};
 use std::collections::HashMap;
-use std::fs;
-use universal_sierra_compiler_api::{compile_sierra_at_path, SierraType};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::{env, fs};
+use universal_sierra_compiler_api::{
+    compile_sierra, compile_sierra_at_path, compile_sierra_batch, supports_batch_compilation,
+    SierraType,
+};
 
 pub use command::*;
+use contracts_filter::ContractsFilter;
 
+mod archive;
 mod command;
+pub mod contract_size;
+pub mod contracts_filter;
 pub mod metadata;
+pub mod std_version;
 pub mod version;
 
+const SCARB_TARGET_DIR: &str = "SCARB_TARGET_DIR";
+const STARKNET_CONTRACT_TARGET_KIND: &str = "starknet-contract";
+
 #[derive(Deserialize, Debug, PartialEq, Clone)]
 struct StarknetArtifacts {
     version: u32,
@@ -39,26 +58,122 @@ struct StarknetContractArtifactPaths {
 pub struct StarknetContractArtifacts {
     /// Compiled sierra code
     pub sierra: String,
-    /// Compiled casm code
-    pub casm: String,
+    /// Compiled casm code, compiled from `sierra` on first use if the contract was loaded without it
+    casm: OnceLock<String>,
+}
+
+/// Location of the files referenced in `starknet_artifacts.json`
+enum ArtifactsSource<'a> {
+    /// Directory containing the `starknet_artifacts.json` file
+    Directory(&'a Utf8Path),
+    Archive(&'a mut ArtifactsArchive),
 }
 
 impl StarknetContractArtifacts {
+    #[must_use]
+    pub fn new(sierra: String, casm: String) -> Self {
+        Self {
+            sierra,
+            casm: OnceLock::from(casm),
+        }
+    }
+
+    /// Artifacts of a contract which is compiled to CASM only when [`Self::casm`] is called
+    #[must_use]
+    pub fn from_sierra(sierra: String) -> Self {
+        Self {
+            sierra,
+            casm: OnceLock::new(),
+        }
+    }
+
+    /// Whether CASM of the contract is already available, i.e. calling [`Self::casm`] will not compile it
+    #[must_use]
+    pub fn is_casm_compiled(&self) -> bool {
+        self.casm.get().is_some()
+    }
+
+    /// Compiled casm code, compiled with `universal-sierra-compiler` if it is not available yet
+    pub fn casm(&self) -> Result<&str> {
+        if let Some(casm) = self.casm.get() {
+            return Ok(casm);
+        }
+        let casm = recompile_casm(&self.sierra)?;
+
+        Ok(self.casm.get_or_init(|| casm))
+    }
+
+    /// ABI of the contract, as it is written in its Sierra contract class
+    pub fn abi(&self) -> Result<serde_json::Value> {
+        #[derive(Deserialize)]
+        struct SierraAbi {
+            abi: serde_json::Value,
+        }
+
+        let sierra: SierraAbi =
+            serde_json::from_str(&self.sierra).context("Failed to parse Sierra")?;
+
+        Ok(sierra.abi)
+    }
+
     fn from_scarb_contract_artifact(
         starknet_contract: &StarknetContract,
-        base_path: &Utf8Path,
+        source: &mut ArtifactsSource,
+        compile_to_casm: &CompileToCasm,
     ) -> Result<Self> {
-        let sierra_path = base_path.join(starknet_contract.artifacts.sierra.clone());
-        let sierra = fs::read_to_string(sierra_path)?;
+        match source {
+            ArtifactsSource::Directory(base_path) => {
+                let sierra_path = base_path.join(starknet_contract.artifacts.sierra.clone());
+                let sierra = fs::read_to_string(sierra_path)?;
+                if !compile_to_casm(&sierra) {
+                    return Ok(Self::from_sierra(sierra));
+                }
+                let sierra_type = sierra_type_of(&sierra).with_context(|| {
+                    format!(
+                        "Failed to compile Sierra of contract = {}",
+                        starknet_contract.contract_name
+                    )
+                })?;
+
+                let casm = compile_sierra_at_path(
+                    starknet_contract.artifacts.sierra.as_str(),
+                    Some(base_path.as_std_path()),
+                    &sierra_type,
+                )?;
+
+                Ok(Self::new(sierra, casm))
+            }
+            ArtifactsSource::Archive(archive) => {
+                let sierra = archive.read_relative(&starknet_contract.artifacts.sierra)?;
+                if !compile_to_casm(&sierra) {
+                    return Ok(Self::from_sierra(sierra));
+                }
+                let casm = recompile_casm(&sierra).with_context(|| {
+                    format!(
+                        "Failed to compile Sierra of contract = {}",
+                        starknet_contract.contract_name
+                    )
+                })?;
+
+                Ok(Self::new(sierra, casm))
+            }
+        }
+    }
+}
 
-        let casm = compile_sierra_at_path(
-            starknet_contract.artifacts.sierra.as_str(),
-            Some(base_path.as_std_path()),
-            &SierraType::Contract,
-        )?;
+/// Compiles a Sierra contract class given as JSON to CASM, for Sierra which is not saved to a file
+pub fn recompile_casm(sierra_json: &str) -> Result<String> {
+    let sierra = serde_json::from_str(sierra_json).context("Failed to parse Sierra")?;
+    let sierra_type = SierraType::detect(&sierra)?;
 
-        Ok(Self { sierra, casm })
-    }
+    // Compiler reads Sierra from a file, so it is written to a temporary one
+    compile_sierra(&sierra, None, &sierra_type)
+}
+
+/// Contracts of different contract class versions may be mixed in a workspace, so the version is checked per contract
+fn sierra_type_of(sierra_json: &str) -> Result<SierraType> {
+    let sierra = serde_json::from_str(sierra_json).context("Failed to parse Sierra")?;
+    SierraType::detect(&sierra)
 }
 
 /// Get deserialized contents of `starknet_artifacts.json` file generated by Scarb
@@ -111,6 +226,31 @@ fn get_starknet_artifacts_path(
     }
 }
 
+/// Receives updates on the compilation of contracts to CASM, e.g. to render a progress bar.
+/// Contracts are compiled in parallel, so the methods may be called from multiple threads
+/// and updates of `contract_compiled` may arrive out of order
+pub trait CompilationProgress: Sync {
+    /// Called before the compilation starts, with the number of contracts to compile
+    fn started(&self, _total: usize) {}
+
+    /// Called after each compiled contract, `compiled` of `total` contracts are compiled so far
+    fn contract_compiled(&self, compiled: usize, total: usize);
+
+    /// Called after the compilation ends, also when it failed
+    fn finished(&self) {}
+}
+
+/// Decides from the Sierra of a contract whether it is compiled to CASM when it is loaded,
+/// the remaining contracts are compiled on the first call to [`StarknetContractArtifacts::casm`]
+pub type CompileToCasm = dyn Fn(&str) -> bool + Sync;
+
+/// Ignores the updates, used when the caller does not report the progress
+pub struct NoProgress;
+
+impl CompilationProgress for NoProgress {
+    fn contract_compiled(&self, _compiled: usize, _total: usize) {}
+}
+
 /// Get the map with `StarknetContractArtifacts` for the given package
 pub fn get_contracts_artifacts_and_source_sierra_paths(
     metadata: &Metadata,
@@ -118,40 +258,383 @@ pub fn get_contracts_artifacts_and_source_sierra_paths(
     profile: Option<&str>,
     use_test_target_contracts: bool,
 ) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
+    get_contracts_artifacts_and_source_sierra_paths_with_progress(
+        metadata,
+        package,
+        profile,
+        use_test_target_contracts,
+        &NoProgress,
+        None,
+        &|_| true,
+    )
+}
+
+/// Same as [`get_contracts_artifacts_and_source_sierra_paths`], reporting the compilation of contracts to `progress`.
+/// If `contracts_filter` is passed, only the contracts matching it are loaded and compiled to CASM
+pub fn get_contracts_artifacts_and_source_sierra_paths_with_progress(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+    use_test_target_contracts: bool,
+    progress: &dyn CompilationProgress,
+    contracts_filter: Option<&ContractsFilter>,
+    compile_to_casm: &CompileToCasm,
+) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
+    match find_starknet_artifacts_path(metadata, package, profile, use_test_target_contracts)? {
+        Some(contracts_path) => load_contracts_artifacts_and_source_sierra_paths(
+            &contracts_path,
+            progress,
+            contracts_filter,
+            compile_to_casm,
+        ),
+        None => Ok(HashMap::default()),
+    }
+}
+
+/// Names of the contracts of the package, read without loading their artifacts
+pub fn get_contract_names(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+    use_test_target_contracts: bool,
+) -> Result<Vec<String>> {
+    match find_starknet_artifacts_path(metadata, package, profile, use_test_target_contracts)? {
+        Some(contracts_path) => Ok(artifacts_for_package(&contracts_path)?
+            .contracts
+            .into_iter()
+            .map(|contract| contract.contract_name)
+            .collect()),
+        None => Ok(vec![]),
+    }
+}
+
+/// Same as [`get_contracts_artifacts_and_source_sierra_paths`], but only Sierra of the contracts is read,
+/// they are compiled to CASM on the first call to [`StarknetContractArtifacts::casm`]
+pub fn get_contracts_sierra_and_source_sierra_paths(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+    use_test_target_contracts: bool,
+) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
+    match find_starknet_artifacts_path(metadata, package, profile, use_test_target_contracts)? {
+        Some(contracts_path) => load_contracts_sierra_and_source_sierra_paths(&contracts_path),
+        None => Ok(HashMap::default()),
+    }
+}
+
+/// Path to `starknet_artifacts.json` file of the package, warns if a package with a `starknet-contract`
+/// target has none
+fn find_starknet_artifacts_path(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+    use_test_target_contracts: bool,
+) -> Result<Option<Utf8PathBuf>> {
     let target_name = target_name_for_package(metadata, package)?;
     let target_dir = target_dir_for_workspace(metadata);
+    let profile = profile.unwrap_or(metadata.current_profile.as_str());
     let maybe_contracts_path = get_starknet_artifacts_path(
         &target_dir,
         &target_name,
-        profile.unwrap_or(metadata.current_profile.as_str()),
+        profile,
         use_test_target_contracts,
     );
 
-    let map = match maybe_contracts_path {
-        Some(contracts_path) => load_contracts_artifacts_and_source_sierra_paths(&contracts_path)?,
-        None => HashMap::default(),
-    };
+    // Packages without a `starknet-contract` target have no contracts to load
+    if maybe_contracts_path.is_none() && has_starknet_contract_target(metadata, package) {
+        emit_warning(
+            DiagnosticCode::MissingStarknetArtifacts,
+            format!(
+                "Starknet artifacts file of target {target_name} not found in {}, its contracts are not available",
+                target_dir.join(profile)
+            ),
+        );
+    }
 
-    Ok(map)
+    Ok(maybe_contracts_path)
+}
+
+/// Contracts of the members of the workspace, keyed by package name and then by contract name
+pub type WorkspaceContractsArtifacts = HashMap<String, HashMap<String, StarknetContractArtifacts>>;
+
+/// Get the maps with `StarknetContractArtifacts` for all members of the workspace.
+/// Members without a `starknet-contract` target are skipped, if none of them defines it
+/// a single error is returned for the whole workspace
+pub fn get_contracts_artifacts_for_workspace(
+    metadata: &Metadata,
+    profile: Option<&str>,
+) -> Result<WorkspaceContractsArtifacts> {
+    contracts_for_workspace(metadata, |package| {
+        get_contracts_artifacts_and_source_sierra_paths(metadata, package, profile, false)
+    })
+}
+
+/// Same as [`get_contracts_artifacts_for_workspace`], but only Sierra of the contracts is read,
+/// they are compiled to CASM on the first call to [`StarknetContractArtifacts::casm`]
+pub fn get_contracts_sierra_for_workspace(
+    metadata: &Metadata,
+    profile: Option<&str>,
+) -> Result<WorkspaceContractsArtifacts> {
+    contracts_for_workspace(metadata, |package| {
+        get_contracts_sierra_and_source_sierra_paths(metadata, package, profile, false)
+    })
+}
+
+/// Same as [`get_contracts_sierra_for_workspace`], but only the contracts of `package` are read,
+/// the workspace is still checked to define any `starknet-contract` target
+pub fn get_package_contracts_sierra_in_workspace(
+    metadata: &Metadata,
+    package: &PackageId,
+    profile: Option<&str>,
+) -> Result<HashMap<String, StarknetContractArtifacts>> {
+    members_with_contracts(metadata)?;
+
+    Ok(
+        get_contracts_sierra_and_source_sierra_paths(metadata, package, profile, false)?
+            .into_iter()
+            .map(|(name, (artifacts, _))| (name, artifacts))
+            .collect(),
+    )
+}
+
+fn contracts_for_workspace(
+    metadata: &Metadata,
+    load_package_contracts: impl Fn(
+        &PackageId,
+    )
+        -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>>,
+) -> Result<WorkspaceContractsArtifacts> {
+    members_with_contracts(metadata)?
+        .into_iter()
+        .map(|package| {
+            let contracts = load_package_contracts(&package.id)?
+                .into_iter()
+                .map(|(name, (artifacts, _))| (name, artifacts))
+                .collect();
+
+            Ok((package.name.clone(), contracts))
+        })
+        .collect()
+}
+
+/// Members of the workspace with a `starknet-contract` target, fails if there are none
+fn members_with_contracts(metadata: &Metadata) -> Result<Vec<&PackageMetadata>> {
+    let members_with_contracts: Vec<_> = metadata
+        .packages
+        .iter()
+        .filter(|package| metadata.workspace.members.contains(&package.id))
+        .filter(|package| has_starknet_contract_target(metadata, &package.id))
+        .collect();
+
+    ensure!(
+        !members_with_contracts.is_empty(),
+        "No Starknet contract targets found in any package of the workspace\n\
+        Hint: add `[[target.starknet-contract]]` section to `Scarb.toml` of the packages defining contracts"
+    );
+
+    Ok(members_with_contracts)
+}
+
+fn has_starknet_contract_target(metadata: &Metadata, package: &PackageId) -> bool {
+    metadata.get_package(package).is_some_and(|package| {
+        package
+            .targets
+            .iter()
+            .any(|target| target.kind == STARKNET_CONTRACT_TARGET_KIND)
+    })
 }
 
 fn load_contracts_artifacts_and_source_sierra_paths(
     contracts_path: &Utf8PathBuf,
+    progress: &dyn CompilationProgress,
+    contracts_filter: Option<&ContractsFilter>,
+    compile_to_casm: &CompileToCasm,
+) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
+    let base_path = contracts_path
+        .parent()
+        .ok_or_else(|| anyhow!("Failed to get parent for path = {}", &contracts_path))?;
+    let mut artifacts = artifacts_for_package(contracts_path)?;
+    if let Some(contracts_filter) = contracts_filter {
+        artifacts
+            .contracts
+            .retain(|contract| contracts_filter.matches(&contract.contract_name));
+    }
+
+    let total = artifacts.contracts.len();
+    let compiled = AtomicUsize::new(0);
+    progress.started(total);
+
+    if supports_batch_compilation(&SierraType::Contract) {
+        let result =
+            compile_contracts_in_batch(&artifacts.contracts, base_path, progress, compile_to_casm);
+        progress.finished();
+        return result;
+    }
+
+    let result = run_in_compile_pool(|| {
+        artifacts
+            .contracts
+            .par_iter()
+            .map(|contract| {
+                let name = contract.contract_name.clone();
+                let contract_artifacts = StarknetContractArtifacts::from_scarb_contract_artifact(
+                    contract,
+                    &mut ArtifactsSource::Directory(base_path),
+                    compile_to_casm,
+                )?;
+                progress.contract_compiled(compiled.fetch_add(1, Ordering::Relaxed) + 1, total);
+
+                let sierra_path = base_path.join(contract.artifacts.sierra.clone());
+
+                Ok((name, (contract_artifacts, sierra_path)))
+            })
+            .collect()
+    });
+    progress.finished();
+
+    result
+}
+
+fn load_contracts_sierra_and_source_sierra_paths(
+    contracts_path: &Utf8PathBuf,
 ) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
     let base_path = contracts_path
         .parent()
         .ok_or_else(|| anyhow!("Failed to get parent for path = {}", &contracts_path))?;
     let artifacts = artifacts_for_package(contracts_path)?;
+
+    artifacts
+        .contracts
+        .iter()
+        .map(|contract| {
+            let sierra_path = base_path.join(&contract.artifacts.sierra);
+            let sierra = fs::read_to_string(&sierra_path)
+                .with_context(|| format!("Failed to read Sierra file {sierra_path}"))?;
+
+            Ok((
+                contract.contract_name.clone(),
+                (StarknetContractArtifacts::from_sierra(sierra), sierra_path),
+            ))
+        })
+        .collect()
+}
+
+/// Compiles contract classes with a single `universal-sierra-compiler` process, the rest of Sierra
+/// programs is compiled one by one
+fn compile_contracts_in_batch(
+    contracts: &[StarknetContract],
+    base_path: &Utf8Path,
+    progress: &dyn CompilationProgress,
+    compile_to_casm: &CompileToCasm,
+) -> Result<HashMap<String, (StarknetContractArtifacts, Utf8PathBuf)>> {
+    let total = contracts.len();
+    let mut result = HashMap::new();
+    let mut batched = vec![];
+
+    for contract in contracts {
+        let sierra = fs::read_to_string(base_path.join(&contract.artifacts.sierra))?;
+        if !compile_to_casm(&sierra) {
+            result.insert(
+                contract.contract_name.clone(),
+                (
+                    StarknetContractArtifacts::from_sierra(sierra),
+                    base_path.join(&contract.artifacts.sierra),
+                ),
+            );
+            progress.contract_compiled(result.len(), total);
+            continue;
+        }
+        let sierra_type = sierra_type_of(&sierra).with_context(|| {
+            format!(
+                "Failed to compile Sierra of contract = {}",
+                contract.contract_name
+            )
+        })?;
+
+        match sierra_type {
+            SierraType::Contract => batched.push((contract, sierra)),
+            SierraType::Raw => {
+                let casm = compile_sierra_at_path(
+                    contract.artifacts.sierra.as_str(),
+                    Some(base_path.as_std_path()),
+                    &sierra_type,
+                )?;
+                result.insert(
+                    contract.contract_name.clone(),
+                    (
+                        StarknetContractArtifacts::new(sierra, casm),
+                        base_path.join(&contract.artifacts.sierra),
+                    ),
+                );
+                progress.contract_compiled(result.len(), total);
+            }
+        }
+    }
+
+    let sierra_paths: Vec<&str> = batched
+        .iter()
+        .map(|(contract, _)| contract.artifacts.sierra.as_str())
+        .collect();
+    let casms = compile_sierra_batch(
+        &sierra_paths,
+        Some(base_path.as_std_path()),
+        &SierraType::Contract,
+    )?;
+
+    for ((contract, sierra), casm) in batched.into_iter().zip(casms) {
+        result.insert(
+            contract.contract_name.clone(),
+            (
+                StarknetContractArtifacts::new(sierra, casm),
+                base_path.join(&contract.artifacts.sierra),
+            ),
+        );
+        progress.contract_compiled(result.len(), total);
+    }
+
+    Ok(result)
+}
+
+/// Runs the compilation of contracts on a thread pool with the number of threads set with
+/// `SNFOUNDRY_COMPILE_JOBS` environment variable, or on the global rayon pool if it is not set
+fn run_in_compile_pool<T: Send>(compile: impl FnOnce() -> Result<T> + Send) -> Result<T> {
+    match parse_compile_jobs(env::var(SNFOUNDRY_COMPILE_JOBS).ok().as_deref())? {
+        Some(jobs) => ThreadPoolBuilder::new()
+            .num_threads(jobs.get())
+            .build()
+            .context("Failed to create a thread pool for the compilation of contracts")?
+            .install(compile),
+        None => compile(),
+    }
+}
+
+fn parse_compile_jobs(value: Option<&str>) -> Result<Option<NonZeroUsize>> {
+    value
+        .map(|value| {
+            value.parse().with_context(|| {
+                format!("{SNFOUNDRY_COMPILE_JOBS} must be a positive integer, got `{value}`")
+            })
+        })
+        .transpose()
+}
+
+/// Get the map with `StarknetContractArtifacts` from a `.zip` archive containing a `starknet_artifacts.json` file
+/// and the Sierra files it references, e.g. a zipped target directory. The archive is read without extracting it.
+pub fn get_contracts_artifacts_from_archive(
+    archive_path: &Utf8Path,
+) -> Result<HashMap<String, StarknetContractArtifacts>> {
+    let mut archive = ArtifactsArchive::open(archive_path)?;
+    let artifacts = archive.artifacts()?;
     let mut map = HashMap::new();
 
     for ref contract in artifacts.contracts {
-        let name = contract.contract_name.clone();
-        let contract_artifacts =
-            StarknetContractArtifacts::from_scarb_contract_artifact(contract, base_path)?;
-
-        let sierra_path = base_path.join(contract.artifacts.sierra.clone());
+        let contract_artifacts = StarknetContractArtifacts::from_scarb_contract_artifact(
+            contract,
+            &mut ArtifactsSource::Archive(&mut archive),
+            &|_| true,
+        )?;
 
-        map.insert(name.clone(), (contract_artifacts, sierra_path));
+        map.insert(contract.contract_name.clone(), contract_artifacts);
     }
     Ok(map)
 }
@@ -178,12 +661,29 @@ pub fn target_name_for_package(metadata: &Metadata, package: &PackageId) -> Resu
     Ok(compilation_unit.target.name.clone())
 }
 
+/// Target directory of the workspace, when it is missing in the metadata it is read from the
+/// `SCARB_TARGET_DIR` environment variable, like Scarb does, and then defaults to `target` in the workspace root
 #[must_use]
 pub fn target_dir_for_workspace(metadata: &Metadata) -> Utf8PathBuf {
-    metadata
-        .target_dir
-        .clone()
-        .unwrap_or_else(|| metadata.workspace.root.join("target"))
+    resolve_target_dir(metadata, env::var(SCARB_TARGET_DIR).ok().as_deref())
+}
+
+fn resolve_target_dir(metadata: &Metadata, scarb_target_dir: Option<&str>) -> Utf8PathBuf {
+    if let Some(target_dir) = &metadata.target_dir {
+        return target_dir.clone();
+    }
+
+    match scarb_target_dir.filter(|target_dir| !target_dir.is_empty()) {
+        // Relative paths are resolved against the current directory, the same as in Scarb
+        Some(target_dir) => env::current_dir()
+            .ok()
+            .and_then(|current_dir| Utf8PathBuf::from_path_buf(current_dir).ok())
+            .map_or_else(
+                || Utf8PathBuf::from(target_dir),
+                |current_dir| current_dir.join(target_dir),
+            ),
+        None => metadata.workspace.root.join("target"),
+    }
 }
 
 /// Get a name of the given package
@@ -222,7 +722,13 @@ mod tests {
     use assert_fs::TempDir;
     use camino::Utf8PathBuf;
     use indoc::{formatdoc, indoc};
+    use std::fs::File;
+    use std::io::Write;
+    use std::path::Path;
     use std::str::FromStr;
+    use std::sync::Mutex;
+    use zip::write::FileOptions;
+    use zip::ZipWriter;
 
     fn setup_package(package_name: &str) -> TempDir {
         let temp = TempDir::new().unwrap();
@@ -418,6 +924,63 @@ mod tests {
         .unwrap());
     }
 
+    #[test]
+    fn std_version_mismatch() {
+        let temp = setup_package("basic_package");
+
+        let manifest_path = temp.child("Scarb.toml");
+        manifest_path
+            .write_str(&formatdoc!(
+                r#"
+                [package]
+                name = "version_checker"
+                version = "0.1.0"
+
+                [dependencies]
+                starknet = "2.5.4"
+                "#,
+            ))
+            .unwrap();
+
+        let scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+        let tool_version = semver::Version::new(0, 0, 1);
+
+        // `starknet` stands in for a std package from the registry, deliberately mismatching the tool version
+        let error = std_version::ensure_std_version_compatible(
+            &scarb_metadata,
+            "starknet",
+            "snforge",
+            &tool_version,
+            false,
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(error.contains("is not compatible with snforge 0.0.1"));
+        assert!(error.contains(&format!(
+            "change the starknet dependency in {} to:\nstarknet = \"0.0.1\"",
+            temp.path()
+                .join("Scarb.toml")
+                .canonicalize()
+                .unwrap()
+                .display()
+        )));
+        assert!(error.contains("Pass `--no-version-check` to run anyway"));
+
+        assert!(std_version::ensure_std_version_compatible(
+            &scarb_metadata,
+            "starknet",
+            "snforge",
+            &tool_version,
+            true,
+        )
+        .is_ok());
+    }
+
     #[test]
     fn get_starknet_artifacts_path_for_project_with_different_package_and_target_name() {
         let temp = setup_package("basic_package");
@@ -577,7 +1140,7 @@ mod tests {
 
         let contract = contracts.get("ERC20").unwrap();
         assert_eq!(&sierra_contents_erc20, &contract.0.sierra);
-        assert!(!contract.0.casm.is_empty());
+        assert!(!contract.0.casm().unwrap().is_empty());
 
         let sierra_contents_erc20 = fs::read_to_string(
             temp.join("target/dev/basic_package_HelloStarknet.contract_class.json"),
@@ -585,7 +1148,436 @@ mod tests {
         .unwrap();
         let contract = contracts.get("HelloStarknet").unwrap();
         assert_eq!(&sierra_contents_erc20, &contract.0.sierra);
-        assert!(!contract.0.casm.is_empty());
+        assert!(!contract.0.casm().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_contracts_sierra_compiles_casm_lazily() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let package = metadata.packages.first().unwrap();
+        let compiled =
+            get_contracts_artifacts_and_source_sierra_paths(&metadata, &package.id, None, false)
+                .unwrap();
+        let contracts =
+            get_contracts_sierra_and_source_sierra_paths(&metadata, &package.id, None, false)
+                .unwrap();
+
+        let (compiled, _) = compiled.get("ERC20").unwrap();
+        let (contract, sierra_path) = contracts.get("ERC20").unwrap();
+        assert!(compiled.is_casm_compiled());
+        assert!(!contract.is_casm_compiled());
+        assert!(sierra_path.ends_with("basic_package_ERC20.contract_class.json"));
+        assert_eq!(contract.sierra, compiled.sierra);
+
+        assert_eq!(contract.casm().unwrap(), compiled.casm().unwrap());
+        assert!(contract.is_casm_compiled());
+    }
+
+    #[test]
+    fn get_contracts_with_progress() {
+        #[derive(Default)]
+        struct RecordingProgress {
+            updates: Mutex<Vec<String>>,
+        }
+
+        impl CompilationProgress for RecordingProgress {
+            fn started(&self, total: usize) {
+                self.updates
+                    .lock()
+                    .unwrap()
+                    .push(format!("started {total}"));
+            }
+
+            fn contract_compiled(&self, compiled: usize, total: usize) {
+                self.updates
+                    .lock()
+                    .unwrap()
+                    .push(format!("compiled {compiled}/{total}"));
+            }
+
+            fn finished(&self) {
+                self.updates.lock().unwrap().push("finished".to_string());
+            }
+        }
+
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let package = metadata.packages.first().unwrap();
+        let progress = RecordingProgress::default();
+        let contracts = get_contracts_artifacts_and_source_sierra_paths_with_progress(
+            &metadata,
+            &package.id,
+            None,
+            false,
+            &progress,
+            None,
+            &|_| true,
+        )
+        .unwrap();
+
+        let mut updates = progress.updates.into_inner().unwrap();
+        updates.sort();
+        assert_eq!(contracts.len(), 2);
+        assert_eq!(
+            updates,
+            vec!["compiled 1/2", "compiled 2/2", "finished", "started 2"]
+        );
+    }
+
+    #[test]
+    fn get_contracts_compiles_only_selected_to_casm() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let package = metadata.packages.first().unwrap();
+        let contracts = get_contracts_artifacts_and_source_sierra_paths_with_progress(
+            &metadata,
+            &package.id,
+            None,
+            false,
+            &NoProgress,
+            None,
+            &|_| false,
+        )
+        .unwrap();
+
+        assert_eq!(contracts.len(), 2);
+        assert!(contracts
+            .values()
+            .all(|(artifacts, _)| !artifacts.is_casm_compiled()));
+    }
+
+    #[test]
+    fn get_contracts_with_filter() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let package = metadata.packages.first().unwrap();
+        let filter = ContractsFilter::from_str("ERC*").unwrap();
+        let contracts = get_contracts_artifacts_and_source_sierra_paths_with_progress(
+            &metadata,
+            &package.id,
+            None,
+            false,
+            &NoProgress,
+            Some(&filter),
+            &|_| true,
+        )
+        .unwrap();
+
+        assert_eq!(contracts.keys().collect::<Vec<_>>(), vec!["ERC20"]);
+
+        let mut names = get_contract_names(&metadata, &package.id, None, false).unwrap();
+        names.sort();
+        assert_eq!(names, vec!["ERC20", "HelloStarknet"]);
+    }
+
+    #[test]
+    fn get_contracts_for_workspace() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let contracts = get_contracts_artifacts_for_workspace(&metadata, None).unwrap();
+
+        assert_eq!(contracts.len(), 1);
+        let package_contracts = contracts.get("basic_package").unwrap();
+        assert!(package_contracts.contains_key("ERC20"));
+        assert!(package_contracts.contains_key("HelloStarknet"));
+    }
+
+    #[test]
+    fn get_contracts_for_workspace_without_starknet_target() {
+        let temp = setup_package("empty_lib");
+        temp.child("Scarb.toml")
+            .write_str(indoc!(
+                r#"
+                [package]
+                name = "empty_lib"
+                version = "0.1.0"
+                "#,
+            ))
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let error = get_contracts_artifacts_for_workspace(&metadata, None).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            indoc!(
+                "
+                No Starknet contract targets found in any package of the workspace
+                Hint: add `[[target.starknet-contract]]` section to `Scarb.toml` of the packages defining contracts"
+            )
+        );
+    }
+
+    #[test]
+    fn get_package_contracts_in_workspace() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(temp.join("Scarb.toml"))
+            .run()
+            .unwrap();
+
+        let package = metadata.packages.first().unwrap();
+        let contracts =
+            get_package_contracts_sierra_in_workspace(&metadata, &package.id, None).unwrap();
+
+        assert!(contracts.contains_key("ERC20"));
+        assert!(contracts.contains_key("HelloStarknet"));
+        assert!(!contracts["ERC20"].is_casm_compiled());
+    }
+
+    #[test]
+    fn recompile_casm_from_sierra() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let sierra =
+            fs::read_to_string(temp.join("target/dev/basic_package_ERC20.contract_class.json"))
+                .unwrap();
+        let casm = compile_sierra_at_path(
+            "basic_package_ERC20.contract_class.json",
+            Some(temp.join("target/dev").as_path()),
+            &SierraType::Contract,
+        )
+        .unwrap();
+
+        assert_eq!(recompile_casm(&sierra).unwrap(), casm);
+    }
+
+    #[test]
+    fn abi_of_contract() {
+        let artifacts = StarknetContractArtifacts::from_sierra(
+            r#"{"sierra_program": [], "abi": [{"type": "function", "name": "get"}]}"#.to_string(),
+        );
+
+        assert_eq!(
+            artifacts.abi().unwrap(),
+            serde_json::json!([{"type": "function", "name": "get"}])
+        );
+        assert!(!artifacts.is_casm_compiled());
+    }
+
+    #[test]
+    fn abi_of_invalid_sierra() {
+        let artifacts =
+            StarknetContractArtifacts::from_sierra("{\"sierra_program\": [".to_string());
+
+        assert_eq!(
+            artifacts.abi().unwrap_err().to_string(),
+            "Failed to parse Sierra"
+        );
+    }
+
+    #[test]
+    fn recompile_casm_from_invalid_sierra() {
+        let err = recompile_casm("{\"sierra_program\": ").unwrap_err();
+
+        assert_eq!(err.to_string(), "Failed to parse Sierra");
+    }
+
+    #[test]
+    fn recompile_casm_of_unsupported_contract_class_version() {
+        let err = recompile_casm(r#"{"sierra_program": [], "contract_class_version": "0.2.0"}"#)
+            .unwrap_err();
+
+        assert!(err.to_string().starts_with(
+            "Contract class version 0.2.0 is not supported by universal-sierra-compiler"
+        ));
+    }
+
+    fn write_archive(path: &Path, entries: &[(String, String)]) {
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            writer.start_file(name, FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn get_contracts_from_archive() {
+        let temp = setup_package("basic_package");
+
+        ScarbCommand::new_with_stdio()
+            .current_dir(temp.path())
+            .arg("build")
+            .run()
+            .unwrap();
+
+        let entries: Vec<_> = [
+            "basic_package.starknet_artifacts.json",
+            "basic_package_ERC20.contract_class.json",
+            "basic_package_HelloStarknet.contract_class.json",
+        ]
+        .into_iter()
+        .map(|name| {
+            let content = fs::read_to_string(temp.join("target/dev").join(name)).unwrap();
+            (format!("dev/{name}"), content)
+        })
+        .collect();
+        let archive_path = temp.child("artifacts.zip");
+        write_archive(archive_path.path(), &entries);
+
+        let contracts = get_contracts_artifacts_from_archive(
+            &Utf8PathBuf::from_path_buf(archive_path.to_path_buf()).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(contracts.len(), 2);
+        let contract = contracts.get("ERC20").unwrap();
+        assert_eq!(contract.sierra, entries[1].1);
+        assert!(!contract.casm().unwrap().is_empty());
+        let contract = contracts.get("HelloStarknet").unwrap();
+        assert_eq!(contract.sierra, entries[2].1);
+        assert!(!contract.casm().unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_contracts_from_archive_without_referenced_sierra() {
+        let temp = TempDir::new().unwrap();
+        let archive_path = temp.child("artifacts.zip");
+        write_archive(
+            archive_path.path(),
+            &[(
+                "dev/basic_package.starknet_artifacts.json".to_string(),
+                indoc! {r#"
+                    {
+                        "version": 1,
+                        "contracts": [
+                            {
+                                "id": "1",
+                                "package_name": "basic_package",
+                                "contract_name": "ERC20",
+                                "artifacts": {
+                                    "sierra": "basic_package_ERC20.contract_class.json"
+                                }
+                            }
+                        ]
+                    }
+                "#}
+                .to_string(),
+            )],
+        );
+        let archive_path = Utf8PathBuf::from_path_buf(archive_path.to_path_buf()).unwrap();
+
+        let err = get_contracts_artifacts_from_archive(&archive_path).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Archive {archive_path} does not contain entry dev/basic_package_ERC20.contract_class.json referenced by contract = ERC20"
+            )
+        );
+    }
+
+    #[test]
+    fn get_contracts_from_archive_without_starknet_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let archive_path = temp.child("artifacts.zip");
+        write_archive(
+            archive_path.path(),
+            &[("dev/other.json".to_string(), "{}".to_string())],
+        );
+        let archive_path = Utf8PathBuf::from_path_buf(archive_path.to_path_buf()).unwrap();
+
+        let err = get_contracts_artifacts_from_archive(&archive_path).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("Archive {archive_path} does not contain a starknet_artifacts.json file")
+        );
+    }
+
+    #[test]
+    fn parsing_compile_jobs() {
+        assert_eq!(parse_compile_jobs(None).unwrap(), None);
+        assert_eq!(parse_compile_jobs(Some("2")).unwrap(), NonZeroUsize::new(2));
+
+        for value in ["0", "-1", "all"] {
+            let err = parse_compile_jobs(Some(value)).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("SNFOUNDRY_COMPILE_JOBS must be a positive integer, got `{value}`")
+            );
+        }
     }
 
     #[test]
@@ -617,4 +1609,38 @@ mod tests {
 
         assert_eq!(target_name, "basic_package");
     }
+
+    // The variable is passed explicitly, setting it in the process would affect Scarb run by other tests
+    #[test]
+    fn target_dir_from_scarb_target_dir_env() {
+        let temp = setup_package("basic_package");
+        let mut scarb_metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .current_dir(temp.path())
+            .run()
+            .unwrap();
+        scarb_metadata.target_dir = None;
+
+        let custom_target_dir =
+            Utf8PathBuf::from_path_buf(temp.path().join("custom_target")).unwrap();
+
+        assert_eq!(
+            resolve_target_dir(&scarb_metadata, Some(custom_target_dir.as_str())),
+            custom_target_dir
+        );
+        assert_eq!(
+            resolve_target_dir(&scarb_metadata, Some("relative_target")),
+            Utf8PathBuf::from_path_buf(env::current_dir().unwrap())
+                .unwrap()
+                .join("relative_target")
+        );
+        assert_eq!(
+            resolve_target_dir(&scarb_metadata, None),
+            scarb_metadata.workspace.root.join("target")
+        );
+        assert_eq!(
+            resolve_target_dir(&scarb_metadata, Some("")),
+            scarb_metadata.workspace.root.join("target")
+        );
+    }
 }
@@ -1,5 +1,12 @@
+use crate::ScarbCommand;
 use anyhow::{Context, Result};
+use camino::{Utf8Path, Utf8PathBuf};
 pub use scarb_metadata::{Metadata, MetadataCommand, MetadataCommandError, PackageMetadata};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
 
 pub trait MetadataCommandExt {
     fn run(&mut self) -> Result<Metadata>;
@@ -12,3 +19,342 @@ impl MetadataCommandExt for MetadataCommand {
             .context("error: could not gather project metadata from Scarb due to previous error")
     }
 }
+
+/// Metadata is cached per manifest, with or without dependencies
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    manifest_path: Utf8PathBuf,
+    no_deps: bool,
+}
+
+/// Cached metadata is only valid as long as none of the files it was gathered from is modified
+struct CacheEntry {
+    sources: Vec<(Utf8PathBuf, Option<SystemTime>)>,
+    metadata: Metadata,
+}
+
+impl CacheEntry {
+    fn new(manifest_path: &Utf8Path, metadata: Metadata) -> Self {
+        Self {
+            sources: source_files(manifest_path, &metadata)
+                .into_iter()
+                .map(|path| {
+                    let modified = modified(&path);
+                    (path, modified)
+                })
+                .collect(),
+            metadata,
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        self.sources
+            .iter()
+            .all(|(path, source_modified)| modified(path) == *source_modified)
+    }
+}
+
+fn cache() -> &'static Mutex<HashMap<CacheKey, CacheEntry>> {
+    static CACHE: OnceLock<Mutex<HashMap<CacheKey, CacheEntry>>> = OnceLock::new();
+    CACHE.get_or_init(Mutex::default)
+}
+
+fn cache_key(manifest_path: &Utf8Path, no_deps: bool) -> CacheKey {
+    CacheKey {
+        manifest_path: manifest_path
+            .canonicalize_utf8()
+            .unwrap_or_else(|_| manifest_path.to_path_buf()),
+        no_deps,
+    }
+}
+
+/// Manifests of the workspace, its members and path dependencies, the lockfile, and the directories
+/// their targets are found in, metadata changes only if one of them does, e.g. when `tests/` is created
+fn source_files(manifest_path: &Utf8Path, metadata: &Metadata) -> Vec<Utf8PathBuf> {
+    let mut files = vec![
+        manifest_path.to_path_buf(),
+        metadata.workspace.manifest_path.clone(),
+        metadata.workspace.root.join("Scarb.lock"),
+        metadata.workspace.root.clone(),
+    ];
+    for package in metadata.packages.iter().filter(|package| {
+        metadata.workspace.members.contains(&package.id) || package.source.repr.starts_with("path+")
+    }) {
+        files.push(package.manifest_path.clone());
+        files.extend([
+            package.root.join("src"),
+            package.root.join("tests"),
+            package.root.clone(),
+        ]);
+        // Members matched by a glob are added to the same directory of the workspace
+        files.extend(
+            package
+                .root
+                .parent()
+                .filter(|parent| parent.starts_with(&metadata.workspace.root))
+                .map(Utf8Path::to_path_buf),
+        );
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn modified(path: &Utf8Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Returns metadata of the manifest gathered earlier in this process, calling `fetch` only if there
+/// is none or a manifest, the lockfile or a directory with targets of the workspace was modified since.
+/// This way `scarb metadata` is run at most once per command, or per run in `snforge test --watch`
+pub fn cached_metadata(
+    manifest_path: &Utf8Path,
+    no_deps: bool,
+    fetch: impl FnOnce() -> Result<Metadata>,
+) -> Result<Metadata> {
+    let key = cache_key(manifest_path, no_deps);
+
+    if let Some(entry) = cache().lock().unwrap().get(&key) {
+        if entry.is_fresh() {
+            return Ok(entry.metadata.clone());
+        }
+    }
+
+    // The lock is not held while fetching, metadata of other manifests can be gathered meanwhile
+    let metadata = fetch()?;
+    cache()
+        .lock()
+        .unwrap()
+        .insert(key, CacheEntry::new(manifest_path, metadata.clone()));
+
+    Ok(metadata)
+}
+
+/// Stores metadata of the manifest, so it is returned by [`cached_metadata`] and [`workspace_metadata`]
+/// without running `scarb metadata` until a manifest of the workspace is modified, e.g. in tests
+pub fn inject_metadata(manifest_path: &Utf8Path, no_deps: bool, metadata: Metadata) {
+    cache().lock().unwrap().insert(
+        cache_key(manifest_path, no_deps),
+        CacheEntry::new(manifest_path, metadata),
+    );
+}
+
+/// Metadata with dependencies of the workspace Scarb runs in from the current directory, cached by [`cached_metadata`]
+pub fn workspace_metadata() -> Result<Metadata> {
+    let fetch = || ScarbCommand::metadata().inherit_stderr().run();
+
+    match find_manifest_path() {
+        Some(manifest_path) => cached_metadata(&manifest_path, false, fetch),
+        // Scarb reports the missing manifest
+        None => fetch(),
+    }
+}
+
+/// Manifest Scarb uses when run from the current directory, the nearest one in it or its parents
+fn find_manifest_path() -> Option<Utf8PathBuf> {
+    if let Ok(manifest_path) = env::var("SCARB_MANIFEST_PATH") {
+        return Some(Utf8PathBuf::from(manifest_path));
+    }
+
+    let current_dir = Utf8PathBuf::try_from(env::current_dir().ok()?).ok()?;
+    current_dir
+        .ancestors()
+        .map(|dir| dir.join("Scarb.toml"))
+        .find(|manifest_path| manifest_path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_fs::fixture::{FileWriteStr, PathChild};
+    use assert_fs::TempDir;
+    use indoc::indoc;
+    use std::fs::File;
+    use std::time::Duration;
+
+    fn setup_manifest() -> (TempDir, Utf8PathBuf) {
+        let temp = TempDir::new().unwrap();
+        temp.child("Scarb.toml")
+            .write_str(indoc!(
+                r#"
+                [package]
+                name = "cached"
+                version = "0.1.0"
+                "#
+            ))
+            .unwrap();
+        temp.child("src/lib.cairo").write_str("").unwrap();
+
+        let manifest_path = Utf8PathBuf::try_from(temp.join("Scarb.toml")).unwrap();
+        (temp, manifest_path)
+    }
+
+    fn fetch(manifest_path: &Utf8Path) -> Result<Metadata> {
+        ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(manifest_path.as_std_path())
+            .no_deps()
+            .run()
+    }
+
+    #[test]
+    fn injected_metadata_is_returned_without_fetching() {
+        let (_temp, manifest_path) = setup_manifest();
+        let metadata = fetch(&manifest_path).unwrap();
+
+        inject_metadata(&manifest_path, true, metadata.clone());
+        let cached = cached_metadata(&manifest_path, true, || {
+            panic!("Metadata should not be fetched")
+        })
+        .unwrap();
+
+        assert_eq!(cached.workspace.root, metadata.workspace.root);
+    }
+
+    #[test]
+    fn metadata_is_fetched_once() {
+        let (_temp, manifest_path) = setup_manifest();
+        let mut fetched = 0;
+
+        for _ in 0..2 {
+            cached_metadata(&manifest_path, true, || {
+                fetched += 1;
+                fetch(&manifest_path)
+            })
+            .unwrap();
+        }
+
+        assert_eq!(fetched, 1);
+    }
+
+    fn touch(path: &Utf8Path) {
+        let modified = modified(path).unwrap() + Duration::from_secs(1);
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn assert_fetched_again(manifest_path: &Utf8Path) {
+        let mut fetched = false;
+        cached_metadata(manifest_path, true, || {
+            fetched = true;
+            fetch(manifest_path)
+        })
+        .unwrap();
+
+        assert!(fetched);
+    }
+
+    #[test]
+    fn metadata_is_fetched_again_after_manifest_is_modified() {
+        let (_temp, manifest_path) = setup_manifest();
+        let metadata = fetch(&manifest_path).unwrap();
+        inject_metadata(&manifest_path, true, metadata);
+
+        touch(&manifest_path);
+
+        assert_fetched_again(&manifest_path);
+    }
+
+    #[test]
+    fn metadata_is_fetched_again_after_member_manifest_is_modified() {
+        let temp = TempDir::new().unwrap();
+        temp.child("Scarb.toml")
+            .write_str(indoc!(
+                r#"
+                [workspace]
+                members = ["crates/*"]
+                "#
+            ))
+            .unwrap();
+        temp.child("crates/member/Scarb.toml")
+            .write_str(indoc!(
+                r#"
+                [package]
+                name = "member"
+                version = "0.1.0"
+                "#
+            ))
+            .unwrap();
+        temp.child("crates/member/src/lib.cairo")
+            .write_str("")
+            .unwrap();
+        let manifest_path = Utf8PathBuf::try_from(temp.join("Scarb.toml")).unwrap();
+        let member_manifest_path =
+            Utf8PathBuf::try_from(temp.join("crates/member/Scarb.toml")).unwrap();
+
+        let metadata = fetch(&manifest_path).unwrap();
+        inject_metadata(&manifest_path, true, metadata);
+
+        touch(&member_manifest_path);
+
+        assert_fetched_again(&manifest_path);
+    }
+
+    #[test]
+    fn metadata_is_fetched_again_after_tests_directory_is_created() {
+        let (temp, manifest_path) = setup_manifest();
+        let metadata = fetch(&manifest_path).unwrap();
+        inject_metadata(&manifest_path, true, metadata);
+
+        temp.child("tests/test.cairo").write_str("").unwrap();
+
+        assert_fetched_again(&manifest_path);
+    }
+
+    #[test]
+    fn metadata_is_fetched_again_after_path_dependency_manifest_is_modified() {
+        let (temp, manifest_path) = setup_manifest();
+        temp.child("Scarb.toml")
+            .write_str(indoc!(
+                r#"
+                [package]
+                name = "cached"
+                version = "0.1.0"
+
+                [dependencies]
+                dependency = { path = "dependency" }
+                "#
+            ))
+            .unwrap();
+        temp.child("dependency/Scarb.toml")
+            .write_str(indoc!(
+                r#"
+                [package]
+                name = "dependency"
+                version = "0.1.0"
+                "#
+            ))
+            .unwrap();
+        temp.child("dependency/src/lib.cairo")
+            .write_str("")
+            .unwrap();
+        let dependency_manifest_path =
+            Utf8PathBuf::try_from(temp.join("dependency/Scarb.toml")).unwrap();
+
+        let metadata = ScarbCommand::metadata()
+            .inherit_stderr()
+            .manifest_path(manifest_path.as_std_path())
+            .run()
+            .unwrap();
+        inject_metadata(&manifest_path, false, metadata);
+
+        touch(&dependency_manifest_path);
+
+        let mut fetched = false;
+        cached_metadata(&manifest_path, false, || {
+            fetched = true;
+            ScarbCommand::metadata()
+                .inherit_stderr()
+                .manifest_path(manifest_path.as_std_path())
+                .run()
+        })
+        .unwrap();
+        assert!(fetched);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::package_matches_version_requirement;
+use anyhow::{bail, Result};
+use scarb_metadata::{Metadata, PackageMetadata};
+use semver::{Comparator, Op, Version, VersionReq};
+use shared::print::{emit_warning, DiagnosticCode};
+
+/// Where the std package (`snforge_std` or `sncast_std`) comes from
+#[derive(Debug, PartialEq)]
+enum StdSource {
+    /// Git dependency pinned to a tag is checked like a registry one, as the tag names the release
+    Git {
+        url: String,
+        tag: Option<String>,
+    },
+    Path,
+    Other,
+}
+
+impl StdSource {
+    fn parse(source: &str) -> Self {
+        if source.starts_with("path+") {
+            return StdSource::Path;
+        }
+
+        let Some(git) = source.strip_prefix("git+") else {
+            return StdSource::Other;
+        };
+        let git = git.split_once('#').map_or(git, |(git, _revision)| git);
+        let (url, query) = git.split_once('?').unwrap_or((git, ""));
+        let tag = query
+            .split('&')
+            .find_map(|param| param.strip_prefix("tag="))
+            .map(ToString::to_string);
+
+        StdSource::Git {
+            url: url.to_string(),
+            tag,
+        }
+    }
+
+    /// Line of `Scarb.toml` adding the std package compatible with `version`
+    fn manifest_line(&self, name: &str, version: &Version) -> String {
+        match self {
+            StdSource::Git { url, tag: Some(_) } => {
+                format!(r#"{name} = {{ git = "{url}", tag = "v{version}" }}"#)
+            }
+            _ => format!(r#"{name} = "{version}""#),
+        }
+    }
+}
+
+/// Only the std package released together with the binary is compatible with it
+#[must_use]
+pub fn std_version_requirement(version: &Version) -> VersionReq {
+    let comparator = Comparator {
+        op: Op::Exact,
+        major: version.major,
+        minor: Some(version.minor),
+        patch: Some(version.patch),
+        pre: version.pre.clone(),
+    };
+    VersionReq {
+        comparators: vec![comparator],
+    }
+}
+
+/// Fails if the version of the std package `name` is not compatible with `tool` in `tool_version`,
+/// or only warns about it with `no_version_check`.
+/// Versions of git dependencies not pinned to a tag and of path dependencies are not checked
+pub fn ensure_std_version_compatible(
+    metadata: &Metadata,
+    name: &str,
+    tool: &str,
+    tool_version: &Version,
+    no_version_check: bool,
+) -> Result<()> {
+    let version_requirement = std_version_requirement(tool_version);
+    let is_compatible = package_matches_version_requirement(metadata, name, &version_requirement)?;
+    let package = metadata
+        .packages
+        .iter()
+        .find(|package| package.name == name)
+        .expect("Package should be present, as its version was checked");
+
+    let source = StdSource::parse(&package.source.repr);
+    if matches!(source, StdSource::Path | StdSource::Git { tag: None, .. }) {
+        emit_warning(
+            DiagnosticCode::UncheckedStdVersion,
+            format!(
+                "Package {name} is a {} dependency, so its version {} is not checked against {tool} {tool_version}",
+                if source == StdSource::Path { "path" } else { "git" },
+                package.version
+            ),
+        );
+        return Ok(());
+    }
+
+    if is_compatible {
+        return Ok(());
+    }
+
+    let message = format!(
+        "Package {name} {} is not compatible with {tool} {tool_version}, change the {name} dependency in {} to:\n{}",
+        package.version,
+        dependent_manifests(metadata, name),
+        source.manifest_line(name, tool_version)
+    );
+
+    if no_version_check {
+        emit_warning(DiagnosticCode::IncompatibleStdVersion, message);
+        Ok(())
+    } else {
+        bail!("{message}\nPass `--no-version-check` to run anyway")
+    }
+}
+
+fn dependent_manifests(metadata: &Metadata, name: &str) -> String {
+    metadata
+        .packages
+        .iter()
+        .filter(|package| metadata.workspace.members.contains(&package.id))
+        .filter(|package| depends_on(package, name))
+        .map(|package| package.manifest_path.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn depends_on(package: &PackageMetadata, name: &str) -> bool {
+    package
+        .dependencies
+        .iter()
+        .any(|dependency| dependency.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sources() {
+        assert_eq!(
+            StdSource::parse(
+                "git+https://github.com/foundry-rs/starknet-foundry.git?tag=v0.31.0#3d3b1a5"
+            ),
+            StdSource::Git {
+                url: "https://github.com/foundry-rs/starknet-foundry.git".to_string(),
+                tag: Some("v0.31.0".to_string()),
+            }
+        );
+        assert_eq!(
+            StdSource::parse(
+                "git+https://github.com/foundry-rs/starknet-foundry.git?branch=master#3d3b1a5"
+            ),
+            StdSource::Git {
+                url: "https://github.com/foundry-rs/starknet-foundry.git".to_string(),
+                tag: None,
+            }
+        );
+        assert_eq!(
+            StdSource::parse("path+file:///home/user/snforge_std/Scarb.toml"),
+            StdSource::Path
+        );
+        assert_eq!(
+            StdSource::parse("registry+https://scarbs.xyz/"),
+            StdSource::Other
+        );
+    }
+
+    #[test]
+    fn manifest_lines() {
+        let version = Version::new(0, 31, 0);
+
+        assert_eq!(
+            StdSource::Git {
+                url: "https://github.com/foundry-rs/starknet-foundry.git".to_string(),
+                tag: Some("v0.30.0".to_string()),
+            }
+            .manifest_line("snforge_std", &version),
+            r#"snforge_std = { git = "https://github.com/foundry-rs/starknet-foundry.git", tag = "v0.31.0" }"#
+        );
+        assert_eq!(
+            StdSource::Other.manifest_line("sncast_std", &version),
+            r#"sncast_std = "0.31.0""#
+        );
+    }
+
+    #[test]
+    fn requirement_is_exact() {
+        let requirement = std_version_requirement(&Version::new(0, 31, 0));
+
+        assert!(requirement.matches(&Version::new(0, 31, 0)));
+        assert!(!requirement.matches(&Version::new(0, 31, 1)));
+        assert!(!requirement.matches(&Version::new(0, 30, 0)));
+    }
+}
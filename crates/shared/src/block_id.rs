@@ -0,0 +1,216 @@
+use anyhow::{anyhow, Error, Result};
+use serde::{de, Deserialize, Deserializer};
+use starknet::core::types::{BlockId, BlockTag, Felt};
+use std::fmt;
+use std::str::FromStr;
+
+const ACCEPTED_FORMS: &str =
+    "Possible values are latest, pending, block number (decimal u64) and block hash (0x prefixed hex)";
+
+/// Block identifier passed by the user, in flags, config files or scripts.
+///
+/// Numbers are always decimal and hashes are always `0x` prefixed,
+/// so a value is never interpreted as both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockIdArg {
+    Latest,
+    Pending,
+    Number(u64),
+    Hash(Felt),
+}
+
+impl FromStr for BlockIdArg {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let invalid =
+            |reason: &str| anyhow!("Invalid block id = {value}, {reason}. {ACCEPTED_FORMS}");
+
+        match value {
+            "latest" => Ok(Self::Latest),
+            "pending" => Ok(Self::Pending),
+            "" => Err(invalid("the value is empty")),
+            _ if value.starts_with("0x") => {
+                let digits = &value[2..];
+                if digits.is_empty() || !digits.chars().all(|char| char.is_ascii_hexdigit()) {
+                    return Err(invalid("block hash must contain only hex digits after 0x"));
+                }
+
+                let hash =
+                    Felt::from_hex(value).map_err(|_| invalid("block hash is not a valid felt"))?;
+                // Values exceeding the field prime must not be silently reduced
+                let normalized = digits.trim_start_matches('0').to_ascii_lowercase();
+                if hash.to_hex_string()[2..].trim_start_matches('0') != normalized {
+                    return Err(invalid("block hash is not a valid felt"));
+                }
+
+                Ok(Self::Hash(hash))
+            }
+            _ if value.chars().all(|char| char.is_ascii_digit()) => value
+                .parse()
+                .map(Self::Number)
+                .map_err(|_| invalid("block number does not fit in u64")),
+            _ if value.chars().all(|char| char.is_ascii_hexdigit()) => {
+                Err(invalid("block hash must be prefixed with 0x"))
+            }
+            _ => Err(invalid("the value is not recognized")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BlockIdArg {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for BlockIdArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Latest => write!(f, "latest"),
+            Self::Pending => write!(f, "pending"),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Hash(hash) => write!(f, "{hash:#x}"),
+        }
+    }
+}
+
+impl From<BlockIdArg> for BlockId {
+    fn from(value: BlockIdArg) -> Self {
+        match value {
+            BlockIdArg::Latest => BlockId::Tag(BlockTag::Latest),
+            BlockIdArg::Pending => BlockId::Tag(BlockTag::Pending),
+            BlockIdArg::Number(number) => BlockId::Number(number),
+            BlockIdArg::Hash(hash) => BlockId::Hash(hash),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn error_of(value: &str) -> String {
+        value.parse::<BlockIdArg>().unwrap_err().to_string()
+    }
+
+    #[test_case("latest", BlockIdArg::Latest; "latest")]
+    #[test_case("pending", BlockIdArg::Pending; "pending")]
+    #[test_case("0", BlockIdArg::Number(0); "zero")]
+    #[test_case("123", BlockIdArg::Number(123); "number")]
+    #[test_case("007", BlockIdArg::Number(7); "number with leading zeros")]
+    #[test_case("18446744073709551615", BlockIdArg::Number(u64::MAX); "max number")]
+    #[test_case("0x0", BlockIdArg::Hash(Felt::ZERO); "zero hash")]
+    #[test_case("0x10", BlockIdArg::Hash(Felt::from(16_u8)); "hash of decimal digits")]
+    #[test_case("0xabc", BlockIdArg::Hash(Felt::from(0xabc_u16)); "lowercase hash")]
+    #[test_case("0xABC", BlockIdArg::Hash(Felt::from(0xabc_u16)); "uppercase hash")]
+    #[test_case("0x000abc", BlockIdArg::Hash(Felt::from(0xabc_u16)); "hash with leading zeros")]
+    fn parses_valid_values(value: &str, expected: BlockIdArg) {
+        assert_eq!(value.parse::<BlockIdArg>().unwrap(), expected);
+    }
+
+    #[test]
+    fn parses_max_felt_hash() {
+        let value = "0x800000000000011000000000000000000000000000000000000000000000000";
+
+        assert_eq!(
+            value.parse::<BlockIdArg>().unwrap(),
+            BlockIdArg::Hash(Felt::MAX)
+        );
+    }
+
+    #[test_case("", "the value is empty"; "empty")]
+    #[test_case("abc", "block hash must be prefixed with 0x"; "bare hex")]
+    #[test_case("1a", "block hash must be prefixed with 0x"; "bare hex starting with digit")]
+    #[test_case("0x", "block hash must contain only hex digits after 0x"; "prefix only")]
+    #[test_case("0xg", "block hash must contain only hex digits after 0x"; "non hex digit")]
+    #[test_case("0X1", "the value is not recognized"; "uppercase prefix")]
+    #[test_case("18446744073709551616", "block number does not fit in u64"; "number overflow")]
+    #[test_case("-1", "the value is not recognized"; "negative number")]
+    #[test_case("+1", "the value is not recognized"; "number with sign")]
+    #[test_case("1.5", "the value is not recognized"; "fraction")]
+    #[test_case(" 1", "the value is not recognized"; "whitespace")]
+    #[test_case("Latest", "the value is not recognized"; "capitalized tag")]
+    #[test_case("earliest", "the value is not recognized"; "unknown tag")]
+    #[test_case(
+        "0x800000000000011000000000000000000000000000000000000000000000001",
+        "block hash is not a valid felt";
+        "hash exceeding prime"
+    )]
+    #[test_case(
+        "0x10000000000000000000000000000000000000000000000000000000000000000",
+        "block hash is not a valid felt";
+        "hash longer than felt"
+    )]
+    fn rejects_invalid_values(value: &str, reason: &str) {
+        assert_eq!(
+            error_of(value),
+            format!("Invalid block id = {value}, {reason}. {ACCEPTED_FORMS}")
+        );
+    }
+
+    #[test_case("latest"; "latest")]
+    #[test_case("pending"; "pending")]
+    #[test_case("123"; "number")]
+    #[test_case("0xabc"; "hash")]
+    fn display_round_trips(value: &str) {
+        let block_id: BlockIdArg = value.parse().unwrap();
+
+        assert_eq!(block_id.to_string(), value);
+        assert_eq!(
+            block_id.to_string().parse::<BlockIdArg>().unwrap(),
+            block_id
+        );
+    }
+
+    #[test]
+    fn deserializes_from_string() {
+        let block_ids: Vec<BlockIdArg> =
+            serde_json::from_str(r#"["latest", "pending", "123", "0x7b"]"#).unwrap();
+
+        assert_eq!(
+            block_ids,
+            vec![
+                BlockIdArg::Latest,
+                BlockIdArg::Pending,
+                BlockIdArg::Number(123),
+                BlockIdArg::Hash(Felt::from(123_u8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_to_deserialize_number_literal() {
+        let error = serde_json::from_str::<BlockIdArg>("123").unwrap_err();
+
+        assert!(error.to_string().contains("expected a string"));
+    }
+
+    #[test]
+    fn fails_to_deserialize_bare_hex() {
+        let error = serde_json::from_str::<BlockIdArg>(r#""7b""#).unwrap_err();
+
+        assert!(error
+            .to_string()
+            .contains("Invalid block id = 7b, block hash must be prefixed with 0x"));
+    }
+
+    #[test]
+    fn converts_to_starknet_block_id() {
+        assert_eq!(
+            BlockId::from(BlockIdArg::Latest),
+            BlockId::Tag(BlockTag::Latest)
+        );
+        assert_eq!(
+            BlockId::from(BlockIdArg::Pending),
+            BlockId::Tag(BlockTag::Pending)
+        );
+        assert_eq!(BlockId::from(BlockIdArg::Number(5)), BlockId::Number(5));
+        assert_eq!(
+            BlockId::from(BlockIdArg::Hash(Felt::ONE)),
+            BlockId::Hash(Felt::ONE)
+        );
+    }
+}
@@ -1,11 +1,12 @@
 use crate::consts::EXPECTED_RPC_VERSION;
-use crate::print::print_as_warning;
+use crate::print::{emit_warning, DiagnosticCode};
 use crate::rpc::{get_rpc_version, is_expected_version};
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use std::fmt::Display;
 
+pub mod block_id;
 pub mod command;
 pub mod consts;
 pub mod print;
@@ -19,9 +20,10 @@ pub async fn verify_and_warn_if_incompatible_rpc_version(
 ) -> Result<()> {
     let node_spec_version = get_rpc_version(client).await?;
     if !is_expected_version(&node_spec_version) {
-        print_as_warning(&anyhow!(
-            "RPC node with the url {url} uses incompatible version {node_spec_version}. Expected version: {EXPECTED_RPC_VERSION}"
-        ));
+        emit_warning(
+            DiagnosticCode::IncompatibleRpcVersion,
+            format!("RPC node with the url {url} uses incompatible version {node_spec_version}. Expected version: {EXPECTED_RPC_VERSION}"),
+        );
     }
 
     Ok(())
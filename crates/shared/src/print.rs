@@ -1,7 +1,289 @@
-use anyhow::Error;
 use console::style;
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 
-pub fn print_as_warning(error: &Error) {
-    let warning_tag = style("WARNING").color256(11);
-    println!("[{warning_tag}] {error}");
+static QUIET: AtomicBool = AtomicBool::new(false);
+static HUMAN_OUTPUT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+static COLLECT_DIAGNOSTICS: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables printing of non-error diagnostics (e.g. warnings) for the whole process.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Makes output meant for humans go to stderr for the whole process,
+/// so that stdout only contains machine-readable output.
+pub fn set_human_output_to_stderr(to_stderr: bool) {
+    HUMAN_OUTPUT_TO_STDERR.store(to_stderr, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn is_human_output_to_stderr() -> bool {
+    HUMAN_OUTPUT_TO_STDERR.load(Ordering::Relaxed)
+}
+
+/// Keeps reported diagnostics for the whole process, so they can be taken with [`drain_diagnostics`],
+/// e.g. to include them in JSON output. They are only printed to stderr otherwise.
+pub fn set_collect_diagnostics(collect: bool) {
+    COLLECT_DIAGNOSTICS.store(collect, Ordering::Relaxed);
+}
+
+/// Prints a line meant for humans, to stdout or to stderr if set with [`set_human_output_to_stderr`]
+#[macro_export]
+macro_rules! human_println {
+    ($($arg:tt)*) => {
+        if $crate::print::is_human_output_to_stderr() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Stable identifier of a diagnostic, kept when the wording of its message changes,
+/// so that machine-readable output can be matched against it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiagnosticCode {
+    MissingStarknetArtifacts,
+    IncompatibleRpcVersion,
+    IncompatibleStdVersion,
+    IncompatibleScarbVersion,
+    ExceededProtocolLimit,
+    MissingProfile,
+    ContractNameCaseMismatch,
+    ScriptNotInWorkspace,
+    MissingPreviousRun,
+    MissingTest,
+    InvalidFuzzCorpus,
+    MissingCoverageData,
+    UncheckedStdVersion,
+    UnsupportedSierraVersion,
+}
+
+impl DiagnosticCode {
+    /// Codes are never reused, new diagnostics get the next free number
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCode::MissingStarknetArtifacts => "W0001",
+            DiagnosticCode::IncompatibleRpcVersion => "W0002",
+            DiagnosticCode::IncompatibleStdVersion => "W0003",
+            DiagnosticCode::IncompatibleScarbVersion => "W0004",
+            DiagnosticCode::ExceededProtocolLimit => "W0005",
+            DiagnosticCode::MissingProfile => "W0006",
+            DiagnosticCode::ContractNameCaseMismatch => "W0007",
+            DiagnosticCode::ScriptNotInWorkspace => "W0008",
+            DiagnosticCode::MissingPreviousRun => "W0009",
+            DiagnosticCode::MissingTest => "W0010",
+            DiagnosticCode::InvalidFuzzCorpus => "W0011",
+            DiagnosticCode::MissingCoverageData => "W0012",
+            DiagnosticCode::UncheckedStdVersion => "W0013",
+            DiagnosticCode::UnsupportedSierraVersion => "W0014",
+        }
+    }
+}
+
+impl Display for DiagnosticCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for DiagnosticCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: DiagnosticCode,
+    pub message: String,
+}
+
+impl Diagnostic {
+    #[must_use]
+    pub fn warning(code: DiagnosticCode, message: impl Display) -> Self {
+        Self {
+            severity: Severity::Warning,
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn error(code: DiagnosticCode, message: impl Display) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+struct DiagnosticsSink {
+    /// All diagnostics reported during the run, to deduplicate them
+    emitted: Vec<Diagnostic>,
+    /// Diagnostics not taken with [`drain_diagnostics`] yet, kept only if set with [`set_collect_diagnostics`]
+    pending: Vec<Diagnostic>,
+}
+
+static DIAGNOSTICS: Mutex<DiagnosticsSink> = Mutex::new(DiagnosticsSink {
+    emitted: Vec::new(),
+    pending: Vec::new(),
+});
+
+/// Reports the diagnostic to stderr, unless an identical one was already reported during the run.
+/// Warnings are not printed when quiet, but they can still be drained if collected.
+/// Returns whether the diagnostic was reported
+pub fn emit_diagnostic(diagnostic: Diagnostic) -> bool {
+    {
+        let mut sink = DIAGNOSTICS.lock().unwrap();
+        if sink.emitted.contains(&diagnostic) {
+            return false;
+        }
+        sink.emitted.push(diagnostic.clone());
+        if COLLECT_DIAGNOSTICS.load(Ordering::Relaxed) {
+            sink.pending.push(diagnostic.clone());
+        }
+    }
+
+    match diagnostic.severity {
+        Severity::Warning if is_quiet() => {}
+        Severity::Warning => eprintln!("[{}] {diagnostic}", style("WARNING").color256(11)),
+        Severity::Error => eprintln!("[{}] {diagnostic}", style("ERROR").red()),
+    }
+
+    true
+}
+
+pub fn emit_warning(code: DiagnosticCode, message: impl Display) {
+    emit_diagnostic(Diagnostic::warning(code, message));
+}
+
+/// Takes the diagnostics reported since the last call, e.g. to include them in JSON output.
+/// Drained diagnostics are still deduplicated
+pub fn drain_diagnostics() -> Vec<Diagnostic> {
+    std::mem::take(&mut DIAGNOSTICS.lock().unwrap().pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The sink is shared by all tests of the process, so every test uses its own messages
+    fn drained_with(message: &str) -> Vec<Diagnostic> {
+        drain_diagnostics()
+            .into_iter()
+            .filter(|diagnostic| diagnostic.message.contains(message))
+            .collect()
+    }
+
+    #[test]
+    fn deduplicates_identical_diagnostics() {
+        set_collect_diagnostics(true);
+        let diagnostic = Diagnostic::warning(
+            DiagnosticCode::MissingStarknetArtifacts,
+            "deduplicated artifacts are missing",
+        );
+
+        assert!(emit_diagnostic(diagnostic.clone()));
+        assert!(!emit_diagnostic(diagnostic.clone()));
+
+        assert_eq!(drained_with("deduplicated"), vec![diagnostic.clone()]);
+        assert!(!emit_diagnostic(diagnostic));
+        assert!(drained_with("deduplicated").is_empty());
+    }
+
+    #[test]
+    fn keeps_diagnostics_with_different_messages_or_severities() {
+        set_collect_diagnostics(true);
+        let first = Diagnostic::warning(DiagnosticCode::MissingTest, "different first");
+        let second = Diagnostic::warning(DiagnosticCode::MissingTest, "different second");
+        let error = Diagnostic::error(DiagnosticCode::MissingTest, "different first");
+
+        assert!(emit_diagnostic(first.clone()));
+        assert!(emit_diagnostic(second.clone()));
+        assert!(emit_diagnostic(error.clone()));
+
+        assert_eq!(drained_with("different"), vec![first, second, error]);
+    }
+
+    #[test]
+    fn codes_do_not_depend_on_message() {
+        let old_wording = Diagnostic::warning(
+            DiagnosticCode::MissingStarknetArtifacts,
+            "missing starknet artifacts file",
+        );
+        let new_wording = Diagnostic::warning(
+            DiagnosticCode::MissingStarknetArtifacts,
+            "Starknet artifacts file was not found",
+        );
+
+        assert_eq!(old_wording.code.as_str(), "W0001");
+        assert_eq!(new_wording.code.as_str(), "W0001");
+        assert_eq!(
+            old_wording.to_string(),
+            "W0001: missing starknet artifacts file"
+        );
+    }
+
+    #[test]
+    fn codes_are_stable() {
+        let codes = [
+            (DiagnosticCode::MissingStarknetArtifacts, "W0001"),
+            (DiagnosticCode::IncompatibleRpcVersion, "W0002"),
+            (DiagnosticCode::IncompatibleStdVersion, "W0003"),
+            (DiagnosticCode::IncompatibleScarbVersion, "W0004"),
+            (DiagnosticCode::ExceededProtocolLimit, "W0005"),
+            (DiagnosticCode::MissingProfile, "W0006"),
+            (DiagnosticCode::ContractNameCaseMismatch, "W0007"),
+            (DiagnosticCode::ScriptNotInWorkspace, "W0008"),
+            (DiagnosticCode::MissingPreviousRun, "W0009"),
+            (DiagnosticCode::MissingTest, "W0010"),
+            (DiagnosticCode::InvalidFuzzCorpus, "W0011"),
+            (DiagnosticCode::MissingCoverageData, "W0012"),
+            (DiagnosticCode::UncheckedStdVersion, "W0013"),
+            (DiagnosticCode::UnsupportedSierraVersion, "W0014"),
+        ];
+
+        for (code, expected) in codes {
+            assert_eq!(code.as_str(), expected);
+        }
+    }
+
+    #[test]
+    fn serialized_format() {
+        let diagnostic = Diagnostic::warning(DiagnosticCode::MissingProfile, "serialized");
+
+        assert_eq!(
+            serde_json::to_value(&diagnostic).unwrap(),
+            serde_json::json!({
+                "severity": "warning",
+                "code": "W0006",
+                "message": "serialized"
+            })
+        );
+    }
 }
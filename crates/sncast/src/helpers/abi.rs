@@ -0,0 +1,70 @@
+use anyhow::{Context, Result};
+use camino::Utf8Path;
+use data_transformer::{ensure_function_in_abi, get_abi, Calldata};
+use serde::Deserialize;
+use starknet::core::types::contract::AbiEntry;
+use starknet::core::types::{ContractClass, Felt};
+use std::fs;
+
+/// ABI used for encoding calldata written as Cairo expressions
+pub enum CalldataAbi {
+    /// ABI embedded in the contract class
+    Class(ContractClass),
+    /// ABI passed with `--abi-file`
+    File(Vec<AbiEntry>),
+}
+
+/// Either the ABI itself or a Sierra contract class containing it
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AbiFile {
+    Abi(Vec<AbiEntry>),
+    ContractClass { abi: Vec<AbiEntry> },
+}
+
+/// Reads the ABI from a JSON file containing either the ABI or a Sierra contract class
+pub fn read_abi_file(path: &Utf8Path) -> Result<Vec<AbiEntry>> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read ABI file {path}"))?;
+
+    let abi = match serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse ABI file {path}"))?
+    {
+        AbiFile::Abi(abi) | AbiFile::ContractClass { abi } => abi,
+    };
+
+    Ok(abi)
+}
+
+impl CalldataAbi {
+    pub fn from_file(path: &Utf8Path) -> Result<Self> {
+        read_abi_file(path).map(Self::File)
+    }
+
+    /// Checks that the function is present in the ABI passed with `--abi-file`.
+    /// The ABI embedded in the class is not checked, the node verifies calls to the contract anyway.
+    pub fn ensure_function(&self, function_name: &str) -> Result<()> {
+        match self {
+            Self::Class(_) => Ok(()),
+            Self::File(abi) => ensure_function_in_abi(abi, function_name),
+        }
+    }
+
+    pub fn serialize(&self, calldata: Vec<String>, function_selector: &Felt) -> Result<Vec<Felt>> {
+        match self {
+            Self::Class(class) => {
+                Calldata::from(calldata).serialized(class.clone(), function_selector)
+            }
+            Self::File(abi) => Calldata::from(calldata).serialized_with_abi(abi, function_selector),
+        }
+    }
+
+    /// Returns `None` if the ABI embedded in the class is not available
+    #[must_use]
+    pub fn abi(&self) -> Option<Vec<AbiEntry>> {
+        match self {
+            Self::Class(class) => get_abi(class),
+            Self::File(abi) => Some(abi.clone()),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::{bail, Result};
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+pub const STARKLI_ACCOUNT_FILE_NAME: &str = "account.json";
+pub const STARKLI_KEYSTORE_FILE_NAME: &str = "keystore.json";
+
+/// Where accounts passed with `--account` are read from
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountSource {
+    /// Accounts file of sncast, or a starkli account file when `--keystore` is passed
+    #[default]
+    Snfoundry,
+    /// Starkli account files, looked up by name in the accounts directory
+    Starkli,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarkliAccountPaths {
+    pub account: Utf8PathBuf,
+    pub keystore: Utf8PathBuf,
+}
+
+/// Resolves `account` to a starkli account file and its keystore.
+///
+/// `account` is either a path to an account file or a name of a directory in `accounts_dir`
+/// holding `account.json` and `keystore.json`. Explicitly passed `keystore` always takes
+/// precedence over the one stored next to the account file.
+pub fn resolve_starkli_account(
+    account: &str,
+    keystore: Option<&Utf8Path>,
+    accounts_dir: &Utf8Path,
+) -> Result<StarkliAccountPaths> {
+    if account.is_empty() {
+        bail!("Argument `--account` must be passed when using `--account-source starkli`");
+    }
+
+    let account_path = Utf8PathBuf::from(account);
+    let account_path = if account_path.is_file() {
+        account_path
+    } else {
+        let named_account_path = accounts_dir.join(account).join(STARKLI_ACCOUNT_FILE_NAME);
+        if !named_account_path.is_file() {
+            bail!(
+                "Account = {account} not found: it is neither a path to a starkli account file nor an account in {accounts_dir} (expected {named_account_path})"
+            );
+        }
+        named_account_path
+    };
+
+    let keystore_path = match keystore {
+        Some(keystore) => keystore.to_path_buf(),
+        None => {
+            let keystore_path = account_path
+                .parent()
+                .unwrap_or(Utf8Path::new(""))
+                .join(STARKLI_KEYSTORE_FILE_NAME);
+            if !keystore_path.is_file() {
+                bail!(
+                    "Keystore of account = {account} not found at {keystore_path}, pass its path with `--keystore`"
+                );
+            }
+            keystore_path
+        }
+    };
+
+    Ok(StarkliAccountPaths {
+        account: account_path,
+        keystore: keystore_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn accounts_dir_with(accounts: &[(&str, bool)]) -> (TempDir, Utf8PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let accounts_dir = Utf8PathBuf::from_path_buf(temp_dir.path().to_path_buf()).unwrap();
+
+        for (name, with_keystore) in accounts {
+            let account_dir = accounts_dir.join(name);
+            fs::create_dir_all(&account_dir).unwrap();
+            fs::write(account_dir.join(STARKLI_ACCOUNT_FILE_NAME), "{}").unwrap();
+            if *with_keystore {
+                fs::write(account_dir.join(STARKLI_KEYSTORE_FILE_NAME), "{}").unwrap();
+            }
+        }
+
+        (temp_dir, accounts_dir)
+    }
+
+    #[test]
+    fn resolves_account_by_name() {
+        let (_temp_dir, accounts_dir) = accounts_dir_with(&[("deployer", true)]);
+
+        let paths = resolve_starkli_account("deployer", None, &accounts_dir).unwrap();
+
+        assert_eq!(
+            paths,
+            StarkliAccountPaths {
+                account: accounts_dir.join("deployer/account.json"),
+                keystore: accounts_dir.join("deployer/keystore.json"),
+            }
+        );
+    }
+
+    #[test]
+    fn resolves_account_by_path() {
+        let (_temp_dir, accounts_dir) = accounts_dir_with(&[("deployer", true)]);
+        let account_path = accounts_dir.join("deployer/account.json");
+
+        let paths =
+            resolve_starkli_account(account_path.as_str(), None, Utf8Path::new("inexistent"))
+                .unwrap();
+
+        assert_eq!(paths.account, account_path);
+        assert_eq!(paths.keystore, accounts_dir.join("deployer/keystore.json"));
+    }
+
+    #[test]
+    fn explicit_keystore_takes_precedence() {
+        let (_temp_dir, accounts_dir) = accounts_dir_with(&[("deployer", false)]);
+        let keystore = Utf8PathBuf::from("/path/to/keystore.json");
+
+        let paths = resolve_starkli_account("deployer", Some(&keystore), &accounts_dir).unwrap();
+
+        assert_eq!(paths.account, accounts_dir.join("deployer/account.json"));
+        assert_eq!(paths.keystore, keystore);
+    }
+
+    #[test]
+    fn fails_for_missing_account() {
+        let (_temp_dir, accounts_dir) = accounts_dir_with(&[("deployer", true)]);
+
+        let error = resolve_starkli_account("user", None, &accounts_dir).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Account = user not found: it is neither a path to a starkli account file nor an account in {accounts_dir} (expected {})",
+                accounts_dir.join("user/account.json")
+            )
+        );
+    }
+
+    #[test]
+    fn fails_for_missing_keystore() {
+        let (_temp_dir, accounts_dir) = accounts_dir_with(&[("deployer", false)]);
+
+        let error = resolve_starkli_account("deployer", None, &accounts_dir).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Keystore of account = deployer not found at {}, pass its path with `--keystore`",
+                accounts_dir.join("deployer/keystore.json")
+            )
+        );
+    }
+
+    #[test]
+    fn fails_for_empty_account() {
+        let error = resolve_starkli_account("", None, Utf8Path::new("dir")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Argument `--account` must be passed when using `--account-source starkli`"
+        );
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{chain_id_to_network_name, get_chain_id};
+use anyhow::{bail, ensure, Context, Result};
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::Felt;
+use starknet::providers::{jsonrpc::HttpTransport, JsonRpcClient};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+/// Contract address passed either directly or as `@name` of an address book entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractReference {
+    Address(Felt),
+    Name(String),
+}
+
+impl FromStr for ContractReference {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        if let Some(name) = value.strip_prefix('@') {
+            validate_name(name)?;
+            return Ok(Self::Name(name.to_string()));
+        }
+
+        Felt::from_str(value)
+            .map(Self::Address)
+            .with_context(|| format!("Invalid contract address = {value}"))
+    }
+}
+
+impl fmt::Display for ContractReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(address) => write!(f, "{address:#x}"),
+            Self::Name(name) => write!(f, "@{name}"),
+        }
+    }
+}
+
+impl ContractReference {
+    /// Returns the address, looking it up in `address_book` for the network of `provider` if needed.
+    /// Chain id is fetched only for names, addresses are returned without any request.
+    pub async fn resolve(
+        &self,
+        address_book: &Utf8PathBuf,
+        provider: &JsonRpcClient<HttpTransport>,
+    ) -> Result<Felt> {
+        match self {
+            Self::Address(address) => Ok(*address),
+            Self::Name(name) => {
+                let chain_id = get_chain_id(provider).await?;
+                AddressBook::load(address_book)?.get(name, chain_id)
+            }
+        }
+    }
+}
+
+pub fn validate_name(name: &str) -> Result<()> {
+    ensure!(!name.is_empty(), "Address book entry name can't be empty");
+    ensure!(
+        name.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+        "Invalid address book entry name = {name}, only alphanumeric characters, `_` and `-` are allowed"
+    );
+    Ok(())
+}
+
+/// Names of contracts mapped to their addresses, stored separately for every network,
+/// so the same name can't point to a contract deployed on a different chain
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct AddressBook {
+    #[serde(flatten)]
+    networks: BTreeMap<String, BTreeMap<String, Felt>>,
+}
+
+impl AddressBook {
+    pub fn load(path: &Utf8PathBuf) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read address book at = {path}"))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse address book at = {path}"))
+    }
+
+    pub fn save(&self, path: &Utf8PathBuf) -> Result<()> {
+        let contents = toml::to_string_pretty(self).context("Failed to serialize address book")?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write address book at = {path}"))
+    }
+
+    pub fn get(&self, name: &str, chain_id: Felt) -> Result<Felt> {
+        let network_name = chain_id_to_network_name(chain_id);
+
+        match self
+            .networks
+            .get(&network_name)
+            .and_then(|entries| entries.get(name))
+        {
+            Some(address) => Ok(*address),
+            None => bail!(
+                "Contract with name = {name} not found in address book for network = {network_name}"
+            ),
+        }
+    }
+
+    pub fn insert(&mut self, name: &str, chain_id: Felt, address: Felt) {
+        self.networks
+            .entry(chain_id_to_network_name(chain_id))
+            .or_default()
+            .insert(name.to_string(), address);
+    }
+}
+
+/// Stores `address` under `name` for the network of `chain_id`, replacing the previous entry
+pub fn save_to_address_book(
+    address_book: &Utf8PathBuf,
+    name: &str,
+    chain_id: Felt,
+    address: Felt,
+) -> Result<()> {
+    let mut book = AddressBook::load(address_book)?;
+    book.insert(name, chain_id, address);
+    book.save(address_book)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_provider, MAINNET, SEPOLIA};
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_reference() {
+        assert_eq!(
+            ContractReference::from_str("0x123").unwrap(),
+            ContractReference::Address(Felt::from_hex_unchecked("0x123"))
+        );
+        assert_eq!(
+            ContractReference::from_str("@my_contract").unwrap(),
+            ContractReference::Name("my_contract".to_string())
+        );
+        assert!(ContractReference::from_str("@").is_err());
+        assert!(ContractReference::from_str("@my contract").is_err());
+        assert!(ContractReference::from_str("my_contract").is_err());
+    }
+
+    #[test]
+    fn get_is_scoped_per_network() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(temp_dir.path().join("addresses.toml")).unwrap();
+
+        save_to_address_book(&path, "token", SEPOLIA, Felt::ONE).unwrap();
+        save_to_address_book(&path, "token", MAINNET, Felt::TWO).unwrap();
+
+        let book = AddressBook::load(&path).unwrap();
+        assert_eq!(book.get("token", SEPOLIA).unwrap(), Felt::ONE);
+        assert_eq!(book.get("token", MAINNET).unwrap(), Felt::TWO);
+
+        save_to_address_book(&path, "token", SEPOLIA, Felt::THREE).unwrap();
+        let book = AddressBook::load(&path).unwrap();
+        assert_eq!(book.get("token", SEPOLIA).unwrap(), Felt::THREE);
+    }
+
+    #[test]
+    fn get_missing_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(temp_dir.path().join("addresses.toml")).unwrap();
+
+        save_to_address_book(&path, "token", MAINNET, Felt::ONE).unwrap();
+
+        let error = AddressBook::load(&path)
+            .unwrap()
+            .get("token", SEPOLIA)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Contract with name = token not found in address book for network = alpha-sepolia"
+        );
+    }
+
+    #[tokio::test]
+    async fn resolve_address_without_fetching_chain_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = Utf8PathBuf::from_path_buf(temp_dir.path().join("addresses.toml")).unwrap();
+        // Nothing listens on this port, any request would fail
+        let provider = get_provider("http://127.0.0.1:1").unwrap();
+
+        let address = ContractReference::Address(Felt::ONE)
+            .resolve(&path, &provider)
+            .await
+            .unwrap();
+        assert_eq!(address, Felt::ONE);
+
+        let error = ContractReference::Name("token".to_string())
+            .resolve(&path, &provider)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Failed to fetch chain_id");
+    }
+}
@@ -0,0 +1,96 @@
+use std::future::{self, Future};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Source of time used when waiting for transactions, so the waiting can be tested without real delays
+pub trait Clock: Sync {
+    fn now(&self) -> Instant;
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// Uses the system time, sleeping yields to the runtime instead of blocking the thread
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
+}
+
+/// Time passes only when sleeping or when advanced explicitly, sleeping returns immediately
+#[derive(Debug)]
+pub struct ManualClock {
+    start: Instant,
+    state: Mutex<ManualClockState>,
+}
+
+#[derive(Debug, Default)]
+struct ManualClockState {
+    elapsed: Duration,
+    sleeps: Vec<Duration>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            state: Mutex::default(),
+        }
+    }
+}
+
+impl ManualClock {
+    pub fn advance(&self, duration: Duration) {
+        self.state.lock().unwrap().elapsed += duration;
+    }
+
+    /// Durations of all sleeps so far, in order
+    #[must_use]
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.state.lock().unwrap().sleeps.clone()
+    }
+
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.state.lock().unwrap().elapsed
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send {
+        let mut state = self.state.lock().unwrap();
+        state.elapsed += duration;
+        state.sleeps.push(duration);
+
+        future::ready(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn manual_clock_advances_only_when_sleeping_or_advanced() {
+        let clock = ManualClock::default();
+        let start = clock.now();
+
+        assert_eq!(clock.now(), start);
+
+        clock.sleep(Duration::from_secs(5)).await;
+        clock.advance(Duration::from_millis(300));
+
+        assert_eq!(clock.now() - start, Duration::from_millis(5300));
+        assert_eq!(clock.sleeps(), vec![Duration::from_secs(5)]);
+    }
+}
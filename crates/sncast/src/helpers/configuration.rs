@@ -2,9 +2,15 @@ use crate::ValidatedWaitParams;
 use anyhow::Result;
 use camino::Utf8PathBuf;
 use configuration::GlobalConfig;
-use serde::{Deserialize, Serialize};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use starknet::core::types::Felt;
+use std::str::FromStr;
 
+use super::account_source::AccountSource;
 use super::block_explorer;
+use super::constants::DEFAULT_ADDRESS_BOOK_FILE;
+use super::fee::FeeMultiplier;
 
 const fn show_explorer_links_default() -> bool {
     true
@@ -27,6 +33,13 @@ pub struct CastConfig {
 
     pub keystore: Option<Utf8PathBuf>,
 
+    #[serde(
+        default,
+        rename(serialize = "account-source", deserialize = "account-source")
+    )]
+    /// Where accounts are read from, the accounts file of sncast or starkli account files
+    pub account_source: AccountSource,
+
     #[serde(
         default,
         rename(serialize = "wait-params", deserialize = "wait-params")
@@ -46,6 +59,28 @@ pub struct CastConfig {
     )]
     /// Print links pointing to pages with transaction details in the chosen block explorer
     pub show_explorer_links: bool,
+
+    #[serde(
+        default,
+        rename(serialize = "address-book", deserialize = "address-book")
+    )]
+    /// File mapping contract names to their addresses, used by `--save-as` and `@name` references
+    pub address_book: Option<Utf8PathBuf>,
+
+    #[serde(
+        default,
+        rename(serialize = "max-fee-cap", deserialize = "max-fee-cap"),
+        deserialize_with = "deserialize_max_fee_cap"
+    )]
+    /// Highest max fee of transactions, unless `--max-fee-cap` is passed
+    pub max_fee_cap: Option<Felt>,
+
+    #[serde(
+        default,
+        rename(serialize = "fee-multiplier", deserialize = "fee-multiplier")
+    )]
+    /// Multiplier of the estimated fee, unless `--fee-multiplier` is passed
+    pub fee_multiplier: Option<FeeMultiplier>,
 }
 
 impl Default for CastConfig {
@@ -55,13 +90,43 @@ impl Default for CastConfig {
             account: String::default(),
             accounts_file: Utf8PathBuf::default(),
             keystore: None,
+            account_source: AccountSource::default(),
             wait_params: ValidatedWaitParams::default(),
             block_explorer: Some(block_explorer::Service::default()),
             show_explorer_links: true,
+            address_book: None,
+            max_fee_cap: None,
+            fee_multiplier: None,
         }
     }
 }
 
+impl CastConfig {
+    #[must_use]
+    pub fn address_book_path(&self) -> Utf8PathBuf {
+        self.address_book
+            .clone()
+            .unwrap_or_else(|| Utf8PathBuf::from(DEFAULT_ADDRESS_BOOK_FILE))
+    }
+}
+
+/// Fees exceed the range of numbers in TOML, so the cap can also be a decimal or hex string
+fn deserialize_max_fee_cap<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Felt>, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Null => Ok(None),
+        serde_json::Value::Number(number) => number
+            .as_u64()
+            .map(|number| Some(Felt::from(number)))
+            .ok_or_else(|| D::Error::custom(format!("invalid max-fee-cap {number}"))),
+        serde_json::Value::String(string) => Felt::from_str(&string)
+            .map(Some)
+            .map_err(|_| D::Error::custom(format!("invalid max-fee-cap {string}"))),
+        other => Err(D::Error::custom(format!("invalid max-fee-cap {other}"))),
+    }
+}
+
 impl GlobalConfig for CastConfig {
     #[must_use]
     fn tool_name() -> &'static str {
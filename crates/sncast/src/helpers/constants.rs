@@ -1,5 +1,6 @@
 use starknet::core::types::Felt;
 use starknet::macros::felt;
+use std::time::Duration;
 
 pub static DEFAULT_MULTICALL_CONTENTS: &str = r#"[[call]]
 call_type = "deploy"
@@ -33,10 +34,18 @@ pub const BRAAVOS_BASE_ACCOUNT_CLASS_HASH: Felt =
 pub const WAIT_TIMEOUT: u16 = 300;
 #[allow(dead_code)]
 pub const WAIT_RETRY_INTERVAL: u8 = 5;
+// fraction of the interval added at random to each wait, so many processes do not poll in lockstep
+pub const WAIT_INTERVAL_JITTER: f64 = 0.2;
+// the interval is doubled up to this limit every time the node rate limits the requests
+pub const MAX_RATE_LIMITED_WAIT_INTERVAL: Duration = Duration::from_secs(60);
 
 #[allow(dead_code)]
 pub const DEFAULT_ACCOUNTS_FILE: &str = "~/.starknet_accounts/starknet_open_zeppelin_accounts.json";
 
+pub const DEFAULT_STARKLI_ACCOUNTS_DIR: &str = "~/.starknet_accounts";
+
+pub const DEFAULT_ADDRESS_BOOK_FILE: &str = "snfoundry_addresses.toml";
+
 pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "KEYSTORE_PASSWORD";
 pub const CREATE_KEYSTORE_PASSWORD_ENV_VAR: &str = "CREATE_KEYSTORE_PASSWORD";
 
@@ -44,6 +53,10 @@ pub const SCRIPT_LIB_ARTIFACT_NAME: &str = "__sncast_script_lib";
 
 pub const STATE_FILE_VERSION: u8 = 1;
 
+pub const EXECUTION_LOG_VERSION: u8 = 1;
+
+pub const EXECUTION_LOG_FILE: &str = ".snfoundry_script_state.json";
+
 pub const INIT_SCRIPTS_DIR: &str = "scripts";
 
 pub const DEFAULT_STATE_FILE_SUFFIX: &str = "state.json";
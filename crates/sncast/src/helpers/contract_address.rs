@@ -0,0 +1,181 @@
+use crate::udc_uniqueness;
+use anyhow::{bail, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use starknet::core::types::Felt;
+use starknet::core::utils::{get_contract_address, get_udc_deployed_address};
+
+/// How the address of a deployed contract is derived
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddressDerivation {
+    /// Deployment through the Universal Deployer Contract, as done by `deploy`
+    #[default]
+    Udc,
+    /// `deploy_syscall` called by the deployer, or a deploy account transaction when there is no deployer
+    DeploySyscall,
+}
+
+/// Computes the address a contract is deployed at, without deploying it.
+///
+/// With [`AddressDerivation::Udc`], `deployer_address` is required with `unique` and rejected
+/// without it, the same way the salt is modified with the account address by `deploy --unique`.
+/// With [`AddressDerivation::DeploySyscall`], the address of the calling contract is used,
+/// zero when it is not passed.
+pub fn compute_contract_address(
+    derivation: AddressDerivation,
+    class_hash: Felt,
+    salt: Felt,
+    constructor_calldata: &[Felt],
+    deployer_address: Option<Felt>,
+    unique: bool,
+) -> Result<Felt> {
+    match derivation {
+        AddressDerivation::Udc => {
+            let deployer_address = match (unique, deployer_address) {
+                (true, Some(deployer_address)) => deployer_address,
+                (true, None) => {
+                    bail!("Argument `--deployer-address` must be passed with `--unique`")
+                }
+                (false, Some(_)) => {
+                    bail!("Argument `--deployer-address` can only be used with `--unique` or `--derivation deploy-syscall`")
+                }
+                (false, None) => Felt::ZERO,
+            };
+
+            Ok(get_udc_deployed_address(
+                salt,
+                class_hash,
+                &udc_uniqueness(unique, deployer_address),
+                constructor_calldata,
+            ))
+        }
+        AddressDerivation::DeploySyscall => {
+            if unique {
+                bail!("Argument `--unique` can only be used with `--derivation udc`");
+            }
+
+            Ok(get_contract_address(
+                salt,
+                class_hash,
+                constructor_calldata,
+                deployer_address.unwrap_or(Felt::ZERO),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::constants::UDC_ADDRESS;
+    use starknet::core::crypto::pedersen_hash;
+    use starknet::macros::felt;
+
+    const CLASS_HASH: Felt = felt!("0x123");
+    const SALT: Felt = felt!("0x456");
+    const DEPLOYER: Felt = felt!("0x789");
+
+    #[test]
+    fn udc_not_unique_is_deployed_from_zero() {
+        let address = compute_contract_address(
+            AddressDerivation::Udc,
+            CLASS_HASH,
+            SALT,
+            &[Felt::ONE, Felt::TWO],
+            None,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            address,
+            get_contract_address(SALT, CLASS_HASH, &[Felt::ONE, Felt::TWO], Felt::ZERO)
+        );
+    }
+
+    #[test]
+    fn udc_deployer_without_unique() {
+        let result = compute_contract_address(
+            AddressDerivation::Udc,
+            CLASS_HASH,
+            SALT,
+            &[],
+            Some(DEPLOYER),
+            false,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn udc_unique_salts_with_deployer() {
+        let address = compute_contract_address(
+            AddressDerivation::Udc,
+            CLASS_HASH,
+            SALT,
+            &[],
+            Some(DEPLOYER),
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            address,
+            get_contract_address(
+                pedersen_hash(&DEPLOYER, &SALT),
+                CLASS_HASH,
+                &[],
+                UDC_ADDRESS
+            )
+        );
+    }
+
+    #[test]
+    fn deploy_syscall_uses_deployer() {
+        let address = compute_contract_address(
+            AddressDerivation::DeploySyscall,
+            CLASS_HASH,
+            SALT,
+            &[Felt::ONE],
+            Some(DEPLOYER),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            address,
+            get_contract_address(SALT, CLASS_HASH, &[Felt::ONE], DEPLOYER)
+        );
+    }
+
+    #[test]
+    fn unique_without_deployer() {
+        let error =
+            compute_contract_address(AddressDerivation::Udc, CLASS_HASH, SALT, &[], None, true)
+                .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Argument `--deployer-address` must be passed with `--unique`"
+        );
+    }
+
+    #[test]
+    fn unique_with_deploy_syscall() {
+        let error = compute_contract_address(
+            AddressDerivation::DeploySyscall,
+            CLASS_HASH,
+            SALT,
+            &[],
+            Some(DEPLOYER),
+            true,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Argument `--unique` can only be used with `--derivation udc`"
+        );
+    }
+}
@@ -1,10 +1,18 @@
-use anyhow::{bail, ensure, Result};
+use crate::helpers::configuration::CastConfig;
+use anyhow::{anyhow, bail, ensure, Context, Result};
 use clap::{Args, ValueEnum};
 use conversions::serde::deserialize::CairoDeserialize;
 use conversions::TryIntoConv;
-use starknet::core::types::{BlockId, Felt};
+use num_bigint::BigUint;
+use num_traits::ToPrimitive;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use starknet::core::types::{BlockId, FeeEstimate, Felt};
 use starknet::providers::Provider;
 use starknet_types_core::felt::NonZeroFelt;
+use std::fmt::{Display, Formatter};
+use std::future::Future;
+use std::str::FromStr;
 
 #[derive(Args, Debug, Clone)]
 pub struct FeeArgs {
@@ -23,6 +31,14 @@ pub struct FeeArgs {
     /// Max gas price in Fri. If not provided, will be automatically estimated. (Only for STRK fee payment)
     #[clap(long)]
     pub max_gas_unit_price: Option<Felt>,
+
+    /// Refuse to send the transaction if its max fee exceeds this value
+    #[clap(long)]
+    pub max_fee_cap: Option<Felt>,
+
+    /// Multiplier of the estimated fee. For STRK fee payment it is applied to both the estimated gas amount and gas unit price [default: 1.1]
+    #[clap(long)]
+    pub fee_multiplier: Option<FeeMultiplier>,
 }
 
 impl From<ScriptFeeSettings> for FeeArgs {
@@ -33,6 +49,8 @@ impl From<ScriptFeeSettings> for FeeArgs {
                 max_fee,
                 max_gas: None,
                 max_gas_unit_price: None,
+                max_fee_cap: None,
+                fee_multiplier: None,
             },
             ScriptFeeSettings::Strk {
                 max_fee,
@@ -43,6 +61,8 @@ impl From<ScriptFeeSettings> for FeeArgs {
                 max_fee,
                 max_gas: max_gas.map(Into::into),
                 max_gas_unit_price: max_gas_unit_price.map(Into::into),
+                max_fee_cap: None,
+                fee_multiplier: None,
             },
         }
     }
@@ -57,83 +77,137 @@ impl FeeArgs {
         }
     }
 
+    /// Fills the fee options which were not passed with the ones from the profile
+    #[must_use]
+    pub fn with_config_defaults(self, config: &CastConfig) -> Self {
+        Self {
+            max_fee_cap: self.max_fee_cap.or(config.max_fee_cap),
+            fee_multiplier: self.fee_multiplier.or(config.fee_multiplier),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn fee_limits(&self) -> FeeLimits {
+        FeeLimits {
+            fee_multiplier: self.fee_multiplier.unwrap_or_default(),
+            max_fee_cap: self.max_fee_cap,
+        }
+    }
+
     pub async fn try_into_fee_settings<P: Provider>(
         &self,
         provider: P,
         block_id: BlockId,
     ) -> Result<FeeSettings> {
-        match self.fee_token.clone().unwrap_or_else(|| unreachable!()) {
-            FeeToken::Eth => {
-                ensure!(
-                    self.max_gas.is_none(),
-                    "--max-gas is not supported for ETH fee payment"
-                );
-                ensure!(
-                    self.max_gas_unit_price.is_none(),
-                    "--max-gas-unit-price is not supported for ETH fee payment"
-                );
-                Ok(FeeSettings::Eth {
-                    max_fee: self.max_fee,
-                })
-            }
-            FeeToken::Strk => {
-                let settings = match (self.max_fee, self.max_gas, self.max_gas_unit_price) {
-                    (Some(_), Some(_), Some(_)) => {
-                        bail!("Passing all --max-fee, --max-gas and --max-gas-unit-price is conflicting. Please pass only two of them or less")
-                    }
-                    (Some(max_fee), Some(max_gas), None) if max_fee < max_gas => {
-                        bail!("--max-fee should be greater than or equal to --max-gas amount")
-                    }
-                    (Some(max_fee), None, Some(max_gas_unit_price))
-                        if max_fee < max_gas_unit_price =>
-                    {
-                        bail!("--max-fee should be greater than or equal to --max-gas-unit-price")
-                    }
-                    (None, _, _) => FeeSettings::Strk {
-                        max_gas: self.max_gas.map(TryIntoConv::try_into_).transpose()?,
-                        max_gas_unit_price: self
-                            .max_gas_unit_price
-                            .map(TryIntoConv::try_into_)
-                            .transpose()?,
-                    },
-                    (Some(max_fee), None, Some(max_gas_unit_price)) => FeeSettings::Strk {
+        build_fee_settings(
+            &self.fee_token.clone().unwrap_or_else(|| unreachable!()),
+            self.max_fee,
+            ResourceBoundsArgs {
+                max_gas: self.max_gas,
+                max_gas_unit_price: self.max_gas_unit_price,
+            },
+            provider,
+            block_id,
+        )
+        .await
+    }
+}
+
+/// Resource bounds of a v3 transaction passed by the user, missing ones are estimated
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ResourceBoundsArgs {
+    pub max_gas: Option<Felt>,
+    pub max_gas_unit_price: Option<Felt>,
+}
+
+/// Validates the combination of fee options and builds settings of a transaction paid in `fee_token`.
+/// ETH is paid in v1 (v2 for declare) transactions, STRK in v3 transactions with resource bounds.
+pub async fn build_fee_settings<P: Provider>(
+    fee_token: &FeeToken,
+    max_fee: Option<Felt>,
+    resource_bounds: ResourceBoundsArgs,
+    provider: P,
+    block_id: BlockId,
+) -> Result<FeeSettings> {
+    let ResourceBoundsArgs {
+        max_gas,
+        max_gas_unit_price,
+    } = resource_bounds;
+
+    match fee_token {
+        FeeToken::Eth => {
+            ensure!(
+                max_gas.is_none(),
+                "--max-gas is not supported for ETH fee payment"
+            );
+            ensure!(
+                max_gas_unit_price.is_none(),
+                "--max-gas-unit-price is not supported for ETH fee payment"
+            );
+            Ok(FeeSettings::Eth { max_fee })
+        }
+        FeeToken::Strk => {
+            ensure!(
+                max_gas != Some(Felt::ZERO),
+                "--max-gas should be greater than 0"
+            );
+            ensure!(
+                max_gas_unit_price != Some(Felt::ZERO),
+                "--max-gas-unit-price should be greater than 0"
+            );
+
+            let settings = match (max_fee, max_gas, max_gas_unit_price) {
+                (Some(_), Some(_), Some(_)) => {
+                    bail!("Passing all --max-fee, --max-gas and --max-gas-unit-price is conflicting. Please pass only two of them or less")
+                }
+                (Some(max_fee), Some(max_gas), None) if max_fee < max_gas => {
+                    bail!("--max-fee should be greater than or equal to --max-gas amount")
+                }
+                (Some(max_fee), None, Some(max_gas_unit_price)) if max_fee < max_gas_unit_price => {
+                    bail!("--max-fee should be greater than or equal to --max-gas-unit-price")
+                }
+                (None, _, _) => FeeSettings::Strk {
+                    max_gas: max_gas.map(TryIntoConv::try_into_).transpose()?,
+                    max_gas_unit_price: max_gas_unit_price
+                        .map(TryIntoConv::try_into_)
+                        .transpose()?,
+                },
+                (Some(max_fee), None, Some(max_gas_unit_price)) => FeeSettings::Strk {
+                    max_gas: Some(
+                        max_fee
+                            .floor_div(&NonZeroFelt::from_felt_unchecked(max_gas_unit_price))
+                            .try_into_()?,
+                    ),
+                    max_gas_unit_price: Some(max_gas_unit_price.try_into_()?),
+                },
+                (Some(max_fee), Some(max_gas), None) => FeeSettings::Strk {
+                    max_gas: Some(max_gas.try_into_()?),
+                    max_gas_unit_price: Some(
+                        max_fee
+                            .floor_div(&NonZeroFelt::from_felt_unchecked(max_gas))
+                            .try_into_()?,
+                    ),
+                },
+                (Some(max_fee), None, None) => {
+                    let max_gas_unit_price = provider
+                        .get_block_with_tx_hashes(block_id)
+                        .await?
+                        .l1_gas_price()
+                        .price_in_fri;
+
+                    FeeSettings::Strk {
                         max_gas: Some(
                             max_fee
                                 .floor_div(&NonZeroFelt::from_felt_unchecked(max_gas_unit_price))
                                 .try_into_()?,
                         ),
                         max_gas_unit_price: Some(max_gas_unit_price.try_into_()?),
-                    },
-                    (Some(max_fee), Some(max_gas), None) => FeeSettings::Strk {
-                        max_gas: Some(max_gas.try_into_()?),
-                        max_gas_unit_price: Some(
-                            max_fee
-                                .floor_div(&NonZeroFelt::from_felt_unchecked(max_gas))
-                                .try_into_()?,
-                        ),
-                    },
-                    (Some(max_fee), None, None) => {
-                        let max_gas_unit_price = provider
-                            .get_block_with_tx_hashes(block_id)
-                            .await?
-                            .l1_gas_price()
-                            .price_in_fri;
-
-                        FeeSettings::Strk {
-                            max_gas: Some(
-                                max_fee
-                                    .floor_div(&NonZeroFelt::from_felt_unchecked(
-                                        max_gas_unit_price,
-                                    ))
-                                    .try_into_()?,
-                            ),
-                            max_gas_unit_price: Some(max_gas_unit_price.try_into_()?),
-                        }
                     }
-                };
+                }
+            };
 
-                Ok(settings)
-            }
+            Ok(settings)
         }
     }
 }
@@ -158,7 +232,7 @@ pub enum ScriptFeeSettings {
     },
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FeeSettings {
     Eth {
         max_fee: Option<Felt>,
@@ -169,18 +243,255 @@ pub enum FeeSettings {
     },
 }
 
-impl From<ScriptFeeSettings> for FeeSettings {
-    fn from(value: ScriptFeeSettings) -> Self {
-        match value {
-            ScriptFeeSettings::Eth { max_fee } => FeeSettings::Eth { max_fee },
-            ScriptFeeSettings::Strk {
-                max_gas,
-                max_gas_unit_price,
-                ..
-            } => FeeSettings::Strk {
-                max_gas,
-                max_gas_unit_price,
-            },
+/// Multiplier of the estimated fee, kept as a decimal fraction so it is applied without precision loss
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeMultiplier {
+    numerator: u64,
+    decimals: u32,
+}
+
+impl FeeMultiplier {
+    const MAX_DECIMALS: usize = 18;
+
+    /// Leaves the fee unchanged, bumped to increase a max fee passed explicitly
+    pub const ONE: Self = Self {
+        numerator: 1,
+        decimals: 0,
+    };
+
+    fn denominator(self) -> BigUint {
+        BigUint::from(10_u32).pow(self.decimals)
+    }
+
+    /// `value` multiplied by the multiplier, rounded up
+    fn apply(self, value: &BigUint) -> BigUint {
+        let denominator = self.denominator();
+        (value * self.numerator + &denominator - 1_u32) / denominator
+    }
+
+    /// Multiplier increased 1.5 times for each of `retries`, to retry transactions rejected
+    /// because of an insufficient max fee
+    pub fn bumped(self, retries: u32) -> Result<Self> {
+        let mut multiplier = self;
+        for _ in 0..retries {
+            multiplier.numerator = multiplier.numerator.checked_mul(15).with_context(|| {
+                format!("Fee multiplier = {self} bumped {retries} times is too large")
+            })?;
+            multiplier.decimals += 1;
+
+            while multiplier.decimals > 0 && multiplier.numerator % 10 == 0 {
+                multiplier.numerator /= 10;
+                multiplier.decimals -= 1;
+            }
+        }
+
+        Ok(multiplier)
+    }
+
+    /// Max fee of a v1 (v2 for declare) transaction paying `estimated_fee`
+    pub fn max_fee(self, estimated_fee: Felt) -> Result<Felt> {
+        let max_fee = self.apply(&estimated_fee.to_biguint());
+        ensure!(
+            max_fee <= Felt::MAX.to_biguint(),
+            "Estimated fee = {estimated_fee} multiplied by --fee-multiplier = {self} does not fit in a felt"
+        );
+
+        Ok(Felt::from_bytes_be_slice(&max_fee.to_bytes_be()))
+    }
+
+    /// Max gas amount and max gas unit price of a v3 transaction paying `estimated_fee` with `gas_price`
+    pub fn resource_bounds(self, estimated_fee: Felt, gas_price: Felt) -> Result<(u64, u128)> {
+        ensure!(
+            gas_price != Felt::ZERO,
+            "Estimated gas unit price is 0, pass --max-gas and --max-gas-unit-price explicitly"
+        );
+        let gas_price = gas_price.to_biguint();
+        let gas = (estimated_fee.to_biguint() + &gas_price - 1_u32) / &gas_price;
+
+        let max_gas = self.apply(&gas);
+        let max_gas_unit_price = self.apply(&gas_price);
+
+        Ok((
+            max_gas.to_u64().with_context(|| {
+                format!("Max gas = {max_gas}, from the estimated gas multiplied by --fee-multiplier = {self}, exceeds the maximum of {}", u64::MAX)
+            })?,
+            max_gas_unit_price.to_u128().with_context(|| {
+                format!("Max gas unit price = {max_gas_unit_price}, from the estimated gas unit price multiplied by --fee-multiplier = {self}, exceeds the maximum of {}", u128::MAX)
+            })?,
+        ))
+    }
+}
+
+impl Default for FeeMultiplier {
+    fn default() -> Self {
+        Self {
+            numerator: 11,
+            decimals: 1,
+        }
+    }
+}
+
+impl FromStr for FeeMultiplier {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+        let fraction = fraction.trim_end_matches('0');
+        ensure!(
+            !integer.is_empty()
+                && integer
+                    .chars()
+                    .chain(fraction.chars())
+                    .all(|char| char.is_ascii_digit()),
+            "Invalid fee multiplier `{value}`, expected a decimal number, e.g. 1.5"
+        );
+        ensure!(
+            fraction.len() <= Self::MAX_DECIMALS,
+            "Fee multiplier `{value}` has more than {} decimal places",
+            Self::MAX_DECIMALS
+        );
+
+        let numerator = format!("{integer}{fraction}")
+            .parse()
+            .with_context(|| format!("Fee multiplier `{value}` is too large"))?;
+        let multiplier = Self {
+            numerator,
+            decimals: u32::try_from(fraction.len()).unwrap(),
+        };
+        ensure!(
+            BigUint::from(multiplier.numerator) >= multiplier.denominator(),
+            "Fee multiplier should be greater than or equal to 1, got {value}"
+        );
+
+        Ok(multiplier)
+    }
+}
+
+impl Display for FeeMultiplier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let denominator = 10_u64.pow(self.decimals);
+        let integer = self.numerator / denominator;
+        if self.decimals == 0 {
+            return write!(f, "{integer}");
+        }
+
+        let fraction = self.numerator % denominator;
+        write!(
+            f,
+            "{integer}.{fraction:0width$}",
+            width = self.decimals as usize
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for FeeMultiplier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = match serde_json::Value::deserialize(deserializer)? {
+            serde_json::Value::Number(number) => number.to_string(),
+            serde_json::Value::String(string) => string,
+            other => {
+                return Err(D::Error::custom(format!(
+                    "invalid fee multiplier {other}, expected a decimal number"
+                )))
+            }
+        };
+
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for FeeMultiplier {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// How the fee of a transaction is derived from its estimate and the highest fee it may have
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FeeLimits {
+    pub fee_multiplier: FeeMultiplier,
+    pub max_fee_cap: Option<Felt>,
+}
+
+impl FeeLimits {
+    /// Max fee of a v1 (v2 for declare) transaction, `max_fee` or the estimated fee multiplied by the fee multiplier.
+    /// The fee is estimated with `estimate_fee` only if `max_fee` is not passed, fails if the max fee exceeds the cap
+    pub async fn max_fee<E, F, Fut>(
+        &self,
+        max_fee: Option<Felt>,
+        estimate_fee: F,
+    ) -> Result<Felt, E>
+    where
+        E: From<anyhow::Error>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<FeeEstimate, E>>,
+    {
+        let (max_fee, estimated_fee) = match max_fee {
+            Some(max_fee) => (max_fee, None),
+            None => {
+                let estimated_fee = estimate_fee().await?.overall_fee;
+                (
+                    self.fee_multiplier.max_fee(estimated_fee)?,
+                    Some(estimated_fee),
+                )
+            }
+        };
+        self.check_max_fee_cap(max_fee, estimated_fee)?;
+
+        Ok(max_fee)
+    }
+
+    /// Max gas amount and max gas unit price of a v3 transaction, the ones not passed are estimated with
+    /// `estimate_fee` and multiplied by the fee multiplier. Fails if the max fee they allow exceeds the cap
+    pub async fn resource_bounds<E, F, Fut>(
+        &self,
+        max_gas: Option<u64>,
+        max_gas_unit_price: Option<u128>,
+        estimate_fee: F,
+    ) -> Result<(u64, u128), E>
+    where
+        E: From<anyhow::Error>,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<FeeEstimate, E>>,
+    {
+        let (max_gas, max_gas_unit_price, estimated_fee) = match (max_gas, max_gas_unit_price) {
+            (Some(max_gas), Some(max_gas_unit_price)) => (max_gas, max_gas_unit_price, None),
+            _ => {
+                let estimate = estimate_fee().await?;
+                let (estimated_gas, estimated_gas_unit_price) = self
+                    .fee_multiplier
+                    .resource_bounds(estimate.overall_fee, estimate.gas_price)?;
+                (
+                    max_gas.unwrap_or(estimated_gas),
+                    max_gas_unit_price.unwrap_or(estimated_gas_unit_price),
+                    Some(estimate.overall_fee),
+                )
+            }
+        };
+        self.check_max_fee_cap(
+            Felt::from(max_gas) * Felt::from(max_gas_unit_price),
+            estimated_fee,
+        )?;
+
+        Ok((max_gas, max_gas_unit_price))
+    }
+
+    fn check_max_fee_cap(&self, max_fee: Felt, estimated_fee: Option<Felt>) -> Result<()> {
+        let Some(max_fee_cap) = self.max_fee_cap else {
+            return Ok(());
+        };
+        if max_fee <= max_fee_cap {
+            return Ok(());
+        }
+
+        match estimated_fee {
+            None => bail!(
+                "Max fee = {max_fee} exceeds --max-fee-cap = {max_fee_cap}, the transaction was not sent"
+            ),
+            Some(estimated_fee) => bail!(
+                "Max fee = {max_fee}, from the estimated fee = {estimated_fee} and --fee-multiplier = {}, exceeds --max-fee-cap = {max_fee_cap}, the transaction was not sent",
+                self.fee_multiplier
+            ),
         }
     }
 }
@@ -225,3 +536,162 @@ macro_rules! impl_payable_transaction {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multiplier(value: &str) -> FeeMultiplier {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_multipliers() {
+        assert_eq!(FeeMultiplier::default(), multiplier("1.1"));
+        assert_eq!(multiplier("1.50"), multiplier("1.5"));
+        assert_eq!(multiplier("1.50").to_string(), "1.5");
+        assert_eq!(multiplier("2.").to_string(), "2");
+        assert_eq!(multiplier("1.05").to_string(), "1.05");
+        assert_eq!(
+            multiplier("1.000000000000000001").to_string(),
+            "1.000000000000000001"
+        );
+
+        for invalid in [
+            "",
+            ".5",
+            "-1",
+            "1,5",
+            "1e3",
+            "0.99",
+            "1.0000000000000000001",
+            "99999999999999999999",
+        ] {
+            assert!(
+                invalid.parse::<FeeMultiplier>().is_err(),
+                "{invalid} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn bumped_multipliers() {
+        assert_eq!(multiplier("1.1").bumped(0).unwrap(), multiplier("1.1"));
+        assert_eq!(multiplier("1.1").bumped(1).unwrap(), multiplier("1.65"));
+        assert_eq!(multiplier("1.1").bumped(2).unwrap(), multiplier("2.475"));
+        assert_eq!(multiplier("2").bumped(1).unwrap().to_string(), "3");
+        assert!(multiplier("18446744073709551615").bumped(1).is_err());
+    }
+
+    #[test]
+    fn max_fee_is_rounded_up() {
+        assert_eq!(
+            multiplier("1.1").max_fee(Felt::from(10)).unwrap(),
+            Felt::from(11)
+        );
+        assert_eq!(multiplier("1.1").max_fee(Felt::ONE).unwrap(), Felt::TWO);
+        assert_eq!(multiplier("1").max_fee(Felt::ZERO).unwrap(), Felt::ZERO);
+    }
+
+    #[test]
+    fn max_fee_without_precision_loss() {
+        // Not representable as f64
+        let estimated_fee = Felt::from_dec_str("1000000000000000000000000000001").unwrap();
+
+        assert_eq!(
+            multiplier("1.1").max_fee(estimated_fee).unwrap(),
+            Felt::from_dec_str("1100000000000000000000000000002").unwrap()
+        );
+        assert_eq!(
+            multiplier("1.000000000000000001")
+                .max_fee(Felt::from(u128::MAX))
+                .unwrap(),
+            Felt::from_dec_str("340282366920938463803656974352706674919").unwrap()
+        );
+    }
+
+    #[test]
+    fn max_fee_overflowing_felt() {
+        assert_eq!(multiplier("1").max_fee(Felt::MAX).unwrap(), Felt::MAX);
+        assert!(multiplier("1.1").max_fee(Felt::MAX).is_err());
+        assert!(multiplier("18446744073709551615")
+            .max_fee(Felt::from_hex(&format!("0x1{}", "0".repeat(50))).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn resource_bounds() {
+        assert_eq!(
+            multiplier("1.5")
+                .resource_bounds(Felt::from(1000), Felt::from(3))
+                .unwrap(),
+            (501, 5)
+        );
+        assert_eq!(
+            multiplier("1")
+                .resource_bounds(
+                    Felt::from(u64::MAX) * Felt::from(u128::MAX),
+                    Felt::from(u128::MAX)
+                )
+                .unwrap(),
+            (u64::MAX, u128::MAX)
+        );
+    }
+
+    #[test]
+    fn resource_bounds_out_of_range() {
+        assert!(multiplier("1")
+            .resource_bounds(Felt::from(1000), Felt::ZERO)
+            .is_err());
+        assert!(multiplier("1.1")
+            .resource_bounds(Felt::from(u64::MAX), Felt::ONE)
+            .is_err());
+        assert!(multiplier("1.1")
+            .resource_bounds(Felt::from(u128::MAX), Felt::from(u128::MAX))
+            .is_err());
+    }
+
+    async fn no_estimate() -> Result<FeeEstimate> {
+        unreachable!("Fee is not estimated when it is passed")
+    }
+
+    #[tokio::test]
+    async fn max_fee_cap() {
+        let fee_limits = FeeLimits {
+            fee_multiplier: multiplier("1.1"),
+            max_fee_cap: Some(Felt::from(1000)),
+        };
+
+        assert_eq!(
+            fee_limits
+                .max_fee(Some(Felt::from(1000)), no_estimate)
+                .await
+                .unwrap(),
+            Felt::from(1000)
+        );
+        assert_eq!(
+            fee_limits
+                .resource_bounds(Some(10), Some(100), no_estimate)
+                .await
+                .unwrap(),
+            (10, 100)
+        );
+
+        let error = fee_limits
+            .max_fee(Some(Felt::from(1001)), no_estimate)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Max fee = 1001 exceeds --max-fee-cap = 1000, the transaction was not sent"
+        );
+        let error = fee_limits
+            .resource_bounds(Some(11), Some(100), no_estimate)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Max fee = 1100 exceeds --max-fee-cap = 1000, the transaction was not sent"
+        );
+    }
+}
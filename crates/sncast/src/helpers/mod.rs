@@ -1,7 +1,12 @@
+pub mod abi;
+pub mod account_source;
+pub mod address_book;
 pub mod block_explorer;
 pub mod braavos;
+pub mod clock;
 pub mod configuration;
 pub mod constants;
+pub mod contract_address;
 pub mod error;
 pub mod fee;
 pub mod rpc;
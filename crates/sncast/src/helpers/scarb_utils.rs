@@ -1,12 +1,18 @@
+use crate::response::errors::StarknetCommandError;
+use crate::ErrorData;
 use anyhow::{anyhow, Context, Result};
 use camino::{Utf8Path, Utf8PathBuf};
+use itertools::Itertools;
 use scarb_api::{
-    get_contracts_artifacts_and_source_sierra_paths,
-    metadata::{Metadata, MetadataCommand, PackageMetadata},
+    get_contracts_sierra_and_source_sierra_paths, get_package_contracts_sierra_in_workspace,
+    metadata::{cached_metadata, Metadata, MetadataCommand, PackageMetadata},
     ScarbCommand, ScarbCommandError, StarknetContractArtifacts,
 };
 use scarb_ui::args::PackagesFilter;
-use shared::{command::CommandExt, print::print_as_warning};
+use shared::{
+    command::CommandExt,
+    print::{emit_warning, is_quiet, DiagnosticCode},
+};
 use std::collections::HashMap;
 use std::env;
 use std::str::FromStr;
@@ -54,14 +60,18 @@ fn execute_scarb_metadata_command(command: &MetadataCommand) -> Result<Metadata>
 }
 
 pub fn get_scarb_metadata(manifest_path: &Utf8PathBuf) -> Result<Metadata> {
-    let mut command = get_scarb_metadata_command(manifest_path)?;
-    let command = command.no_deps();
-    execute_scarb_metadata_command(command)
+    cached_metadata(manifest_path, true, || {
+        let mut command = get_scarb_metadata_command(manifest_path)?;
+        let command = command.no_deps();
+        execute_scarb_metadata_command(command)
+    })
 }
 
 pub fn get_scarb_metadata_with_deps(manifest_path: &Utf8PathBuf) -> Result<Metadata> {
-    let command = get_scarb_metadata_command(manifest_path)?;
-    execute_scarb_metadata_command(&command)
+    cached_metadata(manifest_path, false, || {
+        let command = get_scarb_metadata_command(manifest_path)?;
+        execute_scarb_metadata_command(&command)
+    })
 }
 
 pub fn get_cairo_version(manifest_path: &Utf8PathBuf) -> Result<String> {
@@ -147,9 +157,15 @@ pub fn build(
     if config.json {
         cmd.json();
     }
+    if is_quiet() {
+        cmd.quiet();
+    }
     cmd.run()
 }
 
+/// Sierra of all contracts of the package is loaded, contracts are not filtered with a `ContractsFilter`
+/// as `declare` falls back to a name differing in letter case and scripts can declare any contract.
+/// Only the declared contracts are compiled to CASM, so loading the others is cheap
 pub fn build_and_load_artifacts(
     package: &PackageMetadata,
     config: &BuildConfig,
@@ -161,36 +177,133 @@ pub fn build_and_load_artifacts(
         .map_err(|e| anyhow!(format!("Failed to build using scarb; {e}")))?;
 
     let metadata = get_scarb_metadata_with_deps(&config.scarb_toml_path)?;
-    if metadata.profiles.contains(&config.profile) {
-        Ok(get_contracts_artifacts_and_source_sierra_paths(
-            &metadata,
-            &package.id,
-            Some(&config.profile),
-            false,
-        )?
-        .into_iter()
-        .map(|(name, (artifacts, _))| (name, artifacts))
-        .collect())
+    let profile = if metadata.profiles.contains(&config.profile) {
+        &config.profile
     } else {
         let profile = &config.profile;
-        print_as_warning(&anyhow!(
-            "Profile {profile} does not exist in scarb, using '{default_profile}' profile."
-        ));
-        Ok(get_contracts_artifacts_and_source_sierra_paths(
+        emit_warning(
+            DiagnosticCode::MissingProfile,
+            format!(
+                "Profile {profile} does not exist in scarb, using '{default_profile}' profile."
+            ),
+        );
+        default_profile
+    };
+
+    if build_for_script {
+        // Scripts do not have to define any contracts
+        return Ok(get_contracts_sierra_and_source_sierra_paths(
             &metadata,
             &package.id,
-            Some(default_profile),
+            Some(profile),
             false,
         )?
         .into_iter()
         .map(|(name, (artifacts, _))| (name, artifacts))
-        .collect())
+        .collect());
+    }
+
+    get_package_contracts_sierra_in_workspace(&metadata, &package.id, Some(profile))
+}
+
+/// Returns artifacts of the contract. If there are none, falls back with a warning
+/// to the only contract which name differs just in letter case, e.g. `erc20` and `ERC20`
+pub fn get_contract_artifacts<'a>(
+    artifacts: &'a HashMap<String, StarknetContractArtifacts>,
+    contract_name: &str,
+) -> Result<&'a StarknetContractArtifacts, StarknetCommandError> {
+    if let Some(contract_artifacts) = artifacts.get(contract_name) {
+        return Ok(contract_artifacts);
+    }
+
+    let mut matches: Vec<_> = artifacts
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case(contract_name))
+        .collect();
+    matches.sort_by_key(|(name, _)| name.as_str());
+
+    match matches.as_slice() {
+        [] => Err(StarknetCommandError::ContractArtifactsNotFound(
+            ErrorData::new(contract_name.to_string()),
+        )),
+        [(name, contract_artifacts)] => {
+            emit_warning(
+                DiagnosticCode::ContractNameCaseMismatch,
+                format!("Contract {contract_name} not found, using {name} which differs only in letter case"),
+            );
+            Ok(contract_artifacts)
+        }
+        _ => {
+            let names = matches.iter().map(|(name, _)| name.as_str()).join(", ");
+            Err(StarknetCommandError::UnknownError(anyhow!(
+                "Contract {contract_name} not found, multiple contracts differ from it only in letter case: {names}. Please use the exact name"
+            )))
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::get_contract_artifacts;
     use crate::helpers::scarb_utils::{get_package_metadata, get_scarb_metadata};
+    use crate::response::errors::StarknetCommandError;
+    use scarb_api::StarknetContractArtifacts;
+    use std::collections::HashMap;
+
+    fn artifacts_of(names: &[&str]) -> HashMap<String, StarknetContractArtifacts> {
+        names
+            .iter()
+            .map(|name| {
+                let artifacts = StarknetContractArtifacts::new(
+                    format!("{name} sierra"),
+                    format!("{name} casm"),
+                );
+                ((*name).to_string(), artifacts)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_get_contract_artifacts_exact_match() {
+        let artifacts = artifacts_of(&["ERC20", "erc20"]);
+
+        let contract_artifacts = get_contract_artifacts(&artifacts, "erc20").unwrap();
+
+        assert_eq!(contract_artifacts.sierra, "erc20 sierra");
+    }
+
+    #[test]
+    fn test_get_contract_artifacts_case_insensitive_match() {
+        let artifacts = artifacts_of(&["ERC20", "Map"]);
+
+        let contract_artifacts = get_contract_artifacts(&artifacts, "erc20").unwrap();
+
+        assert_eq!(contract_artifacts.sierra, "ERC20 sierra");
+    }
+
+    #[test]
+    fn test_get_contract_artifacts_ambiguous_match() {
+        let artifacts = artifacts_of(&["ERC20", "Erc20"]);
+
+        let err = get_contract_artifacts(&artifacts, "erc20").unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Contract erc20 not found, multiple contracts differ from it only in letter case: ERC20, Erc20. Please use the exact name"
+        );
+    }
+
+    #[test]
+    fn test_get_contract_artifacts_not_found() {
+        let artifacts = artifacts_of(&["Map"]);
+
+        let err = get_contract_artifacts(&artifacts, "erc20").unwrap_err();
+
+        assert!(matches!(
+            err,
+            StarknetCommandError::ContractArtifactsNotFound(_)
+        ));
+    }
 
     #[test]
     fn test_get_scarb_metadata() {
@@ -1,21 +1,27 @@
-use crate::helpers::constants::{DEFAULT_STATE_FILE_SUFFIX, WAIT_RETRY_INTERVAL, WAIT_TIMEOUT};
+use crate::helpers::clock::{Clock, SystemClock};
+use crate::helpers::constants::{
+    DEFAULT_STATE_FILE_SUFFIX, MAX_RATE_LIMITED_WAIT_INTERVAL, WAIT_INTERVAL_JITTER,
+    WAIT_RETRY_INTERVAL, WAIT_TIMEOUT,
+};
 use crate::response::errors::SNCastProviderError;
 use anyhow::{anyhow, bail, Context, Error, Result};
 use camino::Utf8PathBuf;
 use clap::ValueEnum;
+use conversions::serde::deserialize::CairoDeserialize;
 use conversions::serde::serialize::CairoSerialize;
 use helpers::constants::{KEYSTORE_PASSWORD_ENV_VAR, UDC_ADDRESS};
 use rand::rngs::OsRng;
-use rand::RngCore;
+use rand::{Rng, RngCore};
 use response::errors::SNCastStarknetError;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{Deserializer, Value};
+use shared::block_id::BlockIdArg;
 use shared::rpc::create_rpc_client;
 use starknet::accounts::{AccountFactory, AccountFactoryError};
 use starknet::core::types::{
     BlockId, BlockTag,
-    BlockTag::{Latest, Pending},
+    BlockTag::Pending,
     ContractClass, ContractErrorData, Felt,
     StarknetError::{ClassHashNotFound, ContractNotFound, TransactionHashNotFound},
 };
@@ -31,7 +37,6 @@ use starknet::{
     signers::{LocalWallet, SigningKey},
 };
 use std::str::FromStr;
-use std::thread::sleep;
 use std::time::Duration;
 use std::{collections::HashMap, fmt::Display};
 use std::{env, fs};
@@ -149,6 +154,14 @@ pub struct ValidatedWaitParams {
         rename(serialize = "retry-interval", deserialize = "retry-interval")
     )]
     retry_interval: u8,
+
+    /// Overrides `retry_interval` with a millisecond precision, set with `--wait-interval`
+    #[serde(skip)]
+    interval_ms: Option<u64>,
+
+    /// Wait until the transaction is accepted on L1 instead of L2, set by scripts per transaction
+    #[serde(skip)]
+    until_accepted_on_l1: bool,
 }
 
 impl ValidatedWaitParams {
@@ -162,17 +175,43 @@ impl ValidatedWaitParams {
         Self {
             timeout,
             retry_interval,
+            interval_ms: None,
+            until_accepted_on_l1: false,
         }
     }
 
     #[must_use]
-    pub fn get_retries(&self) -> u16 {
-        self.timeout / u16::from(self.retry_interval)
+    pub fn with_interval_ms(self, interval_ms: u64) -> Self {
+        assert!(
+            !(interval_ms == 0 || interval_ms > u64::from(self.timeout) * 1000),
+            "Invalid values for wait interval and/or timeout!"
+        );
+
+        Self {
+            interval_ms: Some(interval_ms),
+            ..self
+        }
+    }
+
+    /// Same parameters with the timeout in seconds replaced, which must not be shorter than the interval
+    pub fn with_timeout(self, timeout: u16) -> Result<Self> {
+        let interval = self.get_interval();
+        if timeout == 0 || interval > Duration::from_secs(timeout.into()) {
+            bail!(
+                "Invalid wait timeout = {timeout}s, it must not be shorter than the retry interval = {}s",
+                interval.as_secs_f64()
+            );
+        }
+
+        Ok(Self { timeout, ..self })
     }
 
     #[must_use]
-    pub fn remaining_time(&self, steps_done: u16) -> u16 {
-        steps_done * u16::from(self.retry_interval)
+    pub fn until_accepted_on_l1(self) -> Self {
+        Self {
+            until_accepted_on_l1: true,
+            ..self
+        }
     }
 
     #[must_use]
@@ -180,12 +219,73 @@ impl ValidatedWaitParams {
         self.retry_interval
     }
 
+    /// Time between consecutive attempts to fetch the transaction
+    #[must_use]
+    pub fn get_interval(&self) -> Duration {
+        self.interval_ms.map_or_else(
+            || Duration::from_secs(self.retry_interval.into()),
+            Duration::from_millis,
+        )
+    }
+
     #[must_use]
     pub fn get_timeout(&self) -> u16 {
         self.timeout
     }
 }
 
+/// What a transaction sent by `invoke_with_wait` or `deploy_with_wait` of a script is awaited for
+#[derive(CairoDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum ScriptWaitFor {
+    None,
+    Receipt,
+    AcceptedOnL1,
+}
+
+/// Wait configuration of a single transaction sent by a script
+#[derive(CairoDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct ScriptWaitConfig {
+    pub wait_for: ScriptWaitFor,
+    /// Timeout in seconds, the one from the profile is used if it is not set
+    pub timeout: Option<u16>,
+}
+
+impl WaitForTx {
+    /// Waiting for a transaction sent by a script. Transactions without `wait_config` are awaited
+    /// until they are accepted on L2, with `wait_params` from the profile
+    pub fn for_script(
+        wait_params: ValidatedWaitParams,
+        wait_config: Option<ScriptWaitConfig>,
+    ) -> Result<Self> {
+        let Some(ScriptWaitConfig { wait_for, timeout }) = wait_config else {
+            return Ok(Self {
+                wait: true,
+                wait_params,
+            });
+        };
+
+        let wait_params = match timeout {
+            Some(timeout) => wait_params.with_timeout(timeout)?,
+            None => wait_params,
+        };
+
+        Ok(match wait_for {
+            ScriptWaitFor::None => Self {
+                wait: false,
+                wait_params,
+            },
+            ScriptWaitFor::Receipt => Self {
+                wait: true,
+                wait_params,
+            },
+            ScriptWaitFor::AcceptedOnL1 => Self {
+                wait: true,
+                wait_params: wait_params.until_accepted_on_l1(),
+            },
+        })
+    }
+}
+
 impl Default for ValidatedWaitParams {
     fn default() -> Self {
         Self::new(WAIT_RETRY_INTERVAL, WAIT_TIMEOUT)
@@ -516,17 +616,7 @@ fn map_encoding(legacy: bool) -> ExecutionEncoding {
 }
 
 pub fn get_block_id(value: &str) -> Result<BlockId> {
-    match value {
-        "pending" => Ok(BlockId::Tag(Pending)),
-        "latest" => Ok(BlockId::Tag(Latest)),
-        _ if value.starts_with("0x") => Ok(BlockId::Hash(Felt::from_hex(value)?)),
-        _ => match value.parse::<u64>() {
-            Ok(value) => Ok(BlockId::Number(value)),
-            Err(_) => Err(anyhow::anyhow!(
-                "Incorrect value passed for block_id = {value}. Possible values are pending, latest, block hash (hex) and block number (u64)"
-            )),
-        },
-    }
+    value.parse::<BlockIdArg>().map(Into::into)
 }
 
 #[derive(Debug, CairoSerialize)]
@@ -572,16 +662,39 @@ pub async fn wait_for_tx(
     tx_hash: Felt,
     wait_params: ValidatedWaitParams,
 ) -> Result<&str, WaitForTransactionError> {
+    wait_for_tx_with_clock(provider, tx_hash, wait_params, &SystemClock).await
+}
+
+/// Same as [`wait_for_tx`], with the time of the timeout and the sleeps between attempts taken from `clock`
+pub async fn wait_for_tx_with_clock<'a>(
+    provider: &'a JsonRpcClient<HttpTransport>,
+    tx_hash: Felt,
+    wait_params: ValidatedWaitParams,
+    clock: &impl Clock,
+) -> Result<&'a str, WaitForTransactionError> {
     println!("Transaction hash = {tx_hash:#x}");
 
-    let retries = wait_params.get_retries();
-    for i in (1..retries).rev() {
+    let deadline = clock.now() + Duration::from_secs(wait_params.get_timeout().into());
+    let mut interval = wait_params.get_interval();
+
+    loop {
         match provider.get_transaction_status(tx_hash).await {
             Ok(starknet::core::types::TransactionStatus::Rejected) => {
                 return Err(WaitForTransactionError::TransactionError(
                     TransactionError::Rejected,
                 ));
             }
+            // Reverted transactions are not awaited on L1, their status won't change anymore
+            Ok(starknet::core::types::TransactionStatus::AcceptedOnL2(
+                starknet::core::types::TransactionExecutionStatus::Succeeded,
+            )) if wait_params.until_accepted_on_l1 => {
+                interval = wait_params.get_interval();
+                let remaining_time = deadline.saturating_duration_since(clock.now());
+                println!(
+                    "Waiting for transaction to be accepted on L1 ({}s left until timeout)",
+                    remaining_time.as_secs()
+                );
+            }
             Ok(
                 starknet::core::types::TransactionStatus::AcceptedOnL2(execution_status)
                 | starknet::core::types::TransactionStatus::AcceptedOnL1(execution_status),
@@ -595,20 +708,35 @@ pub async fn wait_for_tx(
             },
             Ok(starknet::core::types::TransactionStatus::Received)
             | Err(StarknetError(TransactionHashNotFound)) => {
-                let remaining_time = wait_params.remaining_time(i);
-                println!("Waiting for transaction to be accepted ({i} retries / {remaining_time}s left until timeout)");
+                interval = wait_params.get_interval();
+                let remaining_time = deadline.saturating_duration_since(clock.now());
+                println!(
+                    "Waiting for transaction to be accepted ({}s left until timeout)",
+                    remaining_time.as_secs()
+                );
             }
             Err(ProviderError::RateLimited) => {
-                println!("Request rate limited while waiting for transaction to be accepted");
-                sleep(Duration::from_secs(wait_params.get_retry_interval().into()));
+                interval = (interval * 2).min(MAX_RATE_LIMITED_WAIT_INTERVAL);
+                println!(
+                    "Request rate limited while waiting for transaction to be accepted, retrying in {}s",
+                    interval.as_secs()
+                );
             }
             Err(err) => return Err(WaitForTransactionError::ProviderError(err.into())),
         };
 
-        sleep(Duration::from_secs(wait_params.get_retry_interval().into()));
+        let delay = with_jitter(interval);
+        if clock.now() + delay >= deadline {
+            return Err(WaitForTransactionError::TimedOut);
+        }
+        clock.sleep(delay).await;
     }
+}
 
-    Err(WaitForTransactionError::TimedOut)
+/// Lengthens the interval by a random fraction of it, so processes waiting for transactions
+/// at the same time do not poll the node in lockstep
+fn with_jitter(interval: Duration) -> Duration {
+    interval.mul_f64(1.0 + OsRng.gen_range(0.0..WAIT_INTERVAL_JITTER))
 }
 
 async fn get_revert_reason(
@@ -732,7 +860,8 @@ mod tests {
     use crate::helpers::constants::KEYSTORE_PASSWORD_ENV_VAR;
     use crate::{
         chain_id_to_network_name, extract_or_generate_salt, get_account_data_from_accounts_file,
-        get_account_data_from_keystore, get_block_id, udc_uniqueness, AccountType,
+        get_account_data_from_keystore, get_block_id, udc_uniqueness, with_jitter, AccountType,
+        ValidatedWaitParams, WAIT_INTERVAL_JITTER,
     };
     use camino::Utf8PathBuf;
     use conversions::string::IntoHexStr;
@@ -744,6 +873,7 @@ mod tests {
     use starknet::core::utils::UdcUniqueSettings;
     use starknet::core::utils::UdcUniqueness::{NotUnique, Unique};
     use std::env;
+    use std::time::Duration;
 
     #[test]
     fn test_get_block_id() {
@@ -781,7 +911,7 @@ mod tests {
         let block = get_block_id("mariusz").unwrap_err();
         assert!(block
             .to_string()
-            .contains("Incorrect value passed for block_id = mariusz. Possible values are pending, latest, block hash (hex) and block number (u64)"));
+            .contains("Invalid block id = mariusz, the value is not recognized. Possible values are latest, pending, block number (decimal u64) and block hash (0x prefixed hex)"));
     }
 
     #[test]
@@ -919,4 +1049,75 @@ mod tests {
             .to_string()
             .contains("Account = user1 not found under network = CUSTOM_CHAIN_ID"));
     }
+
+    #[test]
+    fn test_wait_interval() {
+        let wait_params = ValidatedWaitParams::new(5, 60);
+        assert_eq!(wait_params.get_interval(), Duration::from_secs(5));
+
+        let wait_params = wait_params.with_interval_ms(250);
+        assert_eq!(wait_params.get_interval(), Duration::from_millis(250));
+        assert_eq!(wait_params.get_timeout(), 60);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid values for wait interval and/or timeout!")]
+    fn test_wait_interval_longer_than_timeout() {
+        let _ = ValidatedWaitParams::new(1, 2).with_interval_ms(2001);
+    }
+
+    #[test]
+    fn test_wait_timeout_shorter_than_interval() {
+        let wait_params = ValidatedWaitParams::new(5, 60);
+
+        assert_eq!(wait_params.with_timeout(10).unwrap().get_timeout(), 10);
+        assert_eq!(
+            wait_params.with_timeout(4).unwrap_err().to_string(),
+            "Invalid wait timeout = 4s, it must not be shorter than the retry interval = 5s"
+        );
+    }
+
+    #[test]
+    fn test_script_wait_config() {
+        let wait_params = ValidatedWaitParams::new(5, 60);
+
+        let default = WaitForTx::for_script(wait_params, None).unwrap();
+        assert!(default.wait);
+        assert_eq!(default.wait_params, wait_params);
+
+        let no_wait = WaitForTx::for_script(
+            wait_params,
+            Some(ScriptWaitConfig {
+                wait_for: ScriptWaitFor::None,
+                timeout: None,
+            }),
+        )
+        .unwrap();
+        assert!(!no_wait.wait);
+
+        let accepted_on_l1 = WaitForTx::for_script(
+            wait_params,
+            Some(ScriptWaitConfig {
+                wait_for: ScriptWaitFor::AcceptedOnL1,
+                timeout: Some(600),
+            }),
+        )
+        .unwrap();
+        assert!(accepted_on_l1.wait);
+        assert_eq!(
+            accepted_on_l1.wait_params,
+            ValidatedWaitParams::new(5, 600).until_accepted_on_l1()
+        );
+    }
+
+    #[test]
+    fn test_jitter_never_shortens_interval() {
+        let interval = Duration::from_secs(5);
+
+        for _ in 0..100 {
+            let jittered = with_jitter(interval);
+            assert!(interval <= jittered);
+            assert!(jittered <= interval.mul_f64(1.0 + WAIT_INTERVAL_JITTER));
+        }
+    }
 }
@@ -1,34 +1,50 @@
 use crate::starknet_commands::account::Account;
 use crate::starknet_commands::show_config::ShowConfig;
 use crate::starknet_commands::{
-    account, call::Call, declare::Declare, deploy::Deploy, invoke::Invoke, multicall::Multicall,
-    script::Script, tx_status::TxStatus,
+    account, call::Call, compute_address::ComputeAddress, declare::Declare,
+    declare_all::DeclareAll, decode::Decode, deploy::Deploy, deploy_plan::DeployPlan, devnet,
+    devnet::Devnet, events::Events, get_storage_at::GetStorageAt, invoke::Invoke, multicall,
+    multicall::Multicall, script::Script, tx_status::TxStatus, verify_class::VerifyClass,
 };
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
+use configuration::env_file::load_env_file;
 use configuration::load_global_config;
-use data_transformer::Calldata;
+use data_transformer::validate_constructor_calldata;
 use sncast::response::explorer_link::print_block_explorer_link_if_allowed;
 use sncast::response::print::{print_command_result, OutputFormat};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::{Parser, Subcommand};
+use shared::print::{set_collect_diagnostics, set_quiet};
+use sncast::helpers::abi::CalldataAbi;
+use sncast::helpers::account_source::{resolve_starkli_account, AccountSource};
+use sncast::helpers::address_book::save_to_address_book;
 use sncast::helpers::configuration::CastConfig;
-use sncast::helpers::constants::{DEFAULT_ACCOUNTS_FILE, DEFAULT_MULTICALL_CONTENTS};
-use sncast::helpers::fee::PayableTransaction;
+use sncast::helpers::constants::{
+    DEFAULT_ACCOUNTS_FILE, DEFAULT_MULTICALL_CONTENTS, DEFAULT_STARKLI_ACCOUNTS_DIR,
+    EXECUTION_LOG_FILE,
+};
+use sncast::helpers::fee::{FeeArgs, PayableTransaction};
 use sncast::helpers::scarb_utils::{
     assert_manifest_path_exists, build, build_and_load_artifacts, get_package_metadata,
     get_scarb_metadata_with_deps, BuildConfig,
 };
 use sncast::response::errors::handle_starknet_command_error;
+use sncast::response::structs::ClassHashComparison;
+use sncast::state::execution_log::ExecutionLog;
 use sncast::{
-    chain_id_to_network_name, get_account, get_block_id, get_chain_id, get_class_hash_by_address,
+    chain_id_to_network_name, get_account, get_chain_id, get_class_hash_by_address,
     get_contract_class, get_default_state_file_name, NumbersFormat, ValidatedWaitParams, WaitForTx,
 };
 use starknet::accounts::ConnectedAccount;
+use starknet::core::types::{BlockId, Felt};
 use starknet::core::utils::get_selector_from_name;
-use starknet::providers::Provider;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider};
 use starknet_commands::account::list::print_account_list;
 use starknet_commands::verify::Verify;
+use std::collections::HashMap;
+use std::env;
 use tokio::runtime::Runtime;
 
 mod starknet_commands;
@@ -85,6 +101,11 @@ struct Cli {
     #[clap(short, long)]
     keystore: Option<Utf8PathBuf>,
 
+    /// Where accounts are read from; with `starkli`, --account is a name of an account
+    /// in `~/.starknet_accounts/<name>/` or a path to starkli JSON account file
+    #[clap(long, value_enum)]
+    account_source: Option<AccountSource>,
+
     /// If passed, values will be displayed as integers
     #[clap(long, conflicts_with = "hex_format")]
     int_format: bool,
@@ -101,6 +122,10 @@ struct Cli {
     #[clap(short = 'w', long)]
     wait: bool,
 
+    /// If passed together with --wait, the full transaction receipt will be printed once the transaction is accepted
+    #[clap(long, requires = "wait")]
+    receipt: bool,
+
     /// Adjusts the time after which --wait assumes transaction was not received or rejected
     #[clap(long)]
     wait_timeout: Option<u16>,
@@ -109,6 +134,18 @@ struct Cli {
     #[clap(long)]
     wait_retry_interval: Option<u8>,
 
+    /// Adjusts the time between consecutive attempts to fetch transaction by --wait flag, in milliseconds
+    #[clap(long, value_name = "MS", conflicts_with = "wait_retry_interval")]
+    wait_interval: Option<u64>,
+
+    /// If passed, warnings and other non-error diagnostics will not be printed
+    #[clap(long, alias = "quiet")]
+    silent: bool,
+
+    /// Do not load environment variables from the `.env` file in the project root
+    #[clap(long)]
+    no_env_file: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -118,9 +155,15 @@ enum Commands {
     /// Declare a contract
     Declare(Declare),
 
+    /// Declare multiple contracts
+    DeclareAll(DeclareAll),
+
     /// Deploy a contract
     Deploy(Deploy),
 
+    /// Declare and deploy contracts listed in a deployment plan
+    DeployPlan(DeployPlan),
+
     /// Call a contract
     Call(Call),
 
@@ -142,12 +185,38 @@ enum Commands {
     /// Get the status of a transaction
     TxStatus(TxStatus),
 
+    /// Get events emitted by contracts
+    Events(Events),
+
+    /// Get the value of a storage slot of a contract
+    GetStorageAt(GetStorageAt),
+
     /// Verify a contract
     Verify(Verify),
+
+    /// Compare the class hash of a deployed contract with the class hash of a local contract
+    VerifyClass(VerifyClass),
+
+    /// Decode felts into a value of a type from the ABI
+    Decode(Decode),
+
+    /// Compute the address a contract will be deployed at
+    ComputeAddress(ComputeAddress),
+
+    /// Start and stop a local devnet instance
+    Devnet(Devnet),
 }
 
 fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+
+    if !cli.no_env_file {
+        load_env_file(&Utf8PathBuf::try_from(env::current_dir()?)?)?;
+    }
+
+    set_quiet(cli.silent);
+    // Diagnostics are added to JSON error responses
+    set_collect_diagnostics(cli.json);
 
     let numbers_format = NumbersFormat::from_flags(cli.hex_format, cli.int_format);
     let output_format = OutputFormat::from_flag(cli.json);
@@ -158,7 +227,10 @@ fn main() -> Result<()> {
         run_script_command(&cli, runtime, script, numbers_format, output_format)
     } else {
         let mut config = load_global_config::<CastConfig>(&None, &cli.profile)?;
-        update_cast_config(&mut config, &cli);
+        update_cast_config(&mut config, &cli)?;
+        if let Some(fee_args) = fee_args_mut(&mut cli.command) {
+            *fee_args = fee_args.clone().with_config_defaults(&config);
+        }
 
         runtime.block_on(run_async_command(
             cli,
@@ -180,6 +252,7 @@ async fn run_async_command(
         wait: cli.wait,
         wait_params: config.wait_params,
     };
+    let print_receipt = cli.receipt;
 
     match cli.command {
         Commands::Declare(declare) => {
@@ -219,6 +292,55 @@ async fn run_async_command(
                 config.show_explorer_links,
                 config.block_explorer,
             );
+
+            print_receipts_if_requested(
+                print_receipt,
+                &provider,
+                result
+                    .as_ref()
+                    .ok()
+                    .map(|response| response.transaction_hash),
+                config.wait_params,
+                numbers_format,
+                output_format,
+            )
+            .await?;
+            Ok(())
+        }
+
+        Commands::DeclareAll(declare_all) => {
+            let provider = declare_all.rpc.get_provider(&config).await?;
+
+            declare_all.validate()?;
+
+            let account = get_account(
+                &config.account,
+                &config.accounts_file,
+                &provider,
+                config.keystore,
+            )
+            .await?;
+            let manifest_path = assert_manifest_path_exists()?;
+            let package_metadata = get_package_metadata(&manifest_path, &declare_all.package)?;
+            let artifacts = build_and_load_artifacts(
+                &package_metadata,
+                &BuildConfig {
+                    scarb_toml_path: manifest_path,
+                    json: cli.json,
+                    profile: cli.profile.unwrap_or("release".to_string()),
+                },
+                false,
+            )
+            .expect("Failed to build contract");
+            let result = starknet_commands::declare_all::declare_all(
+                declare_all,
+                &account,
+                &artifacts,
+                wait_config,
+            )
+            .await;
+
+            print_command_result("declare-all", &result, numbers_format, output_format)?;
             Ok(())
         }
 
@@ -229,6 +351,9 @@ async fn run_async_command(
 
             let Deploy {
                 constructor_calldata,
+                init,
+                init_calldata,
+                abi_file,
                 fee_args,
                 rpc,
                 ..
@@ -253,34 +378,195 @@ async fn run_async_command(
             // safe to unwrap because "constructor" is a standardized name
             let selector = get_selector_from_name("constructor").unwrap();
 
-            let contract_class = get_contract_class(deploy.class_hash, &provider).await?;
+            let calldata_abi = match abi_file {
+                Some(abi_file) => CalldataAbi::from_file(&abi_file)?,
+                None => CalldataAbi::Class(
+                    starknet_commands::deploy::get_class_to_deploy(deploy.class_hash, &provider)
+                        .await?,
+                ),
+            };
 
+            let init = init
+                .map(|init| -> Result<_> {
+                    calldata_abi.ensure_function(&init)?;
+                    let init_selector = get_selector_from_name(&init)
+                        .context("Failed to convert initializer selector to FieldElement")?;
+                    let init_calldata = init_calldata
+                        .map(|data| calldata_abi.serialize(data, &init_selector))
+                        .transpose()?
+                        .unwrap_or_default();
+
+                    Ok((init_selector, init_calldata))
+                })
+                .transpose()?;
+
+            if constructor_calldata.is_some() {
+                calldata_abi.ensure_function("constructor")?;
+            }
             let serialized_calldata = constructor_calldata
-                .map(|data| Calldata::from(data).serialized(contract_class, &selector))
+                .map(|data| calldata_abi.serialize(data, &selector))
                 .transpose()?
                 .unwrap_or_default();
 
-            let result = starknet_commands::deploy::deploy(
-                deploy.class_hash,
-                &serialized_calldata,
-                deploy.salt,
-                deploy.unique,
-                fee_settings,
-                deploy.nonce,
+            // Validation is skipped if the ABI is not available, the node will verify the calldata anyway
+            let calldata_validation = match calldata_abi.abi() {
+                Some(abi) => validate_constructor_calldata(&serialized_calldata, &abi),
+                None => Ok(()),
+            };
+
+            // The initializer can only be invoked once the deployment is accepted
+            let deploy_wait_config = if init.is_some() {
+                WaitForTx {
+                    wait: true,
+                    ..wait_config
+                }
+            } else {
+                wait_config
+            };
+
+            let result = match calldata_validation {
+                Ok(()) => {
+                    starknet_commands::deploy::deploy(
+                        deploy.class_hash,
+                        &serialized_calldata,
+                        deploy.salt,
+                        deploy.unique,
+                        fee_settings,
+                        fee_args.fee_limits(),
+                        deploy.nonce,
+                        &account,
+                        deploy_wait_config,
+                    )
+                    .await
+                }
+                Err(error) => Err(error.into()),
+            }
+            .map_err(handle_starknet_command_error);
+
+            if let (Some(name), Ok(response)) = (&deploy.save_as, &result) {
+                save_to_address_book(
+                    &config.address_book_path(),
+                    name,
+                    get_chain_id(&provider).await?,
+                    response.contract_address,
+                )?;
+            }
+
+            let mut transaction_hashes = vec![];
+
+            if let Some((init_selector, init_calldata)) = init {
+                let result = match result {
+                    Ok(deployed) => {
+                        starknet_commands::deploy::initialize(
+                            deployed,
+                            init_selector,
+                            init_calldata,
+                            fee_args.fee_token(fee_token),
+                            deploy.nonce.map(|nonce| nonce + Felt::ONE),
+                            &account,
+                            wait_config,
+                        )
+                        .await
+                    }
+                    Err(error) => Err(error),
+                };
+
+                print_command_result("deploy", &result, numbers_format, output_format)?;
+                print_block_explorer_link_if_allowed(
+                    &result,
+                    output_format,
+                    provider.chain_id().await?,
+                    config.show_explorer_links,
+                    config.block_explorer,
+                );
+
+                if let Ok(response) = &result {
+                    transaction_hashes
+                        .extend([response.transaction_hash, response.init_transaction_hash]);
+                }
+            } else {
+                print_command_result("deploy", &result, numbers_format, output_format)?;
+                print_block_explorer_link_if_allowed(
+                    &result,
+                    output_format,
+                    provider.chain_id().await?,
+                    config.show_explorer_links,
+                    config.block_explorer,
+                );
+
+                if let Ok(response) = &result {
+                    transaction_hashes.push(response.transaction_hash);
+                }
+            }
+
+            print_receipts_if_requested(
+                print_receipt,
+                &provider,
+                transaction_hashes,
+                config.wait_params,
+                numbers_format,
+                output_format,
+            )
+            .await?;
+            Ok(())
+        }
+
+        Commands::DeployPlan(deploy_plan) => {
+            deploy_plan.validate()?;
+
+            // Invalid plans are rejected before anything is sent to the network
+            let plan = starknet_commands::deploy_plan::DeploymentPlan::load(&deploy_plan.path)?;
+
+            let provider = deploy_plan.rpc.get_provider(&config).await?;
+
+            let account = get_account(
+                &config.account,
+                &config.accounts_file,
+                &provider,
+                config.keystore,
+            )
+            .await?;
+
+            let artifacts = if plan.declares_contracts() {
+                let manifest_path = assert_manifest_path_exists()?;
+                let package_metadata = get_package_metadata(&manifest_path, &deploy_plan.package)?;
+                build_and_load_artifacts(
+                    &package_metadata,
+                    &BuildConfig {
+                        scarb_toml_path: manifest_path,
+                        json: cli.json,
+                        profile: cli.profile.unwrap_or("release".to_string()),
+                    },
+                    false,
+                )
+                .expect("Failed to build contract")
+            } else {
+                HashMap::new()
+            };
+
+            if deploy_plan.dry_run {
+                let result = starknet_commands::deploy_plan::dry_run(
+                    deploy_plan,
+                    plan,
+                    &account,
+                    &artifacts,
+                )
+                .await;
+
+                print_command_result("deploy-plan", &result, numbers_format, output_format)?;
+                return Ok(());
+            }
+
+            let result = starknet_commands::deploy_plan::deploy_plan(
+                deploy_plan,
+                plan,
                 &account,
+                &artifacts,
                 wait_config,
             )
-            .await
-            .map_err(handle_starknet_command_error);
+            .await;
 
-            print_command_result("deploy", &result, numbers_format, output_format)?;
-            print_block_explorer_link_if_allowed(
-                &result,
-                output_format,
-                provider.chain_id().await?,
-                config.show_explorer_links,
-                config.block_explorer,
-            );
+            print_command_result("deploy-plan", &result, numbers_format, output_format)?;
             Ok(())
         }
 
@@ -288,20 +574,30 @@ async fn run_async_command(
             contract_address,
             function,
             calldata,
+            abi_file,
             block_id,
             rpc,
         }) => {
             let provider = rpc.get_provider(&config).await?;
+            let contract_address = contract_address
+                .resolve(&config.address_book_path(), &provider)
+                .await?;
 
-            let block_id = get_block_id(&block_id)?;
-            let class_hash = get_class_hash_by_address(&provider, contract_address).await?;
-            let contract_class = get_contract_class(class_hash, &provider).await?;
+            let block_id = BlockId::from(block_id);
+            let calldata_abi = match abi_file {
+                Some(abi_file) => CalldataAbi::from_file(&abi_file)?,
+                None => {
+                    let class_hash = get_class_hash_by_address(&provider, contract_address).await?;
+                    CalldataAbi::Class(get_contract_class(class_hash, &provider).await?)
+                }
+            };
+            calldata_abi.ensure_function(&function)?;
 
             let selector = get_selector_from_name(&function)
                 .context("Failed to convert entry point selector to FieldElement")?;
 
             let serialized_calldata = calldata
-                .map(|data| Calldata::from(data).serialized(contract_class, &selector))
+                .map(|data| calldata_abi.serialize(data, &selector))
                 .transpose()?
                 .unwrap_or_default();
 
@@ -328,6 +624,7 @@ async fn run_async_command(
                 contract_address,
                 function,
                 calldata,
+                abi_file,
                 fee_args,
                 rpc,
                 nonce,
@@ -346,14 +643,24 @@ async fn run_async_command(
 
             let fee_args = fee_args.fee_token(fee_token);
 
+            let contract_address = contract_address
+                .resolve(&config.address_book_path(), &provider)
+                .await?;
+
             let selector = get_selector_from_name(&function)
                 .context("Failed to convert entry point selector to FieldElement")?;
 
-            let class_hash = get_class_hash_by_address(&provider, contract_address).await?;
-            let contract_class = get_contract_class(class_hash, &provider).await?;
+            let calldata_abi = match abi_file {
+                Some(abi_file) => CalldataAbi::from_file(&abi_file)?,
+                None => {
+                    let class_hash = get_class_hash_by_address(&provider, contract_address).await?;
+                    CalldataAbi::Class(get_contract_class(class_hash, &provider).await?)
+                }
+            };
+            calldata_abi.ensure_function(&function)?;
 
             let serialized_calldata = calldata
-                .map(|data| Calldata::from(data).serialized(contract_class, &selector))
+                .map(|data| calldata_abi.serialize(data, &selector))
                 .transpose()?
                 .unwrap_or_default();
 
@@ -377,6 +684,19 @@ async fn run_async_command(
                 config.show_explorer_links,
                 config.block_explorer,
             );
+
+            print_receipts_if_requested(
+                print_receipt,
+                &provider,
+                result
+                    .as_ref()
+                    .ok()
+                    .map(|response| response.transaction_hash),
+                config.wait_params,
+                numbers_format,
+                output_format,
+            )
+            .await?;
             Ok(())
         }
 
@@ -399,6 +719,16 @@ async fn run_async_command(
                         println!("{DEFAULT_MULTICALL_CONTENTS}");
                     }
                 }
+                starknet_commands::multicall::Commands::Validate(validate) => {
+                    let result = starknet_commands::multicall::validate::validate(&validate);
+
+                    print_command_result(
+                        "multicall validate",
+                        &result,
+                        numbers_format,
+                        output_format,
+                    )?;
+                }
                 starknet_commands::multicall::Commands::Run(run) => {
                     let provider = run.rpc.get_provider(&config).await?;
 
@@ -423,6 +753,19 @@ async fn run_async_command(
                         config.show_explorer_links,
                         config.block_explorer,
                     );
+
+                    print_receipts_if_requested(
+                        print_receipt,
+                        &provider,
+                        result
+                            .as_ref()
+                            .ok()
+                            .map(|response| response.transaction_hash),
+                        config.wait_params,
+                        numbers_format,
+                        output_format,
+                    )
+                    .await?;
                 }
             }
             Ok(())
@@ -552,6 +895,45 @@ async fn run_async_command(
             Ok(())
         }
 
+        Commands::Events(events) => {
+            let provider = events.rpc.get_provider(&config).await?;
+            let address = match &events.address {
+                Some(address) => Some(
+                    address
+                        .resolve(&config.address_book_path(), &provider)
+                        .await?,
+                ),
+                None => None,
+            };
+            let filter = events.filter(address);
+
+            let result = starknet_commands::events::events(&provider, filter, events.chunk_size)
+                .await
+                .map_err(handle_starknet_command_error);
+
+            print_command_result("events", &result, numbers_format, output_format)?;
+            Ok(())
+        }
+
+        Commands::GetStorageAt(get_storage_at) => {
+            let provider = get_storage_at.rpc.get_provider(&config).await?;
+            let address = get_storage_at
+                .address
+                .resolve(&config.address_book_path(), &provider)
+                .await?;
+            let key = get_storage_at.storage_key()?;
+            let block_id = BlockId::from(get_storage_at.block_id);
+
+            let result = starknet_commands::get_storage_at::get_storage_at(
+                &provider, address, key, &block_id,
+            )
+            .await
+            .map_err(handle_starknet_command_error);
+
+            print_command_result("get-storage-at", &result, numbers_format, output_format)?;
+            Ok(())
+        }
+
         Commands::Verify(verify) => {
             let manifest_path = assert_manifest_path_exists()?;
             let package_metadata = get_package_metadata(&manifest_path, &verify.package)?;
@@ -580,10 +962,123 @@ async fn run_async_command(
             Ok(())
         }
 
+        Commands::VerifyClass(verify_class) => {
+            let provider = verify_class.rpc.get_provider(&config).await?;
+            let address = verify_class
+                .address
+                .resolve(&config.address_book_path(), &provider)
+                .await?;
+            let block_id = BlockId::from(verify_class.block_id);
+
+            let manifest_path = assert_manifest_path_exists()?;
+            let package_metadata = get_package_metadata(&manifest_path, &verify_class.package)?;
+            let artifacts = build_and_load_artifacts(
+                &package_metadata,
+                &BuildConfig {
+                    scarb_toml_path: manifest_path,
+                    json: cli.json,
+                    profile: cli.profile.unwrap_or("release".to_string()),
+                },
+                false,
+            )
+            .expect("Failed to build contract");
+            let result = starknet_commands::verify_class::verify_class(
+                &provider,
+                address,
+                &verify_class.contract_name,
+                &block_id,
+                &artifacts,
+            )
+            .await;
+
+            print_command_result("verify-class", &result, numbers_format, output_format)?;
+
+            // Exit with a non-zero code, so that a mismatch can fail the CI
+            if let Ok(response) = &result {
+                ensure!(
+                    response.result == ClassHashComparison::Match,
+                    "Class hash of the contract at address {:#x} does not match the class hash of contract = {}",
+                    response.contract_address,
+                    response.contract_name
+                );
+            }
+            Ok(())
+        }
+
+        Commands::Decode(decode) => {
+            let result = starknet_commands::decode::decode(&decode);
+
+            print_command_result("decode", &result, numbers_format, output_format)?;
+            Ok(())
+        }
+
+        Commands::ComputeAddress(compute_address) => {
+            let result = starknet_commands::compute_address::compute_address(&compute_address);
+
+            print_command_result("compute-address", &result, numbers_format, output_format)?;
+            Ok(())
+        }
+
+        Commands::Devnet(Devnet { command }) => match command {
+            devnet::Commands::Start(start) => {
+                let result = starknet_commands::devnet::start::start(&start).await;
+
+                match result {
+                    Ok(response) if start.print_env => {
+                        print!(
+                            "{}",
+                            starknet_commands::devnet::start::env_assignments(&response)
+                        );
+                        Ok(())
+                    }
+                    result => {
+                        print_command_result(
+                            "devnet start",
+                            &result,
+                            numbers_format,
+                            output_format,
+                        )?;
+                        Ok(())
+                    }
+                }
+            }
+
+            devnet::Commands::Stop(stop) => {
+                let result = starknet_commands::devnet::stop::stop(&stop);
+
+                print_command_result("devnet stop", &result, numbers_format, output_format)?;
+                Ok(())
+            }
+        },
+
         Commands::Script(_) => unreachable!(),
     }
 }
 
+/// Prints receipts of the transactions sent by the command, if it was run with `--receipt`
+async fn print_receipts_if_requested(
+    print_receipt: bool,
+    provider: &JsonRpcClient<HttpTransport>,
+    transaction_hashes: impl IntoIterator<Item = Felt>,
+    wait_params: ValidatedWaitParams,
+    numbers_format: NumbersFormat,
+    output_format: OutputFormat,
+) -> Result<()> {
+    if !print_receipt {
+        return Ok(());
+    }
+
+    for transaction_hash in transaction_hashes {
+        let result = starknet_commands::receipt::receipt(provider, transaction_hash, wait_params)
+            .await
+            .map_err(handle_starknet_command_error);
+
+        print_command_result("receipt", &result, numbers_format, output_format)?;
+    }
+
+    Ok(())
+}
+
 fn run_script_command(
     cli: &Cli,
     runtime: Runtime,
@@ -604,7 +1099,7 @@ fn run_script_command(
                 &Some(package_metadata.root.clone()),
                 &cli.profile,
             )?;
-            update_cast_config(&mut config, cli);
+            update_cast_config(&mut config, cli)?;
             let provider = runtime.block_on(run.rpc.get_provider(&config))?;
 
             let mut artifacts = build_and_load_artifacts(
@@ -631,14 +1126,24 @@ fn run_script_command(
             let metadata_with_deps = get_scarb_metadata_with_deps(&manifest_path)?;
 
             let chain_id = runtime.block_on(get_chain_id(&provider))?;
-            let state_file_path = if run.no_state_file {
-                None
+            let network_name = chain_id_to_network_name(chain_id);
+            let (state_file_path, execution_log_path) = if run.no_state_file {
+                (None, None)
             } else {
-                Some(package_metadata.root.join(get_default_state_file_name(
-                    &run.script_name,
-                    &chain_id_to_network_name(chain_id),
-                )))
+                (
+                    Some(
+                        package_metadata
+                            .root
+                            .join(get_default_state_file_name(&run.script_name, &network_name)),
+                    ),
+                    Some(package_metadata.root.join(EXECUTION_LOG_FILE)),
+                )
             };
+            let execution_log = ExecutionLog::from(
+                execution_log_path,
+                format!("{}_{network_name}", run.script_name),
+                run.resume,
+            )?;
 
             let result = starknet_commands::script::run::run(
                 &run.script_name,
@@ -649,6 +1154,9 @@ fn run_script_command(
                 runtime,
                 &config,
                 state_file_path,
+                execution_log,
+                run.parallel,
+                run.no_version_check,
             );
 
             print_command_result("script run", &result, numbers_format, output_format)?;
@@ -658,7 +1166,7 @@ fn run_script_command(
     Ok(())
 }
 
-fn update_cast_config(config: &mut CastConfig, cli: &Cli) {
+fn update_cast_config(config: &mut CastConfig, cli: &Cli) -> Result<()> {
     macro_rules! clone_or_else {
         ($field:expr, $config_field:expr) => {
             $field.clone().unwrap_or_else(|| $config_field.clone())
@@ -682,4 +1190,60 @@ fn update_cast_config(config: &mut CastConfig, cli: &Cli) {
         ),
         clone_or_else!(cli.wait_timeout, config.wait_params.get_timeout()),
     );
+    if let Some(wait_interval) = cli.wait_interval {
+        config.wait_params = config.wait_params.with_interval_ms(wait_interval);
+    }
+
+    config.account_source = cli.account_source.unwrap_or(config.account_source);
+    if config.account_source == AccountSource::Starkli {
+        ensure!(
+            cli.accounts_file_path.is_none(),
+            "`--accounts-file` cannot be used with `--account-source starkli`"
+        );
+
+        if !config.account.is_empty() && reads_existing_account(&cli.command) {
+            let accounts_dir = shellexpand::tilde(DEFAULT_STARKLI_ACCOUNTS_DIR).to_string();
+            let paths = resolve_starkli_account(
+                &config.account,
+                config.keystore.as_deref(),
+                Utf8Path::new(&accounts_dir),
+            )?;
+            config.account = paths.account.to_string();
+            config.keystore = Some(paths.keystore);
+        }
+    }
+
+    Ok(())
+}
+
+/// Fee options of commands sending transactions
+fn fee_args_mut(command: &mut Commands) -> Option<&mut FeeArgs> {
+    match command {
+        Commands::Declare(declare) => Some(&mut declare.fee_args),
+        Commands::DeclareAll(declare_all) => Some(&mut declare_all.fee_args),
+        Commands::Deploy(deploy) => Some(&mut deploy.fee_args),
+        Commands::DeployPlan(deploy_plan) => Some(&mut deploy_plan.fee_args),
+        Commands::Invoke(invoke) => Some(&mut invoke.fee_args),
+        Commands::Multicall(Multicall {
+            command: multicall::Commands::Run(run),
+        }) => Some(&mut run.fee_args),
+        Commands::Account(Account {
+            command: account::Commands::Deploy(deploy),
+        }) => Some(&mut deploy.fee_args),
+        _ => None,
+    }
+}
+
+/// Accounts are created, imported, listed and deleted in the accounts file of sncast
+/// and devnet is managed without an account, all other commands use an already existing account
+fn reads_existing_account(command: &Commands) -> bool {
+    !matches!(
+        command,
+        Commands::Account(Account {
+            command: account::Commands::Create(_)
+                | account::Commands::Import(_)
+                | account::Commands::Delete(_)
+                | account::Commands::List(_),
+        }) | Commands::Devnet(_)
+    )
 }
@@ -1,10 +1,14 @@
-use crate::{handle_rpc_error, ErrorData, WaitForTransactionError};
+use crate::response::revert_reason::RevertReason;
+use crate::{handle_rpc_error, ErrorData, TransactionError, WaitForTransactionError};
 use anyhow::anyhow;
 use conversions::serde::serialize::CairoSerialize;
+use starknet::accounts::AccountError;
 use starknet::core::types::StarknetError::{
     ContractError, TransactionExecutionError, ValidationFailure,
 };
-use starknet::core::types::{ContractErrorData, StarknetError, TransactionExecutionErrorData};
+use starknet::core::types::{
+    ContractErrorData, Felt, StarknetError, TransactionExecutionErrorData,
+};
 use starknet::providers::ProviderError;
 use thiserror::Error;
 
@@ -18,16 +22,53 @@ pub enum StarknetCommandError {
     WaitForTransactionError(#[from] WaitForTransactionError),
     #[error(transparent)]
     ProviderError(#[from] SNCastProviderError),
+    #[error("Compiled class hash mismatch")]
+    CompiledClassHashMismatch(CompiledClassHashMismatchData),
+}
+
+#[derive(Debug, CairoSerialize)]
+pub struct CompiledClassHashMismatchData {
+    /// Compiled class hash sent in the declare transaction
+    pub declared: Felt,
+    /// Compiled class hash computed from the local CASM
+    pub local: Felt,
+    /// Compiled class hash the node most likely expects, computed from CASM compiled with the latest compiler
+    pub expected: Felt,
 }
 
 #[must_use]
 pub fn handle_starknet_command_error(error: StarknetCommandError) -> anyhow::Error {
     match error {
         StarknetCommandError::ProviderError(err) => handle_rpc_error(err),
+        StarknetCommandError::WaitForTransactionError(
+            WaitForTransactionError::TransactionError(TransactionError::Reverted(ErrorData {
+                data,
+            })),
+        ) => RevertReason::parse(&data).into(),
+        StarknetCommandError::CompiledClassHashMismatch(CompiledClassHashMismatchData {
+            declared,
+            local,
+            expected,
+        }) => anyhow!(
+            "Compiled class hash {declared:#x} does not match the one computed by the node, compiled class hash of the local CASM: {local:#x}\n\
+            The node most likely expects {expected:#x}, computed from CASM compiled with the latest compiler, pass it with `--compiled-class-hash {expected:#x}`"
+        ),
         _ => error.into(),
     }
 }
 
+/// Converts an error returned by the account before the transaction was sent, e.g. during fee estimation
+#[must_use]
+pub fn handle_account_error<S>(error: AccountError<S>) -> StarknetCommandError
+where
+    AccountError<S>: std::fmt::Display,
+{
+    match error {
+        AccountError::Provider(error) => StarknetCommandError::ProviderError(error.into()),
+        error => anyhow!(error.to_string()).into(),
+    }
+}
+
 #[derive(Debug, Error, CairoSerialize)]
 pub enum SNCastProviderError {
     #[error(transparent)]
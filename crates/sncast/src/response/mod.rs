@@ -1,4 +1,5 @@
 pub mod errors;
 pub mod explorer_link;
 pub mod print;
+pub mod revert_reason;
 pub mod structs;
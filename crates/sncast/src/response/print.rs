@@ -1,9 +1,11 @@
+use super::revert_reason::RevertReason;
 use super::structs::CommandResponse;
 use crate::NumbersFormat;
 use anyhow::Result;
 use itertools::Itertools;
 use serde::{Serialize, Serializer};
 use serde_json::Value;
+use shared::print::drain_diagnostics;
 use starknet::core::types::Felt;
 use std::{collections::HashMap, fmt::Display, str::FromStr};
 
@@ -184,7 +186,30 @@ pub fn print_command_result<T: CommandResponse>(
     numbers_format: NumbersFormat,
     output_format: OutputFormat,
 ) -> Result<()> {
-    let output: OutputData = result.into();
+    let mut output: OutputData = result.into();
+    if let (Err(error), OutputFormat::Json) = (result, output_format) {
+        // The revert reason is rendered in a human-readable form in the error message
+        if let Some(revert_reason) = error.downcast_ref::<RevertReason>() {
+            output.0.push((
+                String::from("raw_revert_reason"),
+                OutputValue::String(revert_reason.raw.clone()),
+            ));
+        }
+
+        // Warnings which may explain the error, e.g. a missing profile, go to stderr only otherwise
+        let diagnostics = drain_diagnostics();
+        if !diagnostics.is_empty() {
+            output.0.push((
+                String::from("diagnostics"),
+                OutputValue::Array(
+                    diagnostics
+                        .iter()
+                        .map(|diagnostic| OutputValue::String(diagnostic.to_string()))
+                        .collect(),
+                ),
+            ));
+        }
+    }
     let repr = output
         .format_with(numbers_format)
         .to_string_pretty(command, output_format)?;
@@ -0,0 +1,304 @@
+use cairo_lang_runner::short_string::as_cairo_short_string;
+use itertools::Itertools;
+use regex::Regex;
+use starknet::core::types::Felt;
+use std::fmt::{Display, Formatter};
+
+const FAILURE_REASON_PREFIX: &str = "Failure reason: ";
+const HINT_EXCEPTION_PREFIX: &str = "Got an exception while executing a hint: ";
+/// Appended to the panic data by every call which the failure propagated through
+const ENTRYPOINT_FAILED: &str = "ENTRYPOINT_FAILED";
+
+/// Reason of a reverted transaction, parsed from the revert error returned by the node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevertReason {
+    /// Calls the failure propagated through, from the outermost one
+    pub call_stack: Vec<FailedCall>,
+    /// Decoded failure message, e.g. the panic data of the failing call
+    pub message: String,
+    /// Revert error as returned by the node
+    pub raw: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedCall {
+    pub contract_address: Felt,
+    pub class_hash: Option<Felt>,
+    pub selector: Option<Felt>,
+    pub is_constructor: bool,
+}
+
+impl RevertReason {
+    /// Never fails, the parts which could not be parsed are left out and the whole error is kept in `raw`
+    #[must_use]
+    pub fn parse(raw: &str) -> Self {
+        let failed_call_re =
+            Regex::new(r"Error in the (called contract|contract class constructor) \(([^)]*)\):")
+                .unwrap();
+        let mut call_stack = vec![];
+        let mut body = raw;
+
+        for captures in failed_call_re.captures_iter(raw) {
+            if let Some(call) = FailedCall::parse(&captures[2], &captures[1]) {
+                call_stack.push(call);
+            }
+            body = &raw[captures.get(0).unwrap().end()..];
+        }
+
+        Self {
+            call_stack,
+            message: decode_message(body).unwrap_or_else(|| raw.trim().to_string()),
+            raw: raw.to_string(),
+        }
+    }
+
+    /// The innermost call, which has failed
+    #[must_use]
+    pub fn failing_call(&self) -> Option<&FailedCall> {
+        self.call_stack.last()
+    }
+}
+
+impl FailedCall {
+    fn parse(call: &str, kind: &str) -> Option<Self> {
+        let felt = |pattern: &str| {
+            Regex::new(pattern)
+                .unwrap()
+                .captures(call)
+                .and_then(|captures| captures.iter().skip(1).flatten().next())
+                .and_then(|value| Felt::from_hex(value.as_str()).ok())
+        };
+
+        Some(Self {
+            contract_address: felt(r"^(0x[0-9a-fA-F]+)$|contract address: (0x[0-9a-fA-F]+)")?,
+            class_hash: felt(r"class hash: (0x[0-9a-fA-F]+)"),
+            selector: felt(r"selector: (0x[0-9a-fA-F]+)"),
+            is_constructor: kind == "contract class constructor",
+        })
+    }
+}
+
+fn decode_message(body: &str) -> Option<String> {
+    if let Some((_, failure_reason)) = body.split_once(FAILURE_REASON_PREFIX) {
+        let failure_reason = failure_reason.lines().next().unwrap_or_default();
+        return Some(decode_failure_reason(failure_reason));
+    }
+
+    body.lines()
+        .map(str::trim)
+        .find(|line| {
+            !line.is_empty()
+                && !line.starts_with("Error at pc=")
+                && !line.starts_with("Cairo traceback")
+                && !line.starts_with("Unknown location")
+        })
+        .map(|line| line.trim_start_matches(HINT_EXCEPTION_PREFIX).to_string())
+}
+
+/// Decodes felts of the panic data, e.g. `(0x4661696c6564 ('Failed'), 0x454e545259504f494e545f4641494c4544 ('ENTRYPOINT_FAILED'))`,
+/// or a byte array message, e.g. `"Failed"`
+fn decode_failure_reason(failure_reason: &str) -> String {
+    let failure_reason = failure_reason.trim().trim_end_matches('.');
+    if let Some(message) = failure_reason
+        .strip_prefix('"')
+        .and_then(|message| message.strip_suffix('"'))
+    {
+        return message.to_string();
+    }
+
+    let items: Vec<_> = Regex::new(r"(0x[0-9a-fA-F]+)(?: \('(.*?)'\))?")
+        .unwrap()
+        .captures_iter(failure_reason)
+        .map(|captures| match captures.get(2) {
+            Some(decoded) => decoded.as_str().to_string(),
+            None => {
+                let felt = &captures[1];
+                Felt::from_hex(felt)
+                    .ok()
+                    .and_then(|felt| as_cairo_short_string(&felt))
+                    .filter(|decoded| !decoded.is_empty())
+                    .unwrap_or_else(|| felt.to_string())
+            }
+        })
+        .collect();
+
+    if items.is_empty() {
+        return failure_reason.to_string();
+    }
+
+    let messages: Vec<_> = items
+        .iter()
+        .filter(|item| item.as_str() != ENTRYPOINT_FAILED)
+        .collect();
+    if messages.is_empty() {
+        items.join(", ")
+    } else {
+        messages.into_iter().join(", ")
+    }
+}
+
+impl Display for RevertReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Transaction has been reverted: {}", self.message)?;
+
+        if let Some(call) = self.failing_call() {
+            write!(f, "\nFailing contract: {:#x}", call.contract_address)?;
+            if call.is_constructor {
+                write!(f, "\nFailing selector: constructor")?;
+            } else if let Some(selector) = call.selector {
+                write!(f, "\nFailing selector: {selector:#x}")?;
+            }
+        }
+        if self.call_stack.len() > 1 {
+            let call_stack = self
+                .call_stack
+                .iter()
+                .map(|call| format!("{:#x}", call.contract_address))
+                .join(" -> ");
+            write!(f, "\nCall stack: {call_stack}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for RevertReason {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn nested_call_failure() {
+        let raw = indoc! {r"
+            Transaction execution has failed:
+            0: Error in the called contract (contract address: 0x0000000000000000000000000000000000000000000000000000000000000abc, class hash: 0x0000000000000000000000000000000000000000000000000000000000000001, selector: 0x015d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad):
+            Error at pc=0:4573:
+            Cairo traceback (most recent call last):
+            Unknown location (pc=0:67)
+            1: Error in the called contract (contract address: 0x0000000000000000000000000000000000000000000000000000000000000def, class hash: 0x0000000000000000000000000000000000000000000000000000000000000002, selector: 0x0083afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e):
+            Execution failed. Failure reason: (0x496e73756666696369656e742062616c616e6365 ('Insufficient balance'), 0x454e545259504f494e545f4641494c4544 ('ENTRYPOINT_FAILED')).
+        "};
+
+        let reason = RevertReason::parse(raw);
+
+        assert_eq!(reason.message, "Insufficient balance");
+        assert_eq!(reason.raw, raw);
+        assert_eq!(
+            reason.call_stack,
+            vec![
+                FailedCall {
+                    contract_address: Felt::from(0xabc),
+                    class_hash: Some(Felt::from(1)),
+                    selector: Some(
+                        Felt::from_hex(
+                            "0x015d40a3d6ca2ac30f4031e42be28da9b056fef9bb7357ac5e85627ee876e5ad"
+                        )
+                        .unwrap()
+                    ),
+                    is_constructor: false,
+                },
+                FailedCall {
+                    contract_address: Felt::from(0xdef),
+                    class_hash: Some(Felt::from(2)),
+                    selector: Some(
+                        Felt::from_hex(
+                            "0x0083afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e"
+                        )
+                        .unwrap()
+                    ),
+                    is_constructor: false,
+                },
+            ]
+        );
+        assert_eq!(
+            reason.to_string(),
+            indoc! {r"
+                Transaction has been reverted: Insufficient balance
+                Failing contract: 0xdef
+                Failing selector: 0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e
+                Call stack: 0xabc -> 0xdef"
+            }
+        );
+    }
+
+    #[test]
+    fn constructor_failure() {
+        let raw = indoc! {r"
+            Transaction execution has failed:
+            0: Error in the called contract (contract address: 0x0000000000000000000000000000000000000000000000000000000000000abc, class hash: 0x0000000000000000000000000000000000000000000000000000000000000001, selector: 0x01987cbd17808b9a23693d4de7e246a443cfe37e6e7fbaeabd7d7e6532b07c3d):
+            Error at pc=0:4573:
+            1: Error in the contract class constructor (contract address: 0x0000000000000000000000000000000000000000000000000000000000000def, class hash: 0x0000000000000000000000000000000000000000000000000000000000000002, selector: 0x028ffe4ff0f226a9107253e17a904099aa4f63a02a5621de0576e5aa71bc5194):
+            Execution failed. Failure reason: 0x4661696c656420746f20646573657269616c697a6520706172616d202332 ('Failed to deserialize param #2').
+        "};
+
+        let reason = RevertReason::parse(raw);
+
+        assert_eq!(reason.message, "Failed to deserialize param #2");
+        assert!(reason.failing_call().unwrap().is_constructor);
+        assert!(reason
+            .to_string()
+            .contains("Failing contract: 0xdef\nFailing selector: constructor"));
+    }
+
+    #[test]
+    fn hint_exception() {
+        let raw = indoc! {r"
+            Error in the called contract (0x00f6ecd22832b7c3713cfa7826ee309ce96a2769833f093795fafa1b8f20c48b):
+            Error at pc=0:4835:
+            Got an exception while executing a hint: Requested contract address 0x123 is not deployed.
+            Cairo traceback (most recent call last):
+            Unknown location (pc=0:67)
+        "};
+
+        let reason = RevertReason::parse(raw);
+
+        assert_eq!(
+            reason.message,
+            "Requested contract address 0x123 is not deployed."
+        );
+        assert_eq!(
+            reason.failing_call().unwrap(),
+            &FailedCall {
+                contract_address: Felt::from_hex(
+                    "0x00f6ecd22832b7c3713cfa7826ee309ce96a2769833f093795fafa1b8f20c48b"
+                )
+                .unwrap(),
+                class_hash: None,
+                selector: None,
+                is_constructor: false,
+            }
+        );
+    }
+
+    #[test]
+    fn undecoded_felt_array() {
+        let reason =
+            RevertReason::parse("Execution failed. Failure reason: [0x4e6f7420656e6f756768, 0x1].");
+
+        assert!(reason.call_stack.is_empty());
+        assert_eq!(reason.message, "Not enough, 0x1");
+        assert_eq!(
+            reason.to_string(),
+            "Transaction has been reverted: Not enough, 0x1"
+        );
+    }
+
+    #[test]
+    fn byte_array_message() {
+        let reason = RevertReason::parse(
+            "Execution failed. Failure reason: \"Caller is not the owner, (see docs)\".",
+        );
+
+        assert_eq!(reason.message, "Caller is not the owner, (see docs)");
+    }
+
+    #[test]
+    fn unknown_format() {
+        let reason = RevertReason::parse("Insufficient max fee\n");
+
+        assert!(reason.call_stack.is_empty());
+        assert_eq!(reason.message, "Insufficient max fee");
+    }
+}
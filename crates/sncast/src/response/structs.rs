@@ -4,7 +4,7 @@ use camino::Utf8PathBuf;
 use conversions::serde::serialize::CairoSerialize;
 use indoc::formatdoc;
 use serde::{Deserialize, Serialize, Serializer};
-use starknet::core::types::Felt;
+use starknet::core::types::{Felt, PriceUnit};
 
 pub struct Decimal(pub u64);
 
@@ -45,6 +45,14 @@ pub struct DeployResponse {
 }
 impl CommandResponse for DeployResponse {}
 
+#[derive(Clone, Serialize, Debug, PartialEq)]
+pub struct DeployWithInitResponse {
+    pub contract_address: Felt,
+    pub transaction_hash: Felt,
+    pub init_transaction_hash: Felt,
+}
+impl CommandResponse for DeployWithInitResponse {}
+
 #[derive(Clone, Serialize, Deserialize, CairoSerialize, Debug, PartialEq)]
 pub struct DeclareResponse {
     pub class_hash: Felt,
@@ -52,6 +60,54 @@ pub struct DeclareResponse {
 }
 impl CommandResponse for DeclareResponse {}
 
+/// Contract declared with `declare-all`, serialized as `[contract_name, class_hash, transaction_hash]`
+#[derive(Serialize)]
+pub struct DeclaredContract(pub String, pub Felt, pub Felt);
+
+/// Contract skipped by `declare-all` as its class is already declared, serialized as `[contract_name, class_hash]`
+#[derive(Serialize)]
+pub struct SkippedContract(pub String, pub Felt);
+
+/// Contract which `declare-all` failed to declare, serialized as `[contract_name, error]`
+#[derive(Serialize)]
+pub struct FailedContract(pub String, pub String);
+
+#[derive(Serialize, Default)]
+pub struct DeclareAllResponse {
+    pub declared: Vec<DeclaredContract>,
+    pub skipped: Vec<SkippedContract>,
+    pub failed: Vec<FailedContract>,
+    /// Contracts listed after the failed one, with `--fail-fast`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub not_attempted: Vec<String>,
+}
+impl CommandResponse for DeclareAllResponse {}
+
+/// Contract deployed from a deployment plan, serialized as `[id, class_hash, contract_address]`
+#[derive(Serialize)]
+pub struct PlannedDeployment(pub String, pub Felt, pub Felt);
+
+#[derive(Serialize)]
+pub struct DeployPlanResponse {
+    pub contracts: Vec<PlannedDeployment>,
+    pub results_file: Utf8PathBuf,
+}
+impl CommandResponse for DeployPlanResponse {}
+
+/// Step of a deployment plan checked with `--dry-run`, serialized as `[id, action, class_hash, estimated_fee]`
+#[derive(Serialize)]
+pub struct PlannedStep(pub String, pub String, pub Felt, pub String);
+
+#[derive(Serialize)]
+pub struct DeployPlanDryRunResponse {
+    pub steps: Vec<PlannedStep>,
+    #[serde(serialize_with = "crate::response::structs::serialize_as_decimal")]
+    pub total_estimated_fee: Felt,
+    pub fee_unit: String,
+    pub message: String,
+}
+impl CommandResponse for DeployPlanDryRunResponse {}
+
 #[derive(Serialize)]
 pub struct AccountCreateResponse {
     pub address: Felt,
@@ -84,6 +140,28 @@ pub struct MulticallNewResponse {
 }
 impl CommandResponse for MulticallNewResponse {}
 
+#[derive(Serialize)]
+pub struct MulticallValidateResponse {
+    pub path: Utf8PathBuf,
+    pub calls: Decimal,
+}
+impl CommandResponse for MulticallValidateResponse {}
+
+#[derive(Serialize)]
+pub struct DevnetStartResponse {
+    pub url: String,
+    pub accounts_file: Utf8PathBuf,
+    pub accounts: Vec<String>,
+    pub profile_out: Option<Utf8PathBuf>,
+}
+impl CommandResponse for DevnetStartResponse {}
+
+#[derive(Serialize)]
+pub struct DevnetStopResponse {
+    pub url: String,
+}
+impl CommandResponse for DevnetStopResponse {}
+
 #[derive(Serialize)]
 pub struct ShowConfigResponse {
     pub profile: Option<String>,
@@ -134,6 +212,70 @@ pub struct TransactionStatusResponse {
 
 impl CommandResponse for TransactionStatusResponse {}
 
+/// Event emitted by a transaction, serialized as `[from_address, [keys], [data]]`
+#[derive(Serialize)]
+pub struct ReceiptEvent(pub Felt, pub Vec<Felt>, pub Vec<Felt>);
+
+#[derive(Serialize)]
+pub struct TransactionReceiptResponse {
+    pub transaction_hash: Felt,
+    pub finality_status: FinalityStatus,
+    pub execution_status: ExecutionStatus,
+    pub revert_reason: Option<String>,
+    pub block_hash: Option<Felt>,
+    pub block_number: Option<Decimal>,
+    pub actual_fee: Felt,
+    pub fee_unit: PriceUnit,
+    pub steps: Decimal,
+    pub memory_holes: Option<Decimal>,
+    pub l1_gas: Decimal,
+    pub l1_data_gas: Decimal,
+    pub events: Vec<ReceiptEvent>,
+}
+
+impl CommandResponse for TransactionReceiptResponse {}
+
+/// Event returned by `events`, serialized as
+/// `[name, from_address, [keys], [data], transaction_hash, block_number]`.
+/// Name is `unknown` if the event was not found in the contract ABI, block number is `pending` for pending blocks.
+#[derive(Serialize)]
+pub struct EmittedEventResponse(
+    pub String,
+    pub Felt,
+    pub Vec<Felt>,
+    pub Vec<Felt>,
+    pub Felt,
+    pub String,
+);
+
+#[derive(Serialize)]
+pub struct EventsResponse {
+    pub events: Vec<EmittedEventResponse>,
+}
+
+impl CommandResponse for EventsResponse {}
+
+#[derive(Serialize)]
+pub struct GetStorageAtResponse {
+    pub value: Felt,
+}
+
+impl CommandResponse for GetStorageAtResponse {}
+
+#[derive(Serialize)]
+pub struct DecodeResponse {
+    pub response: String,
+}
+
+impl CommandResponse for DecodeResponse {}
+
+#[derive(Serialize)]
+pub struct ComputeAddressResponse {
+    pub contract_address: Felt,
+}
+
+impl CommandResponse for ComputeAddressResponse {}
+
 #[derive(Serialize)]
 pub struct VerifyResponse {
     pub message: String,
@@ -141,6 +283,23 @@ pub struct VerifyResponse {
 
 impl CommandResponse for VerifyResponse {}
 
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClassHashComparison {
+    Match,
+    Mismatch,
+}
+
+#[derive(Serialize)]
+pub struct VerifyClassResponse {
+    pub contract_name: String,
+    pub contract_address: Felt,
+    pub onchain_class_hash: Felt,
+    pub local_class_hash: Felt,
+    pub result: ClassHashComparison,
+}
+
+impl CommandResponse for VerifyClassResponse {}
+
 impl OutputLink for InvokeResponse {
     const TITLE: &'static str = "invocation";
 
@@ -167,6 +326,23 @@ impl OutputLink for DeployResponse {
     }
 }
 
+impl OutputLink for DeployWithInitResponse {
+    const TITLE: &'static str = "deployment";
+
+    fn format_links(&self, provider: Box<dyn LinkProvider>) -> String {
+        formatdoc!(
+            "
+            contract: {}
+            transaction: {}
+            initializer transaction: {}
+            ",
+            provider.contract(self.contract_address),
+            provider.transaction(self.transaction_hash),
+            provider.transaction(self.init_transaction_hash)
+        )
+    }
+}
+
 impl OutputLink for DeclareResponse {
     const TITLE: &'static str = "declaration";
 
@@ -9,13 +9,11 @@ use sncast::helpers::fee::{FeeArgs, FeeSettings, FeeToken, PayableTransaction};
 use sncast::helpers::rpc::RpcArgs;
 use sncast::response::structs::InvokeResponse;
 use sncast::{
-    apply_optional, chain_id_to_network_name, check_account_file_exists,
-    get_account_data_from_accounts_file, get_account_data_from_keystore, get_keystore_password,
+    chain_id_to_network_name, check_account_file_exists, get_account_data_from_accounts_file,
+    get_account_data_from_keystore, get_keystore_password, handle_account_factory_error,
     handle_rpc_error, handle_wait_for_tx, impl_payable_transaction, AccountType, WaitForTx,
 };
-use starknet::accounts::{
-    AccountDeploymentV1, AccountDeploymentV3, AccountFactory, OpenZeppelinAccountFactory,
-};
+use starknet::accounts::{AccountFactory, OpenZeppelinAccountFactory};
 use starknet::accounts::{AccountFactoryError, ArgentAccountFactory};
 use starknet::core::types::BlockTag::Pending;
 use starknet::core::types::{BlockId, Felt, StarknetError::ClassHashNotFound};
@@ -262,24 +260,43 @@ where
     let fee_settings = fee_args
         .try_into_fee_settings(account_factory.provider(), account_factory.block_id())
         .await?;
+    let fee_limits = fee_args.fee_limits();
+
     let result = match fee_settings {
         FeeSettings::Eth { max_fee } => {
             let deployment = account_factory.deploy_v1(salt);
-            let deployment = apply_optional(deployment, max_fee, AccountDeploymentV1::max_fee);
-            deployment.send().await
+
+            let max_fee = fee_limits
+                .max_fee(max_fee, || async {
+                    deployment
+                        .estimate_fee()
+                        .await
+                        .map_err(handle_account_factory_error::<T>)
+                })
+                .await?;
+
+            deployment.max_fee(max_fee).send().await
         }
         FeeSettings::Strk {
             max_gas,
             max_gas_unit_price,
         } => {
             let deployment = account_factory.deploy_v3(salt);
-            let deployment = apply_optional(deployment, max_gas, AccountDeploymentV3::gas);
-            let deployment = apply_optional(
-                deployment,
-                max_gas_unit_price,
-                AccountDeploymentV3::gas_price,
-            );
-            deployment.send().await
+
+            let (max_gas, max_gas_unit_price) = fee_limits
+                .resource_bounds(max_gas, max_gas_unit_price, || async {
+                    deployment
+                        .estimate_fee()
+                        .await
+                        .map_err(handle_account_factory_error::<T>)
+                })
+                .await?;
+
+            deployment
+                .gas(max_gas)
+                .gas_price(max_gas_unit_price)
+                .send()
+                .await
         }
     };
 
@@ -1,5 +1,8 @@
 use anyhow::Result;
+use camino::Utf8PathBuf;
 use clap::Args;
+use shared::block_id::BlockIdArg;
+use sncast::helpers::address_book::ContractReference;
 use sncast::helpers::rpc::RpcArgs;
 use sncast::response::errors::StarknetCommandError;
 use sncast::response::structs::CallResponse;
@@ -10,9 +13,9 @@ use starknet::providers::{JsonRpcClient, Provider};
 #[derive(Args)]
 #[command(about = "Call a contract instance on Starknet", long_about = None)]
 pub struct Call {
-    /// Address of the called contract (hex)
+    /// Address of the called contract (hex), or `@name` of a contract saved in the address book
     #[clap(short = 'd', long)]
-    pub contract_address: Felt,
+    pub contract_address: ContractReference,
 
     /// Name of the contract function to be called
     #[clap(short, long)]
@@ -22,11 +25,15 @@ pub struct Call {
     #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
     pub calldata: Option<Vec<String>>,
 
+    /// Path to a JSON file with the ABI, or a Sierra contract class, used to encode the calldata instead of the ABI of the deployed class
+    #[clap(long)]
+    pub abi_file: Option<Utf8PathBuf>,
+
     /// Block identifier on which call should be performed.
-    /// Possible values: pending, latest, block hash (0x prefixed string)
-    /// and block number (u64)
+    /// Possible values: pending, latest, block hash (0x prefixed hex)
+    /// and block number (decimal u64)
     #[clap(short, long, default_value = "pending")]
-    pub block_id: String,
+    pub block_id: BlockIdArg,
 
     #[clap(flatten)]
     pub rpc: RpcArgs,
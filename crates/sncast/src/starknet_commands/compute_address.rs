@@ -0,0 +1,46 @@
+use anyhow::Result;
+use clap::Args;
+use sncast::helpers::contract_address::{compute_contract_address, AddressDerivation};
+use sncast::response::structs::ComputeAddressResponse;
+use starknet::core::types::Felt;
+
+#[derive(Args)]
+#[command(about = "Compute the address a contract will be deployed at", long_about = None)]
+pub struct ComputeAddress {
+    /// Class hash of the contract to deploy
+    #[clap(short = 'g', long)]
+    pub class_hash: Felt,
+
+    /// Constructor arguments serialized as a series of felts
+    #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
+    pub constructor_calldata: Vec<Felt>,
+
+    /// Salt for the address
+    #[clap(short, long)]
+    pub salt: Felt,
+
+    /// Address of the account deploying through UDC with `--unique`, or of the contract calling `deploy_syscall`
+    #[clap(long)]
+    pub deployer_address: Option<Felt>,
+
+    /// If true, salt is modified with the deployer address, as done by `deploy --unique`
+    #[clap(long)]
+    pub unique: bool,
+
+    /// How the address is derived
+    #[clap(value_enum, long, default_value_t = AddressDerivation::Udc)]
+    pub derivation: AddressDerivation,
+}
+
+pub fn compute_address(compute_address: &ComputeAddress) -> Result<ComputeAddressResponse> {
+    let contract_address = compute_contract_address(
+        compute_address.derivation,
+        compute_address.class_hash,
+        compute_address.salt,
+        &compute_address.constructor_calldata,
+        compute_address.deployer_address,
+        compute_address.unique,
+    )?;
+
+    Ok(ComputeAddressResponse { contract_address })
+}
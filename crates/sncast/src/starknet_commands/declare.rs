@@ -2,21 +2,27 @@ use anyhow::{anyhow, Context, Result};
 use clap::{Args, ValueEnum};
 use scarb_api::StarknetContractArtifacts;
 use sncast::helpers::error::token_not_supported_for_declaration;
-use sncast::helpers::fee::{FeeArgs, FeeSettings, FeeToken, PayableTransaction};
+use sncast::helpers::fee::{
+    FeeArgs, FeeLimits, FeeMultiplier, FeeSettings, FeeToken, PayableTransaction,
+};
 use sncast::helpers::rpc::RpcArgs;
-use sncast::response::errors::StarknetCommandError;
+use sncast::helpers::scarb_utils::get_contract_artifacts;
+use sncast::response::errors::{CompiledClassHashMismatchData, StarknetCommandError};
 use sncast::response::structs::DeclareResponse;
-use sncast::{apply_optional, handle_wait_for_tx, impl_payable_transaction, ErrorData, WaitForTx};
-use starknet::accounts::AccountError::Provider;
+use sncast::{apply_optional, handle_wait_for_tx, impl_payable_transaction, WaitForTx};
+use starknet::accounts::AccountError;
 use starknet::accounts::{ConnectedAccount, DeclarationV2, DeclarationV3};
-use starknet::core::types::{DeclareTransactionResult, Felt};
+use starknet::core::types::{DeclareTransactionResult, Felt, StarknetError};
+use starknet::providers::ProviderError;
 use starknet::{
     accounts::{Account, SingleOwnerAccount},
     core::types::contract::{CompiledClass, SierraClass},
+    core::types::FlattenedSierraClass,
     providers::jsonrpc::{HttpTransport, JsonRpcClient},
-    signers::LocalWallet,
+    signers::{local_wallet::SignError, LocalWallet},
 };
 use std::collections::HashMap;
+use std::num::NonZeroU32;
 use std::sync::Arc;
 
 #[derive(Args)]
@@ -41,10 +47,44 @@ pub struct Declare {
     #[clap(short, long)]
     pub version: Option<DeclareVersion>,
 
+    #[clap(flatten)]
+    pub bump_fee: AutoBumpFeeArgs,
+
+    /// Compiled class hash to declare the contract with, instead of the one computed from the local CASM.
+    /// The contract is then declared without compiling it to CASM
+    #[clap(long)]
+    pub compiled_class_hash: Option<Felt>,
+
     #[clap(flatten)]
     pub rpc: RpcArgs,
 }
 
+#[derive(Args, Debug, Clone, Copy)]
+pub struct AutoBumpFeeArgs {
+    /// Retry the declaration with the estimated fee multiplied by a factor growing 1.5 times with every attempt,
+    /// if it was rejected because of an insufficient max fee. A passed `--max-fee` is bumped the same way
+    #[clap(long, conflicts_with_all = ["max_gas", "max_gas_unit_price"])]
+    pub auto_bump_fee: bool,
+
+    /// Maximal number of attempts to declare the contract with `--auto-bump-fee`, including the first one
+    #[clap(long, default_value_t = DEFAULT_MAX_BUMP_ATTEMPTS, requires = "auto_bump_fee")]
+    pub max_bump_attempts: NonZeroU32,
+}
+
+const DEFAULT_MAX_BUMP_ATTEMPTS: NonZeroU32 = match NonZeroU32::new(3) {
+    Some(attempts) => attempts,
+    None => unreachable!(),
+};
+
+impl Default for AutoBumpFeeArgs {
+    fn default() -> Self {
+        Self {
+            auto_bump_fee: false,
+            max_bump_attempts: DEFAULT_MAX_BUMP_ATTEMPTS,
+        }
+    }
+}
+
 #[derive(ValueEnum, Debug, Clone)]
 pub enum DeclareVersion {
     V2,
@@ -63,57 +103,54 @@ pub async fn declare(
     artifacts: &HashMap<String, StarknetContractArtifacts>,
     wait_config: WaitForTx,
 ) -> Result<DeclareResponse, StarknetCommandError> {
-    let fee_settings = declare
+    let mut fee_settings = declare
         .fee_args
         .clone()
         .fee_token(declare.token_from_version())
         .try_into_fee_settings(account.provider(), account.block_id())
         .await?;
 
-    let contract_artifacts =
-        artifacts
-            .get(&declare.contract)
-            .ok_or(StarknetCommandError::ContractArtifactsNotFound(
-                ErrorData::new(declare.contract),
-            ))?;
+    let contract_artifacts = get_contract_artifacts(artifacts, &declare.contract)?;
 
     let contract_definition: SierraClass = serde_json::from_str(&contract_artifacts.sierra)
         .context("Failed to parse sierra artifact")?;
-    let casm_contract_definition: CompiledClass =
-        serde_json::from_str(&contract_artifacts.casm).context("Failed to parse casm artifact")?;
-
-    let casm_class_hash = casm_contract_definition
-        .class_hash()
-        .map_err(anyhow::Error::from)?;
+    // CASM is only needed to compute the compiled class hash, so it is not compiled if the hash is passed
+    let compiled_class_hash = match declare.compiled_class_hash {
+        Some(compiled_class_hash) => compiled_class_hash,
+        None => local_compiled_class_hash(&declare.contract, contract_artifacts)?,
+    };
 
-    let declared = match fee_settings {
-        FeeSettings::Eth { max_fee } => {
-            let declaration = account.declare_v2(
-                Arc::new(contract_definition.flatten().map_err(anyhow::Error::from)?),
-                casm_class_hash,
-            );
+    let contract_class = Arc::new(contract_definition.flatten().map_err(anyhow::Error::from)?);
+    let bump_fee = declare.bump_fee;
+    let mut fee_limits = declare.fee_args.fee_limits();
+    let fee_multiplier = fee_limits.fee_multiplier;
+    let mut attempt = 1;
 
-            let declaration = apply_optional(declaration, max_fee, DeclarationV2::max_fee);
-            let declaration = apply_optional(declaration, declare.nonce, DeclarationV2::nonce);
+    let declared = loop {
+        let declared = send_declaration(
+            account,
+            contract_class.clone(),
+            compiled_class_hash,
+            declare.nonce,
+            fee_settings,
+            &fee_limits,
+            bump_fee.auto_bump_fee.then_some(attempt),
+        )
+        .await?;
 
-            declaration.send().await
+        if !bump_fee.auto_bump_fee
+            || !should_bump_fee(&declared, attempt, bump_fee.max_bump_attempts)?
+        {
+            break declared;
         }
-        FeeSettings::Strk {
-            max_gas,
-            max_gas_unit_price,
-        } => {
-            let declaration = account.declare_v3(
-                Arc::new(contract_definition.flatten().map_err(anyhow::Error::from)?),
-                casm_class_hash,
-            );
-
-            let declaration = apply_optional(declaration, max_gas, DeclarationV3::gas);
-            let declaration =
-                apply_optional(declaration, max_gas_unit_price, DeclarationV3::gas_price);
-            let declaration = apply_optional(declaration, declare.nonce, DeclarationV3::nonce);
-
-            declaration.send().await
+        fee_limits.fee_multiplier = fee_multiplier.bumped(attempt)?;
+        if let FeeSettings::Eth {
+            max_fee: Some(max_fee),
+        } = &mut fee_settings
+        {
+            *max_fee = FeeMultiplier::ONE.bumped(1)?.max_fee(*max_fee)?;
         }
+        attempt += 1;
     };
 
     match declared {
@@ -131,7 +168,251 @@ pub async fn declare(
         )
         .await
         .map_err(StarknetCommandError::from),
-        Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
-        _ => Err(anyhow!("Unknown RPC error").into()),
+        Err(AccountError::Provider(ProviderError::StarknetError(
+            StarknetError::CompiledClassHashMismatch,
+        ))) => Err(compiled_class_hash_mismatch(
+            &declare.contract,
+            contract_artifacts,
+            compiled_class_hash,
+        )),
+        Err(error) => Err(declare_error(error)),
+    }
+}
+
+/// Whether the declaration sent in `attempt` with `--auto-bump-fee` should be sent again with a bumped fee.
+/// Fails if the fee can't be bumped enough to declare the contract
+fn should_bump_fee<S>(
+    declared: &Result<DeclareTransactionResult, AccountError<S>>,
+    attempt: u32,
+    max_attempts: NonZeroU32,
+) -> Result<bool> {
+    match declared {
+        Err(AccountError::Provider(ProviderError::StarknetError(
+            StarknetError::InsufficientMaxFee,
+        ))) if attempt < max_attempts.get() => Ok(true),
+        Err(AccountError::Provider(ProviderError::StarknetError(
+            StarknetError::InsufficientMaxFee,
+        ))) => Err(anyhow!(
+            "Max fee is smaller than the minimal transaction cost in all {attempt} attempts with --auto-bump-fee, pass a higher --max-bump-attempts or --fee-multiplier"
+        )),
+        // Bumping the fee only makes it harder to pay
+        Err(AccountError::Provider(ProviderError::StarknetError(
+            StarknetError::InsufficientAccountBalance,
+        ))) if attempt > 1 => Err(anyhow!(
+            "Account balance is too small to cover the fee bumped in attempt {attempt} with --auto-bump-fee, top up the account to declare the contract"
+        )),
+        _ => Ok(false),
+    }
+}
+
+/// Sends the declaration with the fee derived from `fee_limits`. `attempt` is set with `--auto-bump-fee`,
+/// the fee of every attempt is then reported to stderr, so it does not mix with the command response
+async fn send_declaration(
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    contract_class: Arc<FlattenedSierraClass>,
+    compiled_class_hash: Felt,
+    nonce: Option<Felt>,
+    fee_settings: FeeSettings,
+    fee_limits: &FeeLimits,
+    attempt: Option<u32>,
+) -> Result<Result<DeclareTransactionResult, AccountError<SignError>>, StarknetCommandError> {
+    match fee_settings {
+        FeeSettings::Eth { max_fee } => {
+            let declaration = account.declare_v2(contract_class, compiled_class_hash);
+            let declaration = apply_optional(declaration, nonce, DeclarationV2::nonce);
+
+            let is_max_fee_passed = max_fee.is_some();
+            let max_fee = fee_limits
+                .max_fee(max_fee, || async {
+                    declaration.estimate_fee().await.map_err(declare_error)
+                })
+                .await?;
+            match attempt {
+                Some(attempt) if is_max_fee_passed => {
+                    eprintln!("Declare attempt {attempt} with max fee = {max_fee}");
+                }
+                Some(attempt) => eprintln!(
+                    "Declare attempt {attempt} with max fee = {max_fee} (--fee-multiplier = {})",
+                    fee_limits.fee_multiplier
+                ),
+                None => {}
+            }
+
+            Ok(declaration.max_fee(max_fee).send().await)
+        }
+        FeeSettings::Strk {
+            max_gas,
+            max_gas_unit_price,
+        } => {
+            let declaration = account.declare_v3(contract_class, compiled_class_hash);
+            let declaration = apply_optional(declaration, nonce, DeclarationV3::nonce);
+
+            let (max_gas, max_gas_unit_price) = fee_limits
+                .resource_bounds(max_gas, max_gas_unit_price, || async {
+                    declaration.estimate_fee().await.map_err(declare_error)
+                })
+                .await?;
+            if let Some(attempt) = attempt {
+                eprintln!(
+                    "Declare attempt {attempt} with max gas = {max_gas}, max gas unit price = {max_gas_unit_price} (--fee-multiplier = {})",
+                    fee_limits.fee_multiplier
+                );
+            }
+
+            Ok(declaration
+                .gas(max_gas)
+                .gas_price(max_gas_unit_price)
+                .send()
+                .await)
+        }
+    }
+}
+
+/// Compiled class hash of the local CASM, the contract is compiled to CASM if it was loaded without it
+fn local_compiled_class_hash(
+    contract_name: &str,
+    contract_artifacts: &StarknetContractArtifacts,
+) -> Result<Felt> {
+    let casm = contract_artifacts.casm().map_err(|error| {
+        anyhow!(
+            "Failed to compile contract {contract_name} to CASM, which is needed to compute its compiled class hash: {error:#}\n\
+            Pass `--compiled-class-hash` to declare the contract without CASM"
+        )
+    })?;
+    let casm_contract_definition: CompiledClass =
+        serde_json::from_str(casm).context("Failed to parse casm artifact")?;
+
+    casm_contract_definition
+        .class_hash()
+        .map_err(anyhow::Error::from)
+}
+
+/// Nodes don't report the compiled class hash they expect, it is computed from CASM compiled from the Sierra
+/// with `universal-sierra-compiler`, which uses the latest compiler like the nodes do
+fn compiled_class_hash_mismatch(
+    contract_name: &str,
+    contract_artifacts: &StarknetContractArtifacts,
+    declared: Felt,
+) -> StarknetCommandError {
+    let hashes = local_compiled_class_hash(contract_name, contract_artifacts).and_then(|local| {
+        let recompiled = StarknetContractArtifacts::from_sierra(contract_artifacts.sierra.clone());
+        let expected = local_compiled_class_hash(contract_name, &recompiled)?;
+        Ok((local, expected))
+    });
+
+    match hashes {
+        Ok((local, expected)) => {
+            StarknetCommandError::CompiledClassHashMismatch(CompiledClassHashMismatchData {
+                declared,
+                local,
+                expected,
+            })
+        }
+        Err(error) => anyhow!(
+            "Compiled class hash {declared:#x} does not match the one computed by the node, the local one could not be computed: {error:#}"
+        )
+        .into(),
+    }
+}
+
+fn declare_error<S: std::fmt::Display>(error: AccountError<S>) -> StarknetCommandError {
+    match error {
+        AccountError::Provider(error) => StarknetCommandError::ProviderError(error.into()),
+        AccountError::Signing(error) => {
+            anyhow!("Failed to sign the declare transaction: {error}").into()
+        }
+        AccountError::ClassHashCalculation(error) => {
+            anyhow!("Failed to calculate the class hash of the contract: {error}").into()
+        }
+        AccountError::ClassCompression(error) => {
+            anyhow!("Failed to compress the contract class: {error}").into()
+        }
+        AccountError::FeeOutOfRange => {
+            anyhow!("Fee of the declare transaction is out of range").into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected(
+        error: StarknetError,
+    ) -> Result<DeclareTransactionResult, AccountError<&'static str>> {
+        Err(AccountError::Provider(ProviderError::StarknetError(error)))
+    }
+
+    fn attempts(attempts: u32) -> NonZeroU32 {
+        NonZeroU32::new(attempts).unwrap()
+    }
+
+    #[test]
+    fn fee_is_bumped_after_insufficient_max_fee() {
+        let declared = rejected(StarknetError::InsufficientMaxFee);
+
+        assert!(should_bump_fee(&declared, 1, attempts(3)).unwrap());
+        assert!(should_bump_fee(&declared, 2, attempts(3)).unwrap());
+    }
+
+    #[test]
+    fn fee_is_not_bumped_after_last_attempt() {
+        let declared = rejected(StarknetError::InsufficientMaxFee);
+
+        let error = should_bump_fee(&declared, 3, attempts(3)).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Max fee is smaller than the minimal transaction cost in all 3 attempts with --auto-bump-fee, pass a higher --max-bump-attempts or --fee-multiplier"
+        );
+    }
+
+    #[test]
+    fn insufficient_balance_after_bumping_is_reported() {
+        let declared = rejected(StarknetError::InsufficientAccountBalance);
+
+        let error = should_bump_fee(&declared, 2, attempts(3)).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Account balance is too small to cover the fee bumped in attempt 2 with --auto-bump-fee, top up the account to declare the contract"
+        );
+    }
+
+    #[test]
+    fn insufficient_balance_in_first_attempt_is_not_retried() {
+        let declared = rejected(StarknetError::InsufficientAccountBalance);
+
+        assert!(!should_bump_fee(&declared, 1, attempts(3)).unwrap());
+    }
+
+    #[test]
+    fn declared_contract_is_not_retried() {
+        let declared = Ok(DeclareTransactionResult {
+            transaction_hash: Felt::ONE,
+            class_hash: Felt::TWO,
+        });
+
+        assert!(!should_bump_fee::<&str>(&declared, 1, attempts(3)).unwrap());
+    }
+
+    #[test]
+    fn signing_error_is_reported() {
+        let error = declare_error(AccountError::Signing("invalid key"));
+
+        assert_eq!(
+            error.to_string(),
+            "Failed to sign the declare transaction: invalid key"
+        );
+    }
+
+    #[test]
+    fn fee_out_of_range_is_reported() {
+        let error = declare_error::<&str>(AccountError::FeeOutOfRange);
+
+        assert_eq!(
+            error.to_string(),
+            "Fee of the declare transaction is out of range"
+        );
     }
 }
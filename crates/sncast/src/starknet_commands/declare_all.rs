@@ -0,0 +1,194 @@
+use crate::starknet_commands::declare::{self, AutoBumpFeeArgs, Declare, DeclareVersion};
+use crate::starknet_commands::deploy_plan::{class_hash_for, is_declared, sierra_class_for};
+use anyhow::{Context, Result};
+use clap::Args;
+use itertools::Itertools;
+use scarb_api::contracts_filter::ContractsFilter;
+use scarb_api::StarknetContractArtifacts;
+use sncast::helpers::error::token_not_supported_for_declaration;
+use sncast::helpers::fee::{FeeArgs, FeeToken, PayableTransaction};
+use sncast::helpers::rpc::RpcArgs;
+use sncast::response::errors::handle_starknet_command_error;
+use sncast::response::structs::{
+    DeclareAllResponse, DeclareResponse, DeclaredContract, FailedContract, SkippedContract,
+};
+use sncast::{impl_payable_transaction, WaitForTx};
+use starknet::accounts::{ConnectedAccount, SingleOwnerAccount};
+use starknet::core::types::Felt;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::JsonRpcClient;
+use starknet::signers::LocalWallet;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Args)]
+#[command(about = "Declare multiple contracts to starknet, skipping the ones already declared", long_about = None)]
+pub struct DeclareAll {
+    /// Names of the contracts, declared in the given order
+    #[clap(
+        short = 'c',
+        long = "contract-name",
+        required_unless_present = "contracts_filter"
+    )]
+    pub contracts: Vec<String>,
+
+    /// Declare all contracts of the package which names match the glob pattern, e.g. `ERC20*`, in alphabetical order
+    #[clap(long, value_name = "GLOB", conflicts_with = "contracts")]
+    pub contracts_filter: Option<ContractsFilter>,
+
+    /// Stop at the first failed declaration instead of declaring the remaining contracts
+    #[clap(long)]
+    pub fail_fast: bool,
+
+    #[clap(flatten)]
+    pub fee_args: FeeArgs,
+
+    /// Specifies scarb package to be used
+    #[clap(long)]
+    pub package: Option<String>,
+
+    /// Version of the declarations (can be inferred from fee token)
+    #[clap(short, long)]
+    pub version: Option<DeclareVersion>,
+
+    #[clap(flatten)]
+    pub rpc: RpcArgs,
+}
+
+impl_payable_transaction!(DeclareAll, token_not_supported_for_declaration,
+    DeclareVersion::V2 => FeeToken::Eth,
+    DeclareVersion::V3 => FeeToken::Strk
+);
+
+/// Declares the contracts one by one with the same account and fee settings,
+/// fee of each declaration is estimated separately unless it is set explicitly
+pub async fn declare_all(
+    declare_all: DeclareAll,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    wait_config: WaitForTx,
+) -> Result<DeclareAllResponse> {
+    let fee_args = declare_all
+        .fee_args
+        .clone()
+        .fee_token(declare_all.token_from_version());
+
+    // Transactions are sent without waiting for the previous ones, so the nonce is tracked here
+    let mut nonce = get_nonce(account).await?;
+    let mut declared_class_hashes = HashSet::new();
+    let mut response = DeclareAllResponse::default();
+
+    let contract_names = match &declare_all.contracts_filter {
+        Some(contracts_filter) => {
+            contracts_filter.ensure_matches_any(artifacts.keys().map(String::as_str))?;
+            artifacts
+                .keys()
+                .filter(|name| contracts_filter.matches(name))
+                .cloned()
+                .sorted()
+                .collect()
+        }
+        None => declare_all.contracts,
+    };
+
+    let mut contracts = contract_names.into_iter();
+    while let Some(contract_name) = contracts.next() {
+        let declared = declare_if_needed(
+            &contract_name,
+            &declared_class_hashes,
+            nonce,
+            account,
+            artifacts,
+            &fee_args,
+            wait_config,
+        )
+        .await;
+
+        match declared {
+            Ok(Declaration::Declared(DeclareResponse {
+                class_hash,
+                transaction_hash,
+            })) => {
+                nonce += Felt::ONE;
+                declared_class_hashes.insert(class_hash);
+                response.declared.push(DeclaredContract(
+                    contract_name,
+                    class_hash,
+                    transaction_hash,
+                ));
+            }
+            Ok(Declaration::Skipped(class_hash)) => {
+                response
+                    .skipped
+                    .push(SkippedContract(contract_name, class_hash));
+            }
+            Err(error) => {
+                response
+                    .failed
+                    .push(FailedContract(contract_name, format!("{error:#}")));
+
+                if declare_all.fail_fast {
+                    response.not_attempted = contracts.collect();
+                    break;
+                }
+                // The transaction could have been sent before failing, e.g. if it was reverted
+                nonce = get_nonce(account).await?;
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+enum Declaration {
+    Declared(DeclareResponse),
+    /// Class hash of the contract, its class is already declared
+    Skipped(Felt),
+}
+
+/// Declares the contract unless its class is already declared
+async fn declare_if_needed(
+    contract_name: &str,
+    declared_class_hashes: &HashSet<Felt>,
+    nonce: Felt,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    fee_args: &FeeArgs,
+    wait_config: WaitForTx,
+) -> Result<Declaration> {
+    let class_hash = class_hash_for(&sierra_class_for(contract_name, artifacts)?)?;
+
+    if declared_class_hashes.contains(&class_hash)
+        || is_declared(class_hash, account.provider()).await?
+    {
+        return Ok(Declaration::Skipped(class_hash));
+    }
+
+    let declared = declare::declare(
+        Declare {
+            contract: contract_name.to_string(),
+            fee_args: fee_args.clone(),
+            nonce: Some(nonce),
+            package: None,
+            version: None,
+            bump_fee: AutoBumpFeeArgs::default(),
+            compiled_class_hash: None,
+            rpc: RpcArgs::default(),
+        },
+        account,
+        artifacts,
+        wait_config,
+    )
+    .await
+    .map_err(handle_starknet_command_error)?;
+
+    Ok(Declaration::Declared(declared))
+}
+
+async fn get_nonce(
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+) -> Result<Felt> {
+    account
+        .get_nonce()
+        .await
+        .context("Failed to get the nonce of the account")
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use camino::Utf8PathBuf;
+use clap::Args;
+use data_transformer::reverse_transform;
+use sncast::helpers::abi::read_abi_file;
+use sncast::response::structs::DecodeResponse;
+use starknet::core::types::Felt;
+
+#[derive(Args)]
+#[command(about = "Decode felts into a value of a type from the ABI", long_about = None)]
+pub struct Decode {
+    /// Path to a JSON file with the ABI, or a Sierra contract class, containing the decoded type
+    #[clap(long)]
+    pub abi_file: Utf8PathBuf,
+
+    /// Name of the decoded type, e.g. `core::integer::u256` or a struct or an enum name from the ABI
+    #[clap(long = "type")]
+    pub type_name: String,
+
+    /// Serialized value as a series of felts
+    #[clap(long, value_delimiter = ' ', num_args = 1..)]
+    pub data: Vec<Felt>,
+}
+
+pub fn decode(decode: &Decode) -> Result<DecodeResponse> {
+    let abi = read_abi_file(&decode.abi_file)?;
+    let response = reverse_transform(&decode.data, &decode.type_name, &abi)?;
+
+    Ok(DecodeResponse { response })
+}
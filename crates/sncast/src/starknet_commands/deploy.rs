@@ -1,19 +1,26 @@
-use anyhow::{anyhow, Result};
+use crate::starknet_commands::invoke::invoke;
+use anyhow::{anyhow, Context, Result};
+use camino::Utf8PathBuf;
 use clap::{Args, ValueEnum};
+use sncast::helpers::address_book::validate_name;
 use sncast::helpers::error::token_not_supported_for_deployment;
-use sncast::helpers::fee::{FeeArgs, FeeSettings, FeeToken, PayableTransaction};
+use sncast::helpers::fee::{FeeArgs, FeeLimits, FeeSettings, FeeToken, PayableTransaction};
 use sncast::helpers::rpc::RpcArgs;
-use sncast::response::errors::StarknetCommandError;
-use sncast::response::structs::DeployResponse;
-use sncast::{extract_or_generate_salt, impl_payable_transaction, udc_uniqueness};
+use sncast::response::errors::{
+    handle_account_error, handle_starknet_command_error, StarknetCommandError,
+};
+use sncast::response::structs::{DeployResponse, DeployWithInitResponse};
+use sncast::{
+    extract_or_generate_salt, handle_rpc_error, impl_payable_transaction, udc_uniqueness,
+};
 use sncast::{handle_wait_for_tx, WaitForTx};
 use starknet::accounts::AccountError::Provider;
 use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
 use starknet::contract::ContractFactory;
-use starknet::core::types::Felt;
+use starknet::core::types::{BlockId, BlockTag, ContractClass, Felt, StarknetError};
 use starknet::core::utils::get_udc_deployed_address;
 use starknet::providers::jsonrpc::HttpTransport;
-use starknet::providers::JsonRpcClient;
+use starknet::providers::{JsonRpcClient, Provider as _, ProviderError};
 use starknet::signers::LocalWallet;
 
 #[derive(Args)]
@@ -35,6 +42,22 @@ pub struct Deploy {
     #[clap(long)]
     pub unique: bool,
 
+    /// Save the address of the deployed contract in the address book under this name
+    #[clap(long, value_parser = parse_address_book_name)]
+    pub save_as: Option<String>,
+
+    /// Name of the function to invoke on the contract once its deployment is accepted
+    #[clap(long)]
+    pub init: Option<String>,
+
+    /// Arguments of the initializer function (serialized as a series of felts or written as comma-separated expressions in Cairo syntax)
+    #[clap(long, value_delimiter = ' ', num_args = 1.., requires = "init")]
+    pub init_calldata: Option<Vec<String>>,
+
+    /// Path to a JSON file with the ABI, or a Sierra contract class, used to encode the calldata instead of the ABI of the declared class
+    #[clap(long)]
+    pub abi_file: Option<Utf8PathBuf>,
+
     #[clap(flatten)]
     pub fee_args: FeeArgs,
 
@@ -61,6 +84,11 @@ impl_payable_transaction!(Deploy, token_not_supported_for_deployment,
     DeployVersion::V3 => FeeToken::Strk
 );
 
+fn parse_address_book_name(name: &str) -> Result<String> {
+    validate_name(name)?;
+    Ok(name.to_string())
+}
+
 #[allow(clippy::ptr_arg, clippy::too_many_arguments)]
 pub async fn deploy(
     class_hash: Felt,
@@ -68,6 +96,7 @@ pub async fn deploy(
     salt: Option<Felt>,
     unique: bool,
     fee_settings: FeeSettings,
+    fee_limits: FeeLimits,
     nonce: Option<Felt>,
     account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
     wait_config: WaitForTx,
@@ -77,35 +106,40 @@ pub async fn deploy(
     let result = match fee_settings {
         FeeSettings::Eth { max_fee } => {
             let execution = factory.deploy_v1(calldata.clone(), salt, unique);
-            let execution = match max_fee {
-                None => execution,
-                Some(max_fee) => execution.max_fee(max_fee),
-            };
             let execution = match nonce {
                 None => execution,
                 Some(nonce) => execution.nonce(nonce),
             };
-            execution.send().await
+
+            let max_fee = fee_limits
+                .max_fee(max_fee, || async {
+                    execution.estimate_fee().await.map_err(handle_account_error)
+                })
+                .await?;
+
+            execution.max_fee(max_fee).send().await
         }
         FeeSettings::Strk {
             max_gas,
             max_gas_unit_price,
         } => {
             let execution = factory.deploy_v3(calldata.clone(), salt, unique);
-
-            let execution = match max_gas {
-                None => execution,
-                Some(max_gas) => execution.gas(max_gas),
-            };
-            let execution = match max_gas_unit_price {
-                None => execution,
-                Some(max_gas_unit_price) => execution.gas_price(max_gas_unit_price),
-            };
             let execution = match nonce {
                 None => execution,
                 Some(nonce) => execution.nonce(nonce),
             };
-            execution.send().await
+
+            let (max_gas, max_gas_unit_price) = fee_limits
+                .resource_bounds(max_gas, max_gas_unit_price, || async {
+                    execution.estimate_fee().await.map_err(handle_account_error)
+                })
+                .await?;
+
+            execution
+                .gas(max_gas)
+                .gas_price(max_gas_unit_price)
+                .send()
+                .await
         }
     };
 
@@ -126,7 +160,71 @@ pub async fn deploy(
         )
         .await
         .map_err(StarknetCommandError::from),
+        Err(Provider(ProviderError::StarknetError(StarknetError::ClassHashNotFound))) => {
+            Err(class_not_declared(class_hash).into())
+        }
         Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
         _ => Err(anyhow!("Unknown RPC error").into()),
     }
 }
+
+/// Gets the class of the contract to deploy, suggesting to declare it if it does not exist
+pub async fn get_class_to_deploy(
+    class_hash: Felt,
+    provider: &JsonRpcClient<HttpTransport>,
+) -> Result<ContractClass> {
+    match provider
+        .get_class(BlockId::Tag(BlockTag::Latest), class_hash)
+        .await
+    {
+        Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => {
+            Err(class_not_declared(class_hash))
+        }
+        result => result.map_err(handle_rpc_error),
+    }
+}
+
+fn class_not_declared(class_hash: Felt) -> anyhow::Error {
+    anyhow!(
+        "Class with hash {class_hash:#x} is not declared, declare it first with `sncast declare --contract-name <CONTRACT_NAME>`"
+    )
+}
+
+/// Invokes the initializer on the deployed contract.
+/// The contract stays deployed if the invoke fails, so the error includes its address.
+pub async fn initialize(
+    deployed: DeployResponse,
+    selector: Felt,
+    calldata: Vec<Felt>,
+    fee_args: FeeArgs,
+    nonce: Option<Felt>,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    wait_config: WaitForTx,
+) -> Result<DeployWithInitResponse> {
+    let DeployResponse {
+        contract_address,
+        transaction_hash,
+    } = deployed;
+
+    invoke(
+        contract_address,
+        calldata,
+        nonce,
+        fee_args,
+        selector,
+        account,
+        wait_config,
+    )
+    .await
+    .map(|init| DeployWithInitResponse {
+        contract_address,
+        transaction_hash,
+        init_transaction_hash: init.transaction_hash,
+    })
+    .map_err(handle_starknet_command_error)
+    .with_context(|| {
+        format!(
+            "Contract has been deployed at address {contract_address:#x} (transaction hash: {transaction_hash:#x}), but invoking its initializer failed"
+        )
+    })
+}
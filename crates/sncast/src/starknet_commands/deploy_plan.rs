@@ -0,0 +1,776 @@
+use crate::starknet_commands::declare::{self, AutoBumpFeeArgs, Declare};
+use crate::starknet_commands::deploy::{self, DeployVersion};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use data_transformer::{get_abi, validate_constructor_calldata, Calldata};
+use scarb_api::StarknetContractArtifacts;
+use serde::{Deserialize, Serialize};
+use sncast::helpers::error::token_not_supported_for_deployment;
+use sncast::helpers::fee::{FeeArgs, FeeSettings, FeeToken, PayableTransaction};
+use sncast::helpers::rpc::RpcArgs;
+use sncast::helpers::scarb_utils::get_contract_artifacts;
+use sncast::response::errors::{handle_starknet_command_error, StarknetCommandError};
+use sncast::response::structs::{
+    DeployPlanDryRunResponse, DeployPlanResponse, PlannedDeployment, PlannedStep,
+};
+use sncast::{
+    extract_or_generate_salt, get_contract_class, impl_payable_transaction, udc_uniqueness,
+    WaitForTx,
+};
+use starknet::accounts::{Account, AccountError, ConnectedAccount, SingleOwnerAccount};
+use starknet::contract::ContractFactory;
+use starknet::core::types::contract::{CompiledClass, SierraClass};
+use starknet::core::types::{BlockId, BlockTag, ContractClass, FeeEstimate, Felt, StarknetError};
+use starknet::core::utils::{get_selector_from_name, get_udc_deployed_address};
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider, ProviderError};
+use starknet::signers::LocalWallet;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::sync::Arc;
+
+#[derive(Args)]
+#[command(about = "Declare and deploy contracts listed in a deployment plan", long_about = None)]
+pub struct DeployPlan {
+    /// Path to the toml file with the deployment plan
+    #[clap(short = 'p', long = "path")]
+    pub path: Utf8PathBuf,
+
+    /// Path to the file results are written to, defaults to `<plan file name>_results.toml` next to the plan
+    #[clap(long)]
+    pub results_path: Option<Utf8PathBuf>,
+
+    /// Specifies scarb package to be used
+    #[clap(long)]
+    pub package: Option<String>,
+
+    #[clap(flatten)]
+    pub fee_args: FeeArgs,
+
+    /// Version of the transactions (can be inferred from fee token)
+    #[clap(short, long)]
+    pub version: Option<DeployVersion>,
+
+    /// Check which classes have to be declared and estimate fees of the steps, without sending any transactions
+    #[clap(long)]
+    pub dry_run: bool,
+
+    #[clap(flatten)]
+    pub rpc: RpcArgs,
+}
+
+impl_payable_transaction!(DeployPlan, token_not_supported_for_deployment,
+    DeployVersion::V1 => FeeToken::Eth,
+    DeployVersion::V3 => FeeToken::Strk
+);
+
+impl DeployPlan {
+    #[must_use]
+    pub fn results_path(&self) -> Utf8PathBuf {
+        self.results_path.clone().unwrap_or_else(|| {
+            let stem = self.path.file_stem().unwrap_or("deployment");
+            self.path.with_file_name(format!("{stem}_results.toml"))
+        })
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct PlanFile {
+    #[serde(default, rename = "contract")]
+    contracts: Vec<PlannedContract>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct PlannedContract {
+    pub id: String,
+    /// Name of the contract to be declared from the scarb package
+    pub contract_name: Option<String>,
+    /// Class hash of an already declared contract
+    pub class_hash: Option<Felt>,
+    #[serde(default)]
+    pub constructor_calldata: Vec<String>,
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    pub salt: Option<Felt>,
+    #[serde(default)]
+    pub unique: bool,
+}
+
+impl PlannedContract {
+    /// Contracts that have to be deployed first, listed in `depends_on` or referenced in the calldata
+    fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.depends_on.iter().map(String::as_str).chain(
+            self.constructor_calldata
+                .iter()
+                .filter_map(|input| input.strip_prefix('@')),
+        )
+    }
+}
+
+/// Contracts of the plan, sorted so every contract comes after its dependencies
+#[derive(Debug)]
+pub struct DeploymentPlan {
+    pub steps: Vec<PlannedContract>,
+}
+
+impl DeploymentPlan {
+    pub fn load(path: &Utf8PathBuf) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read deployment plan at = {path}"))?;
+        Self::parse(&contents).with_context(|| format!("Invalid deployment plan at = {path}"))
+    }
+
+    fn parse(contents: &str) -> Result<Self> {
+        let PlanFile { contracts } = toml::from_str(contents)?;
+        ensure!(
+            !contracts.is_empty(),
+            "Deployment plan does not contain any contracts"
+        );
+
+        let mut ids = HashSet::new();
+        for contract in &contracts {
+            let id = &contract.id;
+            ensure!(
+                !id.is_empty()
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+                "Invalid contract id = {id}, only alphanumeric characters, `_` and `-` are allowed"
+            );
+            ensure!(ids.insert(id.as_str()), "Duplicated contract id = {id}");
+            ensure!(
+                contract.contract_name.is_some() != contract.class_hash.is_some(),
+                "Contract with id = {id} must specify exactly one of `contract_name` or `class_hash`"
+            );
+        }
+
+        for contract in &contracts {
+            for dependency in contract.dependencies() {
+                ensure!(
+                    ids.contains(dependency),
+                    "Contract with id = {} depends on unknown contract with id = {dependency}",
+                    contract.id
+                );
+            }
+        }
+
+        Ok(Self {
+            steps: sort_by_dependencies(contracts)?,
+        })
+    }
+
+    #[must_use]
+    pub fn declares_contracts(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|contract| contract.contract_name.is_some())
+    }
+}
+
+/// Orders contracts topologically, keeping the order from the plan wherever dependencies allow it
+fn sort_by_dependencies(mut remaining: Vec<PlannedContract>) -> Result<Vec<PlannedContract>> {
+    let mut sorted: Vec<PlannedContract> = Vec::with_capacity(remaining.len());
+    let mut sorted_ids = HashSet::new();
+
+    while !remaining.is_empty() {
+        let Some(position) = remaining.iter().position(|contract| {
+            contract
+                .dependencies()
+                .all(|dependency| sorted_ids.contains(dependency))
+        }) else {
+            bail!(
+                "Deployment plan contains a dependency cycle: {}",
+                find_cycle(&remaining).join(" -> ")
+            );
+        };
+
+        let contract = remaining.remove(position);
+        sorted_ids.insert(contract.id.clone());
+        sorted.push(contract);
+    }
+
+    Ok(sorted)
+}
+
+/// Every contract left unsorted depends on another unsorted one, so following them must loop
+fn find_cycle(remaining: &[PlannedContract]) -> Vec<String> {
+    let by_id: HashMap<&str, &PlannedContract> = remaining
+        .iter()
+        .map(|contract| (contract.id.as_str(), contract))
+        .collect();
+
+    let mut path: Vec<&str> = vec![];
+    let mut current = &remaining[0];
+    loop {
+        if let Some(start) = path.iter().position(|id| *id == current.id) {
+            let mut cycle: Vec<String> = path[start..].iter().map(ToString::to_string).collect();
+            cycle.push(current.id.clone());
+            return cycle;
+        }
+        path.push(&current.id);
+        current = current
+            .dependencies()
+            .find_map(|dependency| by_id.get(dependency).copied())
+            .expect("Unsorted contract must depend on another unsorted contract");
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct DeploymentResults {
+    #[serde(rename = "contract")]
+    contracts: Vec<DeployedContract>,
+}
+
+#[derive(Serialize, Debug)]
+struct DeployedContract {
+    id: String,
+    class_hash: Felt,
+    contract_address: Felt,
+    declare_transaction_hash: Option<Felt>,
+    deploy_transaction_hash: Felt,
+}
+
+impl DeploymentResults {
+    fn save(&self, path: &Utf8PathBuf) -> Result<()> {
+        let contents =
+            toml::to_string_pretty(self).context("Failed to serialize deployment results")?;
+        fs::write(path, contents)
+            .with_context(|| format!("Failed to write deployment results at = {path}"))
+    }
+}
+
+pub async fn deploy_plan(
+    deploy_plan: DeployPlan,
+    plan: DeploymentPlan,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    wait_config: WaitForTx,
+) -> Result<DeployPlanResponse> {
+    let fee_args = deploy_plan
+        .fee_args
+        .clone()
+        .fee_token(deploy_plan.token_from_version());
+    let results_path = deploy_plan.results_path();
+
+    // Later steps may use contracts from the earlier ones, so each transaction must be accepted first
+    let wait_config = WaitForTx {
+        wait: true,
+        ..wait_config
+    };
+
+    let mut results = DeploymentResults { contracts: vec![] };
+    let mut addresses = HashMap::new();
+
+    for contract in plan.steps {
+        let id = contract.id.clone();
+        let deployed = execute_step(contract, &addresses, account, artifacts, &fee_args, wait_config)
+            .await
+            .with_context(|| {
+                format!(
+                    "Deployment plan failed at step with id = {id}, results of completed steps were saved to {results_path}"
+                )
+            })?;
+
+        addresses.insert(id, deployed.contract_address);
+        results.contracts.push(deployed);
+        results.save(&results_path)?;
+    }
+
+    Ok(DeployPlanResponse {
+        contracts: results
+            .contracts
+            .iter()
+            .map(|contract| {
+                PlannedDeployment(
+                    contract.id.clone(),
+                    contract.class_hash,
+                    contract.contract_address,
+                )
+            })
+            .collect(),
+        results_file: results_path,
+    })
+}
+
+/// Checks the plan against the network without sending any transactions.
+/// Deploy fees can only be estimated for classes which are already declared.
+#[allow(clippy::too_many_lines)]
+pub async fn dry_run(
+    deploy_plan: DeployPlan,
+    plan: DeploymentPlan,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+) -> Result<DeployPlanDryRunResponse> {
+    let provider = account.provider();
+    let fee_settings = deploy_plan
+        .fee_args
+        .clone()
+        .fee_token(deploy_plan.token_from_version())
+        .try_into_fee_settings(provider, account.block_id())
+        .await?;
+
+    let mut steps = vec![];
+    let mut total_fee = Felt::ZERO;
+    let mut not_estimated = 0;
+    let mut declared_in_plan = HashSet::new();
+    let mut addresses = HashMap::new();
+
+    for contract in plan.steps {
+        let id = contract.id.clone();
+        let mut add_step = |action: &str, class_hash: Felt, fee: Result<Felt>| {
+            let fee = match fee {
+                Ok(fee) => {
+                    total_fee += fee;
+                    fee.to_string()
+                }
+                Err(error) => {
+                    not_estimated += 1;
+                    format!("not estimated: {}", error.root_cause())
+                }
+            };
+            steps.push(PlannedStep(id.clone(), action.to_string(), class_hash, fee));
+        };
+
+        let (class_hash, contract_class, is_on_chain) = match &contract.contract_name {
+            Some(contract_name) => {
+                let sierra_class = sierra_class_for(contract_name, artifacts)?;
+                let class_hash = class_hash_for(&sierra_class)?;
+                let is_on_chain = is_declared(class_hash, provider).await?;
+
+                if is_on_chain {
+                    add_step(
+                        "skip declare (class already declared)",
+                        class_hash,
+                        Ok(Felt::ZERO),
+                    );
+                } else if declared_in_plan.insert(class_hash) {
+                    let fee =
+                        estimate_declare_fee(contract_name, artifacts, &fee_settings, account)
+                            .await;
+                    add_step("declare", class_hash, fee);
+                } else {
+                    add_step(
+                        "skip declare (class declared in an earlier step)",
+                        class_hash,
+                        Ok(Felt::ZERO),
+                    );
+                }
+
+                let contract_class =
+                    ContractClass::Sierra(sierra_class.flatten().map_err(anyhow::Error::from)?);
+                (class_hash, contract_class, is_on_chain)
+            }
+            None => {
+                // Safe to unwrap, validated when loading the plan
+                let class_hash = contract.class_hash.unwrap();
+                let contract_class = get_contract_class(class_hash, provider).await?;
+                (class_hash, contract_class, true)
+            }
+        };
+
+        let calldata =
+            constructor_calldata(&contract.constructor_calldata, &addresses, contract_class)
+                .with_context(|| format!("Invalid constructor calldata of step with id = {id}"))?;
+        // Dependent steps only need some address, the real one differs unless the salt is set
+        let salt = extract_or_generate_salt(contract.salt);
+        addresses.insert(
+            id.clone(),
+            get_udc_deployed_address(
+                salt,
+                class_hash,
+                &udc_uniqueness(contract.unique, account.address()),
+                &calldata,
+            ),
+        );
+
+        let fee = if is_on_chain {
+            estimate_deploy_fee(
+                class_hash,
+                calldata,
+                salt,
+                contract.unique,
+                &fee_settings,
+                account,
+            )
+            .await
+        } else {
+            Err(anyhow!("class is not declared yet"))
+        };
+        add_step("deploy", class_hash, fee);
+    }
+
+    let mut message = "Dry run, no transactions were sent".to_string();
+    if not_estimated > 0 {
+        message.push_str(&format!(
+            ", fees of {not_estimated} step(s) could not be estimated and are not included in the total"
+        ));
+    }
+
+    Ok(DeployPlanDryRunResponse {
+        steps,
+        total_estimated_fee: total_fee,
+        fee_unit: match fee_settings {
+            FeeSettings::Eth { .. } => "wei",
+            FeeSettings::Strk { .. } => "fri",
+        }
+        .to_string(),
+        message,
+    })
+}
+
+async fn estimate_declare_fee(
+    contract_name: &str,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    fee_settings: &FeeSettings,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+) -> Result<Felt> {
+    let sierra_class = Arc::new(
+        sierra_class_for(contract_name, artifacts)?
+            .flatten()
+            .map_err(anyhow::Error::from)?,
+    );
+    // Artifacts of the contract are present, as its sierra class was already loaded
+    let casm = artifacts[contract_name]
+        .casm()
+        .with_context(|| format!("Failed to compile contract {contract_name} to CASM"))?;
+    let casm_class: CompiledClass =
+        serde_json::from_str(casm).context("Failed to parse casm artifact")?;
+    let casm_class_hash = casm_class.class_hash().map_err(anyhow::Error::from)?;
+
+    let estimate = match fee_settings {
+        FeeSettings::Eth { .. } => {
+            account
+                .declare_v2(sierra_class, casm_class_hash)
+                .estimate_fee()
+                .await
+        }
+        FeeSettings::Strk { .. } => {
+            account
+                .declare_v3(sierra_class, casm_class_hash)
+                .estimate_fee()
+                .await
+        }
+    };
+
+    estimated_fee(estimate)
+}
+
+async fn estimate_deploy_fee(
+    class_hash: Felt,
+    calldata: Vec<Felt>,
+    salt: Felt,
+    unique: bool,
+    fee_settings: &FeeSettings,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+) -> Result<Felt> {
+    let factory = ContractFactory::new(class_hash, account);
+
+    let estimate = match fee_settings {
+        FeeSettings::Eth { .. } => {
+            factory
+                .deploy_v1(calldata, salt, unique)
+                .estimate_fee()
+                .await
+        }
+        FeeSettings::Strk { .. } => {
+            factory
+                .deploy_v3(calldata, salt, unique)
+                .estimate_fee()
+                .await
+        }
+    };
+
+    estimated_fee(estimate)
+}
+
+fn estimated_fee<S>(estimate: Result<FeeEstimate, AccountError<S>>) -> Result<Felt> {
+    match estimate {
+        Ok(estimate) => Ok(estimate.overall_fee),
+        Err(AccountError::Provider(error)) => Err(handle_starknet_command_error(
+            StarknetCommandError::ProviderError(error.into()),
+        )),
+        _ => Err(anyhow!("Unknown RPC error")),
+    }
+}
+
+async fn execute_step(
+    contract: PlannedContract,
+    addresses: &HashMap<String, Felt>,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    fee_args: &FeeArgs,
+    wait_config: WaitForTx,
+) -> Result<DeployedContract> {
+    let (class_hash, declare_transaction_hash) = match (contract.class_hash, contract.contract_name)
+    {
+        (Some(class_hash), _) => (class_hash, None),
+        (None, Some(contract_name)) => {
+            declare_if_needed(contract_name, account, artifacts, fee_args, wait_config).await?
+        }
+        (None, None) => unreachable!("Validated when loading the plan"),
+    };
+
+    let provider = account.provider();
+
+    let contract_class = get_contract_class(class_hash, provider).await?;
+    let calldata = constructor_calldata(&contract.constructor_calldata, addresses, contract_class)?;
+
+    let fee_settings = fee_args
+        .try_into_fee_settings(provider, account.block_id())
+        .await?;
+
+    let deployed = deploy::deploy(
+        class_hash,
+        &calldata,
+        contract.salt,
+        contract.unique,
+        fee_settings,
+        fee_args.fee_limits(),
+        None,
+        account,
+        wait_config,
+    )
+    .await
+    .map_err(handle_starknet_command_error)?;
+
+    Ok(DeployedContract {
+        id: contract.id,
+        class_hash,
+        contract_address: deployed.contract_address,
+        declare_transaction_hash,
+        deploy_transaction_hash: deployed.transaction_hash,
+    })
+}
+
+/// Declares the contract unless its class is already declared, returns its class hash
+/// and the hash of the declare transaction, if one was sent
+async fn declare_if_needed(
+    contract_name: String,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+    fee_args: &FeeArgs,
+    wait_config: WaitForTx,
+) -> Result<(Felt, Option<Felt>)> {
+    let class_hash = class_hash_for(&sierra_class_for(&contract_name, artifacts)?)?;
+
+    if is_declared(class_hash, account.provider()).await? {
+        return Ok((class_hash, None));
+    }
+
+    let declared = declare::declare(
+        Declare {
+            contract: contract_name,
+            fee_args: fee_args.clone(),
+            nonce: None,
+            package: None,
+            version: None,
+            bump_fee: AutoBumpFeeArgs::default(),
+            compiled_class_hash: None,
+            rpc: RpcArgs::default(),
+        },
+        account,
+        artifacts,
+        wait_config,
+    )
+    .await
+    .map_err(handle_starknet_command_error)?;
+
+    Ok((declared.class_hash, Some(declared.transaction_hash)))
+}
+
+/// Serializes the constructor calldata, replacing references to other contracts with their addresses
+fn constructor_calldata(
+    inputs: &[String],
+    addresses: &HashMap<String, Felt>,
+    contract_class: ContractClass,
+) -> Result<Vec<Felt>> {
+    // safe to unwrap because "constructor" is a standardized name
+    let selector = get_selector_from_name("constructor").unwrap();
+    let abi = get_abi(&contract_class);
+
+    let inputs = inputs
+        .iter()
+        .map(|input| match input.strip_prefix('@') {
+            // Dependencies are always deployed before, so the address is present
+            Some(dependency) => format!("{:#x}", addresses[dependency]),
+            None => input.clone(),
+        })
+        .collect::<Vec<_>>();
+    let calldata = Calldata::from(inputs).serialized(contract_class, &selector)?;
+
+    if let Some(abi) = abi {
+        validate_constructor_calldata(&calldata, &abi)?;
+    }
+
+    Ok(calldata)
+}
+
+pub(crate) fn sierra_class_for(
+    contract_name: &str,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+) -> Result<SierraClass> {
+    let contract_artifacts =
+        get_contract_artifacts(artifacts, contract_name).map_err(handle_starknet_command_error)?;
+
+    serde_json::from_str(&contract_artifacts.sierra).context("Failed to parse sierra artifact")
+}
+
+pub(crate) fn class_hash_for(sierra_class: &SierraClass) -> Result<Felt> {
+    sierra_class.class_hash().map_err(anyhow::Error::from)
+}
+
+pub(crate) async fn is_declared(
+    class_hash: Felt,
+    provider: &JsonRpcClient<HttpTransport>,
+) -> Result<bool> {
+    match provider
+        .get_class(BlockId::Tag(BlockTag::Pending), class_hash)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(ProviderError::StarknetError(StarknetError::ClassHashNotFound)) => Ok(false),
+        Err(error) => Err(anyhow!(error).context("Failed to check if class is declared")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    fn ids(plan: &DeploymentPlan) -> Vec<&str> {
+        plan.steps
+            .iter()
+            .map(|contract| contract.id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn dependencies_are_deployed_first() {
+        let plan = DeploymentPlan::parse(indoc! {r#"
+            [[contract]]
+            id = "token"
+            contract_name = "Token"
+            constructor_calldata = ["@registry", "0x10"]
+
+            [[contract]]
+            id = "registry"
+            class_hash = "0x123"
+
+            [[contract]]
+            id = "exchange"
+            contract_name = "Exchange"
+            depends_on = ["vault"]
+
+            [[contract]]
+            id = "vault"
+            contract_name = "Vault"
+            salt = "0x1"
+            unique = true
+        "#})
+        .unwrap();
+
+        assert_eq!(ids(&plan), ["registry", "token", "vault", "exchange"]);
+        assert!(plan.declares_contracts());
+    }
+
+    #[test]
+    fn independent_contracts_keep_plan_order() {
+        let plan = DeploymentPlan::parse(indoc! {r#"
+            [[contract]]
+            id = "b"
+            class_hash = "0x1"
+
+            [[contract]]
+            id = "a"
+            class_hash = "0x2"
+        "#})
+        .unwrap();
+
+        assert_eq!(ids(&plan), ["b", "a"]);
+        assert!(!plan.declares_contracts());
+    }
+
+    #[test]
+    fn cycle_is_reported() {
+        let error = DeploymentPlan::parse(indoc! {r#"
+            [[contract]]
+            id = "standalone"
+            class_hash = "0x1"
+
+            [[contract]]
+            id = "a"
+            class_hash = "0x1"
+            depends_on = ["b"]
+
+            [[contract]]
+            id = "b"
+            class_hash = "0x1"
+            constructor_calldata = ["@c"]
+
+            [[contract]]
+            id = "c"
+            class_hash = "0x1"
+            depends_on = ["b"]
+        "#})
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Deployment plan contains a dependency cycle: b -> c -> b"
+        );
+    }
+
+    #[test]
+    fn invalid_plans() {
+        let cases = [
+            ("", "Deployment plan does not contain any contracts"),
+            (
+                indoc! {r#"
+                    [[contract]]
+                    id = "a"
+                    class_hash = "0x1"
+
+                    [[contract]]
+                    id = "a"
+                    class_hash = "0x2"
+                "#},
+                "Duplicated contract id = a",
+            ),
+            (
+                indoc! {r#"
+                    [[contract]]
+                    id = "a"
+                    class_hash = "0x1"
+                    contract_name = "A"
+                "#},
+                "Contract with id = a must specify exactly one of `contract_name` or `class_hash`",
+            ),
+            (
+                indoc! {r#"
+                    [[contract]]
+                    id = "a"
+                    class_hash = "0x1"
+                    constructor_calldata = ["@b"]
+                "#},
+                "Contract with id = a depends on unknown contract with id = b",
+            ),
+            (
+                indoc! {r#"
+                    [[contract]]
+                    id = "a b"
+                    class_hash = "0x1"
+                "#},
+                "Invalid contract id = a b, only alphanumeric characters, `_` and `-` are allowed",
+            ),
+        ];
+
+        for (contents, expected) in cases {
+            let error = DeploymentPlan::parse(contents).unwrap_err();
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+}
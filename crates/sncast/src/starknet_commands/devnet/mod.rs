@@ -0,0 +1,90 @@
+use crate::starknet_commands::devnet::start::Start;
+use crate::starknet_commands::devnet::stop::Stop;
+use anyhow::{Context, Result};
+use camino::Utf8PathBuf;
+use clap::{Args, Subcommand};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+
+pub mod start;
+pub mod stop;
+
+#[derive(Args)]
+#[command(about = "Start and stop a local devnet instance")]
+pub struct Devnet {
+    #[clap(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    Start(Start),
+    Stop(Stop),
+}
+
+pub const DEFAULT_DEVNET_PORT: u16 = 5050;
+
+/// Devnet started by `sncast devnet start`, persisted so it can be stopped from a different shell
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DevnetState {
+    pub url: String,
+    pub accounts_file: Utf8PathBuf,
+    pub process: DevnetProcess,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DevnetProcess {
+    /// `binary` is checked against the process before stopping it, as its `pid` may be reused by the system
+    Binary {
+        pid: u32,
+        binary: Utf8PathBuf,
+    },
+    Docker {
+        container: String,
+    },
+}
+
+/// Directory with the state files, logs and default accounts files of devnets started by sncast
+fn state_dir() -> Result<Utf8PathBuf> {
+    let dir = Utf8PathBuf::try_from(env::temp_dir())
+        .context("Path to the temporary directory is not valid UTF-8")?
+        .join("sncast-devnet");
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create directory {dir}"))?;
+
+    Ok(dir)
+}
+
+fn state_file(port: u16) -> Result<Utf8PathBuf> {
+    Ok(state_dir()?.join(format!("{port}.json")))
+}
+
+fn read_state(port: u16) -> Result<Option<DevnetState>> {
+    let path = state_file(port)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let contents =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read file {path}"))?;
+    let state = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse devnet state file {path}"))?;
+
+    Ok(Some(state))
+}
+
+fn write_state(port: u16, state: &DevnetState) -> Result<()> {
+    let path = state_file(port)?;
+    fs::write(&path, serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("Failed to write file {path}"))
+}
+
+fn remove_state(port: u16) -> Result<()> {
+    let path = state_file(port)?;
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Failed to remove file {path}"))?;
+    }
+
+    Ok(())
+}
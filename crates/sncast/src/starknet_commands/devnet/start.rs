@@ -0,0 +1,340 @@
+use super::stop::kill;
+use super::{
+    read_state, remove_state, state_dir, write_state, DevnetProcess, DevnetState,
+    DEFAULT_DEVNET_PORT,
+};
+use crate::starknet_commands::account::{prepare_account_json, write_account_to_accounts_file};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use indoc::formatdoc;
+use serde::Deserialize;
+use sncast::response::structs::DevnetStartResponse;
+use sncast::{get_chain_id, get_provider, AccountType};
+use starknet::core::types::Felt;
+use starknet::signers::SigningKey;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::net::TcpListener;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+const DEVNET_BINARY: &str = "starknet-devnet";
+const DEVNET_INSTALL_URL: &str = "https://github.com/0xSpaceShard/starknet-devnet-rs";
+/// Port devnet listens on inside of its container
+const DEVNET_CONTAINER_PORT: u16 = 5050;
+const DEVNET_PROFILE: &str = "devnet";
+
+#[derive(Args, Debug)]
+#[command(about = "Start a devnet instance and write its predeployed accounts to an accounts file", long_about = None)]
+pub struct Start {
+    /// Path to the starknet-devnet binary, looked up in PATH if neither this nor `--docker-image` is passed
+    #[clap(long, conflicts_with = "docker_image")]
+    pub binary: Option<Utf8PathBuf>,
+
+    /// Docker image to run devnet from instead of a binary, e.g. shardlabs/starknet-devnet-rs
+    #[clap(long)]
+    pub docker_image: Option<String>,
+
+    /// Host devnet listens on
+    #[clap(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port devnet listens on
+    #[clap(long, default_value_t = DEFAULT_DEVNET_PORT)]
+    pub port: u16,
+
+    /// Seed of the predeployed accounts
+    #[clap(long)]
+    pub seed: Option<u32>,
+
+    /// Number of the predeployed accounts
+    #[clap(long, default_value_t = 10)]
+    pub accounts: u8,
+
+    /// Time in seconds to wait for devnet to accept requests
+    #[clap(long, default_value_t = 30)]
+    pub timeout: u64,
+
+    /// Accounts file to write the predeployed accounts to as `devnet-<index>`, overwritten if it exists.
+    /// Defaults to a file in the temporary directory
+    #[clap(long)]
+    pub accounts_file: Option<Utf8PathBuf>,
+
+    /// Append a `devnet` profile using the devnet url and the first predeployed account to this snfoundry.toml
+    #[clap(long)]
+    pub profile_out: Option<Utf8PathBuf>,
+
+    /// Print only the url and the accounts file as shell variable assignments, e.g. for `eval "$(sncast devnet start --print-env)"`
+    #[clap(long)]
+    pub print_env: bool,
+}
+
+#[derive(Deserialize)]
+struct PredeployedAccount {
+    address: Felt,
+    private_key: Felt,
+}
+
+pub async fn start(start: &Start) -> Result<DevnetStartResponse> {
+    ensure_port_is_free(start)?;
+
+    let base_url = format!("http://{}:{}", start.host, start.port);
+    let (process, child) = match &start.docker_image {
+        Some(image) => (run_container(start, image)?, None),
+        None => {
+            let (child, binary) = spawn_binary(start)?;
+            (
+                DevnetProcess::Binary {
+                    pid: child.id(),
+                    binary,
+                },
+                Some(child),
+            )
+        }
+    };
+
+    let started = setup(start, &base_url, child).await;
+    let (url, accounts_file, accounts) = match started {
+        Ok(started) => started,
+        Err(error) => {
+            // Stopping is best effort, the original error is more relevant
+            let _ = kill(&process, &start.host, start.port);
+            return Err(error);
+        }
+    };
+
+    write_state(
+        start.port,
+        &DevnetState {
+            url: url.clone(),
+            accounts_file: accounts_file.clone(),
+            process,
+        },
+    )?;
+
+    if let Some(profile_out) = &start.profile_out {
+        add_profile(profile_out, &url, &accounts_file)?;
+    }
+
+    Ok(DevnetStartResponse {
+        url,
+        accounts_file,
+        accounts,
+        profile_out: start.profile_out.clone(),
+    })
+}
+
+#[must_use]
+pub fn env_assignments(response: &DevnetStartResponse) -> String {
+    formatdoc! {r#"
+        export DEVNET_URL="{}"
+        export DEVNET_ACCOUNTS_FILE="{}"
+    "#, response.url, response.accounts_file}
+}
+
+async fn setup(
+    start: &Start,
+    base_url: &str,
+    child: Option<Child>,
+) -> Result<(String, Utf8PathBuf, Vec<String>)> {
+    wait_for_readiness(base_url, start, child).await?;
+
+    let url = format!("{base_url}/rpc");
+    let chain_id = get_chain_id(&get_provider(&url)?).await?;
+
+    let predeployed_accounts: Vec<PredeployedAccount> =
+        reqwest::get(format!("{base_url}/predeployed_accounts"))
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .context("Failed to fetch the predeployed accounts of devnet")?
+            .json()
+            .await
+            .context("Failed to parse the predeployed accounts of devnet")?;
+
+    let accounts_file = match &start.accounts_file {
+        Some(accounts_file) => accounts_file.clone(),
+        None => state_dir()?.join(format!("{}-accounts.json", start.port)),
+    };
+    if accounts_file.exists() {
+        fs::remove_file(&accounts_file)
+            .with_context(|| format!("Failed to remove file {accounts_file}"))?;
+    }
+
+    let mut accounts = vec![];
+    for (index, account) in predeployed_accounts.iter().enumerate() {
+        let name = format!("devnet-{index}");
+        let account_json = prepare_account_json(
+            &SigningKey::from_secret_scalar(account.private_key),
+            account.address,
+            true,
+            false,
+            &AccountType::OpenZeppelin,
+            None,
+            None,
+        );
+        write_account_to_accounts_file(&name, &accounts_file, chain_id, account_json)?;
+        accounts.push(name);
+    }
+
+    Ok((url, accounts_file, accounts))
+}
+
+fn ensure_port_is_free(start: &Start) -> Result<()> {
+    let state = read_state(start.port)?;
+
+    if TcpListener::bind((start.host.as_str(), start.port)).is_ok() {
+        // Devnet from the state is not running anymore, e.g. it was killed by hand
+        if state.is_some() {
+            remove_state(start.port)?;
+        }
+        return Ok(());
+    }
+
+    match state {
+        Some(state) => bail!(
+            "Devnet started by sncast is already running at {} on port {}, stop it with `sncast devnet stop --port {}`",
+            state.url,
+            start.port,
+            start.port
+        ),
+        None => bail!(
+            "Port {} is already in use, pass a different one with `--port`",
+            start.port
+        ),
+    }
+}
+
+fn spawn_binary(start: &Start) -> Result<(Child, Utf8PathBuf)> {
+    let binary = match &start.binary {
+        Some(binary) => {
+            ensure!(binary.is_file(), "Devnet binary {binary} does not exist");
+            binary.clone()
+        }
+        None => find_in_path(DEVNET_BINARY).ok_or_else(|| {
+            anyhow!(
+                "{DEVNET_BINARY} was not found in PATH, install it from {DEVNET_INSTALL_URL}, \
+                pass its path with `--binary` or run it from a Docker image with `--docker-image`"
+            )
+        })?,
+    };
+
+    let log_path = state_dir()?.join(format!("{}.log", start.port));
+    let log = File::create(&log_path).with_context(|| format!("Failed to create {log_path}"))?;
+
+    let mut command = Command::new(&binary);
+    command
+        .args(["--host", &start.host])
+        .args(["--port", &start.port.to_string()])
+        .args(["--accounts", &start.accounts.to_string()])
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    if let Some(seed) = start.seed {
+        command.args(["--seed", &seed.to_string()]);
+    }
+
+    let child = command
+        .spawn()
+        .with_context(|| format!("Failed to start devnet binary {binary}"))?;
+
+    Ok((child, binary))
+}
+
+fn run_container(start: &Start, image: &str) -> Result<DevnetProcess> {
+    let container = format!("sncast-devnet-{}", start.port);
+
+    let mut command = Command::new("docker");
+    command
+        .args(["run", "--detach", "--rm", "--name", &container])
+        .args([
+            "--publish",
+            &format!("{}:{}:{DEVNET_CONTAINER_PORT}", start.host, start.port),
+        ])
+        .arg(image)
+        .args(["--accounts", &start.accounts.to_string()]);
+    if let Some(seed) = start.seed {
+        command.args(["--seed", &seed.to_string()]);
+    }
+
+    let output = command.output().map_err(|error| match error.kind() {
+        ErrorKind::NotFound => {
+            anyhow!("Docker was not found, install it or run devnet from a binary with `--binary`")
+        }
+        _ => anyhow!(error).context("Failed to run docker"),
+    })?;
+    ensure!(
+        output.status.success(),
+        "Failed to start devnet container from image {image}: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+
+    Ok(DevnetProcess::Docker { container })
+}
+
+async fn wait_for_readiness(base_url: &str, start: &Start, mut child: Option<Child>) -> Result<()> {
+    let timeout = Duration::from_secs(start.timeout);
+    let started_at = Instant::now();
+
+    loop {
+        if let Some(status) = child.as_mut().map(Child::try_wait).transpose()?.flatten() {
+            bail!(
+                "Devnet exited with {status} before accepting requests, see its logs in {}",
+                state_dir()?.join(format!("{}.log", start.port))
+            );
+        }
+
+        let alive = reqwest::get(format!("{base_url}/is_alive")).await;
+        if alive.is_ok_and(|response| response.status().is_success()) {
+            return Ok(());
+        }
+
+        ensure!(
+            started_at.elapsed() < timeout,
+            "Devnet did not accept requests within {}s, pass a longer `--timeout`",
+            start.timeout
+        );
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+fn find_in_path(binary: &str) -> Option<Utf8PathBuf> {
+    let binary = format!("{binary}{}", env::consts::EXE_SUFFIX);
+
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(&binary))
+        .find(|path| path.is_file())
+        .and_then(|path| Utf8PathBuf::try_from(path).ok())
+}
+
+fn add_profile(profile_out: &Utf8PathBuf, url: &str, accounts_file: &Utf8PathBuf) -> Result<()> {
+    if profile_out.exists() {
+        let contents = fs::read_to_string(profile_out)
+            .with_context(|| format!("Failed to read file {profile_out}"))?;
+        let config: toml::Value =
+            toml::from_str(&contents).with_context(|| format!("Failed to parse {profile_out}"))?;
+        ensure!(
+            config
+                .get("sncast")
+                .and_then(|sncast| sncast.get(DEVNET_PROFILE))
+                .is_none(),
+            "Profile {DEVNET_PROFILE} already exists in {profile_out}"
+        );
+    }
+
+    let profile = formatdoc! {r#"
+
+        [sncast.{DEVNET_PROFILE}]
+        url = {}
+        accounts-file = {}
+        account = "devnet-0"
+    "#, toml::Value::from(url), toml::Value::from(accounts_file.as_str())};
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(profile_out)
+        .and_then(|mut file| file.write_all(profile.as_bytes()))
+        .with_context(|| format!("Failed to write {profile_out}"))
+}
@@ -0,0 +1,121 @@
+use super::{read_state, remove_state, DevnetProcess, DEFAULT_DEVNET_PORT};
+use anyhow::{anyhow, bail, ensure, Context, Result};
+use camino::Utf8Path;
+use clap::Args;
+use sncast::response::structs::DevnetStopResponse;
+use std::net::TcpListener;
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Time to wait for a stopped devnet to release its port
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Args, Debug)]
+#[command(about = "Stop a devnet instance started with `sncast devnet start`", long_about = None)]
+pub struct Stop {
+    /// Port of the devnet instance to stop
+    #[clap(long, default_value_t = DEFAULT_DEVNET_PORT)]
+    pub port: u16,
+}
+
+pub fn stop(stop: &Stop) -> Result<DevnetStopResponse> {
+    let state = read_state(stop.port)?.ok_or_else(|| {
+        anyhow!(
+            "No devnet started with `sncast devnet start` is running on port {}",
+            stop.port
+        )
+    })?;
+
+    let host = Url::parse(&state.url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "127.0.0.1".to_string());
+
+    // The state is removed even if the process is already gone, e.g. killed by hand
+    let stopped = kill(&state.process, &host, stop.port);
+    remove_state(stop.port)?;
+    stopped?;
+
+    Ok(DevnetStopResponse { url: state.url })
+}
+
+/// Stops the devnet and waits until it releases the port, so another one can be started on it right away
+pub(super) fn kill(process: &DevnetProcess, host: &str, port: u16) -> Result<()> {
+    if let DevnetProcess::Binary { pid, binary } = process {
+        ensure!(
+            is_running_binary(*pid, binary)?,
+            "Devnet process with PID {pid} is not running anymore"
+        );
+    }
+
+    let mut command = match process {
+        DevnetProcess::Binary { pid, .. } if cfg!(windows) => {
+            let mut command = Command::new("taskkill");
+            command.args(["/PID", &pid.to_string(), "/F"]);
+            command
+        }
+        DevnetProcess::Binary { pid, .. } => {
+            let mut command = Command::new("kill");
+            command.arg(pid.to_string());
+            command
+        }
+        DevnetProcess::Docker { container } => {
+            let mut command = Command::new("docker");
+            command.args(["stop", container]);
+            command
+        }
+    };
+
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !output.status.success() {
+        bail!(
+            "Failed to stop devnet {process:?}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    wait_for_port_release(host, port)
+}
+
+/// `kill` only signals the process and the port of a container may be released after `docker stop` returns
+fn wait_for_port_release(host: &str, port: u16) -> Result<()> {
+    let started = Instant::now();
+    while TcpListener::bind((host, port)).is_err() {
+        ensure!(
+            started.elapsed() < PORT_RELEASE_TIMEOUT,
+            "Devnet was stopped, but port {port} is still in use after {} seconds",
+            PORT_RELEASE_TIMEOUT.as_secs()
+        );
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(())
+}
+
+/// Checks that the process with `pid` runs `binary`, so a process that reused the PID of an exited devnet is not killed
+fn is_running_binary(pid: u32, binary: &Utf8Path) -> Result<bool> {
+    let Some(name) = binary.file_name() else {
+        return Ok(false);
+    };
+
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("tasklist");
+        command.args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"]);
+        command
+    } else {
+        let mut command = Command::new("ps");
+        command.args(["-p", &pid.to_string(), "-o", "args="]);
+        command
+    };
+
+    // Neither command prints the binary name if there is no process with `pid`
+    let output = command
+        .output()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).contains(name))
+}
@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use data_transformer::get_abi;
+use shared::block_id::BlockIdArg;
+use sncast::helpers::address_book::ContractReference;
+use sncast::helpers::rpc::RpcArgs;
+use sncast::response::errors::StarknetCommandError;
+use sncast::response::structs::{EmittedEventResponse, EventsResponse};
+use sncast::{get_class_hash_by_address, get_contract_class};
+use starknet::core::types::contract::{AbiEntry, AbiEvent, TypedAbiEvent};
+use starknet::core::types::{EmittedEvent, EventFilter, Felt};
+use starknet::core::utils::get_selector_from_name;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+#[derive(Args)]
+#[command(about = "Get events emitted on Starknet", long_about = None)]
+pub struct Events {
+    /// Block from which events are returned.
+    /// Possible values: pending, latest, block hash (0x prefixed hex)
+    /// and block number (decimal u64)
+    #[clap(long)]
+    pub from_block: Option<BlockIdArg>,
+
+    /// Block up to which events are returned, same values as for --from-block are accepted
+    #[clap(long)]
+    pub to_block: Option<BlockIdArg>,
+
+    /// Address of the contract that emitted the events (hex), or `@name` of a contract saved in the address book
+    #[clap(short = 'd', long)]
+    pub address: Option<ContractReference>,
+
+    /// Accepted values of the key at the position the flag is passed at, comma-separated.
+    /// Keys can be passed as felts or event names, `*` accepts any value
+    #[clap(long, value_parser = parse_key_filter)]
+    pub keys: Vec<KeyFilter>,
+
+    /// Number of events fetched with a single request
+    #[clap(long, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    pub chunk_size: u64,
+
+    #[clap(flatten)]
+    pub rpc: RpcArgs,
+}
+
+/// Values accepted at a single position of event keys, empty if any value is accepted
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyFilter(Vec<Felt>);
+
+fn parse_key_filter(value: &str) -> Result<KeyFilter> {
+    if value == "*" {
+        return Ok(KeyFilter(vec![]));
+    }
+
+    value
+        .split(',')
+        .map(|key| {
+            let key = key.trim();
+            match Felt::from_str(key) {
+                Ok(key) => Ok(key),
+                Err(_) => get_selector_from_name(key)
+                    .with_context(|| format!("Failed to convert key = {key} to felt")),
+            }
+        })
+        .collect::<Result<_>>()
+        .map(KeyFilter)
+}
+
+impl Events {
+    #[must_use]
+    pub fn filter(&self, address: Option<Felt>) -> EventFilter {
+        EventFilter {
+            from_block: self.from_block.map(Into::into),
+            to_block: self.to_block.map(Into::into),
+            address,
+            keys: (!self.keys.is_empty())
+                .then(|| self.keys.iter().map(|filter| filter.0.clone()).collect()),
+        }
+    }
+}
+
+pub async fn events(
+    provider: &JsonRpcClient<HttpTransport>,
+    filter: EventFilter,
+    chunk_size: u64,
+) -> Result<EventsResponse, StarknetCommandError> {
+    let event_names = match filter.address {
+        Some(address) => event_names(provider, address).await,
+        None => HashMap::new(),
+    };
+
+    let mut events = vec![];
+    let mut continuation_token = None;
+
+    loop {
+        let page = provider
+            .get_events(filter.clone(), continuation_token, chunk_size)
+            .await
+            .map_err(|error| StarknetCommandError::ProviderError(error.into()))?;
+
+        events.extend(
+            page.events
+                .into_iter()
+                .map(|event| build_event_response(event, &event_names)),
+        );
+
+        match page.continuation_token {
+            Some(token) => continuation_token = Some(token),
+            None => break,
+        }
+    }
+
+    Ok(EventsResponse { events })
+}
+
+/// Names of the events of the contract at `address` by their selectors.
+/// Events are not decoded if the ABI of the contract can't be fetched.
+async fn event_names(
+    provider: &JsonRpcClient<HttpTransport>,
+    address: Felt,
+) -> HashMap<Felt, String> {
+    let Ok(class_hash) = get_class_hash_by_address(provider, address).await else {
+        return HashMap::new();
+    };
+    let Some(abi) = get_contract_class(class_hash, provider)
+        .await
+        .ok()
+        .as_ref()
+        .and_then(get_abi)
+    else {
+        return HashMap::new();
+    };
+
+    abi.iter()
+        .flat_map(|entry| match entry {
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Enum(event))) => event
+                .variants
+                .iter()
+                .map(|variant| variant.name.clone())
+                .collect(),
+            AbiEntry::Event(AbiEvent::Typed(TypedAbiEvent::Struct(event))) => {
+                // Events are emitted with the selector of their name without the module path
+                vec![event
+                    .name
+                    .rsplit("::")
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()]
+            }
+            AbiEntry::Event(AbiEvent::Untyped(event)) => vec![event.name.clone()],
+            _ => vec![],
+        })
+        .filter_map(|name| Some((get_selector_from_name(&name).ok()?, name)))
+        .collect()
+}
+
+fn build_event_response(
+    event: EmittedEvent,
+    event_names: &HashMap<Felt, String>,
+) -> EmittedEventResponse {
+    let name = event
+        .keys
+        .first()
+        .and_then(|selector| event_names.get(selector))
+        .cloned()
+        .unwrap_or_else(|| String::from("unknown"));
+    let block_number = event
+        .block_number
+        .map_or_else(|| String::from("pending"), |number| number.to_string());
+
+    EmittedEventResponse(
+        name,
+        event.from_address,
+        event.keys,
+        event.data,
+        event.transaction_hash,
+        block_number,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::types::BlockId;
+
+    #[test]
+    fn parse_keys() {
+        assert_eq!(
+            parse_key_filter("0x1, 2").unwrap(),
+            KeyFilter(vec![Felt::ONE, Felt::TWO])
+        );
+        assert_eq!(
+            parse_key_filter("Transfer").unwrap(),
+            KeyFilter(vec![get_selector_from_name("Transfer").unwrap()])
+        );
+        assert_eq!(parse_key_filter("*").unwrap(), KeyFilter(vec![]));
+    }
+
+    #[test]
+    fn filter_without_keys() {
+        let events = Events {
+            from_block: Some(BlockIdArg::Number(100)),
+            to_block: None,
+            address: None,
+            keys: vec![],
+            chunk_size: 100,
+            rpc: RpcArgs::default(),
+        };
+
+        let filter = events.filter(Some(Felt::ONE));
+
+        assert_eq!(filter.from_block, Some(BlockId::Number(100)));
+        assert_eq!(filter.to_block, None);
+        assert_eq!(filter.address, Some(Felt::ONE));
+        assert_eq!(filter.keys, None);
+    }
+}
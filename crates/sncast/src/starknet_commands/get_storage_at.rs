@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use shared::block_id::BlockIdArg;
+use sncast::helpers::address_book::ContractReference;
+use sncast::helpers::rpc::RpcArgs;
+use sncast::response::errors::StarknetCommandError;
+use sncast::response::structs::GetStorageAtResponse;
+use starknet::core::types::{BlockId, Felt};
+use starknet::core::utils::get_storage_var_address;
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider};
+
+#[derive(Args)]
+#[command(about = "Get the value of a storage slot of a contract on Starknet", long_about = None)]
+pub struct GetStorageAt {
+    /// Address of the contract (hex), or `@name` of a contract saved in the address book
+    #[clap(short = 'd', long)]
+    pub address: ContractReference,
+
+    /// Key of the storage slot
+    #[clap(long, required_unless_present = "storage_var")]
+    pub key: Option<Felt>,
+
+    /// Name of the storage variable, the key is computed from it and `--storage-var-args`
+    #[clap(long, conflicts_with = "key")]
+    pub storage_var: Option<String>,
+
+    /// Keys of the storage map, or arguments of the legacy storage variable
+    #[clap(long, requires = "storage_var", value_delimiter = ' ', num_args = 1..)]
+    pub storage_var_args: Vec<Felt>,
+
+    /// Block identifier from which the value is read.
+    /// Possible values: pending, latest, block hash (0x prefixed hex)
+    /// and block number (decimal u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: BlockIdArg,
+
+    #[clap(flatten)]
+    pub rpc: RpcArgs,
+}
+
+impl GetStorageAt {
+    pub fn storage_key(&self) -> Result<Felt> {
+        match (self.key, &self.storage_var) {
+            (Some(key), _) => Ok(key),
+            (None, Some(storage_var)) => storage_var_address(storage_var, &self.storage_var_args),
+            (None, None) => unreachable!("Either --key or --storage-var is required"),
+        }
+    }
+}
+
+/// Key of the storage slot of a storage variable, `args` are the keys if the variable is a map
+pub fn storage_var_address(name: &str, args: &[Felt]) -> Result<Felt> {
+    get_storage_var_address(name, args)
+        .with_context(|| format!("Failed to compute the key of storage variable = {name}"))
+}
+
+pub async fn get_storage_at(
+    provider: &JsonRpcClient<HttpTransport>,
+    contract_address: Felt,
+    key: Felt,
+    block_id: &BlockId,
+) -> Result<GetStorageAtResponse, StarknetCommandError> {
+    provider
+        .get_storage_at(contract_address, key, block_id)
+        .await
+        .map(|value| GetStorageAtResponse { value })
+        .map_err(|error| StarknetCommandError::ProviderError(error.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use starknet::core::crypto::pedersen_hash;
+    use starknet::core::utils::starknet_keccak;
+
+    #[test]
+    fn address_of_storage_var() {
+        assert_eq!(
+            storage_var_address("balance", &[]).unwrap(),
+            starknet_keccak(b"balance")
+        );
+    }
+
+    #[test]
+    fn address_of_map_entry() {
+        let key = Felt::from(123);
+
+        assert_eq!(
+            storage_var_address("balances", &[key]).unwrap(),
+            pedersen_hash(&starknet_keccak(b"balances"), &key)
+        );
+    }
+
+    #[test]
+    fn non_ascii_storage_var_name() {
+        assert!(storage_var_address("bałance", &[]).is_err());
+    }
+}
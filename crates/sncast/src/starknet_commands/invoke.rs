@@ -1,24 +1,31 @@
 use anyhow::{anyhow, Result};
+use camino::Utf8PathBuf;
 use clap::{Args, ValueEnum};
+use futures::future::{self, Either};
+use futures::stream::{FuturesOrdered, StreamExt};
+use sncast::helpers::address_book::ContractReference;
 use sncast::helpers::error::token_not_supported_for_invoke;
 use sncast::helpers::fee::{FeeArgs, FeeSettings, FeeToken, PayableTransaction};
 use sncast::helpers::rpc::RpcArgs;
-use sncast::response::errors::StarknetCommandError;
+use sncast::response::errors::{handle_account_error, StarknetCommandError};
 use sncast::response::structs::InvokeResponse;
-use sncast::{apply_optional, handle_wait_for_tx, impl_payable_transaction, WaitForTx};
+use sncast::{
+    apply_optional, handle_wait_for_tx, impl_payable_transaction, ValidatedWaitParams, WaitForTx,
+};
 use starknet::accounts::AccountError::Provider;
 use starknet::accounts::{Account, ConnectedAccount, ExecutionV1, ExecutionV3, SingleOwnerAccount};
 use starknet::core::types::{Call, Felt, InvokeTransactionResult};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use starknet::signers::LocalWallet;
+use std::num::NonZeroUsize;
 
 #[derive(Args, Clone)]
 #[command(about = "Invoke a contract on Starknet")]
 pub struct Invoke {
-    /// Address of contract to invoke
+    /// Address of contract to invoke, or `@name` of a contract saved in the address book
     #[clap(short = 'd', long)]
-    pub contract_address: Felt,
+    pub contract_address: ContractReference,
 
     /// Name of the function to invoke
     #[clap(short, long)]
@@ -28,6 +35,10 @@ pub struct Invoke {
     #[clap(short, long, value_delimiter = ' ', num_args = 1..)]
     pub calldata: Option<Vec<String>>,
 
+    /// Path to a JSON file with the ABI, or a Sierra contract class, used to encode the calldata instead of the ABI of the deployed class
+    #[clap(long)]
+    pub abi_file: Option<Utf8PathBuf>,
+
     #[clap(flatten)]
     pub fee_args: FeeArgs,
 
@@ -79,41 +90,129 @@ pub async fn execute_calls(
     nonce: Option<Felt>,
     wait_config: WaitForTx,
 ) -> Result<InvokeResponse, StarknetCommandError> {
+    let transaction_hash = send_calls(account, calls, fee_args, nonce).await?;
+
+    handle_wait_for_tx(
+        account.provider(),
+        transaction_hash,
+        InvokeResponse { transaction_hash },
+        wait_config,
+    )
+    .await
+    .map_err(StarknetCommandError::from)
+}
+
+/// Sends the invoke transaction without waiting for it, returns its hash
+async fn send_calls(
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    calls: Vec<Call>,
+    fee_args: FeeArgs,
+    nonce: Option<Felt>,
+) -> Result<Felt, StarknetCommandError> {
     let fee_settings = fee_args
         .try_into_fee_settings(account.provider(), account.block_id())
         .await?;
+    let fee_limits = fee_args.fee_limits();
 
     let result = match fee_settings {
         FeeSettings::Eth { max_fee } => {
-            let execution_calls = account.execute_v1(calls);
+            let execution = apply_optional(account.execute_v1(calls), nonce, ExecutionV1::nonce);
 
-            let execution = apply_optional(execution_calls, max_fee, ExecutionV1::max_fee);
-            let execution = apply_optional(execution, nonce, ExecutionV1::nonce);
-            execution.send().await
+            let max_fee = fee_limits
+                .max_fee(max_fee, || async {
+                    execution.estimate_fee().await.map_err(handle_account_error)
+                })
+                .await?;
+
+            execution.max_fee(max_fee).send().await
         }
         FeeSettings::Strk {
             max_gas,
             max_gas_unit_price,
         } => {
-            let execution_calls = account.execute_v3(calls);
-
-            let execution = apply_optional(execution_calls, max_gas, ExecutionV3::gas);
-            let execution = apply_optional(execution, max_gas_unit_price, ExecutionV3::gas_price);
-            let execution = apply_optional(execution, nonce, ExecutionV3::nonce);
-            execution.send().await
+            let execution = apply_optional(account.execute_v3(calls), nonce, ExecutionV3::nonce);
+
+            let (max_gas, max_gas_unit_price) = fee_limits
+                .resource_bounds(max_gas, max_gas_unit_price, || async {
+                    execution.estimate_fee().await.map_err(handle_account_error)
+                })
+                .await?;
+
+            execution
+                .gas(max_gas)
+                .gas_price(max_gas_unit_price)
+                .send()
+                .await
         }
     };
 
     match result {
-        Ok(InvokeTransactionResult { transaction_hash }) => handle_wait_for_tx(
-            account.provider(),
-            transaction_hash,
-            InvokeResponse { transaction_hash },
-            wait_config,
-        )
-        .await
-        .map_err(StarknetCommandError::from),
+        Ok(InvokeTransactionResult { transaction_hash }) => Ok(transaction_hash),
         Err(Provider(error)) => Err(StarknetCommandError::ProviderError(error.into())),
         _ => Err(anyhow!("Unknown RPC error").into()),
     }
 }
+
+/// Sends the invokes one by one and waits for them concurrently,
+/// with at most `parallel` transactions waiting at once.
+/// Results are returned in the order of `invokes`.
+pub async fn invoke_parallel(
+    invokes: Vec<(Call, FeeArgs)>,
+    account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
+    parallel: NonZeroUsize,
+    wait_params: ValidatedWaitParams,
+) -> Vec<Result<InvokeResponse, StarknetCommandError>> {
+    let mut results = Vec::with_capacity(invokes.len());
+    let mut pending = FuturesOrdered::new();
+    // Nonce of the account is not increased until transactions are accepted,
+    // so nonces are assigned locally, starting from the nonce of the account
+    let mut next_nonce = None;
+
+    for (call, fee_args) in invokes {
+        if pending.len() == parallel.get() {
+            results.extend(pending.next().await);
+        }
+
+        let nonce = match next_nonce {
+            Some(nonce) => nonce,
+            None => match account.get_nonce().await {
+                Ok(nonce) => nonce,
+                Err(error) => {
+                    pending.push_back(Either::Left(future::ready(Err(
+                        StarknetCommandError::ProviderError(error.into()),
+                    ))));
+                    continue;
+                }
+            },
+        };
+
+        match send_calls(account, vec![call], fee_args, Some(nonce)).await {
+            Ok(transaction_hash) => {
+                next_nonce = Some(nonce + Felt::ONE);
+
+                pending.push_back(Either::Right(async move {
+                    handle_wait_for_tx(
+                        account.provider(),
+                        transaction_hash,
+                        InvokeResponse { transaction_hash },
+                        WaitForTx {
+                            wait: true,
+                            wait_params,
+                        },
+                    )
+                    .await
+                    .map_err(StarknetCommandError::from)
+                }));
+            }
+            // Nonce of a transaction that was not sent is reused by the next one
+            Err(error) => {
+                next_nonce = Some(nonce);
+                pending.push_back(Either::Left(future::ready(Err(error))));
+            }
+        }
+    }
+
+    results.extend(pending.collect::<Vec<_>>().await);
+
+    results
+}
@@ -1,10 +1,19 @@
 pub mod account;
 pub mod call;
+pub mod compute_address;
 pub mod declare;
+pub mod declare_all;
+pub mod decode;
 pub mod deploy;
+pub mod deploy_plan;
+pub mod devnet;
+pub mod events;
+pub mod get_storage_at;
 pub mod invoke;
 pub mod multicall;
+pub mod receipt;
 pub mod script;
 pub mod show_config;
 pub mod tx_status;
 pub mod verify;
+pub mod verify_class;
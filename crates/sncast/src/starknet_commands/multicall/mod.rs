@@ -2,9 +2,11 @@ use clap::{Args, Subcommand};
 
 pub mod new;
 pub mod run;
+pub mod validate;
 
 use new::New;
 use run::Run;
+use validate::Validate;
 
 #[derive(Args)]
 #[command(about = "Execute multiple calls at once", long_about = None)]
@@ -17,4 +19,5 @@ pub struct Multicall {
 pub enum Commands {
     Run(Run),
     New(New),
+    Validate(Validate),
 }
@@ -1,9 +1,8 @@
 use crate::starknet_commands::invoke::{execute_calls, InvokeVersion};
-use anyhow::anyhow;
+use crate::starknet_commands::multicall::validate::{read_multicall_file, MulticallCall};
 use anyhow::{Context, Result};
 use camino::Utf8PathBuf;
 use clap::Args;
-use serde::Deserialize;
 use sncast::helpers::constants::UDC_ADDRESS;
 use sncast::helpers::error::token_not_supported_for_invoke;
 use sncast::helpers::fee::{FeeArgs, FeeToken, PayableTransaction};
@@ -42,22 +41,6 @@ impl_payable_transaction!(Run, token_not_supported_for_invoke,
     InvokeVersion::V3 => FeeToken::Strk
 );
 
-#[derive(Deserialize, Debug)]
-struct DeployCall {
-    class_hash: Felt,
-    inputs: Vec<String>,
-    unique: bool,
-    salt: Option<Felt>,
-    id: String,
-}
-
-#[derive(Deserialize, Debug)]
-struct InvokeCall {
-    contract_address: String,
-    function: String,
-    inputs: Vec<String>,
-}
-
 pub async fn run(
     run: Run,
     account: &SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>,
@@ -65,24 +48,14 @@ pub async fn run(
 ) -> Result<InvokeResponse> {
     let fee_args = run.fee_args.clone().fee_token(run.token_from_version());
 
-    let contents = std::fs::read_to_string(&run.path)?;
-    let items_map: HashMap<String, Vec<toml::Value>> =
-        toml::from_str(&contents).with_context(|| format!("Failed to parse {}", run.path))?;
+    let calls = read_multicall_file(&run.path)?;
 
     let mut contracts = HashMap::new();
     let mut parsed_calls: Vec<Call> = vec![];
 
-    for call in items_map.get("call").unwrap_or(&vec![]) {
-        let call_type = call.get("call_type");
-        if call_type.is_none() {
-            anyhow::bail!("`Field call_type` is missing in a call specification");
-        }
-
-        match call_type.unwrap().as_str() {
-            Some("deploy") => {
-                let deploy_call: DeployCall = toml::from_str(toml::to_string(&call)?.as_str())
-                    .context("Failed to parse toml `deploy` call")?;
-
+    for call in calls {
+        match call {
+            MulticallCall::Deploy(deploy_call) => {
                 let salt = extract_or_generate_salt(deploy_call.salt);
                 let mut calldata = vec![
                     deploy_call.class_hash,
@@ -108,9 +81,7 @@ pub async fn run(
                 );
                 contracts.insert(deploy_call.id, contract_address.to_string());
             }
-            Some("invoke") => {
-                let invoke_call: InvokeCall = toml::from_str(toml::to_string(&call)?.as_str())
-                    .context("Failed to parse toml `invoke` call")?;
+            MulticallCall::Invoke(invoke_call) => {
                 let mut contract_address = &invoke_call.contract_address;
                 if let Some(addr) = contracts.get(&invoke_call.contract_address) {
                     contract_address = addr;
@@ -126,10 +97,6 @@ pub async fn run(
                     calldata,
                 });
             }
-            Some(unsupported) => {
-                anyhow::bail!("Unsupported call type found = {}", unsupported);
-            }
-            None => anyhow::bail!("Field `call_type` is missing in a call specification"),
         }
     }
 
@@ -0,0 +1,484 @@
+use anyhow::{anyhow, Context, Result};
+use camino::Utf8PathBuf;
+use clap::Args;
+use sncast::response::structs::{Decimal, MulticallValidateResponse};
+use starknet::core::types::Felt;
+use starknet::core::utils::get_selector_from_name;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::str::FromStr;
+use toml_edit::{ImDocument, Item, TableLike, Value};
+
+#[derive(Args, Debug)]
+#[command(about = "Validate a multicall .toml file without executing it", long_about = None)]
+pub struct Validate {
+    /// Path to the toml file with declared operations
+    pub path: Utf8PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DeployCall {
+    pub class_hash: Felt,
+    pub inputs: Vec<String>,
+    pub unique: bool,
+    pub salt: Option<Felt>,
+    pub id: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvokeCall {
+    pub contract_address: String,
+    pub function: String,
+    pub inputs: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MulticallCall {
+    Deploy(DeployCall),
+    Invoke(InvokeCall),
+}
+
+const DEPLOY_KEYS: [&str; 6] = ["call_type", "class_hash", "inputs", "unique", "salt", "id"];
+const INVOKE_KEYS: [&str; 4] = ["call_type", "contract_address", "function", "inputs"];
+
+pub fn validate(validate: &Validate) -> Result<MulticallValidateResponse> {
+    let calls = read_multicall_file(&validate.path)?;
+
+    Ok(MulticallValidateResponse {
+        path: validate.path.clone(),
+        calls: Decimal(calls.len() as u64),
+    })
+}
+
+pub fn read_multicall_file(path: &Utf8PathBuf) -> Result<Vec<MulticallCall>> {
+    let contents = std::fs::read_to_string(path)?;
+    parse_multicall(&contents)
+        .map_err(|errors| anyhow!("Invalid multicall file {path}:\n{}", errors.join("\n")))
+}
+
+/// Parses the calls of a multicall file, rejecting unknown keys, missing keys, values of wrong types
+/// and invalid felts. All problems are returned at once in the order they appear in the file,
+/// each prefixed with its line and column
+pub fn parse_multicall(contents: &str) -> Result<Vec<MulticallCall>, Vec<String>> {
+    let document = ImDocument::parse(contents)
+        .context("Failed to parse toml")
+        .map_err(|error| vec![format!("{error:#}")])?;
+
+    let mut validator = Validator {
+        contents,
+        errors: vec![],
+        ids: HashSet::new(),
+    };
+    let calls = validator.validate_document(document.as_table());
+
+    if validator.errors.is_empty() {
+        Ok(calls)
+    } else {
+        // Errors without a position concern the whole file, they are reported first
+        validator.errors.sort_by_key(|(start, _)| *start);
+        Err(validator
+            .errors
+            .into_iter()
+            .map(|(_, error)| error)
+            .collect())
+    }
+}
+
+struct Validator<'a> {
+    contents: &'a str,
+    /// Errors with the offset they start at in the file, they are found in the order of validation
+    errors: Vec<(Option<usize>, String)>,
+    /// Ids of deploy calls, which can be used as addresses and inputs of the subsequent calls
+    ids: HashSet<String>,
+}
+
+impl Validator<'_> {
+    fn error(&mut self, span: Option<Range<usize>>, message: impl AsRef<str>) {
+        let message = message.as_ref();
+        let start = span.as_ref().map(|span| span.start);
+        let error = match span {
+            Some(span) => {
+                let before = &self.contents[..span.start];
+                let line = before.matches('\n').count() + 1;
+                let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+                format!("line {line}, column {column}: {message}")
+            }
+            None => message.to_string(),
+        };
+        self.errors.push((start, error));
+    }
+
+    fn validate_document(&mut self, document: &dyn TableLike) -> Vec<MulticallCall> {
+        self.reject_unknown_keys(document, &["call"], "the multicall file");
+
+        let calls: Vec<(&dyn TableLike, Option<Range<usize>>)> = match document.get("call") {
+            None => vec![],
+            Some(Item::ArrayOfTables(calls)) => calls
+                .iter()
+                .map(|call| (call as &dyn TableLike, call.span()))
+                .collect(),
+            Some(Item::Value(Value::Array(calls))) => calls
+                .iter()
+                .filter_map(|call| match call.as_inline_table() {
+                    Some(table) => Some((table as &dyn TableLike, call.span())),
+                    None => {
+                        self.error(
+                            call.span(),
+                            format!("`call` must be a table, found {}", call.type_name()),
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            Some(item) => {
+                self.error(
+                    item.span(),
+                    format!(
+                        "`call` must be an array of tables, found {}",
+                        item.type_name()
+                    ),
+                );
+                return vec![];
+            }
+        };
+
+        if calls.is_empty() && self.errors.is_empty() {
+            self.error(None, "No calls found, add at least one [[call]] table");
+        }
+
+        calls
+            .into_iter()
+            .filter_map(|(call, span)| self.validate_call(call, span))
+            .collect()
+    }
+
+    fn validate_call(
+        &mut self,
+        call: &dyn TableLike,
+        span: Option<Range<usize>>,
+    ) -> Option<MulticallCall> {
+        match call.get("call_type") {
+            None => {
+                self.error(span, "Missing key `call_type` in a call");
+                None
+            }
+            Some(call_type) => match call_type.as_str() {
+                Some("deploy") => {
+                    self.reject_unknown_keys(call, &DEPLOY_KEYS, "a `deploy` call");
+                    self.validate_deploy(call, span).map(MulticallCall::Deploy)
+                }
+                Some("invoke") => {
+                    self.reject_unknown_keys(call, &INVOKE_KEYS, "an `invoke` call");
+                    self.validate_invoke(call, span).map(MulticallCall::Invoke)
+                }
+                Some(unsupported) => {
+                    self.error(
+                        call_type.span(),
+                        format!(
+                            "Unsupported call type = {unsupported}, expected `deploy` or `invoke`"
+                        ),
+                    );
+                    None
+                }
+                None => {
+                    self.error(
+                        call_type.span(),
+                        format!(
+                            "`call_type` must be a string, found {}",
+                            call_type.type_name()
+                        ),
+                    );
+                    None
+                }
+            },
+        }
+    }
+
+    fn validate_deploy(
+        &mut self,
+        call: &dyn TableLike,
+        span: Option<Range<usize>>,
+    ) -> Option<DeployCall> {
+        let class_hash = self
+            .required(call, "class_hash", span.clone())
+            .and_then(|item| self.felt(item, "class_hash"));
+        let inputs = self.inputs(call, span.clone());
+        let unique = self
+            .required(call, "unique", span.clone())
+            .and_then(|item| {
+                let unique = item.as_bool();
+                if unique.is_none() {
+                    self.error(
+                        item.span(),
+                        format!("`unique` must be a boolean, found {}", item.type_name()),
+                    );
+                }
+                unique
+            });
+        let salt = call.get("salt").map(|item| self.felt(item, "salt"));
+        let id = self
+            .required(call, "id", span)
+            .and_then(|item| self.string(item, "id"));
+
+        if let Some(id) = &id {
+            if !self.ids.insert(id.clone()) {
+                let span = call.get("id").and_then(Item::span);
+                self.error(
+                    span,
+                    format!("Id `{id}` is used by more than one `deploy` call"),
+                );
+            }
+        }
+
+        Some(DeployCall {
+            class_hash: class_hash?,
+            inputs: inputs?,
+            unique: unique?,
+            salt: salt.map_or(Some(None), |salt| salt.map(Some))?,
+            id: id?,
+        })
+    }
+
+    fn validate_invoke(
+        &mut self,
+        call: &dyn TableLike,
+        span: Option<Range<usize>>,
+    ) -> Option<InvokeCall> {
+        let contract_address = self
+            .required(call, "contract_address", span.clone())
+            .and_then(|item| self.felt_or_id(item, "contract_address"));
+        let function = self
+            .required(call, "function", span.clone())
+            .and_then(|item| {
+                let function = self.string(item, "function")?;
+                if function.is_empty() || get_selector_from_name(&function).is_err() {
+                    self.error(
+                        item.span(),
+                        format!("`function` must be a non-empty ASCII name, found \"{function}\""),
+                    );
+                    return None;
+                }
+                Some(function)
+            });
+        let inputs = self.inputs(call, span);
+
+        Some(InvokeCall {
+            contract_address: contract_address?,
+            function: function?,
+            inputs: inputs?,
+        })
+    }
+
+    fn reject_unknown_keys(&mut self, table: &dyn TableLike, allowed: &[&str], location: &str) {
+        for (key, _) in table.iter() {
+            if !allowed.contains(&key) {
+                let span = table.get_key_value(key).and_then(|(key, _)| key.span());
+                self.error(
+                    span,
+                    format!(
+                        "Unknown key `{key}` in {location}, expected one of: {}",
+                        allowed.join(", ")
+                    ),
+                );
+            }
+        }
+    }
+
+    fn required<'t>(
+        &mut self,
+        call: &'t dyn TableLike,
+        key: &str,
+        span: Option<Range<usize>>,
+    ) -> Option<&'t Item> {
+        let item = call.get(key);
+        if item.is_none() {
+            self.error(span, format!("Missing key `{key}` in a call"));
+        }
+        item
+    }
+
+    fn string(&mut self, item: &Item, key: &str) -> Option<String> {
+        let string = item.as_str().map(ToString::to_string);
+        if string.is_none() {
+            self.error(
+                item.span(),
+                format!("`{key}` must be a string, found {}", item.type_name()),
+            );
+        }
+        string
+    }
+
+    fn felt(&mut self, item: &Item, key: &str) -> Option<Felt> {
+        let string = self.string(item, key)?;
+        let felt = Felt::from_str(&string).ok();
+        if felt.is_none() {
+            self.error(
+                item.span(),
+                format!("`{key}` must be a felt, found \"{string}\""),
+            );
+        }
+        felt
+    }
+
+    fn felt_or_id(&mut self, item: &Item, key: &str) -> Option<String> {
+        let string = self.string(item, key)?;
+        if self.ids.contains(&string) || Felt::from_str(&string).is_ok() {
+            Some(string)
+        } else {
+            self.error(
+                item.span(),
+                format!("`{key}` must be a felt or an id of a preceding `deploy` call, found \"{string}\""),
+            );
+            None
+        }
+    }
+
+    fn inputs(&mut self, call: &dyn TableLike, span: Option<Range<usize>>) -> Option<Vec<String>> {
+        let item = self.required(call, "inputs", span)?;
+        let Some(inputs) = item.as_array() else {
+            self.error(
+                item.span(),
+                format!("`inputs` must be an array, found {}", item.type_name()),
+            );
+            return None;
+        };
+
+        let mut valid = true;
+        let mut parsed = vec![];
+        for input in inputs {
+            match input.as_str() {
+                Some(string) if self.ids.contains(string) || Felt::from_str(string).is_ok() => {
+                    parsed.push(string.to_string());
+                }
+                Some(string) => {
+                    valid = false;
+                    self.error(
+                        input.span(),
+                        format!("Input \"{string}\" must be a felt or an id of a preceding `deploy` call"),
+                    );
+                }
+                None => {
+                    valid = false;
+                    self.error(
+                        input.span(),
+                        format!("Inputs must be strings, found {}", input.type_name()),
+                    );
+                }
+            }
+        }
+
+        valid.then_some(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn valid_calls_are_parsed() {
+        let calls = parse_multicall(indoc! {r#"
+            [[call]]
+            call_type = "deploy"
+            class_hash = "0x123"
+            inputs = []
+            id = "map"
+            unique = false
+
+            [[call]]
+            call_type = "invoke"
+            contract_address = "map"
+            function = "put"
+            inputs = ["0x1", "2"]
+        "#})
+        .unwrap();
+
+        assert_eq!(
+            calls,
+            vec![
+                MulticallCall::Deploy(DeployCall {
+                    class_hash: Felt::from(0x123),
+                    inputs: vec![],
+                    unique: false,
+                    salt: None,
+                    id: "map".to_string(),
+                }),
+                MulticallCall::Invoke(InvokeCall {
+                    contract_address: "map".to_string(),
+                    function: "put".to_string(),
+                    inputs: vec!["0x1".to_string(), "2".to_string()],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_reported_with_location() {
+        let errors = parse_multicall(indoc! {r#"
+            [[call]]
+            call_type = "invoke"
+            contract_address = "0x1"
+            function_nmae = "put"
+            inputs = []
+        "#})
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "line 1, column 1: Missing key `function` in a call",
+                "line 4, column 1: Unknown key `function_nmae` in an `invoke` call, expected one of: call_type, contract_address, function, inputs",
+            ]
+        );
+    }
+
+    #[test]
+    fn wrong_types_are_all_reported() {
+        let errors = parse_multicall(indoc! {r#"
+            [[call]]
+            call_type = "deploy"
+            class_hash = "not a felt"
+            inputs = "0x1"
+            id = 1
+            unique = "no"
+        "#})
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "line 3, column 14: `class_hash` must be a felt, found \"not a felt\"",
+                "line 4, column 10: `inputs` must be an array, found string",
+                "line 5, column 6: `id` must be a string, found integer",
+                "line 6, column 10: `unique` must be a boolean, found string",
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_calls_are_rejected() {
+        let errors = parse_multicall("call = []\n").unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec!["No calls found, add at least one [[call]] table"]
+        );
+    }
+
+    #[test]
+    fn unknown_call_type_is_rejected() {
+        let errors = parse_multicall(indoc! {r#"
+            [[call]]
+            call_type = "declare"
+        "#})
+        .unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                "line 2, column 13: Unsupported call type = declare, expected `deploy` or `invoke`"
+            ]
+        );
+    }
+}
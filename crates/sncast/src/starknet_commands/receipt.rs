@@ -0,0 +1,133 @@
+use sncast::response::errors::StarknetCommandError;
+use sncast::response::structs::{
+    Decimal, ExecutionStatus, FinalityStatus, ReceiptEvent, TransactionReceiptResponse,
+};
+use sncast::ValidatedWaitParams;
+use starknet::core::types::{
+    ExecutionResult, Felt, ReceiptBlock, StarknetError, TransactionFinalityStatus,
+    TransactionReceipt, TransactionReceiptWithBlockInfo,
+};
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider, ProviderError};
+use std::thread::sleep;
+
+// Some nodes report the transaction as accepted before its receipt can be fetched
+const RECEIPT_RETRIES: u8 = 3;
+
+pub async fn receipt(
+    provider: &JsonRpcClient<HttpTransport>,
+    transaction_hash: Felt,
+    wait_params: ValidatedWaitParams,
+) -> Result<TransactionReceiptResponse, StarknetCommandError> {
+    let mut retries_left = RECEIPT_RETRIES;
+
+    loop {
+        match provider.get_transaction_receipt(transaction_hash).await {
+            Ok(receipt) => return Ok(build_transaction_receipt_response(receipt)),
+            Err(ProviderError::StarknetError(StarknetError::TransactionHashNotFound))
+                if retries_left > 0 =>
+            {
+                retries_left -= 1;
+                sleep(wait_params.get_interval());
+            }
+            Err(error) => return Err(StarknetCommandError::ProviderError(error.into())),
+        }
+    }
+}
+
+fn build_transaction_receipt_response(
+    TransactionReceiptWithBlockInfo { receipt, block }: TransactionReceiptWithBlockInfo,
+) -> TransactionReceiptResponse {
+    let (
+        transaction_hash,
+        actual_fee,
+        events,
+        execution_resources,
+        finality_status,
+        execution_result,
+    ) = match receipt {
+        TransactionReceipt::Invoke(receipt) => (
+            receipt.transaction_hash,
+            receipt.actual_fee,
+            receipt.events,
+            receipt.execution_resources,
+            receipt.finality_status,
+            receipt.execution_result,
+        ),
+        TransactionReceipt::L1Handler(receipt) => (
+            receipt.transaction_hash,
+            receipt.actual_fee,
+            receipt.events,
+            receipt.execution_resources,
+            receipt.finality_status,
+            receipt.execution_result,
+        ),
+        TransactionReceipt::Declare(receipt) => (
+            receipt.transaction_hash,
+            receipt.actual_fee,
+            receipt.events,
+            receipt.execution_resources,
+            receipt.finality_status,
+            receipt.execution_result,
+        ),
+        TransactionReceipt::Deploy(receipt) => (
+            receipt.transaction_hash,
+            receipt.actual_fee,
+            receipt.events,
+            receipt.execution_resources,
+            receipt.finality_status,
+            receipt.execution_result,
+        ),
+        TransactionReceipt::DeployAccount(receipt) => (
+            receipt.transaction_hash,
+            receipt.actual_fee,
+            receipt.events,
+            receipt.execution_resources,
+            receipt.finality_status,
+            receipt.execution_result,
+        ),
+    };
+
+    let (block_hash, block_number) = match block {
+        ReceiptBlock::Pending => (None, None),
+        ReceiptBlock::Block {
+            block_hash,
+            block_number,
+        } => (Some(block_hash), Some(Decimal(block_number))),
+    };
+
+    let (execution_status, revert_reason) = match execution_result {
+        ExecutionResult::Succeeded => (ExecutionStatus::Succeeded, None),
+        ExecutionResult::Reverted { reason } => (ExecutionStatus::Reverted, Some(reason)),
+    };
+
+    TransactionReceiptResponse {
+        transaction_hash,
+        finality_status: match finality_status {
+            TransactionFinalityStatus::AcceptedOnL2 => FinalityStatus::AcceptedOnL2,
+            TransactionFinalityStatus::AcceptedOnL1 => FinalityStatus::AcceptedOnL1,
+        },
+        execution_status,
+        revert_reason,
+        block_hash,
+        block_number,
+        actual_fee: actual_fee.amount,
+        fee_unit: actual_fee.unit,
+        steps: Decimal(execution_resources.computation_resources.steps),
+        memory_holes: execution_resources
+            .computation_resources
+            .memory_holes
+            .map(Decimal),
+        l1_gas: Decimal(execution_resources.data_resources.data_availability.l1_gas),
+        l1_data_gas: Decimal(
+            execution_resources
+                .data_resources
+                .data_availability
+                .l1_data_gas,
+        ),
+        events: events
+            .into_iter()
+            .map(|event| ReceiptEvent(event.from_address, event.keys, event.data))
+            .collect(),
+    }
+}
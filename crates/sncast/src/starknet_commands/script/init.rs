@@ -1,11 +1,11 @@
-use anyhow::{anyhow, ensure, Context, Ok, Result};
+use anyhow::{ensure, Context, Ok, Result};
 use camino::Utf8PathBuf;
 use std::fs;
 
 use clap::Args;
 use indoc::{formatdoc, indoc};
 use scarb_api::ScarbCommand;
-use shared::print::print_as_warning;
+use shared::print::{emit_warning, DiagnosticCode};
 use sncast::helpers::constants::INIT_SCRIPTS_DIR;
 use sncast::helpers::scarb_utils::get_cairo_version;
 use sncast::response::structs::ScriptInitResponse;
@@ -24,8 +24,9 @@ pub fn init(init_args: &Init) -> Result<ScriptInitResponse> {
     let modify_files_result = add_dependencies(&script_root_dir_path)
         .and_then(|()| modify_files_in_src_dir(&init_args.script_name, &script_root_dir_path));
 
-    print_as_warning(&anyhow!(
-        "The newly created script isn't auto-added to the workspace. For more details, please see https://foundry-rs.github.io/starknet-foundry/starknet/script.html#initialize-a-script")
+    emit_warning(
+        DiagnosticCode::ScriptNotInWorkspace,
+        "The newly created script isn't auto-added to the workspace. For more details, please see https://foundry-rs.github.io/starknet-foundry/starknet/script.html#initialize-a-script",
     );
 
     match modify_files_result {
@@ -1,4 +1,4 @@
-use crate::starknet_commands::declare::Declare;
+use crate::starknet_commands::declare::{AutoBumpFeeArgs, Declare};
 use crate::starknet_commands::{call, declare, deploy, invoke, tx_status};
 use crate::{get_account, WaitForTx};
 use anyhow::{anyhow, Context, Result};
@@ -19,37 +19,40 @@ use cairo_vm::vm::vm_core::VirtualMachine;
 use camino::Utf8PathBuf;
 use clap::Args;
 use conversions::byte_array::ByteArray;
-use conversions::serde::deserialize::BufferReader;
+use conversions::serde::deserialize::{BufferReader, CairoDeserialize};
 use itertools::chain;
-use runtime::starknet::context::{build_context, SerializableBlockInfo};
+use runtime::starknet::context::{build_context, BlockContextVersion, SerializableBlockInfo};
 use runtime::starknet::state::DictStateReader;
 use runtime::{
     CheatcodeHandlingResult, EnhancedHintError, ExtendedRuntime, ExtensionLogic, StarknetRuntime,
     SyscallHandlingResult,
 };
-use scarb_api::{package_matches_version_requirement, StarknetContractArtifacts};
+use scarb_api::std_version::ensure_std_version_compatible;
+use scarb_api::StarknetContractArtifacts;
 use scarb_metadata::{Metadata, PackageMetadata};
-use semver::{Comparator, Op, Version, VersionReq};
-use shared::print::print_as_warning;
+use semver::Version;
 use shared::utils::build_readable_text;
 use sncast::get_nonce;
 use sncast::helpers::configuration::CastConfig;
 use sncast::helpers::constants::SCRIPT_LIB_ARTIFACT_NAME;
-use sncast::helpers::fee::{FeeSettings, ScriptFeeSettings};
+use sncast::helpers::fee::{FeeArgs, ScriptFeeSettings};
 use sncast::helpers::rpc::RpcArgs;
+use sncast::response::errors::StarknetCommandError;
 use sncast::response::structs::ScriptRunResponse;
+use sncast::state::execution_log::ExecutionLog;
 use sncast::state::hashing::{
     generate_declare_tx_id, generate_deploy_tx_id, generate_invoke_tx_id,
 };
-use sncast::state::state_file::StateManager;
-use starknet::accounts::{Account, SingleOwnerAccount};
-use starknet::core::types::Felt;
+use sncast::state::state_file::{ScriptTransactionOutput, StateManager};
+use starknet::accounts::{Account, ConnectedAccount, SingleOwnerAccount};
 use starknet::core::types::{BlockId, BlockTag::Pending};
+use starknet::core::types::{Call, Felt};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 use starknet::signers::LocalWallet;
 use std::collections::HashMap;
 use std::fs;
+use std::num::NonZeroUsize;
 use tokio::runtime::Runtime;
 
 type ScriptStarknetContractArtifacts = StarknetContractArtifacts;
@@ -64,10 +67,22 @@ pub struct Run {
     #[clap(long)]
     pub package: Option<String>,
 
-    /// Do not use the state file
+    /// Do not use the state file and the execution log
     #[clap(long)]
     pub no_state_file: bool,
 
+    /// Skip the steps completed in the previous run of the script, as recorded in the execution log
+    #[clap(long, conflicts_with = "no_state_file")]
+    pub resume: bool,
+
+    /// Maximal number of transactions sent with `invoke_parallel` that are awaited at once
+    #[clap(long, default_value = "5")]
+    pub parallel: NonZeroUsize,
+
+    /// Only warn when the version of `sncast_std` is not compatible with `sncast`, instead of failing
+    #[clap(long)]
+    pub no_version_check: bool,
+
     #[clap(flatten)]
     pub rpc: RpcArgs,
 }
@@ -79,6 +94,17 @@ pub struct CastScriptExtension<'a> {
     pub config: &'a CastConfig,
     pub artifacts: &'a HashMap<String, StarknetContractArtifacts>,
     pub state: StateManager,
+    pub execution_log: ExecutionLog,
+    pub parallel: NonZeroUsize,
+}
+
+/// Invoke sent by `invoke_parallel`
+#[derive(CairoDeserialize)]
+struct ScriptInvoke {
+    contract_address: Felt,
+    function_selector: Felt,
+    calldata: Vec<Felt>,
+    fee_settings: ScriptFeeSettings,
 }
 
 impl<'a> CastScriptExtension<'a> {
@@ -87,6 +113,44 @@ impl<'a> CastScriptExtension<'a> {
     ) -> Result<&SingleOwnerAccount<&JsonRpcClient<HttpTransport>, LocalWallet>> {
         self.account.ok_or_else(|| anyhow!("Account not defined. Please ensure the correct account is passed to `script run` command"))
     }
+
+    /// Fee options of a transaction sent by the script, completed with the ones from the profile
+    fn fee_args(&self, fee_settings: ScriptFeeSettings) -> FeeArgs {
+        FeeArgs::from(fee_settings).with_config_defaults(self.config)
+    }
+
+    /// Returns the output of the transaction if it was completed in the previous run of the script,
+    /// according to the execution log or the state file
+    fn get_output_if_completed(
+        &mut self,
+        tx_id: &str,
+        selector: &str,
+    ) -> Result<Option<ScriptTransactionOutput>> {
+        if let Some(output) = self.execution_log.get_output_if_completed(tx_id)? {
+            return Ok(Some(output));
+        }
+
+        let output = self.state.get_output_if_success(tx_id);
+        if let Some(output) = &output {
+            self.execution_log.checkpoint(
+                tx_id,
+                selector,
+                &Ok::<_, StarknetCommandError>(output.clone()),
+            )?;
+        }
+
+        Ok(output)
+    }
+
+    fn record_tx_result(
+        &mut self,
+        tx_id: &str,
+        selector: &str,
+        result: &Result<impl Into<ScriptTransactionOutput> + Clone, StarknetCommandError>,
+    ) -> Result<()> {
+        self.state.maybe_insert_tx_entry(tx_id, selector, result)?;
+        self.execution_log.checkpoint(tx_id, selector, result)
+    }
 }
 
 impl<'a> ExtensionLogic for CastScriptExtension<'a> {
@@ -116,7 +180,7 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
             }
             "declare" => {
                 let contract: String = input_reader.read::<ByteArray>()?.into();
-                let fee_args = input_reader.read::<ScriptFeeSettings>()?.into();
+                let fee_args = self.fee_args(input_reader.read()?);
                 let nonce = input_reader.read()?;
 
                 let declare = Declare {
@@ -125,13 +189,15 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     nonce,
                     package: None,
                     version: None,
+                    bump_fee: AutoBumpFeeArgs::default(),
+                    compiled_class_hash: None,
                     rpc: RpcArgs::default(),
                 };
 
                 let declare_tx_id = generate_declare_tx_id(contract.as_str());
 
                 if let Some(success_output) =
-                    self.state.get_output_if_success(declare_tx_id.as_str())
+                    self.get_output_if_completed(declare_tx_id.as_str(), selector)?
                 {
                     return Ok(CheatcodeHandlingResult::from_serializable(success_output));
                 }
@@ -146,11 +212,7 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     },
                 ));
 
-                self.state.maybe_insert_tx_entry(
-                    declare_tx_id.as_str(),
-                    selector,
-                    &declare_result,
-                )?;
+                self.record_tx_result(declare_tx_id.as_str(), selector, &declare_result)?;
                 Ok(CheatcodeHandlingResult::from_serializable(declare_result))
             }
             "deploy" => {
@@ -158,37 +220,41 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                 let constructor_calldata = input_reader.read::<Vec<Felt>>()?;
                 let salt = input_reader.read()?;
                 let unique = input_reader.read()?;
-                let fee_args: FeeSettings = input_reader.read::<ScriptFeeSettings>()?.into();
+                let fee_args = self.fee_args(input_reader.read()?);
                 let nonce = input_reader.read()?;
+                let wait_config =
+                    WaitForTx::for_script(self.config.wait_params, input_reader.read()?)?;
 
                 let deploy_tx_id =
                     generate_deploy_tx_id(class_hash, &constructor_calldata, salt, unique);
 
                 if let Some(success_output) =
-                    self.state.get_output_if_success(deploy_tx_id.as_str())
+                    self.get_output_if_completed(deploy_tx_id.as_str(), selector)?
                 {
                     return Ok(CheatcodeHandlingResult::from_serializable(success_output));
                 }
 
-                let deploy_result = self.tokio_runtime.block_on(deploy::deploy(
-                    class_hash,
-                    &constructor_calldata,
-                    salt,
-                    unique,
-                    fee_args,
-                    nonce,
-                    self.account()?,
-                    WaitForTx {
-                        wait: true,
-                        wait_params: self.config.wait_params,
-                    },
-                ));
-
-                self.state.maybe_insert_tx_entry(
-                    deploy_tx_id.as_str(),
-                    selector,
-                    &deploy_result,
-                )?;
+                let account = self.account()?;
+                let deploy_result = self.tokio_runtime.block_on(async {
+                    let fee_settings = fee_args
+                        .try_into_fee_settings(account.provider(), account.block_id())
+                        .await?;
+
+                    deploy::deploy(
+                        class_hash,
+                        &constructor_calldata,
+                        salt,
+                        unique,
+                        fee_settings,
+                        fee_args.fee_limits(),
+                        nonce,
+                        account,
+                        wait_config,
+                    )
+                    .await
+                });
+
+                self.record_tx_result(deploy_tx_id.as_str(), selector, &deploy_result)?;
 
                 Ok(CheatcodeHandlingResult::from_serializable(deploy_result))
             }
@@ -196,14 +262,16 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                 let contract_address = input_reader.read()?;
                 let function_selector = input_reader.read()?;
                 let calldata: Vec<_> = input_reader.read()?;
-                let fee_args = input_reader.read::<ScriptFeeSettings>()?.into();
+                let fee_args = self.fee_args(input_reader.read()?);
                 let nonce = input_reader.read()?;
+                let wait_config =
+                    WaitForTx::for_script(self.config.wait_params, input_reader.read()?)?;
 
                 let invoke_tx_id =
                     generate_invoke_tx_id(contract_address, function_selector, &calldata);
 
                 if let Some(success_output) =
-                    self.state.get_output_if_success(invoke_tx_id.as_str())
+                    self.get_output_if_completed(invoke_tx_id.as_str(), selector)?
                 {
                     return Ok(CheatcodeHandlingResult::from_serializable(success_output));
                 }
@@ -215,20 +283,92 @@ impl<'a> ExtensionLogic for CastScriptExtension<'a> {
                     fee_args,
                     function_selector,
                     self.account()?,
-                    WaitForTx {
-                        wait: true,
-                        wait_params: self.config.wait_params,
-                    },
+                    wait_config,
                 ));
 
-                self.state.maybe_insert_tx_entry(
-                    invoke_tx_id.as_str(),
-                    selector,
-                    &invoke_result,
-                )?;
+                self.record_tx_result(invoke_tx_id.as_str(), selector, &invoke_result)?;
 
                 Ok(CheatcodeHandlingResult::from_serializable(invoke_result))
             }
+            "invoke_parallel" => {
+                let invokes: Vec<ScriptInvoke> = input_reader.read()?;
+
+                let invokes: Vec<_> = invokes
+                    .into_iter()
+                    .map(|invoke| {
+                        let tx_id = generate_invoke_tx_id(
+                            invoke.contract_address,
+                            invoke.function_selector,
+                            &invoke.calldata,
+                        );
+                        let call = Call {
+                            to: invoke.contract_address,
+                            selector: invoke.function_selector,
+                            calldata: invoke.calldata,
+                        };
+
+                        (tx_id, call, self.fee_args(invoke.fee_settings))
+                    })
+                    .collect();
+
+                // Invokes that already succeeded in a previous run of the script are not sent again
+                let mut logged_count = 0;
+                let mut results = vec![];
+                for (tx_id, _, _) in &invokes {
+                    let output = match self.execution_log.get_output_if_completed(tx_id)? {
+                        Some(output) => {
+                            logged_count += 1;
+                            Some(output)
+                        }
+                        None => self.state.get_output_if_success(tx_id),
+                    };
+
+                    results.push(match output {
+                        Some(ScriptTransactionOutput::InvokeResponse(response)) => {
+                            Some(Ok(response))
+                        }
+                        _ => None,
+                    });
+                }
+
+                let all_tx_ids: Vec<_> =
+                    invokes.iter().map(|(tx_id, _, _)| tx_id.clone()).collect();
+                let (tx_ids, to_send): (Vec<_>, Vec<_>) = invokes
+                    .into_iter()
+                    .zip(&results)
+                    .filter(|(_, result)| result.is_none())
+                    .map(|((tx_id, call, fee_args), _)| (tx_id, (call, fee_args)))
+                    .unzip();
+
+                let sent_results = self.tokio_runtime.block_on(invoke::invoke_parallel(
+                    to_send,
+                    self.account()?,
+                    self.parallel,
+                    self.config.wait_params,
+                ));
+
+                for (tx_id, result) in tx_ids.iter().zip(&sent_results) {
+                    self.state.maybe_insert_tx_entry(tx_id, "invoke", result)?;
+                }
+
+                let mut sent_results = sent_results.into_iter();
+                let results: Vec<_> = results
+                    .iter_mut()
+                    .map(|result| {
+                        result
+                            .take()
+                            .or_else(|| sent_results.next())
+                            .expect("Result of every invoke should be available")
+                    })
+                    .collect();
+
+                // Steps are logged in the order of invokes, regardless of the order they finished in
+                for (tx_id, result) in all_tx_ids.iter().zip(&results).skip(logged_count) {
+                    self.execution_log.checkpoint(tx_id, "invoke", result)?;
+                }
+
+                Ok(CheatcodeHandlingResult::from_serializable(results))
+            }
             "get_nonce" => {
                 let block_id = as_cairo_short_string(&input_reader.read()?)
                     .expect("Failed to convert entry point name to short string");
@@ -278,8 +418,17 @@ pub fn run(
     tokio_runtime: Runtime,
     config: &CastConfig,
     state_file_path: Option<Utf8PathBuf>,
+    execution_log: ExecutionLog,
+    parallel: NonZeroUsize,
+    no_version_check: bool,
 ) -> Result<ScriptRunResponse> {
-    warn_if_sncast_std_not_compatible(metadata)?;
+    ensure_std_version_compatible(
+        metadata,
+        "sncast_std",
+        "sncast",
+        &Version::parse(env!("CARGO_PKG_VERSION")).unwrap(),
+        no_version_check,
+    )?;
     let artifacts = inject_lib_artifact(metadata, package_metadata, artifacts)?;
 
     let artifact = artifacts
@@ -319,7 +468,11 @@ pub fn run(
         .assemble_ex(&entry_code, &footer);
 
     // hint processor
-    let mut context = build_context(&SerializableBlockInfo::default().into(), None);
+    let mut context = build_context(
+        &SerializableBlockInfo::default().into(),
+        None,
+        BlockContextVersion::default(),
+    );
 
     let mut blockifier_state = CachedState::new(DictStateReader::default());
     let mut execution_resources = ExecutionResources::default();
@@ -357,6 +510,8 @@ pub fn run(
         artifacts: &artifacts,
         account: account.as_ref(),
         state,
+        execution_log,
+        parallel,
     };
 
     let mut cast_runtime = ExtendedRuntime {
@@ -387,32 +542,6 @@ pub fn run(
     }
 }
 
-fn sncast_std_version_requirement() -> VersionReq {
-    let version = Version::parse(env!("CARGO_PKG_VERSION")).unwrap();
-    let comparator = Comparator {
-        op: Op::Exact,
-        major: version.major,
-        minor: Some(version.minor),
-        patch: Some(version.patch),
-        pre: version.pre,
-    };
-    VersionReq {
-        comparators: vec![comparator],
-    }
-}
-
-fn warn_if_sncast_std_not_compatible(scarb_metadata: &Metadata) -> Result<()> {
-    let sncast_std_version_requirement = sncast_std_version_requirement();
-    if !package_matches_version_requirement(
-        scarb_metadata,
-        "sncast_std",
-        &sncast_std_version_requirement,
-    )? {
-        print_as_warning(&anyhow!("Package sncast_std version does not meet the recommended version requirement {sncast_std_version_requirement}, it might result in unexpected behaviour"));
-    }
-    Ok(())
-}
-
 fn inject_lib_artifact(
     metadata: &Metadata,
     package_metadata: &PackageMetadata,
@@ -427,10 +556,9 @@ fn inject_lib_artifact(
     // TODO(#2042)
     let sierra_path = &target_dir.join("dev").join(sierra_filename);
 
-    let lib_artifacts = ScriptStarknetContractArtifacts {
-        sierra: fs::read_to_string(sierra_path)?,
-        casm: String::new(),
-    };
+    // The script is not a contract, it is run from Sierra and never compiled to contract CASM
+    let lib_artifacts =
+        ScriptStarknetContractArtifacts::from_sierra(fs::read_to_string(sierra_path)?);
 
     artifacts.insert(SCRIPT_LIB_ARTIFACT_NAME.to_string(), lib_artifacts);
     Ok(artifacts.clone())
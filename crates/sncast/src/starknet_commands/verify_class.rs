@@ -0,0 +1,72 @@
+use crate::starknet_commands::deploy_plan::{class_hash_for, sierra_class_for};
+use anyhow::Result;
+use clap::Args;
+use scarb_api::StarknetContractArtifacts;
+use shared::block_id::BlockIdArg;
+use sncast::helpers::address_book::ContractReference;
+use sncast::helpers::rpc::RpcArgs;
+use sncast::response::errors::{handle_starknet_command_error, StarknetCommandError};
+use sncast::response::structs::{ClassHashComparison, VerifyClassResponse};
+use starknet::core::types::{BlockId, Felt};
+use starknet::providers::jsonrpc::HttpTransport;
+use starknet::providers::{JsonRpcClient, Provider};
+use std::collections::HashMap;
+
+#[derive(Args)]
+#[command(
+    about = "Compare the class hash of a contract deployed on Starknet with the class hash of a local contract",
+    long_about = None
+)]
+pub struct VerifyClass {
+    /// Address of the contract (hex), or `@name` of a contract saved in the address book
+    #[clap(short = 'd', long)]
+    pub address: ContractReference,
+
+    /// Name of the local contract the deployed one is compared with
+    #[clap(short, long)]
+    pub contract_name: String,
+
+    /// Block identifier from which the class hash is read.
+    /// Possible values: pending, latest, block hash (0x prefixed hex)
+    /// and block number (decimal u64)
+    #[clap(short, long, default_value = "pending")]
+    pub block_id: BlockIdArg,
+
+    /// Specifies scarb package to be used
+    #[clap(long)]
+    pub package: Option<String>,
+
+    #[clap(flatten)]
+    pub rpc: RpcArgs,
+}
+
+pub async fn verify_class(
+    provider: &JsonRpcClient<HttpTransport>,
+    contract_address: Felt,
+    contract_name: &str,
+    block_id: &BlockId,
+    artifacts: &HashMap<String, StarknetContractArtifacts>,
+) -> Result<VerifyClassResponse> {
+    let local_class_hash = class_hash_for(&sierra_class_for(contract_name, artifacts)?)?;
+
+    let onchain_class_hash = provider
+        .get_class_hash_at(block_id, contract_address)
+        .await
+        .map_err(|error| {
+            handle_starknet_command_error(StarknetCommandError::ProviderError(error.into()))
+        })?;
+
+    let result = if onchain_class_hash == local_class_hash {
+        ClassHashComparison::Match
+    } else {
+        ClassHashComparison::Mismatch
+    };
+
+    Ok(VerifyClassResponse {
+        contract_name: contract_name.to_string(),
+        contract_address,
+        onchain_class_hash,
+        local_class_hash,
+        result,
+    })
+}
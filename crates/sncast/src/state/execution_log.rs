@@ -0,0 +1,320 @@
+use crate::helpers::constants::EXECUTION_LOG_VERSION;
+use crate::response::errors::StarknetCommandError;
+use crate::state::hashing::generate_step_id;
+use crate::state::state_file::ScriptTransactionOutput;
+use anyhow::{anyhow, bail, Context, Result};
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Default)]
+pub struct ExecutionLogSchema {
+    pub version: u8,
+    /// Completed steps of each script, by script name and network
+    pub scripts: HashMap<String, Vec<ExecutionStep>>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+pub struct ExecutionStep {
+    pub step_id: String,
+    pub name: String,
+    pub output: ScriptTransactionOutput,
+}
+
+struct InnerExecutionLog {
+    log_file: Utf8PathBuf,
+    script: String,
+    completed_steps_prev_run: Vec<ExecutionStep>,
+    completed_steps_current_run: Vec<ExecutionStep>,
+    // set after the first step that did not succeed, later steps are not recorded
+    interrupted: bool,
+}
+
+/// Log of the steps of the script completed in order, used to resume the script from the first
+/// step that did not succeed
+#[derive(Default)]
+pub struct ExecutionLog {
+    inner: Option<InnerExecutionLog>,
+}
+
+impl ExecutionLog {
+    pub fn from(log_file: Option<Utf8PathBuf>, script: String, resume: bool) -> Result<Self> {
+        let res = if let Some(log_file) = log_file {
+            let completed_steps_prev_run = if resume {
+                load_execution_log(&log_file)?
+                    .scripts
+                    .remove(&script)
+                    .unwrap_or_default()
+            } else {
+                vec![]
+            };
+
+            Self {
+                inner: Some(InnerExecutionLog {
+                    log_file,
+                    script,
+                    completed_steps_prev_run,
+                    completed_steps_current_run: vec![],
+                    interrupted: false,
+                }),
+            }
+        } else {
+            Self::default()
+        };
+
+        Ok(res)
+    }
+
+    /// Returns the output of the step if it was completed in the previous run.
+    /// Fails if the previous run executed a different step at this point of the script.
+    pub fn get_output_if_completed(
+        &mut self,
+        tx_id: &str,
+    ) -> Result<Option<ScriptTransactionOutput>> {
+        let Some(log) = &mut self.inner else {
+            return Ok(None);
+        };
+        if log.interrupted {
+            return Ok(None);
+        }
+
+        let index = log.completed_steps_current_run.len();
+        let Some(completed_step) = log.completed_steps_prev_run.get(index) else {
+            return Ok(None);
+        };
+
+        let step_id = generate_step_id(
+            log.completed_steps_current_run
+                .last()
+                .map(|step| step.step_id.as_str()),
+            tx_id,
+        );
+        if completed_step.step_id != step_id {
+            bail!(
+                "Script has changed since it was logged in {}: step {} was `{}` with id {}, but now its id is {step_id}. Run the script without `--resume` to start over",
+                log.log_file,
+                index + 1,
+                completed_step.name,
+                completed_step.step_id,
+            );
+        }
+
+        let completed_step = completed_step.clone();
+        let output = completed_step.output.clone();
+        log.completed_steps_current_run.push(completed_step);
+
+        Ok(Some(output))
+    }
+
+    /// Records the step if it succeeded and all the steps before it did, saving the log
+    pub fn checkpoint(
+        &mut self,
+        tx_id: &str,
+        name: &str,
+        result: &Result<impl Into<ScriptTransactionOutput> + Clone, StarknetCommandError>,
+    ) -> Result<()> {
+        let Some(log) = &mut self.inner else {
+            return Ok(());
+        };
+        if log.interrupted {
+            return Ok(());
+        }
+
+        let Ok(response) = result else {
+            log.interrupted = true;
+            return Ok(());
+        };
+
+        let step_id = generate_step_id(
+            log.completed_steps_current_run
+                .last()
+                .map(|step| step.step_id.as_str()),
+            tx_id,
+        );
+        log.completed_steps_current_run.push(ExecutionStep {
+            step_id,
+            name: name.to_string(),
+            output: response.clone().into(),
+        });
+
+        write_steps_to_execution_log(
+            &log.log_file,
+            &log.script,
+            log.completed_steps_current_run.clone(),
+        )
+    }
+}
+
+pub fn load_execution_log(path: &Utf8PathBuf) -> Result<ExecutionLogSchema> {
+    if !path.exists() {
+        return Ok(ExecutionLogSchema {
+            version: EXECUTION_LOG_VERSION,
+            scripts: HashMap::new(),
+        });
+    }
+
+    let content = fs::read_to_string(path).context("Failed to load execution log")?;
+    match serde_json::from_str::<ExecutionLogSchema>(&content) {
+        Ok(execution_log) => {
+            verify_version(execution_log.version)?;
+            Ok(execution_log)
+        }
+        Err(_) => Err(anyhow!("Failed to parse execution log - it may be corrupt")),
+    }
+}
+
+fn write_steps_to_execution_log(
+    path: &Utf8PathBuf,
+    script: &str,
+    steps: Vec<ExecutionStep>,
+) -> Result<()> {
+    let mut execution_log = load_execution_log(path)?;
+    execution_log.scripts.insert(script.to_string(), steps);
+
+    fs::write(
+        path,
+        serde_json::to_string_pretty(&execution_log)
+            .expect("Failed to convert ExecutionLogSchema to json"),
+    )
+    .with_context(|| anyhow!("Failed to write completed steps to execution log {path}"))
+}
+
+fn verify_version(version: u8) -> Result<()> {
+    match version {
+        EXECUTION_LOG_VERSION => Ok(()),
+        _ => Err(anyhow!(format!(
+            "Unsupported execution log version {version}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::structs::InvokeResponse;
+    use crate::state::hashing::generate_invoke_tx_id;
+    use starknet::core::types::Felt;
+    use tempfile::TempDir;
+
+    fn log_file(tempdir: &TempDir) -> Utf8PathBuf {
+        Utf8PathBuf::from_path_buf(tempdir.path().join("execution_log.json")).unwrap()
+    }
+
+    fn invoke_tx_id(value: u32) -> String {
+        generate_invoke_tx_id(Felt::ONE, Felt::TWO, &[Felt::from(value)])
+    }
+
+    fn success(value: u32) -> Result<InvokeResponse, StarknetCommandError> {
+        Ok(InvokeResponse {
+            transaction_hash: Felt::from(value),
+        })
+    }
+
+    fn failure() -> Result<InvokeResponse, StarknetCommandError> {
+        Err(StarknetCommandError::UnknownError(anyhow!("failure")))
+    }
+
+    #[test]
+    fn checkpoints_successful_steps() {
+        let tempdir = TempDir::new().unwrap();
+        let path = log_file(&tempdir);
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), false).unwrap();
+
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+        log.checkpoint(&invoke_tx_id(2), "invoke", &success(2))
+            .unwrap();
+
+        let steps = load_execution_log(&path).unwrap().scripts["script"].clone();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].step_id, generate_step_id(None, &invoke_tx_id(1)));
+        assert_eq!(
+            steps[1].step_id,
+            generate_step_id(Some(&steps[0].step_id), &invoke_tx_id(2))
+        );
+    }
+
+    #[test]
+    fn stops_checkpointing_after_failure() {
+        let tempdir = TempDir::new().unwrap();
+        let path = log_file(&tempdir);
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), false).unwrap();
+
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+        log.checkpoint(&invoke_tx_id(2), "invoke", &failure())
+            .unwrap();
+        log.checkpoint(&invoke_tx_id(3), "invoke", &success(3))
+            .unwrap();
+
+        let steps = &load_execution_log(&path).unwrap().scripts["script"];
+        assert_eq!(steps.len(), 1);
+    }
+
+    #[test]
+    fn resumes_completed_steps() {
+        let tempdir = TempDir::new().unwrap();
+        let path = log_file(&tempdir);
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), false).unwrap();
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), true).unwrap();
+
+        assert_eq!(
+            log.get_output_if_completed(&invoke_tx_id(1)).unwrap(),
+            Some(success(1).unwrap().into())
+        );
+        assert_eq!(log.get_output_if_completed(&invoke_tx_id(2)).unwrap(), None);
+    }
+
+    #[test]
+    fn does_not_resume_without_flag() {
+        let tempdir = TempDir::new().unwrap();
+        let path = log_file(&tempdir);
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), false).unwrap();
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), false).unwrap();
+
+        assert_eq!(log.get_output_if_completed(&invoke_tx_id(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn detects_drift() {
+        let tempdir = TempDir::new().unwrap();
+        let path = log_file(&tempdir);
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), false).unwrap();
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+        log.checkpoint(&invoke_tx_id(2), "invoke", &success(2))
+            .unwrap();
+
+        let mut log = ExecutionLog::from(Some(path.clone()), "script".to_string(), true).unwrap();
+        log.get_output_if_completed(&invoke_tx_id(1)).unwrap();
+        let err = log.get_output_if_completed(&invoke_tx_id(3)).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("Script has changed since it was logged"));
+        assert!(err.to_string().contains("step 2 was `invoke`"));
+    }
+
+    #[test]
+    fn keeps_logs_of_other_scripts() {
+        let tempdir = TempDir::new().unwrap();
+        let path = log_file(&tempdir);
+        let mut log = ExecutionLog::from(Some(path.clone()), "first".to_string(), false).unwrap();
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+        let mut log = ExecutionLog::from(Some(path.clone()), "second".to_string(), false).unwrap();
+        log.checkpoint(&invoke_tx_id(1), "invoke", &success(1))
+            .unwrap();
+
+        let scripts = load_execution_log(&path).unwrap().scripts;
+        assert!(scripts.contains_key("first"));
+        assert!(scripts.contains_key("second"));
+    }
+}
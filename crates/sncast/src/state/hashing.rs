@@ -95,10 +95,22 @@ pub fn generate_invoke_tx_id(
     generate_id("invoke", bytes)
 }
 
+/// Id of the script step executing the transaction, depends on all the steps before it
+#[must_use]
+pub fn generate_step_id(previous_step_id: Option<&str>, tx_id: &str) -> String {
+    let bytes = [
+        previous_step_id.unwrap_or_default().serialise_as_bytes(),
+        tx_id.serialise_as_bytes(),
+    ]
+    .concat();
+    generate_id("step", bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::state::hashing::{
         generate_declare_tx_id, generate_deploy_tx_id, generate_id, generate_invoke_tx_id,
+        generate_step_id,
     };
     use conversions::IntoConv;
     use starknet::core::types::Felt;
@@ -156,4 +168,21 @@ mod tests {
             "9b7d3fa2d93d1360a343bfd1d3d76aedef74aace5a5ad47ddbda136d9ce9b244"
         );
     }
+
+    #[test]
+    fn step() {
+        let tx_id = generate_declare_tx_id("testcontract");
+
+        let first = generate_step_id(None, &tx_id);
+        assert_eq!(
+            first,
+            "0ea491007ee3b3be53cdd4507bdedfe49e4800da5639861f46abb118bb93bcf9"
+        );
+
+        let second = generate_step_id(Some(&first), &tx_id);
+        assert_eq!(
+            second,
+            "1e46156c804bf6a86aa635e287334ee4d554852fe6a010316d106179e5e0f637"
+        );
+    }
 }
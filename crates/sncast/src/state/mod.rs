@@ -1,2 +1,3 @@
+pub mod execution_log;
 pub mod hashing;
 pub mod state_file;
@@ -0,0 +1,41 @@
+use sncast_std::{invoke_parallel, call, InvokeCall, FeeSettings, EthFeeSettings};
+use starknet::{ContractAddress, Felt252TryIntoContractAddress};
+
+fn main() {
+    let map_contract_address: ContractAddress =
+        0x07537a17e169c96cf2b0392508b3a66cbc50c9a811a8a7896529004c5e93fdf6
+        .try_into()
+        .expect('Invalid contract address value');
+    let fee_settings = FeeSettings::Eth(EthFeeSettings { max_fee: Option::None });
+
+    let mut calls = array![];
+    let mut key = 0x20;
+    while key != 0x23 {
+        calls
+            .append(
+                InvokeCall {
+                    contract_address: map_contract_address,
+                    entry_point_selector: selector!("put"),
+                    calldata: array![key, key + 1],
+                    fee_settings,
+                }
+            );
+        key += 1;
+    };
+
+    let mut results = invoke_parallel(calls);
+    assert(results.len() == 3, 'invalid number of results');
+
+    loop {
+        match results.pop_front() {
+            Option::Some(result) => { result.expect('invoke failed'); },
+            Option::None => { break; }
+        }
+    };
+
+    let call_result = call(map_contract_address, selector!("get"), array![0x22])
+        .expect('call failed');
+    assert(call_result.data == array![0x23], 'invalid value');
+
+    println!("invoke_parallel succeeded");
+}
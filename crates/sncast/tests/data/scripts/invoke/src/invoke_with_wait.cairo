@@ -0,0 +1,57 @@
+use sncast_std::{
+    invoke_with_wait, call, get_nonce, tx_status, FeeSettings, EthFeeSettings, WaitConfig, WaitFor
+};
+use starknet::{ContractAddress, Felt252TryIntoContractAddress};
+
+fn main() {
+    let map_contract_address: ContractAddress =
+        0x07537a17e169c96cf2b0392508b3a66cbc50c9a811a8a7896529004c5e93fdf6
+        .try_into()
+        .expect('Invalid contract address value');
+    let fee_settings = FeeSettings::Eth(EthFeeSettings { max_fee: Option::None });
+    let no_wait = WaitConfig { wait_for: WaitFor::None, timeout: Option::None };
+    let receipt = WaitConfig { wait_for: WaitFor::Receipt, timeout: Option::Some(60) };
+
+    // Transactions are not awaited, so their nonces are set explicitly
+    let nonce = get_nonce('pending');
+
+    let first = invoke_with_wait(
+        map_contract_address,
+        selector!("put"),
+        array![0x30, 0x31],
+        fee_settings,
+        Option::Some(nonce),
+        no_wait
+    )
+        .expect('first invoke failed');
+    let second = invoke_with_wait(
+        map_contract_address,
+        selector!("put"),
+        array![0x31, 0x32],
+        fee_settings,
+        Option::Some(nonce + 1),
+        no_wait
+    )
+        .expect('second invoke failed');
+    let third = invoke_with_wait(
+        map_contract_address,
+        selector!("put"),
+        array![0x32, 0x33],
+        fee_settings,
+        Option::Some(nonce + 2),
+        receipt
+    )
+        .expect('third invoke failed');
+
+    println!("first: {first}");
+    println!("second: {second}");
+    println!("third: {third}");
+    let third_status = tx_status(third.transaction_hash).expect('tx_status failed');
+    println!("third status: {third_status}");
+
+    let call_result = call(map_contract_address, selector!("get"), array![0x30])
+        .expect('call failed');
+    assert(call_result.data == array![0x31], 'invalid value');
+
+    println!("invoke_with_wait succeeded");
+}
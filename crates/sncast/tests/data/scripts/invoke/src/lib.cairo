@@ -1,4 +1,6 @@
 mod contract_does_not_exist;
+mod invoke_parallel;
+mod invoke_with_wait;
 mod max_fee_too_low;
 mod wrong_calldata;
 mod wrong_function_name;
@@ -56,6 +56,84 @@ fn test_happy_case_cairo_expression_calldata() {
     "});
 }
 
+#[test]
+fn test_happy_case_abi_file() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "call",
+        "--url",
+        URL,
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "(0x0_felt252, 0x2137)",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+        "--block-id",
+        "latest",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: []
+    "});
+}
+
+#[test]
+fn test_abi_file_without_function() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "call",
+        "--url",
+        URL,
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "nonexistent_get",
+        "--abi-file",
+        "tests/data/files/map_abi.json",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Function `nonexistent_get` not found in the ABI, available functions: get, put",
+    );
+}
+
+#[test]
+fn test_invalid_abi_file() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "call",
+        "--url",
+        URL,
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "get",
+        "--abi-file",
+        "tests/data/files/correct_snfoundry.toml",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Failed to parse ABI file tests/data/files/correct_snfoundry.toml[..]",
+    );
+}
+
 #[tokio::test]
 async fn test_call_after_storage_changed() {
     invoke_contract(
@@ -226,3 +304,64 @@ fn test_wrong_block_id() {
         "},
     );
 }
+
+#[test]
+fn test_happy_case_address_book_name() {
+    let tempdir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tempdir.path().join("snfoundry_addresses.toml"),
+        format!("[alpha-sepolia]\nmap = \"{MAP_CONTRACT_ADDRESS_SEPOLIA}\"\n"),
+    )
+    .unwrap();
+
+    let args = vec![
+        "call",
+        "--url",
+        URL,
+        "--contract-address",
+        "@map",
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+        "--block-id",
+        "latest",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: call
+        response: [0x0]
+    "});
+}
+
+#[test]
+fn test_address_book_name_from_other_network() {
+    let tempdir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tempdir.path().join("snfoundry_addresses.toml"),
+        format!("[alpha-mainnet]\nmap = \"{MAP_CONTRACT_ADDRESS_SEPOLIA}\"\n"),
+    )
+    .unwrap();
+
+    let args = vec![
+        "call",
+        "--url",
+        URL,
+        "--contract-address",
+        "@map",
+        "--function",
+        "get",
+        "--calldata",
+        "0x0",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Contract with name = map not found in address book for network = alpha-sepolia",
+    );
+}
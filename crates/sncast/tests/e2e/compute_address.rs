@@ -0,0 +1,134 @@
+use crate::helpers::runner::runner;
+use indoc::{formatdoc, indoc};
+use shared::test_utils::output_assert::assert_stderr_contains;
+use sncast::helpers::constants::UDC_ADDRESS;
+use starknet::core::crypto::pedersen_hash;
+use starknet::core::types::Felt;
+use starknet::core::utils::get_contract_address;
+
+const CLASS_HASH: &str = "0x123";
+const SALT: &str = "0x456";
+const DEPLOYER: &str = "0x789";
+
+fn felt(value: &str) -> Felt {
+    Felt::from_hex(value).unwrap()
+}
+
+#[test]
+fn test_udc() {
+    let args = vec![
+        "compute-address",
+        "--class-hash",
+        CLASS_HASH,
+        "--salt",
+        SALT,
+        "--constructor-calldata",
+        "0x1",
+        "0x2",
+    ];
+
+    let expected = get_contract_address(
+        felt(SALT),
+        felt(CLASS_HASH),
+        &[Felt::ONE, Felt::TWO],
+        Felt::ZERO,
+    );
+
+    runner(&args).assert().success().stdout_eq(formatdoc! {r"
+        command: compute-address
+        contract_address: {expected:#x}
+    "});
+}
+
+#[test]
+fn test_udc_unique() {
+    let args = vec![
+        "compute-address",
+        "--class-hash",
+        CLASS_HASH,
+        "--salt",
+        SALT,
+        "--deployer-address",
+        DEPLOYER,
+        "--unique",
+    ];
+
+    let expected = get_contract_address(
+        pedersen_hash(&felt(DEPLOYER), &felt(SALT)),
+        felt(CLASS_HASH),
+        &[],
+        UDC_ADDRESS,
+    );
+
+    runner(&args).assert().success().stdout_eq(formatdoc! {r"
+        command: compute-address
+        contract_address: {expected:#x}
+    "});
+}
+
+#[test]
+fn test_deploy_syscall() {
+    let args = vec![
+        "compute-address",
+        "--class-hash",
+        CLASS_HASH,
+        "--salt",
+        SALT,
+        "--deployer-address",
+        DEPLOYER,
+        "--derivation",
+        "deploy-syscall",
+    ];
+
+    let expected = get_contract_address(felt(SALT), felt(CLASS_HASH), &[], felt(DEPLOYER));
+
+    runner(&args).assert().success().stdout_eq(formatdoc! {r"
+        command: compute-address
+        contract_address: {expected:#x}
+    "});
+}
+
+#[test]
+fn test_unique_without_deployer() {
+    let args = vec![
+        "compute-address",
+        "--class-hash",
+        CLASS_HASH,
+        "--salt",
+        SALT,
+        "--unique",
+    ];
+
+    let output = runner(&args).assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: compute-address
+        error: Argument `--deployer-address` must be passed with `--unique`
+        "},
+    );
+}
+
+#[test]
+fn test_deployer_without_unique() {
+    let args = vec![
+        "compute-address",
+        "--class-hash",
+        CLASS_HASH,
+        "--salt",
+        SALT,
+        "--deployer-address",
+        DEPLOYER,
+    ];
+
+    let output = runner(&args).assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: compute-address
+        error: Argument `--deployer-address` can only be used with `--unique` or `--derivation deploy-syscall`
+        "},
+    );
+}
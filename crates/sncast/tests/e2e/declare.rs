@@ -7,12 +7,15 @@ use crate::helpers::fixtures::{
 use crate::helpers::runner::runner;
 use configuration::CONFIG_FILENAME;
 use indoc::indoc;
-use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use scarb_api::{ScarbCommand, StarknetContractArtifacts};
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains, AsOutput};
 use sncast::helpers::constants::{ARGENT_CLASS_HASH, BRAAVOS_CLASS_HASH, OZ_CLASS_HASH};
 use sncast::AccountType;
+use starknet::core::types::contract::CompiledClass;
 use starknet::core::types::Felt;
 use starknet::core::types::TransactionReceipt::Declare;
 use std::fs;
+use std::path::Path;
 use test_case::test_case;
 
 #[test_case("oz_cairo_0"; "cairo_0_account")]
@@ -332,6 +335,202 @@ async fn test_invalid_nonce() {
     );
 }
 
+#[tokio::test]
+async fn test_compiled_class_hash_mismatch() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "1124");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--compiled-class-hash",
+        "0x1",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: declare
+        error: Compiled class hash 0x1 does not match the one computed by the node, compiled class hash of the local CASM: 0x[..]
+        The node most likely expects 0x[..], computed from CASM compiled with the latest compiler, pass it with `--compiled-class-hash 0x[..]`
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_compiled_class_hash_without_casm() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "5432");
+    let compiled_class_hash = map_compiled_class_hash(contract_path.path());
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "--json",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--compiled-class-hash",
+        &compiled_class_hash,
+        "--fee-token",
+        "eth",
+    ];
+
+    // CASM is not needed with `--compiled-class-hash`, so the compiler is not run
+    let snapbox = runner(&args)
+        .env(
+            "UNIVERSAL_SIERRA_COMPILER",
+            "/nonexistent/universal-sierra-compiler",
+        )
+        .current_dir(contract_path.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let hash = get_transaction_hash(&output);
+    let receipt = get_transaction_receipt(hash).await;
+
+    assert!(matches!(receipt, Declare(_)));
+}
+
+#[test]
+fn test_missing_universal_sierra_compiler() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "6543");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args)
+        .env(
+            "UNIVERSAL_SIERRA_COMPILER",
+            "/nonexistent/universal-sierra-compiler",
+        )
+        .current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: declare
+        error: Failed to compile contract Map to CASM, which is needed to compute its compiled class hash: [..]Cannot find `universal-sierra-compiler` binary[..]
+        "},
+    );
+}
+
+/// Compiled class hash of the `Map` contract in `contract_path`, computed the way `declare` does
+fn map_compiled_class_hash(contract_path: &Path) -> String {
+    ScarbCommand::new_with_stdio()
+        .current_dir(contract_path)
+        .arg("build")
+        .run()
+        .unwrap();
+    let sierra =
+        fs::read_to_string(contract_path.join("target/dev/map_Map.contract_class.json")).unwrap();
+    let casm = StarknetContractArtifacts::from_sierra(sierra)
+        .casm()
+        .unwrap()
+        .to_string();
+    let compiled_class: CompiledClass = serde_json::from_str(&casm).unwrap();
+
+    format!("{:#x}", compiled_class.class_hash().unwrap())
+}
+
+#[tokio::test]
+async fn test_max_fee_exceeds_max_fee_cap() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "4321");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "--int-format",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--max-fee",
+        "99999999999999999",
+        "--max-fee-cap",
+        "1000",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: declare
+        error: Max fee = 99999999999999999 exceeds --max-fee-cap = 1000, the transaction was not sent
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_estimated_fee_exceeds_max_fee_cap() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "4322");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "--int-format",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--max-fee-cap",
+        "1",
+        "--fee-token",
+        "strk",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: declare
+        error: Max fee = [..], from the estimated fee = [..] and --fee-multiplier = 1.1, exceeds --max-fee-cap = 1, the transaction was not sent
+        "},
+    );
+}
+
 #[tokio::test]
 async fn test_wrong_contract_name_passed() {
     let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
@@ -500,6 +699,41 @@ fn test_scarb_no_sierra_artifact() {
     runner(&args).current_dir(tempdir.path()).assert().success();
 }
 
+#[should_panic(expected = "No Starknet contract targets found in any package of the workspace")]
+#[test]
+fn test_scarb_no_starknet_contract_target() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+    fs::write(
+        tempdir.path().join("Scarb.toml"),
+        indoc! {r#"
+            [package]
+            name = "map"
+            version = "0.1.0"
+
+            [dependencies]
+            starknet = ">=2.0.2"
+        "#},
+    )
+    .unwrap();
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user1",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--fee-token",
+        "eth",
+    ];
+
+    runner(&args).current_dir(tempdir.path()).assert().success();
+}
+
 #[test]
 fn test_scarb_no_casm_artifact() {
     let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/no_casm");
@@ -660,11 +894,14 @@ async fn test_no_scarb_profile() {
     let snapbox = runner(&args).current_dir(contract_path.path());
     let output = snapbox.assert().success();
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        "[WARNING] W0006: Profile profile5 does not exist in scarb, using 'release' profile.",
+    );
     assert_stdout_contains(
         output,
         indoc! {"
             [..]
-            [WARNING] Profile profile5 does not exist in scarb, using 'release' profile.
             command: declare
             class_hash: [..]
             transaction_hash: [..]
@@ -675,3 +912,148 @@ async fn test_no_scarb_profile() {
         "},
     );
 }
+
+#[tokio::test]
+async fn test_no_scarb_profile_silent() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "70");
+    fs::copy(
+        "tests/data/files/correct_snfoundry.toml",
+        contract_path.path().join(CONFIG_FILENAME),
+    )
+    .expect("Failed to copy config file to temp dir");
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--profile",
+        "profile5",
+        "--silent",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--max-fee",
+        "99999999999999999",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+
+    assert!(!output.as_stderr().contains("[WARNING]"));
+    assert!(stdout.contains("command: declare"));
+}
+
+#[tokio::test]
+async fn test_auto_bump_fee_reports_attempt() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "auto_bump",
+    );
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--fee-token",
+        "eth",
+        "--auto-bump-fee",
+        "--max-bump-attempts",
+        "2",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    let stderr = output.as_stderr().to_string();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: declare
+        class_hash: 0x[..]
+        transaction_hash: 0x[..]
+        "},
+    );
+    assert_stderr_contains(
+        stderr,
+        "Declare attempt 1 with max fee = [..] (--fee-multiplier = 1.1)",
+    );
+}
+
+#[tokio::test]
+async fn test_auto_bump_fee_retries_with_bumped_fee() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "auto_bump_retry",
+    );
+    let accounts_json_path = get_accounts_path("tests/data/accounts/accounts.json");
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user8",
+        "--json",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--fee-token",
+        "eth",
+        "--max-fee",
+        "2",
+        "--auto-bump-fee",
+        "--max-bump-attempts",
+        "3",
+    ];
+
+    let snapbox = runner(&args).current_dir(contract_path.path());
+    let output = snapbox.assert().success();
+
+    // Attempts do not mix with the JSON output
+    assert!(!output.as_stdout().contains("Declare attempt"));
+    assert_stderr_contains(
+        output,
+        indoc! {r#"
+        Declare attempt 1 with max fee = 2
+        Declare attempt 2 with max fee = 3
+        Declare attempt 3 with max fee = 5
+        [..]"error":"Max fee is smaller than the minimal transaction cost in all 3 attempts with --auto-bump-fee, pass a higher --max-bump-attempts or --fee-multiplier"[..]
+        "#},
+    );
+}
+
+#[test]
+fn test_auto_bump_fee_conflicts_with_max_gas() {
+    let args = vec![
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--max-gas",
+        "1",
+        "--auto-bump-fee",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: the argument '--[..]' cannot be used with '--[..]'",
+    );
+}
@@ -0,0 +1,199 @@
+use crate::helpers::constants::{CONTRACTS_DIR, URL};
+use crate::helpers::fixtures::{
+    create_and_deploy_oz_account, duplicate_contract_directory_with_salt, get_transaction_receipt,
+    join_tempdirs,
+};
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::assert_stderr_contains;
+use starknet::core::types::Felt;
+use starknet::core::types::TransactionReceipt::Declare;
+use std::path::Path;
+
+fn declare_all(current_dir: &Path, contract_names: &[&str], fail_fast: bool) -> Value {
+    let mut args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+        "declare-all",
+        "--url",
+        URL,
+        "--fee-token",
+        "strk",
+    ];
+    for contract_name in contract_names {
+        args.extend(["--contract-name", contract_name]);
+    }
+    if fail_fast {
+        args.push("--fail-fast");
+    }
+
+    let output = runner(&args)
+        .current_dir(current_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[tokio::test]
+async fn test_happy_case() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "declare_all",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let output = declare_all(tempdir.path(), &["Map", "Map"], false);
+
+    let declared = output["declared"].as_array().unwrap();
+    assert_eq!(declared.len(), 1);
+    assert_eq!(declared[0][0], "Map");
+    let transaction_hash: Felt = declared[0][2].as_str().unwrap().parse().unwrap();
+    let receipt = get_transaction_receipt(transaction_hash).await;
+    assert!(matches!(receipt, Declare(_)));
+
+    // The same class is not declared twice
+    let skipped = output["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0][0], "Map");
+    assert_eq!(skipped[0][1], declared[0][1]);
+    assert!(output["failed"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_already_declared_class_is_skipped() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "declare_all_skip",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let first = declare_all(tempdir.path(), &["Map"], false);
+    let second = declare_all(tempdir.path(), &["Map"], false);
+
+    assert!(second["declared"].as_array().unwrap().is_empty());
+    let skipped = second["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0][1], first["declared"][0][1]);
+}
+
+#[tokio::test]
+async fn test_failed_declaration_does_not_stop_others() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "declare_all_continue",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let output = declare_all(tempdir.path(), &["Nonexistent", "Map"], false);
+
+    let failed = output["failed"].as_array().unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0][0], "Nonexistent");
+    assert!(failed[0][1]
+        .as_str()
+        .unwrap()
+        .contains("Failed to find Nonexistent artifact in starknet_artifacts.json file"));
+    assert_eq!(output["declared"][0][0], "Map");
+    assert!(output.get("not_attempted").is_none());
+}
+
+#[tokio::test]
+async fn test_fail_fast() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "declare_all_fail_fast",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let output = declare_all(tempdir.path(), &["Nonexistent", "Map"], true);
+
+    assert_eq!(output["failed"][0][0], "Nonexistent");
+    assert!(output["declared"].as_array().unwrap().is_empty());
+    assert_eq!(output["not_attempted"], serde_json::json!(["Map"]));
+}
+
+#[tokio::test]
+async fn test_contracts_filter() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "declare_all_contracts_filter",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+        "declare-all",
+        "--url",
+        URL,
+        "--fee-token",
+        "strk",
+        "--contracts-filter",
+        "Ma*",
+    ];
+    let output = runner(&args)
+        .current_dir(tempdir.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output: Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(output["declared"][0][0], "Map");
+    assert!(output["failed"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_contracts_filter_matching_nothing() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "declare_all_contracts_filter_no_match",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "declare-all",
+        "--url",
+        URL,
+        "--fee-token",
+        "strk",
+        "--contracts-filter",
+        "ERC20*",
+    ];
+    let output = runner(&args).current_dir(tempdir.path()).assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: declare-all
+        error: No contracts match --contracts-filter = ERC20*, available contracts: Map
+        "},
+    );
+}
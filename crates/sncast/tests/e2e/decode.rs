@@ -0,0 +1,105 @@
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::{json, Value};
+use shared::test_utils::output_assert::assert_stderr_contains;
+
+const ABI_FILE: &str = "tests/data/files/decode_abi.json";
+
+#[test]
+fn test_happy_case() {
+    let args = vec![
+        "decode",
+        "--abi-file",
+        ABI_FILE,
+        "--type",
+        "Shape",
+        "--data",
+        "0x1",
+        "3",
+        "4",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: decode
+        response: Shape::Square(Point { x: 3_u32, y: 4_u32 })
+    "});
+}
+
+#[test]
+fn test_happy_case_json() {
+    let args = vec![
+        "--json",
+        "decode",
+        "--abi-file",
+        ABI_FILE,
+        "--type",
+        "core::array::Array::<shapes::Point>",
+        "--data",
+        "1",
+        "5",
+        "6",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let json: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "command": "decode",
+            "response": "array![Point { x: 5_u32, y: 6_u32 }]",
+        })
+    );
+}
+
+#[test]
+fn test_too_many_felts() {
+    let args = vec![
+        "decode",
+        "--abi-file",
+        ABI_FILE,
+        "--type",
+        "shapes::Shape",
+        "--data",
+        "2",
+        "7",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: decode
+        error: Too many felts: 1 left after decoding a value of type shapes::Shape
+        "},
+    );
+}
+
+#[test]
+fn test_unknown_type() {
+    let args = vec![
+        "decode",
+        "--abi-file",
+        ABI_FILE,
+        "--type",
+        "Triangle",
+        "--data",
+        "0",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: decode
+        error: Type Triangle is not supported and was not found in the ABI
+        "},
+    );
+}
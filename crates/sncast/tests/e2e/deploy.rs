@@ -11,7 +11,10 @@ use indoc::indoc;
 use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
 use sncast::helpers::constants::{ARGENT_CLASS_HASH, BRAAVOS_CLASS_HASH, OZ_CLASS_HASH};
 use sncast::AccountType;
-use starknet::core::types::{Felt, TransactionReceipt::Deploy};
+use starknet::core::types::{
+    Felt,
+    TransactionReceipt::{Deploy, Invoke},
+};
 use test_case::test_case;
 
 #[test_case("oz_cairo_0"; "cairo_0_account")]
@@ -94,6 +97,248 @@ async fn test_happy_case_human_readable() {
     );
 }
 
+#[tokio::test]
+async fn test_happy_case_with_receipt() {
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--int-format",
+        "--wait",
+        "--receipt",
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "0x2",
+        "--unique",
+        "--max-fee",
+        "99999999999999999",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8(output).unwrap();
+
+    assert!(stdout.contains("command: receipt"));
+    assert!(stdout.contains("finality_status: AcceptedOnL2"));
+    assert!(stdout.contains("execution_status: Succeeded"));
+    assert!(stdout.contains("fee_unit: WEI"));
+    assert!(stdout.contains("events: [["));
+}
+
+#[tokio::test]
+async fn test_happy_case_save_as() {
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "0x3",
+        "--unique",
+        "--save-as",
+        "my_map",
+        "--max-fee",
+        "99999999999999999",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let contract_address = output["contract_address"].as_str().unwrap();
+
+    let address_book =
+        std::fs::read_to_string(tempdir.path().join("snfoundry_addresses.toml")).unwrap();
+    assert!(address_book.contains("[alpha-sepolia]"));
+    assert!(address_book.contains(&format!("my_map = \"{contract_address}\"")));
+}
+
+#[tokio::test]
+async fn test_happy_case_with_init() {
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "0x4",
+        "--unique",
+        "--init",
+        "put",
+        "--init-calldata",
+        "0x1",
+        "0x2",
+        "--max-fee",
+        "99999999999999999",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let deploy_hash = output["transaction_hash"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let init_hash = output["init_transaction_hash"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(output["contract_address"].is_string());
+
+    assert!(matches!(
+        get_transaction_receipt(deploy_hash).await,
+        Deploy(_)
+    ));
+    assert!(matches!(
+        get_transaction_receipt(init_hash).await,
+        Invoke(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_init_fails() {
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--salt",
+        "0x5",
+        "--unique",
+        "--init",
+        "nonexistent_init",
+        "--max-fee",
+        "99999999999999999",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: deploy
+        error: Contract has been deployed at address 0x[..] (transaction hash: 0x[..]), but invoking its initializer failed: [..]
+        "},
+    );
+}
+
+#[test]
+fn test_init_calldata_requires_init() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        ACCOUNT,
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--init-calldata",
+        "0x1",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: the following required arguments were not provided:\n  --init <INIT>",
+    );
+}
+
+#[test]
+fn test_save_as_invalid_name() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        ACCOUNT,
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+        "--save-as",
+        "my map",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: invalid value 'my map' for '--save-as <SAVE_AS>': Invalid address book entry name = my map[..]",
+    );
+}
+
+#[test]
+fn test_receipt_requires_wait() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        ACCOUNT,
+        "--receipt",
+        "deploy",
+        "--url",
+        URL,
+        "--class-hash",
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA,
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: the following required arguments were not provided:\n  --wait",
+    );
+}
+
 #[test_case(DEVNET_OZ_CLASS_HASH_CAIRO_0.parse().unwrap(), AccountType::OpenZeppelin; "cairo_0_class_hash")]
 #[test_case(OZ_CLASS_HASH, AccountType::OpenZeppelin; "cairo_1_class_hash")]
 #[test_case(ARGENT_CLASS_HASH, AccountType::Argent; "argent_class_hash")]
@@ -338,7 +583,7 @@ fn test_wrong_calldata() {
         output,
         indoc! {r"
         command: deploy
-        error: An error occurred in the called contract[..]('Input too long for arguments')[..]
+        error: Invalid constructor calldata: passed 4 felts, expected 3 for constructor(first: core::felt252, second: core::integer::u256)
         "},
     );
 }
@@ -364,7 +609,7 @@ async fn test_contract_not_declared() {
 
     assert_stderr_contains(
         output,
-        "Error: An error occurred in the called contract[..]Class with hash[..]is not declared[..]",
+        "Error: Class with hash 0x1 is not declared, declare it first with `sncast declare --contract-name <CONTRACT_NAME>`",
     );
 }
 
@@ -0,0 +1,240 @@
+use crate::helpers::constants::{
+    CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA, CONTRACTS_DIR,
+    MAP_CONTRACT_CLASS_HASH_SEPOLIA, URL,
+};
+use crate::helpers::fixtures::{
+    create_and_deploy_account, duplicate_contract_directory_with_salt, join_tempdirs,
+};
+use crate::helpers::runner::runner;
+use indoc::{formatdoc, indoc};
+use shared::test_utils::output_assert::assert_stderr_contains;
+use sncast::helpers::constants::OZ_CLASS_HASH;
+use sncast::AccountType;
+use std::fs;
+
+#[tokio::test]
+async fn test_happy_case() {
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+
+    // `consumer` is listed first, but is deployed after `map` it references
+    let plan = formatdoc! {r#"
+        [[contract]]
+        id = "consumer"
+        class_hash = "{CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA}"
+        constructor_calldata = ["@map", "0x1", "0x0"]
+        unique = true
+
+        [[contract]]
+        id = "map"
+        class_hash = "{MAP_CONTRACT_CLASS_HASH_SEPOLIA}"
+        unique = true
+    "#};
+    fs::write(tempdir.path().join("deployment.toml"), plan).unwrap();
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+        "deploy-plan",
+        "--url",
+        URL,
+        "--path",
+        "deployment.toml",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let contracts = output["contracts"].as_array().unwrap();
+    assert_eq!(contracts.len(), 2);
+    assert_eq!(contracts[0][0], "map");
+    assert_eq!(contracts[1][0], "consumer");
+    assert_eq!(output["results_file"], "deployment_results.toml");
+
+    let results = fs::read_to_string(tempdir.path().join("deployment_results.toml")).unwrap();
+    let results: toml::Value = toml::from_str(&results).unwrap();
+    let deployed = results["contract"].as_array().unwrap();
+    assert_eq!(deployed[0]["id"].as_str(), Some("map"));
+    assert_eq!(
+        deployed[0]["contract_address"].as_str(),
+        contracts[0][2].as_str()
+    );
+    assert_eq!(deployed[1]["id"].as_str(), Some("consumer"));
+    assert!(deployed[1].get("declare_transaction_hash").is_none());
+}
+
+#[tokio::test]
+async fn test_failed_step_is_reported() {
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+
+    let plan = formatdoc! {r#"
+        [[contract]]
+        id = "map"
+        class_hash = "{MAP_CONTRACT_CLASS_HASH_SEPOLIA}"
+        unique = true
+
+        [[contract]]
+        id = "broken"
+        class_hash = "0x1"
+    "#};
+    fs::write(tempdir.path().join("deployment.toml"), plan).unwrap();
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "deploy-plan",
+        "--url",
+        URL,
+        "--path",
+        "deployment.toml",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: deploy-plan
+        error: Deployment plan failed at step with id = broken, results of completed steps were saved to deployment_results.toml: [..]
+        "},
+    );
+
+    let results = fs::read_to_string(tempdir.path().join("deployment_results.toml")).unwrap();
+    assert!(results.contains(r#"id = "map""#));
+    assert!(!results.contains(r#"id = "broken""#));
+}
+
+#[tokio::test]
+async fn test_dry_run() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "deploy_plan_dry_run",
+    );
+    let tempdir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let plan = formatdoc! {r#"
+        [[contract]]
+        id = "new_map"
+        contract_name = "Map"
+
+        [[contract]]
+        id = "other_map"
+        contract_name = "Map"
+
+        [[contract]]
+        id = "consumer"
+        class_hash = "{CONSTRUCTOR_WITH_PARAMS_CONTRACT_CLASS_HASH_SEPOLIA}"
+        constructor_calldata = ["0x1", "0x1", "0x0"]
+
+        [[contract]]
+        id = "map"
+        class_hash = "{MAP_CONTRACT_CLASS_HASH_SEPOLIA}"
+    "#};
+    fs::write(tempdir.path().join("deployment.toml"), plan).unwrap();
+
+    let args = vec![
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+        "deploy-plan",
+        "--url",
+        URL,
+        "--path",
+        "deployment.toml",
+        "--fee-token",
+        "eth",
+        "--dry-run",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    let steps = output["steps"].as_array().unwrap();
+    let actions: Vec<_> = steps
+        .iter()
+        .map(|step| (step[0].as_str().unwrap(), step[1].as_str().unwrap()))
+        .collect();
+    assert_eq!(
+        actions,
+        [
+            ("new_map", "declare"),
+            ("new_map", "deploy"),
+            (
+                "other_map",
+                "skip declare (class declared in an earlier step)"
+            ),
+            ("other_map", "deploy"),
+            ("consumer", "deploy"),
+            ("map", "deploy"),
+        ]
+    );
+    assert!(steps[1][3]
+        .as_str()
+        .unwrap()
+        .starts_with("not estimated: class is not declared yet"));
+    assert_eq!(steps[2][3], "0");
+    assert!(steps[5][3].as_str().unwrap().parse::<u128>().unwrap() > 0);
+    assert_eq!(output["fee_unit"], "wei");
+    assert_eq!(
+        output["message"],
+        "Dry run, no transactions were sent, fees of 2 step(s) could not be estimated and are not included in the total"
+    );
+
+    assert!(!tempdir.path().join("deployment_results.toml").exists());
+}
+
+#[test]
+fn test_dependency_cycle() {
+    let tempdir = tempfile::tempdir().unwrap();
+
+    let plan = indoc! {r#"
+        [[contract]]
+        id = "a"
+        class_hash = "0x1"
+        depends_on = ["b"]
+
+        [[contract]]
+        id = "b"
+        class_hash = "0x1"
+        constructor_calldata = ["@a"]
+    "#};
+    fs::write(tempdir.path().join("deployment.toml"), plan).unwrap();
+
+    let args = vec![
+        "deploy-plan",
+        "--url",
+        URL,
+        "--path",
+        "deployment.toml",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        Error: Invalid deployment plan at = deployment.toml
+
+        Caused by:
+            Deployment plan contains a dependency cycle: a -> b -> a
+        "},
+    );
+}
@@ -0,0 +1,183 @@
+use crate::helpers::runner::runner;
+use indoc::{formatdoc, indoc};
+use serde_json::json;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use std::env;
+use std::fs;
+use std::net::TcpListener;
+use std::process::Command;
+use tempfile::tempdir;
+
+const DEVNET_BINARY: &str = "tests/utils/devnet/starknet-devnet";
+
+/// Stops the devnet running on the port when dropped, so it is not left running when an assertion fails
+struct StopDevnetOnDrop(&'static str);
+
+impl Drop for StopDevnetOnDrop {
+    fn drop(&mut self) {
+        // Fails if the test already stopped the devnet
+        let _ = runner(&["devnet", "stop", "--port", self.0]).output();
+    }
+}
+
+#[tokio::test]
+async fn test_start_and_stop() {
+    let tempdir = tempdir().unwrap();
+    let accounts_file = tempdir.path().join("accounts.json");
+    let profile_out = tempdir.path().join("snfoundry.toml");
+
+    let args = vec![
+        "devnet",
+        "start",
+        "--binary",
+        DEVNET_BINARY,
+        "--port",
+        "5061",
+        "--accounts",
+        "2",
+        "--accounts-file",
+        accounts_file.to_str().unwrap(),
+        "--profile-out",
+        profile_out.to_str().unwrap(),
+    ];
+    let _stop_devnet = StopDevnetOnDrop("5061");
+    let output = runner(&args).assert().success();
+    assert_stdout_contains(
+        output,
+        formatdoc! {r"
+            command: devnet start
+            accounts: [devnet-0, devnet-1]
+            accounts_file: {}
+            profile_out: {}
+            url: http://127.0.0.1:5061/rpc
+        ", accounts_file.display(), profile_out.display()},
+    );
+
+    let args = vec!["--profile", "devnet", "show-config"];
+    let output = runner(&args).current_dir(tempdir.path()).assert().success();
+    assert_stdout_contains(
+        output,
+        formatdoc! {r"
+            command: show-config
+            profile: devnet
+            account: devnet-0
+            accounts_file_path: {}
+            chain_id: alpha-sepolia
+            rpc_url: http://127.0.0.1:5061/rpc
+        ", accounts_file.display()},
+    );
+
+    let args = vec!["devnet", "stop", "--port", "5061"];
+    let output = runner(&args).assert().success();
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+            command: devnet stop
+            url: http://127.0.0.1:5061/rpc
+        "},
+    );
+    // Stopping waits until the port is released
+    assert!(TcpListener::bind("127.0.0.1:5061").is_ok());
+
+    let output = runner(&args).assert().success();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+            command: devnet stop
+            error: No devnet started with `sncast devnet start` is running on port 5061
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_start_print_env() {
+    let args = vec![
+        "devnet",
+        "start",
+        "--binary",
+        DEVNET_BINARY,
+        "--port",
+        "5062",
+        "--print-env",
+    ];
+    let _stop_devnet = StopDevnetOnDrop("5062");
+    let output = runner(&args).assert().success();
+    assert_stdout_contains(output, r#"export DEVNET_URL="http://127.0.0.1:5062/rpc""#);
+}
+
+#[tokio::test]
+async fn test_start_port_in_use() {
+    let _listener = TcpListener::bind("127.0.0.1:5063").unwrap();
+
+    let args = vec![
+        "devnet",
+        "start",
+        "--binary",
+        DEVNET_BINARY,
+        "--port",
+        "5063",
+    ];
+    let output = runner(&args).assert().success();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+            command: devnet start
+            error: Port 5063 is already in use, pass a different one with `--port`
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_start_binary_does_not_exist() {
+    let args = vec![
+        "devnet",
+        "start",
+        "--binary",
+        "/nonexistent/starknet-devnet",
+        "--port",
+        "5064",
+    ];
+    let output = runner(&args).assert().success();
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+            command: devnet start
+            error: Devnet binary /nonexistent/starknet-devnet does not exist
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_stop_does_not_kill_process_reusing_pid() {
+    // Stands in for an unrelated process that got the PID of an exited devnet
+    let mut other_process = Command::new("sleep").arg("30").spawn().unwrap();
+
+    let state_dir = env::temp_dir().join("sncast-devnet");
+    fs::create_dir_all(&state_dir).unwrap();
+    let state_file = state_dir.join("5065.json");
+    let state = json!({
+        "url": "http://127.0.0.1:5065/rpc",
+        "accounts_file": state_dir.join("5065-accounts.json"),
+        "process": {
+            "kind": "binary",
+            "pid": other_process.id(),
+            "binary": DEVNET_BINARY,
+        },
+    });
+    fs::write(&state_file, state.to_string()).unwrap();
+
+    let args = vec!["devnet", "stop", "--port", "5065"];
+    let output = runner(&args).assert().success();
+    assert_stderr_contains(
+        output,
+        formatdoc! {r"
+            command: devnet stop
+            error: Devnet process with PID {} is not running anymore
+        ", other_process.id()},
+    );
+
+    assert!(other_process.try_wait().unwrap().is_none());
+    assert!(!state_file.exists());
+
+    other_process.kill().unwrap();
+}
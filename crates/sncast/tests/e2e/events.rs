@@ -0,0 +1,95 @@
+use crate::helpers::constants::URL;
+use crate::helpers::fixtures::create_and_deploy_oz_account;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::assert_stderr_contains;
+use starknet::core::types::Felt;
+use std::fs;
+
+#[tokio::test]
+async fn test_happy_case() {
+    let tempdir = create_and_deploy_oz_account().await;
+
+    let accounts = fs::read_to_string(tempdir.path().join("accounts.json")).unwrap();
+    let accounts: Value = serde_json::from_str(&accounts).unwrap();
+    let address = accounts["alpha-sepolia"]["my_account"]["address"]
+        .as_str()
+        .unwrap();
+
+    // Account emits `OwnerAdded` when it is deployed, a chunk size of 1 makes the command paginate
+    let args = vec![
+        "--json",
+        "events",
+        "--url",
+        URL,
+        "--address",
+        address,
+        "--keys",
+        "OwnerAdded",
+        "--chunk-size",
+        "1",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+    let output: Value = serde_json::from_slice(&output).unwrap();
+
+    let events = output["events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0][0], "OwnerAdded");
+    assert_eq!(
+        events[0][1]
+            .as_str()
+            .map(|from| from.trim_start_matches("0x0")),
+        Some(address.trim_start_matches("0x0"))
+    );
+}
+
+#[tokio::test]
+async fn test_no_events() {
+    let args = vec![
+        "events",
+        "--url",
+        URL,
+        "--address",
+        "0x1",
+        "--from-block",
+        "latest",
+        "--to-block",
+        "latest",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: events
+        events: []
+    "});
+}
+
+#[test]
+fn test_invalid_block_id() {
+    let args = vec!["events", "--url", URL, "--from-block", "first"];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: invalid value 'first' for '--from-block <FROM_BLOCK>': Invalid block id = first, the value is not recognized. [..]",
+    );
+}
+
+#[test]
+fn test_block_hash_without_prefix() {
+    let args = vec!["events", "--url", URL, "--to-block", "1a2b"];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: invalid value '1a2b' for '--to-block <TO_BLOCK>': Invalid block id = 1a2b, block hash must be prefixed with 0x. [..]",
+    );
+}
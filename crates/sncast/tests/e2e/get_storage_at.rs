@@ -0,0 +1,124 @@
+use crate::helpers::constants::{MAP_CONTRACT_ADDRESS_SEPOLIA, URL};
+use crate::helpers::fixtures::invoke_contract;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::{json, Value};
+use shared::test_utils::output_assert::assert_stderr_contains;
+
+#[tokio::test]
+async fn test_storage_var_after_invoke() {
+    invoke_contract(
+        "user13",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "put",
+        None,
+        &["0x21", "0x37"],
+    )
+    .await;
+
+    let args = vec![
+        "get-storage-at",
+        "--url",
+        URL,
+        "--address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--storage-var",
+        "storage",
+        "--storage-var-args",
+        "0x21",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: get-storage-at
+        value: 0x37
+    "});
+}
+
+#[test]
+fn test_key() {
+    // Keys of the `storage` map entries are hashes, the slot at 0x1 is never written to
+    let args = vec![
+        "--int-format",
+        "get-storage-at",
+        "--url",
+        URL,
+        "--address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--key",
+        "0x1",
+        "--block-id",
+        "latest",
+    ];
+
+    let snapbox = runner(&args);
+
+    snapbox.assert().success().stdout_eq(indoc! {r"
+        command: get-storage-at
+        value: 0
+    "});
+}
+
+#[test]
+fn test_json_output() {
+    let args = vec![
+        "--json",
+        "get-storage-at",
+        "--url",
+        URL,
+        "--address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--key",
+        "0x1",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success().get_output().stdout.clone();
+
+    let output: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(output, json!({"command": "get-storage-at", "value": "0x0"}));
+}
+
+#[test]
+fn test_contract_does_not_exist() {
+    let args = vec![
+        "get-storage-at",
+        "--url",
+        URL,
+        "--address",
+        "0x1",
+        "--key",
+        "0x1",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: get-storage-at
+        error: [..]There is no contract at the specified address
+        "},
+    );
+}
+
+#[test]
+fn test_key_or_storage_var_is_required() {
+    let args = vec![
+        "get-storage-at",
+        "--url",
+        URL,
+        "--address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: the following required arguments were not provided:\n  --key <KEY>",
+    );
+}
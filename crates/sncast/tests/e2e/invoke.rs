@@ -3,8 +3,8 @@ use crate::helpers::constants::{
     DEVNET_OZ_CLASS_HASH_CAIRO_0, MAP_CONTRACT_ADDRESS_SEPOLIA, URL,
 };
 use crate::helpers::fixtures::{
-    create_and_deploy_account, create_and_deploy_oz_account, get_transaction_hash,
-    get_transaction_receipt,
+    create_and_deploy_account, create_and_deploy_oz_account, get_accounts_path,
+    get_transaction_hash, get_transaction_receipt,
 };
 use crate::helpers::runner::runner;
 use indoc::indoc;
@@ -12,6 +12,8 @@ use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_co
 use sncast::helpers::constants::{ARGENT_CLASS_HASH, BRAAVOS_CLASS_HASH, OZ_CLASS_HASH};
 use sncast::AccountType;
 use starknet::core::types::{Felt, TransactionReceipt::Invoke};
+use std::fs;
+use tempfile::tempdir;
 use test_case::test_case;
 
 #[test_case("oz_cairo_0"; "cairo_0_account")]
@@ -385,6 +387,119 @@ fn test_too_low_max_fee() {
     );
 }
 
+#[test_case("eth"; "eth")]
+#[test_case("strk"; "strk")]
+#[tokio::test]
+async fn test_multiplied_estimated_fee_exceeds_max_fee_cap(fee_token: &str) {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        "user11",
+        "invoke",
+        "--url",
+        URL,
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1",
+        "0x2",
+        "--fee-multiplier",
+        "2.5",
+        "--max-fee-cap",
+        "1",
+        "--fee-token",
+        fee_token,
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: invoke
+        error: Max fee = [..], from the estimated fee = [..] and --fee-multiplier = 2.5, exceeds --max-fee-cap = 1, the transaction was not sent
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_max_fee_cap_from_profile() {
+    let tempdir = tempdir().unwrap();
+    fs::write(
+        tempdir.path().join("snfoundry.toml"),
+        indoc! {r#"
+            [sncast.default]
+            max-fee-cap = "1000"
+            fee-multiplier = 1.5
+        "#},
+    )
+    .unwrap();
+    let accounts_file = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let args = vec![
+        "--accounts-file",
+        accounts_file.as_str(),
+        "--account",
+        "user11",
+        "invoke",
+        "--url",
+        URL,
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--calldata",
+        "0x1",
+        "0x2",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: invoke
+        error: Max fee = [..], from the estimated fee = [..] and --fee-multiplier = 1.5, exceeds --max-fee-cap = 1000, the transaction was not sent
+        "},
+    );
+}
+
+#[test]
+fn test_invalid_fee_multiplier() {
+    let args = vec![
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        "user11",
+        "invoke",
+        "--url",
+        URL,
+        "--contract-address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--function",
+        "put",
+        "--fee-multiplier",
+        "0.5",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "error: invalid value '0.5' for '--fee-multiplier <FEE_MULTIPLIER>': Fee multiplier should be greater than or equal to 1, got 0.5",
+    );
+}
+
 #[tokio::test]
 async fn test_happy_case_cairo_expression_calldata() {
     let calldata = r"(NestedStructWithField { a: SimpleStruct { a: 0x24 }, b: 96 },)";
@@ -9,7 +9,8 @@ use configuration::copy_config_to_tempdir;
 use indoc::indoc;
 use shared::test_utils::output_assert::assert_stderr_contains;
 use sncast::helpers::constants::KEYSTORE_PASSWORD_ENV_VAR;
-use std::env;
+use std::{env, fs};
+use tempfile::tempdir;
 
 #[tokio::test]
 async fn test_happy_case_from_sncast_config() {
@@ -314,3 +315,96 @@ async fn test_keystore_declare() {
 
     assert!(snapbox.assert().success().get_output().stderr.is_empty());
 }
+
+#[tokio::test]
+async fn test_starkli_account_source_declare() {
+    let contract_path =
+        duplicate_contract_directory_with_salt(CONTRACTS_DIR.to_string() + "/map", "put", "1000");
+    let home_dir = tempdir().unwrap();
+    let account_dir = home_dir.path().join(".starknet_accounts/deployer");
+    fs::create_dir_all(&account_dir).unwrap();
+    fs::copy(
+        get_keystores_path("tests/data/keystore/predeployed_account.json"),
+        account_dir.join("account.json"),
+    )
+    .unwrap();
+    fs::copy(
+        get_keystores_path("tests/data/keystore/predeployed_key.json"),
+        account_dir.join("keystore.json"),
+    )
+    .unwrap();
+
+    let args = vec![
+        "--account-source",
+        "starkli",
+        "--account",
+        "deployer",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "Map",
+        "--fee-token",
+        "eth",
+    ];
+
+    env::set_var(KEYSTORE_PASSWORD_ENV_VAR, "123");
+    let snapbox = runner(&args)
+        .current_dir(contract_path.path())
+        .env("HOME", home_dir.path());
+
+    assert!(snapbox.assert().success().get_output().stderr.is_empty());
+}
+
+#[tokio::test]
+async fn test_starkli_account_source_inexistent_account() {
+    let home_dir = tempdir().unwrap();
+    let args = vec![
+        "--account-source",
+        "starkli",
+        "--account",
+        "deployer",
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "my_contract",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args).env("HOME", home_dir.path());
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: Account = deployer not found: it is neither a path to a starkli account file nor an account in [..]",
+    );
+}
+
+#[tokio::test]
+async fn test_starkli_account_source_with_accounts_file() {
+    let args = vec![
+        "--account-source",
+        "starkli",
+        "--accounts-file",
+        ACCOUNT_FILE_PATH,
+        "--account",
+        ACCOUNT,
+        "declare",
+        "--url",
+        URL,
+        "--contract-name",
+        "my_contract",
+        "--fee-token",
+        "eth",
+    ];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        "Error: `--accounts-file` cannot be used with `--account-source starkli`",
+    );
+}
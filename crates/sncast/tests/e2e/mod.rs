@@ -1,7 +1,14 @@
 mod account;
 mod call;
+mod compute_address;
 mod declare;
+mod declare_all;
+mod decode;
 mod deploy;
+mod deploy_plan;
+mod devnet;
+mod events;
+mod get_storage_at;
 mod invoke;
 mod main_tests;
 mod multicall;
@@ -9,3 +16,4 @@ mod script;
 mod show_config;
 mod tx_status;
 mod verify;
+mod verify_class;
@@ -1,2 +1,3 @@
 mod new;
 mod run;
+mod validate;
@@ -0,0 +1,52 @@
+use crate::helpers::constants::MULTICALL_CONFIGS_DIR;
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use std::path::Path;
+
+#[tokio::test]
+async fn test_valid_file() {
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("deploy_invoke.toml");
+    let path = path.to_str().expect("failed converting path to str");
+
+    let args = vec!["multicall", "validate", path];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        command: multicall validate
+        calls: 3
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_all_errors_are_reported() {
+    let path = project_root::get_project_root().expect("failed to get project root path");
+    let path = Path::new(&path)
+        .join(MULTICALL_CONFIGS_DIR)
+        .join("invalid_schema.toml");
+    let path = path.to_str().expect("failed converting path to str");
+
+    let args = vec!["multicall", "validate", path];
+
+    let snapbox = runner(&args);
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: multicall validate
+        error: Invalid multicall file [..]invalid_schema.toml:
+        line 6, column 10: `unique` must be a boolean, found string
+        line 8, column 1: Missing key `function` in a call
+        line 11, column 1: Unknown key `function_nmae` in an `invoke` call, expected one of: call_type, contract_address, function, inputs
+        "},
+    );
+}
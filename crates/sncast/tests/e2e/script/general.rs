@@ -9,6 +9,8 @@ use camino::Utf8PathBuf;
 use indoc::indoc;
 use shared::test_utils::output_assert::assert_stderr_contains;
 use sncast::get_default_state_file_name;
+use sncast::helpers::constants::EXECUTION_LOG_FILE;
+use sncast::state::execution_log::load_execution_log;
 use sncast::state::state_file::{read_txs_from_state_file, ScriptTransactionStatus};
 use tempfile::tempdir;
 use test_case::test_case;
@@ -132,10 +134,43 @@ async fn test_incompatible_sncast_std_version() {
     ];
 
     let snapbox = runner(&args).current_dir(script_dir.path());
+    let output = snapbox.assert();
 
-    snapbox.assert().success().stdout_matches(indoc! {r"
+    assert_stderr_contains(
+        output,
+        indoc! {r#"
+        command: script run
+        error: Package sncast_std 0.13.1 is not compatible with sncast [..], change the sncast_std dependency in [..]Scarb.toml to:
+        sncast_std = { git = "https://github.com/foundry-rs/starknet-foundry.git", tag = "v[..]" }
+        Pass `--no-version-check` to run anyway
+        "#},
+    );
+}
+
+#[tokio::test]
+async fn test_incompatible_sncast_std_version_without_check() {
+    let script_dir = copy_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/old_sncast_std/scripts");
+    let accounts_json_path = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let script_name = "map_script";
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user4",
+        "script",
+        "run",
+        &script_name,
+        "--no-version-check",
+        "--url",
+        URL,
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+
+    snapbox.assert().success().stderr_matches(indoc! {r"
         ...
-        [WARNING] Package sncast_std version does not meet the recommended version requirement =0.31.0, it might result in unexpected behaviour
+        [WARNING] W0003: Package sncast_std 0.13.1 is not compatible with sncast [..], change the sncast_std dependency in [..]Scarb.toml to:
         ...
     "});
 }
@@ -388,6 +423,131 @@ async fn test_run_script_twice_with_state_file_enabled() {
     assert_eq!(tx_entries_after_first_run, tx_entries_after_second_run);
 }
 
+#[tokio::test]
+async fn test_run_script_with_resume() {
+    let contract_dir = duplicate_contract_directory_with_salt(
+        SCRIPTS_DIR.to_owned() + "/state_script/contracts/",
+        "dummy",
+        "34548",
+    );
+    let script_dir = copy_script_directory_to_tempdir(
+        SCRIPTS_DIR.to_owned() + "/state_script/scripts/",
+        vec![contract_dir.as_ref()],
+    );
+
+    let accounts_json_path = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let script_name = "state_script";
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user7",
+        "script",
+        "run",
+        &script_name,
+        "--url",
+        URL,
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        status: success
+    "});
+
+    let execution_log_path =
+        Utf8PathBuf::from_path_buf(script_dir.path().join(EXECUTION_LOG_FILE)).unwrap();
+    let steps_after_first_run = load_execution_log(&execution_log_path)
+        .unwrap()
+        .scripts
+        .remove("state_script_alpha-sepolia")
+        .unwrap();
+
+    assert_eq!(
+        steps_after_first_run
+            .iter()
+            .map(|step| step.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["declare", "deploy", "invoke"]
+    );
+
+    let mut args_with_resume = args.clone();
+    args_with_resume.push("--resume");
+    let snapbox = runner(&args_with_resume).current_dir(script_dir.path());
+
+    snapbox.assert().success().stdout_matches(indoc! {r"
+        ...
+        command: script run
+        status: success
+    "});
+
+    let steps_after_second_run = load_execution_log(&execution_log_path)
+        .unwrap()
+        .scripts
+        .remove("state_script_alpha-sepolia")
+        .unwrap();
+
+    assert_eq!(steps_after_first_run, steps_after_second_run);
+}
+
+#[tokio::test]
+async fn test_run_script_with_resume_detects_drift() {
+    let contract_dir = duplicate_contract_directory_with_salt(
+        SCRIPTS_DIR.to_owned() + "/state_script/contracts/",
+        "dummy",
+        "34549",
+    );
+    let script_dir = copy_script_directory_to_tempdir(
+        SCRIPTS_DIR.to_owned() + "/state_script/scripts/",
+        vec![contract_dir.as_ref()],
+    );
+    let execution_log = indoc! {r#"
+        {
+          "version": 1,
+          "scripts": {
+            "state_script_alpha-sepolia": [
+              {
+                "step_id": "0000000000000000000000000000000000000000000000000000000000000000",
+                "name": "declare",
+                "output": {
+                  "type": "ErrorResponse",
+                  "message": "logged by a different script"
+                }
+              }
+            ]
+          }
+        }
+    "#};
+    std::fs::write(script_dir.path().join(EXECUTION_LOG_FILE), execution_log).unwrap();
+
+    let accounts_json_path = get_accounts_path(ACCOUNT_FILE_PATH);
+
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.as_str(),
+        "--account",
+        "user7",
+        "script",
+        "run",
+        "state_script",
+        "--url",
+        URL,
+        "--resume",
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    snapbox.assert().success().stdout_matches(indoc! {r#"
+        ...
+        command: script run
+        message:[..]
+            "Script has changed since it was logged in [..]: step 1 was `declare` with id 0000000000000000000000000000000000000000000000000000000000000000, but now its id is [..]. Run the script without `--resume` to start over"
+        ...
+    "#});
+}
+
 #[tokio::test]
 async fn test_state_file_contains_all_failed_txs() {
     let script_dir = copy_script_directory_to_tempdir(
@@ -2,7 +2,7 @@ use crate::helpers::runner::runner;
 use camino::Utf8PathBuf;
 use indoc::{formatdoc, indoc};
 use scarb_api::ScarbCommand;
-use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains};
+use shared::test_utils::output_assert::{assert_stderr_contains, assert_stdout_contains, AsOutput};
 use sncast::helpers::constants::INIT_SCRIPTS_DIR;
 use sncast::helpers::scarb_utils::get_cairo_version;
 use tempfile::TempDir;
@@ -14,11 +14,17 @@ fn test_script_init_happy_case() {
 
     let snapbox = runner(&["script", "init", script_name]).current_dir(temp_dir.path());
 
-    snapbox.assert().stdout_matches(formatdoc! {r"
-        [WARNING] [..]
-        command: script init
-        message: Successfully initialized `{script_name}` at [..]/scripts/{script_name}
-    "});
+    snapbox
+        .assert()
+        .stdout_matches(formatdoc! {r"
+            command: script init
+            message: Successfully initialized `{script_name}` at [..]/scripts/{script_name}
+        "})
+        .stderr_matches(indoc! {r"
+            ...
+            [WARNING] W0008: [..]
+            ...
+        "});
 
     let script_dir_path = temp_dir.path().join(INIT_SCRIPTS_DIR).join(script_name);
     let scarb_toml_path = script_dir_path.join("Scarb.toml");
@@ -128,10 +134,13 @@ fn test_initialized_script_compiles() {
     let snapbox = runner(&["script", "init", script_name]).current_dir(temp_dir.path());
     let output = snapbox.assert().success();
 
+    assert_stderr_contains(
+        output.as_stderr().to_string(),
+        "[WARNING] W0008: The newly created script isn't auto-added to the workspace. [..]",
+    );
     assert_stdout_contains(
         output,
         formatdoc! {r"
-        [WARNING] The newly created script isn't auto-added to the workspace. [..]
         command: script init
         message: Successfully initialized `{script_name}` at [..]/scripts/{script_name}
     "},
@@ -1,8 +1,13 @@
 use crate::helpers::constants::{ACCOUNT_FILE_PATH, SCRIPTS_DIR, URL};
-use crate::helpers::fixtures::{copy_script_directory_to_tempdir, get_accounts_path};
+use crate::helpers::fixtures::{
+    copy_script_directory_to_tempdir, create_and_deploy_account, get_accounts_path,
+};
 use crate::helpers::runner::runner;
 use indoc::indoc;
 use shared::test_utils::output_assert::assert_stdout_contains;
+use sncast::helpers::constants::OZ_CLASS_HASH;
+use sncast::AccountType;
+use starknet::core::types::Felt;
 use test_case::test_case;
 
 #[test_case("oz_cairo_0"; "cairo_0_account")]
@@ -152,3 +157,96 @@ fn test_wrong_calldata() {
         "#},
     );
 }
+
+#[tokio::test]
+async fn test_invoke_parallel() {
+    let script_dir =
+        copy_script_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/invoke", Vec::<String>::new());
+    let account_dir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+    let accounts_json_path = account_dir.path().join("accounts.json");
+
+    let script_name = "invoke_parallel";
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.to_str().unwrap(),
+        "--account",
+        "my_account",
+        "script",
+        "run",
+        &script_name,
+        "--url",
+        URL,
+        "--parallel",
+        "2",
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    let output = snapbox.assert().success();
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]
+        invoke_parallel succeeded
+        command: script run
+        status: success
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_invoke_with_wait_only_for_last() {
+    let script_dir =
+        copy_script_directory_to_tempdir(SCRIPTS_DIR.to_owned() + "/invoke", Vec::<String>::new());
+    let account_dir = create_and_deploy_account(OZ_CLASS_HASH, AccountType::OpenZeppelin).await;
+    let accounts_json_path = account_dir.path().join("accounts.json");
+
+    let script_name = "invoke_with_wait";
+    let args = vec![
+        "--accounts-file",
+        accounts_json_path.to_str().unwrap(),
+        "--account",
+        "my_account",
+        "script",
+        "run",
+        &script_name,
+        "--url",
+        URL,
+    ];
+
+    let snapbox = runner(&args).current_dir(script_dir.path());
+    let output = snapbox.assert().success();
+
+    let stdout = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    let transaction_hash = |label: &str| {
+        let hash = stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(&format!("{label}: ")))
+            .unwrap_or_else(|| panic!("No {label} transaction hash in output"));
+        Felt::from_dec_str(hash).unwrap()
+    };
+
+    // Only the awaited transaction is reported while waiting for it
+    assert_eq!(stdout.matches("Transaction hash = ").count(), 1);
+    for label in ["first", "second"] {
+        assert!(!stdout.contains(&format!(
+            "Transaction hash = {:#x}",
+            transaction_hash(label)
+        )));
+    }
+    assert!(stdout.contains(&format!(
+        "Transaction hash = {:#x}",
+        transaction_hash("third")
+    )));
+
+    assert_stdout_contains(
+        output,
+        indoc! {r"
+        [..]
+        third status: finality_status: AcceptedOnL2, execution_status: Succeeded
+        invoke_with_wait succeeded
+        command: script run
+        status: success
+        "},
+    );
+}
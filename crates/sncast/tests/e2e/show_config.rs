@@ -1,6 +1,9 @@
 use crate::helpers::{constants::URL, runner::runner};
 use configuration::copy_config_to_tempdir;
-use indoc::formatdoc;
+use indoc::{formatdoc, indoc};
+use shared::test_utils::output_assert::assert_stderr_contains;
+use std::fs;
+use tempfile::tempdir;
 
 #[tokio::test]
 async fn test_show_config_from_snfoundry_toml() {
@@ -105,3 +108,104 @@ async fn test_show_config_when_keystore() {
         wait_timeout: 300
     ", URL});
 }
+
+#[tokio::test]
+async fn test_show_config_from_env_file() {
+    let tempdir = tempdir().unwrap();
+    fs::write(
+        tempdir.path().join("snfoundry.toml"),
+        indoc! {r#"
+            [sncast.default]
+            account = "$SHOW_CONFIG_ACCOUNT"
+            accounts-file = "../account-file"
+            url = "$SHOW_CONFIG_URL"
+        "#},
+    )
+    .unwrap();
+    fs::write(
+        tempdir.path().join(".env"),
+        formatdoc! {r#"
+            SHOW_CONFIG_ACCOUNT=from_env_file
+            SHOW_CONFIG_URL="{URL}"
+        "#},
+    )
+    .unwrap();
+    let args = vec!["--account", "from_cli", "show-config"];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+
+    snapbox.assert().success().stdout_eq(formatdoc! {r"
+        command: show-config
+        account: from_cli
+        accounts_file_path: ../account-file
+        chain_id: alpha-sepolia
+        rpc_url: {}
+        wait_retry_interval: 5
+        wait_timeout: 300
+    ", URL});
+}
+
+#[tokio::test]
+async fn test_show_config_environment_overrides_env_file() {
+    let tempdir = tempdir().unwrap();
+    fs::write(
+        tempdir.path().join("snfoundry.toml"),
+        indoc! {r#"
+            [sncast.default]
+            account = "$SHOW_CONFIG_ACCOUNT"
+            accounts-file = "../account-file"
+            url = "$SHOW_CONFIG_URL"
+        "#},
+    )
+    .unwrap();
+    fs::write(
+        tempdir.path().join(".env"),
+        "SHOW_CONFIG_ACCOUNT=from_env_file\nSHOW_CONFIG_URL=http://127.0.0.1:1/rpc\n",
+    )
+    .unwrap();
+    let args = vec!["show-config"];
+
+    let snapbox = runner(&args)
+        .env("SHOW_CONFIG_ACCOUNT", "from_environment")
+        .env("SHOW_CONFIG_URL", URL)
+        .current_dir(tempdir.path());
+
+    snapbox.assert().success().stdout_eq(formatdoc! {r"
+        command: show-config
+        account: from_environment
+        accounts_file_path: ../account-file
+        chain_id: alpha-sepolia
+        rpc_url: {}
+        wait_retry_interval: 5
+        wait_timeout: 300
+    ", URL});
+}
+
+#[tokio::test]
+async fn test_malformed_env_file() {
+    let tempdir = copy_config_to_tempdir("tests/data/files/correct_snfoundry.toml", None).unwrap();
+    fs::write(tempdir.path().join(".env"), "# RPC\nNODE_URL\n").unwrap();
+    let args = vec!["show-config"];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        [..]Failed to parse [..].env
+        [..]line 2: expected `NAME=value`, found `NODE_URL`
+        "},
+    );
+}
+
+#[tokio::test]
+async fn test_malformed_env_file_not_loaded_with_no_env_file() {
+    let tempdir = copy_config_to_tempdir("tests/data/files/correct_snfoundry.toml", None).unwrap();
+    fs::write(tempdir.path().join(".env"), "NODE_URL\n").unwrap();
+    let args = vec!["--no-env-file", "show-config"];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+
+    snapbox.assert().success();
+}
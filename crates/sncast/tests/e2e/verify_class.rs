@@ -0,0 +1,159 @@
+use crate::helpers::constants::{
+    CONTRACTS_DIR, MAP_CONTRACT_ADDRESS_SEPOLIA, MAP_CONTRACT_CLASS_HASH_SEPOLIA, URL,
+};
+use crate::helpers::fixtures::{
+    copy_directory_to_tempdir, create_and_deploy_oz_account,
+    duplicate_contract_directory_with_salt, join_tempdirs,
+};
+use crate::helpers::runner::runner;
+use indoc::indoc;
+use serde_json::Value;
+use shared::test_utils::output_assert::{assert_stderr_contains, AsOutput};
+use std::path::Path;
+
+fn run_json(current_dir: &Path, args: &[&str]) -> Value {
+    let output = runner(args)
+        .current_dir(current_dir)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&output).unwrap()
+}
+
+#[tokio::test]
+async fn test_class_hash_matches() {
+    let contract_path = duplicate_contract_directory_with_salt(
+        CONTRACTS_DIR.to_string() + "/map",
+        "put",
+        "verify_class",
+    );
+    let tempdir = create_and_deploy_oz_account().await;
+    join_tempdirs(&contract_path, &tempdir);
+
+    let account_args = [
+        "--accounts-file",
+        "accounts.json",
+        "--account",
+        "my_account",
+        "--json",
+    ];
+    let declared = run_json(
+        tempdir.path(),
+        &[
+            &account_args[..],
+            &[
+                "declare",
+                "--url",
+                URL,
+                "--contract-name",
+                "Map",
+                "--fee-token",
+                "strk",
+            ],
+        ]
+        .concat(),
+    );
+    let class_hash = declared["class_hash"].as_str().unwrap();
+
+    let deployed = run_json(
+        tempdir.path(),
+        &[
+            &account_args[..],
+            &[
+                "deploy",
+                "--url",
+                URL,
+                "--class-hash",
+                class_hash,
+                "--fee-token",
+                "strk",
+            ],
+        ]
+        .concat(),
+    );
+    let contract_address = deployed["contract_address"].as_str().unwrap();
+
+    let output = run_json(
+        tempdir.path(),
+        &[
+            "--json",
+            "verify-class",
+            "--url",
+            URL,
+            "--address",
+            contract_address,
+            "--contract-name",
+            "Map",
+            "--block-id",
+            "latest",
+        ],
+    );
+
+    assert_eq!(output["command"], "verify-class");
+    assert_eq!(output["onchain_class_hash"], class_hash);
+    assert_eq!(output["local_class_hash"], class_hash);
+    assert_eq!(output["result"], "Match");
+}
+
+#[test]
+fn test_class_hash_mismatch() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/constructor_with_params");
+
+    let args = vec![
+        "--json",
+        "verify-class",
+        "--url",
+        URL,
+        "--address",
+        MAP_CONTRACT_ADDRESS_SEPOLIA,
+        "--contract-name",
+        "ConstructorWithParams",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().failure();
+
+    let response: Value = serde_json::from_str(output.as_stdout()).unwrap();
+    assert_eq!(
+        response["onchain_class_hash"],
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA
+    );
+    assert_ne!(
+        response["local_class_hash"],
+        MAP_CONTRACT_CLASS_HASH_SEPOLIA
+    );
+    assert_eq!(response["result"], "Mismatch");
+
+    assert_stderr_contains(
+        output,
+        "does not match the class hash of contract = ConstructorWithParams",
+    );
+}
+
+#[test]
+fn test_contract_does_not_exist() {
+    let tempdir = copy_directory_to_tempdir(CONTRACTS_DIR.to_string() + "/map");
+
+    let args = vec![
+        "verify-class",
+        "--url",
+        URL,
+        "--address",
+        "0x1",
+        "--contract-name",
+        "Map",
+    ];
+
+    let snapbox = runner(&args).current_dir(tempdir.path());
+    let output = snapbox.assert().success();
+
+    assert_stderr_contains(
+        output,
+        indoc! {r"
+        command: verify-class
+        error: [..]There is no contract at the specified address
+        "},
+    );
+}
@@ -1,7 +1,10 @@
 use crate::helpers::constants::URL;
 use sncast::helpers::constants::OZ_CLASS_HASH;
-use sncast::helpers::fee::{FeeArgs, FeeSettings, FeeToken};
+use sncast::helpers::fee::{
+    build_fee_settings, FeeArgs, FeeSettings, FeeToken, ResourceBoundsArgs,
+};
 use starknet::accounts::{AccountFactory, OpenZeppelinAccountFactory};
+use starknet::core::types::Felt;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::{JsonRpcClient, Provider};
 use starknet::signers::{LocalWallet, SigningKey};
@@ -29,6 +32,8 @@ async fn test_happy_case_eth() {
         max_fee: Some(100_u32.into()),
         max_gas: None,
         max_gas_unit_price: None,
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let settings = args
@@ -53,6 +58,8 @@ async fn test_max_gas_eth() {
         max_fee: Some(100_u32.into()),
         max_gas: Some(100_u32.into()),
         max_gas_unit_price: None,
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let error = args
@@ -74,6 +81,8 @@ async fn test_max_gas_unit_price_eth() {
         max_fee: Some(100_u32.into()),
         max_gas: None,
         max_gas_unit_price: Some(100_u32.into()),
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let error = args
@@ -95,6 +104,8 @@ async fn test_all_args() {
         max_fee: Some(100_u32.into()),
         max_gas: Some(100_u32.into()),
         max_gas_unit_price: Some(100_u32.into()),
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let error = args
@@ -116,6 +127,8 @@ async fn test_max_fee_less_than_max_gas() {
         max_fee: Some(50_u32.into()),
         max_gas: Some(100_u32.into()),
         max_gas_unit_price: None,
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let error = args
@@ -137,6 +150,8 @@ async fn test_max_fee_less_than_max_gas_unit_price() {
         max_fee: Some(50_u32.into()),
         max_gas: None,
         max_gas_unit_price: Some(100_u32.into()),
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let error = args
@@ -148,6 +163,50 @@ async fn test_max_fee_less_than_max_gas_unit_price() {
         .to_string()
         .contains("--max-fee should be greater than or equal to --max-gas-unit-price"));
 }
+
+#[tokio::test]
+async fn test_zero_max_gas_unit_price() {
+    let factory = get_factory().await;
+
+    let error = build_fee_settings(
+        &FeeToken::Strk,
+        Some(100_u32.into()),
+        ResourceBoundsArgs {
+            max_gas: None,
+            max_gas_unit_price: Some(Felt::ZERO),
+        },
+        factory.provider(),
+        factory.block_id(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("--max-gas-unit-price should be greater than 0"));
+}
+
+#[tokio::test]
+async fn test_build_fee_settings_eth_with_resource_bounds() {
+    let factory = get_factory().await;
+
+    let error = build_fee_settings(
+        &FeeToken::Eth,
+        Some(100_u32.into()),
+        ResourceBoundsArgs {
+            max_gas: Some(100_u32.into()),
+            max_gas_unit_price: Some(100_u32.into()),
+        },
+        factory.provider(),
+        factory.block_id(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(error
+        .to_string()
+        .contains("--max-gas is not supported for ETH fee payment"));
+}
 #[tokio::test]
 async fn test_strk_fee_get_max_fee() {
     let factory = get_factory().await;
@@ -157,6 +216,8 @@ async fn test_strk_fee_get_max_fee() {
         max_fee: Some(MAX_FEE.into()),
         max_gas: None,
         max_gas_unit_price: None,
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let settings = args
@@ -187,6 +248,8 @@ async fn test_strk_fee_get_max_fee_with_max_gas() {
         max_fee: Some(MAX_FEE.into()),
         max_gas: Some(1_000_000_u32.into()),
         max_gas_unit_price: None,
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let settings = args
@@ -225,6 +288,8 @@ async fn test_strk_fee_get_max_gas_and_max_gas_unit_price() {
         max_fee: None,
         max_gas: Some(1_000_000_u32.into()),
         max_gas_unit_price: Some(1_000_u32.into()),
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let settings = args
@@ -250,6 +315,8 @@ async fn test_strk_fee_get_max_fee_with_max_gas_unit_price() {
         max_fee: Some(MAX_FEE.into()),
         max_gas: None,
         max_gas_unit_price: Some(1_000_u32.into()),
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let settings = args
@@ -288,6 +355,8 @@ async fn test_strk_fee_get_none() {
         max_fee: None,
         max_gas: None,
         max_gas_unit_price: None,
+        max_fee_cap: None,
+        fee_multiplier: None,
     };
 
     let settings = args
@@ -10,10 +10,14 @@ use crate::helpers::constants::{
 };
 use camino::Utf8PathBuf;
 use conversions::string::IntoHexStr;
+use sncast::helpers::clock::ManualClock;
 use sncast::{get_account, ValidatedWaitParams};
-use sncast::{handle_wait_for_tx, wait_for_tx, WaitForTx};
+use sncast::{
+    handle_wait_for_tx, wait_for_tx, wait_for_tx_with_clock, WaitForTransactionError, WaitForTx,
+};
 use starknet::contract::ContractFactory;
 use starknet::core::types::Felt;
+use std::time::Duration;
 
 #[tokio::test]
 async fn test_happy_path() {
@@ -95,6 +99,46 @@ async fn test_wait_for_nonexistent_tx() {
     .unwrap();
 }
 
+#[tokio::test]
+async fn test_wait_for_nonexistent_tx_with_manual_clock() {
+    let provider = create_test_provider();
+    let clock = ManualClock::default();
+
+    let result = wait_for_tx_with_clock(
+        &provider,
+        "0x123456789".parse().expect("Could not parse a number"),
+        ValidatedWaitParams::new(1, 3),
+        &clock,
+    )
+    .await;
+
+    assert!(matches!(result, Err(WaitForTransactionError::TimedOut)));
+    // With up to 20% of jitter, only two sleeps of a bit over 1s fit in the 3s timeout
+    let sleeps = clock.sleeps();
+    assert_eq!(sleeps.len(), 2);
+    assert!(sleeps
+        .iter()
+        .all(|sleep| (Duration::from_secs(1)..Duration::from_millis(1200)).contains(sleep)));
+    assert!(clock.elapsed() < Duration::from_secs(3));
+}
+
+#[tokio::test]
+async fn test_happy_path_with_manual_clock() {
+    let provider = create_test_provider();
+    let clock = ManualClock::default();
+
+    let result = wait_for_tx_with_clock(
+        &provider,
+        MAP_CONTRACT_DECLARE_TX_HASH_SEPOLIA.parse().unwrap(),
+        ValidatedWaitParams::default(),
+        &clock,
+    )
+    .await;
+
+    assert!(matches!(result, Ok("Transaction accepted")));
+    assert!(clock.sleeps().is_empty());
+}
+
 #[tokio::test]
 async fn test_happy_path_handle_wait_for_tx() {
     let provider = create_test_provider();
@@ -150,3 +194,17 @@ async fn test_wait_for_wrong_retry_values_interval_zero() {
     .await
     .unwrap();
 }
+
+#[tokio::test]
+#[should_panic(expected = "sncast timed out while waiting for transaction to succeed")]
+async fn test_wait_for_nonexistent_tx_with_wait_interval() {
+    let provider = create_test_provider();
+    wait_for_tx(
+        &provider,
+        "0x123456789".parse().expect("Could not parse a number"),
+        ValidatedWaitParams::new(1, 2).with_interval_ms(300),
+    )
+    .await
+    .map_err(anyhow::Error::from)
+    .unwrap();
+}
@@ -3,12 +3,17 @@ use cairo_lang_macro::{Diagnostic, Diagnostics};
 use cairo_lang_syntax::node::db::SyntaxGroup;
 
 pub mod available_gas;
+pub mod available_steps;
+pub mod fixture;
 pub mod fork;
 pub mod fuzzer;
 pub mod ignore;
 pub mod internal_config_statement;
+pub mod serial;
 pub mod should_panic;
 pub mod test;
+pub mod test_case;
+pub mod timeout;
 
 pub trait AttributeInfo {
     const ATTR_NAME: &'static str;
@@ -0,0 +1,61 @@
+use crate::{
+    args::Arguments,
+    attributes::{AttributeCollector, AttributeInfo, AttributeTypeData, ErrorExt},
+    cairo_expression::CairoExpression,
+    config_statement::extend_with_config_cheatcodes,
+    types::{Number, ParseFromExpr},
+};
+use blockifier::versioned_constants::VersionedConstants;
+use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use num_bigint::BigInt;
+
+pub struct AvailableStepsCollector;
+
+impl AttributeInfo for AvailableStepsCollector {
+    const ATTR_NAME: &'static str = "available_steps";
+}
+
+impl AttributeTypeData for AvailableStepsCollector {
+    const CHEATCODE_NAME: &'static str = "set_config_available_steps";
+}
+
+impl AttributeCollector for AvailableStepsCollector {
+    fn args_into_config_expression(
+        db: &dyn SyntaxGroup,
+        args: Arguments,
+        warns: &mut Vec<Diagnostic>,
+    ) -> Result<String, Diagnostics> {
+        let &[arg] = args.unnamed_only::<Self>()?.of_length::<1, Self>()?;
+
+        let steps = Number::parse_from_expr::<Self>(db, arg.1, arg.0.to_string().as_str())?;
+
+        if steps.0 > BigInt::from(u32::MAX) {
+            Err(Self::error(format!(
+                "<{}> can not be greater than {}",
+                arg.0,
+                u32::MAX
+            )))?;
+        }
+        // Maximum number of steps of a single transaction on Starknet
+        let protocol_max_n_steps = VersionedConstants::latest_constants().invoke_tx_max_n_steps;
+        if steps.0 > BigInt::from(protocol_max_n_steps) {
+            warns.push(Self::warn(format!(
+                "<{}> exceeds the maximum of {protocol_max_n_steps} steps allowed in a Starknet transaction, \
+                the tested code would not be executable on the network",
+                arg.0
+            )));
+        }
+
+        let steps = steps.as_cairo_expression();
+
+        Ok(format!(
+            "snforge_std::_config_types::AvailableStepsConfig {{ steps: {steps} }}"
+        ))
+    }
+}
+
+#[must_use]
+pub fn available_steps(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    extend_with_config_cheatcodes::<AvailableStepsCollector>(args, item)
+}
@@ -0,0 +1,67 @@
+use super::{fuzzer::is_fuzzable_type, test::TestCollector, AttributeInfo, ErrorExt};
+use crate::{
+    args::Arguments,
+    common::{into_proc_macro_result, with_parsed_values},
+};
+use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
+use cairo_lang_syntax::node::{
+    ast::{FunctionWithBody, OptionReturnTypeClause},
+    db::SyntaxGroup,
+    helpers::QueryAttrs,
+    TypedSyntaxNode,
+};
+
+pub struct FixtureCollector;
+
+impl AttributeInfo for FixtureCollector {
+    const ATTR_NAME: &'static str = "fixture";
+}
+
+#[must_use]
+pub fn fixture(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    into_proc_macro_result(args, item, |args, item, warns| {
+        with_parsed_values::<FixtureCollector>(args, item, warns, fixture_internal)
+    })
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn fixture_internal(
+    db: &dyn SyntaxGroup,
+    func: &FunctionWithBody,
+    _args_db: &dyn SyntaxGroup,
+    args: Arguments,
+    _warns: &mut Vec<Diagnostic>,
+) -> Result<String, Diagnostics> {
+    args.assert_is_empty::<FixtureCollector>()?;
+
+    if func.has_attr(db, TestCollector::ATTR_NAME) {
+        Err(FixtureCollector::error(
+            "can't be used together with #[test]",
+        ))?;
+    }
+
+    let signature = func.declaration(db).signature(db);
+
+    if !signature.parameters(db).elements(db).is_empty() {
+        Err(FixtureCollector::error(
+            "can be used only on a function without parameters",
+        ))?;
+    }
+
+    let OptionReturnTypeClause::ReturnTypeClause(return_type) = signature.ret_ty(db) else {
+        return Err(
+            FixtureCollector::error("can be used only on a function returning a value").into(),
+        );
+    };
+
+    let return_type = return_type.ty(db).as_syntax_node().get_text(db);
+
+    if is_fuzzable_type(&return_type) {
+        Err(FixtureCollector::error(format!(
+            "can't return {}, test parameters of this type are generated by the fuzzer",
+            return_type.trim()
+        )))?;
+    }
+
+    Ok(func.as_syntax_node().get_text(db))
+}
@@ -1,14 +1,24 @@
 use self::block_id::{BlockId, BlockIdVariants};
 use crate::{
     args::Arguments,
-    attributes::{AttributeCollector, AttributeInfo, AttributeTypeData},
+    attributes::{
+        test::TestCollector, AttributeCollector, AttributeInfo, AttributeTypeData, ErrorExt,
+    },
     branch,
     cairo_expression::CairoExpression,
+    common::into_proc_macro_result,
     config_statement::extend_with_config_cheatcodes,
+    parse::{parse_args, parse_module},
     types::ParseFromExpr,
 };
 use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, Severity, TokenStream};
-use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::{
+    ast::{Expr, ItemModule, MaybeModuleBody, ModuleBody, ModuleItem},
+    db::SyntaxGroup,
+    helpers::{GetIdentifier, QueryAttrs},
+    Terminal, TypedSyntaxNode,
+};
+use cairo_lang_utils::Upcast;
 use indoc::formatdoc;
 use url::Url;
 
@@ -30,6 +40,10 @@ impl AttributeCollector for ForkCollector {
         args: Arguments,
         _warns: &mut Vec<Diagnostic>,
     ) -> Result<String, Diagnostics> {
+        if is_disabled(db, &args) {
+            return Ok("snforge_std::_config_types::ForkConfig::Disabled(())".to_string());
+        }
+
         let expr = branch!(
             inline_args(db, &args),
             overridden_args(db, &args),
@@ -40,6 +54,21 @@ impl AttributeCollector for ForkCollector {
     }
 }
 
+/// `#[fork(none)]` opts a test out of the default fork from Scarb.toml
+fn is_disabled(db: &dyn SyntaxGroup, args: &Arguments) -> bool {
+    let Ok(unnamed) = args.unnamed_only::<ForkCollector>() else {
+        return false;
+    };
+    let Ok(&[(_, Expr::Path(path))]) = unnamed.of_length::<1, ForkCollector>() else {
+        return false;
+    };
+
+    matches!(
+        path.elements(db).as_slice(),
+        [segment] if segment.identifier(db).as_str() == "none"
+    )
+}
+
 fn inline_args(db: &dyn SyntaxGroup, args: &Arguments) -> Result<String, Diagnostic> {
     let named_args = args.named_only::<ForkCollector>()?;
 
@@ -109,7 +138,81 @@ fn overridden_args(db: &dyn SyntaxGroup, args: &Arguments) -> Result<String, Dia
     ))
 }
 
+/// Adds the attribute to every test in the module and its nested modules that does not specify its own fork
+fn with_fork_on_tests(
+    db: &dyn SyntaxGroup,
+    module: &ItemModule,
+    args: &TokenStream,
+    warns: &mut Vec<Diagnostic>,
+) -> Result<String, Diagnostics> {
+    let MaybeModuleBody::Some(body) = module.body(db) else {
+        return Err(ForkCollector::error("can be used only on a module with a body").into());
+    };
+
+    let (args_db, parsed_args) = parse_args(&args.to_string());
+    let args_db = args_db.upcast();
+    let arguments = Arguments::new::<ForkCollector>(args_db, parsed_args, warns);
+
+    ForkCollector::args_into_config_expression(args_db, arguments, warns)?;
+
+    Ok(with_fork_on_module_tests(db, module, &body, args))
+}
+
+fn with_fork_on_module_tests(
+    db: &dyn SyntaxGroup,
+    module: &ItemModule,
+    body: &ModuleBody,
+    args: &TokenStream,
+) -> String {
+    let items = body
+        .items(db)
+        .elements(db)
+        .into_iter()
+        .map(|item| match item {
+            ModuleItem::FreeFunction(func)
+                if func.has_attr(db, TestCollector::ATTR_NAME)
+                    && !func.has_attr(db, ForkCollector::ATTR_NAME) =>
+            {
+                let code = func.as_syntax_node().get_text(db);
+
+                format!("#[{}{args}]\n{code}", ForkCollector::ATTR_NAME)
+            }
+            // Modules with their own `#[fork]` apply it to their tests when expanded
+            ModuleItem::Module(nested) if !nested.has_attr(db, ForkCollector::ATTR_NAME) => {
+                match nested.body(db) {
+                    MaybeModuleBody::Some(nested_body) => {
+                        with_fork_on_module_tests(db, &nested, &nested_body, args)
+                    }
+                    MaybeModuleBody::None(_) => nested.as_syntax_node().get_text(db),
+                }
+            }
+            item => item.as_syntax_node().get_text(db),
+        })
+        .collect::<String>();
+
+    let attrs = module.attributes(db).as_syntax_node().get_text(db);
+    let vis = module.visibility(db).as_syntax_node().get_text(db);
+    let name = module.name(db).text(db);
+
+    formatdoc!(
+        "
+            {attrs}
+            {vis} mod {name} {{
+                {items}
+            }}
+        "
+    )
+}
+
 #[must_use]
 pub fn fork(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    if let Some((db, module)) = parse_module(&item.to_string()) {
+        let db = db.upcast();
+
+        return into_proc_macro_result(args, item, |args, _item, warns| {
+            with_fork_on_tests(db, &module, args, warns)
+        });
+    }
+
     extend_with_config_cheatcodes::<ForkCollector>(args, item)
 }
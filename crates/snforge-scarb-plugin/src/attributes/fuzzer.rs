@@ -1,13 +1,18 @@
 use super::{AttributeInfo, AttributeTypeData};
 use crate::{
     args::Arguments,
-    attributes::{AttributeCollector, ErrorExt},
+    attributes::ErrorExt,
     cairo_expression::CairoExpression,
-    config_statement::extend_with_config_cheatcodes,
+    common::{into_proc_macro_result, with_parsed_values},
+    config_statement::{append_config_statements, config_cheatcode_statement},
     types::{Number, ParseFromExpr},
 };
 use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
-use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::{
+    ast::{Expr, FunctionWithBody, OptionArgListParenthesized},
+    db::SyntaxGroup,
+    Terminal, TypedSyntaxNode,
+};
 use num_bigint::BigInt;
 
 pub struct FuzzerCollector;
@@ -20,40 +25,218 @@ impl AttributeTypeData for FuzzerCollector {
     const CHEATCODE_NAME: &'static str = "set_config_fuzzer";
 }
 
-impl AttributeCollector for FuzzerCollector {
-    fn args_into_config_expression(
-        db: &dyn SyntaxGroup,
-        args: Arguments,
-        _warns: &mut Vec<Diagnostic>,
-    ) -> Result<String, Diagnostics> {
-        let named_args = args.named_only::<Self>()?;
-
-        let seed = named_args
-            .as_once_optional("seed")?
-            .map(|arg| Number::parse_from_expr::<Self>(db, arg, "seed"))
-            .transpose()?;
-
-        let runs = named_args
-            .as_once_optional("runs")?
-            .map(|arg| Number::parse_from_expr::<Self>(db, arg, "runs"))
-            .transpose()?;
-
-        if let Some(Number(ref runs)) = runs {
-            if runs <= &BigInt::from(0) {
-                Err(Self::error("runs must be greater than 0"))?;
-            }
+const CONFIG_ARGS: [&str; 4] = ["runs", "seed", "shrink_runs", "non_deterministic"];
+const RANGE_ARGS: [&str; 2] = ["low", "high"];
+
+/// Largest value of a type generated by the fuzzer, `None` if the type is not fuzzable.
+// Must be kept in sync with `CairoType` of the fuzzer in `forge-runner`, which also explains the bounds
+fn fuzzable_type_max(ty: &str) -> Option<BigInt> {
+    let bits = match ty.trim() {
+        "u8" | "core::integer::u8" => 8,
+        "u16" | "core::integer::u16" => 16,
+        "u32" | "core::integer::u32" => 32,
+        "u64" | "core::integer::u64" => 64,
+        "u128" | "core::integer::u128" => 128,
+        "u256" | "core::integer::u256" => 256,
+        "ContractAddress"
+        | "starknet::ContractAddress"
+        | "starknet::contract_address::ContractAddress"
+        | "core::starknet::contract_address::ContractAddress" => 251,
+        "felt252" | "core::felt252" => {
+            let prime = (BigInt::from(1) << 251) + BigInt::from(17) * (BigInt::from(1) << 192) + 1;
+            return Some(prime - 1);
         }
+        _ => return None,
+    };
 
-        let seed = seed.as_cairo_expression();
-        let runs = runs.as_cairo_expression();
+    Some((BigInt::from(1) << bits) - 1)
+}
 
-        Ok(format!(
-            "snforge_std::_config_types::FuzzerConfig {{ seed: {seed}, runs: {runs} }}"
-        ))
+/// Test parameters of fuzzable types are generated by the fuzzer, all other ones must be listed as fixtures
+#[must_use]
+pub fn is_fuzzable_type(ty: &str) -> bool {
+    fuzzable_type_max(ty).is_some()
+}
+
+struct FuzzerRange {
+    index: usize,
+    low: Number,
+    high: Number,
+}
+
+impl CairoExpression for FuzzerRange {
+    fn as_cairo_expression(&self) -> String {
+        format!(
+            "snforge_std::_config_types::FuzzerRange {{ index: {}, low: {}, high: {} }}",
+            self.index,
+            self.low.as_cairo_expression(),
+            self.high.as_cairo_expression()
+        )
     }
 }
 
 #[must_use]
 pub fn fuzzer(args: TokenStream, item: TokenStream) -> ProcMacroResult {
-    extend_with_config_cheatcodes::<FuzzerCollector>(args, item)
+    into_proc_macro_result(args, item, |args, item, warns| {
+        with_parsed_values::<FuzzerCollector>(args, item, warns, with_fuzzer_config)
+    })
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn with_fuzzer_config(
+    db: &dyn SyntaxGroup,
+    func: &FunctionWithBody,
+    args_db: &dyn SyntaxGroup,
+    args: Arguments,
+    warns: &mut Vec<Diagnostic>,
+) -> Result<String, Diagnostics> {
+    let named_args = args.named_only::<FuzzerCollector>()?;
+
+    let seed = named_args
+        .as_once_optional("seed")?
+        .map(|arg| Number::parse_from_expr::<FuzzerCollector>(args_db, arg, "seed"))
+        .transpose()?;
+
+    let runs = named_args
+        .as_once_optional("runs")?
+        .map(|arg| Number::parse_from_expr::<FuzzerCollector>(args_db, arg, "runs"))
+        .transpose()?;
+
+    if let Some(Number(ref runs)) = runs {
+        if runs <= &BigInt::from(0) {
+            Err(FuzzerCollector::error("runs must be greater than 0"))?;
+        }
+    }
+
+    let shrink_runs = named_args
+        .as_once_optional("shrink_runs")?
+        .map(|arg| Number::parse_from_expr::<FuzzerCollector>(args_db, arg, "shrink_runs"))
+        .transpose()?;
+
+    if let Some(Number(ref shrink_runs)) = shrink_runs {
+        if shrink_runs < &BigInt::from(0) {
+            Err(FuzzerCollector::error("shrink_runs must not be negative"))?;
+        }
+    }
+
+    let non_deterministic = named_args
+        .as_once_optional("non_deterministic")?
+        .map(|arg| bool::parse_from_expr::<FuzzerCollector>(args_db, arg, "non_deterministic"))
+        .transpose()?
+        .unwrap_or(false);
+
+    // Parameters of other types are fixtures, they are not passed to the fuzzer
+    let fuzzed_params: Vec<_> = func
+        .declaration(db)
+        .signature(db)
+        .parameters(db)
+        .elements(db)
+        .into_iter()
+        .filter_map(|param| {
+            let max =
+                fuzzable_type_max(&param.type_clause(db).ty(db).as_syntax_node().get_text(db))?;
+            Some((param.name(db).text(db), max))
+        })
+        .collect();
+
+    for name in named_args.keys() {
+        if !CONFIG_ARGS.contains(&name.as_str())
+            && !fuzzed_params
+                .iter()
+                .any(|(param, _)| param.as_str() == name.as_str())
+        {
+            Err(FuzzerCollector::error(format!(
+                "<{name}> is not a fuzzed parameter of the test"
+            )))?;
+        }
+    }
+
+    let ranges = fuzzed_params
+        .iter()
+        .enumerate()
+        // Config arguments always configure the fuzzer, even if a parameter has the same name
+        .filter(|(_, (name, _))| !CONFIG_ARGS.contains(&name.as_str()))
+        .filter_map(|(index, (name, max))| {
+            named_args.as_once_optional(name).transpose().map(|expr| {
+                let (low, high) = parse_range(args_db, expr?, name, max, warns)?;
+                Ok(FuzzerRange { index, low, high })
+            })
+        })
+        .collect::<Result<Vec<_>, Diagnostic>>()?;
+
+    let seed = seed.as_cairo_expression();
+    let runs = runs.as_cairo_expression();
+    let ranges = ranges.as_cairo_expression();
+    let shrink_runs = shrink_runs.as_cairo_expression();
+    let non_deterministic = non_deterministic.as_cairo_expression();
+
+    let config = format!(
+        "snforge_std::_config_types::FuzzerConfig {{ seed: {seed}, runs: {runs}, ranges: {ranges}, shrink_runs: {shrink_runs}, non_deterministic: {non_deterministic} }}"
+    );
+
+    Ok(append_config_statements(
+        db,
+        func,
+        &config_cheatcode_statement::<FuzzerCollector>(&config),
+    ))
+}
+
+/// Parses `range(low: <number>, high: <number>)`, omitted bounds are the limits of the parameter type
+fn parse_range(
+    db: &dyn SyntaxGroup,
+    expr: &Expr,
+    name: &str,
+    max: &BigInt,
+    warns: &mut Vec<Diagnostic>,
+) -> Result<(Number, Number), Diagnostic> {
+    let invalid_range = || {
+        FuzzerCollector::error(format!(
+            "<{name}> should be range(low: <number>, high: <number>)"
+        ))
+    };
+
+    let Expr::FunctionCall(call) = expr else {
+        return Err(invalid_range());
+    };
+    if call.path(db).as_syntax_node().get_text(db).trim() != "range" {
+        return Err(invalid_range());
+    }
+
+    let args = Arguments::new::<FuzzerCollector>(
+        db,
+        OptionArgListParenthesized::ArgListParenthesized(call.arguments(db)),
+        warns,
+    );
+    let named_args = args.named_only::<FuzzerCollector>()?;
+
+    if named_args
+        .keys()
+        .any(|arg| !RANGE_ARGS.contains(&arg.as_str()))
+    {
+        return Err(invalid_range());
+    }
+
+    let low = named_args
+        .as_once_optional("low")?
+        .map(|arg| Number::parse_from_expr::<FuzzerCollector>(db, arg, "low"))
+        .transpose()?
+        .unwrap_or(Number(BigInt::from(0)));
+    let high = named_args
+        .as_once_optional("high")?
+        .map(|arg| Number::parse_from_expr::<FuzzerCollector>(db, arg, "high"))
+        .transpose()?
+        .unwrap_or_else(|| Number(max.clone()));
+
+    if low.0 > high.0 {
+        Err(FuzzerCollector::error(format!(
+            "<{name}> range is empty, low is greater than high"
+        )))?;
+    }
+    if &high.0 > max {
+        Err(FuzzerCollector::error(format!(
+            "<{name}> range exceeds values of its type, high can be at most {max}"
+        )))?;
+    }
+
+    Ok((low, high))
 }
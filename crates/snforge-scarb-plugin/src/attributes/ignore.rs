@@ -1,7 +1,7 @@
-use super::{AttributeInfo, AttributeTypeData};
+use super::{AttributeInfo, AttributeTypeData, ErrorExt};
 use crate::{
-    args::Arguments, attributes::AttributeCollector,
-    config_statement::extend_with_config_cheatcodes,
+    args::Arguments, attributes::AttributeCollector, cairo_expression::CairoExpression,
+    config_statement::extend_with_config_cheatcodes, types::ParseFromExpr,
 };
 use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
 use cairo_lang_syntax::node::db::SyntaxGroup;
@@ -18,13 +18,28 @@ impl AttributeTypeData for IgnoreCollector {
 
 impl AttributeCollector for IgnoreCollector {
     fn args_into_config_expression(
-        _db: &dyn SyntaxGroup,
+        db: &dyn SyntaxGroup,
         args: Arguments,
         _warns: &mut Vec<Diagnostic>,
     ) -> Result<String, Diagnostics> {
-        args.assert_is_empty::<Self>()?;
+        let reason = match args.unnamed_only::<Self>()?.as_slice() {
+            [] => None,
+            [(position, expr)] => Some(String::parse_from_expr::<Self>(
+                db,
+                expr,
+                position.to_string().as_str(),
+            )?),
+            args => Err(Self::error(format!(
+                "expected at most 1 argument, got: {}",
+                args.len()
+            )))?,
+        };
 
-        Ok("snforge_std::_config_types::IgnoreConfig { is_ignored: true }".to_string())
+        let reason = reason.as_cairo_expression();
+
+        Ok(format!(
+            "snforge_std::_config_types::IgnoreConfig {{ is_ignored: true, reason: {reason} }}"
+        ))
     }
 }
 
@@ -0,0 +1,34 @@
+use crate::{
+    args::Arguments,
+    attributes::{AttributeCollector, AttributeInfo, AttributeTypeData},
+    config_statement::extend_with_config_cheatcodes,
+};
+use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+
+pub struct SerialCollector;
+
+impl AttributeInfo for SerialCollector {
+    const ATTR_NAME: &'static str = "serial";
+}
+
+impl AttributeTypeData for SerialCollector {
+    const CHEATCODE_NAME: &'static str = "set_config_serial";
+}
+
+impl AttributeCollector for SerialCollector {
+    fn args_into_config_expression(
+        _db: &dyn SyntaxGroup,
+        args: Arguments,
+        _warns: &mut Vec<Diagnostic>,
+    ) -> Result<String, Diagnostics> {
+        args.assert_is_empty::<Self>()?;
+
+        Ok("snforge_std::_config_types::SerialConfig { is_serial: true }".to_string())
+    }
+}
+
+#[must_use]
+pub fn serial(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    extend_with_config_cheatcodes::<SerialCollector>(args, item)
+}
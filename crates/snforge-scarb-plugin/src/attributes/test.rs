@@ -1,15 +1,27 @@
-use super::{internal_config_statement::InternalConfigStatementCollector, AttributeInfo};
+use super::{
+    fuzzer::is_fuzzable_type, internal_config_statement::InternalConfigStatementCollector,
+    test_case::TestCaseCollector, AttributeInfo, ErrorExt,
+};
 use crate::{
     args::Arguments,
     common::{into_proc_macro_result, with_parsed_values},
+    config_statement::as_config_run_check,
 };
 use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
-use cairo_lang_syntax::node::{ast::FunctionWithBody, db::SyntaxGroup, Terminal, TypedSyntaxNode};
+use cairo_lang_syntax::node::{
+    ast::{Expr, FunctionWithBody},
+    db::SyntaxGroup,
+    helpers::{GetIdentifier, QueryAttrs},
+    Terminal, TypedSyntaxNode,
+};
 use indoc::formatdoc;
 
 use shared::consts::SNFORGE_TEST_FILTER;
 use std::env::{self, VarError};
-struct TestCollector;
+
+const FIXTURES_ARG: &str = "fixtures";
+
+pub struct TestCollector;
 
 impl AttributeInfo for TestCollector {
     const ATTR_NAME: &'static str = "test";
@@ -27,15 +39,26 @@ pub fn test(args: TokenStream, item: TokenStream) -> ProcMacroResult {
 fn test_internal(
     db: &dyn SyntaxGroup,
     func: &FunctionWithBody,
-    _args_db: &dyn SyntaxGroup,
+    args_db: &dyn SyntaxGroup,
     args: Arguments,
     _warns: &mut Vec<Diagnostic>,
 ) -> Result<String, Diagnostics> {
-    args.assert_is_empty::<TestCollector>()?;
+    let fixtures = parse_fixtures(args_db, &args)?;
+
+    if func.has_attr(db, TestCaseCollector::ATTR_NAME) {
+        if !fixtures.is_empty() {
+            Err(TestCollector::error(format!(
+                "<{FIXTURES_ARG}> argument can't be used together with #[{}]",
+                TestCaseCollector::ATTR_NAME
+            )))?;
+        }
+
+        return Ok(with_test_after_test_cases(db, func));
+    }
 
     let config = InternalConfigStatementCollector::ATTR_NAME;
 
-    let func_item = func.as_syntax_node().get_text(db);
+    let func_item = with_fixtures(db, func, &fixtures)?;
     let name = func.declaration(db).name(db).text(db).to_string();
 
     let test_filter = get_forge_test_filter().ok();
@@ -63,6 +86,165 @@ fn test_internal(
     }
 }
 
+/// Moves the attribute after `#[test_case]` attributes, so they are expanded first.
+/// Every case is then expanded into a separate test marked with this attribute.
+fn with_test_after_test_cases(db: &dyn SyntaxGroup, func: &FunctionWithBody) -> String {
+    let attrs = func.attributes(db).as_syntax_node().get_text(db);
+    let vis = func.visibility(db).as_syntax_node().get_text(db);
+    let declaration = func.declaration(db).as_syntax_node().get_text(db);
+    let body = func.body(db).as_syntax_node().get_text(db);
+    let test = TestCollector::ATTR_NAME;
+
+    formatdoc!(
+        "
+            {attrs}
+            #[{test}]
+            {vis} {declaration} {body}
+        "
+    )
+}
+
+/// Parses names of the parameters listed in `fixtures: (a, b)` argument.
+fn parse_fixtures(db: &dyn SyntaxGroup, args: &Arguments) -> Result<Vec<String>, Diagnostic> {
+    let named_args = args.named_only::<TestCollector>()?;
+
+    if let Some(arg) = named_args.keys().find(|arg| arg.as_str() != FIXTURES_ARG) {
+        Err(TestCollector::error(format!("unexpected argument <{arg}>")))?;
+    }
+
+    let Some(expr) = named_args.as_once_optional(FIXTURES_ARG)? else {
+        return Ok(vec![]);
+    };
+
+    let exprs = match expr {
+        Expr::Tuple(expressions) => expressions.expressions(db).elements(db),
+        Expr::Parenthesized(expression) => vec![expression.expr(db)],
+        expr => vec![expr.clone()],
+    };
+
+    exprs
+        .iter()
+        .map(|expr| match expr {
+            Expr::Path(path) => match path.elements(db).as_slice() {
+                [segment] => Ok(segment.identifier(db).to_string()),
+                _ => Err(()),
+            },
+            _ => Err(()),
+        })
+        .collect::<Result<_, ()>>()
+        .map_err(|()| {
+            TestCollector::error(format!(
+                "<{FIXTURES_ARG}> argument must be a parameter name or list of parameter names in regular brackets ()"
+            ))
+        })
+}
+
+/// Replaces parameters listed in `fixtures` with calls to the `#[fixture]` functions of the same name.
+/// Fixtures are called after the config run check, so they are executed only in the actual test run.
+fn with_fixtures(
+    db: &dyn SyntaxGroup,
+    func: &FunctionWithBody,
+    fixtures: &[String],
+) -> Result<String, Diagnostic> {
+    let declaration = func.declaration(db);
+    let signature = declaration.signature(db);
+    let params = signature.parameters(db).elements(db);
+
+    if let Some(fixture) = fixtures.iter().find(|fixture| {
+        !params
+            .iter()
+            .any(|param| param.name(db).text(db).as_str() == fixture.as_str())
+    }) {
+        Err(TestCollector::error(format!(
+            "<{FIXTURES_ARG}> argument lists `{fixture}`, which is not a parameter of the test"
+        )))?;
+    }
+
+    let mut fuzzer_params = vec![];
+    let mut fixture_params = vec![];
+
+    for param in params {
+        let name = param.name(db).text(db);
+        let ty = param.type_clause(db).ty(db).as_syntax_node().get_text(db);
+        let ty = ty.trim();
+
+        match (
+            fixtures
+                .iter()
+                .any(|fixture| fixture.as_str() == name.as_str()),
+            is_fuzzable_type(ty),
+        ) {
+            (true, false) => fixture_params.push(param),
+            (false, true) => fuzzer_params.push(param),
+            (true, true) => Err(TestCollector::error(format!(
+                "parameter `{name}` of type {ty} is generated by the fuzzer and can't be a fixture"
+            )))?,
+            (false, false) => Err(TestCollector::error(format!(
+                "parameter `{name}` of type {ty} can't be generated by the fuzzer, \
+                 if it is provided by a #[fixture] function, list it in <{FIXTURES_ARG}> argument"
+            )))?,
+        }
+    }
+
+    if fixture_params.is_empty() {
+        return Ok(func.as_syntax_node().get_text(db));
+    }
+
+    let fixture_statements = fixture_params
+        .iter()
+        .map(|param| {
+            let fixture = param.name(db).text(db);
+            let binding = param.as_syntax_node().get_text(db);
+            let binding = binding.trim();
+
+            formatdoc!(
+                r#"
+                    let {binding} = {{
+                        snforge_std::_cheatcode::_enter_fixture("{fixture}");
+                        let fixture_value = {fixture}();
+                        snforge_std::_cheatcode::_exit_fixture();
+                        fixture_value
+                    }};
+                "#
+            )
+        })
+        .collect::<String>();
+
+    let fuzzer_params = fuzzer_params
+        .iter()
+        .map(|param| param.as_syntax_node().get_text(db).trim().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let statements = func.body(db).statements(db).elements(db);
+    let (config_check, statements) = match statements.split_first() {
+        Some((first, rest)) if as_config_run_check(db, first).is_some() => {
+            (first.as_syntax_node().get_text(db), rest)
+        }
+        _ => (String::new(), statements.as_slice()),
+    };
+    let statements = statements
+        .iter()
+        .map(|statement| statement.as_syntax_node().get_text(db))
+        .collect::<String>();
+
+    let attrs = func.attributes(db).as_syntax_node().get_text(db);
+    let vis = func.visibility(db).as_syntax_node().get_text(db);
+    let name = declaration.name(db).text(db);
+    let ret_ty = signature.ret_ty(db).as_syntax_node().get_text(db);
+
+    Ok(formatdoc!(
+        "
+            {attrs}
+            {vis} fn {name}({fuzzer_params}) {ret_ty} {{
+                {config_check}
+                {fixture_statements}
+                {statements}
+            }}
+        "
+    ))
+}
+
 fn get_forge_test_filter() -> Result<String, VarError> {
     env::var(SNFORGE_TEST_FILTER)
 }
@@ -0,0 +1,236 @@
+use super::{
+    fuzzer::FuzzerCollector, test::TestCollector, AttributeInfo, AttributeTypeData, ErrorExt,
+};
+use crate::{
+    args::Arguments,
+    common::into_proc_macro_result,
+    config_statement::{as_config_run_check, config_cheatcode_statement},
+    parse::{parse, parse_args},
+    types::ParseFromExpr,
+};
+use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
+use cairo_lang_syntax::node::{
+    ast::{Attribute, FunctionWithBody},
+    db::SyntaxGroup,
+    helpers::QueryAttrs,
+    Terminal, TypedSyntaxNode,
+};
+use cairo_lang_utils::Upcast;
+use indoc::formatdoc;
+
+pub struct TestCaseCollector;
+
+impl AttributeInfo for TestCaseCollector {
+    const ATTR_NAME: &'static str = "test_case";
+}
+
+impl AttributeTypeData for TestCaseCollector {
+    const CHEATCODE_NAME: &'static str = "set_config_test_case";
+}
+
+/// Single set of arguments the test is run with
+struct Case {
+    name: String,
+    arguments: Vec<String>,
+}
+
+#[must_use]
+pub fn test_case(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    into_proc_macro_result(args, item, |args, item, warns| {
+        let item = item.to_string();
+        let (db, func) = parse::<TestCaseCollector>(&item)?;
+        let db = db.upcast();
+
+        let (args_db, args) = parse_args(&args.to_string());
+        let args_db = args_db.upcast();
+        let args = Arguments::new::<TestCaseCollector>(args_db, args, warns);
+
+        test_case_internal(db, &func, args_db, &args, warns)
+    })
+}
+
+/// Expands the test into a module with a test for each case, named by the case.
+/// All `#[test_case]` attributes of the function are expanded at once,
+/// other attributes are applied to every case.
+fn test_case_internal(
+    db: &dyn SyntaxGroup,
+    func: &FunctionWithBody,
+    args_db: &dyn SyntaxGroup,
+    args: &Arguments,
+    warns: &mut Vec<Diagnostic>,
+) -> Result<String, Diagnostics> {
+    if !func.has_attr(db, TestCollector::ATTR_NAME) {
+        Err(TestCaseCollector::error(
+            "can only be used on functions marked with #[test]",
+        ))?;
+    }
+    if func.has_attr(db, FuzzerCollector::ATTR_NAME) {
+        Err(TestCaseCollector::error("cannot be used with #[fuzzer]"))?;
+    }
+
+    let declaration = func.declaration(db);
+    let signature = declaration.signature(db);
+    let params = signature.parameters(db).elements(db);
+
+    let mut cases = vec![Case::new(args_db, args, 0, params.len())?];
+    for attr in func.query_attr(db, TestCaseCollector::ATTR_NAME) {
+        let args = Arguments::new::<TestCaseCollector>(db, attr.arguments(db), warns);
+        cases.push(Case::new(db, &args, cases.len(), params.len())?);
+    }
+
+    for (index, case) in cases.iter().enumerate() {
+        if cases[..index].iter().any(|other| other.name == case.name) {
+            Err(TestCaseCollector::error(format!(
+                "<name> = {} is used by more than one case",
+                case.name
+            )))?;
+        }
+    }
+
+    let statements = func.body(db).statements(db).elements(db);
+    // config statements of attributes expanded before are run by every case
+    let (config_statements, statements) = match statements.split_first() {
+        Some((first, rest)) if as_config_run_check(db, first).is_some() => {
+            let if_statements = as_config_run_check(db, first)
+                .unwrap()
+                .if_block(db)
+                .statements(db)
+                .elements(db);
+
+            // omit last one (`return;`) as it is inserted after config statements of the case
+            let config_statements = if_statements[..if_statements.len() - 1]
+                .iter()
+                .map(|statement| statement.as_syntax_node().get_text(db))
+                .collect::<String>();
+
+            (config_statements, rest)
+        }
+        _ => (String::new(), statements.as_slice()),
+    };
+    let statements = statements
+        .iter()
+        .map(|statement| statement.as_syntax_node().get_text(db))
+        .collect::<String>();
+
+    let case_attrs = func
+        .attributes(db)
+        .elements(db)
+        .iter()
+        .filter(|attr| {
+            !is_attr::<TestCaseCollector>(db, attr) && !is_attr::<TestCollector>(db, attr)
+        })
+        .map(|attr| attr.as_syntax_node().get_text(db))
+        .collect::<String>();
+
+    let vis = func.visibility(db).as_syntax_node().get_text(db);
+    let name = declaration.name(db).text(db);
+    let params = signature.parameters(db).as_syntax_node().get_text(db);
+    let ret_ty = signature.ret_ty(db).as_syntax_node().get_text(db);
+    let test = TestCollector::ATTR_NAME;
+    let body_name = format!("__snforge_test_case_{name}");
+
+    let cases = cases
+        .iter()
+        .map(|case| {
+            let case_name = &case.name;
+            let arguments = case.arguments.join(", ");
+            let config = config_cheatcode_statement::<TestCaseCollector>(&format!(
+                r#"snforge_std::_config_types::TestCaseConfig {{ name: "{case_name}", arguments: "{}" }}"#,
+                escape(&arguments)
+            ));
+
+            formatdoc!(
+                "
+                    #[{test}]
+                    {case_attrs}
+                    fn {case_name}() {ret_ty} {{
+                        if snforge_std::_cheatcode::_is_config_run() {{
+                            {config_statements}
+
+                            {config}
+
+                            return;
+                        }}
+
+                        super::{body_name}({arguments})
+                    }}
+                "
+            )
+        })
+        .collect::<String>();
+
+    Ok(formatdoc!(
+        "
+            fn {body_name}({params}) {ret_ty} {{
+                {statements}
+            }}
+
+            {vis} mod {name} {{
+                {cases}
+            }}
+        "
+    ))
+}
+
+impl Case {
+    fn new(
+        db: &dyn SyntaxGroup,
+        args: &Arguments,
+        index: usize,
+        params_count: usize,
+    ) -> Result<Self, Diagnostic> {
+        if !args.shorthand.is_empty() || args.named.keys().any(|arg| arg != "name") {
+            Err(TestCaseCollector::error(
+                "accepts the arguments of the test and an optional <name> only",
+            ))?;
+        }
+
+        let arguments: Vec<_> = args
+            .unnamed()
+            .iter()
+            .map(|(_, expr)| expr.as_syntax_node().get_text(db).trim().to_string())
+            .collect();
+
+        if arguments.len() != params_count {
+            Err(TestCaseCollector::error(format!(
+                "expected {params_count} arguments, got: {}",
+                arguments.len()
+            )))?;
+        }
+
+        let name = match args.named.as_once_optional("name")? {
+            Some(expr) => {
+                let name = String::parse_from_expr::<TestCaseCollector>(db, expr, "name")?;
+
+                if !is_identifier(&name) {
+                    Err(TestCaseCollector::error(
+                        "<name> must be a valid identifier",
+                    ))?;
+                }
+
+                name
+            }
+            None => format!("case_{}", index + 1),
+        };
+
+        Ok(Self { name, arguments })
+    }
+}
+
+fn is_attr<T: AttributeInfo>(db: &dyn SyntaxGroup, attr: &Attribute) -> bool {
+    attr.attr(db).as_syntax_node().get_text(db).trim() == T::ATTR_NAME
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+}
+
+/// Escapes the arguments to be passed in a string literal
+fn escape(arguments: &str) -> String {
+    arguments.replace('\\', r"\\").replace('"', r#"\""#)
+}
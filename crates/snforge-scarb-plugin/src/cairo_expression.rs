@@ -2,6 +2,12 @@ pub trait CairoExpression {
     fn as_cairo_expression(&self) -> String;
 }
 
+impl CairoExpression for bool {
+    fn as_cairo_expression(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl<T> CairoExpression for Option<T>
 where
     T: CairoExpression,
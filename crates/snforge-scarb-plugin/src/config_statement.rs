@@ -1,11 +1,11 @@
 use crate::{
     args::Arguments,
-    attributes::AttributeCollector,
+    attributes::{AttributeCollector, AttributeTypeData},
     common::{into_proc_macro_result, with_parsed_values},
 };
 use cairo_lang_macro::{Diagnostic, Diagnostics, ProcMacroResult, TokenStream};
 use cairo_lang_syntax::node::{
-    ast::{Condition, Expr, FunctionWithBody, Statement},
+    ast::{Condition, Expr, ExprIf, FunctionWithBody, Statement},
     db::SyntaxGroup,
     helpers::GetIdentifier,
     TypedSyntaxNode,
@@ -37,9 +37,19 @@ where
 {
     let value = Collector::args_into_config_expression(args_db, args, warns)?;
 
+    let config_cheatcode = config_cheatcode_statement::<Collector>(&value);
+
+    Ok(append_config_statements(db, func, &config_cheatcode))
+}
+
+/// Statements passing the config `value` to the runner with the cheatcode of `Collector`
+pub fn config_cheatcode_statement<Collector>(value: &str) -> String
+where
+    Collector: AttributeTypeData,
+{
     let cheatcode_name = Collector::CHEATCODE_NAME;
 
-    let config_cheatcode = formatdoc!(
+    formatdoc!(
         r#"
             let mut data = array![];
 
@@ -48,9 +58,7 @@ where
 
             starknet::testing::cheatcode::<'{cheatcode_name}'>(data.span());
         "#
-    );
-
-    Ok(append_config_statements(db, func, &config_cheatcode))
+    )
 }
 
 pub fn append_config_statements(
@@ -64,34 +72,7 @@ pub fn append_config_statements(
     let statements = func.body(db).statements(db).elements(db);
 
     let if_content = statements.first().and_then(|stmt| {
-        // first statement is `if`
-        let Statement::Expr(expr) = stmt else {
-            return None;
-        };
-        let Expr::If(if_expr) = expr.expr(db) else {
-            return None;
-        };
-        // it's condition is function call
-        let Condition::Expr(expr) = if_expr.condition(db) else {
-            return None;
-        };
-        let Expr::FunctionCall(expr) = expr.expr(db) else {
-            return None;
-        };
-
-        // this function is named "snforge_std::_cheatcode::_is_config_run"
-        let segments = expr.path(db).elements(db);
-
-        let [snforge_std, cheatcode, is_config_run] = segments.as_slice() else {
-            return None;
-        };
-
-        if snforge_std.identifier(db) != "snforge_std"
-            || cheatcode.identifier(db) != "_cheatcode"
-            || is_config_run.identifier(db) != "_is_config_run"
-        {
-            return None;
-        }
+        let if_expr = as_config_run_check(db, stmt)?;
 
         let statements = if_expr.if_block(db).statements(db).elements(db);
 
@@ -135,3 +116,37 @@ pub fn append_config_statements(
         "
     )
 }
+
+/// Returns the `if` expression if `stmt` is the config run check inserted by the attributes
+pub fn as_config_run_check(db: &dyn SyntaxGroup, stmt: &Statement) -> Option<ExprIf> {
+    // first statement is `if`
+    let Statement::Expr(expr) = stmt else {
+        return None;
+    };
+    let Expr::If(if_expr) = expr.expr(db) else {
+        return None;
+    };
+    // it's condition is function call
+    let Condition::Expr(expr) = if_expr.condition(db) else {
+        return None;
+    };
+    let Expr::FunctionCall(expr) = expr.expr(db) else {
+        return None;
+    };
+
+    // this function is named "snforge_std::_cheatcode::_is_config_run"
+    let segments = expr.path(db).elements(db);
+
+    let [snforge_std, cheatcode, is_config_run] = segments.as_slice() else {
+        return None;
+    };
+
+    if snforge_std.identifier(db) != "snforge_std"
+        || cheatcode.identifier(db) != "_cheatcode"
+        || is_config_run.identifier(db) != "_is_config_run"
+    {
+        return None;
+    }
+
+    Some(if_expr)
+}
@@ -1,6 +1,7 @@
 use attributes::{
-    available_gas::available_gas, fork::fork, fuzzer::fuzzer, ignore::ignore,
-    internal_config_statement::internal_config_statement, should_panic::should_panic, test::test,
+    available_gas::available_gas, available_steps::available_steps, fixture::fixture, fork::fork,
+    fuzzer::fuzzer, ignore::ignore, internal_config_statement::internal_config_statement,
+    serial::serial, should_panic::should_panic, test::test, test_case::test_case, timeout::timeout,
 };
 use cairo_lang_macro::{attribute_macro, executable_attribute, ProcMacroResult, TokenStream};
 
@@ -33,6 +34,11 @@ fn ignore(args: TokenStream, item: TokenStream) -> ProcMacroResult {
     ignore(args, item)
 }
 
+#[attribute_macro]
+fn fixture(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    fixture(args, item)
+}
+
 #[attribute_macro]
 fn fuzzer(args: TokenStream, item: TokenStream) -> ProcMacroResult {
     fuzzer(args, item)
@@ -48,7 +54,27 @@ fn available_gas(args: TokenStream, item: TokenStream) -> ProcMacroResult {
     available_gas(args, item)
 }
 
+#[attribute_macro]
+fn available_steps(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    available_steps(args, item)
+}
+
 #[attribute_macro]
 fn should_panic(args: TokenStream, item: TokenStream) -> ProcMacroResult {
     should_panic(args, item)
 }
+
+#[attribute_macro]
+fn test_case(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    test_case(args, item)
+}
+
+#[attribute_macro]
+fn timeout(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    timeout(args, item)
+}
+
+#[attribute_macro]
+fn serial(args: TokenStream, item: TokenStream) -> ProcMacroResult {
+    serial(args, item)
+}
@@ -4,7 +4,7 @@ use cairo_lang_filesystem::ids::{FileKind, FileLongId, VirtualFile};
 use cairo_lang_macro::Diagnostic;
 use cairo_lang_parser::{parser::Parser, utils::SimpleParserDatabase};
 use cairo_lang_syntax::node::{
-    ast::{FunctionWithBody, ModuleItem, OptionArgListParenthesized},
+    ast::{FunctionWithBody, ItemModule, ModuleItem, OptionArgListParenthesized},
     db::SyntaxGroup,
     helpers::QueryAttrs,
 };
@@ -15,6 +15,38 @@ use std::sync::Arc;
 pub fn parse<T: AttributeInfo>(
     code: &str,
 ) -> Result<(SimpleParserDatabase, FunctionWithBody), Diagnostic> {
+    let (simple_db, elements) = parse_items(code);
+
+    elements
+        .into_iter()
+        .find_map(|element| {
+            if let ModuleItem::FreeFunction(func) = element {
+                Some(func)
+            } else {
+                None
+            }
+        })
+        .map(|func| (simple_db, func))
+        .ok_or_else(|| T::error("can be used only on a function"))
+}
+
+/// Returns the module if `code` is a module item, `None` for any other item
+pub fn parse_module(code: &str) -> Option<(SimpleParserDatabase, ItemModule)> {
+    let (simple_db, elements) = parse_items(code);
+
+    elements
+        .into_iter()
+        .find_map(|element| {
+            if let ModuleItem::Module(module) = element {
+                Some(module)
+            } else {
+                None
+            }
+        })
+        .map(|module| (simple_db, module))
+}
+
+fn parse_items(code: &str) -> (SimpleParserDatabase, Vec<ModuleItem>) {
     let simple_db = SimpleParserDatabase::default();
     let code = Arc::new(code.to_string());
     let db: &dyn SyntaxGroup = simple_db.upcast();
@@ -31,17 +63,7 @@ pub fn parse<T: AttributeInfo>(
         .items(db)
         .elements(db);
 
-    elements
-        .into_iter()
-        .find_map(|element| {
-            if let ModuleItem::FreeFunction(func) = element {
-                Some(func)
-            } else {
-                None
-            }
-        })
-        .map(|func| (simple_db, func))
-        .ok_or_else(|| T::error("can be used only on a function"))
+    (simple_db, elements)
 }
 
 struct InternalCollector;
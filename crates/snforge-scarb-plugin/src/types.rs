@@ -88,6 +88,20 @@ impl ParseFromExpr<Expr> for Number {
     }
 }
 
+impl ParseFromExpr<Expr> for bool {
+    fn parse_from_expr<T: AttributeInfo>(
+        _db: &dyn SyntaxGroup,
+        expr: &Expr,
+        arg_name: &str,
+    ) -> Result<Self, Diagnostic> {
+        match expr {
+            Expr::True(_) => Ok(true),
+            Expr::False(_) => Ok(false),
+            _ => Err(T::error(format!("<{arg_name}> should be true or false"))),
+        }
+    }
+}
+
 impl ParseFromExpr<Expr> for Url {
     fn parse_from_expr<T: AttributeInfo>(
         db: &dyn SyntaxGroup,
@@ -1,7 +1,12 @@
 mod available_gas;
+mod available_steps;
+mod fixture;
 mod fork;
 mod fuzzer;
 mod ignore;
 mod internal_config_statement;
+mod serial;
 mod should_panic;
 mod test;
+mod test_case;
+mod timeout;
@@ -0,0 +1,149 @@
+use crate::utils::{assert_diagnostics, assert_output, EMPTY_FN};
+use cairo_lang_macro::{Diagnostic, TokenStream};
+use indoc::formatdoc;
+use snforge_scarb_plugin::attributes::available_steps::available_steps;
+
+#[test]
+fn fails_with_empty() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("()".into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[available_steps] expected 1 arguments, got: 0",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_more_than_one() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(123,123,123)".into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[available_steps] expected 1 arguments, got: 3",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_non_number_literal() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new(r#"("123")"#.into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[available_steps] <0> should be number literal",
+        )],
+    );
+}
+
+#[test]
+fn work_with_number() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(123)".into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        "
+            fn empty_fn() {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::AvailableStepsConfig {
+                        steps: 0x7b
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_available_steps'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn fails_with_number_not_fitting_u32() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(4294967296)".into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[available_steps] <0> can not be greater than 4294967295",
+        )],
+    );
+}
+
+#[test]
+fn warns_with_number_exceeding_protocol_max() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(20_000_000)".into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::warn(
+            "#[available_steps] <0> exceeds the maximum of 10000000 steps allowed in a Starknet transaction, the tested code would not be executable on the network",
+        )],
+    );
+
+    assert_output(
+        &result,
+        "
+            fn empty_fn() {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::AvailableStepsConfig {
+                        steps: 0x1312d00
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_available_steps'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn is_used_once() {
+    let item = TokenStream::new(formatdoc!(
+        "
+            #[available_steps]
+            {EMPTY_FN}
+        "
+    ));
+    let args = TokenStream::new("(123)".into());
+
+    let result = available_steps(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[available_steps] can only be used once per item",
+        )],
+    );
+}
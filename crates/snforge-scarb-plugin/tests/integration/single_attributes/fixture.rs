@@ -0,0 +1,75 @@
+use crate::utils::{assert_diagnostics, assert_output, EMPTY_FN};
+use cairo_lang_macro::{Diagnostic, TokenStream};
+use snforge_scarb_plugin::attributes::fixture::fixture;
+
+#[test]
+fn accepts_function_returning_value() {
+    let item = TokenStream::new("fn setup() -> Setup { Setup {} }".into());
+    let args = TokenStream::new(String::new());
+
+    let result = fixture(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(&result, "fn setup() -> Setup { Setup {} }");
+}
+
+#[test]
+fn fails_with_non_empty_args() {
+    let item = TokenStream::new("fn setup() -> Setup { Setup {} }".into());
+    let args = TokenStream::new("(123)".into());
+
+    let result = fixture(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fixture] does not accept any arguments",
+        )],
+    );
+}
+
+#[test]
+fn fails_without_return_value() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new(String::new());
+
+    let result = fixture(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fixture] can be used only on a function returning a value",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_parameters() {
+    let item = TokenStream::new("fn setup(a: felt252) -> Setup { Setup {} }".into());
+    let args = TokenStream::new(String::new());
+
+    let result = fixture(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fixture] can be used only on a function without parameters",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_fuzzable_return_type() {
+    let item = TokenStream::new("fn setup() -> felt252 { 1 }".into());
+    let args = TokenStream::new(String::new());
+
+    let result = fixture(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fixture] can't return felt252, test parameters of this type are generated by the fuzzer",
+        )],
+    );
+}
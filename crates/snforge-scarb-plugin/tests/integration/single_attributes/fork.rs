@@ -237,3 +237,150 @@ fn is_used_once() {
         &[Diagnostic::error("#[fork] can only be used once per item")],
     );
 }
+
+#[test]
+fn accepts_none() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(none)".into());
+
+    let result = fork(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r"
+            fn empty_fn() {
+                if snforge_std::_cheatcode::_is_config_run() {
+
+                    let mut data = array![];
+
+                    snforge_std::_config_types::ForkConfig::Disabled(())
+                        .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_fork'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn applies_to_tests_in_module() {
+    let item = TokenStream::new(
+        r#"
+            mod tests {
+                #[test]
+                fn first() {}
+
+                #[test]
+                #[fork(none)]
+                fn second() {}
+
+                fn helper() {}
+            }
+        "#
+        .into(),
+    );
+    let args = TokenStream::new(r#"("test")"#.into());
+
+    let result = fork(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            mod tests {
+                #[fork("test")]
+                #[test]
+                fn first() {}
+
+                #[test]
+                #[fork(none)]
+                fn second() {}
+
+                fn helper() {}
+            }
+        "#,
+    );
+}
+
+#[test]
+fn applies_to_tests_in_nested_modules() {
+    let item = TokenStream::new(
+        r#"
+            mod tests {
+                mod nested {
+                    #[test]
+                    fn first() {}
+
+                    mod deeper {
+                        #[test]
+                        fn second() {}
+                    }
+                }
+
+                #[fork("other")]
+                mod overridden {
+                    #[test]
+                    fn third() {}
+                }
+            }
+        "#
+        .into(),
+    );
+    let args = TokenStream::new(r#"("test")"#.into());
+
+    let result = fork(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            mod tests {
+                mod nested {
+                    #[fork("test")]
+                    #[test]
+                    fn first() {}
+
+                    mod deeper {
+                        #[fork("test")]
+                        #[test]
+                        fn second() {}
+                    }
+                }
+
+                #[fork("other")]
+                mod overridden {
+                    #[test]
+                    fn third() {}
+                }
+            }
+        "#,
+    );
+}
+
+#[test]
+fn fails_on_module_with_invalid_args() {
+    let item = TokenStream::new("mod tests {}".into());
+    let args = TokenStream::new("(block_number: 23)".into());
+
+    let result = fork(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(formatdoc!(
+            "
+                All options failed
+                - variant: <url> argument is missing
+                - variant: #[fork] expected 1 arguments, got: 0
+                - variant: #[fork] can be used with unnamed attributes only
+                Resolve at least one of them
+            "
+        ))],
+    );
+}
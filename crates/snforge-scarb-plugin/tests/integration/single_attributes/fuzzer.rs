@@ -21,7 +21,10 @@ fn work_without_args() {
 
                     snforge_std::_config_types::FuzzerConfig {
                         seed: Option::None,
-                        runs: Option::None
+                        runs: Option::None,
+                        ranges: array![],
+                        shrink_runs: Option::None,
+                        non_deterministic: false
                     }
                     .serialize(ref data);
 
@@ -52,7 +55,10 @@ fn work_with_both_args() {
 
                     snforge_std::_config_types::FuzzerConfig {
                         seed: Option::Some(0x1f597a5),
-                        runs: Option::Some(0x28f)
+                        runs: Option::Some(0x28f),
+                        ranges: array![],
+                        shrink_runs: Option::None,
+                        non_deterministic: false
                     }
                     .serialize(ref data);
 
@@ -83,7 +89,10 @@ fn work_with_runs_only() {
 
                     snforge_std::_config_types::FuzzerConfig {
                         seed: Option::None,
-                        runs: Option::Some(0x28f)
+                        runs: Option::Some(0x28f),
+                        ranges: array![],
+                        shrink_runs: Option::None,
+                        non_deterministic: false
                     }
                     .serialize(ref data);
 
@@ -114,7 +123,10 @@ fn work_with_seed_only() {
 
                     snforge_std::_config_types::FuzzerConfig {
                         seed: Option::Some(0x28f),
-                        runs: Option::None
+                        runs: Option::None,
+                        ranges: array![],
+                        shrink_runs: Option::None,
+                        non_deterministic: false
                     }
                     .serialize(ref data);
 
@@ -161,3 +173,187 @@ fn is_used_once() {
         )],
     );
 }
+
+#[test]
+fn work_with_ranges() {
+    let item = TokenStream::new("fn fuzzed(setup: Setup, a: u64, b: u256, c: felt252) {}".into());
+    let args =
+        TokenStream::new("(runs: 10, a: range(low: 1, high: 10_000), c: range(high: 5))".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        "
+            fn fuzzed(setup: Setup, a: u64, b: u256, c: felt252) {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::FuzzerConfig {
+                        seed: Option::None,
+                        runs: Option::Some(0xa),
+                        ranges: array![
+                            snforge_std::_config_types::FuzzerRange { index: 0, low: 0x1, high: 0x2710 },
+                            snforge_std::_config_types::FuzzerRange { index: 2, low: 0x0, high: 0x5 },
+                        ],
+                        shrink_runs: Option::None,
+                        non_deterministic: false
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_fuzzer'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn range_defaults_to_type_bounds() {
+    let item = TokenStream::new("fn fuzzed(a: u8, b: ContractAddress) {}".into());
+    let args = TokenStream::new("(a: range(low: 10), b: range(low: 1))".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        "
+            fn fuzzed(a: u8, b: ContractAddress) {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::FuzzerConfig {
+                        seed: Option::None,
+                        runs: Option::None,
+                        ranges: array![
+                            snforge_std::_config_types::FuzzerRange { index: 0, low: 0xa, high: 0xff },
+                            snforge_std::_config_types::FuzzerRange { index: 1, low: 0x1, high: 0x7ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff },
+                        ],
+                        shrink_runs: Option::None,
+                        non_deterministic: false
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_fuzzer'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn fail_with_range_for_unknown_param() {
+    let item = TokenStream::new("fn fuzzed(setup: Setup, a: u64) {}".into());
+    let args = TokenStream::new("(setup: range(low: 1))".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fuzzer] <setup> is not a fuzzed parameter of the test",
+        )],
+    );
+}
+
+#[test]
+fn fail_with_invalid_range() {
+    let item = TokenStream::new("fn fuzzed(a: u64) {}".into());
+    let args = TokenStream::new("(a: (1, 2))".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fuzzer] <a> should be range(low: <number>, high: <number>)",
+        )],
+    );
+}
+
+#[test]
+fn fail_with_empty_range() {
+    let item = TokenStream::new("fn fuzzed(a: u64) {}".into());
+    let args = TokenStream::new("(a: range(low: 20, high: 10))".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fuzzer] <a> range is empty, low is greater than high",
+        )],
+    );
+}
+
+#[test]
+fn fail_with_range_exceeding_type() {
+    let item = TokenStream::new("fn fuzzed(a: u8) {}".into());
+    let args = TokenStream::new("(a: range(high: 256))".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fuzzer] <a> range exceeds values of its type, high can be at most 255",
+        )],
+    );
+}
+
+#[test]
+fn work_with_shrinking_config() {
+    let item = TokenStream::new("fn fuzzed(a: u64) {}".into());
+    let args = TokenStream::new("(shrink_runs: 50, non_deterministic: true)".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        "
+            fn fuzzed(a: u64) {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::FuzzerConfig {
+                        seed: Option::None,
+                        runs: Option::None,
+                        ranges: array![],
+                        shrink_runs: Option::Some(0x32),
+                        non_deterministic: true
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_fuzzer'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn fail_with_invalid_non_deterministic() {
+    let item = TokenStream::new("fn fuzzed(a: u64) {}".into());
+    let args = TokenStream::new("(non_deterministic: 1)".into());
+
+    let result = fuzzer(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[fuzzer] <non_deterministic> should be true or false",
+        )],
+    );
+}
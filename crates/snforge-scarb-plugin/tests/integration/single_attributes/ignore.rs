@@ -4,7 +4,7 @@ use indoc::formatdoc;
 use snforge_scarb_plugin::attributes::ignore::ignore;
 
 #[test]
-fn fails_with_args() {
+fn fails_with_non_string_arg() {
     let item = TokenStream::new(EMPTY_FN.into());
     let args = TokenStream::new("(123)".into());
 
@@ -12,7 +12,70 @@ fn fails_with_args() {
 
     assert_diagnostics(
         &result,
-        &[Diagnostic::error("#[ignore] does not accept any arguments")],
+        &[Diagnostic::error(
+            "#[ignore] <0> invalid type, should be: double quotted string",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_multiple_args() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new(r#"("first", "second")"#.into());
+
+    let result = ignore(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[ignore] expected at most 1 argument, got: 2",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_named_args() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new(r#"(reason: "flaky")"#.into());
+
+    let result = ignore(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[ignore] can be used with unnamed attributes only",
+        )],
+    );
+}
+
+#[test]
+fn works_with_reason() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new(r#"("flaky until fixed")"#.into());
+
+    let result = ignore(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            fn empty_fn() {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::IgnoreConfig {
+                        is_ignored: true,
+                        reason: Option::Some("flaky until fixed")
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_ignore'>(data.span());
+
+                    return;
+                }
+            }
+        "#,
     );
 }
 
@@ -33,7 +96,8 @@ fn works_without_args() {
                     let mut data = array![];
 
                     snforge_std::_config_types::IgnoreConfig {
-                        is_ignored: true
+                        is_ignored: true,
+                        reason: Option::None
                     }
                     .serialize(ref data);
 
@@ -0,0 +1,67 @@
+use crate::utils::{assert_diagnostics, assert_output, EMPTY_FN};
+use cairo_lang_macro::{Diagnostic, TokenStream};
+use indoc::formatdoc;
+use snforge_scarb_plugin::attributes::serial::serial;
+
+#[test]
+fn fails_with_args() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(123)".into());
+
+    let result = serial(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error("#[serial] does not accept any arguments")],
+    );
+}
+
+#[test]
+fn works_without_args() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new(String::new());
+
+    let result = serial(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        "
+            fn empty_fn() {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let mut data = array![];
+
+                    snforge_std::_config_types::SerialConfig {
+                        is_serial: true
+                    }
+                    .serialize(ref data);
+
+                    starknet::testing::cheatcode::<'set_config_serial'>(data.span());
+
+                    return;
+                }
+            }
+        ",
+    );
+}
+
+#[test]
+fn is_used_once() {
+    let item = TokenStream::new(formatdoc!(
+        "
+            #[serial]
+            {EMPTY_FN}
+        "
+    ));
+    let args = TokenStream::new(String::new());
+
+    let result = serial(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[serial] can only be used once per item",
+        )],
+    );
+}
@@ -23,7 +23,7 @@ fn appends_internal_config_and_executable() {
 }
 
 #[test]
-fn fails_with_non_empty_args() {
+fn fails_with_unnamed_args() {
     let item = TokenStream::new(EMPTY_FN.into());
     let args = TokenStream::new("(123)".into());
 
@@ -31,7 +31,22 @@ fn fails_with_non_empty_args() {
 
     assert_diagnostics(
         &result,
-        &[Diagnostic::error("#[test] does not accept any arguments")],
+        &[Diagnostic::error(
+            "#[test] can be used with named attributes only",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_unexpected_named_arg() {
+    let item = TokenStream::new(EMPTY_FN.into());
+    let args = TokenStream::new("(fixture: setup)".into());
+
+    let result = test(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error("#[test] unexpected argument <fixture>")],
     );
 }
 
@@ -52,3 +67,166 @@ fn is_used_once() {
         &[Diagnostic::error("#[test] can only be used once per item")],
     );
 }
+
+#[test]
+fn replaces_fixture_params_with_fixture_calls() {
+    let item = TokenStream::new(
+        "
+            fn with_fixture(setup: Setup, a: u64) {
+                assert(a == a, 'a == a');
+            }
+        "
+        .into(),
+    );
+    let args = TokenStream::new("(fixtures: (setup))".into());
+
+    let result = test(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            #[snforge_internal_test_executable]
+            #[__internal_config_statement]
+            fn with_fixture(a: u64) {
+                let setup: Setup = {
+                    snforge_std::_cheatcode::_enter_fixture("setup");
+                    let fixture_value = setup();
+                    snforge_std::_cheatcode::_exit_fixture();
+                    fixture_value
+                };
+                assert(a == a, 'a == a');
+            }
+        "#,
+    );
+}
+
+#[test]
+fn replaces_multiple_fixture_params() {
+    let item = TokenStream::new(
+        "
+            fn with_fixtures(setup: Setup, token: Token) {}
+        "
+        .into(),
+    );
+    let args = TokenStream::new("(fixtures: (setup, token))".into());
+
+    let result = test(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            #[snforge_internal_test_executable]
+            #[__internal_config_statement]
+            fn with_fixtures() {
+                let setup: Setup = {
+                    snforge_std::_cheatcode::_enter_fixture("setup");
+                    let fixture_value = setup();
+                    snforge_std::_cheatcode::_exit_fixture();
+                    fixture_value
+                };
+                let token: Token = {
+                    snforge_std::_cheatcode::_enter_fixture("token");
+                    let fixture_value = token();
+                    snforge_std::_cheatcode::_exit_fixture();
+                    fixture_value
+                };
+            }
+        "#,
+    );
+}
+
+#[test]
+fn fails_with_param_not_listed_in_fixtures() {
+    let item = TokenStream::new("fn with_fixture(setup: Setup, a: u64) {}".into());
+    let args = TokenStream::new(String::new());
+
+    let result = test(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test] parameter `setup` of type Setup can't be generated by the fuzzer, \
+             if it is provided by a #[fixture] function, list it in <fixtures> argument",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_fixture_not_being_param() {
+    let item = TokenStream::new("fn with_fixture(setup: Setup) {}".into());
+    let args = TokenStream::new("(fixtures: (setup, stup))".into());
+
+    let result = test(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test] <fixtures> argument lists `stup`, which is not a parameter of the test",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_fuzzable_fixture() {
+    let item = TokenStream::new("fn with_fixture(a: u64) {}".into());
+    let args = TokenStream::new("(fixtures: a)".into());
+
+    let result = test(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test] parameter `a` of type u64 is generated by the fuzzer and can't be a fixture",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_fixtures_and_test_cases() {
+    let item = TokenStream::new(
+        "
+            #[test_case(1)]
+            fn test_case_fn(setup: Setup, a: u64) {}
+        "
+        .into(),
+    );
+    let args = TokenStream::new("(fixtures: (setup))".into());
+
+    let result = test(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test] <fixtures> argument can't be used together with #[test_case]",
+        )],
+    );
+}
+
+#[test]
+fn is_moved_after_test_cases() {
+    let item = TokenStream::new(
+        "
+            #[test_case(1)]
+            fn test_case_fn(a: u64) {}
+        "
+        .into(),
+    );
+    let args = TokenStream::new(String::new());
+
+    let result = test(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        "
+            #[test_case(1)]
+            #[test]
+            fn test_case_fn(a: u64) {}
+        ",
+    );
+}
@@ -0,0 +1,216 @@
+use crate::utils::{assert_diagnostics, assert_output};
+use cairo_lang_macro::{Diagnostic, TokenStream};
+use snforge_scarb_plugin::attributes::test_case::test_case;
+
+const TEST_ADD: &str = "
+    #[test_case(0, 0, 0, name: \"zeros\")]
+    #[test]
+    fn test_add(a: u64, b: u64, expected: u64) {
+        assert(a + b == expected, 'invalid sum');
+    }
+";
+
+#[test]
+fn expands_every_case() {
+    let item = TokenStream::new(TEST_ADD.into());
+    let args = TokenStream::new("(2, 3, 5)".into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            fn __snforge_test_case_test_add(a: u64, b: u64, expected: u64) {
+                assert(a + b == expected, 'invalid sum');
+            }
+
+            mod test_add {
+                #[test]
+                fn case_1() {
+                    if snforge_std::_cheatcode::_is_config_run() {
+                        let mut data = array![];
+
+                        snforge_std::_config_types::TestCaseConfig { name: "case_1", arguments: "2, 3, 5" }
+                        .serialize(ref data);
+
+                        starknet::testing::cheatcode::<'set_config_test_case'>(data.span());
+
+                        return;
+                    }
+
+                    super::__snforge_test_case_test_add(2, 3, 5)
+                }
+
+                #[test]
+                fn zeros() {
+                    if snforge_std::_cheatcode::_is_config_run() {
+                        let mut data = array![];
+
+                        snforge_std::_config_types::TestCaseConfig { name: "zeros", arguments: "0, 0, 0" }
+                        .serialize(ref data);
+
+                        starknet::testing::cheatcode::<'set_config_test_case'>(data.span());
+
+                        return;
+                    }
+
+                    super::__snforge_test_case_test_add(0, 0, 0)
+                }
+            }
+        "#,
+    );
+}
+
+#[test]
+fn applies_other_attributes_to_every_case() {
+    let item = TokenStream::new(
+        "
+            #[test]
+            #[should_panic]
+            fn test_fails(a: ByteArray) {
+                if snforge_std::_cheatcode::_is_config_run() {
+                    let x = 1;
+
+                    return;
+                }
+
+                assert(a.len() == 0, 'not empty');
+            }
+        "
+        .into(),
+    );
+    let args = TokenStream::new(r#"("abc")"#.into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(&result, &[]);
+
+    assert_output(
+        &result,
+        r#"
+            fn __snforge_test_case_test_fails(a: ByteArray) {
+                assert(a.len() == 0, 'not empty');
+            }
+
+            mod test_fails {
+                #[test]
+                #[should_panic]
+                fn case_1() {
+                    if snforge_std::_cheatcode::_is_config_run() {
+                        let x = 1;
+
+                        let mut data = array![];
+
+                        snforge_std::_config_types::TestCaseConfig { name: "case_1", arguments: "\"abc\"" }
+                        .serialize(ref data);
+
+                        starknet::testing::cheatcode::<'set_config_test_case'>(data.span());
+
+                        return;
+                    }
+
+                    super::__snforge_test_case_test_fails("abc")
+                }
+            }
+        "#,
+    );
+}
+
+#[test]
+fn fails_without_test_attribute() {
+    let item = TokenStream::new("fn test_add(a: u64) {}".into());
+    let args = TokenStream::new("(1)".into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test_case] can only be used on functions marked with #[test]",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_fuzzer() {
+    let item = TokenStream::new(
+        "
+            #[test]
+            #[fuzzer]
+            fn test_add(a: u64) {}
+        "
+        .into(),
+    );
+    let args = TokenStream::new("(1)".into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test_case] cannot be used with #[fuzzer]",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_invalid_number_of_arguments() {
+    let item = TokenStream::new(TEST_ADD.into());
+    let args = TokenStream::new("(2, 3)".into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test_case] expected 3 arguments, got: 2",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_invalid_name() {
+    let item = TokenStream::new(TEST_ADD.into());
+    let args = TokenStream::new(r#"(2, 3, 5, name: "two plus three")"#.into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test_case] <name> must be a valid identifier",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_duplicated_name() {
+    let item = TokenStream::new(TEST_ADD.into());
+    let args = TokenStream::new(r#"(2, 3, 5, name: "zeros")"#.into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test_case] <name> = zeros is used by more than one case",
+        )],
+    );
+}
+
+#[test]
+fn fails_with_unknown_named_argument() {
+    let item = TokenStream::new(TEST_ADD.into());
+    let args = TokenStream::new("(2, 3, 5, label: 1)".into());
+
+    let result = test_case(args, item);
+
+    assert_diagnostics(
+        &result,
+        &[Diagnostic::error(
+            "#[test_case] accepts the arguments of the test and an optional <name> only",
+        )],
+    );
+}
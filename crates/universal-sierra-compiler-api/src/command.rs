@@ -1,13 +1,21 @@
+use crate::version::INSTALL_HINT;
 use anyhow::Context;
+use semver::Version;
+use shared::command::CommandExt;
 use std::env;
 use std::ffi::{OsStr, OsString};
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::str::from_utf8;
+
+/// Environment variable with a path to a custom `universal-sierra-compiler` binary
+pub const UNIVERSAL_SIERRA_COMPILER_ENV_VAR: &str = "UNIVERSAL_SIERRA_COMPILER";
 
 /// A builder for `universal-sierra-compiler` command invocation.
 #[derive(Clone, Debug, Default)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct UniversalSierraCompilerCommand {
+    binary: Option<PathBuf>,
     args: Vec<OsString>,
     current_dir: Option<PathBuf>,
     inherit_stderr: bool,
@@ -21,16 +29,53 @@ impl UniversalSierraCompilerCommand {
         Self::default()
     }
 
-    /// Ensures that `universal-sierra-compiler` binary is available in the system.
+    /// Ensures that `universal-sierra-compiler` binary is available in the system
+    /// and that its version is supported.
     pub fn ensure_available() -> anyhow::Result<()> {
-        which::which(UniversalSierraCompilerCommand::binary_path())
-            .context("Cannot find `universal-sierra-compiler` binary. \
-                      Make sure you have USC installed https://github.com/software-mansion/universal-sierra-compiler \
-                      and added to PATH (or set at UNIVERSAL_SIERRA_COMPILER env var)"
-            )?;
+        crate::version::ensure_supported_version()
+    }
+
+    /// Version of the `universal-sierra-compiler` binary, e.g. `2.3.0`.
+    pub fn version() -> anyhow::Result<String> {
+        Self::new()
+            .read_version()
+            .map(|version| version.to_string())
+    }
+
+    /// Path to the `universal-sierra-compiler` binary, overriding the one from
+    /// `UNIVERSAL_SIERRA_COMPILER` env var and `$PATH`.
+    pub fn binary(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.binary = Some(path.into());
+        self
+    }
+
+    pub(crate) fn ensure_binary_exists(&self) -> anyhow::Result<()> {
+        which::which(self.resolved_binary_path()).with_context(|| {
+            format!("Cannot find `universal-sierra-compiler` binary\n{INSTALL_HINT}")
+        })?;
         Ok(())
     }
 
+    pub(crate) fn read_version(&self) -> anyhow::Result<Version> {
+        let output = self
+            .clone()
+            .arg("--version")
+            .command()
+            .output_checked()
+            .context("Failed to execute `universal-sierra-compiler --version`")?;
+        let output = from_utf8(&output.stdout)
+            .context("Failed to parse `universal-sierra-compiler --version` output to UTF-8")?;
+
+        // Output has the form of `universal-sierra-compiler 2.3.0`
+        let version = output
+            .split_whitespace()
+            .last()
+            .context("Could not find universal-sierra-compiler version")?;
+
+        Version::parse(version)
+            .with_context(|| format!("Failed to parse universal-sierra-compiler version {version}"))
+    }
+
     /// Current directory of the `universal-sierra-compiler` process.
     pub fn current_dir(&mut self, path: impl Into<PathBuf>) -> &mut Self {
         self.current_dir = Some(path.into());
@@ -63,9 +108,7 @@ impl UniversalSierraCompilerCommand {
     /// Build executable `universal-sierra-compiler` command.
     #[must_use]
     pub fn command(&self) -> Command {
-        let universal_sierra_compiler = UniversalSierraCompilerCommand::binary_path();
-
-        let mut cmd = Command::new(universal_sierra_compiler);
+        let mut cmd = Command::new(self.resolved_binary_path());
 
         cmd.args(&self.args);
 
@@ -80,8 +123,14 @@ impl UniversalSierraCompilerCommand {
         cmd
     }
 
+    fn resolved_binary_path(&self) -> PathBuf {
+        self.binary
+            .clone()
+            .unwrap_or_else(UniversalSierraCompilerCommand::binary_path)
+    }
+
     fn binary_path() -> PathBuf {
-        env::var("UNIVERSAL_SIERRA_COMPILER")
+        env::var(UNIVERSAL_SIERRA_COMPILER_ENV_VAR)
             .map(PathBuf::from)
             .ok()
             .unwrap_or_else(|| PathBuf::from("universal-sierra-compiler"))
@@ -1,4 +1,4 @@
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use cairo_lang_casm::hints::Hint;
 use cairo_lang_sierra::program::Program;
 use num_bigint::BigInt;
@@ -8,12 +8,25 @@ use std::fmt::Display;
 use std::io::Write;
 use std::path::Path;
 use std::str::from_utf8;
+use std::sync::OnceLock;
 use tempfile::Builder;
 
 pub use command::*;
 use shared::command::CommandExt;
+use version::ensure_supported_version;
+pub use version::SUPPORTED_USC_VERSIONS;
 
 mod command;
+mod version;
+
+const BATCH_ARG: &str = "--sierra-paths";
+
+const COMPILATION_ERROR: &str = "Error while compiling Sierra. \
+    Make sure you have the latest universal-sierra-compiler binary installed. \
+    Contact us if it doesn't help";
+
+/// Contract class versions which `compile-contract` of universal-sierra-compiler accepts
+pub const SUPPORTED_CONTRACT_CLASS_VERSIONS: [&str; 1] = ["0.1.0"];
 
 pub type CasmCodeOffset = usize;
 pub type CasmInstructionIdx = usize;
@@ -69,7 +82,68 @@ pub fn compile_sierra_at_path(
     current_dir: Option<&Path>,
     sierra_type: &SierraType,
 ) -> Result<String> {
-    let mut usc_command = UniversalSierraCompilerCommand::new();
+    ensure_supported_version()?;
+
+    compile_at_path_with(
+        UniversalSierraCompilerCommand::new(),
+        sierra_file_path,
+        current_dir,
+        sierra_type,
+    )
+}
+
+/// Compiles multiple Sierra files with a single `universal-sierra-compiler` process, to save
+/// its startup time, if the installed version supports it, and with a process per file otherwise.
+/// Results are in the order of `sierra_file_paths`
+pub fn compile_sierra_batch(
+    sierra_file_paths: &[&str],
+    current_dir: Option<&Path>,
+    sierra_type: &SierraType,
+) -> Result<Vec<String>> {
+    ensure_supported_version()?;
+
+    compile_batch_with(
+        UniversalSierraCompilerCommand::new(),
+        sierra_file_paths,
+        current_dir,
+        sierra_type,
+        supports_batch_compilation(sierra_type),
+    )
+}
+
+/// Whether the installed `universal-sierra-compiler` compiles multiple files in a single process,
+/// checked once per process and Sierra type
+#[must_use]
+pub fn supports_batch_compilation(sierra_type: &SierraType) -> bool {
+    static CONTRACT: OnceLock<bool> = OnceLock::new();
+    static RAW: OnceLock<bool> = OnceLock::new();
+
+    let support = match sierra_type {
+        SierraType::Contract => &CONTRACT,
+        SierraType::Raw => &RAW,
+    };
+
+    *support.get_or_init(|| probe_batch_support(UniversalSierraCompilerCommand::new(), sierra_type))
+}
+
+/// Batch compilation is advertised with `--sierra-paths` argument in the help of the subcommand
+fn probe_batch_support(
+    mut usc_command: UniversalSierraCompilerCommand,
+    sierra_type: &SierraType,
+) -> bool {
+    usc_command
+        .args([&subcommand(sierra_type), "--help"])
+        .command()
+        .output_checked()
+        .is_ok_and(|output| from_utf8(&output.stdout).is_ok_and(|help| help.contains(BATCH_ARG)))
+}
+
+fn compile_at_path_with(
+    mut usc_command: UniversalSierraCompilerCommand,
+    sierra_file_path: &str,
+    current_dir: Option<&Path>,
+    sierra_type: &SierraType,
+) -> Result<String> {
     if let Some(dir) = current_dir {
         usc_command.current_dir(dir);
     }
@@ -77,26 +151,95 @@ pub fn compile_sierra_at_path(
     let usc_output = usc_command
         .inherit_stderr()
         .args(vec![
-            &("compile-".to_string() + &sierra_type.to_string()),
+            &subcommand(sierra_type),
             "--sierra-path",
             sierra_file_path,
         ])
         .command()
         .output_checked()
-        .context(
-            "Error while compiling Sierra. \
-            Make sure you have the latest universal-sierra-compiler binary installed. \
-            Contact us if it doesn't help",
-        )?;
+        .context(COMPILATION_ERROR)?;
 
     Ok(from_utf8(&usc_output.stdout)?.to_string())
 }
 
+fn compile_batch_with(
+    mut usc_command: UniversalSierraCompilerCommand,
+    sierra_file_paths: &[&str],
+    current_dir: Option<&Path>,
+    sierra_type: &SierraType,
+    batch: bool,
+) -> Result<Vec<String>> {
+    if !batch {
+        return sierra_file_paths
+            .iter()
+            .map(|path| compile_at_path_with(usc_command.clone(), path, current_dir, sierra_type))
+            .collect();
+    }
+
+    if let Some(dir) = current_dir {
+        usc_command.current_dir(dir);
+    }
+
+    let usc_output = usc_command
+        .inherit_stderr()
+        .arg(subcommand(sierra_type))
+        .arg(BATCH_ARG)
+        .args(sierra_file_paths)
+        .command()
+        .output_checked()
+        .context(COMPILATION_ERROR)?;
+
+    // Output is a JSON array with results for the files in the order they were passed
+    let results: Vec<Value> = serde_json::from_slice(&usc_output.stdout)
+        .context("Failed to parse output of batch compilation of Sierra")?;
+    ensure!(
+        results.len() == sierra_file_paths.len(),
+        "Batch compilation of Sierra returned {} results for {} files",
+        results.len(),
+        sierra_file_paths.len()
+    );
+
+    Ok(results.iter().map(Value::to_string).collect())
+}
+
+fn subcommand(sierra_type: &SierraType) -> String {
+    format!("compile-{sierra_type}")
+}
+
 pub enum SierraType {
     Contract,
     Raw,
 }
 
+impl SierraType {
+    /// Picks how Sierra given as JSON is compiled: contract classes as contracts, other programs as raw Sierra.
+    /// Fails for contract classes of a version which the compiler does not support.
+    pub fn detect(sierra: &Value) -> Result<Self> {
+        let Some(contract_class_version) = sierra.get("contract_class_version") else {
+            return Ok(SierraType::Raw);
+        };
+        let contract_class_version = contract_class_version
+            .as_str()
+            .context("`contract_class_version` of the contract class must be a string")?;
+
+        ensure!(
+            SUPPORTED_CONTRACT_CLASS_VERSIONS.contains(&contract_class_version),
+            "Contract class version {contract_class_version} is not supported by {}, supported versions: {}",
+            compiler_description(),
+            SUPPORTED_CONTRACT_CLASS_VERSIONS.join(", ")
+        );
+
+        Ok(SierraType::Contract)
+    }
+}
+
+fn compiler_description() -> String {
+    UniversalSierraCompilerCommand::version().map_or_else(
+        |_| "universal-sierra-compiler of unknown version".to_string(),
+        |version| format!("universal-sierra-compiler {version}"),
+    )
+}
+
 impl Display for SierraType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -109,3 +252,154 @@ impl Display for SierraType {
         )
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::version::check_version;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    /// Writes an executable script pretending to be `universal-sierra-compiler`
+    fn stub_compiler(dir: &TempDir, script: &str) -> UniversalSierraCompilerCommand {
+        let path = dir.path().join("universal-sierra-compiler");
+        fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut command = UniversalSierraCompilerCommand::new();
+        command.binary(path);
+        command
+    }
+
+    fn stub_with_version(dir: &TempDir, version: &str) -> UniversalSierraCompilerCommand {
+        stub_compiler(
+            dir,
+            &format!("echo \"universal-sierra-compiler {version}\"\n"),
+        )
+    }
+
+    #[test]
+    fn missing_binary() {
+        let dir = TempDir::new().unwrap();
+        let mut command = UniversalSierraCompilerCommand::new();
+        command.binary(dir.path().join("universal-sierra-compiler"));
+
+        let error = check_version(&command).unwrap_err();
+
+        assert!(format!("{error:#}").starts_with("Cannot find `universal-sierra-compiler` binary"));
+        assert!(format!("{error:#}").contains("UNIVERSAL_SIERRA_COMPILER"));
+    }
+
+    #[test]
+    fn too_old_version() {
+        let dir = TempDir::new().unwrap();
+        let command = stub_with_version(&dir, "1.0.0");
+
+        let error = check_version(&command).unwrap_err();
+
+        assert!(error.to_string().starts_with(
+            "universal-sierra-compiler 1.0.0 is not supported, supported versions: ^2.0.0\nInstall a supported version with"
+        ));
+    }
+
+    #[test]
+    fn supported_version() {
+        let dir = TempDir::new().unwrap();
+        let command = stub_with_version(&dir, "2.3.0");
+
+        check_version(&command).unwrap();
+    }
+
+    #[test]
+    fn detects_batch_support() {
+        let dir = TempDir::new().unwrap();
+        let supported = stub_compiler(&dir, "echo \"  --sierra-paths <SIERRA_PATHS>...\"\n");
+        assert!(probe_batch_support(supported, &SierraType::Contract));
+
+        let dir = TempDir::new().unwrap();
+        let unsupported = stub_compiler(&dir, "echo \"  --sierra-path <SIERRA_PATH>\"\n");
+        assert!(!probe_batch_support(unsupported, &SierraType::Contract));
+    }
+
+    #[test]
+    fn compiles_batch_in_single_process() {
+        let dir = TempDir::new().unwrap();
+        // Every invocation is logged, results are the compiled file paths
+        let command = stub_compiler(
+            &dir,
+            &format!(
+                "echo invoked >> {log}\necho \"[\\\"$3\\\", \\\"$4\\\"]\"\n",
+                log = dir.path().join("log").display()
+            ),
+        );
+
+        let results = compile_batch_with(
+            command,
+            &["first.json", "second.json"],
+            None,
+            &SierraType::Contract,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(results, ["\"first.json\"", "\"second.json\""]);
+        assert_eq!(
+            fs::read_to_string(dir.path().join("log")).unwrap(),
+            "invoked\n"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_process_per_file() {
+        let dir = TempDir::new().unwrap();
+        let command = stub_compiler(
+            &dir,
+            &format!(
+                "echo invoked >> {log}\necho \"{{\\\"path\\\": \\\"$3\\\"}}\"\n",
+                log = dir.path().join("log").display()
+            ),
+        );
+
+        let results = compile_batch_with(
+            command,
+            &["first.json", "second.json"],
+            None,
+            &SierraType::Contract,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            results,
+            [
+                "{\"path\": \"first.json\"}\n",
+                "{\"path\": \"second.json\"}\n"
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("log")).unwrap(),
+            "invoked\ninvoked\n"
+        );
+    }
+
+    #[test]
+    fn batch_with_missing_results() {
+        let dir = TempDir::new().unwrap();
+        let command = stub_compiler(&dir, "echo \"[\\\"$3\\\"]\"\n");
+
+        let error = compile_batch_with(
+            command,
+            &["first.json", "second.json"],
+            None,
+            &SierraType::Contract,
+            true,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Batch compilation of Sierra returned 1 results for 2 files"
+        );
+    }
+}
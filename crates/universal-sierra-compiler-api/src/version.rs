@@ -0,0 +1,38 @@
+use crate::UniversalSierraCompilerCommand;
+use anyhow::{anyhow, ensure, Result};
+use semver::VersionReq;
+use std::sync::OnceLock;
+
+/// Versions of `universal-sierra-compiler` which Sierra can be compiled with
+pub const SUPPORTED_USC_VERSIONS: &str = "^2.0.0";
+
+pub(crate) const INSTALL_HINT: &str = "Install a supported version with \
+    `curl -L https://raw.githubusercontent.com/software-mansion/universal-sierra-compiler/master/scripts/install.sh | sh` \
+    or set `UNIVERSAL_SIERRA_COMPILER` env var to a path of a compatible binary";
+
+pub(crate) fn check_version(command: &UniversalSierraCompilerCommand) -> Result<()> {
+    command.ensure_binary_exists()?;
+
+    let version = command.read_version()?;
+    let supported_versions =
+        VersionReq::parse(SUPPORTED_USC_VERSIONS).expect("Supported versions should be valid");
+
+    ensure!(
+        supported_versions.matches(&version),
+        "universal-sierra-compiler {version} is not supported, supported versions: {SUPPORTED_USC_VERSIONS}\n{INSTALL_HINT}"
+    );
+
+    Ok(())
+}
+
+/// Checks the binary used by default once per process, before it is first used
+pub(crate) fn ensure_supported_version() -> Result<()> {
+    static CHECK_RESULT: OnceLock<Result<(), String>> = OnceLock::new();
+
+    CHECK_RESULT
+        .get_or_init(|| {
+            check_version(&UniversalSierraCompilerCommand::new()).map_err(|err| format!("{err:#}"))
+        })
+        .clone()
+        .map_err(|err| anyhow!(err))
+}
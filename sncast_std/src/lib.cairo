@@ -8,6 +8,13 @@ pub struct ErrorData {
     msg: ByteArray
 }
 
+#[derive(Drop, PartialEq, Serde, Debug)]
+pub struct CompiledClassHashMismatchData {
+    declared: felt252,
+    local: felt252,
+    expected: felt252,
+}
+
 #[derive(Drop, PartialEq, Serde, Debug)]
 pub struct TransactionExecutionErrorData {
     transaction_index: felt252,
@@ -86,6 +93,7 @@ pub enum ScriptCommandError {
     ContractArtifactsNotFound: ErrorData,
     WaitForTransactionError: WaitForTransactionError,
     ProviderError: ProviderError,
+    CompiledClassHashMismatch: CompiledClassHashMismatchData,
 }
 
 pub impl DisplayClassHash of Display<ClassHash> {
@@ -208,6 +216,23 @@ pub struct StrkFeeSettings {
     pub max_gas_unit_price: Option<u128>,
 }
 
+/// What `invoke_with_wait` and `deploy_with_wait` wait for after sending the transaction
+#[derive(Drop, Copy, Debug, Serde, PartialEq)]
+pub enum WaitFor {
+    /// Return as soon as the transaction is sent
+    None,
+    /// Wait until the transaction is accepted on L2 or reverted, as `invoke` and `deploy` do
+    Receipt,
+    /// Wait until the transaction is accepted on L1 or reverted
+    AcceptedOnL1,
+}
+
+#[derive(Drop, Copy, Debug, Serde, PartialEq)]
+pub struct WaitConfig {
+    pub wait_for: WaitFor,
+    /// Timeout in seconds, the one from `snfoundry.toml` is used if not set
+    pub timeout: Option<u16>,
+}
 
 pub fn deploy(
     class_hash: ClassHash,
@@ -216,6 +241,33 @@ pub fn deploy(
     unique: bool,
     fee_settings: FeeSettings,
     nonce: Option<felt252>
+) -> Result<DeployResult, ScriptCommandError> {
+    send_deploy(class_hash, constructor_calldata, salt, unique, fee_settings, nonce, Option::None)
+}
+
+/// Same as `deploy`, waiting for the transaction as set in `wait`
+pub fn deploy_with_wait(
+    class_hash: ClassHash,
+    constructor_calldata: Array::<felt252>,
+    salt: Option<felt252>,
+    unique: bool,
+    fee_settings: FeeSettings,
+    nonce: Option<felt252>,
+    wait: WaitConfig
+) -> Result<DeployResult, ScriptCommandError> {
+    send_deploy(
+        class_hash, constructor_calldata, salt, unique, fee_settings, nonce, Option::Some(wait)
+    )
+}
+
+fn send_deploy(
+    class_hash: ClassHash,
+    constructor_calldata: Array::<felt252>,
+    salt: Option<felt252>,
+    unique: bool,
+    fee_settings: FeeSettings,
+    nonce: Option<felt252>,
+    wait: Option<WaitConfig>
 ) -> Result<DeployResult, ScriptCommandError> {
     let class_hash_felt: felt252 = class_hash.into();
     let mut inputs = array![class_hash_felt];
@@ -237,6 +289,7 @@ pub fn deploy(
     inputs.append(unique.into());
     inputs.append_span(fee_settings_serialized.span());
     inputs.append_span(nonce_serialized.span());
+    wait.serialize(ref inputs);
 
     let mut buf = handle_cheatcode(cheatcode::<'deploy'>(inputs.span()));
 
@@ -266,6 +319,32 @@ pub fn invoke(
     calldata: Array::<felt252>,
     fee_settings: FeeSettings,
     nonce: Option<felt252>
+) -> Result<InvokeResult, ScriptCommandError> {
+    send_invoke(contract_address, entry_point_selector, calldata, fee_settings, nonce, Option::None)
+}
+
+/// Same as `invoke`, waiting for the transaction as set in `wait`.
+/// Transactions which are not awaited may still fail after the script moves on
+pub fn invoke_with_wait(
+    contract_address: ContractAddress,
+    entry_point_selector: felt252,
+    calldata: Array::<felt252>,
+    fee_settings: FeeSettings,
+    nonce: Option<felt252>,
+    wait: WaitConfig
+) -> Result<InvokeResult, ScriptCommandError> {
+    send_invoke(
+        contract_address, entry_point_selector, calldata, fee_settings, nonce, Option::Some(wait)
+    )
+}
+
+fn send_invoke(
+    contract_address: ContractAddress,
+    entry_point_selector: felt252,
+    calldata: Array::<felt252>,
+    fee_settings: FeeSettings,
+    nonce: Option<felt252>,
+    wait: Option<WaitConfig>
 ) -> Result<InvokeResult, ScriptCommandError> {
     let contract_address_felt: felt252 = contract_address.into();
     let mut inputs = array![contract_address_felt, entry_point_selector];
@@ -282,6 +361,7 @@ pub fn invoke(
     inputs.append_span(calldata_serialized.span());
     inputs.append_span(fee_settings_serialized.span());
     inputs.append_span(nonce_serialized.span());
+    wait.serialize(ref inputs);
 
     let mut buf = handle_cheatcode(cheatcode::<'invoke'>(inputs.span()));
 
@@ -294,6 +374,34 @@ pub fn invoke(
     result_data
 }
 
+#[derive(Drop, Clone, Debug, Serde)]
+pub struct InvokeCall {
+    pub contract_address: ContractAddress,
+    pub entry_point_selector: felt252,
+    pub calldata: Array::<felt252>,
+    pub fee_settings: FeeSettings,
+}
+
+/// Sends all invokes with consecutive nonces and waits for them together.
+/// Number of transactions awaited at once is limited with `--parallel` flag of `script run`.
+/// Results are returned in the order of `calls`.
+pub fn invoke_parallel(
+    calls: Array<InvokeCall>
+) -> Array<Result<InvokeResult, ScriptCommandError>> {
+    let mut inputs = array![];
+    calls.serialize(ref inputs);
+
+    let mut buf = handle_cheatcode(cheatcode::<'invoke_parallel'>(inputs.span()));
+
+    let mut result_data: Array<Result<InvokeResult, ScriptCommandError>> =
+        match Serde::<Array<Result<InvokeResult>>>::deserialize(ref buf) {
+        Option::Some(result_data) => result_data,
+        Option::None => panic!("invoke_parallel deserialize failed")
+    };
+
+    result_data
+}
+
 pub fn get_nonce(block_tag: felt252) -> felt252 {
     let inputs = array![block_tag];
     let buf = handle_cheatcode(cheatcode::<'get_nonce'>(inputs.span()));
@@ -25,3 +25,15 @@ fn _is_config_run() -> bool {
 
     Serde::deserialize(ref res).unwrap_or(false)
 }
+
+// Marks the start of a fixture requested by a test, so its panics are not reported as test failures
+fn _enter_fixture(name: ByteArray) {
+    let mut data = array![];
+    name.serialize(ref data);
+
+    handle_cheatcode(starknet::testing::cheatcode::<'enter_fixture'>(data.span()));
+}
+
+fn _exit_fixture() {
+    handle_cheatcode(starknet::testing::cheatcode::<'exit_fixture'>(array![].span()));
+}
@@ -26,13 +26,24 @@ struct OverriddenForkConfig {
 enum ForkConfig {
     Inline: InlineForkConfig,
     Named: ByteArray,
-    Overridden: OverriddenForkConfig
+    Overridden: OverriddenForkConfig,
+    Disabled: ()
 }
 
 #[derive(Drop, Serde)]
 struct FuzzerConfig {
     runs: Option<felt252>,
-    seed: Option<felt252>
+    seed: Option<felt252>,
+    ranges: Array<FuzzerRange>,
+    shrink_runs: Option<felt252>,
+    non_deterministic: bool
+}
+
+#[derive(Drop, Serde)]
+struct FuzzerRange {
+    index: u32,
+    low: u256,
+    high: u256
 }
 
 #[derive(Drop, Serde)]
@@ -51,4 +62,26 @@ struct ShouldPanicConfig {
 #[derive(Drop, Serde)]
 struct IgnoreConfig {
     is_ignored: bool,
+    reason: Option<ByteArray>,
+}
+
+#[derive(Drop, Serde)]
+struct TestCaseConfig {
+    name: ByteArray,
+    arguments: ByteArray,
+}
+
+#[derive(Drop, Serde)]
+struct AvailableStepsConfig {
+    steps: felt252,
+}
+
+#[derive(Drop, Serde)]
+struct TimeoutConfig {
+    seconds: felt252,
+}
+
+#[derive(Drop, Serde)]
+struct SerialConfig {
+    is_serial: bool,
 }
@@ -10,6 +10,9 @@ mod fork;
 mod storage;
 mod execution_info;
 mod message_to_l1;
+mod erc20;
+mod state_snapshot;
+mod block_gas_prices;
 
 /// Enum used to specify how long the target should be cheated for.
 #[derive(Copy, Drop, Serde, PartialEq, Clone, Debug)]
@@ -0,0 +1,22 @@
+use starknet::testing::cheatcode;
+use super::super::_cheatcode::handle_cheatcode;
+
+/// Gas prices of the block, ETH prices are denominated in wei and STRK prices in fri
+#[derive(Drop, Copy, Serde, PartialEq, Debug)]
+struct BlockGasPrices {
+    eth_l1_gas_price: u128,
+    strk_l1_gas_price: u128,
+    eth_l1_data_gas_price: u128,
+    strk_l1_data_gas_price: u128,
+}
+
+/// Changes gas prices of the block the rest of the test is executed in.
+/// Prices are kept separately for every fork.
+/// Fails the test if any of the prices is equal to 0.
+/// `gas_prices` - new gas prices of the block
+fn set_block_gas_prices(gas_prices: BlockGasPrices) {
+    let mut inputs = array![];
+    gas_prices.serialize(ref inputs);
+
+    handle_cheatcode(cheatcode::<'set_block_gas_prices'>(inputs.span()));
+}
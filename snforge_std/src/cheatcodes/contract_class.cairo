@@ -2,7 +2,7 @@ use core::clone::Clone;
 use core::serde::Serde;
 use core::traits::TryInto;
 use starknet::{ContractAddress, ClassHash, testing::cheatcode, SyscallResult};
-use super::super::byte_array::byte_array_as_felt_array;
+use super::super::byte_array::{byte_array_as_felt_array, try_deserialize_bytearray_error};
 use super::super::_cheatcode::handle_cheatcode;
 use core::traits::Into;
 
@@ -102,6 +102,11 @@ trait DeclareResultTrait {
     /// `self` - an instance of the struct `DeclareResult` which is obtained by calling `declare`
     // Returns the `@ContractClass`
     fn contract_class(self: @DeclareResult) -> @ContractClass;
+
+    /// Gets the class hash of the declared contract
+    /// `self` - an instance of the struct `DeclareResult` which is obtained by calling `declare`
+    /// Returns the `ClassHash`, the same for `Success` and `AlreadyDeclared`
+    fn class_hash(self: @DeclareResult) -> ClassHash;
 }
 
 impl DeclareResultImpl of DeclareResultTrait {
@@ -111,6 +116,10 @@ impl DeclareResultImpl of DeclareResultTrait {
             DeclareResult::AlreadyDeclared(contract_class) => contract_class
         }
     }
+
+    fn class_hash(self: @DeclareResult) -> ClassHash {
+        *self.contract_class().class_hash
+    }
 }
 
 /// Declares a contract
@@ -128,6 +137,55 @@ fn declare(contract: ByteArray) -> Result<DeclareResult, Array<felt252>> {
     Serde::deserialize(ref span).unwrap()
 }
 
+/// Declares and deploys a contract, then wraps its address in a dispatcher
+/// `contract` - name of a contract as Cairo string, e.g. "HelloStarknet"
+/// `constructor_calldata` - calldata for the constructor, serialized with `Serde`
+/// Returns the dispatcher `T` (e.g. `IHelloStarknetDispatcher`) of the deployed contract
+/// Panics with a message naming the contract if it could not be declared or its constructor failed
+fn deploy_contract<T, +Serde<T>, +Drop<T>>(
+    contract: ByteArray, constructor_calldata: @Array::<felt252>
+) -> T {
+    let contract_class = _declare_or_panic(contract.clone());
+
+    match contract_class.deploy(constructor_calldata) {
+        Result::Ok((contract_address, _)) => _dispatcher_at(contract_address),
+        Result::Err(panic_data) => panic!(
+            "Failed to deploy contract {}: {}", contract, _describe_panic_data(panic_data.span())
+        ),
+    }
+}
+
+/// Declares and deploys a contract at a given address, then wraps the address in a dispatcher
+/// `contract` - name of a contract as Cairo string, e.g. "HelloStarknet"
+/// `constructor_calldata` - calldata for the constructor, serialized with `Serde`
+/// `contract_address` - address the contract should be deployed at
+/// Returns the dispatcher `T` (e.g. `IHelloStarknetDispatcher`) of the deployed contract
+/// Panics with a message naming the contract if it could not be declared or its constructor failed
+fn deploy_contract_at<T, +Serde<T>, +Drop<T>>(
+    contract: ByteArray, constructor_calldata: @Array::<felt252>, contract_address: ContractAddress
+) -> T {
+    let contract_class = _declare_or_panic(contract.clone());
+
+    match contract_class.deploy_at(constructor_calldata, contract_address) {
+        Result::Ok((contract_address, _)) => _dispatcher_at(contract_address),
+        Result::Err(panic_data) => panic!(
+            "Failed to deploy contract {}: {}", contract, _describe_panic_data(panic_data.span())
+        ),
+    }
+}
+
+/// Retrieves a class hash of a contract declared in the test, without declaring it
+/// `contract` - name of a contract as Cairo string, e.g. "HelloStarknet"
+/// Returns `Option::Some` with the `ClassHash` if the contract was already declared,
+/// `Option::None` otherwise
+fn get_declared_class_hash(contract: ByteArray) -> Option<ClassHash> {
+    let mut span = handle_cheatcode(
+        cheatcode::<'get_declared_class_hash'>(byte_array_as_felt_array(@contract).span())
+    );
+
+    Serde::deserialize(ref span).unwrap()
+}
+
 /// Retrieves a class hash of a contract deployed under the given address
 /// `contract_address` - target contract address
 /// Returns the `ClassHash` under given address
@@ -147,3 +205,32 @@ fn _prepare_calldata(
     constructor_calldata.serialize(ref inputs);
     inputs
 }
+
+fn _declare_or_panic(contract: ByteArray) -> ContractClass {
+    match declare(contract.clone()) {
+        Result::Ok(declare_result) => *declare_result.contract_class(),
+        Result::Err(panic_data) => panic!(
+            "Failed to declare contract {}: {}", contract, _describe_panic_data(panic_data.span())
+        ),
+    }
+}
+
+// Dispatchers are structs with a single `contract_address` field, so they deserialize from it
+fn _dispatcher_at<T, +Serde<T>, +Drop<T>>(contract_address: ContractAddress) -> T {
+    let mut serialized = array![contract_address.into()].span();
+
+    match Serde::deserialize(ref serialized) {
+        Option::Some(dispatcher) => dispatcher,
+        Option::None => {
+            let contract_address: felt252 = contract_address.into();
+            panic!("Failed to create a dispatcher for address {}", contract_address)
+        },
+    }
+}
+
+fn _describe_panic_data(panic_data: Span<felt252>) -> ByteArray {
+    match try_deserialize_bytearray_error(panic_data) {
+        Result::Ok(message) => message,
+        Result::Err(_) => format!("{:?}", panic_data),
+    }
+}
@@ -0,0 +1,37 @@
+use starknet::{ContractAddress, contract_address_const};
+use starknet::testing::cheatcode;
+use super::super::_cheatcode::handle_cheatcode;
+
+/// Fee token deployed on Starknet networks
+#[derive(Drop, Copy, Serde, PartialEq, Debug)]
+enum Token {
+    STRK,
+    ETH,
+}
+
+#[generate_trait]
+impl TokenImpl of TokenTrait {
+    /// Returns the address of the `token` contract on Starknet Mainnet and Sepolia,
+    /// fees are charged in it during tests
+    fn contract_address(self: Token) -> ContractAddress {
+        match self {
+            Token::STRK => contract_address_const::<
+                0x04718f5a0fc34cc1af16a1cdee98ffb20c31f5cd61d6ab07201858f4287c938d
+            >(),
+            Token::ETH => contract_address_const::<
+                0x049d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7
+            >(),
+        }
+    }
+}
+
+/// Sets the balance of `token` for `target` contract by writing the token contract's storage
+/// - `target` - address of the contract which balance is changed
+/// - `new_balance` - balance `target` will have in `token`
+/// - `token` - fee token which balance is changed
+fn set_balance(target: ContractAddress, new_balance: u256, token: Token) {
+    let mut inputs = array![target.into()];
+    new_balance.serialize(ref inputs);
+    token.serialize(ref inputs);
+    handle_cheatcode(cheatcode::<'set_balance'>(inputs.span()));
+}
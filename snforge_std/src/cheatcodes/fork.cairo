@@ -1,3 +1,7 @@
+use starknet::{ContractAddress, SyscallResult, testing::cheatcode};
+use super::super::byte_array::byte_array_as_felt_array;
+use super::super::_cheatcode::handle_cheatcode;
+
 #[derive(Drop, Copy, Serde)]
 enum BlockTag {
     Latest,
@@ -10,3 +14,84 @@ enum BlockId {
     Hash: felt252,
     Number: u64,
 }
+
+/// Identifies a fork created within a test.
+/// `ForkHandle { id: 0 }` refers to the state the test was started with.
+#[derive(Drop, Copy, Serde, PartialEq, Debug)]
+struct ForkHandle {
+    id: usize,
+}
+
+/// Header data of the block a fork is pinned to
+#[derive(Drop, Copy, Serde, PartialEq, Debug)]
+struct ForkBlockInfo {
+    block_number: u64,
+    block_hash: felt252,
+    block_timestamp: u64,
+    sequencer_address: ContractAddress,
+}
+
+/// Creates a new fork of the network available under `url` at the given block.
+/// Created fork has its own state, so contracts deployed and cheats applied while
+/// it is selected are not visible in other forks.
+/// `url` - RPC url of the node to fork from
+/// `block_number` - number of the block the fork is pinned to
+/// Returns the `ForkHandle` which can be later passed to `select_fork`
+fn create_fork(url: ByteArray, block_number: u64) -> ForkHandle {
+    let mut inputs = byte_array_as_felt_array(@url);
+    inputs.append(block_number.into());
+
+    let mut outputs = handle_cheatcode(cheatcode::<'create_fork'>(inputs.span()));
+
+    Serde::deserialize(ref outputs).unwrap()
+}
+
+/// Makes the fork identified by `handle` the active one.
+/// Changes made to the previously active fork are preserved and become visible again after
+/// selecting it back. Cheats, mocks and spied events are kept separately for every fork.
+/// `handle` - handle of the fork obtained from `create_fork` or `active_fork`
+fn select_fork(handle: ForkHandle) {
+    let mut inputs = array![];
+    handle.serialize(ref inputs);
+
+    handle_cheatcode(cheatcode::<'select_fork'>(inputs.span()));
+}
+
+/// Returns the `ForkHandle` of the currently active fork
+fn active_fork() -> ForkHandle {
+    let mut outputs = handle_cheatcode(cheatcode::<'active_fork'>(array![].span()));
+
+    Serde::deserialize(ref outputs).unwrap()
+}
+
+/// Returns the number, hash, timestamp and sequencer address of the block the active fork is
+/// pinned to, as reported by the fork provider. Values are not affected by cheats.
+/// Fails the test if it is not run on a fork.
+fn get_fork_block_info() -> ForkBlockInfo {
+    let mut outputs = handle_cheatcode(cheatcode::<'get_fork_block_info'>(array![].span()));
+
+    Serde::deserialize(ref outputs).unwrap()
+}
+
+/// Calls a contract on the active fork as of the given block, e.g. to read a historical value.
+/// Changes made in the test are not visible to the call and changes made by the call are discarded.
+/// Fails the test if it is not run on a fork or the node can't serve the block.
+/// `contract_address` - address of the contract to call
+/// `function_selector` - selector of the function to call, e.g. `selector!("get_balance")`
+/// `calldata` - arguments of the function, serialized with `Serde`
+/// `block_id` - block to read the state at, `Option::None` for the block the fork is pinned to
+/// Returns the serialized return data of the function, or panic data if it failed
+fn call_contract_at_block(
+    contract_address: ContractAddress,
+    function_selector: felt252,
+    calldata: Span<felt252>,
+    block_id: Option<BlockId>
+) -> SyscallResult<Span<felt252>> {
+    let mut inputs = array![contract_address.into(), function_selector];
+    calldata.serialize(ref inputs);
+    block_id.serialize(ref inputs);
+
+    let mut outputs = handle_cheatcode(cheatcode::<'call_contract_at_block'>(inputs.span()));
+
+    Serde::deserialize(ref outputs).unwrap()
+}
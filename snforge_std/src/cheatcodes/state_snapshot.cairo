@@ -0,0 +1,41 @@
+use starknet::testing::cheatcode;
+use super::super::_cheatcode::handle_cheatcode;
+
+/// Identifies a snapshot of the state taken within a test
+#[derive(Drop, Copy, Serde, PartialEq, Debug)]
+struct StateSnapshot {
+    id: usize,
+}
+
+/// Saves the state of the active fork: storage, deployed and declared contracts and nonces,
+/// together with cheats, mocks and spied events and messages.
+/// Snapshots can be nested, each of them can be reverted to any number of times.
+/// Returns the `StateSnapshot` which can be later passed to `revert_to`
+fn snapshot_state() -> StateSnapshot {
+    let mut outputs = handle_cheatcode(cheatcode::<'snapshot_state'>(array![].span()));
+
+    Serde::deserialize(ref outputs).unwrap()
+}
+
+/// Brings back the state saved in `snapshot`, dropping all changes made after it was taken,
+/// including cheats, mocks and spied events and messages.
+/// Snapshots taken after `snapshot` are released and can't be reverted to anymore.
+/// Fails the test if `snapshot` was released or was taken on a fork other than the active one.
+/// `snapshot` - snapshot obtained from `snapshot_state`
+fn revert_to(snapshot: StateSnapshot) {
+    _revert_to(snapshot, false);
+}
+
+/// Same as `revert_to`, but cheats, mocks and spied events and messages are kept as they are
+/// `snapshot` - snapshot obtained from `snapshot_state`
+fn revert_to_keeping_cheats(snapshot: StateSnapshot) {
+    _revert_to(snapshot, true);
+}
+
+fn _revert_to(snapshot: StateSnapshot, keep_cheats: bool) {
+    let mut inputs = array![];
+    snapshot.serialize(ref inputs);
+    keep_cheats.serialize(ref inputs);
+
+    handle_cheatcode(cheatcode::<'revert_to'>(inputs.span()));
+}
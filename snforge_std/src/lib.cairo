@@ -1,7 +1,10 @@
 mod cheatcodes;
 
 use cheatcodes::contract_class::declare;
+use cheatcodes::contract_class::deploy_contract;
+use cheatcodes::contract_class::deploy_contract_at;
 use cheatcodes::contract_class::get_class_hash;
+use cheatcodes::contract_class::get_declared_class_hash;
 use cheatcodes::contract_class::ContractClass;
 use cheatcodes::contract_class::ContractClassTrait;
 use cheatcodes::contract_class::DeclareResult;
@@ -12,6 +15,21 @@ use cheatcodes::l1_handler::L1HandlerTrait;
 
 use cheatcodes::fork::BlockTag;
 use cheatcodes::fork::BlockId;
+use cheatcodes::fork::ForkHandle;
+use cheatcodes::fork::create_fork;
+use cheatcodes::fork::select_fork;
+use cheatcodes::fork::active_fork;
+use cheatcodes::fork::ForkBlockInfo;
+use cheatcodes::fork::get_fork_block_info;
+use cheatcodes::fork::call_contract_at_block;
+
+use cheatcodes::state_snapshot::StateSnapshot;
+use cheatcodes::state_snapshot::snapshot_state;
+use cheatcodes::state_snapshot::revert_to;
+use cheatcodes::state_snapshot::revert_to_keeping_cheats;
+
+use cheatcodes::block_gas_prices::BlockGasPrices;
+use cheatcodes::block_gas_prices::set_block_gas_prices;
 
 use cheatcodes::events::Event;
 use cheatcodes::events::EventSpy;
@@ -29,6 +47,10 @@ use cheatcodes::storage::store;
 use cheatcodes::storage::load;
 use cheatcodes::storage::map_entry_address;
 
+use cheatcodes::erc20::Token;
+use cheatcodes::erc20::TokenTrait;
+use cheatcodes::erc20::set_balance;
+
 use cheatcodes::CheatSpan;
 use cheatcodes::ReplaceBytecodeError;
 use cheatcodes::test_address;
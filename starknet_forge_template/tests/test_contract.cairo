@@ -45,3 +45,19 @@ fn test_cannot_increase_balance_with_zero_value() {
         }
     };
 }
+
+#[test]
+#[fuzzer]
+fn test_increase_balance_by_any_amount(amount: u64) {
+    let amount: felt252 = amount.into();
+    // Zero is rejected by the contract
+    let amount = amount + 1;
+    let contract_address = deploy_contract("HelloStarknet");
+
+    let dispatcher = IHelloStarknetDispatcher { contract_address };
+
+    dispatcher.increase_balance(amount);
+
+    let balance_after = dispatcher.get_balance();
+    assert(balance_after == amount, 'Invalid balance');
+}